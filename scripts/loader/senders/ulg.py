@@ -40,13 +40,18 @@ class ULGSender(QObject):
         
         parameters = ulg.initial_parameters
         version_info = ulg.msg_info_dict
+        # System defaults, only recorded by newer firmware and read by newer pyulog.
+        try:
+            defaults = ulg.get_default_parameters(0)
+        except (AttributeError, KeyError):
+            defaults = {}
         
-        return tables, parameters, version_info
+        return tables, parameters, version_info, defaults
     
     def run(self):
         try:
             self.log_signal.emit(f"Parsing ULG file: {self.filename}")
-            tables, parameters, version_info = self.parse_ulg_file()
+            tables, parameters, version_info, defaults = self.parse_ulg_file()
             
             self.log_signal.emit(f"\nData Topics: {len(tables)}")
             for name, table in tables.items():
@@ -79,6 +84,8 @@ class ULGSender(QObject):
                     'parameters': {k: float(v) if isinstance(v, (int, float)) else str(v) 
                                   for k, v in parameters.items()},
                     'version_info': {k: str(v) for k, v in version_info.items()},
+                    'parameter_defaults': {k: float(v) if isinstance(v, (int, float)) else str(v)
+                                           for k, v in defaults.items()},
                     'table_count': len(tables),
                     'table_names': list(tables.keys()),
                     'timeline_range': {
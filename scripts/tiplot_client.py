@@ -0,0 +1,100 @@
+"""Minimal client for pushing pandas DataFrames into TiPlot.
+
+    from tiplot_client import TiplotClient
+
+    with TiplotClient() as client:
+        client.send({"attitude": df})  # df needs an int64 'timestamp' column (µs)
+
+Mirrors the Rust `tiplot-client` crate; see docs/protocol.md for the wire format.
+"""
+
+import json
+import socket
+import struct
+
+import pyarrow as pa
+import pyarrow.compute as pc
+import pyarrow.ipc as ipc
+
+MAGIC = b"TIPL"
+PROTOCOL_VERSION = 1
+DEFAULT_PORT = 9999
+STATUS_OK = 0
+
+
+class TiplotClient:
+    def __init__(self, host="127.0.0.1", port=DEFAULT_PORT):
+        self.sock = socket.create_connection((host, port))
+        self.sock.sendall(MAGIC + struct.pack('<H', PROTOCOL_VERSION))
+
+        reply = self._recv_exact(7)
+        if reply[:4] != MAGIC:
+            raise ConnectionError("Peer is not a TiPlot server")
+        version, status = struct.unpack('<HB', reply[4:])
+        if status != STATUS_OK:
+            raise ConnectionError(
+                f"TiPlot speaks protocol v{version}, this client speaks v{PROTOCOL_VERSION}")
+        self.version = version
+
+    def _recv_exact(self, n):
+        buf = b""
+        while len(buf) < n:
+            chunk = self.sock.recv(n - len(buf))
+            if not chunk:
+                raise ConnectionError("TiPlot closed the connection during the handshake")
+            buf += chunk
+        return buf
+
+    def send(self, frames, parameters=None, version_info=None, parameter_defaults=None):
+        """Sends one packet. `frames` maps topic names to DataFrames or pyarrow Tables."""
+        tables = {
+            name: frame if isinstance(frame, pa.Table) else pa.Table.from_pandas(frame, preserve_index=False)
+            for name, frame in frames.items()
+        }
+
+        timestamps = [
+            t.column('timestamp') for t in tables.values()
+            if 'timestamp' in t.column_names and len(t) > 0
+        ]
+        metadata = {
+            'parameters': parameters or {},
+            'version_info': version_info or {},
+            'parameter_defaults': parameter_defaults or {},
+            'table_count': len(tables),
+            'table_names': list(tables),
+            'timeline_range': {
+                'min_timestamp': min((int(pc.min(c).as_py()) for c in timestamps), default=None),
+                'max_timestamp': max((int(pc.max(c).as_py()) for c in timestamps), default=None),
+            },
+        }
+
+        meta_json = json.dumps(metadata).encode('utf-8')
+        packet = [struct.pack('<I', len(meta_json)), meta_json]
+        for name, table in tables.items():
+            sink = pa.BufferOutputStream()
+            with ipc.new_stream(sink, table.schema) as writer:
+                writer.write_table(table)
+            data = sink.getvalue().to_pybytes()
+            name_bytes = name.encode('utf-8')
+            packet += [struct.pack('<I', len(name_bytes)), name_bytes, struct.pack('<Q', len(data)), data]
+        self.sock.sendall(b"".join(packet))
+
+    def close(self):
+        self.sock.close()
+
+    def __enter__(self):
+        return self
+
+    def __exit__(self, *exc):
+        self.close()
+
+
+if __name__ == '__main__':
+    import numpy as np
+    import pandas as pd
+
+    t = np.arange(0, 10_000_000, 10_000, dtype=np.int64)
+    df = pd.DataFrame({'timestamp': t, 'value': np.sin(t * 1e-6)})
+    with TiplotClient() as client:
+        client.send({'sine': df})
+        print(f"✓ Sent {len(df)} rows (protocol v{client.version})")
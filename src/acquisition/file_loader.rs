@@ -0,0 +1,238 @@
+use crate::acquisition::{DataMessage, LoadedFile};
+use crate::core::{session_topic, DataStore, SharedIngestFilter};
+use arrow::array::Float64Array;
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::record_batch::RecordBatch;
+use crossbeam_channel::Sender;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Reads a data file into a store of its own on a blocking worker, reporting progress
+/// as it goes. The UI only swaps the store in once the whole file was read, so a corrupt
+/// file leaves the loaded data untouched and live data arriving meanwhile stays out of it.
+/// Topics are spilled to disk as they are read once `memory_budget` bytes are in use.
+pub fn start_file_load(
+    path: PathBuf,
+    ingest_filter: SharedIngestFilter,
+    memory_budget: Option<usize>,
+    sender: Sender<DataMessage>,
+    ctx: egui::Context,
+) {
+    tokio::task::spawn_blocking(move || {
+        sender.send(DataMessage::LoadStarted(path.clone())).ok();
+        ctx.request_repaint();
+
+        let mut data_store = DataStore::new();
+        data_store.set_ingest_filter(&ingest_filter.read().unwrap());
+        data_store.set_memory_budget(memory_budget);
+        let result = DataStore::read_arrow_file(
+            &path,
+            |topic, batch| {
+                data_store.ingest(topic.to_string(), batch);
+                data_store.fit_memory_budget(topic);
+            },
+            |bytes_read, total_bytes| {
+                sender
+                    .send(DataMessage::LoadProgress {
+                        bytes_read,
+                        total_bytes,
+                    })
+                    .ok();
+                ctx.request_repaint();
+            },
+        );
+
+        let result = result.map(|session| {
+            Box::new(LoadedFile {
+                data_store,
+                session,
+            })
+        });
+        sender
+            .send(DataMessage::LoadFinished(
+                path,
+                result.map_err(|e| e.to_string()),
+            ))
+            .ok();
+        ctx.request_repaint();
+    });
+}
+
+/// Reads a data file next to the loaded data, prefixing its topics with `session` so
+/// several flights can be replayed together.
+pub fn start_session_load(
+    path: PathBuf,
+    session: String,
+    sender: Sender<DataMessage>,
+    ctx: egui::Context,
+) {
+    tokio::task::spawn_blocking(move || {
+        let result = DataStore::read_arrow_file(
+            &path,
+            |topic, batch| {
+                sender
+                    .send(DataMessage::NewBatch(session_topic(&session, topic), batch))
+                    .ok();
+                ctx.request_repaint();
+            },
+            |bytes_read, total_bytes| {
+                sender
+                    .send(DataMessage::LoadProgress {
+                        bytes_read,
+                        total_bytes,
+                    })
+                    .ok();
+                ctx.request_repaint();
+            },
+        );
+
+        sender
+            .send(DataMessage::SessionLoaded(
+                path,
+                session,
+                result.map(|_| ()).map_err(|e| e.to_string()),
+            ))
+            .ok();
+        ctx.request_repaint();
+    });
+}
+
+/// Where merged data is placed in time relative to the data already loaded.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MergeAlignment {
+    /// Timestamps are taken as they are in the file.
+    #[default]
+    Keep,
+    /// The file starts where the loaded data starts.
+    AlignStarts,
+    /// The file starts where the loaded data ends.
+    AfterEnd,
+}
+
+impl MergeAlignment {
+    pub const ALL: [MergeAlignment; 3] = [
+        MergeAlignment::Keep,
+        MergeAlignment::AlignStarts,
+        MergeAlignment::AfterEnd,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            MergeAlignment::Keep => "Keep Timestamps",
+            MergeAlignment::AlignStarts => "Align Starts",
+            MergeAlignment::AfterEnd => "Append After End",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MergeOptions {
+    /// Prepended to every topic name. Without one, topics that are already loaded get
+    /// the file's samples appended.
+    pub prefix: String,
+    pub alignment: MergeAlignment,
+    /// Shift in seconds applied on top of the alignment.
+    pub offset: f64,
+}
+
+/// Reads a data file into the loaded data instead of replacing it. `bounds` is the
+/// time range of the loaded data, which the alignment is resolved against.
+pub fn start_merge_load(
+    path: PathBuf,
+    options: MergeOptions,
+    bounds: Option<(f32, f32)>,
+    sender: Sender<DataMessage>,
+    ctx: egui::Context,
+) {
+    tokio::task::spawn_blocking(move || {
+        // The whole file is read first, as aligning it needs its earliest timestamp.
+        let mut batches = Vec::new();
+        let result = DataStore::read_arrow_file(
+            &path,
+            |topic, batch| batches.push((format!("{}{}", options.prefix, topic), batch)),
+            |bytes_read, total_bytes| {
+                sender
+                    .send(DataMessage::LoadProgress {
+                        bytes_read,
+                        total_bytes,
+                    })
+                    .ok();
+                ctx.request_repaint();
+            },
+        );
+
+        let result = result.and_then(|session| {
+            let first = batches
+                .iter()
+                .filter_map(|(_, batch)| first_timestamp(batch))
+                .reduce(f64::min);
+            let anchor = match (options.alignment, bounds, first) {
+                (MergeAlignment::AlignStarts, Some((start, _)), Some(first)) => {
+                    start as f64 - first
+                }
+                (MergeAlignment::AfterEnd, Some((_, end)), Some(first)) => end as f64 - first,
+                _ => 0.0,
+            };
+            let shift = anchor + options.offset;
+
+            for (topic, batch) in batches {
+                let batch = shift_timestamps(batch, shift)?;
+                sender.send(DataMessage::NewBatch(topic, batch)).ok();
+            }
+            Ok(session.map(|mut session| {
+                session.columns = std::mem::take(&mut session.columns)
+                    .into_iter()
+                    .map(|(topic, columns)| (format!("{}{}", options.prefix, topic), columns))
+                    .collect();
+                session.transforms = std::mem::take(&mut session.transforms)
+                    .into_iter()
+                    .map(|(topic, list)| (format!("{}{}", options.prefix, topic), list))
+                    .collect();
+                session
+            }))
+        });
+
+        sender
+            .send(DataMessage::Merged(path, result.map_err(|e| e.to_string())))
+            .ok();
+        ctx.request_repaint();
+    });
+}
+
+/// Earliest relative timestamp of a loaded batch, in seconds.
+fn first_timestamp(batch: &RecordBatch) -> Option<f64> {
+    let column = batch.column_by_name("timestamp")?;
+    let seconds = arrow::compute::cast(column, &DataType::Float64).ok()?;
+    let seconds = seconds.as_any().downcast_ref::<Float64Array>()?;
+    arrow::compute::min(seconds)
+}
+
+/// Moves the relative timestamps of a loaded batch by `shift` seconds.
+fn shift_timestamps(batch: RecordBatch, shift: f64) -> anyhow::Result<RecordBatch> {
+    let Ok(index) = batch.schema().index_of("timestamp") else {
+        return Ok(batch);
+    };
+    if shift == 0.0 {
+        return Ok(batch);
+    }
+    let seconds = arrow::compute::cast(batch.column(index), &DataType::Float64)?;
+    let seconds = seconds
+        .as_any()
+        .downcast_ref::<Float64Array>()
+        .ok_or_else(|| anyhow::anyhow!("Unsupported timestamp column"))?;
+    let shifted = Float64Array::from_iter_values(seconds.values().iter().map(|&t| t + shift));
+
+    let mut fields: Vec<Field> = batch
+        .schema()
+        .fields()
+        .iter()
+        .map(|f| f.as_ref().clone())
+        .collect();
+    fields[index] = Field::new("timestamp", DataType::Float64, false);
+    let mut columns = batch.columns().to_vec();
+    columns[index] = Arc::new(shifted);
+    Ok(RecordBatch::try_new(
+        Arc::new(Schema::new(fields)),
+        columns,
+    )?)
+}
@@ -0,0 +1,89 @@
+use super::tcp_receiver::DataMessage;
+use crate::core::arrow_files::EXTENSIONS;
+use crossbeam_channel::Sender;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Polls a folder for log files that appear in it, e.g. synced from a vehicle's SD card,
+/// and reports each once its size stops changing. Watching stops when this is dropped.
+pub struct FolderWatcher {
+    dir: PathBuf,
+    stop: Arc<AtomicBool>,
+}
+
+impl FolderWatcher {
+    pub fn start(dir: PathBuf, sender: Sender<DataMessage>, ctx: egui::Context) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = stop.clone();
+        let thread_dir = dir.clone();
+        std::thread::spawn(move || watch(&thread_dir, &sender, &ctx, &thread_stop));
+        tracing::info!("Watching {} for new logs", dir.display());
+        Self { dir, stop }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+}
+
+impl Drop for FolderWatcher {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+fn watch(dir: &Path, sender: &Sender<DataMessage>, ctx: &egui::Context, stop: &AtomicBool) {
+    // Files already there when watching starts are not new.
+    let mut seen: HashSet<PathBuf> = log_files(dir).into_keys().collect();
+    // Size of each new file at the last poll; a file still being copied keeps growing.
+    let mut pending: HashMap<PathBuf, u64> = HashMap::new();
+
+    while !stop.load(Ordering::Relaxed) {
+        std::thread::sleep(POLL_INTERVAL);
+        let files = log_files(dir);
+        pending.retain(|path, _| files.contains_key(path));
+
+        for (path, size) in files {
+            if seen.contains(&path) {
+                continue;
+            }
+            if size == 0 || pending.insert(path.clone(), size) != Some(size) {
+                continue;
+            }
+
+            pending.remove(&path);
+            seen.insert(path.clone());
+            tracing::info!("New log in watched folder: {}", path.display());
+            if sender.send(DataMessage::LogAppeared(path)).is_err() {
+                return;
+            }
+            ctx.request_repaint();
+        }
+    }
+}
+
+/// Arrow files directly in `dir` with their sizes.
+fn log_files(dir: &Path) -> HashMap<PathBuf, u64> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return HashMap::new();
+    };
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            entry
+                .path()
+                .extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| EXTENSIONS.contains(&e.to_lowercase().as_str()))
+        })
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok().filter(|m| m.is_file())?;
+            Some((entry.path(), metadata.len()))
+        })
+        .collect()
+}
@@ -0,0 +1,403 @@
+use super::tcp_receiver::TimelineRange;
+use crate::core::DataStore;
+use arrow::record_batch::RecordBatch;
+use std::collections::VecDeque;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use tiplot_client::protocol::{self, Compression, HandshakeReply, PacketMetadata};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::{broadcast, mpsc, watch};
+
+/// Packets a slow follower may fall behind before it is resent the history.
+const LIVE_CAPACITY: usize = 65536;
+
+/// Bytes of encoded data packets kept for followers that join late or fall behind. The
+/// oldest packets are dropped first.
+const HISTORY_CAPACITY: usize = 512 * 1024 * 1024;
+
+type Packet = Arc<Vec<u8>>;
+type MirrorError = Box<dyn std::error::Error + Send + Sync>;
+
+enum MirrorEvent {
+    Metadata(TimelineRange),
+    Batch(String, RecordBatch),
+    Cursor(f64),
+    Reset,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum MirrorStatus {
+    Starting,
+    Serving(SocketAddr),
+    Error(String),
+}
+
+/// A packet as sent to followers. Data packets are numbered so a follower that fell
+/// behind can resume from the history; cursor packets are not.
+#[derive(Clone)]
+struct LivePacket {
+    seq: Option<u64>,
+    packet: Packet,
+}
+
+/// Data packets since publishing started, up to [`HISTORY_CAPACITY`] bytes.
+#[derive(Default)]
+struct History {
+    packets: VecDeque<(u64, Packet)>,
+    bytes: usize,
+    /// Number of the last data packet, counting from 1.
+    last_seq: u64,
+    /// Number of the last packet dropped to stay within capacity.
+    dropped: u64,
+}
+
+impl History {
+    fn push(&mut self, packet: Packet) -> u64 {
+        self.last_seq += 1;
+        let seq = self.last_seq;
+        self.bytes += packet.len();
+        self.packets.push_back((seq, packet));
+        while self.bytes > HISTORY_CAPACITY && self.packets.len() > 1 {
+            let (seq, packet) = self.packets.pop_front().unwrap();
+            self.bytes -= packet.len();
+            if self.dropped == 0 {
+                tracing::warn!("Mirror history is full, late followers miss the oldest data");
+            }
+            self.dropped = seq;
+        }
+        seq
+    }
+
+    fn clear(&mut self) {
+        self.packets.clear();
+        self.bytes = 0;
+        self.dropped = 0;
+    }
+}
+
+/// What a follower needs to catch up, and the live feed continuing right after it.
+struct Replay {
+    packets: Vec<Packet>,
+    last_seq: u64,
+    /// Whether packets the follower had not received were dropped from the history.
+    missed: bool,
+    live: broadcast::Receiver<LivePacket>,
+}
+
+struct MirrorShared {
+    status: Mutex<MirrorStatus>,
+    history: Mutex<History>,
+    last_cursor: Mutex<Option<Packet>>,
+    live: broadcast::Sender<LivePacket>,
+    followers: AtomicUsize,
+}
+
+impl MirrorShared {
+    /// Retained data packets numbered after `after`, then the last cursor. Subscribes
+    /// while holding the history lock, so every packet reaches the follower exactly once:
+    /// either in the replay or live.
+    fn replay(&self, after: u64) -> Replay {
+        let history = self.history.lock().unwrap();
+        let cursor = self.last_cursor.lock().unwrap().clone();
+        let packets = history
+            .packets
+            .iter()
+            .filter(|(seq, _)| *seq > after)
+            .map(|(_, packet)| packet.clone())
+            .chain(cursor)
+            .collect();
+        Replay {
+            packets,
+            last_seq: history.last_seq,
+            missed: history.dropped > after,
+            live: self.live.subscribe(),
+        }
+    }
+}
+
+/// Republishes everything this instance ingests, plus its playback cursor, to other
+/// TiPlot instances following it. Publishing stops when this is dropped.
+pub struct MirrorPublisher {
+    events: mpsc::UnboundedSender<MirrorEvent>,
+    shared: Arc<MirrorShared>,
+    last_cursor: Option<f64>,
+    _stop: watch::Sender<()>,
+}
+
+impl MirrorPublisher {
+    pub fn start(listen_addr: String, ctx: egui::Context) -> Self {
+        let (events, events_rx) = mpsc::unbounded_channel();
+        let (stop, stop_rx) = watch::channel(());
+        let shared = Arc::new(MirrorShared {
+            status: Mutex::new(MirrorStatus::Starting),
+            history: Mutex::new(History::default()),
+            last_cursor: Mutex::new(None),
+            live: broadcast::channel(LIVE_CAPACITY).0,
+            followers: AtomicUsize::new(0),
+        });
+
+        tokio::spawn(encode_events(events_rx, shared.clone()));
+        tokio::spawn(serve(listen_addr, shared.clone(), stop_rx, ctx));
+
+        Self {
+            events,
+            shared,
+            last_cursor: None,
+            _stop: stop,
+        }
+    }
+
+    pub fn status(&self) -> MirrorStatus {
+        self.shared.status.lock().unwrap().clone()
+    }
+
+    pub fn followers(&self) -> usize {
+        self.shared.followers.load(Ordering::Relaxed)
+    }
+
+    pub fn publish_metadata(&self, range: TimelineRange) {
+        self.events.send(MirrorEvent::Metadata(range)).ok();
+    }
+
+    pub fn publish_batch(&self, topic: &str, batch: &RecordBatch) {
+        self.events
+            .send(MirrorEvent::Batch(topic.to_string(), batch.clone()))
+            .ok();
+    }
+
+    /// Publishes every topic of `data_store`, e.g. after a file replaced the loaded data.
+    pub fn publish_store(&self, data_store: &DataStore) {
+        for topic in data_store.saved_topics() {
+            match data_store.topic_batch(topic) {
+                Ok(batch) => self.publish_batch(topic, &batch),
+                Err(e) => tracing::error!("Failed to mirror topic '{}': {}", topic, e),
+            }
+        }
+    }
+
+    /// Shares the playback cursor, in absolute seconds, if it moved.
+    pub fn publish_cursor(&mut self, time: f64) {
+        if self.last_cursor != Some(time) {
+            self.last_cursor = Some(time);
+            self.events.send(MirrorEvent::Cursor(time)).ok();
+        }
+    }
+
+    /// Forgets the history when a new session starts, so late followers only get it.
+    pub fn reset(&self) {
+        self.events.send(MirrorEvent::Reset).ok();
+    }
+}
+
+fn encode_packet(
+    metadata: PacketMetadata,
+    table: Option<(&str, &RecordBatch)>,
+) -> anyhow::Result<Vec<u8>> {
+    let mut packet = protocol::encode_metadata(&metadata)?;
+    if let Some((name, batch)) = table {
+        packet.extend(protocol::encode_table(
+            name,
+            batch,
+            protocol::PROTOCOL_VERSION,
+            Compression::Lz4,
+        )?);
+    }
+    Ok(packet)
+}
+
+/// Serializes published events off the UI thread and fans them out to followers.
+async fn encode_events(
+    mut events: mpsc::UnboundedReceiver<MirrorEvent>,
+    shared: Arc<MirrorShared>,
+) {
+    while let Some(event) = events.recv().await {
+        let encoded = match &event {
+            MirrorEvent::Metadata(range) => {
+                encode_packet(PacketMetadata::new(Vec::new(), *range), None)
+            }
+            MirrorEvent::Batch(topic, batch) => encode_packet(
+                PacketMetadata::new(vec![topic.clone()], TimelineRange::default()),
+                Some((topic, batch)),
+            ),
+            MirrorEvent::Cursor(time) => encode_packet(
+                PacketMetadata {
+                    cursor: Some(*time),
+                    ..Default::default()
+                },
+                None,
+            ),
+            MirrorEvent::Reset => {
+                shared.history.lock().unwrap().clear();
+                *shared.last_cursor.lock().unwrap() = None;
+                continue;
+            }
+        };
+
+        let packet = match encoded {
+            Ok(packet) => Arc::new(packet),
+            Err(e) => {
+                tracing::error!("Failed to encode mirror packet: {}", e);
+                continue;
+            }
+        };
+
+        // Sent under the history lock; see `MirrorShared::replay`.
+        let mut history = shared.history.lock().unwrap();
+        let seq = if matches!(event, MirrorEvent::Cursor(_)) {
+            *shared.last_cursor.lock().unwrap() = Some(packet.clone());
+            None
+        } else {
+            Some(history.push(packet.clone()))
+        };
+        shared.live.send(LivePacket { seq, packet }).ok();
+    }
+}
+
+async fn serve(
+    listen_addr: String,
+    shared: Arc<MirrorShared>,
+    mut stop: watch::Receiver<()>,
+    ctx: egui::Context,
+) {
+    let bound = TcpListener::bind(&listen_addr)
+        .await
+        .and_then(|listener| Ok((listener.local_addr()?, listener)));
+    let (local_addr, listener) = match bound {
+        Ok(bound) => bound,
+        Err(e) => {
+            tracing::error!("Failed to bind mirror port {}: {}", listen_addr, e);
+            *shared.status.lock().unwrap() = MirrorStatus::Error(e.to_string());
+            ctx.request_repaint();
+            return;
+        }
+    };
+
+    tracing::info!("Mirroring session on {}", local_addr);
+    *shared.status.lock().unwrap() = MirrorStatus::Serving(local_addr);
+    ctx.request_repaint();
+
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok((socket, addr)) => {
+                    let shared = shared.clone();
+                    let stop = stop.clone();
+                    let ctx = ctx.clone();
+                    tokio::spawn(async move {
+                        tracing::info!("Follower connected: {}", addr);
+                        shared.followers.fetch_add(1, Ordering::Relaxed);
+                        ctx.request_repaint();
+
+                        if let Err(e) = serve_follower(socket, &shared, stop).await {
+                            tracing::error!("Follower {} disconnected: {}", addr, e);
+                        }
+
+                        shared.followers.fetch_sub(1, Ordering::Relaxed);
+                        ctx.request_repaint();
+                    });
+                }
+                Err(e) => tracing::warn!("Failed to accept follower: {}", e),
+            },
+            _ = stop.changed() => break,
+        }
+    }
+
+    tracing::info!("Mirror stopped");
+}
+
+async fn serve_follower(
+    mut socket: TcpStream,
+    shared: &MirrorShared,
+    mut stop: watch::Receiver<()>,
+) -> Result<(), MirrorError> {
+    let mut hello = [0u8; protocol::HELLO_LEN];
+    socket.read_exact(&mut hello).await?;
+    if hello[..4] != protocol::MAGIC {
+        return Err("peer did not start with the TiPlot handshake".into());
+    }
+
+    // Packets are encoded once for every follower, so all of them must speak the
+    // current version.
+    let reply = match HandshakeReply::negotiate(u16::from_le_bytes([hello[4], hello[5]])) {
+        HandshakeReply::Accepted(version) if version < protocol::PROTOCOL_VERSION => {
+            HandshakeReply::Rejected(protocol::PROTOCOL_VERSION)
+        }
+        reply => reply,
+    };
+    socket.write_all(&reply.encode()).await?;
+    if let HandshakeReply::Rejected(_) = reply {
+        return Err("unsupported protocol version".into());
+    }
+
+    let Replay {
+        packets,
+        mut last_seq,
+        mut live,
+        ..
+    } = shared.replay(0);
+    for packet in &packets {
+        socket.write_all(packet).await?;
+    }
+
+    let mut heartbeat = tokio::time::interval(protocol::HEARTBEAT_INTERVAL);
+    loop {
+        tokio::select! {
+            received = live.recv() => match received {
+                Ok(LivePacket { seq, packet }) => {
+                    socket.write_all(&packet).await?;
+                    last_seq = seq.unwrap_or(last_seq);
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!(
+                        "Follower fell {} packets behind, resending from the history",
+                        skipped
+                    );
+                    let replay = shared.replay(last_seq);
+                    if replay.missed {
+                        tracing::warn!("Follower missed data no longer in the mirror history");
+                    }
+                    for packet in &replay.packets {
+                        socket.write_all(packet).await?;
+                    }
+                    last_seq = replay.last_seq;
+                    live = replay.live;
+                }
+                Err(RecvError::Closed) => return Ok(()),
+            },
+            _ = heartbeat.tick() => socket.write_all(&protocol::HEARTBEAT).await?,
+            _ = stop.changed() => return Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replay_resumes_after_the_last_packet_sent() {
+        let shared = MirrorShared {
+            status: Mutex::new(MirrorStatus::Starting),
+            history: Mutex::new(History::default()),
+            last_cursor: Mutex::new(Some(Arc::new(vec![9]))),
+            live: broadcast::channel(1).0,
+            followers: AtomicUsize::new(0),
+        };
+        for byte in 1..=3 {
+            shared.history.lock().unwrap().push(Arc::new(vec![byte]));
+        }
+
+        let replay = shared.replay(1);
+        let packets: Vec<u8> = replay.packets.iter().map(|p| p[0]).collect();
+        assert_eq!(packets, vec![2, 3, 9]);
+        assert_eq!(replay.last_seq, 3);
+        assert!(!replay.missed);
+
+        shared.history.lock().unwrap().dropped = 2;
+        assert!(shared.replay(1).missed);
+        assert!(!shared.replay(2).missed);
+    }
+}
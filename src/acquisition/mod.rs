@@ -1,3 +1,17 @@
+pub mod file_loader;
+pub mod folder_watcher;
+pub mod mirror;
+pub mod synthetic;
 pub mod tcp_receiver;
+#[cfg(test)]
+pub mod test_support;
 
-pub use tcp_receiver::{start_tcp_server, DataMessage};
+pub use file_loader::{
+    start_file_load, start_merge_load, start_session_load, MergeAlignment, MergeOptions,
+};
+pub use folder_watcher::FolderWatcher;
+pub use mirror::{MirrorPublisher, MirrorStatus};
+pub use synthetic::{SyntheticConfig, SyntheticGenerator};
+pub use tcp_receiver::{
+    start_follow, start_tcp_server, DataMessage, LoadedFile, StreamControl, StreamStatus,
+};
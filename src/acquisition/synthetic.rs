@@ -0,0 +1,192 @@
+use super::tcp_receiver::{DataMessage, TimelineRange};
+use arrow::array::{ArrayRef, Float32Array, Int64Array};
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::record_batch::RecordBatch;
+use crossbeam_channel::Sender;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Generated timestamps start here rather than at zero, which the timeline treats as
+/// "no start time yet".
+const START_US: i64 = 1_000_000;
+/// Rows per batch when generating a whole recording at once.
+const CHUNK_ROWS: usize = 10_000;
+/// Interval between batches of a live stream.
+const LIVE_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Shape of the generated data: `topics` topics sampled at `rate_hz`, each with a sine,
+/// noise and step column.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SyntheticConfig {
+    pub topics: usize,
+    pub rate_hz: f32,
+    /// Length of the recording in seconds; ignored when streaming live.
+    pub duration: f32,
+    /// Stream samples as they come due instead of generating the recording at once.
+    pub live: bool,
+}
+
+impl Default for SyntheticConfig {
+    fn default() -> Self {
+        Self {
+            topics: 10,
+            rate_hz: 100.0,
+            duration: 60.0,
+            live: false,
+        }
+    }
+}
+
+impl SyntheticConfig {
+    pub fn topic_name(index: usize) -> String {
+        format!("synthetic_{:03}", index)
+    }
+
+    fn timestamp_us(&self, row: usize) -> i64 {
+        START_US + (row as f64 * 1_000_000.0 / self.rate_hz as f64) as i64
+    }
+
+    /// Rows `start..end` of one topic.
+    fn batch(&self, topic: usize, start: usize, end: usize) -> RecordBatch {
+        let frequency = 0.2 * (topic + 1) as f32;
+        let step_period = 2.0 + (topic % 5) as f32;
+        let mut seed = (topic as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15) ^ start as u64;
+
+        let timestamps: Vec<i64> = (start..end).map(|row| self.timestamp_us(row)).collect();
+        let times = (start..end).map(|row| row as f32 / self.rate_hz);
+        let sine: Vec<f32> = times
+            .clone()
+            .map(|t| (std::f32::consts::TAU * frequency * t).sin())
+            .collect();
+        let noise: Vec<f32> = (start..end).map(|_| uniform(&mut seed)).collect();
+        let step: Vec<f32> = times
+            .map(|t| ((t / step_period) as u64 % 2) as f32)
+            .collect();
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("timestamp", DataType::Int64, false),
+            Field::new("sine", DataType::Float32, false),
+            Field::new("noise", DataType::Float32, false),
+            Field::new("step", DataType::Float32, false),
+        ]));
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(Int64Array::from(timestamps)),
+            Arc::new(Float32Array::from(sine)),
+            Arc::new(Float32Array::from(noise)),
+            Arc::new(Float32Array::from(step)),
+        ];
+        RecordBatch::try_new(schema, columns).expect("synthetic columns match their schema")
+    }
+
+    fn range(&self, rows: usize) -> TimelineRange {
+        TimelineRange {
+            min_timestamp: Some(START_US),
+            max_timestamp: Some(self.timestamp_us(rows.saturating_sub(1))),
+        }
+    }
+}
+
+/// Xorshift noise in `-1..1`, deterministic per topic.
+fn uniform(state: &mut u64) -> f32 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    (*state >> 40) as f32 / (1u64 << 24) as f32 * 2.0 - 1.0
+}
+
+/// Feeds generated data through the same channel as loaded and streamed data, to
+/// exercise the UI without a log. Generation stops when this is dropped.
+pub struct SyntheticGenerator {
+    stop: Arc<AtomicBool>,
+    finished: Arc<AtomicBool>,
+}
+
+impl SyntheticGenerator {
+    pub fn start(config: SyntheticConfig, sender: Sender<DataMessage>, ctx: egui::Context) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let finished = Arc::new(AtomicBool::new(false));
+        let (thread_stop, thread_finished) = (stop.clone(), finished.clone());
+        std::thread::spawn(move || {
+            if config.live {
+                stream(&config, &sender, &ctx, &thread_stop);
+            } else {
+                generate(&config, &sender, &ctx, &thread_stop);
+            }
+            thread_finished.store(true, Ordering::Relaxed);
+            ctx.request_repaint();
+        });
+        Self { stop, finished }
+    }
+
+    pub fn is_running(&self) -> bool {
+        !self.finished.load(Ordering::Relaxed)
+    }
+}
+
+impl Drop for SyntheticGenerator {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+fn generate(
+    config: &SyntheticConfig,
+    sender: &Sender<DataMessage>,
+    ctx: &egui::Context,
+    stop: &AtomicBool,
+) {
+    let rows = (config.duration * config.rate_hz).max(1.0) as usize;
+    sender.send(DataMessage::Metadata(config.range(rows))).ok();
+    for start in (0..rows).step_by(CHUNK_ROWS) {
+        let end = (start + CHUNK_ROWS).min(rows);
+        for topic in 0..config.topics {
+            if stop.load(Ordering::Relaxed) {
+                return;
+            }
+            let batch = config.batch(topic, start, end);
+            if sender
+                .send(DataMessage::NewBatch(
+                    SyntheticConfig::topic_name(topic),
+                    batch,
+                ))
+                .is_err()
+            {
+                return;
+            }
+        }
+        ctx.request_repaint();
+    }
+}
+
+fn stream(
+    config: &SyntheticConfig,
+    sender: &Sender<DataMessage>,
+    ctx: &egui::Context,
+    stop: &AtomicBool,
+) {
+    let started = Instant::now();
+    let mut sent = 0;
+    while !stop.load(Ordering::Relaxed) {
+        std::thread::sleep(LIVE_INTERVAL);
+        let due = (started.elapsed().as_secs_f32() * config.rate_hz) as usize;
+        if due <= sent {
+            continue;
+        }
+        for topic in 0..config.topics {
+            let batch = config.batch(topic, sent, due);
+            if sender
+                .send(DataMessage::NewBatch(
+                    SyntheticConfig::topic_name(topic),
+                    batch,
+                ))
+                .is_err()
+            {
+                return;
+            }
+        }
+        sent = due;
+        sender.send(DataMessage::Metadata(config.range(sent))).ok();
+        ctx.request_repaint();
+    }
+}
@@ -1,97 +1,501 @@
+use crate::core::{DataStore, LogInfo, SessionMetadata, SharedIngestFilter};
 use arrow::record_batch::RecordBatch;
 use crossbeam_channel::Sender;
-use serde::Deserialize;
 use std::collections::HashMap;
 use std::io::Cursor;
-use tokio::io::AsyncReadExt;
-use tokio::net::TcpListener;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tiplot_client::protocol::{self, Compression, HandshakeReply, PacketMetadata};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::Notify;
 
 #[derive(Debug)]
 pub enum DataMessage {
     Metadata(TimelineRange),
+    /// Version details and parameters of the streamed log, sent with packets that have any.
+    LogInfo(LogInfo),
+    /// Playback cursor of a mirrored instance, in absolute seconds.
+    Cursor(f64),
     NewBatch(String, RecordBatch),
+    LoadStarted(PathBuf),
+    LoadProgress {
+        bytes_read: u64,
+        total_bytes: u64,
+    },
+    LoadFinished(PathBuf, Result<Box<LoadedFile>, String>),
+    /// A log was added next to the loaded one under the given session name.
+    SessionLoaded(PathBuf, String, Result<(), String>),
+    /// A file was merged into the loaded data, see
+    /// [`start_merge_load`](super::file_loader::start_merge_load).
+    Merged(PathBuf, Result<Option<SessionMetadata>, String>),
+    /// A log file finished arriving in the watched folder, see
+    /// [`FolderWatcher`](super::FolderWatcher).
+    LogAppeared(PathBuf),
 }
 
-#[derive(Deserialize, Debug, Clone, Copy)]
-pub struct TimelineRange {
-    pub min_timestamp: Option<i64>,
-    pub max_timestamp: Option<i64>,
+/// A data file read in full, see [`start_file_load`](super::file_loader::start_file_load).
+#[derive(Debug)]
+pub struct LoadedFile {
+    pub data_store: DataStore,
+    pub session: Option<SessionMetadata>,
 }
 
-#[derive(Deserialize, Debug)]
-struct PacketMetadata {
-    #[allow(dead_code)]
-    parameters: HashMap<String, serde_json::Value>,
-    #[allow(dead_code)]
-    version_info: HashMap<String, String>,
-    table_count: usize,
-    #[allow(dead_code)]
-    table_names: Vec<String>,
-    timeline_range: TimelineRange,
+pub use tiplot_client::protocol::TimelineRange;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum StreamStatus {
+    Stopped,
+    Listening(SocketAddr),
+    Connected(SocketAddr),
+    Following(SocketAddr),
+    Error(String),
 }
 
-pub fn start_tcp_server(sender: Sender<DataMessage>, ctx: egui::Context) {
-    tokio::spawn(async move {
-        let listener = TcpListener::bind("127.0.0.1:9999")
-            .await
-            .expect("Failed to bind TCP port 9999");
+struct StreamShared {
+    status: Mutex<StreamStatus>,
+    armed: AtomicBool,
+    paused: AtomicBool,
+    bytes_received: AtomicU64,
+    dropped_tables: AtomicU64,
+    last_error: Mutex<Option<String>>,
+    /// Errors not yet shown to the user.
+    pending_errors: Mutex<Vec<String>>,
+    armed_changed: Notify,
+    drop_connection: Notify,
+    shutting_down: AtomicBool,
+}
+
+/// Handle shared between the UI and the TCP server for observing and steering the
+/// live stream.
+#[derive(Clone)]
+pub struct StreamControl {
+    shared: Arc<StreamShared>,
+}
+
+impl StreamControl {
+    pub fn new() -> Self {
+        Self {
+            shared: Arc::new(StreamShared {
+                status: Mutex::new(StreamStatus::Stopped),
+                armed: AtomicBool::new(true),
+                paused: AtomicBool::new(false),
+                bytes_received: AtomicU64::new(0),
+                dropped_tables: AtomicU64::new(0),
+                last_error: Mutex::new(None),
+                pending_errors: Mutex::new(Vec::new()),
+                armed_changed: Notify::new(),
+                drop_connection: Notify::new(),
+                shutting_down: AtomicBool::new(false),
+            }),
+        }
+    }
+
+    pub fn status(&self) -> StreamStatus {
+        self.shared.status.lock().unwrap().clone()
+    }
+
+    fn set_status(&self, status: StreamStatus) {
+        *self.shared.status.lock().unwrap() = status;
+    }
+
+    pub fn is_armed(&self) -> bool {
+        self.shared.armed.load(Ordering::Relaxed)
+    }
+
+    /// Starts or stops listening. Stopping also drops the current connection.
+    pub fn set_armed(&self, armed: bool) {
+        self.shared.armed.store(armed, Ordering::Relaxed);
+        if !armed {
+            self.shared.drop_connection.notify_waiters();
+        }
+        self.shared.armed_changed.notify_one();
+    }
 
-        println!("TCP Receiver listening on 127.0.0.1:9999");
+    pub fn is_paused(&self) -> bool {
+        self.shared.paused.load(Ordering::Relaxed)
+    }
+
+    /// While paused the receiver stops reading from the socket, so the sender is held
+    /// back by TCP flow control instead of data being dropped.
+    pub fn set_paused(&self, paused: bool) {
+        self.shared.paused.store(paused, Ordering::Relaxed);
+    }
 
+    pub fn bytes_received(&self) -> u64 {
+        self.shared.bytes_received.load(Ordering::Relaxed)
+    }
+
+    fn add_bytes(&self, bytes: usize) {
+        self.shared
+            .bytes_received
+            .fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    /// Tables discarded because they were corrupt, truncated or unreadable.
+    pub fn dropped_tables(&self) -> u64 {
+        self.shared.dropped_tables.load(Ordering::Relaxed)
+    }
+
+    pub fn last_error(&self) -> Option<String> {
+        self.shared.last_error.lock().unwrap().clone()
+    }
+
+    /// Errors reported since the last call, for notifying the user.
+    pub fn take_errors(&self) -> Vec<String> {
+        std::mem::take(&mut self.shared.pending_errors.lock().unwrap())
+    }
+
+    fn report_error(&self, error: String) {
+        tracing::warn!("{}", error);
+        *self.shared.last_error.lock().unwrap() = Some(error.clone());
+        self.shared.pending_errors.lock().unwrap().push(error);
+    }
+
+    fn drop_table(&self, error: String) {
+        self.shared.dropped_tables.fetch_add(1, Ordering::Relaxed);
+        self.report_error(error);
+    }
+
+    pub fn drop_connection(&self) {
+        self.shared.drop_connection.notify_waiters();
+    }
+
+    /// Closes the connection and ends the server task for good, on exit.
+    pub fn shutdown(&self) {
+        self.shared.shutting_down.store(true, Ordering::Relaxed);
+        self.set_armed(false);
+    }
+
+    fn is_shutting_down(&self) -> bool {
+        self.shared.shutting_down.load(Ordering::Relaxed)
+    }
+}
+
+impl Default for StreamControl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn start_tcp_server(
+    sender: Sender<DataMessage>,
+    ingest_filter: SharedIngestFilter,
+    control: StreamControl,
+    ctx: egui::Context,
+    listen_addr: String,
+) {
+    tokio::spawn(async move {
         loop {
-            match listener.accept().await {
-                Ok((mut socket, addr)) => {
-                    println!("New connection from: {}", addr);
+            if control.is_shutting_down() {
+                tracing::info!("TCP Receiver shut down");
+                control.set_status(StreamStatus::Stopped);
+                break;
+            }
 
-                    if let Err(e) = handle_connection(&mut socket, &sender, &ctx).await {
-                        eprintln!("Error handling connection: {}", e);
-                    }
+            if !control.is_armed() {
+                control.set_status(StreamStatus::Stopped);
+                ctx.request_repaint();
+                control.shared.armed_changed.notified().await;
+                continue;
+            }
 
-                    println!("Connection closed");
-                }
+            let bound = TcpListener::bind(&listen_addr)
+                .await
+                .and_then(|listener| Ok((listener.local_addr()?, listener)));
+            let (local_addr, listener) = match bound {
+                Ok(bound) => bound,
                 Err(e) => {
-                    eprintln!("Failed to accept connection: {}", e);
+                    tracing::error!("Failed to bind TCP port {}: {}", listen_addr, e);
+                    control.shared.armed.store(false, Ordering::Relaxed);
+                    control.set_status(StreamStatus::Error(e.to_string()));
+                    ctx.request_repaint();
+                    control.shared.armed_changed.notified().await;
+                    continue;
                 }
+            };
+
+            tracing::info!("TCP Receiver listening on {}", local_addr);
+            control.set_status(StreamStatus::Listening(local_addr));
+            ctx.request_repaint();
+
+            while control.is_armed() {
+                let accepted = tokio::select! {
+                    accepted = listener.accept() => accepted,
+                    _ = control.shared.armed_changed.notified() => continue,
+                };
+
+                match accepted {
+                    Ok((mut socket, addr)) => {
+                        tracing::info!("New connection from: {}", addr);
+                        control.set_status(StreamStatus::Connected(addr));
+                        ctx.request_repaint();
+
+                        let connection =
+                            handle_connection(&mut socket, &sender, &ingest_filter, &control, &ctx);
+
+                        tokio::select! {
+                            result = connection => {
+                                if let Err(e) = result {
+                                    control.report_error(format!("Connection from {} failed: {}", addr, e));
+                                }
+                            }
+                            _ = control.shared.drop_connection.notified() => {
+                                tracing::info!("Connection dropped by user");
+                            }
+                        }
+
+                        tracing::info!("Connection closed");
+                        control.set_status(StreamStatus::Listening(local_addr));
+                        ctx.request_repaint();
+                    }
+                    Err(e) => {
+                        tracing::warn!("Failed to accept connection: {}", e);
+                    }
+                }
+            }
+
+            tracing::info!("TCP Receiver stopped listening");
+        }
+    });
+}
+
+/// Connects to the mirror server of another TiPlot instance and ingests its session
+/// until either side closes the connection or it is dropped from the Data Source window.
+pub fn start_follow(
+    addr: String,
+    sender: Sender<DataMessage>,
+    ingest_filter: SharedIngestFilter,
+    control: StreamControl,
+    ctx: egui::Context,
+) {
+    tokio::spawn(async move {
+        let previous_status = control.status();
+        let result = follow(&addr, &sender, &ingest_filter, &control, &ctx).await;
+
+        match result {
+            Ok(()) => {
+                tracing::info!("Stopped following {}", addr);
+                control.set_status(previous_status);
+            }
+            Err(e) => {
+                tracing::error!("Failed to follow {}: {}", addr, e);
+                control.set_status(StreamStatus::Error(e));
             }
         }
+        ctx.request_repaint();
     });
 }
 
+async fn follow(
+    addr: &str,
+    sender: &Sender<DataMessage>,
+    ingest_filter: &SharedIngestFilter,
+    control: &StreamControl,
+    ctx: &egui::Context,
+) -> Result<(), String> {
+    let mut socket = TcpStream::connect(addr).await.map_err(|e| e.to_string())?;
+    let peer = socket.peer_addr().map_err(|e| e.to_string())?;
+
+    let handshake = async {
+        socket
+            .write_all(&protocol::encode_hello(protocol::PROTOCOL_VERSION))
+            .await?;
+        let mut reply = [0u8; protocol::REPLY_LEN];
+        socket.read_exact(&mut reply).await?;
+        anyhow::Ok(protocol::decode_reply(&reply)?)
+    };
+    let version = match handshake.await.map_err(|e| e.to_string())? {
+        HandshakeReply::Accepted(version) => {
+            tracing::info!("Following {} (protocol v{})", peer, version);
+            version
+        }
+        HandshakeReply::Rejected(version) => {
+            return Err(format!("the mirror speaks protocol v{}", version));
+        }
+    };
+
+    control.set_status(StreamStatus::Following(peer));
+    ctx.request_repaint();
+
+    tokio::select! {
+        result = receive_packets(&mut socket, version, sender, ingest_filter, control, ctx) => {
+            result.map_err(|e| e.to_string())
+        }
+        _ = control.shared.drop_connection.notified() => {
+            tracing::info!("Connection dropped by user");
+            Ok(())
+        }
+    }
+}
+
 async fn handle_connection(
-    socket: &mut tokio::net::TcpStream,
+    socket: &mut TcpStream,
     sender: &Sender<DataMessage>,
+    ingest_filter: &SharedIngestFilter,
+    control: &StreamControl,
     ctx: &egui::Context,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let mut len_buf = [0u8; 4];
     socket.read_exact(&mut len_buf).await?;
-    let meta_len = u32::from_le_bytes(len_buf) as usize;
 
-    let mut meta_json = vec![0u8; meta_len];
-    socket.read_exact(&mut meta_json).await?;
+    if len_buf != protocol::MAGIC {
+        // Legacy sender: no handshake, a single packet per connection.
+        let meta_len = u32::from_le_bytes(len_buf) as usize;
+        let version = protocol::MIN_PROTOCOL_VERSION;
+        return receive_packet(
+            socket,
+            meta_len,
+            version,
+            sender,
+            ingest_filter,
+            control,
+            ctx,
+        )
+        .await;
+    }
 
-    let metadata: PacketMetadata = serde_json::from_slice(&meta_json)?;
-    println!("Received metadata: {} tables", metadata.table_count);
+    let mut version_buf = [0u8; 2];
+    socket.read_exact(&mut version_buf).await?;
+    let client_version = u16::from_le_bytes(version_buf);
+    let reply = HandshakeReply::negotiate(client_version);
+    socket.write_all(&reply.encode()).await?;
+    control.add_bytes(protocol::HELLO_LEN);
 
-    sender
-        .send(DataMessage::Metadata(metadata.timeline_range))
-        .ok();
+    let HandshakeReply::Accepted(version) = reply else {
+        return Err(format!("Client protocol v{} is not supported", client_version).into());
+    };
+    tracing::info!("Client negotiated protocol v{}", version);
+    receive_packets(socket, version, sender, ingest_filter, control, ctx).await
+}
 
-    ctx.request_repaint();
+/// Reads packets until the peer closes the connection. Once the peer has sent a
+/// heartbeat, it is dropped if it stays silent for longer than the heartbeat timeout.
+async fn receive_packets(
+    socket: &mut TcpStream,
+    version: u16,
+    sender: &Sender<DataMessage>,
+    ingest_filter: &SharedIngestFilter,
+    control: &StreamControl,
+    ctx: &egui::Context,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut len_buf = [0u8; 4];
+    let mut heartbeating = false;
+    loop {
+        let read = socket.read_exact(&mut len_buf);
+        let read = if heartbeating {
+            tokio::time::timeout(protocol::HEARTBEAT_TIMEOUT, read)
+                .await
+                .map_err(|_| "heartbeat timed out")?
+        } else {
+            read.await
+        };
+        match read {
+            Ok(_) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(()),
+            Err(e) => return Err(e.into()),
+        }
 
+        if len_buf == protocol::HEARTBEAT && version >= protocol::CHECKSUM_VERSION {
+            heartbeating = true;
+            control.add_bytes(len_buf.len());
+            continue;
+        }
+        let meta_len = u32::from_le_bytes(len_buf) as usize;
+        receive_packet(
+            socket,
+            meta_len,
+            version,
+            sender,
+            ingest_filter,
+            control,
+            ctx,
+        )
+        .await?;
+    }
+}
+
+/// Reads a frame of `len` bytes. The buffer grows as data arrives rather than trusting
+/// the announced length up front, so a corrupt length fails on a short read instead of
+/// a huge allocation.
+async fn read_frame(socket: &mut TcpStream, len: usize) -> std::io::Result<Vec<u8>> {
+    let mut frame = Vec::with_capacity(len.min(1 << 20));
+    socket.take(len as u64).read_to_end(&mut frame).await?;
+    if frame.len() < len {
+        return Err(std::io::ErrorKind::UnexpectedEof.into());
+    }
+    Ok(frame)
+}
+
+/// Reads one packet and forwards it once it has fully arrived, so a connection cut
+/// mid-packet leaves nothing half-ingested. Corrupt or unreadable tables are dropped
+/// and reported without affecting the rest of the packet.
+async fn receive_packet(
+    socket: &mut TcpStream,
+    meta_len: usize,
+    version: u16,
+    sender: &Sender<DataMessage>,
+    ingest_filter: &SharedIngestFilter,
+    control: &StreamControl,
+    ctx: &egui::Context,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut len_buf = [0u8; 4];
+    let meta_json = read_frame(socket, meta_len).await?;
+    control.add_bytes(4 + meta_len);
+
+    let metadata: PacketMetadata = serde_json::from_slice(&meta_json)
+        .map_err(|e| format!("Malformed packet metadata: {}", e))?;
+    tracing::debug!("Received metadata: {} tables", metadata.table_count);
+
+    let mut batches = Vec::new();
     for _i in 0..metadata.table_count {
+        while control.is_paused() {
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        }
+
         socket.read_exact(&mut len_buf).await?;
         let name_len = u32::from_le_bytes(len_buf) as usize;
-
-        let mut name_buf = vec![0u8; name_len];
-        socket.read_exact(&mut name_buf).await?;
+        let name_buf = read_frame(socket, name_len).await?;
         let table_name = String::from_utf8_lossy(&name_buf).to_string();
+        control.add_bytes(4 + name_len);
+
+        let mut flag = [0u8; 1];
+        if version >= protocol::COMPRESSION_VERSION {
+            socket.read_exact(&mut flag).await?;
+            control.add_bytes(flag.len());
+        }
 
         let mut size_buf = [0u8; 8];
         socket.read_exact(&mut size_buf).await?;
         let table_size = u64::from_le_bytes(size_buf) as usize;
+        let payload = read_frame(socket, table_size).await?;
+        control.add_bytes(8 + table_size);
 
-        let mut arrow_data = vec![0u8; table_size];
-        socket.read_exact(&mut arrow_data).await?;
+        if version >= protocol::CHECKSUM_VERSION {
+            socket.read_exact(&mut len_buf).await?;
+            control.add_bytes(len_buf.len());
+            if protocol::checksum(&payload) != u32::from_le_bytes(len_buf) {
+                control.drop_table(format!("Dropped table '{}': checksum mismatch", table_name));
+                continue;
+            }
+        }
+
+        let filter = ingest_filter.read().unwrap().clone();
+        if !filter.accepts_topic(&table_name) {
+            continue;
+        }
+
+        let decompressed = Compression::from_flag(flag[0])
+            .and_then(|codec| codec.decompress_within(payload, protocol::MAX_DECOMPRESSED_SIZE));
+        let arrow_data = match decompressed {
+            Ok(data) => data,
+            Err(e) => {
+                control.drop_table(format!("Dropped table '{}': {}", table_name, e));
+                continue;
+            }
+        };
 
         let cursor = Cursor::new(arrow_data);
         match arrow::ipc::reader::StreamReader::try_new(cursor, None) {
@@ -99,24 +503,372 @@ async fn handle_connection(
                 for batch_result in reader {
                     match batch_result {
                         Ok(batch) => {
-                            sender
-                                .send(DataMessage::NewBatch(table_name.clone(), batch))
-                                .ok();
-
-                            ctx.request_repaint();
-                        }
-                        Err(e) => {
-                            eprintln!("Error reading batch from '{}': {}", table_name, e);
+                            if let Some(batch) = filter.apply(&table_name, batch) {
+                                batches.push((table_name.clone(), batch));
+                            }
                         }
+                        Err(e) => control.drop_table(format!(
+                            "Dropped batch: error reading batch from '{}': {}",
+                            table_name, e
+                        )),
                     }
                 }
             }
-            Err(e) => {
-                eprintln!("Arrow IPC parse error for '{}': {}", table_name, e);
-            }
+            Err(e) => control.drop_table(format!(
+                "Dropped table: Arrow IPC parse error for '{}': {}",
+                table_name, e
+            )),
         }
     }
 
-    println!("Finished processing all tables");
+    if let Some(cursor) = metadata.cursor {
+        sender.send(DataMessage::Cursor(cursor))?;
+    }
+    if let Some(info) = log_info(&metadata) {
+        sender.send(DataMessage::LogInfo(info))?;
+    }
+    if metadata.cursor.is_none() || metadata.table_count > 0 {
+        sender.send(DataMessage::Metadata(metadata.timeline_range))?;
+    }
+    for (table_name, batch) in batches {
+        sender.send(DataMessage::NewBatch(table_name, batch))?;
+    }
+    ctx.request_repaint();
+
+    tracing::debug!("Finished processing all tables");
     Ok(())
 }
+
+/// Log header fields of `metadata`, if the sender filled any in.
+fn log_info(metadata: &PacketMetadata) -> Option<LogInfo> {
+    let texts = |values: &HashMap<String, serde_json::Value>| {
+        values
+            .iter()
+            .map(|(name, value)| {
+                let value = match value {
+                    serde_json::Value::String(text) => text.clone(),
+                    value => value.to_string(),
+                };
+                (name.clone(), value)
+            })
+            .collect()
+    };
+    let info = LogInfo {
+        version_info: metadata.version_info.clone().into_iter().collect(),
+        parameters: texts(&metadata.parameters),
+        parameter_defaults: texts(&metadata.parameter_defaults),
+    };
+    (!info.is_empty()).then_some(info)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::acquisition::test_support::*;
+    use crate::core::IngestFilter;
+
+    const V: u16 = protocol::PROTOCOL_VERSION;
+
+    fn reply(bytes: &[u8]) -> HandshakeReply {
+        let reply: [u8; protocol::REPLY_LEN] = bytes[..protocol::REPLY_LEN].try_into().unwrap();
+        protocol::decode_reply(&reply).unwrap()
+    }
+
+    #[tokio::test]
+    async fn negotiates_the_lower_version() {
+        let server = TestServer::start().await;
+        let answer = server.exchange(&protocol::encode_hello(99)).await;
+        assert_eq!(
+            reply(&answer),
+            HandshakeReply::Accepted(protocol::PROTOCOL_VERSION)
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_unsupported_versions() {
+        let server = TestServer::start().await;
+        let answer = server.exchange(&protocol::encode_hello(0)).await;
+        assert_eq!(
+            reply(&answer),
+            HandshakeReply::Rejected(protocol::PROTOCOL_VERSION)
+        );
+        assert!(server.drain().is_empty());
+    }
+
+    #[tokio::test]
+    async fn ingests_tables() {
+        let server = TestServer::start().await;
+        let first = sample_batch(1_000_000, 0.0, 5);
+        let second = sample_batch(1_500_000, 10.0, 2);
+        let mut bytes = hello();
+        bytes.extend(packet(&[("first", &first), ("second", &second)], V));
+        server.exchange(&bytes).await;
+
+        let store = store_from(server.drain());
+        assert_eq!(store.get_topics(), ["first", "second"]);
+        assert_eq!(store.start_time, 1.0);
+        assert_eq!(
+            store.get_column("first", "value").unwrap(),
+            &[0.0, 1.0, 2.0, 3.0, 4.0]
+        );
+        assert_eq!(store.get_column("second", "value").unwrap(), &[10.0, 11.0]);
+        let times = store.get_column("second", "timestamp").unwrap();
+        assert!((times[0] - 0.5).abs() < 1e-6);
+        assert_eq!(server.control.bytes_received(), bytes.len() as u64);
+    }
+
+    #[tokio::test]
+    async fn appends_packets_of_one_connection() {
+        let server = TestServer::start().await;
+        let mut bytes = hello();
+        bytes.extend(packet(&[("a", &sample_batch(1_000_000, 0.0, 2))], V));
+        bytes.extend(packet(&[("a", &sample_batch(1_200_000, 2.0, 2))], V));
+        server.exchange(&bytes).await;
+
+        let store = store_from(server.drain());
+        assert_eq!(
+            store.get_column("a", "value").unwrap(),
+            &[0.0, 1.0, 2.0, 3.0]
+        );
+    }
+
+    #[tokio::test]
+    async fn accepts_legacy_single_packets() {
+        let server = TestServer::start().await;
+        let answer = server
+            .exchange(&packet(
+                &[("legacy", &sample_batch(1_000_000, 7.0, 1))],
+                protocol::MIN_PROTOCOL_VERSION,
+            ))
+            .await;
+
+        assert!(answer.is_empty());
+        let store = store_from(server.drain());
+        assert_eq!(store.get_column("legacy", "value").unwrap(), &[7.0]);
+    }
+
+    #[tokio::test]
+    async fn drops_malformed_tables_but_keeps_the_rest() {
+        let server = TestServer::start().await;
+        let mut bytes = hello();
+        bytes.extend(metadata(2));
+        bytes.extend(raw_table(
+            "broken",
+            b"not an arrow stream",
+            Compression::None,
+        ));
+        bytes.extend(table("good", &sample_batch(1_000_000, 1.0, 2)));
+        server.exchange(&bytes).await;
+
+        let store = store_from(server.drain());
+        assert_eq!(store.get_topics(), ["good"]);
+        assert_eq!(store.get_column("good", "value").unwrap(), &[1.0, 2.0]);
+    }
+
+    #[tokio::test]
+    async fn drops_tables_with_bad_checksums() {
+        let server = TestServer::start().await;
+        let mut corrupt = table("corrupt", &sample_batch(1_000_000, 0.0, 2));
+        let payload_byte = corrupt.len() - 8;
+        corrupt[payload_byte] ^= 0xFF;
+        let mut bytes = hello();
+        bytes.extend(metadata(2));
+        bytes.extend(corrupt);
+        bytes.extend(table("good", &sample_batch(1_000_000, 1.0, 1)));
+        server.exchange(&bytes).await;
+
+        assert_eq!(store_from(server.drain()).get_topics(), ["good"]);
+        assert_eq!(server.control.dropped_tables(), 1);
+        assert_eq!(server.control.take_errors().len(), 1);
+        assert!(server.control.take_errors().is_empty());
+    }
+
+    #[tokio::test]
+    async fn decompresses_tables() {
+        let server = TestServer::start().await;
+        let batch = sample_batch(1_000_000, 0.0, 100);
+        let mut bytes = hello();
+        bytes.extend(metadata(3));
+        for (name, compression) in [
+            ("none", Compression::None),
+            ("lz4", Compression::Lz4),
+            ("zstd", Compression::Zstd),
+        ] {
+            bytes.extend(protocol::encode_table(name, &batch, V, compression).unwrap());
+        }
+        server.exchange(&bytes).await;
+
+        let store = store_from(server.drain());
+        assert_eq!(store.get_topics(), ["lz4", "none", "zstd"]);
+        for topic in ["lz4", "none", "zstd"] {
+            assert_eq!(store.get_column(topic, "value").unwrap().len(), 100);
+        }
+    }
+
+    #[tokio::test]
+    async fn drops_tables_claiming_oversized_decompression() {
+        let server = TestServer::start().await;
+        let mut lz4 = u32::MAX.to_le_bytes().to_vec();
+        lz4.extend([0; 16]);
+        // Zstandard frame header: single segment, 8-byte content size.
+        let mut zstd = vec![0x28, 0xB5, 0x2F, 0xFD, 0xE0];
+        zstd.extend((1u64 << 40).to_le_bytes());
+        let mut bytes = hello();
+        bytes.extend(metadata(3));
+        bytes.extend(raw_table("lz4", &lz4, Compression::Lz4));
+        bytes.extend(raw_table("zstd", &zstd, Compression::Zstd));
+        bytes.extend(table("good", &sample_batch(1_000_000, 0.0, 1)));
+        server.exchange(&bytes).await;
+
+        assert_eq!(store_from(server.drain()).get_topics(), ["good"]);
+        assert_eq!(server.control.dropped_tables(), 2);
+        let errors = server.control.take_errors();
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().all(|e| e.contains("exceeds the limit")));
+    }
+
+    #[tokio::test]
+    async fn drops_tables_with_unknown_compression() {
+        let server = TestServer::start().await;
+        let mut unknown = table("unknown", &sample_batch(1_000_000, 0.0, 1));
+        unknown["unknown".len() + 4] = 0xEE;
+        let mut bytes = hello();
+        bytes.extend(metadata(2));
+        bytes.extend(unknown);
+        bytes.extend(table("good", &sample_batch(1_000_000, 0.0, 1)));
+        server.exchange(&bytes).await;
+
+        assert_eq!(store_from(server.drain()).get_topics(), ["good"]);
+        assert_eq!(server.control.dropped_tables(), 1);
+    }
+
+    #[tokio::test]
+    async fn accepts_version_2_tables_without_checksums() {
+        let server = TestServer::start().await;
+        let mut bytes = protocol::encode_hello(2).to_vec();
+        bytes.extend(packet(&[("old", &sample_batch(1_000_000, 3.0, 1))], 2));
+        let answer = server.exchange(&bytes).await;
+
+        assert_eq!(reply(&answer), HandshakeReply::Accepted(2));
+        assert_eq!(
+            store_from(server.drain())
+                .get_column("old", "value")
+                .unwrap(),
+            &[3.0]
+        );
+    }
+
+    #[tokio::test]
+    async fn skips_heartbeats() {
+        let server = TestServer::start().await;
+        let mut bytes = hello();
+        bytes.extend(protocol::HEARTBEAT);
+        bytes.extend(packet(&[("a", &sample_batch(1_000_000, 0.0, 1))], V));
+        bytes.extend(protocol::HEARTBEAT);
+        server.exchange(&bytes).await;
+
+        assert_eq!(store_from(server.drain()).get_topics(), ["a"]);
+        assert!(server.control.last_error().is_none());
+    }
+
+    #[tokio::test]
+    async fn survives_malformed_metadata() {
+        let server = TestServer::start().await;
+        let mut bytes = hello();
+        let json = b"{\"table_count\": ";
+        bytes.extend((json.len() as u32).to_le_bytes());
+        bytes.extend(json);
+        server.exchange(&bytes).await;
+        assert!(server.drain().is_empty());
+
+        let mut bytes = hello();
+        bytes.extend(packet(&[("after", &sample_batch(1_000_000, 0.0, 1))], V));
+        server.exchange(&bytes).await;
+        assert_eq!(store_from(server.drain()).get_topics(), ["after"]);
+        assert_eq!(
+            server.control.status(),
+            StreamStatus::Listening(server.addr)
+        );
+    }
+
+    #[tokio::test]
+    async fn survives_truncated_tables() {
+        let server = TestServer::start().await;
+        let cut = table("cut", &sample_batch(1_000_000, 0.0, 4));
+        let mut bytes = hello();
+        bytes.extend(metadata(2));
+        bytes.extend(table("whole", &sample_batch(1_000_000, 0.0, 1)));
+        bytes.extend(&cut[..cut.len() / 2]);
+        server.exchange(&bytes).await;
+
+        // Nothing of the cut packet is ingested, not even its complete tables.
+        assert!(server.drain().is_empty());
+        assert!(server.control.last_error().is_some());
+
+        let mut bytes = hello();
+        bytes.extend(packet(&[("after", &sample_batch(1_000_000, 0.0, 1))], V));
+        server.exchange(&bytes).await;
+        assert_eq!(store_from(server.drain()).get_topics(), ["after"]);
+    }
+
+    #[tokio::test]
+    async fn forwards_cursor_only_packets() {
+        let server = TestServer::start().await;
+        let metadata = PacketMetadata {
+            cursor: Some(12.5),
+            ..Default::default()
+        };
+        let mut bytes = hello();
+        bytes.extend(protocol::encode_metadata(&metadata).unwrap());
+        server.exchange(&bytes).await;
+
+        let messages = server.drain();
+        assert!(matches!(messages[..], [DataMessage::Cursor(c)] if c == 12.5));
+    }
+
+    #[tokio::test]
+    async fn forwards_log_headers() {
+        let server = TestServer::start().await;
+        let mut metadata = PacketMetadata::default();
+        metadata
+            .version_info
+            .insert("ver_sw_release".into(), "v1.14.0".into());
+        metadata
+            .parameters
+            .insert("MPC_XY_VEL_MAX".into(), serde_json::json!(12.5));
+        metadata
+            .parameters
+            .insert("SYS_AUTOSTART".into(), serde_json::json!("4001"));
+        let mut bytes = hello();
+        bytes.extend(protocol::encode_metadata(&metadata).unwrap());
+        server.exchange(&bytes).await;
+
+        let messages = server.drain();
+        let Some(DataMessage::LogInfo(info)) = messages.first() else {
+            panic!("expected the log header first, got {:?}", messages);
+        };
+        assert_eq!(info.version_info["ver_sw_release"], "v1.14.0");
+        assert_eq!(info.parameters["MPC_XY_VEL_MAX"], "12.5");
+        assert_eq!(info.parameters["SYS_AUTOSTART"], "4001");
+    }
+
+    #[tokio::test]
+    async fn applies_the_ingest_filter() {
+        let server =
+            TestServer::start_with_filter(IngestFilter::parse("", "hidden, shown/secret")).await;
+        let batch = sample_batch(1_000_000, 0.0, 2);
+        let with_secret = RecordBatch::try_from_iter([
+            ("timestamp", batch.column(0).clone()),
+            ("value", batch.column(1).clone()),
+            ("secret", batch.column(1).clone()),
+        ])
+        .unwrap();
+        let mut bytes = hello();
+        bytes.extend(packet(&[("hidden", &batch), ("shown", &with_secret)], V));
+        server.exchange(&bytes).await;
+
+        let store = store_from(server.drain());
+        assert_eq!(store.get_topics(), ["shown"]);
+        assert_eq!(store.get_columns("shown"), ["value"]);
+    }
+}
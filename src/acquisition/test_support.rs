@@ -0,0 +1,166 @@
+//! Helpers for exercising the TCP receiver end to end: a server on an ephemeral port,
+//! a peer that sends raw bytes, and a store fed the way the UI feeds it.
+
+use super::tcp_receiver::{start_tcp_server, DataMessage, StreamControl, StreamStatus};
+use crate::core::{DataStore, IngestFilter, SharedIngestFilter};
+use arrow::array::{ArrayRef, Float32Array, Int64Array};
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::record_batch::RecordBatch;
+use crossbeam_channel::{unbounded, Receiver};
+use std::net::SocketAddr;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tiplot_client::protocol::{self, Compression, PacketMetadata, TimelineRange};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+pub struct TestServer {
+    pub addr: SocketAddr,
+    pub control: StreamControl,
+    rx: Receiver<DataMessage>,
+}
+
+impl TestServer {
+    pub async fn start() -> Self {
+        Self::start_with_filter(IngestFilter::default()).await
+    }
+
+    pub async fn start_with_filter(filter: IngestFilter) -> Self {
+        let (tx, rx) = unbounded();
+        let control = StreamControl::new();
+        let ingest_filter: SharedIngestFilter = Arc::new(RwLock::new(filter));
+        start_tcp_server(
+            tx,
+            ingest_filter,
+            control.clone(),
+            egui::Context::default(),
+            "127.0.0.1:0".to_string(),
+        );
+
+        let addr = tokio::time::timeout(TIMEOUT, async {
+            loop {
+                if let StreamStatus::Listening(addr) = control.status() {
+                    return addr;
+                }
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        })
+        .await
+        .expect("server did not start listening");
+
+        Self { addr, control, rx }
+    }
+
+    /// Sends `bytes` on a fresh connection, closes the sending half and waits until the
+    /// server closes its side. Returns everything the server replied.
+    pub async fn exchange(&self, bytes: &[u8]) -> Vec<u8> {
+        tokio::time::timeout(TIMEOUT, async {
+            let mut socket = TcpStream::connect(self.addr).await.unwrap();
+            socket.write_all(bytes).await.unwrap();
+            socket.shutdown().await.unwrap();
+            let mut reply = Vec::new();
+            socket.read_to_end(&mut reply).await.unwrap();
+            reply
+        })
+        .await
+        .expect("server did not close the connection")
+    }
+
+    /// Messages the server has sent so far.
+    pub fn drain(&self) -> Vec<DataMessage> {
+        self.rx.try_iter().collect()
+    }
+}
+
+impl Drop for TestServer {
+    fn drop(&mut self) {
+        self.control.shutdown();
+    }
+}
+
+/// Applies messages to a store the way the UI does.
+pub fn store_from(messages: Vec<DataMessage>) -> DataStore {
+    let mut store = DataStore::new();
+    for message in messages {
+        match message {
+            DataMessage::Metadata(range) => {
+                if let (Some(min), 0.0) = (range.min_timestamp, store.start_time) {
+                    store.start_time = (min as f64 / 1_000_000.0) as f32;
+                }
+            }
+            DataMessage::NewBatch(topic, batch) => store.ingest(topic, batch),
+            DataMessage::LogInfo(info) => store.log_info.merge(info),
+            _ => {}
+        }
+    }
+    store
+}
+
+/// `timestamp` in microseconds starting at `start_us` at 10 Hz, and `value` counting
+/// up from `first_value`.
+pub fn sample_batch(start_us: i64, first_value: f32, rows: usize) -> RecordBatch {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("timestamp", DataType::Int64, false),
+        Field::new("value", DataType::Float32, false),
+    ]));
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(Int64Array::from_iter_values(
+            (0..rows as i64).map(|i| start_us + i * 100_000),
+        )),
+        Arc::new(Float32Array::from_iter_values(
+            (0..rows).map(|i| first_value + i as f32),
+        )),
+    ];
+    RecordBatch::try_new(schema, columns).unwrap()
+}
+
+/// A complete packet carrying `tables`, framed as in protocol `version`.
+pub fn packet(tables: &[(&str, &RecordBatch)], version: u16) -> Vec<u8> {
+    let batches: Vec<&RecordBatch> = tables.iter().map(|(_, batch)| *batch).collect();
+    let metadata = PacketMetadata::new(
+        tables.iter().map(|(name, _)| name.to_string()).collect(),
+        TimelineRange::from_batches(&batches),
+    );
+    let mut bytes = protocol::encode_metadata(&metadata).unwrap();
+    for (name, batch) in tables {
+        bytes.extend(protocol::encode_table(name, batch, version, Compression::None).unwrap());
+    }
+    bytes
+}
+
+/// An uncompressed current-version table frame.
+pub fn table(name: &str, batch: &RecordBatch) -> Vec<u8> {
+    protocol::encode_table(name, batch, protocol::PROTOCOL_VERSION, Compression::None).unwrap()
+}
+
+/// A current-version table frame with `payload` verbatim instead of an Arrow IPC
+/// stream, flagged as compressed with `compression`, and its valid checksum.
+pub fn raw_table(name: &str, payload: &[u8], compression: Compression) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.extend((name.len() as u32).to_le_bytes());
+    bytes.extend(name.as_bytes());
+    bytes.push(compression.flag());
+    bytes.extend((payload.len() as u64).to_le_bytes());
+    bytes.extend(payload);
+    bytes.extend(protocol::checksum(payload).to_le_bytes());
+    bytes
+}
+
+/// Length-prefixed metadata announcing `table_count` tables.
+pub fn metadata(table_count: usize) -> Vec<u8> {
+    let metadata = PacketMetadata {
+        table_count,
+        timeline_range: TimelineRange {
+            min_timestamp: Some(1_000_000),
+            max_timestamp: Some(2_000_000),
+        },
+        ..Default::default()
+    };
+    protocol::encode_metadata(&metadata).unwrap()
+}
+
+pub fn hello() -> Vec<u8> {
+    protocol::encode_hello(protocol::PROTOCOL_VERSION).to_vec()
+}
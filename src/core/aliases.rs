@@ -0,0 +1,236 @@
+use crate::core::DataStore;
+use serde::{Deserialize, Serialize};
+
+/// A vendor-neutral signal name and the column it is logged as by one autopilot.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Alias {
+    /// Dotted canonical name, e.g. `attitude.roll` or `position.ned.x`.
+    pub canonical: String,
+    pub topic: String,
+    pub col: String,
+}
+
+impl Alias {
+    fn new(canonical: &str, topic: &str, col: &str) -> Self {
+        Self {
+            canonical: canonical.to_string(),
+            topic: topic.to_string(),
+            col: col.to_string(),
+        }
+    }
+}
+
+/// Aliases of one autopilot ecosystem. Several aliases may share a canonical name when a
+/// signal moved between topics across firmware versions; the first one present in the log
+/// is used.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AliasProfile {
+    pub name: String,
+    pub enabled: bool,
+    pub aliases: Vec<Alias>,
+}
+
+impl AliasProfile {
+    /// Profiles shipped with TiPlot.
+    pub fn builtin() -> Vec<Self> {
+        vec![Self::px4(), Self::ardupilot(), Self::mavlink()]
+    }
+
+    fn px4() -> Self {
+        let mut aliases = Vec::new();
+        let mut add = |canonical: &str, topic: &str, cols: &[&str]| {
+            aliases.extend(components(canonical, topic, cols));
+        };
+        add(
+            "attitude.q",
+            "vehicle_attitude",
+            &["q[0]", "q[1]", "q[2]", "q[3]"],
+        );
+        add(
+            "rates",
+            "vehicle_angular_velocity",
+            &["xyz[0]", "xyz[1]", "xyz[2]"],
+        );
+        add(
+            "rates_setpoint",
+            "vehicle_rates_setpoint",
+            &["roll", "pitch", "yaw"],
+        );
+        add("position.ned", "vehicle_local_position", &["x", "y", "z"]);
+        add(
+            "velocity.ned",
+            "vehicle_local_position",
+            &["vx", "vy", "vz"],
+        );
+        add(
+            "position.ref",
+            "vehicle_local_position",
+            &["ref_lat", "ref_lon", "ref_alt"],
+        );
+        add(
+            "position.gps",
+            "vehicle_gps_position",
+            &["latitude_deg", "longitude_deg", "altitude_msl_m"],
+        );
+        add(
+            "position.gps",
+            "sensor_gps",
+            &["latitude_deg", "longitude_deg", "altitude_msl_m"],
+        );
+        add(
+            "imu.accel",
+            "sensor_combined",
+            &[
+                "accelerometer_m_s2[0]",
+                "accelerometer_m_s2[1]",
+                "accelerometer_m_s2[2]",
+            ],
+        );
+        add(
+            "imu.gyro",
+            "sensor_combined",
+            &["gyro_rad[0]", "gyro_rad[1]", "gyro_rad[2]"],
+        );
+        add("battery.voltage", "battery_status", &["voltage_v"]);
+        add("battery.current", "battery_status", &["current_a"]);
+        Self {
+            name: "PX4".to_string(),
+            enabled: true,
+            aliases,
+        }
+    }
+
+    fn ardupilot() -> Self {
+        let mut aliases = Vec::new();
+        let mut add = |canonical: &str, topic: &str, cols: &[&str]| {
+            aliases.extend(components(canonical, topic, cols));
+        };
+        add("attitude.q", "xkq", &["Q1", "Q2", "Q3", "Q4"]);
+        add("attitude", "att", &["Roll", "Pitch", "Yaw"]);
+        add("rates", "rate", &["R", "P", "Y"]);
+        add("rates_setpoint", "rate", &["RDes", "PDes", "YDes"]);
+        add("position.ned", "xkf1", &["PN", "PE", "PD"]);
+        add("velocity.ned", "xkf1", &["VN", "VE", "VD"]);
+        add("position.ref", "orgn", &["Lat", "Lng", "Alt"]);
+        add("position.gps", "pos", &["Lat", "Lng", "Alt"]);
+        add("position.gps", "gps", &["Lat", "Lng", "Alt"]);
+        add("imu.accel", "imu", &["AccX", "AccY", "AccZ"]);
+        add("imu.gyro", "imu", &["GyrX", "GyrY", "GyrZ"]);
+        add("battery.voltage", "bat", &["Volt"]);
+        add("battery.current", "bat", &["Curr"]);
+        Self {
+            name: "ArduPilot".to_string(),
+            enabled: true,
+            aliases,
+        }
+    }
+
+    /// Messages of a telemetry log, named after the lowercased message type. Only fields
+    /// in SI units are mapped; the integer-scaled GPS messages are left out.
+    fn mavlink() -> Self {
+        let mut aliases = Vec::new();
+        let mut add = |canonical: &str, topic: &str, cols: &[&str]| {
+            aliases.extend(components(canonical, topic, cols));
+        };
+        add(
+            "attitude.q",
+            "attitude_quaternion",
+            &["q1", "q2", "q3", "q4"],
+        );
+        add("attitude", "attitude", &["roll", "pitch", "yaw"]);
+        add(
+            "rates",
+            "attitude",
+            &["rollspeed", "pitchspeed", "yawspeed"],
+        );
+        add("position.ned", "local_position_ned", &["x", "y", "z"]);
+        add("velocity.ned", "local_position_ned", &["vx", "vy", "vz"]);
+        Self {
+            name: "MAVLink".to_string(),
+            enabled: true,
+            aliases,
+        }
+    }
+}
+
+/// One alias per column, named `<canonical>.<axis>` when there are several.
+fn components<'a>(
+    canonical: &'a str,
+    topic: &'a str,
+    cols: &'a [&'a str],
+) -> impl Iterator<Item = Alias> + 'a {
+    axes(cols.len())
+        .iter()
+        .zip(cols)
+        .map(move |(suffix, col)| Alias::new(&join(canonical, suffix), topic, col))
+}
+
+/// Suffixes of the components of a canonical name with `count` columns.
+fn axes(count: usize) -> &'static [&'static str] {
+    match count {
+        1 => &[""],
+        3 => &["x", "y", "z"],
+        4 => &["w", "x", "y", "z"],
+        _ => &[],
+    }
+}
+
+fn join(canonical: &str, suffix: &str) -> String {
+    match (canonical, suffix) {
+        (c, "") => c.to_string(),
+        // Attitude and rates are named by axis rather than x/y/z.
+        ("attitude" | "rates" | "rates_setpoint", s) => {
+            let axis = match s {
+                "x" => "roll",
+                "y" => "pitch",
+                _ => "yaw",
+            };
+            format!("{}.{}", canonical, axis)
+        }
+        (c, s) => format!("{}.{}", c, s),
+    }
+}
+
+/// Topic and column a canonical name refers to in the loaded data, from the first enabled
+/// profile that has it.
+pub fn resolve<'a>(
+    profiles: &'a [AliasProfile],
+    data_store: &DataStore,
+    canonical: &str,
+) -> Option<(&'a str, &'a str)> {
+    profiles
+        .iter()
+        .filter(|profile| profile.enabled)
+        .flat_map(|profile| &profile.aliases)
+        .find(|alias| {
+            alias.canonical == canonical
+                && data_store.get_column(&alias.topic, &alias.col).is_some()
+        })
+        .map(|alias| (alias.topic.as_str(), alias.col.as_str()))
+}
+
+/// Canonical name of a vendor topic and column, if any enabled profile maps it.
+pub fn canonical_name<'a>(profiles: &'a [AliasProfile], topic: &str, col: &str) -> Option<&'a str> {
+    profiles
+        .iter()
+        .filter(|profile| profile.enabled)
+        .flat_map(|profile| &profile.aliases)
+        .find(|alias| alias.topic == topic && alias.col == col)
+        .map(|alias| alias.canonical.as_str())
+}
+
+/// Replacement for `topic`/`col` when it is missing from the loaded data: the column
+/// another autopilot logs the same signal as.
+pub fn translate(
+    profiles: &[AliasProfile],
+    data_store: &DataStore,
+    topic: &str,
+    col: &str,
+) -> Option<(String, String)> {
+    if data_store.get_column(topic, col).is_some() {
+        return None;
+    }
+    let canonical = canonical_name(profiles, topic, col)?;
+    resolve(profiles, data_store, canonical)
+        .map(|(topic, col)| (topic.to_string(), col.to_string()))
+}
@@ -0,0 +1,291 @@
+//! Plain Arrow IPC files, which other tools (pandas, DuckDB, ...) open without a custom
+//! reader, next to TiPlot's own multi-topic container.
+
+use super::DataStore;
+use arrow::array::{Array, ArrayRef, Float32Array, Float64Array, Int64Array, StringArray};
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::ipc::reader::FileReader;
+use arrow::ipc::writer::FileWriter;
+use arrow::record_batch::RecordBatch;
+use std::collections::{BTreeSet, HashMap};
+use std::fs::File;
+use std::io::{BufWriter, Read};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tiplot_client::protocol::TimelineRange;
+
+/// Column naming the topic of each row in single-table exports.
+pub const TOPIC_COLUMN: &str = "topic";
+/// Schema metadata keeping the original topic name of a per-topic file, whose file name
+/// may have been sanitized.
+const TOPIC_METADATA: &str = "tiplot.topic";
+const ARROW_FILE_MAGIC: &[u8; 6] = b"ARROW1";
+/// File extensions of Arrow IPC files.
+pub const EXTENSIONS: [&str; 3] = ["arrow", "feather", "ipc"];
+
+/// Whether `path` is a folder of Arrow IPC files or a single one, rather than a TiPlot
+/// container.
+pub fn is_standard_arrow(path: &Path) -> bool {
+    if path.is_dir() {
+        return true;
+    }
+    let mut magic = [0u8; 6];
+    File::open(path)
+        .and_then(|mut file| file.read_exact(&mut magic))
+        .is_ok_and(|_| &magic == ARROW_FILE_MAGIC)
+}
+
+/// Writes every topic to `<dir>/<topic>.arrow` and returns how many were written.
+pub fn export_topic_files(store: &DataStore, dir: &Path) -> anyhow::Result<usize> {
+    let topics = store.saved_topics();
+    if topics.is_empty() {
+        return Err(anyhow::anyhow!("No data to save"));
+    }
+    std::fs::create_dir_all(dir)?;
+
+    for topic in &topics {
+        let batch = export_batch(store, topic)?;
+        let mut metadata = HashMap::new();
+        metadata.insert(TOPIC_METADATA.to_string(), topic.to_string());
+        let schema = Arc::new(batch.schema().as_ref().clone().with_metadata(metadata));
+        let batch = batch.with_schema(schema.clone())?;
+
+        let file = File::create(dir.join(format!("{}.arrow", file_stem(topic))))?;
+        let mut writer = FileWriter::try_new(BufWriter::new(file), &schema)?;
+        writer.write(&batch)?;
+        writer.finish()?;
+    }
+    Ok(topics.len())
+}
+
+/// Writes all topics into one file with a [`TOPIC_COLUMN`]. Columns are merged by name
+/// and null for the rows of topics that lack them.
+pub fn export_table(store: &DataStore, path: &Path) -> anyhow::Result<()> {
+    let topics = store.saved_topics();
+    if topics.is_empty() {
+        return Err(anyhow::anyhow!("No data to save"));
+    }
+
+    let batches = topics
+        .iter()
+        .map(|topic| Ok((topic.as_str(), export_batch(store, topic)?)))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let columns: BTreeSet<String> = batches
+        .iter()
+        .flat_map(|(_, batch)| {
+            batch
+                .schema()
+                .fields()
+                .iter()
+                .map(|f| f.name().clone())
+                .collect::<Vec<_>>()
+        })
+        .filter(|name| name != "timestamp" && name != TOPIC_COLUMN)
+        .collect();
+
+    let mut fields = vec![
+        Field::new(TOPIC_COLUMN, DataType::Utf8, false),
+        Field::new("timestamp", DataType::Int64, false),
+    ];
+    fields.extend(
+        columns
+            .iter()
+            .map(|name| Field::new(name.as_str(), DataType::Float32, true)),
+    );
+    let schema = Arc::new(Schema::new(fields));
+
+    let mut writer = FileWriter::try_new(BufWriter::new(File::create(path)?), &schema)?;
+    for (topic, batch) in batches {
+        let rows = batch.num_rows();
+        let mut arrays: Vec<ArrayRef> = vec![
+            Arc::new(StringArray::from(vec![topic; rows])),
+            batch
+                .column_by_name("timestamp")
+                .cloned()
+                .ok_or_else(|| anyhow::anyhow!("Topic '{}' has no timestamps", topic))?,
+        ];
+        arrays.extend(columns.iter().map(|name| {
+            batch
+                .column_by_name(name)
+                .cloned()
+                .unwrap_or_else(|| arrow::array::new_null_array(&DataType::Float32, rows))
+        }));
+        writer.write(&RecordBatch::try_new(schema.clone(), arrays)?)?;
+    }
+    writer.finish()?;
+    Ok(())
+}
+
+/// A topic with its timestamps as absolute `Int64` microseconds, as the streaming
+/// protocol expects them.
+fn export_batch(store: &DataStore, topic: &str) -> anyhow::Result<RecordBatch> {
+    let batch = store.topic_batch(topic)?;
+    let schema = batch.schema();
+    let start_time = store.start_time as f64;
+
+    let mut fields = Vec::new();
+    let mut arrays = Vec::new();
+    for (field, column) in schema.fields().iter().zip(batch.columns()) {
+        let times = column.as_any().downcast_ref::<Float32Array>();
+        match times.filter(|_| field.name() == "timestamp") {
+            Some(times) => {
+                fields.insert(0, Field::new("timestamp", DataType::Int64, false));
+                let micros = times
+                    .values()
+                    .iter()
+                    .map(|&t| ((t as f64 + start_time) * 1_000_000.0).round() as i64);
+                arrays.insert(
+                    0,
+                    Arc::new(Int64Array::from_iter_values(micros)) as ArrayRef,
+                );
+            }
+            None => {
+                fields.push(field.as_ref().clone());
+                arrays.push(column.clone());
+            }
+        }
+    }
+    Ok(RecordBatch::try_new(Arc::new(Schema::new(fields)), arrays)?)
+}
+
+/// File name for a topic, with path separators and other unsafe characters replaced.
+fn file_stem(topic: &str) -> String {
+    topic
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c => c,
+        })
+        .collect()
+}
+
+/// Reads a folder of Arrow IPC files or a single one. Files with a [`TOPIC_COLUMN`] are
+/// split by it; otherwise the topic is the one recorded on export or the file name.
+/// Integer microsecond timestamps are made relative to the earliest one, the way the
+/// TiPlot container stores them.
+pub fn read_standard_arrow(
+    path: &Path,
+    mut on_batch: impl FnMut(&str, RecordBatch),
+    mut on_progress: impl FnMut(u64, u64),
+) -> anyhow::Result<()> {
+    let files = if path.is_dir() {
+        let mut files: Vec<PathBuf> = std::fs::read_dir(path)?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|p| {
+                p.extension()
+                    .and_then(|e| e.to_str())
+                    .is_some_and(|e| EXTENSIONS.contains(&e.to_lowercase().as_str()))
+            })
+            .collect();
+        files.sort();
+        if files.is_empty() {
+            return Err(anyhow::anyhow!("{} has no Arrow files", path.display()));
+        }
+        files
+    } else {
+        vec![path.to_path_buf()]
+    };
+
+    let total_bytes: u64 = files
+        .iter()
+        .filter_map(|f| f.metadata().ok())
+        .map(|m| m.len())
+        .sum();
+    let mut bytes_read = 0;
+    let mut batches = Vec::new();
+    for file_path in &files {
+        let reader = FileReader::try_new(File::open(file_path)?, None)
+            .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", file_path.display(), e))?;
+        let schema = reader.schema();
+        let topic = schema
+            .metadata()
+            .get(TOPIC_METADATA)
+            .cloned()
+            .or_else(|| {
+                file_path
+                    .file_stem()
+                    .map(|s| s.to_string_lossy().into_owned())
+            })
+            .unwrap_or_else(|| "data".to_string());
+
+        for batch in reader {
+            let batch = batch?;
+            match batch.column_by_name(TOPIC_COLUMN) {
+                Some(_) => batches.extend(split_by_topic(&batch)?),
+                None => batches.push((topic.clone(), batch)),
+            }
+        }
+
+        bytes_read += file_path.metadata().map_or(0, |m| m.len());
+        on_progress(bytes_read, total_bytes);
+    }
+
+    let refs: Vec<&RecordBatch> = batches.iter().map(|(_, batch)| batch).collect();
+    let origin = TimelineRange::from_batches(&refs).min_timestamp;
+    for (topic, batch) in batches {
+        on_batch(&topic, relative_timestamps(batch, origin)?);
+    }
+    Ok(())
+}
+
+/// One batch per distinct topic, without the topic column and the columns that are
+/// null throughout for that topic.
+fn split_by_topic(batch: &RecordBatch) -> anyhow::Result<Vec<(String, RecordBatch)>> {
+    let topics = batch
+        .column_by_name(TOPIC_COLUMN)
+        .and_then(|c| c.as_any().downcast_ref::<StringArray>())
+        .ok_or_else(|| anyhow::anyhow!("The '{}' column is not a string column", TOPIC_COLUMN))?;
+    let names: BTreeSet<&str> = topics.iter().flatten().collect();
+
+    let mut split = Vec::new();
+    for name in names {
+        let mask = arrow::compute::kernels::cmp::eq(topics, &StringArray::new_scalar(name))?;
+        let rows = arrow::compute::filter_record_batch(batch, &mask)?;
+        let schema = rows.schema();
+        let keep: Vec<usize> = (0..rows.num_columns())
+            .filter(|&i| {
+                let column = rows.column(i);
+                schema.field(i).name() != TOPIC_COLUMN && column.null_count() < column.len()
+            })
+            .collect();
+        split.push((name.to_string(), rows.project(&keep)?));
+    }
+    Ok(split)
+}
+
+/// Replaces integer microsecond timestamps by seconds since `origin`.
+fn relative_timestamps(batch: RecordBatch, origin: Option<i64>) -> anyhow::Result<RecordBatch> {
+    let (Some(origin), Some(index)) = (origin, batch.schema().index_of("timestamp").ok()) else {
+        return Ok(batch);
+    };
+    if !matches!(
+        batch.column(index).data_type(),
+        DataType::Int64 | DataType::UInt64
+    ) {
+        return Ok(batch);
+    }
+    let column = arrow::compute::cast(batch.column(index), &DataType::Int64)?;
+    let Some(micros) = column.as_any().downcast_ref::<Int64Array>() else {
+        return Ok(batch);
+    };
+    let seconds = Float64Array::from_iter_values(
+        micros
+            .values()
+            .iter()
+            .map(|&us| (us - origin) as f64 / 1_000_000.0),
+    );
+
+    let mut fields: Vec<Field> = batch
+        .schema()
+        .fields()
+        .iter()
+        .map(|f| f.as_ref().clone())
+        .collect();
+    fields[index] = Field::new("timestamp", DataType::Float64, false);
+    let mut columns = batch.columns().to_vec();
+    columns[index] = Arc::new(seconds);
+    Ok(RecordBatch::try_new(
+        Arc::new(Schema::new(fields)),
+        columns,
+    )?)
+}
@@ -0,0 +1,166 @@
+use super::{DataStore, SessionMetadata, Snapshot};
+use crossbeam_channel::{Receiver, TryRecvError};
+use std::path::PathBuf;
+use std::time::{Duration, Instant, SystemTime};
+use tiplot_client::protocol::Compression;
+
+const RECOVERY_FILE: &str = "live_session.arrow";
+
+/// Periodically checkpoints live-streamed data to a recovery file, which is removed again
+/// once the data is saved or cleared. A recovery file found at startup therefore means
+/// the last session crashed or exited with live data that was never saved.
+pub struct Autosave {
+    dirty: bool,
+    /// Live data arrived that the user has not saved to a file.
+    unsaved: bool,
+    last_save: Instant,
+    /// Result of the checkpoint being written in the background, if any.
+    writing: Option<Receiver<anyhow::Result<PathBuf>>>,
+}
+
+impl Autosave {
+    pub fn new() -> Self {
+        Self {
+            dirty: false,
+            unsaved: false,
+            last_save: Instant::now(),
+            writing: None,
+        }
+    }
+
+    /// Records that live data arrived since the last checkpoint.
+    pub fn mark_dirty(&mut self) {
+        self.dirty = true;
+        self.unsaved = true;
+    }
+
+    /// Whether a checkpoint is due with an interval of `interval_secs`, 0 meaning off.
+    pub fn is_due(&self, interval_secs: u32) -> bool {
+        interval_secs > 0
+            && self.dirty
+            && self.writing.is_none()
+            && self.last_save.elapsed() >= Duration::from_secs(interval_secs as u64)
+    }
+
+    /// Snapshots the data and writes the checkpoint on a blocking task, so the UI does
+    /// not stall on encoding and disk writes. See [`Autosave::poll`] for the outcome.
+    pub fn save(
+        &mut self,
+        data_store: &DataStore,
+        session: &SessionMetadata,
+    ) -> anyhow::Result<()> {
+        self.last_save = Instant::now();
+        self.dirty = false;
+
+        let snapshot = data_store.snapshot(Some(session))?;
+        let (sender, receiver) = crossbeam_channel::bounded(1);
+        tokio::task::spawn_blocking(move || sender.send(write_checkpoint(&snapshot)).ok());
+        self.writing = Some(receiver);
+        Ok(())
+    }
+
+    /// Outcome of the background checkpoint, once it is written.
+    pub fn poll(&mut self) -> Option<anyhow::Result<PathBuf>> {
+        let result = match self.writing.as_ref()?.try_recv() {
+            Ok(result) => result,
+            Err(TryRecvError::Empty) => return None,
+            Err(TryRecvError::Disconnected) => Err(anyhow::anyhow!("Autosave task stopped")),
+        };
+        self.writing = None;
+        Some(result)
+    }
+
+    /// Blocks until the background checkpoint, if any, is written, so it cannot replace
+    /// the recovery file afterwards.
+    fn wait(&mut self) {
+        if let Some(Ok(Err(e))) = self.writing.take().map(|receiver| receiver.recv()) {
+            tracing::error!("Autosave failed: {}", e);
+        }
+    }
+
+    /// Drops the checkpoint, e.g. when the data is saved or cleared.
+    pub fn discard(&mut self) {
+        self.wait();
+        self.dirty = false;
+        self.unsaved = false;
+        let Some(path) = recovery_path() else {
+            return;
+        };
+        for path in [path.with_extension("arrow.partial"), path] {
+            if path.exists() {
+                if let Err(e) = std::fs::remove_file(&path) {
+                    tracing::error!("Failed to remove {}: {}", path.display(), e);
+                }
+            }
+        }
+    }
+
+    /// On exit, brings the checkpoint up to date if live data was never saved so it can
+    /// be recovered on the next start, and removes it otherwise.
+    pub fn finish(
+        &mut self,
+        data_store: &DataStore,
+        session: &SessionMetadata,
+        interval_secs: u32,
+    ) -> anyhow::Result<()> {
+        if interval_secs == 0 || !self.unsaved {
+            self.discard();
+            return Ok(());
+        }
+        self.wait();
+        if self.dirty {
+            self.dirty = false;
+            let path = write_checkpoint(&data_store.snapshot(Some(session))?)?;
+            tracing::info!("Kept unsaved live data in {}", path.display());
+        }
+        Ok(())
+    }
+}
+
+/// Writes a checkpoint next to the recovery file and moves it into place, so a crash
+/// while saving leaves the previous checkpoint intact.
+fn write_checkpoint(snapshot: &Snapshot) -> anyhow::Result<PathBuf> {
+    let path = recovery_path().ok_or_else(|| anyhow::anyhow!("No data directory"))?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let partial = path.with_extension("arrow.partial");
+    snapshot.write(&partial, Compression::Lz4)?;
+    std::fs::rename(&partial, &path)?;
+    Ok(path)
+}
+
+impl Default for Autosave {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn recovery_dir() -> Option<PathBuf> {
+    directories::ProjectDirs::from("io", "tilak", "TiPlot")
+        .map(|dirs| dirs.data_local_dir().join("recovery"))
+}
+
+fn recovery_path() -> Option<PathBuf> {
+    recovery_dir().map(|dir| dir.join(RECOVERY_FILE))
+}
+
+/// Checkpoint left behind by the last session, with its save time.
+pub fn pending_recovery() -> Option<(PathBuf, SystemTime)> {
+    let path = recovery_path()?;
+    let modified = std::fs::metadata(&path).ok()?.modified().ok()?;
+    Some((path, modified))
+}
+
+/// Moves the pending checkpoint to a file of its own so the next autosave cannot
+/// overwrite it, returning the new path to load.
+pub fn claim_recovery() -> anyhow::Result<PathBuf> {
+    let (path, modified) =
+        pending_recovery().ok_or_else(|| anyhow::anyhow!("No recovery file found"))?;
+    let stamp = modified
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    let target = path.with_file_name(format!("recovered_{}.arrow", stamp));
+    std::fs::rename(&path, &target)?;
+    Ok(target)
+}
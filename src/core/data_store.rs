@@ -1,43 +1,291 @@
 use arrow::array::{
-    Array, BooleanArray, Float32Array, Float64Array, Int16Array, Int32Array, Int64Array, Int8Array,
-    StringArray, UInt16Array, UInt32Array, UInt64Array, UInt8Array,
+    Array, BinaryArray, BooleanArray, Float32Array, Float64Array, Int16Array, Int32Array,
+    Int64Array, Int8Array, LargeBinaryArray, StringArray, UInt16Array, UInt32Array, UInt64Array,
+    UInt8Array,
 };
 use arrow::datatypes::{DataType, Field, Schema};
 use arrow::record_batch::RecordBatch;
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
 use std::fs::File;
 use std::hash::{Hash, Hasher};
 use std::io::{BufReader, BufWriter, Read, Write};
 use std::path::Path;
 use std::sync::Arc;
 
-#[derive(Clone)]
+use super::{
+    AngleUnit, ColumnMeta, ColumnSummary, ImageColumn, IngestFilter, InterpolationMode, LogInfo,
+    RangeStats, SessionMetadata, SignalFilter, SpillCache, Transform, SESSION_SEPARATOR,
+};
+use tiplot_client::protocol::Compression;
+
+const FILE_MAGIC: &[u8; 4] = b"TPLT";
+const FILE_VERSION: u32 = 3;
+/// First container version with a compression flag in front of every topic stream.
+const COMPRESSED_FILE_VERSION: u32 = 3;
+const MAX_RESAMPLED_POINTS: usize = 10_000_000;
+/// Intervals inspected per topic when estimating the minimum sample interval.
+const INTERVAL_SAMPLES: usize = 100;
+/// Fraction a topic may exceed its retention limit by before it is trimmed, so columns
+/// are not shifted on every batch.
+const RETENTION_SLACK: f32 = 0.1;
+
+/// How much live data to keep per topic.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RetentionPolicy {
+    Unlimited,
+    /// Keep samples within this many seconds of the newest one.
+    Duration(f32),
+    /// Keep at most this many samples.
+    Samples(usize),
+}
+
+/// Sample count, time span and average rate of a topic.
+#[derive(Clone, Copy, Debug)]
+pub struct TopicStats {
+    pub samples: usize,
+    pub duration: f32,
+    pub rate_hz: f32,
+}
+
 pub struct DataStore {
     pub topics: HashMap<String, HashMap<String, Vec<f32>>>,
+    /// Units, scale factors and descriptions, keyed like `topics`.
+    pub column_meta: HashMap<String, HashMap<String, ColumnMeta>>,
+    /// Version details and parameters from the header of the loaded log.
+    pub log_info: LogInfo,
+    /// Filtered copies of columns, keyed like `topics` but never saved.
+    pub derived: HashMap<String, HashMap<String, Vec<f32>>>,
+    /// Camera frames referenced by string or binary columns, keyed like `topics`. Only
+    /// kept in memory; saved files keep the numeric stand-ins of these columns.
+    pub images: HashMap<String, HashMap<String, ImageColumn>>,
+    /// Transforms whose outputs are stored as columns of the topic and extended on every
+    /// ingest. Saves leave the outputs out and keep the transforms in the session instead.
+    transforms: HashMap<String, Vec<Transform>>,
+
+    /// Chunk statistics of raw and derived columns, keyed like `topics`.
+    summaries: HashMap<String, HashMap<String, ColumnSummary>>,
+    sample_intervals: HashMap<String, f32>,
+
+    /// Topics evicted to disk keep their column names with empty vectors in `topics`.
+    spill: SpillCache,
+    memory_budget: Option<usize>,
+    last_access: HashMap<String, u64>,
+    access_tick: u64,
+
+    ingest_filter: IngestFilter,
 
     pub start_time: f32,
 }
 
+impl std::fmt::Debug for DataStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DataStore")
+            .field("topics", &self.topics.len())
+            .field("start_time", &self.start_time)
+            .finish_non_exhaustive()
+    }
+}
+
 impl DataStore {
     pub fn new() -> Self {
         Self {
             topics: HashMap::new(),
+            column_meta: HashMap::new(),
+            log_info: LogInfo::default(),
+            derived: HashMap::new(),
+            images: HashMap::new(),
+            transforms: HashMap::new(),
+            summaries: HashMap::new(),
+            sample_intervals: HashMap::new(),
+            spill: SpillCache::new(),
+            memory_budget: None,
+            last_access: HashMap::new(),
+            access_tick: 0,
+            ingest_filter: IngestFilter::default(),
             start_time: 0.0,
         }
     }
 
+    pub fn set_ingest_filter(&mut self, filter: &IngestFilter) {
+        if self.ingest_filter != *filter {
+            self.ingest_filter = filter.clone();
+        }
+    }
+
     pub fn ingest(&mut self, topic: String, batch: RecordBatch) {
+        let Some(batch) = self.ingest_filter.apply(&topic, batch) else {
+            return;
+        };
+
+        self.restore_topic(&topic);
+        self.last_access.insert(topic.clone(), self.access_tick);
+
         let schema = batch.schema();
 
         let time_offset = self.start_time;
 
-        let entry = self.topics.entry(topic).or_default();
+        let entry = self.topics.entry(topic.clone()).or_default();
         for (i, field) in schema.fields().iter().enumerate() {
             let col_name = field.name();
             let column = batch.column(i);
 
+            if !entry.contains_key(col_name) {
+                Self::import_field_meta(&mut self.column_meta, &topic, field);
+            }
+
             Self::convert_and_append_static(column, col_name, time_offset, entry);
         }
+
+        let rows = batch.num_rows();
+        let batch_times = entry
+            .get("timestamp")
+            .filter(|times| times.len() >= rows)
+            .map(|times| &times[times.len() - rows..]);
+        for (i, field) in schema.fields().iter().enumerate() {
+            let is_image = matches!(
+                field.data_type(),
+                DataType::Utf8 | DataType::Binary | DataType::LargeBinary
+            );
+            if let (true, Some(times)) = (is_image, batch_times) {
+                let images = self.images.entry(topic.clone()).or_default();
+                let column = images.entry(field.name().clone()).or_default();
+                column.extend_from(batch.column(i).as_ref(), times);
+                if column.frames.is_empty() {
+                    images.remove(field.name());
+                }
+            }
+        }
+        self.images.retain(|_, cols| !cols.is_empty());
+
+        self.update_transforms(&topic);
+        self.update_summaries(&topic);
+    }
+
+    /// Adds a transform to a topic and computes its output columns for the existing data.
+    pub fn add_transform(&mut self, topic: &str, transform: Transform) -> anyhow::Result<()> {
+        self.restore_topic(topic);
+
+        let cols = self
+            .topics
+            .get(topic)
+            .ok_or_else(|| anyhow::anyhow!("Topic '{}' not found", topic))?;
+        if let Some(missing) = transform
+            .inputs()
+            .into_iter()
+            .find(|c| !cols.contains_key(*c))
+        {
+            return Err(anyhow::anyhow!("Column '{}/{}' not found", topic, missing));
+        }
+        if let Some((existing, _)) = transform
+            .outputs()
+            .iter()
+            .find(|(name, _)| cols.contains_key(*name))
+        {
+            return Err(anyhow::anyhow!(
+                "Column '{}/{}' already exists",
+                topic,
+                existing
+            ));
+        }
+
+        for (name, unit) in transform.outputs() {
+            self.column_meta_mut(topic, name).unit = unit.to_string();
+        }
+        self.transforms
+            .entry(topic.to_string())
+            .or_default()
+            .push(transform);
+        self.update_transforms(topic);
+        self.update_summaries(topic);
+
+        Ok(())
+    }
+
+    /// Transforms by topic, saved with the session so their outputs can be recomputed.
+    pub fn transforms(&self) -> &HashMap<String, Vec<Transform>> {
+        &self.transforms
+    }
+
+    /// Whether `col` of `topic` is computed by a transform rather than logged.
+    pub fn is_transform_output(&self, topic: &str, col: &str) -> bool {
+        self.transforms.get(topic).is_some_and(|list| {
+            list.iter()
+                .any(|t| t.outputs().iter().any(|(name, _)| *name == col))
+        })
+    }
+
+    pub fn has_transform(&self, topic: &str, transform: &Transform) -> bool {
+        self.transforms
+            .get(topic)
+            .is_some_and(|list| list.iter().any(|t| t.same_as(transform)))
+    }
+
+    fn update_transforms(&mut self, topic: &str) {
+        let (Some(transforms), Some(cols)) =
+            (self.transforms.get_mut(topic), self.topics.get_mut(topic))
+        else {
+            return;
+        };
+
+        for transform in transforms {
+            transform.extend(cols);
+        }
+    }
+
+    /// Extends the cached chunk statistics and sample interval of a topic to cover
+    /// any samples appended since the last update.
+    fn update_summaries(&mut self, topic: &str) {
+        let Some(cols) = self.topics.get(topic) else {
+            return;
+        };
+
+        let summaries = self.summaries.entry(topic.to_string()).or_default();
+        for (col_name, values) in cols {
+            summaries
+                .entry(col_name.clone())
+                .or_default()
+                .update(values);
+        }
+
+        if let Some(timestamps) = cols.get("timestamp") {
+            let samples_to_check = timestamps.len().min(INTERVAL_SAMPLES);
+            let min_interval = (1..samples_to_check)
+                .map(|i| (timestamps[i] - timestamps[i - 1]).abs())
+                .filter(|&interval| interval > 0.0)
+                .fold(f32::MAX, f32::min);
+
+            if min_interval != f32::MAX {
+                self.sample_intervals
+                    .insert(topic.to_string(), min_interval);
+            }
+        }
+    }
+
+    /// Picks up unit and description annotations from Arrow field metadata.
+    fn import_field_meta(
+        column_meta: &mut HashMap<String, HashMap<String, ColumnMeta>>,
+        topic: &str,
+        field: &Field,
+    ) {
+        let metadata = field.metadata();
+        let unit = metadata.get("unit").or_else(|| metadata.get("units"));
+        let description = metadata.get("description");
+        if unit.is_none() && description.is_none() {
+            return;
+        }
+
+        let meta = column_meta
+            .entry(topic.to_string())
+            .or_default()
+            .entry(field.name().clone())
+            .or_default();
+        if let Some(unit) = unit.filter(|_| meta.unit.is_empty()) {
+            meta.unit = unit.clone();
+        }
+        if let Some(description) = description.filter(|_| meta.description.is_empty()) {
+            meta.description = description.clone();
+        }
     }
 
     fn convert_and_append_static(
@@ -86,6 +334,11 @@ impl DataStore {
             }
         } else if let Some(arr) = column.as_any().downcast_ref::<BooleanArray>() {
             target.extend(arr.values().iter().map(|v| if v { 1.0 } else { 0.0 }));
+        } else if let Some(arr) = column.as_any().downcast_ref::<BinaryArray>() {
+            // Payload sizes, so images and other blobs stay aligned with the timestamps.
+            target.extend(arr.iter().map(|v| v.map_or(f32::NAN, |b| b.len() as f32)));
+        } else if let Some(arr) = column.as_any().downcast_ref::<LargeBinaryArray>() {
+            target.extend(arr.iter().map(|v| v.map_or(f32::NAN, |b| b.len() as f32)));
         } else if let Some(arr) = column.as_any().downcast_ref::<StringArray>() {
             target.extend(arr.iter().map(|v| {
                 v.map(|s| {
@@ -96,102 +349,132 @@ impl DataStore {
                 .unwrap_or(f32::NAN)
             }));
         } else {
-            eprintln!(
-                "Warning: Unhandled Arrow type for column '{}': {:?}",
+            tracing::warn!(
+                "Unhandled Arrow type for column '{}': {:?}",
                 col_name,
                 column.data_type()
             );
         }
     }
 
-    pub fn save_to_arrow<P: AsRef<Path>>(&self, path: P) -> anyhow::Result<()> {
-        use arrow::ipc::writer::StreamWriter;
-
-        if self.topics.is_empty() {
-            return Err(anyhow::anyhow!("No data to save"));
-        }
-
-        let file = File::create(path)?;
-        let mut writer = BufWriter::new(file);
-
-        let valid_topics: Vec<_> = self
+    /// Topics holding data, sorted. Spilled topics count even though their columns are
+    /// empty in memory.
+    pub fn saved_topics(&self) -> Vec<&String> {
+        let mut topics: Vec<_> = self
             .topics
             .iter()
             .filter(|(topic_name, columns)| {
                 if columns.is_empty() {
-                    println!("  Skipping empty topic: {}", topic_name);
+                    tracing::debug!("Skipping empty topic: {}", topic_name);
                     return false;
                 }
 
-                let has_data = columns.values().any(|v| !v.is_empty());
+                let has_data =
+                    self.spill.contains(topic_name) || columns.values().any(|v| !v.is_empty());
                 if !has_data {
-                    println!("  Skipping topic with no data: {}", topic_name);
+                    tracing::debug!("Skipping topic with no data: {}", topic_name);
                     return false;
                 }
 
                 true
             })
+            .map(|(topic_name, _)| topic_name)
             .collect();
+        topics.sort();
+        topics
+    }
 
-        writer.write_all(&(valid_topics.len() as u32).to_le_bytes())?;
-        writer.write_all(&self.start_time.to_le_bytes())?;
-
-        for (topic_name, columns) in valid_topics {
-            let mut column_names: Vec<_> = columns.keys().cloned().collect();
-            column_names.sort();
-            let mut fields = Vec::new();
-            let mut arrays: Vec<Arc<dyn Array>> = Vec::new();
+    /// The non-empty logged columns of `topic` as one batch of `Float32` columns sorted by
+    /// name, read back from disk if the topic was spilled. Transform outputs are left out,
+    /// as the session stores the transforms themselves.
+    pub fn topic_batch(&self, topic_name: &str) -> anyhow::Result<RecordBatch> {
+        let columns = if self.spill.contains(topic_name) {
+            Cow::Owned(self.spill.read(topic_name)?)
+        } else {
+            Cow::Borrowed(
+                self.topics
+                    .get(topic_name)
+                    .ok_or_else(|| anyhow::anyhow!("Unknown topic '{}'", topic_name))?,
+            )
+        };
 
-            for col_name in &column_names {
-                if let Some(data) = columns.get(col_name) {
-                    if data.is_empty() {
-                        continue;
-                    }
+        let mut column_names: Vec<_> = columns
+            .keys()
+            .filter(|col| !self.is_transform_output(topic_name, col))
+            .cloned()
+            .collect();
+        column_names.sort();
+        let mut fields = Vec::new();
+        let mut arrays: Vec<Arc<dyn Array>> = Vec::new();
 
-                    fields.push(Field::new(col_name.as_str(), DataType::Float32, false));
-                    arrays.push(Arc::new(Float32Array::from(data.clone())));
+        for col_name in &column_names {
+            if let Some(data) = columns.get(col_name) {
+                if data.is_empty() {
+                    continue;
                 }
-            }
 
-            if arrays.is_empty() {
-                println!(
-                    "    ERROR: No valid arrays for topic '{}', this shouldn't happen!",
-                    topic_name
-                );
-                return Err(anyhow::anyhow!(
-                    "Topic '{}' passed validation but has no arrays",
-                    topic_name
-                ));
+                fields.push(Field::new(col_name.as_str(), DataType::Float32, false));
+                arrays.push(Arc::new(Float32Array::from(data.clone())));
             }
+        }
 
-            let schema = Arc::new(Schema::new(fields));
-            let batch = RecordBatch::try_new(schema.clone(), arrays)?;
+        if arrays.is_empty() {
+            tracing::error!(
+                "No valid arrays for topic '{}', this shouldn't happen!",
+                topic_name
+            );
+            return Err(anyhow::anyhow!(
+                "Topic '{}' passed validation but has no arrays",
+                topic_name
+            ));
+        }
 
-            let topic_bytes = topic_name.as_bytes();
-            writer.write_all(&(topic_bytes.len() as u32).to_le_bytes())?;
-            writer.write_all(topic_bytes)?;
+        Ok(RecordBatch::try_new(Arc::new(Schema::new(fields)), arrays)?)
+    }
 
-            let mut stream_buffer = Vec::new();
-            {
-                let mut stream_writer = StreamWriter::try_new(&mut stream_buffer, &schema)?;
-                stream_writer.write(&batch)?;
-                stream_writer.finish()?;
-            }
+    pub fn save_to_arrow<P: AsRef<Path>>(
+        &self,
+        path: P,
+        session: Option<&SessionMetadata>,
+        compression: Compression,
+    ) -> anyhow::Result<()> {
+        self.snapshot(session)?.write(path, compression)
+    }
 
-            writer.write_all(&(stream_buffer.len() as u64).to_le_bytes())?;
-            writer.write_all(&stream_buffer)?;
+    /// Copies what [`DataStore::save_to_arrow`] writes, so it can be written without
+    /// holding on to the store.
+    pub fn snapshot(&self, session: Option<&SessionMetadata>) -> anyhow::Result<Snapshot> {
+        if self.topics.is_empty() {
+            return Err(anyhow::anyhow!("No data to save"));
         }
 
-        writer.flush()?;
-
-        Ok(())
+        let topics = self
+            .saved_topics()
+            .into_iter()
+            .map(|topic| Ok((topic.clone(), self.topic_batch(topic)?)))
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Snapshot {
+            start_time: self.start_time,
+            topics,
+            session: session.filter(|s| !s.is_empty()).cloned(),
+        })
     }
 
-    pub fn load_from_arrow<P: AsRef<Path>>(&mut self, path: P) -> anyhow::Result<()> {
+    /// Reads a data file, handing each decoded batch to `on_batch` and reporting
+    /// `(bytes_read, file_size)` to `on_progress` after every topic. Returns the embedded
+    /// session metadata if the file has any. Files written before the container was
+    /// versioned are still accepted.
+    pub fn read_arrow_file<P: AsRef<Path>>(
+        path: P,
+        mut on_batch: impl FnMut(&str, RecordBatch),
+        mut on_progress: impl FnMut(u64, u64),
+    ) -> anyhow::Result<Option<SessionMetadata>> {
         use arrow::ipc::reader::StreamReader;
 
-        self.topics.clear();
-        self.start_time = 0.0;
+        if super::arrow_files::is_standard_arrow(path.as_ref()) {
+            super::arrow_files::read_standard_arrow(path.as_ref(), on_batch, on_progress)?;
+            return Ok(None);
+        }
 
         let file = File::open(&path)?;
         let file_size = file.metadata()?.len();
@@ -200,12 +483,31 @@ impl DataStore {
 
         let mut buf = [0u8; 4];
         reader.read_exact(&mut buf)?;
+
+        let mut bytes_read = 4u64;
+        let versioned = &buf == FILE_MAGIC;
+
+        let mut version = 0;
+        if versioned {
+            reader.read_exact(&mut buf)?;
+            bytes_read += 4;
+            version = u32::from_le_bytes(buf);
+            if version > FILE_VERSION {
+                return Err(anyhow::anyhow!(
+                    "File format version {} is newer than supported version {}",
+                    version,
+                    FILE_VERSION
+                ));
+            }
+
+            reader.read_exact(&mut buf)?;
+            bytes_read += 4;
+        }
         let num_topics = u32::from_le_bytes(buf) as usize;
 
         let mut buf = [0u8; 4];
         reader.read_exact(&mut buf)?;
-
-        let mut bytes_read = 8u64; // 4 bytes for topic count + 4 bytes for start_time
+        bytes_read += 4; // start_time
 
         for topic_idx in 0..num_topics {
             let mut buf = [0u8; 4];
@@ -220,6 +522,14 @@ impl DataStore {
             })?;
             bytes_read += 4;
             let name_len = u32::from_le_bytes(buf) as usize;
+            if name_len as u64 > file_size.saturating_sub(bytes_read) {
+                return Err(anyhow::anyhow!(
+                    "Topic name length {} of topic {}/{} would exceed file size. File appears corrupted.",
+                    name_len,
+                    topic_idx + 1,
+                    num_topics
+                ));
+            }
             let mut name_buf = vec![0u8; name_len];
             reader.read_exact(&mut name_buf).map_err(|e| {
                 anyhow::anyhow!(
@@ -235,6 +545,15 @@ impl DataStore {
             let topic_name = String::from_utf8(name_buf)
                 .map_err(|e| anyhow::anyhow!("Invalid UTF-8 in topic name: {}", e))?;
 
+            let mut compression = Compression::None;
+            if version >= COMPRESSED_FILE_VERSION {
+                let mut flag = [0u8; 1];
+                reader.read_exact(&mut flag)?;
+                bytes_read += 1;
+                compression = Compression::from_flag(flag[0])
+                    .map_err(|e| anyhow::anyhow!("Topic '{}': {}", topic_name, e))?;
+            }
+
             let mut buf = [0u8; 8];
             reader.read_exact(&mut buf)
             .map_err(|e| anyhow::anyhow!(
@@ -246,7 +565,7 @@ impl DataStore {
             bytes_read += 8;
             let stream_size = u64::from_le_bytes(buf) as usize;
 
-            if bytes_read + stream_size as u64 > file_size {
+            if stream_size as u64 > file_size.saturating_sub(bytes_read) {
                 return Err(anyhow::anyhow!(
                     "Stream size {} would exceed file size. File appears corrupted.\n\
                  Topic: '{}', current position: {}, file size: {}",
@@ -269,6 +588,9 @@ impl DataStore {
             })?;
             bytes_read += stream_size as u64;
 
+            let stream_data = compression.decompress(stream_data).map_err(|e| {
+                anyhow::anyhow!("Failed to decompress topic '{}': {}", topic_name, e)
+            })?;
             let cursor = std::io::Cursor::new(stream_data);
             let stream_reader = StreamReader::try_new(cursor, None).map_err(|e| {
                 anyhow::anyhow!(
@@ -278,8 +600,7 @@ impl DataStore {
                 )
             })?;
 
-            let mut batch_count = 0;
-            for batch_result in stream_reader {
+            for (batch_count, batch_result) in stream_reader.enumerate() {
                 let batch = batch_result.map_err(|e| {
                     anyhow::anyhow!(
                         "Failed to read batch {} for topic '{}': {}",
@@ -288,34 +609,742 @@ impl DataStore {
                         e
                     )
                 })?;
-                let schema = batch.schema();
 
-                let entry = self.topics.entry(topic_name.clone()).or_default();
+                on_batch(&topic_name, batch);
+            }
+
+            on_progress(bytes_read, file_size);
+        }
+
+        let mut session = None;
+
+        if versioned {
+            let mut buf = [0u8; 8];
+            reader
+                .read_exact(&mut buf)
+                .map_err(|e| anyhow::anyhow!("Failed to read session metadata length: {}", e))?;
+            bytes_read += 8;
+            let meta_len = u64::from_le_bytes(buf) as usize;
+            if meta_len as u64 > file_size.saturating_sub(bytes_read) {
+                return Err(anyhow::anyhow!(
+                    "Session metadata length {} would exceed file size. File appears corrupted.\n\
+                     Current position: {}, file size: {}",
+                    meta_len,
+                    bytes_read,
+                    file_size
+                ));
+            }
 
-                for (i, field) in schema.fields().iter().enumerate() {
-                    let col_name = field.name();
-                    let column = batch.column(i);
+            if meta_len > 0 {
+                let mut meta_json = vec![0u8; meta_len];
+                reader.read_exact(&mut meta_json).map_err(|e| {
+                    anyhow::anyhow!(
+                        "Failed to read session metadata ({} bytes): {}",
+                        meta_len,
+                        e
+                    )
+                })?;
+                bytes_read += meta_len as u64;
 
-                    if let Some(arr) = column.as_any().downcast_ref::<Float32Array>() {
-                        let target = entry.entry(col_name.to_string()).or_default();
-                        target.extend(arr.values());
+                match serde_json::from_slice::<SessionMetadata>(&meta_json) {
+                    Ok(meta) => session = Some(meta),
+                    Err(e) => {
+                        tracing::warn!("Ignoring unreadable session metadata: {}", e);
                     }
                 }
-                batch_count += 1;
             }
         }
 
         if bytes_read != file_size {
-            println!("  WARNING: File has {} extra bytes", file_size - bytes_read);
+            tracing::warn!("File has {} extra bytes", file_size - bytes_read);
         }
 
-        self.start_time = 0.0;
+        Ok(session)
+    }
 
-        Ok(())
+    pub fn column_meta(&self, topic: &str, col: &str) -> Option<&ColumnMeta> {
+        self.column_meta.get(topic)?.get(col)
+    }
+
+    pub fn column_meta_mut(&mut self, topic: &str, col: &str) -> &mut ColumnMeta {
+        self.column_meta
+            .entry(topic.to_string())
+            .or_default()
+            .entry(col.to_string())
+            .or_default()
+    }
+
+    /// Drops metadata entries that carry no information.
+    pub fn prune_column_meta(&mut self) {
+        for cols in self.column_meta.values_mut() {
+            cols.retain(|_, meta| !meta.is_default());
+        }
+        self.column_meta.retain(|_, cols| !cols.is_empty());
     }
 
     pub fn get_column(&self, topic: &str, col: &str) -> Option<&Vec<f32>> {
-        self.topics.get(topic)?.get(col)
+        self.topics
+            .get(topic)?
+            .get(col)
+            .or_else(|| self.derived.get(topic)?.get(col))
+    }
+
+    /// Adds an empty topic with the given timestamps, for columns computed by scripts.
+    pub fn insert_topic(&mut self, topic: &str, times: Vec<f32>) -> anyhow::Result<()> {
+        if self.topics.contains_key(topic) {
+            return Err(anyhow::anyhow!("Topic '{}' already exists", topic));
+        }
+        let mut columns = HashMap::new();
+        columns.insert("timestamp".to_string(), times);
+        self.topics.insert(topic.to_string(), columns);
+        Ok(())
+    }
+
+    /// Adds or replaces a column of `topic`, one value per timestamp.
+    pub fn set_column(&mut self, topic: &str, col: &str, values: Vec<f32>) -> anyhow::Result<()> {
+        if col == "timestamp" {
+            return Err(anyhow::anyhow!("The timestamp column cannot be replaced"));
+        }
+        self.restore_topic(topic);
+
+        let cols = self
+            .topics
+            .get_mut(topic)
+            .ok_or_else(|| anyhow::anyhow!("Topic '{}' not found", topic))?;
+        let samples = cols.get("timestamp").map_or(0, Vec::len);
+        if values.len() != samples {
+            return Err(anyhow::anyhow!(
+                "Expected {} values for '{}/{}', got {}",
+                samples,
+                topic,
+                col,
+                values.len()
+            ));
+        }
+        cols.insert(col.to_string(), values);
+        // Summaries only ever grow, so those of the replaced values are dropped outright.
+        let summaries = self.summaries.entry(topic.to_string()).or_default();
+        summaries.remove(col);
+        if let Some(derived) = self.derived.get_mut(topic) {
+            let prefix = format!("{} [", col);
+            derived.retain(|name, _| {
+                let stale = name.starts_with(&prefix);
+                if stale {
+                    summaries.remove(name);
+                }
+                !stale
+            });
+        }
+
+        self.update_summaries(topic);
+        Ok(())
+    }
+
+    /// Timestamps of the rows of `topic` where any column differs from the previous row.
+    pub fn change_times(&self, topic: &str) -> Vec<f32> {
+        let Some(times) = self.get_column(topic, "timestamp") else {
+            return Vec::new();
+        };
+        let columns: Vec<&Vec<f32>> = self
+            .get_columns(topic)
+            .into_iter()
+            .filter_map(|col| self.get_column(topic, col))
+            .collect();
+
+        (1..times.len())
+            .filter(|&row| {
+                columns
+                    .iter()
+                    .any(|col| match (col.get(row - 1), col.get(row)) {
+                        (Some(a), Some(b)) => a != b && !(a.is_nan() && b.is_nan()),
+                        _ => false,
+                    })
+            })
+            .map(|row| times[row])
+            .collect()
+    }
+
+    /// Min/max/mean of `col` over the sample index range `start..end`, served from the
+    /// chunk summaries where available.
+    pub fn column_range_stats(
+        &self,
+        topic: &str,
+        col: &str,
+        start: usize,
+        end: usize,
+    ) -> Option<RangeStats> {
+        let values = self.get_column(topic, col)?;
+
+        match self.summaries.get(topic).and_then(|s| s.get(col)) {
+            Some(summary) => summary.range_stats(values, start, end),
+            None => ColumnSummary::default().range_stats(values, start, end),
+        }
+    }
+
+    /// Sample count, duration and mean rate of a topic's timestamps.
+    pub fn topic_stats(&self, topic: &str) -> Option<TopicStats> {
+        let timestamps = self.get_column(topic, "timestamp")?;
+        let samples = timestamps.len();
+        let duration = match (timestamps.first(), timestamps.last()) {
+            (Some(first), Some(last)) => last - first,
+            _ => 0.0,
+        };
+        let rate_hz = if duration > 0.0 {
+            (samples - 1) as f32 / duration
+        } else {
+            0.0
+        };
+
+        Some(TopicStats {
+            samples,
+            duration,
+            rate_hz,
+        })
+    }
+
+    /// Smallest interval between consecutive samples across all topics, estimated from
+    /// the start of each topic.
+    pub fn min_sample_interval(&self) -> Option<f32> {
+        self.sample_intervals.values().copied().reduce(f32::min)
+    }
+
+    pub fn derived_column_name(col: &str, filter: &SignalFilter) -> String {
+        format!("{} [{}]", col, filter.label())
+    }
+
+    pub fn unwrapped_column_name(col: &str, unit: AngleUnit) -> String {
+        format!("{} [unwrap {}]", col, unit.label())
+    }
+
+    /// Computes the unwrapped copy of an angle column if it is missing or its source has
+    /// grown. Returns the derived column name when it was (re)computed.
+    pub fn ensure_unwrapped(&mut self, topic: &str, col: &str, unit: AngleUnit) -> Option<String> {
+        let values = self.topics.get(topic)?.get(col)?;
+        let name = Self::unwrapped_column_name(col, unit);
+
+        let up_to_date = self
+            .derived
+            .get(topic)
+            .and_then(|d| d.get(&name))
+            .is_some_and(|d| d.len() == values.len());
+        if up_to_date {
+            return None;
+        }
+
+        let unwrapped = unit.unwrap(values);
+        self.insert_derived(topic, name.clone(), unwrapped);
+
+        Some(name)
+    }
+
+    /// Computes the filtered copy of a column if it is missing or its source has grown.
+    /// The source may itself be a derived column. Returns the derived column name when it
+    /// was (re)computed.
+    pub fn ensure_derived(
+        &mut self,
+        topic: &str,
+        col: &str,
+        filter: &SignalFilter,
+    ) -> Option<String> {
+        let times = self.get_column(topic, "timestamp")?;
+        let values = self.get_column(topic, col)?;
+
+        let name = Self::derived_column_name(col, filter);
+        let expected_len = times.len().min(values.len());
+
+        let up_to_date = self
+            .derived
+            .get(topic)
+            .and_then(|d| d.get(&name))
+            .is_some_and(|d| d.len() == expected_len);
+        if up_to_date {
+            return None;
+        }
+
+        let filtered = filter.apply(times, values);
+        self.insert_derived(topic, name.clone(), filtered);
+
+        Some(name)
+    }
+
+    /// Stores a derived column with a fresh chunk summary, so that value ranges of
+    /// filtered and unwrapped traces are looked up as quickly as those of raw columns.
+    fn insert_derived(&mut self, topic: &str, name: String, values: Vec<f32>) {
+        let mut summary = ColumnSummary::default();
+        summary.update(&values);
+        self.summaries
+            .entry(topic.to_string())
+            .or_default()
+            .insert(name.clone(), summary);
+        self.derived
+            .entry(topic.to_string())
+            .or_default()
+            .insert(name, values);
+    }
+
+    /// Drops the derived columns `keep` returns false for, with their summaries.
+    pub fn retain_derived(&mut self, mut keep: impl FnMut(&str, &str) -> bool) {
+        for (topic, cols) in self.derived.iter_mut() {
+            let summaries = self.summaries.get_mut(topic);
+            let mut removed = Vec::new();
+            cols.retain(|name, _| {
+                let kept = keep(topic, name);
+                if !kept {
+                    removed.push(name.clone());
+                }
+                kept
+            });
+            if let Some(summaries) = summaries {
+                for name in removed {
+                    summaries.remove(&name);
+                }
+            }
+        }
+    }
+
+    /// Resamples the given columns onto a shared uniform time base covering the span where
+    /// all of them have data, and stores the result as a new topic named `name`.
+    /// Columns are named `<topic>/<column>` in the new topic.
+    pub fn resample_topics(
+        &mut self,
+        name: &str,
+        sources: &[(String, String)],
+        rate_hz: f32,
+        mode: InterpolationMode,
+    ) -> anyhow::Result<()> {
+        if sources.is_empty() {
+            return Err(anyhow::anyhow!("No columns selected for resampling"));
+        }
+        if rate_hz <= 0.0 {
+            return Err(anyhow::anyhow!("Resample rate must be positive"));
+        }
+        if name.trim().is_empty() {
+            return Err(anyhow::anyhow!("Resampled topic name cannot be empty"));
+        }
+        if self.topics.contains_key(name) {
+            return Err(anyhow::anyhow!("Topic '{}' already exists", name));
+        }
+
+        let mut series = Vec::with_capacity(sources.len());
+        let mut start = f32::MIN;
+        let mut end = f32::MAX;
+
+        for (topic, col) in sources {
+            let (Some(times), Some(values)) = (
+                self.get_column(topic, "timestamp"),
+                self.get_column(topic, col),
+            ) else {
+                return Err(anyhow::anyhow!("Column '{}/{}' not found", topic, col));
+            };
+            if times.is_empty() || values.is_empty() {
+                return Err(anyhow::anyhow!("Column '{}/{}' has no data", topic, col));
+            }
+
+            start = start.max(times[0]);
+            end = end.min(times[times.len() - 1]);
+            series.push((format!("{}/{}", topic, col), times, values));
+        }
+
+        if end <= start {
+            return Err(anyhow::anyhow!("Selected columns do not overlap in time"));
+        }
+
+        let step = 1.0 / rate_hz as f64;
+        let count = ((end - start) as f64 / step).floor() as usize + 1;
+        if count > MAX_RESAMPLED_POINTS {
+            return Err(anyhow::anyhow!(
+                "Resampling would produce {} points (limit {}), lower the rate",
+                count,
+                MAX_RESAMPLED_POINTS
+            ));
+        }
+
+        let grid: Vec<f32> = (0..count)
+            .map(|i| (start as f64 + i as f64 * step) as f32)
+            .collect();
+
+        let mut columns = HashMap::new();
+        for (col_name, times, values) in series {
+            let resampled = grid
+                .iter()
+                .map(|&t| {
+                    mode.sample(times, values, t).unwrap_or_else(|| {
+                        let idx = times.partition_point(|&x| x < t).min(values.len() - 1);
+                        values[idx]
+                    })
+                })
+                .collect();
+            columns.insert(col_name, resampled);
+        }
+        columns.insert("timestamp".to_string(), grid);
+
+        self.topics.insert(name.to_string(), columns);
+        self.update_summaries(name);
+
+        Ok(())
+    }
+
+    /// Drops the oldest samples of a topic that fall outside `policy`. Cached summaries and
+    /// derived columns of the topic are rebuilt. Returns true if anything was removed.
+    pub fn apply_retention(&mut self, topic: &str, policy: RetentionPolicy) -> bool {
+        let Some(cols) = self.topics.get_mut(topic) else {
+            return false;
+        };
+        let Some(timestamps) = cols.get("timestamp") else {
+            return false;
+        };
+
+        let len = timestamps.len();
+        let cut = match policy {
+            RetentionPolicy::Unlimited => 0,
+            RetentionPolicy::Samples(max) => {
+                let slack = ((max as f32 * RETENTION_SLACK) as usize).max(1);
+                if len > max + slack {
+                    len - max
+                } else {
+                    0
+                }
+            }
+            RetentionPolicy::Duration(seconds) => {
+                let Some(&last) = timestamps.last() else {
+                    return false;
+                };
+                let cut = timestamps.partition_point(|&t| t < last - seconds);
+                if cut as f32 > len as f32 * RETENTION_SLACK {
+                    cut
+                } else {
+                    0
+                }
+            }
+        };
+
+        if cut == 0 {
+            return false;
+        }
+
+        let oldest = cols
+            .get("timestamp")
+            .and_then(|times| times.get(cut).copied());
+        for values in cols.values_mut() {
+            values.drain(..cut.min(values.len()));
+        }
+        if let (Some(images), Some(oldest)) = (self.images.get_mut(topic), oldest) {
+            for column in images.values_mut() {
+                column.trim_before(oldest);
+            }
+        }
+
+        self.summaries.remove(topic);
+        self.derived.remove(topic);
+        self.update_summaries(topic);
+
+        true
+    }
+
+    /// Names of all raw and derived columns of a topic, excluding the timestamp.
+    pub fn all_columns(&self, topic: &str) -> Vec<String> {
+        self.topics
+            .get(topic)
+            .into_iter()
+            .chain(self.derived.get(topic))
+            .flat_map(|cols| cols.keys())
+            .filter(|name| *name != "timestamp")
+            .cloned()
+            .collect()
+    }
+
+    pub fn rename_topic(&mut self, from: &str, to: &str) -> anyhow::Result<()> {
+        let to = to.trim();
+        if to.is_empty() {
+            return Err(anyhow::anyhow!("Topic name cannot be empty"));
+        }
+        if self.topics.contains_key(to) {
+            return Err(anyhow::anyhow!("Topic '{}' already exists", to));
+        }
+        self.restore_topic(from);
+
+        let cols = self
+            .topics
+            .remove(from)
+            .ok_or_else(|| anyhow::anyhow!("Topic '{}' not found", from))?;
+        self.topics.insert(to.to_string(), cols);
+
+        if let Some(meta) = self.column_meta.remove(from) {
+            self.column_meta.insert(to.to_string(), meta);
+        }
+        if let Some(transforms) = self.transforms.remove(from) {
+            self.transforms.insert(to.to_string(), transforms);
+        }
+        // Derived columns are recomputed under the new name on next use.
+        self.derived.remove(from);
+        if let Some(images) = self.images.remove(from) {
+            self.images.insert(to.to_string(), images);
+        }
+        if let Some(summaries) = self.summaries.remove(from) {
+            self.summaries.insert(to.to_string(), summaries);
+        }
+        if let Some(interval) = self.sample_intervals.remove(from) {
+            self.sample_intervals.insert(to.to_string(), interval);
+        }
+        if let Some(access) = self.last_access.remove(from) {
+            self.last_access.insert(to.to_string(), access);
+        }
+
+        Ok(())
+    }
+
+    pub fn delete_topic(&mut self, topic: &str) {
+        self.topics.remove(topic);
+        self.column_meta.remove(topic);
+        self.derived.remove(topic);
+        self.images.remove(topic);
+        self.transforms.remove(topic);
+        self.summaries.remove(topic);
+        self.sample_intervals.remove(topic);
+        self.last_access.remove(topic);
+        self.spill.remove(topic);
+    }
+
+    pub fn delete_column(&mut self, topic: &str, col: &str) -> anyhow::Result<()> {
+        if col == "timestamp" {
+            return Err(anyhow::anyhow!("The timestamp column cannot be deleted"));
+        }
+        self.restore_topic(topic);
+
+        self.topics
+            .get_mut(topic)
+            .and_then(|cols| cols.remove(col))
+            .ok_or_else(|| anyhow::anyhow!("Column '{}/{}' not found", topic, col))?;
+
+        if let Some(meta) = self.column_meta.get_mut(topic) {
+            meta.remove(col);
+        }
+        if let Some(summaries) = self.summaries.get_mut(topic) {
+            summaries.remove(col);
+        }
+        if let Some(derived) = self.derived.get_mut(topic) {
+            let prefix = format!("{} [", col);
+            derived.retain(|name, _| !name.starts_with(&prefix));
+        }
+        // Outputs already computed stay as plain columns once a transform loses a column.
+        if let Some(transforms) = self.transforms.get_mut(topic) {
+            transforms.retain(|t| {
+                !t.inputs().contains(&col) && t.outputs().iter().all(|(name, _)| *name != col)
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Interleaves the samples of `source` into `target` in timestamp order and removes
+    /// `source`. Columns present in only one of the topics are padded with NaN.
+    pub fn merge_topics(&mut self, source: &str, target: &str) -> anyhow::Result<()> {
+        if source == target {
+            return Err(anyhow::anyhow!("Cannot merge a topic into itself"));
+        }
+        self.restore_topic(source);
+        self.restore_topic(target);
+
+        let (Some(src), Some(dst)) = (self.topics.get(source), self.topics.get(target)) else {
+            return Err(anyhow::anyhow!("Both topics must exist to merge"));
+        };
+        let (Some(src_times), Some(dst_times)) = (src.get("timestamp"), dst.get("timestamp"))
+        else {
+            return Err(anyhow::anyhow!("Both topics need a timestamp column"));
+        };
+
+        // Stable merge of the two timestamp sequences, recording where each row comes from.
+        let mut order = Vec::with_capacity(src_times.len() + dst_times.len());
+        let (mut i, mut j) = (0, 0);
+        while i < dst_times.len() || j < src_times.len() {
+            let take_dst =
+                j >= src_times.len() || (i < dst_times.len() && dst_times[i] <= src_times[j]);
+            if take_dst {
+                order.push((true, i));
+                i += 1;
+            } else {
+                order.push((false, j));
+                j += 1;
+            }
+        }
+
+        let mut names: Vec<&String> = dst.keys().chain(src.keys()).collect();
+        names.sort();
+        names.dedup();
+
+        let merged: HashMap<String, Vec<f32>> = names
+            .into_iter()
+            .map(|name| {
+                let (a, b) = (dst.get(name), src.get(name));
+                let values = order
+                    .iter()
+                    .map(|&(from_dst, idx)| {
+                        let column = if from_dst { a } else { b };
+                        column.and_then(|c| c.get(idx)).copied().unwrap_or(f32::NAN)
+                    })
+                    .collect();
+                (name.clone(), values)
+            })
+            .collect();
+
+        if let Some(src_meta) = self.column_meta.remove(source) {
+            let dst_meta = self.column_meta.entry(target.to_string()).or_default();
+            for (col, meta) in src_meta {
+                dst_meta.entry(col).or_insert(meta);
+            }
+        }
+
+        let src_transforms = self.transforms.remove(source).unwrap_or_default();
+
+        self.delete_topic(source);
+        self.topics.insert(target.to_string(), merged);
+
+        // Recompute transform outputs so rows from either topic are covered.
+        let transforms = self.transforms.entry(target.to_string()).or_default();
+        for transform in src_transforms {
+            if !transforms.iter().any(|t| t.same_as(&transform)) {
+                transforms.push(transform);
+            }
+        }
+        if let Some(cols) = self.topics.get_mut(target) {
+            for transform in transforms.iter() {
+                for (name, _) in transform.outputs() {
+                    cols.remove(*name);
+                }
+            }
+        }
+        self.update_transforms(target);
+
+        self.summaries.remove(target);
+        self.derived.remove(target);
+        self.update_summaries(target);
+
+        Ok(())
+    }
+
+    /// Bytes held in memory by raw and derived columns.
+    pub fn memory_usage(&self) -> usize {
+        self.topics
+            .values()
+            .chain(self.derived.values())
+            .flat_map(|cols| cols.values())
+            .map(|v| v.len() * std::mem::size_of::<f32>())
+            .sum()
+    }
+
+    /// Bytes held in memory per topic, raw and derived columns together.
+    pub fn topic_memory_usage(&self) -> HashMap<String, usize> {
+        let mut usage: HashMap<String, usize> = HashMap::new();
+        for (topic, cols) in self.topics.iter().chain(&self.derived) {
+            let bytes: usize = cols
+                .values()
+                .map(|v| v.len() * std::mem::size_of::<f32>())
+                .sum();
+            *usage.entry(topic.clone()).or_default() += bytes;
+        }
+        usage
+    }
+
+    pub fn set_memory_budget(&mut self, budget: Option<usize>) {
+        self.memory_budget = budget;
+    }
+
+    /// Spills topics other than `topic` while it is being read, so that files larger than
+    /// the memory budget can be loaded.
+    pub fn fit_memory_budget(&mut self, topic: &str) {
+        if self.memory_budget.is_some() {
+            self.enforce_memory_budget(&HashSet::from([topic.to_string()]));
+        }
+    }
+
+    pub fn is_spilled(&self, topic: &str) -> bool {
+        self.spill.contains(topic)
+    }
+
+    /// Marks `hot` topics as in use, restoring any that were spilled, then spills the
+    /// least recently used other topics until memory usage fits the budget.
+    /// Returns the topics that were restored.
+    pub fn touch_topics(&mut self, hot: &HashSet<String>) -> Vec<String> {
+        self.access_tick += 1;
+
+        let mut restored = Vec::new();
+        for topic in hot {
+            if !self.topics.contains_key(topic) {
+                continue;
+            }
+            self.last_access.insert(topic.clone(), self.access_tick);
+            if self.restore_topic(topic) {
+                restored.push(topic.clone());
+            }
+        }
+
+        self.enforce_memory_budget(hot);
+        restored
+    }
+
+    fn enforce_memory_budget(&mut self, hot: &HashSet<String>) {
+        let Some(budget) = self.memory_budget else {
+            return;
+        };
+
+        let mut usage = self.memory_usage();
+        if usage <= budget {
+            return;
+        }
+
+        let mut candidates: Vec<_> = self
+            .topics
+            .keys()
+            .filter(|topic| !hot.contains(*topic) && !self.spill.contains(topic))
+            .map(|topic| {
+                let last = self.last_access.get(topic).copied().unwrap_or(0);
+                (last, topic.clone())
+            })
+            .collect();
+        candidates.sort();
+
+        for (_, topic) in candidates {
+            if usage <= budget {
+                break;
+            }
+
+            let Some(cols) = self.topics.get_mut(&topic) else {
+                continue;
+            };
+            if let Err(e) = self.spill.write(&topic, cols) {
+                tracing::error!("Failed to spill topic '{}' to disk: {}", topic, e);
+                return;
+            }
+
+            for values in cols.values_mut() {
+                usage -= values.len() * std::mem::size_of::<f32>();
+                *values = Vec::new();
+            }
+            if let Some(derived) = self.derived.remove(&topic) {
+                usage -= derived
+                    .values()
+                    .map(|v| v.len() * std::mem::size_of::<f32>())
+                    .sum::<usize>();
+            }
+        }
+    }
+
+    /// Loads a spilled topic back into memory. Returns true if it was spilled.
+    fn restore_topic(&mut self, topic: &str) -> bool {
+        if !self.spill.contains(topic) {
+            return false;
+        }
+
+        match self.spill.take(topic) {
+            Ok(columns) => {
+                self.topics.insert(topic.to_string(), columns);
+                true
+            }
+            Err(e) => {
+                tracing::error!("Failed to restore topic '{}' from disk: {}", topic, e);
+                false
+            }
+        }
     }
 
     pub fn get_topics(&self) -> Vec<&String> {
@@ -324,6 +1353,64 @@ impl DataStore {
         topics
     }
 
+    /// Names of the log sessions added next to the main log, see
+    /// [`session_topic`](super::session_topic).
+    pub fn sessions(&self) -> Vec<&str> {
+        let mut sessions: Vec<&str> = self
+            .topics
+            .keys()
+            .filter_map(|topic| topic.split_once(SESSION_SEPARATOR))
+            .map(|(session, _)| session)
+            .collect();
+        sessions.sort();
+        sessions.dedup();
+        sessions
+    }
+
+    /// Moves every topic of the log session `session` by `seconds` on the timeline.
+    pub fn shift_session(&mut self, session: &str, seconds: f32) {
+        let prefix = format!("{}{}", session, SESSION_SEPARATOR);
+        let topics: Vec<String> = self
+            .topics
+            .keys()
+            .filter(|topic| topic.starts_with(&prefix))
+            .cloned()
+            .collect();
+        for topic in topics {
+            self.restore_topic(&topic);
+            if let Some(times) = self
+                .topics
+                .get_mut(&topic)
+                .and_then(|cols| cols.get_mut("timestamp"))
+            {
+                times.iter_mut().for_each(|t| *t += seconds);
+            }
+            for image in self
+                .images
+                .get_mut(&topic)
+                .into_iter()
+                .flat_map(|c| c.values_mut())
+            {
+                image.times.iter_mut().for_each(|t| *t += seconds);
+            }
+            if let Some(summaries) = self.summaries.get_mut(&topic) {
+                summaries.remove("timestamp");
+            }
+            self.update_summaries(&topic);
+        }
+    }
+
+    /// Topic and column of every column that references camera frames, sorted.
+    pub fn image_columns(&self) -> Vec<(&String, &String)> {
+        let mut columns: Vec<_> = self
+            .images
+            .iter()
+            .flat_map(|(topic, cols)| cols.keys().map(move |col| (topic, col)))
+            .collect();
+        columns.sort();
+        columns
+    }
+
     pub fn get_columns(&self, topic: &str) -> Vec<&String> {
         if let Some(cols) = self.topics.get(topic) {
             let mut col_names: Vec<_> = cols.keys().collect();
@@ -346,3 +1433,134 @@ impl Default for DataStore {
         Self::new()
     }
 }
+
+/// Topics and session of a [`DataStore`] captured for saving, e.g. on a background task.
+pub struct Snapshot {
+    start_time: f32,
+    topics: Vec<(String, RecordBatch)>,
+    session: Option<SessionMetadata>,
+}
+
+impl Snapshot {
+    pub fn write<P: AsRef<Path>>(&self, path: P, compression: Compression) -> anyhow::Result<()> {
+        use arrow::ipc::writer::StreamWriter;
+
+        let file = File::create(path)?;
+        let mut writer = BufWriter::new(file);
+
+        writer.write_all(FILE_MAGIC)?;
+        writer.write_all(&FILE_VERSION.to_le_bytes())?;
+        writer.write_all(&(self.topics.len() as u32).to_le_bytes())?;
+        writer.write_all(&self.start_time.to_le_bytes())?;
+
+        for (topic_name, batch) in &self.topics {
+            let topic_bytes = topic_name.as_bytes();
+            writer.write_all(&(topic_bytes.len() as u32).to_le_bytes())?;
+            writer.write_all(topic_bytes)?;
+
+            let mut stream_buffer = Vec::new();
+            {
+                let mut stream_writer = StreamWriter::try_new(&mut stream_buffer, &batch.schema())?;
+                stream_writer.write(batch)?;
+                stream_writer.finish()?;
+            }
+            let stream_buffer = compression.compress(stream_buffer)?;
+
+            writer.write_all(&[compression.flag()])?;
+            writer.write_all(&(stream_buffer.len() as u64).to_le_bytes())?;
+            writer.write_all(&stream_buffer)?;
+        }
+
+        match &self.session {
+            Some(session) => {
+                let json = serde_json::to_vec(session)?;
+                writer.write_all(&(json.len() as u64).to_le_bytes())?;
+                writer.write_all(&json)?;
+            }
+            None => writer.write_all(&0u64.to_le_bytes())?,
+        }
+
+        writer.flush()?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reloads_saved_files_with_every_compression() {
+        let mut store = DataStore::new();
+        let times: Vec<f32> = (0..10_000).map(|i| i as f32 * 0.01).collect();
+        let values: Vec<f32> = times.iter().map(|t| t.sin()).collect();
+        store.insert_topic("imu", times.clone()).unwrap();
+        store.set_column("imu", "gyro_x", values.clone()).unwrap();
+
+        for compression in Compression::ALL {
+            let path = std::env::temp_dir().join(format!(
+                "tiplot-roundtrip-{}-{}.arrow",
+                std::process::id(),
+                compression.label()
+            ));
+            store.save_to_arrow(&path, None, compression).unwrap();
+            let mut loaded = DataStore::new();
+            let result = DataStore::read_arrow_file(
+                &path,
+                |topic, batch| loaded.ingest(topic.to_string(), batch),
+                |_, _| {},
+            );
+            std::fs::remove_file(&path).ok();
+
+            result.unwrap();
+            assert_eq!(loaded.get_column("imu", "gyro_x"), Some(&values));
+            assert_eq!(loaded.get_column("imu", "timestamp"), Some(&times));
+        }
+    }
+
+    #[test]
+    fn spills_topics_while_reading_a_file_over_budget() {
+        let mut store = DataStore::new();
+        let times: Vec<f32> = (0..10_000).map(|i| i as f32).collect();
+        for topic in ["a", "b"] {
+            store.insert_topic(topic, times.clone()).unwrap();
+            store.set_column(topic, "x", times.clone()).unwrap();
+        }
+        let path = std::env::temp_dir().join(format!("tiplot-budget-{}.arrow", std::process::id()));
+        store.save_to_arrow(&path, None, Compression::None).unwrap();
+
+        let mut loaded = DataStore::new();
+        loaded.set_memory_budget(Some(100_000));
+        let result = DataStore::read_arrow_file(
+            &path,
+            |topic, batch| {
+                loaded.ingest(topic.to_string(), batch);
+                loaded.fit_memory_budget(topic);
+            },
+            |_, _| {},
+        );
+        std::fs::remove_file(&path).ok();
+
+        result.unwrap();
+        assert!(loaded.is_spilled("a"));
+        assert!(!loaded.is_spilled("b"));
+        loaded.touch_topics(&HashSet::from(["a".to_string()]));
+        assert_eq!(loaded.get_column("a", "x"), Some(&times));
+    }
+
+    #[test]
+    fn replacing_a_column_refreshes_its_bounds() {
+        let mut store = DataStore::new();
+        let times: Vec<f32> = (0..5_000).map(|i| i as f32).collect();
+        store.insert_topic("a", times.clone()).unwrap();
+        store.set_column("a", "x", vec![1.0; times.len()]).unwrap();
+        let stats = store.column_range_stats("a", "x", 0, times.len()).unwrap();
+        assert_eq!((stats.min, stats.max), (1.0, 1.0));
+
+        store.set_column("a", "x", times.clone()).unwrap();
+        let stats = store.column_range_stats("a", "x", 0, times.len()).unwrap();
+        assert_eq!((stats.min, stats.max), (0.0, 4_999.0));
+        assert_eq!(stats.mean, 2_499.5);
+    }
+}
@@ -0,0 +1,318 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum SignalFilter {
+    MovingAverage { window: usize },
+    LowPass { cutoff_hz: f32 },
+    Median { window: usize },
+    Derivative,
+    Integral,
+}
+
+impl SignalFilter {
+    pub const DEFAULTS: [SignalFilter; 5] = [
+        SignalFilter::MovingAverage { window: 5 },
+        SignalFilter::LowPass { cutoff_hz: 5.0 },
+        SignalFilter::Median { window: 5 },
+        SignalFilter::Derivative,
+        SignalFilter::Integral,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SignalFilter::MovingAverage { .. } => "Moving Average",
+            SignalFilter::LowPass { .. } => "Low-pass (Butterworth)",
+            SignalFilter::Median { .. } => "Median",
+            SignalFilter::Derivative => "Derivative",
+            SignalFilter::Integral => "Integral",
+        }
+    }
+
+    /// Short label used in legends and as the derived column suffix.
+    pub fn label(&self) -> String {
+        match self {
+            SignalFilter::MovingAverage { window } => format!("avg {}", window),
+            SignalFilter::LowPass { cutoff_hz } => format!("lpf {}Hz", cutoff_hz),
+            SignalFilter::Median { window } => format!("median {}", window),
+            SignalFilter::Derivative => "d/dt".to_string(),
+            SignalFilter::Integral => "∫dt".to_string(),
+        }
+    }
+
+    pub fn same_kind(&self, other: &SignalFilter) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+
+    pub fn apply(&self, times: &[f32], values: &[f32]) -> Vec<f32> {
+        let n = times.len().min(values.len());
+        let (times, values) = (&times[..n], &values[..n]);
+
+        match *self {
+            SignalFilter::MovingAverage { window } => moving_average(values, window),
+            SignalFilter::LowPass { cutoff_hz } => butterworth_lowpass(times, values, cutoff_hz),
+            SignalFilter::Median { window } => median(values, window),
+            SignalFilter::Derivative => derivative(times, values),
+            SignalFilter::Integral => integral(times, values),
+        }
+    }
+}
+
+fn moving_average(values: &[f32], window: usize) -> Vec<f32> {
+    let half = window.max(1) / 2;
+    let mut prefix = Vec::with_capacity(values.len() + 1);
+    prefix.push(0.0f64);
+    for &v in values {
+        prefix.push(prefix[prefix.len() - 1] + v as f64);
+    }
+
+    (0..values.len())
+        .map(|i| {
+            let start = i.saturating_sub(half);
+            let end = (i + half + 1).min(values.len());
+            ((prefix[end] - prefix[start]) / (end - start) as f64) as f32
+        })
+        .collect()
+}
+
+fn median(values: &[f32], window: usize) -> Vec<f32> {
+    let half = window.max(1) / 2;
+    let mut scratch = Vec::with_capacity(half * 2 + 1);
+
+    (0..values.len())
+        .map(|i| {
+            let start = i.saturating_sub(half);
+            let end = (i + half + 1).min(values.len());
+            scratch.clear();
+            scratch.extend_from_slice(&values[start..end]);
+            scratch.sort_by(|a, b| a.total_cmp(b));
+            scratch[scratch.len() / 2]
+        })
+        .collect()
+}
+
+/// Median sample interval over the first samples, robust to occasional dropouts.
+fn estimate_sample_interval(times: &[f32]) -> Option<f32> {
+    let mut intervals: Vec<f32> = times
+        .windows(2)
+        .take(1000)
+        .map(|w| w[1] - w[0])
+        .filter(|dt| *dt > 0.0)
+        .collect();
+
+    if intervals.is_empty() {
+        return None;
+    }
+
+    intervals.sort_by(|a, b| a.total_cmp(b));
+    Some(intervals[intervals.len() / 2])
+}
+
+/// Second-order Butterworth low-pass, run forward and backward for zero phase lag.
+fn butterworth_lowpass(times: &[f32], values: &[f32], cutoff_hz: f32) -> Vec<f32> {
+    let Some(dt) = estimate_sample_interval(times) else {
+        return values.to_vec();
+    };
+
+    let fs = 1.0 / dt as f64;
+    let fc = cutoff_hz as f64;
+    if values.is_empty() || fc <= 0.0 || fc >= fs / 2.0 {
+        return values.to_vec();
+    }
+
+    let k = (std::f64::consts::PI * fc / fs).tan();
+    let sqrt2 = std::f64::consts::SQRT_2;
+    let norm = 1.0 / (1.0 + sqrt2 * k + k * k);
+    let b0 = k * k * norm;
+    let b1 = 2.0 * b0;
+    let b2 = b0;
+    let a1 = 2.0 * (k * k - 1.0) * norm;
+    let a2 = (1.0 - sqrt2 * k + k * k) * norm;
+
+    let run = |input: &mut Vec<f64>| {
+        let x0 = input[0];
+        let (mut x1, mut x2, mut y1, mut y2) = (x0, x0, x0, x0);
+        for v in input.iter_mut() {
+            let x = *v;
+            let y = b0 * x + b1 * x1 + b2 * x2 - a1 * y1 - a2 * y2;
+            x2 = x1;
+            x1 = x;
+            y2 = y1;
+            y1 = y;
+            *v = y;
+        }
+    };
+
+    let mut buf: Vec<f64> = values.iter().map(|&v| v as f64).collect();
+    run(&mut buf);
+    buf.reverse();
+    run(&mut buf);
+    buf.reverse();
+
+    buf.into_iter().map(|v| v as f32).collect()
+}
+
+fn derivative(times: &[f32], values: &[f32]) -> Vec<f32> {
+    let n = values.len();
+    if n < 2 {
+        return vec![0.0; n];
+    }
+
+    (0..n)
+        .map(|i| {
+            let (a, b) = match i {
+                0 => (0, 1),
+                _ if i == n - 1 => (n - 2, n - 1),
+                _ => (i - 1, i + 1),
+            };
+            let dt = times[b] - times[a];
+            if dt.abs() < 1e-9 {
+                0.0
+            } else {
+                (values[b] - values[a]) / dt
+            }
+        })
+        .collect()
+}
+
+fn integral(times: &[f32], values: &[f32]) -> Vec<f32> {
+    let mut acc = 0.0f64;
+    let mut out = Vec::with_capacity(values.len());
+
+    for i in 0..values.len() {
+        if i > 0 {
+            let dt = (times[i] - times[i - 1]) as f64;
+            acc += 0.5 * (values[i] + values[i - 1]) as f64 * dt;
+        }
+        out.push(acc as f32);
+    }
+
+    out
+}
+
+/// Points the compared windows are resampled to.
+const ALIGNMENT_POINTS: usize = 512;
+
+/// Linear interpolation of a sorted series at `time`, holding the end values outside it.
+fn sample_linear(times: &[f32], values: &[f32], time: f32) -> f32 {
+    let idx = times.partition_point(|&t| t < time);
+    if idx == 0 {
+        return values[0];
+    }
+    if idx >= times.len() {
+        return values[times.len() - 1];
+    }
+    let (t0, t1) = (times[idx - 1], times[idx]);
+    let (v0, v1) = (values[idx - 1], values[idx]);
+    if t1 - t0 <= f32::EPSILON {
+        v0
+    } else {
+        v0 + (time - t0) / (t1 - t0) * (v1 - v0)
+    }
+}
+
+/// Finds the shift in `-max_shift..=max_shift` that, added to the timestamps of `other`,
+/// best lines it up with `reference` over the time range of `reference`. Returns the
+/// shift and its correlation coefficient.
+pub fn best_offset(
+    reference: (&[f32], &[f32]),
+    other: (&[f32], &[f32]),
+    max_shift: f32,
+) -> Option<(f32, f32)> {
+    let window = (*reference.0.first()?, *reference.0.last()?);
+    correlate(reference, other, window, (-max_shift, max_shift)).map(|(lag, r)| (-lag, r))
+}
+
+/// Timestamps and values cut to the same length, if there are at least two samples.
+fn paired<'a>((times, values): (&'a [f32], &'a [f32])) -> Option<(&'a [f32], &'a [f32])> {
+    let n = times.len().min(values.len());
+    (n >= 2).then(|| (&times[..n], &values[..n]))
+}
+
+/// Normalized cross-correlation of `window` in `start..end` with `span` `lag` seconds
+/// later, for lags in `min_lag..=max_lag`. Returns the best lag and its coefficient.
+fn correlate(
+    window: (&[f32], &[f32]),
+    span: (&[f32], &[f32]),
+    (start, end): (f32, f32),
+    (min_lag, max_lag): (f32, f32),
+) -> Option<(f32, f32)> {
+    let (window, span) = (paired(window)?, paired(span)?);
+    if end <= start || max_lag < min_lag {
+        return None;
+    }
+
+    let dt = (end - start) / (ALIGNMENT_POINTS - 1) as f32;
+    let first_lag = (min_lag / dt).floor() as i64;
+    let lag_count = ((max_lag - min_lag) / dt).ceil() as usize + 1;
+    let span_start = start + first_lag as f32 * dt;
+    let resample = |(times, values): (&[f32], &[f32]), count: usize, from: f32| -> Vec<f64> {
+        (0..count)
+            .map(|i| sample_linear(times, values, from + i as f32 * dt) as f64)
+            .collect()
+    };
+
+    let window = resample(window, ALIGNMENT_POINTS, start);
+    let span = resample(span, ALIGNMENT_POINTS + lag_count - 1, span_start);
+
+    let count = ALIGNMENT_POINTS as f64;
+    let mean = window.iter().sum::<f64>() / count;
+    let centered: Vec<f64> = window.iter().map(|v| v - mean).collect();
+    let norm = centered.iter().map(|v| v * v).sum::<f64>().sqrt();
+    if norm == 0.0 || !norm.is_finite() {
+        return None;
+    }
+
+    let mut best: Option<(f32, f32)> = None;
+    for k in 0..lag_count {
+        let lag = (first_lag + k as i64) as f32 * dt;
+        let candidate = &span[k..k + ALIGNMENT_POINTS];
+        let candidate_mean = candidate.iter().sum::<f64>() / count;
+        let mut dot = 0.0;
+        let mut candidate_norm = 0.0;
+        for (a, b) in centered.iter().zip(candidate) {
+            let b = b - candidate_mean;
+            dot += a * b;
+            candidate_norm += b * b;
+        }
+        if candidate_norm == 0.0 {
+            continue;
+        }
+        let r = (dot / (norm * candidate_norm.sqrt())) as f32;
+        if r.is_finite() && best.is_none_or(|(_, best_r)| r > best_r) {
+            best = Some((lag, r));
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A bump centred on `peak`, sampled every 0.1 s over `0..duration`.
+    fn bump(peak: f32, duration: f32) -> (Vec<f32>, Vec<f32>) {
+        let times: Vec<f32> = (0..(duration * 10.0) as usize)
+            .map(|i| i as f32 * 0.1)
+            .collect();
+        let values = times.iter().map(|t| (-(t - peak).powi(2)).exp()).collect();
+        (times, values)
+    }
+
+    #[test]
+    fn best_offset_finds_the_shift_between_sessions() {
+        let reference = bump(30.0, 60.0);
+        // The same event, recorded 12 s later by the other log's clock.
+        let other = bump(42.0, 80.0);
+        let (shift, r) =
+            best_offset((&reference.0, &reference.1), (&other.0, &other.1), 20.0).unwrap();
+        assert!((shift + 12.0).abs() < 0.2, "shift {}", shift);
+        assert!(r > 0.99, "r {}", r);
+    }
+
+    #[test]
+    fn best_offset_needs_a_varying_reference() {
+        let flat = (vec![0.0, 1.0, 2.0], vec![1.0, 1.0, 1.0]);
+        let other = bump(1.0, 3.0);
+        assert!(best_offset((&flat.0, &flat.1), (&other.0, &other.1), 1.0).is_none());
+    }
+}
@@ -0,0 +1,27 @@
+/// WGS84 equatorial radius in metres.
+const EARTH_RADIUS: f64 = 6378137.0;
+
+/// Local north/east/down offset of a GPS fix from a reference fix, using a flat-earth
+/// approximation around the reference. Angles are in degrees and altitudes in metres.
+pub fn gps_to_ned(
+    lat: f64,
+    lon: f64,
+    alt: f64,
+    lat_ref: f64,
+    lon_ref: f64,
+    alt_ref: f64,
+) -> glam::Vec3 {
+    let lat_rad = lat.to_radians();
+    let lon_rad = lon.to_radians();
+    let lat_ref_rad = lat_ref.to_radians();
+    let lon_ref_rad = lon_ref.to_radians();
+
+    let d_lat = lat_rad - lat_ref_rad;
+    let d_lon = lon_rad - lon_ref_rad;
+
+    let north = (d_lat * EARTH_RADIUS) as f32;
+    let east = (d_lon * EARTH_RADIUS * lat_ref_rad.cos()) as f32;
+    let down = -(alt - alt_ref) as f32;
+
+    glam::Vec3::new(north, east, down)
+}
@@ -0,0 +1,81 @@
+use arrow::array::{Array, BinaryArray, LargeBinaryArray, StringArray};
+use std::sync::Arc;
+
+/// File extensions of image paths logged by camera capture topics.
+const IMAGE_EXTENSIONS: [&str; 5] = ["jpg", "jpeg", "png", "bmp", "webp"];
+
+/// A camera frame referenced by a log: a file path or the encoded image itself.
+#[derive(Clone, Debug)]
+pub enum ImageRef {
+    Path(String),
+    Encoded(Arc<[u8]>),
+}
+
+/// Camera frames of one column with the time of each.
+#[derive(Clone, Debug, Default)]
+pub struct ImageColumn {
+    pub times: Vec<f32>,
+    pub frames: Vec<ImageRef>,
+}
+
+impl ImageColumn {
+    /// Index of the frame closest to `time`.
+    pub fn nearest(&self, time: f32) -> Option<usize> {
+        let after = self.times.partition_point(|&t| t < time);
+        let before = after.checked_sub(1);
+        match (before, (after < self.times.len()).then_some(after)) {
+            (Some(b), Some(a)) if time - self.times[b] <= self.times[a] - time => Some(b),
+            (_, Some(a)) => Some(a),
+            (before, None) => before,
+        }
+    }
+
+    /// Drops frames older than `time`.
+    pub fn trim_before(&mut self, time: f32) {
+        let cut = self.times.partition_point(|&t| t < time);
+        self.times.drain(..cut);
+        self.frames.drain(..cut);
+    }
+
+    /// Appends the image references of a string or binary column whose rows were logged
+    /// at `times`. Rows that are not images are skipped.
+    pub fn extend_from(&mut self, column: &dyn Array, times: &[f32]) {
+        let rows = column.len().min(times.len());
+        let any = column.as_any();
+        if let Some(arr) = any.downcast_ref::<StringArray>() {
+            for (i, &time) in times.iter().enumerate().take(rows) {
+                if arr.is_valid(i) && is_image_path(arr.value(i)) {
+                    self.push(time, ImageRef::Path(arr.value(i).to_string()));
+                }
+            }
+        } else if let Some(arr) = any.downcast_ref::<BinaryArray>() {
+            for (i, &time) in times.iter().enumerate().take(rows) {
+                if arr.is_valid(i) && is_encoded_image(arr.value(i)) {
+                    self.push(time, ImageRef::Encoded(arr.value(i).into()));
+                }
+            }
+        } else if let Some(arr) = any.downcast_ref::<LargeBinaryArray>() {
+            for (i, &time) in times.iter().enumerate().take(rows) {
+                if arr.is_valid(i) && is_encoded_image(arr.value(i)) {
+                    self.push(time, ImageRef::Encoded(arr.value(i).into()));
+                }
+            }
+        }
+    }
+
+    fn push(&mut self, time: f32, frame: ImageRef) {
+        self.times.push(time);
+        self.frames.push(frame);
+    }
+}
+
+fn is_image_path(value: &str) -> bool {
+    value
+        .rsplit_once('.')
+        .is_some_and(|(_, ext)| IMAGE_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+}
+
+/// JPEG and PNG signatures.
+fn is_encoded_image(bytes: &[u8]) -> bool {
+    bytes.starts_with(&[0xFF, 0xD8, 0xFF]) || bytes.starts_with(&[0x89, b'P', b'N', b'G'])
+}
@@ -0,0 +1,130 @@
+use arrow::record_batch::RecordBatch;
+use std::sync::{Arc, RwLock};
+
+pub type SharedIngestFilter = Arc<RwLock<IngestFilter>>;
+
+/// Include/exclude patterns applied to incoming data before it is stored. A pattern is
+/// either `topic` or `topic/column`, where `*` matches any run of characters and `?` a
+/// single character. Excluding a bare topic drops it entirely; when include patterns
+/// are given, only matching topics and columns are kept.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct IngestFilter {
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+}
+
+impl IngestFilter {
+    /// Builds a filter from newline or comma separated pattern lists.
+    pub fn parse(include: &str, exclude: &str) -> Self {
+        fn split(patterns: &str) -> Vec<String> {
+            patterns
+                .split(['\n', ','])
+                .map(str::trim)
+                .filter(|p| !p.is_empty())
+                .map(str::to_string)
+                .collect()
+        }
+
+        Self {
+            include: split(include),
+            exclude: split(exclude),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+
+    pub fn accepts_topic(&self, topic: &str) -> bool {
+        let excluded = self.exclude.iter().any(|p| {
+            let (topic_pat, col_pat) = split_pattern(p);
+            col_pat.is_none() && glob_match(topic_pat, topic)
+        });
+
+        !excluded
+            && (self.include.is_empty()
+                || self
+                    .include
+                    .iter()
+                    .any(|p| glob_match(split_pattern(p).0, topic)))
+    }
+
+    /// Whether a column of an accepted topic should be kept. Timestamps always are.
+    pub fn accepts_column(&self, topic: &str, col: &str) -> bool {
+        if col == "timestamp" {
+            return true;
+        }
+
+        let matches = |p: &String| {
+            let (topic_pat, col_pat) = split_pattern(p);
+            glob_match(topic_pat, topic) && col_pat.is_none_or(|c| glob_match(c, col))
+        };
+
+        !self.exclude.iter().any(matches)
+            && (self.include.is_empty() || self.include.iter().any(matches))
+    }
+
+    /// Drops rejected columns from a batch, or the whole batch if its topic is rejected.
+    pub fn apply(&self, topic: &str, batch: RecordBatch) -> Option<RecordBatch> {
+        if self.is_empty() {
+            return Some(batch);
+        }
+        if !self.accepts_topic(topic) {
+            return None;
+        }
+
+        let schema = batch.schema();
+        let indices: Vec<usize> = schema
+            .fields()
+            .iter()
+            .enumerate()
+            .filter(|(_, field)| self.accepts_column(topic, field.name()))
+            .map(|(i, _)| i)
+            .collect();
+
+        if indices.len() == schema.fields().len() {
+            return Some(batch);
+        }
+
+        match batch.project(&indices) {
+            Ok(projected) => Some(projected),
+            Err(e) => {
+                tracing::error!("Failed to filter columns of '{}': {}", topic, e);
+                Some(batch)
+            }
+        }
+    }
+}
+
+fn split_pattern(pattern: &str) -> (&str, Option<&str>) {
+    match pattern.split_once('/') {
+        Some((topic, col)) => (topic, Some(col)),
+        None => (pattern, None),
+    }
+}
+
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = backtrack {
+            p = star_p + 1;
+            t = star_t + 1;
+            backtrack = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
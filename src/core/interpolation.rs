@@ -0,0 +1,70 @@
+/// How a series is read between its samples, e.g. for tooltips and derived columns.
+#[derive(Clone, Debug, Copy, PartialEq, Default)]
+pub enum InterpolationMode {
+    #[default]
+    PreviousPoint,
+    Linear,
+    NextPoint,
+}
+
+impl InterpolationMode {
+    /// Value of a series at `time`, or `None` outside the range this mode can answer for.
+    pub fn sample(self, times: &[f32], values: &[f32], time: f32) -> Option<f32> {
+        match self {
+            InterpolationMode::PreviousPoint => {
+                let idx = times.partition_point(|&t| t < time);
+                if idx == 0 {
+                    None
+                } else {
+                    let prev_idx = idx - 1;
+                    if prev_idx < values.len() {
+                        Some(values[prev_idx])
+                    } else {
+                        None
+                    }
+                }
+            }
+            InterpolationMode::NextPoint => {
+                let idx = times.partition_point(|&t| t <= time);
+                if idx >= times.len() {
+                    None
+                } else if idx < values.len() {
+                    Some(values[idx])
+                } else {
+                    None
+                }
+            }
+            InterpolationMode::Linear => {
+                let idx = times.partition_point(|&t| t < time);
+
+                if idx == 0 {
+                    None
+                } else if idx >= times.len() {
+                    if !times.is_empty() && times.len() == values.len() {
+                        Some(values[values.len() - 1])
+                    } else {
+                        None
+                    }
+                } else {
+                    // Between two points - interpolate
+                    let prev_idx = idx - 1;
+                    if prev_idx < values.len() && idx < values.len() {
+                        let t0 = times[prev_idx];
+                        let t1 = times[idx];
+                        let v0 = values[prev_idx];
+                        let v1 = values[idx];
+
+                        if (t1 - t0).abs() < 1e-6 {
+                            Some(v0)
+                        } else {
+                            let t = (time - t0) / (t1 - t0);
+                            Some(v0 + t * (v1 - v0))
+                        }
+                    } else {
+                        None
+                    }
+                }
+            }
+        }
+    }
+}
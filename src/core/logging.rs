@@ -0,0 +1,191 @@
+//! Application logging. Events from `tracing` (and from dependencies using `log`, such as
+//! wgpu) are printed to stderr and kept in memory for the in-app log console.
+
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::SystemTime;
+use tracing::field::{Field, Visit};
+use tracing::span;
+use tracing::{Event, Level, Metadata, Subscriber};
+
+/// Records kept for the console; older ones are dropped.
+const CAPACITY: usize = 5000;
+
+#[derive(Clone, Debug)]
+pub struct LogRecord {
+    pub time: SystemTime,
+    pub level: Level,
+    pub target: String,
+    pub message: String,
+}
+
+impl LogRecord {
+    /// Single-line form used on stderr and when copying from the console.
+    pub fn format(&self) -> String {
+        let secs = self
+            .time
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map_or(0.0, |d| d.as_secs_f64());
+        let clock = secs.rem_euclid(86400.0);
+        format!(
+            "{:02}:{:02}:{:06.3} {:5} {}: {}",
+            (clock / 3600.0) as u32,
+            (clock / 60.0 % 60.0) as u32,
+            clock % 60.0,
+            self.level,
+            self.target,
+            self.message
+        )
+    }
+}
+
+struct LogBuffer {
+    records: Mutex<VecDeque<LogRecord>>,
+    /// Total warnings and errors ever logged, so the UI can tell when new ones arrived.
+    problems: AtomicUsize,
+}
+
+fn buffer() -> &'static LogBuffer {
+    static BUFFER: OnceLock<LogBuffer> = OnceLock::new();
+    BUFFER.get_or_init(|| LogBuffer {
+        records: Mutex::new(VecDeque::new()),
+        problems: AtomicUsize::new(0),
+    })
+}
+
+fn push(record: LogRecord) {
+    eprintln!("{}", record.format());
+
+    let buffer = buffer();
+    if record.level <= Level::WARN {
+        buffer.problems.fetch_add(1, Ordering::Relaxed);
+    }
+    let mut records = buffer.records.lock().unwrap();
+    if records.len() == CAPACITY {
+        records.pop_front();
+    }
+    records.push_back(record);
+}
+
+/// Snapshot of the retained records, oldest first.
+pub fn records() -> Vec<LogRecord> {
+    buffer().records.lock().unwrap().iter().cloned().collect()
+}
+
+pub fn clear() {
+    buffer().records.lock().unwrap().clear();
+}
+
+/// Number of warnings and errors logged since startup.
+pub fn problem_count() -> usize {
+    buffer().problems.load(Ordering::Relaxed)
+}
+
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: String,
+}
+
+impl Visit for MessageVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            let _ = write!(self.fields, " {}={}", field.name(), value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{:?}", value);
+        } else {
+            let _ = write!(self.fields, " {}={:?}", field.name(), value);
+        }
+    }
+}
+
+struct ConsoleSubscriber {
+    max_level: Level,
+    next_span: AtomicU64,
+}
+
+impl Subscriber for ConsoleSubscriber {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        *metadata.level() <= self.max_level
+    }
+
+    fn new_span(&self, _span: &span::Attributes<'_>) -> span::Id {
+        span::Id::from_u64(self.next_span.fetch_add(1, Ordering::Relaxed))
+    }
+
+    fn record(&self, _span: &span::Id, _values: &span::Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &span::Id, _follows: &span::Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        push(LogRecord {
+            time: SystemTime::now(),
+            level: *event.metadata().level(),
+            target: event.metadata().target().to_string(),
+            message: visitor.message + visitor.fields.as_str(),
+        });
+    }
+
+    fn enter(&self, _span: &span::Id) {}
+
+    fn exit(&self, _span: &span::Id) {}
+}
+
+/// Forwards warnings and errors of dependencies that use the `log` crate.
+struct LogBridge;
+
+impl log::Log for LogBridge {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= log::Level::Warn
+    }
+
+    fn log(&self, record: &log::Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let level = match record.level() {
+            log::Level::Error => Level::ERROR,
+            _ => Level::WARN,
+        };
+        push(LogRecord {
+            time: SystemTime::now(),
+            level,
+            target: record.target().to_string(),
+            message: record.args().to_string(),
+        });
+    }
+
+    fn flush(&self) {}
+}
+
+/// Installs the logger. `TIPLOT_LOG` selects the most verbose level shown, `info` by
+/// default.
+pub fn init() {
+    let max_level = std::env::var("TIPLOT_LOG")
+        .ok()
+        .and_then(|level| level.parse().ok())
+        .unwrap_or(Level::INFO);
+
+    let subscriber = ConsoleSubscriber {
+        max_level,
+        next_span: AtomicU64::new(1),
+    };
+    if tracing::subscriber::set_global_default(subscriber).is_err() {
+        return;
+    }
+
+    static BRIDGE: LogBridge = LogBridge;
+    if log::set_logger(&BRIDGE).is_ok() {
+        log::set_max_level(log::LevelFilter::Warn);
+    }
+}
@@ -1,3 +1,35 @@
+pub mod aliases;
+pub mod arrow_files;
+pub mod autosave;
 pub mod data_store;
+pub mod filters;
+pub mod geo;
+pub mod images;
+pub mod ingest_filter;
+pub mod interpolation;
+pub mod logging;
+pub mod script;
+pub mod session;
+pub mod settings;
+pub mod spill;
+pub mod summary;
+pub mod transforms;
+pub mod trigger;
+pub mod units;
 
-pub use data_store::DataStore;
+pub use aliases::{Alias, AliasProfile};
+pub use autosave::Autosave;
+pub use data_store::{DataStore, RetentionPolicy, Snapshot, TopicStats};
+pub use filters::SignalFilter;
+pub use images::{ImageColumn, ImageRef};
+pub use ingest_filter::{IngestFilter, SharedIngestFilter};
+pub use interpolation::InterpolationMode;
+pub use session::{
+    parameter_changes, session_topic, Annotation, LogInfo, SessionMetadata, SESSION_SEPARATOR,
+};
+pub use settings::DataSettings;
+pub use spill::SpillCache;
+pub use summary::{ColumnSummary, RangeStats};
+pub use transforms::Transform;
+pub use trigger::{Crossing, Trigger, TriggerActions, TriggerCondition};
+pub use units::{AngleUnit, ColumnMeta, Notation, UnitConversion, ValueFormat};
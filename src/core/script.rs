@@ -0,0 +1,830 @@
+use crate::core::{Annotation, DataStore, InterpolationMode};
+use rhai::{Array, Dynamic, Engine, EvalAltResult, FLOAT, INT};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// State a script can read and change besides the data store.
+pub struct ScriptHost<'a> {
+    pub data_store: &'a mut DataStore,
+    pub annotations: &'a mut Vec<Annotation>,
+    pub current_time: &'a mut f32,
+    pub is_playing: &'a mut bool,
+    pub playback_speed: &'a mut f32,
+    pub min_time: &'a mut f32,
+    pub max_time: &'a mut f32,
+}
+
+/// Printed lines of a run, and the error that stopped it, if any.
+#[derive(Default)]
+pub struct ScriptOutput {
+    pub lines: Vec<String>,
+    pub error: Option<String>,
+}
+
+pub const HELP: &str = "\
+Scripts are written in Rhai, see https://rhai.rs/book for the language. Columns work
+with operators and functions elementwise, mixed with plain numbers.
+
+use_topic(\"topic\")                 topic that col(), time() and set_col() work on
+col(\"name\")                        column of the current topic
+col(\"topic\", \"name\")               column of another topic, interpolated onto time()
+time()                             timestamps of the current topic
+set_col(\"name\", value)             add or replace a column of the current topic
+set_col(\"topic\", \"name\", value)    same, creating the topic on time() if needed
+print(value)                       print a value, or min/max/mean of a column
+annotate(time, \"text\")             add a marker
+mark(condition, \"text\")            add a marker wherever condition becomes true
+seek(time)  play()  pause()  speed(x)  view(start, end)  now()  topics()  columns()
+
+Column operators: + - * / % ** == != < <= > >= and & | ! for and, or, not
+Column functions: sin cos tan asin acos atan atan2 sqrt abs exp ln log10 floor ceil
+  round sign deg rad pow min max select(cond, a, b) diff deriv integral len
+  mean sum count first last std min max (of one column) at(column, time)";
+
+/// Deepest expression a script may hold, so that parsing and evaluating it cannot
+/// overflow the stack.
+const MAX_EXPR_DEPTH: usize = 64;
+const MAX_FUNCTION_EXPR_DEPTH: usize = 32;
+const MAX_CALL_LEVELS: usize = 32;
+/// Bounds the run time of a script, as it runs on the UI thread.
+const MAX_OPERATIONS: u64 = 50_000_000;
+
+type Result<T> = std::result::Result<T, Box<EvalAltResult>>;
+type Unary = fn(f64) -> f64;
+type Binary = fn(f64, f64) -> f64;
+type Aggregate = fn(&[f64]) -> f64;
+
+/// Values of a column as seen by scripts, shared since Rhai copies values freely.
+#[derive(Clone)]
+struct Column(Rc<[f64]>);
+
+impl Column {
+    fn map(&self, f: impl Fn(f64) -> f64) -> Column {
+        Column(self.0.iter().map(|&v| f(v)).collect())
+    }
+
+    fn zip(&self, other: &Column, f: impl Fn(f64, f64) -> f64) -> Result<Column> {
+        if self.0.len() != other.0.len() {
+            return Err(format!(
+                "Columns have different lengths ({} and {})",
+                self.0.len(),
+                other.0.len()
+            )
+            .into());
+        }
+        Ok(Column(
+            self.0
+                .iter()
+                .zip(other.0.iter())
+                .map(|(&a, &b)| f(a, b))
+                .collect(),
+        ))
+    }
+
+    fn finite(&self) -> Vec<f64> {
+        self.0.iter().copied().filter(|v| v.is_finite()).collect()
+    }
+}
+
+/// A number or a column, for functions that take either.
+enum Value {
+    Scalar(f64),
+    Column(Column),
+}
+
+impl Value {
+    fn from_dynamic(value: Dynamic, what: &str) -> Result<Value> {
+        if let Some(column) = value.clone().try_cast::<Column>() {
+            return Ok(Value::Column(column));
+        }
+        if let Ok(v) = value.as_float() {
+            return Ok(Value::Scalar(v));
+        }
+        if let Ok(v) = value.as_int() {
+            return Ok(Value::Scalar(v as f64));
+        }
+        if let Ok(v) = value.as_bool() {
+            return Ok(Value::Scalar(flag(v)));
+        }
+        Err(format!(
+            "{} must be a number or a column, not {}",
+            what,
+            value.type_name()
+        )
+        .into())
+    }
+
+    fn at(&self, i: usize) -> f64 {
+        match self {
+            Value::Scalar(v) => *v,
+            Value::Column(c) => c.0[i],
+        }
+    }
+
+    fn len(&self) -> Option<usize> {
+        match self {
+            Value::Scalar(_) => None,
+            Value::Column(c) => Some(c.0.len()),
+        }
+    }
+}
+
+/// A single number, given as an integer or a decimal.
+fn number(value: Dynamic, what: &str) -> Result<f64> {
+    match Value::from_dynamic(value, what)? {
+        Value::Scalar(v) => Ok(v),
+        Value::Column(_) => Err(format!("{} must be a single value", what).into()),
+    }
+}
+
+fn truth(v: f64) -> bool {
+    v != 0.0 && !v.is_nan()
+}
+
+fn flag(b: bool) -> f64 {
+    if b {
+        1.0
+    } else {
+        0.0
+    }
+}
+
+/// Host state moved into the engine for the length of a run, as Rhai functions cannot
+/// borrow from the caller.
+#[derive(Default)]
+struct State {
+    data_store: DataStore,
+    annotations: Vec<Annotation>,
+    current_time: f32,
+    is_playing: bool,
+    playback_speed: f32,
+    view: (f32, f32),
+    /// Topic selected with `use_topic`.
+    topic: Option<String>,
+    lines: Vec<String>,
+}
+
+impl State {
+    fn topic(&self) -> Result<&str> {
+        self.topic
+            .as_deref()
+            .ok_or_else(|| "Select a topic with use_topic() first".into())
+    }
+
+    fn times(&self) -> Result<&Vec<f32>> {
+        let topic = self.topic()?;
+        self.data_store
+            .get_column(topic, "timestamp")
+            .ok_or_else(|| format!("Topic '{}' not found", topic).into())
+    }
+
+    fn column(&self, topic: &str, col: &str) -> Result<Column> {
+        let store = &self.data_store;
+        let (Some(times), Some(values)) = (
+            store.get_column(topic, "timestamp"),
+            store.get_column(topic, col),
+        ) else {
+            return Err(format!("Column '{}/{}' not found", topic, col).into());
+        };
+
+        Ok(match &self.topic {
+            Some(current) if current != topic => Column(
+                self.times()?
+                    .iter()
+                    .map(|&t| {
+                        InterpolationMode::Linear
+                            .sample(times, values, t)
+                            .map_or(f64::NAN, |v| v as f64)
+                    })
+                    .collect(),
+            ),
+            _ => Column(values.iter().map(|&v| v as f64).collect()),
+        })
+    }
+
+    fn set_column(&mut self, topic: &str, col: &str, value: Value) -> Result<()> {
+        let times = self.times()?;
+        let values: Vec<f32> = match &value {
+            Value::Column(c) => c.0.iter().map(|&v| v as f32).collect(),
+            Value::Scalar(v) => vec![*v as f32; times.len()],
+        };
+
+        if self.data_store.get_column(topic, "timestamp").is_none() {
+            let times = times.clone();
+            self.data_store
+                .insert_topic(topic, times)
+                .map_err(|e| e.to_string())?;
+            self.lines.push(format!("Created topic '{}'", topic));
+        }
+        self.data_store
+            .set_column(topic, col, values)
+            .map_err(|e| e.to_string().into())
+    }
+
+    fn mark(&mut self, condition: &Column, text: &str) -> Result<INT> {
+        let times = self.times()?;
+        if times.len() != condition.0.len() {
+            return Err("mark() needs a condition on the current topic".into());
+        }
+        let mut markers = Vec::new();
+        let mut was_true = false;
+        for (&v, &time) in condition.0.iter().zip(times) {
+            let is_true = truth(v);
+            if is_true && !was_true {
+                markers.push(Annotation {
+                    time,
+                    text: text.to_string(),
+                });
+            }
+            was_true = is_true;
+        }
+        let count = markers.len();
+        self.annotations.extend(markers);
+        Ok(count as INT)
+    }
+}
+
+/// Runs `source`, stopping at the first error.
+pub fn run(source: &str, host: &mut ScriptHost) -> ScriptOutput {
+    let state = Rc::new(RefCell::new(State {
+        data_store: std::mem::take(host.data_store),
+        annotations: std::mem::take(host.annotations),
+        current_time: *host.current_time,
+        is_playing: *host.is_playing,
+        playback_speed: *host.playback_speed,
+        view: (*host.min_time, *host.max_time),
+        topic: None,
+        lines: Vec::new(),
+    }));
+
+    let error = engine(&state).run(source).err().map(|e| e.to_string());
+
+    let state = std::mem::take(&mut *state.borrow_mut());
+    *host.data_store = state.data_store;
+    *host.annotations = state.annotations;
+    *host.current_time = state.current_time;
+    *host.is_playing = state.is_playing;
+    *host.playback_speed = state.playback_speed;
+    (*host.min_time, *host.max_time) = state.view;
+    ScriptOutput {
+        lines: state.lines,
+        error,
+    }
+}
+
+fn engine(state: &Rc<RefCell<State>>) -> Engine {
+    let mut engine = Engine::new();
+    engine
+        .set_max_expr_depths(MAX_EXPR_DEPTH, MAX_FUNCTION_EXPR_DEPTH)
+        .set_max_call_levels(MAX_CALL_LEVELS)
+        .set_max_operations(MAX_OPERATIONS);
+
+    let s = state.clone();
+    engine.on_print(move |text| s.borrow_mut().lines.push(text.to_string()));
+
+    register_host(&mut engine, state);
+    register_columns(&mut engine, state);
+    engine
+}
+
+/// Functions that read and change the data store, markers and timeline.
+fn register_host(engine: &mut Engine, state: &Rc<RefCell<State>>) {
+    let s = state.clone();
+    engine.register_fn("use_topic", move |topic: &str| -> Result<()> {
+        let mut state = s.borrow_mut();
+        if state.data_store.get_column(topic, "timestamp").is_none() {
+            return Err(format!("Topic '{}' not found", topic).into());
+        }
+        state.topic = Some(topic.to_string());
+        Ok(())
+    });
+    let s = state.clone();
+    engine.register_fn("topics", move || -> Array {
+        let state = s.borrow();
+        state
+            .data_store
+            .get_topics()
+            .into_iter()
+            .map(|topic| Dynamic::from(topic.clone()))
+            .collect()
+    });
+    let s = state.clone();
+    engine.register_fn("columns", move || -> Result<Array> {
+        let state = s.borrow();
+        let topic = state.topic()?;
+        Ok(state
+            .data_store
+            .all_columns(topic)
+            .into_iter()
+            .map(Dynamic::from)
+            .collect())
+    });
+
+    let s = state.clone();
+    engine.register_fn("col", move |col: &str| -> Result<Column> {
+        let state = s.borrow();
+        let topic = state.topic()?.to_string();
+        state.column(&topic, col)
+    });
+    let s = state.clone();
+    engine.register_fn("col", move |topic: &str, col: &str| -> Result<Column> {
+        s.borrow().column(topic, col)
+    });
+    let s = state.clone();
+    engine.register_fn("time", move || -> Result<Column> {
+        Ok(Column(
+            s.borrow().times()?.iter().map(|&t| t as f64).collect(),
+        ))
+    });
+    let s = state.clone();
+    engine.register_fn("set_col", move |col: &str, value: Dynamic| -> Result<()> {
+        let value = Value::from_dynamic(value, "Column value")?;
+        let mut state = s.borrow_mut();
+        let topic = state.topic()?.to_string();
+        state.set_column(&topic, col, value)
+    });
+    let s = state.clone();
+    engine.register_fn(
+        "set_col",
+        move |topic: &str, col: &str, value: Dynamic| -> Result<()> {
+            let value = Value::from_dynamic(value, "Column value")?;
+            s.borrow_mut().set_column(topic, col, value)
+        },
+    );
+
+    let s = state.clone();
+    engine.register_fn("annotate", move |time: Dynamic, text: &str| -> Result<()> {
+        let time = number(time, "Marker time")?;
+        s.borrow_mut().annotations.push(Annotation {
+            time: time as f32,
+            text: text.to_string(),
+        });
+        Ok(())
+    });
+    let s = state.clone();
+    engine.register_fn(
+        "mark",
+        move |condition: Column, text: &str| -> Result<INT> {
+            s.borrow_mut().mark(&condition, text)
+        },
+    );
+
+    let s = state.clone();
+    engine.register_fn("now", move || s.borrow().current_time as FLOAT);
+    let s = state.clone();
+    engine.register_fn("seek", move |time: Dynamic| -> Result<()> {
+        let time = number(time, "Seek time")?;
+        let mut state = s.borrow_mut();
+        state.current_time = time as f32;
+        state.is_playing = false;
+        Ok(())
+    });
+    let s = state.clone();
+    engine.register_fn("play", move || s.borrow_mut().is_playing = true);
+    let s = state.clone();
+    engine.register_fn("pause", move || s.borrow_mut().is_playing = false);
+    let s = state.clone();
+    engine.register_fn("speed", move |speed: Dynamic| -> Result<()> {
+        let speed = number(speed, "Speed")?;
+        s.borrow_mut().playback_speed = (speed as f32).clamp(0.01, 1000.0);
+        Ok(())
+    });
+    let s = state.clone();
+    engine.register_fn("view", move |start: Dynamic, end: Dynamic| -> Result<()> {
+        let (start, end) = (number(start, "View start")?, number(end, "View end")?);
+        if end <= start {
+            return Err("View end must be after its start".into());
+        }
+        s.borrow_mut().view = (start as f32, end as f32);
+        Ok(())
+    });
+}
+
+/// The column type with its operators and math functions.
+fn register_columns(engine: &mut Engine, state: &Rc<RefCell<State>>) {
+    engine
+        .register_type_with_name::<Column>("Column")
+        .register_fn("len", |c: &mut Column| c.0.len() as INT)
+        .register_fn("to_string", |c: &mut Column| summarize(&c.0))
+        .register_fn("to_debug", |c: &mut Column| summarize(&c.0))
+        .register_fn("-", |c: Column| c.map(|v| -v))
+        .register_fn("!", |c: Column| c.map(|v| flag(!truth(v))));
+
+    let elementwise: [(&str, Unary); 17] = [
+        ("sin", f64::sin),
+        ("cos", f64::cos),
+        ("tan", f64::tan),
+        ("asin", f64::asin),
+        ("acos", f64::acos),
+        ("atan", f64::atan),
+        ("sqrt", f64::sqrt),
+        ("abs", f64::abs),
+        ("exp", f64::exp),
+        ("ln", f64::ln),
+        ("log10", f64::log10),
+        ("floor", f64::floor),
+        ("ceil", f64::ceil),
+        ("round", f64::round),
+        ("sign", f64::signum),
+        ("deg", f64::to_degrees),
+        ("rad", f64::to_radians),
+    ];
+    for (name, f) in elementwise {
+        engine.register_fn(name, move |c: Column| c.map(f));
+    }
+    // Rhai names these differently or lacks them for plain numbers.
+    engine
+        .register_fn("log10", f64::log10)
+        .register_fn("ceil", f64::ceil)
+        .register_fn("deg", f64::to_degrees)
+        .register_fn("rad", f64::to_radians)
+        .register_fn("atan2", f64::atan2)
+        .register_fn("pow", f64::powf);
+
+    let binary: [(&str, Binary); 18] = [
+        ("+", |a, b| a + b),
+        ("-", |a, b| a - b),
+        ("*", |a, b| a * b),
+        ("/", |a, b| a / b),
+        ("%", |a, b| a % b),
+        ("**", f64::powf),
+        ("==", |a, b| flag(a == b)),
+        ("!=", |a, b| flag(a != b)),
+        ("<", |a, b| flag(a < b)),
+        ("<=", |a, b| flag(a <= b)),
+        (">", |a, b| flag(a > b)),
+        (">=", |a, b| flag(a >= b)),
+        ("&", |a, b| flag(truth(a) && truth(b))),
+        ("|", |a, b| flag(truth(a) || truth(b))),
+        ("atan2", f64::atan2),
+        ("pow", f64::powf),
+        ("min", f64::min),
+        ("max", f64::max),
+    ];
+    for (name, f) in binary {
+        engine
+            .register_fn(name, move |a: Column, b: Column| a.zip(&b, f))
+            .register_fn(name, move |a: Column, b: FLOAT| a.map(|v| f(v, b)))
+            .register_fn(name, move |a: FLOAT, b: Column| b.map(|v| f(a, v)))
+            .register_fn(name, move |a: Column, b: INT| a.map(|v| f(v, b as f64)))
+            .register_fn(name, move |a: INT, b: Column| b.map(|v| f(a as f64, v)));
+    }
+
+    engine.register_fn(
+        "select",
+        |condition: Dynamic, a: Dynamic, b: Dynamic| -> Result<Dynamic> {
+            let condition = Value::from_dynamic(condition, "select() condition")?;
+            let a = Value::from_dynamic(a, "select() value")?;
+            let b = Value::from_dynamic(b, "select() value")?;
+            let lengths: Vec<usize> = [&condition, &a, &b]
+                .iter()
+                .filter_map(|v| v.len())
+                .collect();
+            let Some(&len) = lengths.first() else {
+                let picked = if truth(condition.at(0)) { a } else { b };
+                return Ok(Dynamic::from(picked.at(0)));
+            };
+            if lengths.iter().any(|&l| l != len) {
+                return Err("select() columns have different lengths".into());
+            }
+            let picked = (0..len)
+                .map(|i| {
+                    if truth(condition.at(i)) {
+                        a.at(i)
+                    } else {
+                        b.at(i)
+                    }
+                })
+                .collect();
+            Ok(Dynamic::from(Column(picked)))
+        },
+    );
+
+    for kind in ["diff", "deriv", "integral"] {
+        let s = state.clone();
+        engine.register_fn(kind, move |c: Column| -> Result<Column> {
+            let state = s.borrow();
+            let times = state.times()?;
+            if times.len() != c.0.len() {
+                return Err(format!("{}() needs a column of the current topic", kind).into());
+            }
+            Ok(Column(cumulative(kind, &c.0, times).into()))
+        });
+    }
+    let s = state.clone();
+    engine.register_fn("at", move |c: Column, time: Dynamic| -> Result<FLOAT> {
+        let time = number(time, "at() time")?;
+        let state = s.borrow();
+        let idx = state
+            .times()?
+            .partition_point(|&t| (t as f64) <= time)
+            .saturating_sub(1);
+        c.0.get(idx)
+            .copied()
+            .ok_or_else(|| "at() column is empty".into())
+    });
+
+    let aggregates: [(&str, Aggregate); 8] = [
+        ("mean", |v| v.iter().sum::<f64>() / v.len() as f64),
+        ("sum", |v| v.iter().sum()),
+        ("count", |v| v.len() as f64),
+        ("first", |v| *v.first().unwrap_or(&f64::NAN)),
+        ("last", |v| *v.last().unwrap_or(&f64::NAN)),
+        ("min", |v| v.iter().copied().fold(f64::NAN, f64::min)),
+        ("max", |v| v.iter().copied().fold(f64::NAN, f64::max)),
+        ("std", |v| {
+            let mean = v.iter().sum::<f64>() / v.len() as f64;
+            (v.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / v.len() as f64).sqrt()
+        }),
+    ];
+    for (name, f) in aggregates {
+        engine.register_fn(name, move |c: Column| f(&c.finite()));
+    }
+}
+
+/// Differences, time derivative or trapezoidal integral of `values` over `times`.
+fn cumulative(kind: &str, values: &[f64], times: &[f32]) -> Vec<f64> {
+    let mut out = Vec::with_capacity(values.len());
+    let mut total = 0.0;
+    for i in 0..values.len() {
+        if i == 0 {
+            out.push(0.0);
+            continue;
+        }
+        let dv = values[i] - values[i - 1];
+        let dt = (times[i] - times[i - 1]) as f64;
+        out.push(match kind {
+            "diff" => dv,
+            "deriv" if dt > 0.0 => dv / dt,
+            "deriv" => f64::NAN,
+            _ => {
+                total += (values[i] + values[i - 1]) * 0.5 * dt;
+                total
+            }
+        });
+    }
+    // The first derivative sample has no predecessor, repeat the next one.
+    if kind == "deriv" && out.len() > 1 {
+        out[0] = out[1];
+    }
+    out
+}
+
+fn summarize(values: &[f64]) -> String {
+    let finite: Vec<f64> = values.iter().copied().filter(|v| v.is_finite()).collect();
+    if finite.is_empty() {
+        return format!("{} samples, no finite values", values.len());
+    }
+    let min = finite.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = finite.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let mean = finite.iter().sum::<f64>() / finite.len() as f64;
+    format!(
+        "{} samples, min {:.6}, max {:.6}, mean {:.6}",
+        values.len(),
+        min,
+        max,
+        mean
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Host state owned by a test, with topic `a` sampled at 0, 1, 2 and 3 s.
+    struct Fixture {
+        data_store: DataStore,
+        annotations: Vec<Annotation>,
+        current_time: f32,
+        is_playing: bool,
+        playback_speed: f32,
+        min_time: f32,
+        max_time: f32,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            let mut data_store = DataStore::new();
+            data_store
+                .insert_topic("a", vec![0.0, 1.0, 2.0, 3.0])
+                .unwrap();
+            data_store
+                .set_column("a", "x", vec![0.0, 2.0, 4.0, 6.0])
+                .unwrap();
+            Self {
+                data_store,
+                annotations: Vec::new(),
+                current_time: 0.0,
+                is_playing: false,
+                playback_speed: 1.0,
+                min_time: 0.0,
+                max_time: 3.0,
+            }
+        }
+
+        fn run(&mut self, source: &str) -> ScriptOutput {
+            let mut host = ScriptHost {
+                data_store: &mut self.data_store,
+                annotations: &mut self.annotations,
+                current_time: &mut self.current_time,
+                is_playing: &mut self.is_playing,
+                playback_speed: &mut self.playback_speed,
+                min_time: &mut self.min_time,
+                max_time: &mut self.max_time,
+            };
+            run(source, &mut host)
+        }
+
+        fn column(&self, col: &str) -> Vec<f32> {
+            self.data_store.get_column("a", col).unwrap().clone()
+        }
+    }
+
+    fn print(expr: &str) -> String {
+        let output = Fixture::new().run(&format!("print({});", expr));
+        assert_eq!(output.error, None, "{}", expr);
+        output.lines.concat()
+    }
+
+    fn error(source: &str) -> String {
+        Fixture::new().run(source).error.expect(source)
+    }
+
+    #[test]
+    fn applies_operators_elementwise() {
+        let mut fixture = Fixture::new();
+        let output = fixture.run(
+            r#"use_topic("a");
+            let x = col("x");
+            set_col("y", x * 2 + 1);
+            set_col("z", (x > 1) & !(x > 5) | x == 0.0);
+            set_col("w", 10 - x ** 2 / 4);
+            set_col("c", 3);"#,
+        );
+        assert_eq!(output.error, None);
+        assert_eq!(fixture.column("y"), vec![1.0, 5.0, 9.0, 13.0]);
+        assert_eq!(fixture.column("z"), vec![1.0, 1.0, 1.0, 0.0]);
+        assert_eq!(fixture.column("w"), vec![10.0, 9.0, 6.0, 1.0]);
+        assert_eq!(fixture.column("c"), vec![3.0; 4]);
+    }
+
+    #[test]
+    fn select_picks_elementwise() {
+        let mut fixture = Fixture::new();
+        let output = fixture.run(
+            r#"use_topic("a");
+            set_col("y", select(col("x") > 2, col("x"), -1));
+            set_col("z", select(true, 5, col("x")));"#,
+        );
+        assert_eq!(output.error, None);
+        assert_eq!(fixture.column("y"), vec![-1.0, -1.0, 4.0, 6.0]);
+        assert_eq!(fixture.column("z"), vec![5.0; 4]);
+        assert_eq!(print("select(0, 1, 2)"), "2.0");
+    }
+
+    #[test]
+    fn computes_diff_deriv_and_integral_over_time() {
+        let mut fixture = Fixture::new();
+        let output = fixture.run(
+            r#"use_topic("a");
+            let x = col("x");
+            set_col("d", diff(x * x));
+            set_col("v", deriv(x));
+            set_col("i", integral(x));
+            set_col("w", deriv(time()));"#,
+        );
+        assert_eq!(output.error, None);
+        assert_eq!(fixture.column("d"), vec![0.0, 4.0, 12.0, 20.0]);
+        assert_eq!(fixture.column("v"), vec![2.0; 4]);
+        assert_eq!(fixture.column("i"), vec![0.0, 1.0, 4.0, 9.0]);
+        assert_eq!(fixture.column("w"), vec![1.0; 4]);
+    }
+
+    #[test]
+    fn aggregates_and_prints_columns() {
+        let mut fixture = Fixture::new();
+        let output = fixture.run(
+            r#"use_topic("a");
+            let x = col("x");
+            print(mean(x));
+            print(max(x) - min(x));
+            print(at(x, 1.5));
+            print(x);
+            print(columns());"#,
+        );
+        assert_eq!(output.error, None);
+        assert_eq!(
+            output.lines,
+            vec![
+                "3.0",
+                "6.0",
+                "2.0",
+                "4 samples, min 0.000000, max 6.000000, mean 3.000000",
+                "[\"x\"]",
+            ]
+        );
+    }
+
+    #[test]
+    fn runs_host_commands() {
+        let mut fixture = Fixture::new();
+        let output = fixture.run(
+            r#"use_topic("a");
+            print(mark(col("x") > 1, "up"));
+            annotate(0.5, "note");
+            seek(2);
+            speed(4);
+            view(1, 2.0);
+            set_col("b", "y", col("x"));"#,
+        );
+        assert_eq!(output.error, None);
+        let times: Vec<f32> = fixture.annotations.iter().map(|a| a.time).collect();
+        assert_eq!(times, vec![1.0, 0.5]);
+        assert_eq!(fixture.current_time, 2.0);
+        assert_eq!(fixture.playback_speed, 4.0);
+        assert_eq!((fixture.min_time, fixture.max_time), (1.0, 2.0));
+        assert_eq!(
+            fixture.data_store.get_column("b", "y").unwrap(),
+            &vec![0.0, 2.0, 4.0, 6.0]
+        );
+        assert_eq!(output.lines, vec!["1", "Created topic 'b'"]);
+    }
+
+    #[test]
+    fn interpolates_other_topics_onto_the_current_one() {
+        let mut fixture = Fixture::new();
+        fixture
+            .data_store
+            .insert_topic("slow", vec![-3.0, 3.0])
+            .unwrap();
+        fixture
+            .data_store
+            .set_column("slow", "v", vec![-30.0, 30.0])
+            .unwrap();
+        let output = fixture.run(r#"use_topic("a"); set_col("v", col("slow", "v"));"#);
+        assert_eq!(output.error, None);
+        assert_eq!(fixture.column("v"), vec![0.0, 10.0, 20.0, 30.0]);
+    }
+
+    #[test]
+    fn reports_the_failing_line_and_keeps_the_host_state() {
+        let mut fixture = Fixture::new();
+        let output = fixture.run(
+            "use_topic(\"a\");\n\n// comment\nset_col(\"y\", nope(col(\"x\")));\nset_col(\"z\", 1);",
+        );
+        let error = output.error.unwrap();
+        assert!(
+            error.contains("nope") && error.contains("line 4"),
+            "{}",
+            error
+        );
+        assert!(fixture.data_store.get_column("a", "x").is_some());
+        assert!(fixture.data_store.get_column("a", "z").is_none());
+    }
+
+    #[test]
+    fn reports_errors() {
+        let cases = [
+            (
+                "set_col(\"y\", 1);",
+                "Select a topic with use_topic() first",
+            ),
+            ("use_topic(\"missing\");", "Topic 'missing' not found"),
+            ("use_topic(\"a\"); col(\"y\");", "Column 'a/y' not found"),
+            (
+                "use_topic(\"a\"); view(2, 1);",
+                "View end must be after its start",
+            ),
+            (
+                "use_topic(\"a\"); annotate(col(\"x\"), \"n\");",
+                "Marker time must be a single value",
+            ),
+            (
+                "use_topic(\"a\"); set_col(\"y\", \"text\");",
+                "Column value must be a number or a column",
+            ),
+            (
+                "set_col(\"b\", \"y\", 1); use_topic(\"a\"); col(\"x\") + col(\"b\", \"y\");",
+                "Select a topic",
+            ),
+        ];
+        for (source, expected) in cases {
+            let error = error(source);
+            assert!(error.contains(expected), "{}: {}", source, error);
+        }
+
+        let mut fixture = Fixture::new();
+        fixture.data_store.insert_topic("b", vec![0.0]).unwrap();
+        let output =
+            fixture.run(r#"use_topic("a"); let x = col("x"); use_topic("b"); print(x + time());"#);
+        assert!(output.error.unwrap().contains("different lengths"));
+    }
+
+    #[test]
+    fn limits_expression_depth_and_run_time() {
+        let nested = |n: usize| format!("{}1{}", "(".repeat(n), ")".repeat(n));
+        assert_eq!(print(&nested(30)), "1");
+        assert!(error(&format!("print({});", nested(100_000))).contains("complexity"));
+        assert!(error("loop {}").contains("Too many operations"));
+    }
+}
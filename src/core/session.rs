@@ -0,0 +1,117 @@
+use super::{ColumnMeta, DataStore, Transform};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::Path;
+
+/// Separates the session name from the topic name in logs added next to the main one,
+/// e.g. `follower:vehicle_attitude`.
+pub const SESSION_SEPARATOR: char = ':';
+
+/// Name of `topic` as loaded from the added log session `session`.
+pub fn session_topic(session: &str, topic: &str) -> String {
+    format!("{}{}{}", session, SESSION_SEPARATOR, topic)
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Annotation {
+    pub time: f32,
+    pub text: String,
+}
+
+/// Header of the recorded log as sent by the loader: firmware and vehicle details and
+/// the parameters it was flown with.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct LogInfo {
+    /// Details such as `sys_name`, `ver_sw_release` or `sys_uuid`, by key.
+    #[serde(default)]
+    pub version_info: BTreeMap<String, String>,
+    #[serde(default)]
+    pub parameters: BTreeMap<String, String>,
+    /// Firmware defaults of `parameters`, if the log recorded them.
+    #[serde(default)]
+    pub parameter_defaults: BTreeMap<String, String>,
+}
+
+impl LogInfo {
+    pub fn is_empty(&self) -> bool {
+        self.version_info.is_empty() && self.parameters.is_empty()
+    }
+
+    /// Reads the log info of a saved data file or of a JSON export of the Log Info window.
+    pub fn read(path: &Path) -> anyhow::Result<LogInfo> {
+        if path
+            .extension()
+            .is_some_and(|e| e.eq_ignore_ascii_case("json"))
+        {
+            return Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?);
+        }
+        let session = DataStore::read_arrow_file(path, |_, _| {}, |_, _| {})?;
+        Ok(session.map(|s| s.log_info).unwrap_or_default())
+    }
+
+    /// Adds the entries of a later packet, replacing earlier values of the same keys.
+    pub fn merge(&mut self, other: LogInfo) {
+        self.version_info.extend(other.version_info);
+        self.parameters.extend(other.parameters);
+        self.parameter_defaults.extend(other.parameter_defaults);
+    }
+}
+
+/// A parameter whose value differs between two sets, `None` where a set lacks it.
+pub struct ParameterChange<'a> {
+    pub name: &'a str,
+    pub value: Option<&'a str>,
+    pub reference: Option<&'a str>,
+}
+
+/// Parameters of `values` that differ from `reference`, by name. Numbers compare by
+/// value, so `1` and `1.0` are the same.
+pub fn parameter_changes<'a>(
+    values: &'a BTreeMap<String, String>,
+    reference: &'a BTreeMap<String, String>,
+) -> Vec<ParameterChange<'a>> {
+    let names: BTreeSet<&String> = values.keys().chain(reference.keys()).collect();
+    names
+        .into_iter()
+        .map(|name| ParameterChange {
+            name,
+            value: values.get(name).map(String::as_str),
+            reference: reference.get(name).map(String::as_str),
+        })
+        .filter(|change| match (change.value, change.reference) {
+            (Some(a), Some(b)) => match (a.parse::<f64>(), b.parse::<f64>()) {
+                (Ok(a), Ok(b)) => a != b,
+                _ => a != b,
+            },
+            _ => true,
+        })
+        .collect()
+}
+
+/// Context stored alongside the raw data so a file can be reopened as it was left.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SessionMetadata {
+    #[serde(default)]
+    pub annotations: Vec<Annotation>,
+    /// Vehicles of the 3D view, kept as the JSON the view serializes them to.
+    #[serde(default)]
+    pub vehicles: Vec<serde_json::Value>,
+    /// topic -> column -> metadata
+    #[serde(default)]
+    pub columns: HashMap<String, HashMap<String, ColumnMeta>>,
+    #[serde(default)]
+    pub log_info: LogInfo,
+    /// Transforms by topic, whose outputs are recomputed on load rather than saved.
+    #[serde(default)]
+    pub transforms: HashMap<String, Vec<Transform>>,
+}
+
+impl SessionMetadata {
+    pub fn is_empty(&self) -> bool {
+        self.annotations.is_empty()
+            && self.vehicles.is_empty()
+            && self.columns.is_empty()
+            && self.log_info.is_empty()
+            && self.transforms.is_empty()
+    }
+}
@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+use tiplot_client::protocol::Compression;
+
+pub const DEFAULT_LISTEN_ADDR: &str = "127.0.0.1:9999";
+
+/// Preferences for receiving and storing data, kept in `settings.json` next to the UI ones.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DataSettings {
+    /// Address the TCP receiver binds to, applied on the next start.
+    pub listen_addr: String,
+    /// Seconds between checkpoints of live-streamed data, 0 to disable.
+    pub autosave_interval_secs: u32,
+    /// Codec of saved data files.
+    pub file_compression: Compression,
+    /// In-memory data budget in GB before cold topics are spilled to disk, 0 for no limit.
+    pub memory_budget_gb: f32,
+}
+
+impl Default for DataSettings {
+    fn default() -> Self {
+        Self {
+            listen_addr: DEFAULT_LISTEN_ADDR.to_string(),
+            autosave_interval_secs: 60,
+            file_compression: Compression::None,
+            memory_budget_gb: 0.0,
+        }
+    }
+}
+
+impl DataSettings {
+    /// The memory budget in bytes, `None` when unlimited.
+    pub fn memory_budget(&self) -> Option<usize> {
+        let budget_gb = self.memory_budget_gb as f64;
+        (budget_gb > 0.0).then_some((budget_gb * 1024.0 * 1024.0 * 1024.0) as usize)
+    }
+}
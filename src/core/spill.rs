@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static NEXT_CACHE_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Temporary on-disk storage for topics evicted from memory. Each topic is written to
+/// its own file as raw native-endian `f32` columns and removed again once restored.
+pub struct SpillCache {
+    dir: PathBuf,
+    files: HashMap<String, PathBuf>,
+    next_id: usize,
+}
+
+impl SpillCache {
+    pub fn new() -> Self {
+        Self {
+            dir: std::env::temp_dir().join(format!(
+                "tiplot-spill-{}-{}",
+                std::process::id(),
+                NEXT_CACHE_ID.fetch_add(1, Ordering::Relaxed)
+            )),
+            files: HashMap::new(),
+            next_id: 0,
+        }
+    }
+
+    pub fn contains(&self, topic: &str) -> bool {
+        self.files.contains_key(topic)
+    }
+
+    pub fn write(
+        &mut self,
+        topic: &str,
+        columns: &HashMap<String, Vec<f32>>,
+    ) -> anyhow::Result<()> {
+        std::fs::create_dir_all(&self.dir)?;
+
+        let path = self.dir.join(format!("{}.bin", self.next_id));
+        self.next_id += 1;
+
+        let mut writer = BufWriter::new(File::create(&path)?);
+        writer.write_all(&(columns.len() as u32).to_le_bytes())?;
+        for (name, values) in columns {
+            writer.write_all(&(name.len() as u32).to_le_bytes())?;
+            writer.write_all(name.as_bytes())?;
+            writer.write_all(&(values.len() as u64).to_le_bytes())?;
+            writer.write_all(bytemuck::cast_slice(values))?;
+        }
+        writer.flush()?;
+
+        if let Some(old) = self.files.insert(topic.to_string(), path) {
+            std::fs::remove_file(old).ok();
+        }
+
+        Ok(())
+    }
+
+    /// Reads a spilled topic back without removing it from disk.
+    pub fn read(&self, topic: &str) -> anyhow::Result<HashMap<String, Vec<f32>>> {
+        let path = self
+            .files
+            .get(topic)
+            .ok_or_else(|| anyhow::anyhow!("Topic '{}' is not spilled", topic))?;
+
+        let mut reader = BufReader::new(File::open(path)?);
+        let mut buf4 = [0u8; 4];
+        let mut buf8 = [0u8; 8];
+
+        reader.read_exact(&mut buf4)?;
+        let count = u32::from_le_bytes(buf4) as usize;
+
+        let mut columns = HashMap::with_capacity(count);
+        for _ in 0..count {
+            reader.read_exact(&mut buf4)?;
+            let mut name = vec![0u8; u32::from_le_bytes(buf4) as usize];
+            reader.read_exact(&mut name)?;
+
+            reader.read_exact(&mut buf8)?;
+            let mut values = vec![0.0f32; u64::from_le_bytes(buf8) as usize];
+            reader.read_exact(bytemuck::cast_slice_mut(&mut values))?;
+
+            columns.insert(String::from_utf8(name)?, values);
+        }
+
+        Ok(columns)
+    }
+
+    /// Reads a spilled topic back and deletes its file.
+    pub fn take(&mut self, topic: &str) -> anyhow::Result<HashMap<String, Vec<f32>>> {
+        let columns = self.read(topic)?;
+        if let Some(path) = self.files.remove(topic) {
+            std::fs::remove_file(path).ok();
+        }
+        Ok(columns)
+    }
+
+    pub fn remove(&mut self, topic: &str) {
+        if let Some(path) = self.files.remove(topic) {
+            std::fs::remove_file(path).ok();
+        }
+    }
+}
+
+impl Default for SpillCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for SpillCache {
+    fn drop(&mut self) {
+        for path in self.files.values() {
+            std::fs::remove_file(path).ok();
+        }
+        std::fs::remove_dir(&self.dir).ok();
+    }
+}
@@ -0,0 +1,161 @@
+/// Samples covered by one level-0 chunk.
+const BASE_CHUNK: usize = 64;
+/// Chunks of one level merged into a single chunk of the next level.
+const LEVEL_FACTOR: usize = 16;
+
+#[derive(Clone, Default)]
+struct Level {
+    min: Vec<f32>,
+    max: Vec<f32>,
+    sum: Vec<f64>,
+    count: Vec<u32>,
+}
+
+impl Level {
+    fn len(&self) -> usize {
+        self.min.len()
+    }
+
+    fn push(&mut self, min: f32, max: f32, sum: f64, count: u32) {
+        self.min.push(min);
+        self.max.push(max);
+        self.sum.push(sum);
+        self.count.push(count);
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct RangeStats {
+    pub min: f32,
+    pub max: f32,
+    pub mean: f32,
+    pub count: usize,
+}
+
+struct Accumulator {
+    min: f32,
+    max: f32,
+    sum: f64,
+    count: usize,
+}
+
+impl Accumulator {
+    fn new() -> Self {
+        Self {
+            min: f32::MAX,
+            max: f32::MIN,
+            sum: 0.0,
+            count: 0,
+        }
+    }
+
+    fn add_value(&mut self, v: f32) {
+        if v.is_finite() {
+            self.min = self.min.min(v);
+            self.max = self.max.max(v);
+            self.sum += v as f64;
+            self.count += 1;
+        }
+    }
+
+    fn add_chunk(&mut self, level: &Level, idx: usize) {
+        if level.count[idx] > 0 {
+            self.min = self.min.min(level.min[idx]);
+            self.max = self.max.max(level.max[idx]);
+            self.sum += level.sum[idx];
+            self.count += level.count[idx] as usize;
+        }
+    }
+
+    fn finish(self) -> Option<RangeStats> {
+        (self.count > 0).then(|| RangeStats {
+            min: self.min,
+            max: self.max,
+            mean: (self.sum / self.count as f64) as f32,
+            count: self.count,
+        })
+    }
+}
+
+/// Min/max/mean pyramid over fixed-size chunks of a column, grown incrementally as
+/// samples are appended. Only complete chunks are summarized; partial edges of a
+/// queried range are read from the raw samples.
+#[derive(Clone, Default)]
+pub struct ColumnSummary {
+    levels: Vec<Level>,
+}
+
+impl ColumnSummary {
+    fn chunk_size(level: usize) -> usize {
+        BASE_CHUNK * LEVEL_FACTOR.pow(level as u32)
+    }
+
+    /// Summarizes any chunks completed since the last call.
+    pub fn update(&mut self, values: &[f32]) {
+        let covered = self.levels.first().map_or(0, |l| l.len() * BASE_CHUNK);
+        if self.levels.is_empty() || covered > values.len() {
+            self.levels = vec![Level::default()];
+        }
+
+        let base = &mut self.levels[0];
+        while (base.len() + 1) * BASE_CHUNK <= values.len() {
+            let start = base.len() * BASE_CHUNK;
+            let mut acc = Accumulator::new();
+            for &v in &values[start..start + BASE_CHUNK] {
+                acc.add_value(v);
+            }
+            base.push(acc.min, acc.max, acc.sum, acc.count as u32);
+        }
+
+        let mut level = 1;
+        while self.levels[level - 1].len() >= LEVEL_FACTOR {
+            if self.levels.len() == level {
+                self.levels.push(Level::default());
+            }
+
+            let (lower, upper) = self.levels.split_at_mut(level);
+            let below = &lower[level - 1];
+            let current = &mut upper[0];
+
+            while (current.len() + 1) * LEVEL_FACTOR <= below.len() {
+                let start = current.len() * LEVEL_FACTOR;
+                let mut acc = Accumulator::new();
+                for idx in start..start + LEVEL_FACTOR {
+                    acc.add_chunk(below, idx);
+                }
+                current.push(acc.min, acc.max, acc.sum, acc.count as u32);
+            }
+
+            level += 1;
+        }
+    }
+
+    /// Statistics over `values[start..end]`, ignoring non-finite samples.
+    pub fn range_stats(&self, values: &[f32], start: usize, end: usize) -> Option<RangeStats> {
+        let end = end.min(values.len());
+        let mut acc = Accumulator::new();
+        let mut i = start;
+
+        while i < end {
+            let mut used_chunk = false;
+
+            for level in (0..self.levels.len()).rev() {
+                let size = Self::chunk_size(level);
+                let idx = i / size;
+                if i.is_multiple_of(size) && i + size <= end && idx < self.levels[level].len() {
+                    acc.add_chunk(&self.levels[level], idx);
+                    i += size;
+                    used_chunk = true;
+                    break;
+                }
+            }
+
+            if !used_chunk {
+                acc.add_value(values[i]);
+                i += 1;
+            }
+        }
+
+        acc.finish()
+    }
+}
@@ -0,0 +1,246 @@
+use super::geo::gps_to_ned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Quaternion column names recognised when offering the Euler transform, as (w, x, y, z).
+const QUATERNION_PATTERNS: [[&str; 4]; 3] = [
+    ["q[0]", "q[1]", "q[2]", "q[3]"],
+    ["qw", "qx", "qy", "qz"],
+    ["q_w", "q_x", "q_y", "q_z"],
+];
+
+/// GPS column names recognised when offering the NED transform, as (lat, lon, alt). PX4's
+/// `vehicle_gps_position` logs `lat`/`lon` as integer 1e-7 degrees and `alt` as integer
+/// millimetres under the first names; see [`gps_scale`].
+const GPS_PATTERNS: [[&str; 3]; 2] = [
+    ["lat", "lon", "alt"],
+    ["latitude_deg", "longitude_deg", "altitude_msl_m"],
+];
+
+/// A pointwise computation producing new columns of a topic from existing ones. Outputs
+/// are extended as the inputs grow, so transforms keep working on live data.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Transform {
+    QuaternionToEuler {
+        w: String,
+        x: String,
+        y: String,
+        z: String,
+    },
+    /// Local north/east/down position relative to `reference`, which is taken from the
+    /// first fix when the transform is first evaluated.
+    GpsToNed {
+        lat: String,
+        lon: String,
+        alt: String,
+        reference: Option<(f64, f64, f64)>,
+    },
+}
+
+impl Transform {
+    /// Builds every transform whose input columns are present among `columns`.
+    pub fn detect<S: AsRef<str>>(columns: &[S]) -> Vec<Transform> {
+        let has = |name: &str| columns.iter().any(|c| c.as_ref() == name);
+
+        let quaternion = QUATERNION_PATTERNS
+            .iter()
+            .find(|pattern| pattern.iter().all(|name| has(name)))
+            .map(|[w, x, y, z]| Transform::QuaternionToEuler {
+                w: w.to_string(),
+                x: x.to_string(),
+                y: y.to_string(),
+                z: z.to_string(),
+            });
+        let gps = GPS_PATTERNS
+            .iter()
+            .find(|pattern| pattern.iter().all(|name| has(name)))
+            .map(|[lat, lon, alt]| Transform::GpsToNed {
+                lat: lat.to_string(),
+                lon: lon.to_string(),
+                alt: alt.to_string(),
+                reference: None,
+            });
+
+        quaternion.into_iter().chain(gps).collect()
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Transform::QuaternionToEuler { .. } => "Euler Angles",
+            Transform::GpsToNed { .. } => "Local NED",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Transform::QuaternionToEuler { .. } => {
+                "Compute roll, pitch and yaw from the quaternion"
+            }
+            Transform::GpsToNed { .. } => {
+                "Compute north, east and down relative to the first GPS fix"
+            }
+        }
+    }
+
+    pub fn inputs(&self) -> Vec<&str> {
+        match self {
+            Transform::QuaternionToEuler { w, x, y, z } => vec![w, x, y, z],
+            Transform::GpsToNed { lat, lon, alt, .. } => vec![lat, lon, alt],
+        }
+    }
+
+    /// Output column names and their units.
+    pub fn outputs(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Transform::QuaternionToEuler { .. } => &[
+                ("euler_roll", "rad"),
+                ("euler_pitch", "rad"),
+                ("euler_yaw", "rad"),
+            ],
+            Transform::GpsToNed { .. } => {
+                &[("ned_north", "m"), ("ned_east", "m"), ("ned_down", "m")]
+            }
+        }
+    }
+
+    /// Whether both transforms compute the same outputs from the same inputs.
+    pub fn same_as(&self, other: &Transform) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+            && self.inputs() == other.inputs()
+    }
+
+    /// Appends outputs for input samples not yet covered by the output columns.
+    pub fn extend(&mut self, cols: &mut HashMap<String, Vec<f32>>) {
+        let inputs: Option<Vec<&Vec<f32>>> = self.inputs().iter().map(|n| cols.get(*n)).collect();
+        let Some(inputs) = inputs else {
+            return;
+        };
+
+        let len = inputs.iter().map(|v| v.len()).min().unwrap_or(0);
+        let start = self
+            .outputs()
+            .iter()
+            .map(|(name, _)| cols.get(*name).map_or(0, |v| v.len()))
+            .min()
+            .unwrap_or(0);
+        if start >= len {
+            return;
+        }
+
+        let mut results: Vec<Vec<f32>> =
+            vec![Vec::with_capacity(len - start); self.outputs().len()];
+        match self {
+            Transform::QuaternionToEuler { .. } => {
+                let (w, x, y, z) = (inputs[0], inputs[1], inputs[2], inputs[3]);
+                for (roll, pitch, yaw) in
+                    (start..len).map(|i| quaternion_to_euler(w[i], x[i], y[i], z[i]))
+                {
+                    results[0].push(roll);
+                    results[1].push(pitch);
+                    results[2].push(yaw);
+                }
+            }
+            Transform::GpsToNed { reference, .. } => {
+                let (lat, lon, alt) = (inputs[0], inputs[1], inputs[2]);
+                let origin = (0..len).find(|&i| is_fix(lat[i], lon[i])).map(|i| {
+                    let (angle_scale, alt_scale) = gps_scale(lat[i], lon[i]);
+                    let reference = *reference.get_or_insert_with(|| {
+                        (
+                            lat[i] as f64 * angle_scale,
+                            lon[i] as f64 * angle_scale,
+                            alt[i] as f64 * alt_scale,
+                        )
+                    });
+                    ((angle_scale, alt_scale), reference)
+                });
+                for ned in (start..len).map(|i| match origin {
+                    Some(((angle_scale, alt_scale), (lat_ref, lon_ref, alt_ref)))
+                        if is_fix(lat[i], lon[i]) =>
+                    {
+                        gps_to_ned(
+                            lat[i] as f64 * angle_scale,
+                            lon[i] as f64 * angle_scale,
+                            alt[i] as f64 * alt_scale,
+                            lat_ref,
+                            lon_ref,
+                            alt_ref,
+                        )
+                    }
+                    _ => glam::Vec3::NAN,
+                }) {
+                    results[0].push(ned.x);
+                    results[1].push(ned.y);
+                    results[2].push(ned.z);
+                }
+            }
+        }
+
+        for ((name, _), values) in self.outputs().iter().zip(results) {
+            let column = cols.entry(name.to_string()).or_default();
+            column.truncate(start);
+            column.extend(values);
+        }
+    }
+}
+
+/// Whether a GPS sample holds a position. Receivers log (0, 0) or NaN until they have
+/// a fix, which must not be taken as the origin or be placed at it.
+fn is_fix(lat: f32, lon: f32) -> bool {
+    lat.is_finite() && lon.is_finite() && (lat != 0.0 || lon != 0.0)
+}
+
+/// Factors converting the GPS columns to degrees and metres, as (angle, altitude). Fixes
+/// logged as integer 1e-7 degrees and millimetres are recognised by a first fix outside
+/// the range of degrees.
+fn gps_scale(lat: f32, lon: f32) -> (f64, f64) {
+    if lat.abs() > 90.0 || lon.abs() > 180.0 {
+        (1e-7, 1e-3)
+    } else {
+        (1.0, 1.0)
+    }
+}
+
+/// Roll, pitch and yaw (ZYX convention) of a unit quaternion, in radians.
+fn quaternion_to_euler(w: f32, x: f32, y: f32, z: f32) -> (f32, f32, f32) {
+    let roll = (2.0 * (w * x + y * z)).atan2(1.0 - 2.0 * (x * x + y * y));
+    let pitch = (2.0 * (w * y - z * x)).clamp(-1.0, 1.0).asin();
+    let yaw = (2.0 * (w * z + x * y)).atan2(1.0 - 2.0 * (y * y + z * z));
+    (roll, pitch, yaw)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gps_origin_skips_samples_without_a_fix() {
+        let mut transform = Transform::detect(&["lat", "lon", "alt"]).remove(0);
+        let mut cols = HashMap::from([
+            // Integer 1e-7 degrees and millimetres, as logged by PX4.
+            ("lat".to_string(), vec![0.0, 473_977_420.0, 473_986_420.0]),
+            ("lon".to_string(), vec![0.0, 85_455_940.0, 85_455_940.0]),
+            ("alt".to_string(), vec![0.0, 488_000.0, 478_000.0]),
+        ]);
+        transform.extend(&mut cols);
+
+        assert!(cols["ned_north"][0].is_nan());
+        assert_eq!(cols["ned_north"][1], 0.0);
+        // 0.0009 degrees of latitude north and 10 m lower.
+        assert!(
+            (cols["ned_north"][2] - 100.1).abs() < 0.5,
+            "{}",
+            cols["ned_north"][2]
+        );
+        assert!(cols["ned_east"][2].abs() < 0.01);
+        assert!((cols["ned_down"][2] - 10.0).abs() < 0.01);
+        let Transform::GpsToNed {
+            reference: Some((lat, lon, alt)),
+            ..
+        } = transform
+        else {
+            panic!("no reference in {:?}", transform);
+        };
+        assert!((lat - 47.39774).abs() < 1e-5 && (lon - 8.54559).abs() < 1e-5);
+        assert_eq!(alt, 488.0);
+    }
+}
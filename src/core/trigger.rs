@@ -0,0 +1,226 @@
+use super::DataStore;
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Crossing {
+    Rising,
+    Falling,
+    Either,
+}
+
+impl Crossing {
+    pub const ALL: [Crossing; 3] = [Crossing::Rising, Crossing::Falling, Crossing::Either];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Crossing::Rising => "rises above",
+            Crossing::Falling => "falls below",
+            Crossing::Either => "crosses",
+        }
+    }
+
+    fn matches(&self, prev: f32, value: f32, threshold: f32) -> bool {
+        let rising = prev < threshold && value >= threshold;
+        let falling = prev > threshold && value <= threshold;
+        match self {
+            Crossing::Rising => rising,
+            Crossing::Falling => falling,
+            Crossing::Either => rising || falling,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TriggerCondition {
+    /// A column crosses a threshold, compared in the column's display unit.
+    Threshold {
+        topic: String,
+        col: String,
+        threshold: f32,
+        crossing: Crossing,
+    },
+    /// A topic receives no new samples for a while.
+    Stale { topic: String, timeout_secs: f32 },
+}
+
+impl TriggerCondition {
+    pub fn topic(&self) -> &str {
+        match self {
+            TriggerCondition::Threshold { topic, .. } | TriggerCondition::Stale { topic, .. } => {
+                topic
+            }
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            TriggerCondition::Threshold {
+                topic,
+                col,
+                threshold,
+                crossing,
+            } => format!("{}/{} {} {}", topic, col, crossing.label(), threshold),
+            TriggerCondition::Stale {
+                topic,
+                timeout_secs,
+            } => format!("{} silent for {} s", topic, timeout_secs),
+        }
+    }
+}
+
+/// What happens when a trigger fires.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TriggerActions {
+    pub annotate: bool,
+    /// Stop the viewport from following new data.
+    pub freeze_view: bool,
+    pub pause_stream: bool,
+}
+
+impl Default for TriggerActions {
+    fn default() -> Self {
+        Self {
+            annotate: true,
+            freeze_view: false,
+            pause_stream: false,
+        }
+    }
+}
+
+/// A condition watched on live data. Only samples arriving after the trigger was armed can
+/// fire it.
+#[derive(Clone, Debug)]
+pub struct Trigger {
+    pub condition: TriggerCondition,
+    pub actions: TriggerActions,
+    pub enabled: bool,
+    /// Minimum time between two firings, in seconds of data time.
+    pub holdoff_secs: f32,
+    pub fire_count: usize,
+    pub last_fired: Option<f32>,
+
+    /// Timestamp of the newest sample already checked.
+    last_time: Option<f32>,
+    last_value: Option<f32>,
+    /// Wall-clock time the watched topic last grew, for stale conditions.
+    last_change: Instant,
+    stale: bool,
+}
+
+impl Trigger {
+    pub fn new(condition: TriggerCondition, actions: TriggerActions) -> Self {
+        Self {
+            condition,
+            actions,
+            enabled: true,
+            holdoff_secs: 1.0,
+            fire_count: 0,
+            last_fired: None,
+            last_time: None,
+            last_value: None,
+            last_change: Instant::now(),
+            stale: false,
+        }
+    }
+
+    /// Starts watching from the newest sample, so data already received cannot fire.
+    pub fn arm(&mut self, data_store: &DataStore) {
+        let times = data_store.get_column(self.condition.topic(), "timestamp");
+        self.last_time = times.and_then(|t| t.last().copied());
+        self.last_value = match &self.condition {
+            TriggerCondition::Threshold { topic, col, .. } => data_store
+                .get_column(topic, col)
+                .and_then(|values| values.last().copied())
+                .filter(|v| !v.is_nan())
+                .map(|v| v * Self::scale(data_store, topic, col)),
+            TriggerCondition::Stale { .. } => None,
+        };
+        self.last_change = Instant::now();
+        self.stale = false;
+    }
+
+    fn scale(data_store: &DataStore, topic: &str, col: &str) -> f32 {
+        data_store.column_meta(topic, col).map_or(1.0, |m| m.scale)
+    }
+
+    /// Checks the samples that arrived since the last poll and returns the data times the
+    /// trigger fired at.
+    pub fn poll(&mut self, data_store: &DataStore, now: Instant) -> Vec<f32> {
+        if !self.enabled {
+            return Vec::new();
+        }
+
+        let fired = match &self.condition {
+            TriggerCondition::Threshold {
+                topic,
+                col,
+                threshold,
+                crossing,
+            } => {
+                let (Some(times), Some(values)) = (
+                    data_store.get_column(topic, "timestamp"),
+                    data_store.get_column(topic, col),
+                ) else {
+                    return Vec::new();
+                };
+                let scale = Self::scale(data_store, topic, col);
+                let n = times.len().min(values.len());
+                let start = match self.last_time {
+                    Some(last) => times[..n].partition_point(|&t| t <= last),
+                    None => 0,
+                };
+
+                let mut fired = Vec::new();
+                for i in start..n {
+                    let value = values[i] * scale;
+                    if value.is_nan() {
+                        continue;
+                    }
+                    let held_off = self
+                        .last_fired
+                        .is_some_and(|t| times[i] - t < self.holdoff_secs);
+                    if let Some(prev) = self.last_value {
+                        if !held_off && crossing.matches(prev, value, *threshold) {
+                            fired.push(times[i]);
+                            self.last_fired = Some(times[i]);
+                        }
+                    }
+                    self.last_value = Some(value);
+                }
+                if n > 0 {
+                    self.last_time = Some(times[n - 1]);
+                }
+                fired
+            }
+            TriggerCondition::Stale {
+                topic,
+                timeout_secs,
+            } => {
+                let last = data_store
+                    .get_column(topic, "timestamp")
+                    .and_then(|t| t.last().copied());
+                if last != self.last_time {
+                    self.last_time = last;
+                    self.last_change = now;
+                    self.stale = false;
+                }
+
+                let timeout = Duration::from_secs_f32(timeout_secs.max(0.0));
+                match last {
+                    Some(last)
+                        if !self.stale
+                            && now.saturating_duration_since(self.last_change) >= timeout =>
+                    {
+                        self.stale = true;
+                        self.last_fired = Some(last);
+                        vec![last]
+                    }
+                    _ => Vec::new(),
+                }
+            }
+        };
+
+        self.fire_count += fired.len();
+        fired
+    }
+}
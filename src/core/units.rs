@@ -0,0 +1,261 @@
+use serde::{Deserialize, Serialize};
+
+/// Descriptive metadata for a single column. `scale` converts stored values into `unit`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ColumnMeta {
+    pub unit: String,
+    pub scale: f32,
+    pub description: String,
+    pub format: ValueFormat,
+}
+
+impl Default for ColumnMeta {
+    fn default() -> Self {
+        Self {
+            unit: String::new(),
+            scale: 1.0,
+            description: String::new(),
+            format: ValueFormat::default(),
+        }
+    }
+}
+
+impl ColumnMeta {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Notation {
+    /// A fixed number of decimals.
+    #[default]
+    Fixed,
+    Significant,
+    Scientific,
+}
+
+impl Notation {
+    pub const ALL: [Notation; 3] = [Notation::Fixed, Notation::Significant, Notation::Scientific];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Notation::Fixed => "Decimals",
+            Notation::Significant => "Significant Digits",
+            Notation::Scientific => "Scientific",
+        }
+    }
+}
+
+/// How values of a column are printed in tooltips and the topic panel.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ValueFormat {
+    pub notation: Notation,
+    pub digits: usize,
+    pub show_unit: bool,
+    /// Names of integer values, e.g. the states of a mode enum.
+    pub labels: Vec<(i64, String)>,
+}
+
+impl Default for ValueFormat {
+    fn default() -> Self {
+        Self {
+            notation: Notation::Fixed,
+            digits: 4,
+            show_unit: true,
+            labels: Vec::new(),
+        }
+    }
+}
+
+impl ValueFormat {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn format(&self, value: f32, unit: Option<&str>) -> String {
+        if value.fract() == 0.0 {
+            if let Some((_, label)) = self.labels.iter().find(|(v, _)| *v as f32 == value) {
+                return format!("{} ({})", label, value);
+            }
+        }
+
+        let digits = self.digits;
+        let text = match self.notation {
+            Notation::Fixed => format!("{:.*}", digits, value),
+            Notation::Scientific => format!("{:.*e}", digits, value),
+            Notation::Significant if value == 0.0 || !value.is_finite() => value.to_string(),
+            Notation::Significant => {
+                let magnitude = value.abs().log10().floor() as i32;
+                let decimals = (digits.max(1) as i32 - 1 - magnitude).max(0) as usize;
+                format!("{:.*}", decimals, value)
+            }
+        };
+        match unit.filter(|_| self.show_unit) {
+            Some(unit) => format!("{} {}", text, unit),
+            None => text,
+        }
+    }
+
+    /// Labels as edited in the UI: `0=Disarmed, 1=Armed`.
+    pub fn labels_text(&self) -> String {
+        self.labels
+            .iter()
+            .map(|(value, label)| format!("{}={}", value, label))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Parses labels written as `value=label` pairs separated by commas, skipping
+    /// malformed entries.
+    pub fn set_labels_text(&mut self, text: &str) {
+        self.labels = text
+            .split(',')
+            .filter_map(|entry| {
+                let (value, label) = entry.split_once('=')?;
+                let label = label.trim();
+                Some((value.trim().parse().ok()?, label.to_string())).filter(|_| !label.is_empty())
+            })
+            .collect();
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum UnitConversion {
+    RadToDeg,
+    DegToRad,
+    MpsToKnots,
+    KnotsToMps,
+    MpsToKmh,
+    MetersToFeet,
+    FeetToMeters,
+}
+
+impl UnitConversion {
+    pub const ALL: [UnitConversion; 7] = [
+        UnitConversion::RadToDeg,
+        UnitConversion::DegToRad,
+        UnitConversion::MpsToKnots,
+        UnitConversion::KnotsToMps,
+        UnitConversion::MpsToKmh,
+        UnitConversion::MetersToFeet,
+        UnitConversion::FeetToMeters,
+    ];
+
+    pub fn source_unit(&self) -> &'static str {
+        match self {
+            UnitConversion::RadToDeg => "rad",
+            UnitConversion::DegToRad => "deg",
+            UnitConversion::MpsToKnots | UnitConversion::MpsToKmh => "m/s",
+            UnitConversion::KnotsToMps => "kt",
+            UnitConversion::MetersToFeet => "m",
+            UnitConversion::FeetToMeters => "ft",
+        }
+    }
+
+    pub fn target_unit(&self) -> &'static str {
+        match self {
+            UnitConversion::RadToDeg => "deg",
+            UnitConversion::DegToRad => "rad",
+            UnitConversion::MpsToKnots => "kt",
+            UnitConversion::KnotsToMps => "m/s",
+            UnitConversion::MpsToKmh => "km/h",
+            UnitConversion::MetersToFeet => "ft",
+            UnitConversion::FeetToMeters => "m",
+        }
+    }
+
+    pub fn factor(&self) -> f32 {
+        match self {
+            UnitConversion::RadToDeg => 180.0 / std::f32::consts::PI,
+            UnitConversion::DegToRad => std::f32::consts::PI / 180.0,
+            UnitConversion::MpsToKnots => 1.943_844,
+            UnitConversion::KnotsToMps => 1.0 / 1.943_844,
+            UnitConversion::MpsToKmh => 3.6,
+            UnitConversion::MetersToFeet => 1.0 / 0.3048,
+            UnitConversion::FeetToMeters => 0.3048,
+        }
+    }
+
+    pub fn label(&self) -> String {
+        format!("{} → {}", self.source_unit(), self.target_unit())
+    }
+
+    /// Conversions that apply to values measured in `unit`.
+    pub fn available_for(unit: &str) -> Vec<UnitConversion> {
+        let unit = normalize_unit(unit);
+        Self::ALL
+            .into_iter()
+            .filter(|c| c.source_unit() == unit)
+            .collect()
+    }
+}
+
+/// Unit an angle column is stored in, which sets the period it wraps around at.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum AngleUnit {
+    Radians,
+    Degrees,
+}
+
+impl AngleUnit {
+    /// Best guess from the unit in the column metadata, radians unless it says degrees.
+    pub fn from_unit(unit: &str) -> Self {
+        match normalize_unit(unit) {
+            "deg" => AngleUnit::Degrees,
+            _ => AngleUnit::Radians,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AngleUnit::Radians => "rad",
+            AngleUnit::Degrees => "deg",
+        }
+    }
+
+    pub fn period(&self) -> f32 {
+        match self {
+            AngleUnit::Radians => std::f32::consts::TAU,
+            AngleUnit::Degrees => 360.0,
+        }
+    }
+
+    /// Removes the jumps of a wrapped angle by adding whole turns wherever consecutive
+    /// samples differ by more than half a turn. NaN samples are kept and skipped over.
+    pub fn unwrap(&self, values: &[f32]) -> Vec<f32> {
+        let period = self.period() as f64;
+        let mut turns = 0.0f64;
+        let mut prev: Option<f64> = None;
+
+        values
+            .iter()
+            .map(|&v| {
+                if v.is_nan() {
+                    return v;
+                }
+                let v = v as f64;
+                if let Some(prev) = prev {
+                    turns -= ((v - prev) / period).round();
+                }
+                prev = Some(v);
+                (v + turns * period) as f32
+            })
+            .collect()
+    }
+}
+
+/// Maps common spellings of a unit onto the canonical names used by conversions.
+fn normalize_unit(unit: &str) -> &str {
+    match unit.trim() {
+        "rad" | "radian" | "radians" => "rad",
+        "deg" | "degree" | "degrees" | "°" => "deg",
+        "m/s" | "m s^-1" | "mps" | "m.s-1" => "m/s",
+        "kt" | "kn" | "knot" | "knots" => "kt",
+        "m" | "meter" | "meters" | "metre" | "metres" => "m",
+        "ft" | "foot" | "feet" => "ft",
+        other => other,
+    }
+}
@@ -0,0 +1,801 @@
+//! `tiplot render`: loads a data file, applies a layout and writes one PNG per plot plus
+//! per-trace statistics, without opening a window. Plots are painted with egui shapes
+//! and rasterized on the CPU so no GPU or display is needed.
+
+use crate::core::DataStore;
+use crate::ui::calculate_grid_step;
+use crate::ui::layout::LayoutData;
+use crate::ui::settings::Settings;
+use crate::ui::tiles::plot_tile::MathOp;
+use crate::ui::tiles::{y_sync_ranges, PlotTile, YSyncRanges};
+use anyhow::{bail, Context, Result};
+use egui::epaint::text::{FontDefinitions, Fonts};
+use egui::epaint::{
+    ClippedShape, Color32, FontId, Mesh, Primitive, Shape, Stroke, TessellationOptions, Tessellator,
+};
+use egui::{pos2, vec2, Align2, Pos2, Rect};
+use egui_tiles::{Tile, TileId, Tree};
+use serde::Serialize;
+use std::fmt::Write as _;
+use std::path::PathBuf;
+
+const USAGE: &str = "\
+Usage: tiplot render --layout <file|name> --data <file.arrow> [options]
+
+Options:
+  --out <dir>        Output directory (default: report)
+  --size <WxH>       Size of each plot image in pixels (default: 1600x500)
+  --from <seconds>   Start of the rendered time range
+  --to <seconds>     End of the rendered time range";
+
+struct RenderArgs {
+    layout: String,
+    data: PathBuf,
+    out: PathBuf,
+    size: [u32; 2],
+    from: Option<f32>,
+    to: Option<f32>,
+}
+
+impl RenderArgs {
+    fn parse(args: &[String]) -> Result<Self> {
+        let mut layout = None;
+        let mut data = None;
+        let mut out = PathBuf::from("report");
+        let mut size = [1600, 500];
+        let mut from = None;
+        let mut to = None;
+
+        let mut iter = args.iter();
+        while let Some(flag) = iter.next() {
+            let value = iter
+                .next()
+                .with_context(|| format!("Missing value for {}\n\n{}", flag, USAGE))?;
+            match flag.as_str() {
+                "--layout" => layout = Some(value.clone()),
+                "--data" => data = Some(PathBuf::from(value)),
+                "--out" => out = PathBuf::from(value),
+                "--size" => {
+                    let (w, h) = value
+                        .split_once('x')
+                        .context("--size expects WIDTHxHEIGHT")?;
+                    size = [w.parse()?, h.parse()?];
+                    if size[0] < 100 || size[1] < 100 {
+                        bail!("--size must be at least 100x100");
+                    }
+                }
+                "--from" => from = Some(value.parse().context("--from expects seconds")?),
+                "--to" => to = Some(value.parse().context("--to expects seconds")?),
+                _ => bail!("Unknown option '{}'\n\n{}", flag, USAGE),
+            }
+        }
+
+        Ok(Self {
+            layout: layout.with_context(|| format!("--layout is required\n\n{}", USAGE))?,
+            data: data.with_context(|| format!("--data is required\n\n{}", USAGE))?,
+            out,
+            size,
+            from,
+            to,
+        })
+    }
+}
+
+#[derive(Serialize)]
+struct TraceStats {
+    plot: usize,
+    topic: String,
+    column: String,
+    unit: Option<String>,
+    samples: usize,
+    min: Option<f32>,
+    max: Option<f32>,
+    mean: Option<f32>,
+}
+
+#[derive(Serialize)]
+struct Report {
+    data: PathBuf,
+    layout: String,
+    time_range: [f32; 2],
+    plots: Vec<String>,
+    traces: Vec<TraceStats>,
+}
+
+pub fn run(args: &[String]) -> Result<()> {
+    if args.iter().any(|a| a == "--help" || a == "-h") {
+        println!("{}", USAGE);
+        return Ok(());
+    }
+    let args = RenderArgs::parse(args)?;
+
+    let layout_path = resolve_layout(&args.layout)?;
+    let layout = LayoutData::load_from_file(&layout_path)?;
+    let mut trees = layout.to_trees()?;
+    tracing::info!("Layout loaded from: {}", layout_path.display());
+
+    let mut data_store = DataStore::new();
+    let session = DataStore::read_arrow_file(
+        &args.data,
+        |topic, batch| data_store.ingest(topic.to_string(), batch),
+        |_, _| {},
+    )
+    .with_context(|| format!("Failed to load {}", args.data.display()))?;
+    if let Some(session) = session {
+        data_store.column_meta = session.columns;
+    }
+    tracing::info!("Data loaded from: {}", args.data.display());
+
+    let (data_min, data_max) = time_bounds(&data_store).context("The data file has no samples")?;
+    let min_time = args.from.unwrap_or(data_min);
+    let max_time = args.to.unwrap_or(data_max);
+    if max_time <= min_time {
+        bail!("Empty time range {}..{}", min_time, max_time);
+    }
+
+    std::fs::create_dir_all(&args.out)
+        .with_context(|| format!("Failed to create {}", args.out.display()))?;
+
+    let fonts = Fonts::new(1.0, 8192, FontDefinitions::default());
+    let mut report = Report {
+        data: args.data.clone(),
+        layout: layout.name.clone(),
+        time_range: [min_time, max_time],
+        plots: Vec::new(),
+        traces: Vec::new(),
+    };
+
+    // Plots of all workspaces, in tab order.
+    let panes: Vec<(usize, TileId)> = trees
+        .iter()
+        .enumerate()
+        .flat_map(|(workspace, (_, tree))| {
+            pane_ids(tree).into_iter().map(move |id| (workspace, id))
+        })
+        .collect();
+
+    // Units and filtered columns are needed up front for the ranges of Y-sync groups.
+    for (_, tree) in &mut trees {
+        for tile in tree.tiles.tiles_mut() {
+            let Tile::Pane(tile) = tile else {
+                continue;
+            };
+            tile.refresh_units(&data_store);
+            for trace in &tile.traces {
+                trace.ensure_derived(&mut data_store);
+            }
+        }
+    }
+    let y_sync = y_sync_ranges(
+        trees
+            .iter()
+            .flat_map(|(_, tree)| tree.tiles.tiles())
+            .filter_map(|tile| match tile {
+                Tile::Pane(tile) => Some(tile),
+                Tile::Container(_) => None,
+            }),
+        &data_store,
+        min_time,
+        max_time,
+    );
+
+    for (workspace, tile_id) in panes {
+        let Some(Tile::Pane(tile)) = trees[workspace].1.tiles.get(tile_id) else {
+            continue;
+        };
+        if tile.scene.is_some() {
+            tracing::debug!("Skipping 3D scene tile");
+            continue;
+        }
+
+        let plot = report.plots.len() + 1;
+
+        let rect = Rect::from_min_size(Pos2::ZERO, vec2(args.size[0] as f32, args.size[1] as f32));
+        let shapes = if tile.map.is_some() {
+            map_shapes(&fonts, rect, tile, &data_store, min_time, max_time)
+        } else {
+            plot_shapes(&fonts, rect, tile, &data_store, min_time, max_time, &y_sync)
+        };
+
+        let file_name = format!("plot_{:02}.png", plot);
+        rasterize(&fonts, args.size, shapes)
+            .save(args.out.join(&file_name))
+            .with_context(|| format!("Failed to write {}", file_name))?;
+        tracing::info!("{}", file_name);
+
+        if tile.map.is_none() {
+            report
+                .traces
+                .extend(trace_stats(plot, tile, &data_store, min_time, max_time));
+        }
+        report.plots.push(file_name);
+    }
+
+    if report.plots.is_empty() {
+        bail!("The layout has no plots to render");
+    }
+
+    std::fs::write(
+        args.out.join("stats.json"),
+        serde_json::to_string_pretty(&report)?,
+    )?;
+    std::fs::write(args.out.join("report.md"), markdown_report(&report))?;
+    tracing::info!(
+        "✓ Rendered {} plots to {}",
+        report.plots.len(),
+        args.out.display()
+    );
+    Ok(())
+}
+
+/// A layout given by path, or by name from the configured layouts directory.
+fn resolve_layout(layout: &str) -> Result<PathBuf> {
+    let path = PathBuf::from(layout);
+    if path.is_file() {
+        return Ok(path);
+    }
+
+    let layouts_dir = Settings::load().layouts_dir();
+    LayoutData::list_layouts(&layouts_dir)?
+        .into_iter()
+        .find(|(name, _)| name == layout)
+        .map(|(_, path)| path)
+        .with_context(|| {
+            format!(
+                "No layout file or saved layout named '{}' in {}",
+                layout,
+                layouts_dir.display()
+            )
+        })
+}
+
+fn time_bounds(data_store: &DataStore) -> Option<(f32, f32)> {
+    let mut bounds: Option<(f32, f32)> = None;
+    for cols in data_store.topics.values() {
+        let Some(times) = cols.get("timestamp") else {
+            continue;
+        };
+        if let (Some(&first), Some(&last)) = (times.first(), times.last()) {
+            bounds = Some(match bounds {
+                Some((lo, hi)) => (lo.min(first), hi.max(last)),
+                None => (first, last),
+            });
+        }
+    }
+    bounds
+}
+
+/// Panes in layout order, depth first.
+fn pane_ids(tree: &Tree<PlotTile>) -> Vec<TileId> {
+    fn visit(tree: &Tree<PlotTile>, id: TileId, out: &mut Vec<TileId>) {
+        match tree.tiles.get(id) {
+            Some(Tile::Pane(_)) => out.push(id),
+            Some(Tile::Container(container)) => {
+                for &child in container.children() {
+                    visit(tree, child, out);
+                }
+            }
+            None => {}
+        }
+    }
+
+    let mut out = Vec::new();
+    if let Some(root) = tree.root() {
+        visit(tree, root, &mut out);
+    }
+    out
+}
+
+fn trace_color(color: [f32; 4]) -> Color32 {
+    Color32::from_rgb(
+        (color[0] * 255.0) as u8,
+        (color[1] * 255.0) as u8,
+        (color[2] * 255.0) as u8,
+    )
+}
+
+fn trace_stats(
+    plot: usize,
+    tile: &PlotTile,
+    data_store: &DataStore,
+    min_time: f32,
+    max_time: f32,
+) -> Vec<TraceStats> {
+    tile.traces
+        .iter()
+        .map(|trace| {
+            let stats = data_store
+                .get_column(&trace.topic, "timestamp")
+                .and_then(|times| {
+                    let start = times.partition_point(|&t| t < min_time);
+                    let end = times.partition_point(|&t| t <= max_time);
+                    data_store.column_range_stats(&trace.topic, &trace.data_col(), start, end)
+                })
+                .filter(|stats| stats.count > 0);
+            let range = stats.map(|s| {
+                trace
+                    .math
+                    .apply_range((s.min * trace.scale, s.max * trace.scale))
+            });
+            // The mean only maps through linear math, otherwise it is taken from the samples.
+            let mean = stats.and_then(|s| match trace.math.op {
+                MathOp::None | MathOp::Negate => Some(trace.math.apply(s.mean * trace.scale)),
+                MathOp::Abs | MathOp::WrapPi => {
+                    let times = data_store.get_column(&trace.topic, "timestamp")?;
+                    let values = data_store.get_column(&trace.topic, &trace.data_col())?;
+                    let start = times.partition_point(|&t| t < min_time);
+                    let end = times.partition_point(|&t| t <= max_time).min(values.len());
+                    let samples = values.get(start..end)?.iter().filter(|v| v.is_finite());
+                    let (sum, count) = samples.fold((0.0f64, 0usize), |(sum, count), &v| {
+                        (sum + trace.display_value(v) as f64, count + 1)
+                    });
+                    (count > 0).then(|| (sum / count as f64) as f32)
+                }
+            });
+
+            TraceStats {
+                plot,
+                topic: trace.topic.clone(),
+                column: trace.display_name(),
+                unit: trace.unit.clone(),
+                samples: stats.map_or(0, |s| s.count),
+                min: range.map(|r| r.0),
+                max: range.map(|r| r.1),
+                mean,
+            }
+        })
+        .collect()
+}
+
+fn markdown_report(report: &Report) -> String {
+    let mut md = String::new();
+    let _ = writeln!(md, "# {}\n", report.layout);
+    let _ = writeln!(
+        md,
+        "Data: `{}`, time range {:.3} s to {:.3} s\n",
+        report.data.display(),
+        report.time_range[0],
+        report.time_range[1]
+    );
+
+    let fmt = |v: Option<f32>| v.map_or("-".to_string(), |v| format!("{:.4}", v));
+    for (idx, file_name) in report.plots.iter().enumerate() {
+        let _ = writeln!(md, "## Plot {}\n\n![]({})\n", idx + 1, file_name);
+
+        let traces: Vec<_> = report.traces.iter().filter(|t| t.plot == idx + 1).collect();
+        if traces.is_empty() {
+            continue;
+        }
+        md.push_str("| Trace | Unit | Samples | Min | Max | Mean |\n");
+        md.push_str("|---|---|---|---|---|---|\n");
+        for t in traces {
+            let _ = writeln!(
+                md,
+                "| {}/{} | {} | {} | {} | {} | {} |",
+                t.topic,
+                t.column,
+                t.unit.as_deref().unwrap_or(""),
+                t.samples,
+                fmt(t.min),
+                fmt(t.max),
+                fmt(t.mean)
+            );
+        }
+        md.push('\n');
+    }
+    md
+}
+
+fn plot_shapes(
+    fonts: &Fonts,
+    rect: Rect,
+    tile: &PlotTile,
+    data_store: &DataStore,
+    min_time: f32,
+    max_time: f32,
+    y_sync: &YSyncRanges,
+) -> Vec<Shape> {
+    let mut shapes = vec![
+        Shape::rect_filled(rect, 0.0, Color32::from_rgb(20, 20, 20)),
+        Shape::rect_stroke(
+            rect.shrink(0.5),
+            0.0,
+            Stroke::new(1.0, Color32::from_gray(60)),
+        ),
+    ];
+
+    let (min_y, max_y) = tile.y_bounds(data_store, min_time, max_time, y_sync);
+    let grid_color = Color32::from_gray(45);
+    let text_color = Color32::from_gray(150);
+    let font_id = FontId::proportional(12.0);
+
+    let time_span = max_time - min_time;
+    let t_step = calculate_grid_step(time_span, 10);
+    let mut t = (min_time / t_step).ceil() * t_step;
+    while t <= max_time {
+        let x = rect.min.x + (t - min_time) / time_span * rect.width();
+        shapes.push(Shape::line_segment(
+            [pos2(x, rect.min.y), pos2(x, rect.max.y)],
+            Stroke::new(1.0, grid_color),
+        ));
+        shapes.push(Shape::text(
+            fonts,
+            pos2(x + 2.0, rect.max.y - 4.0),
+            Align2::LEFT_BOTTOM,
+            format!("{:.1}", t),
+            font_id.clone(),
+            text_color,
+        ));
+        t += t_step;
+    }
+
+    let val_span = max_y - min_y;
+    let v_step = calculate_grid_step(val_span, 8);
+    let mut v = (min_y / v_step).ceil() * v_step;
+    while v <= max_y {
+        let y = rect.min.y + (1.0 - (v - min_y) / val_span) * rect.height();
+        shapes.push(Shape::line_segment(
+            [pos2(rect.min.x, y), pos2(rect.max.x, y)],
+            Stroke::new(1.0, grid_color),
+        ));
+        shapes.push(Shape::text(
+            fonts,
+            pos2(rect.min.x + 2.0, y - 2.0),
+            Align2::LEFT_BOTTOM,
+            format!("{:.2}", v),
+            font_id.clone(),
+            text_color,
+        ));
+        v += v_step;
+    }
+
+    if let Some(unit) = tile.common_unit() {
+        shapes.push(Shape::text(
+            fonts,
+            pos2(rect.min.x + 4.0, rect.min.y + 4.0),
+            Align2::LEFT_TOP,
+            format!("[{}]", unit),
+            font_id.clone(),
+            text_color,
+        ));
+    }
+
+    let to_screen = |t: f32, v: f32| {
+        pos2(
+            rect.min.x + (t - min_time) / time_span * rect.width(),
+            rect.min.y + (1.0 - (v - min_y) / val_span) * rect.height(),
+        )
+    };
+
+    for trace in &tile.traces {
+        let (Some(times), Some(values)) = (
+            data_store.get_column(&trace.topic, "timestamp"),
+            data_store.get_column(&trace.topic, &trace.data_col()),
+        ) else {
+            continue;
+        };
+        let color = trace_color(trace.color);
+        let start = times.partition_point(|&t| t < min_time).saturating_sub(1);
+        let end = (times.partition_point(|&t| t <= max_time) + 1).min(values.len());
+        if start >= end {
+            continue;
+        }
+
+        if tile.scatter_mode {
+            for i in start..end {
+                let v = trace.display_value(values[i]);
+                if v.is_finite() {
+                    let p = to_screen(times[i], v);
+                    shapes.push(Shape::circle_filled(p, 1.5, color));
+                }
+            }
+            continue;
+        }
+
+        for segment in decimate(
+            &times[start..end],
+            &values[start..end],
+            |v| trace.display_value(v),
+            rect.width(),
+        ) {
+            let points: Vec<Pos2> = segment.into_iter().map(|(t, v)| to_screen(t, v)).collect();
+            if points.len() > 1 {
+                shapes.push(Shape::line(points, Stroke::new(1.5, color)));
+            }
+        }
+    }
+
+    legend_shapes(fonts, rect, tile, &mut shapes);
+    shapes
+}
+
+/// Splits a series at non-finite values and reduces it to at most four points per
+/// pixel column (first, min, max, last), which keeps the envelope of dense data.
+fn decimate(
+    times: &[f32],
+    values: &[f32],
+    display_value: impl Fn(f32) -> f32,
+    width: f32,
+) -> Vec<Vec<(f32, f32)>> {
+    let (Some(&t0), Some(&t1)) = (times.first(), times.last()) else {
+        return Vec::new();
+    };
+    let span = (t1 - t0).max(f32::EPSILON);
+    let dense = times.len() as f32 > width * 4.0;
+
+    let mut segments = Vec::new();
+    let mut current: Vec<(f32, f32)> = Vec::new();
+    let mut bucket: Option<(i64, [(f32, f32); 4])> = None;
+
+    let flush = |bucket: &mut Option<(i64, [(f32, f32); 4])>, current: &mut Vec<(f32, f32)>| {
+        if let Some((_, [first, min, max, last])) = bucket.take() {
+            let (lo, hi) = if min.0 <= max.0 {
+                (min, max)
+            } else {
+                (max, min)
+            };
+            current.extend([first, lo, hi, last]);
+        }
+    };
+
+    for (&t, &v) in times.iter().zip(values) {
+        let v = display_value(v);
+        if !v.is_finite() {
+            flush(&mut bucket, &mut current);
+            if !current.is_empty() {
+                segments.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        if !dense {
+            current.push((t, v));
+            continue;
+        }
+
+        let column = ((t - t0) / span * width) as i64;
+        match &mut bucket {
+            Some((col, [_, min, max, last])) if *col == column => {
+                if v < min.1 {
+                    *min = (t, v);
+                }
+                if v > max.1 {
+                    *max = (t, v);
+                }
+                *last = (t, v);
+            }
+            _ => {
+                flush(&mut bucket, &mut current);
+                bucket = Some((column, [(t, v); 4]));
+            }
+        }
+    }
+    flush(&mut bucket, &mut current);
+    if !current.is_empty() {
+        segments.push(current);
+    }
+    segments
+}
+
+fn legend_shapes(fonts: &Fonts, rect: Rect, tile: &PlotTile, shapes: &mut Vec<Shape>) {
+    if tile.traces.is_empty() {
+        return;
+    }
+
+    let font_id = FontId::proportional(12.0);
+    let galleys: Vec<_> = tile
+        .traces
+        .iter()
+        .map(|trace| {
+            fonts.layout_no_wrap(
+                format!("{}/{}", trace.topic, trace.display_name()),
+                font_id.clone(),
+                Color32::from_gray(220),
+            )
+        })
+        .collect();
+
+    let row_height = 16.0;
+    let text_width = galleys.iter().map(|g| g.size().x).fold(0.0, f32::max);
+    let size = vec2(text_width + 30.0, galleys.len() as f32 * row_height + 8.0);
+    let legend_rect =
+        Rect::from_min_size(pos2(rect.max.x - size.x - 10.0, rect.min.y + 10.0), size);
+    shapes.push(Shape::rect_filled(
+        legend_rect,
+        4.0,
+        Color32::from_rgba_unmultiplied(30, 30, 30, 220),
+    ));
+
+    for (idx, (trace, galley)) in tile.traces.iter().zip(galleys).enumerate() {
+        let y = legend_rect.min.y + 4.0 + idx as f32 * row_height;
+        shapes.push(Shape::rect_filled(
+            Rect::from_min_size(pos2(legend_rect.min.x + 8.0, y + 3.0), vec2(10.0, 10.0)),
+            2.0,
+            trace_color(trace.color),
+        ));
+        shapes.push(Shape::galley(
+            pos2(legend_rect.min.x + 24.0, y),
+            galley,
+            Color32::from_gray(220),
+        ));
+    }
+}
+
+/// Trajectory of a map tile, drawn without the tile background.
+fn map_shapes(
+    fonts: &Fonts,
+    rect: Rect,
+    tile: &PlotTile,
+    data_store: &DataStore,
+    min_time: f32,
+    max_time: f32,
+) -> Vec<Shape> {
+    let mut shapes = vec![Shape::rect_filled(rect, 0.0, Color32::from_rgb(20, 20, 20))];
+    let Some(map) = &tile.map else {
+        return shapes;
+    };
+    let columns = (
+        data_store.get_column(&map.topic, "timestamp"),
+        data_store.get_column(&map.topic, &map.lat),
+        data_store.get_column(&map.topic, &map.lon),
+    );
+    let (Some(times), Some(lat), Some(lon)) = columns else {
+        return shapes;
+    };
+
+    let start = times.partition_point(|&t| t < min_time);
+    let end = times
+        .partition_point(|&t| t <= max_time)
+        .min(lat.len())
+        .min(lon.len());
+    let points: Vec<(f32, f32)> = (start..end)
+        .map(|i| (lon[i], lat[i]))
+        .filter(|(x, y)| x.is_finite() && y.is_finite())
+        .collect();
+    if points.len() < 2 {
+        return shapes;
+    }
+
+    let (mut x0, mut x1, mut y0, mut y1) = (f32::MAX, f32::MIN, f32::MAX, f32::MIN);
+    for &(x, y) in &points {
+        (x0, x1, y0, y1) = (x0.min(x), x1.max(x), y0.min(y), y1.max(y));
+    }
+    // Equirectangular projection around the mean latitude keeps the shape undistorted.
+    let x_scale = ((y0 + y1) * 0.5).to_radians().cos();
+    let width = ((x1 - x0) * x_scale).max(1e-9);
+    let height = (y1 - y0).max(1e-9);
+    let inner = rect.shrink(20.0);
+    let scale = (inner.width() / width).min(inner.height() / height);
+    let center = inner.center();
+
+    let line: Vec<Pos2> = points
+        .iter()
+        .map(|&(x, y)| {
+            pos2(
+                center.x + ((x - (x0 + x1) * 0.5) * x_scale) * scale,
+                center.y - (y - (y0 + y1) * 0.5) * scale,
+            )
+        })
+        .collect();
+    let color = Color32::from_rgb(255, 140, 0);
+    shapes.push(Shape::circle_filled(
+        line[0],
+        4.0,
+        Color32::from_rgb(80, 200, 80),
+    ));
+    shapes.push(Shape::circle_filled(
+        line[line.len() - 1],
+        4.0,
+        Color32::from_rgb(220, 60, 60),
+    ));
+    shapes.push(Shape::line(line, Stroke::new(2.0, color)));
+    shapes.push(Shape::text(
+        fonts,
+        pos2(rect.min.x + 4.0, rect.min.y + 4.0),
+        Align2::LEFT_TOP,
+        format!("{} ({}, {})", map.topic, map.lat, map.lon),
+        FontId::proportional(12.0),
+        Color32::from_gray(150),
+    ));
+    shapes
+}
+
+/// Tessellates `shapes` like the GPU backend would and fills the triangles on the CPU,
+/// blending premultiplied colors in gamma space.
+fn rasterize(fonts: &Fonts, size: [u32; 2], shapes: Vec<Shape>) -> image::RgbaImage {
+    let [width, height] = size;
+    let canvas = Rect::from_min_size(Pos2::ZERO, vec2(width as f32, height as f32));
+
+    let font_image = fonts.image();
+    let atlas: Vec<u8> = font_image.srgba_pixels(None).map(|c| c.a()).collect();
+    let [atlas_w, atlas_h] = font_image.size;
+
+    let prepared_discs = fonts.texture_atlas().lock().prepared_discs();
+    let mut tessellator = Tessellator::new(
+        1.0,
+        TessellationOptions::default(),
+        font_image.size,
+        prepared_discs,
+    );
+    let primitives = tessellator.tessellate_shapes(
+        shapes
+            .into_iter()
+            .map(|shape| ClippedShape {
+                clip_rect: canvas,
+                shape,
+            })
+            .collect(),
+    );
+
+    let mut pixels = vec![[0.0f32; 4]; (width * height) as usize];
+    let sample = |u: f32, v: f32| {
+        let x = ((u * atlas_w as f32) as usize).min(atlas_w - 1);
+        let y = ((v * atlas_h as f32) as usize).min(atlas_h - 1);
+        atlas[y * atlas_w + x] as f32 / 255.0
+    };
+
+    for primitive in primitives {
+        let Primitive::Mesh(mesh) = primitive.primitive else {
+            continue;
+        };
+        let clip = primitive.clip_rect.intersect(canvas);
+        fill_mesh(&mesh, clip, width, &mut pixels, &sample);
+    }
+
+    image::RgbaImage::from_fn(width, height, |x, y| {
+        let [r, g, b, _] = pixels[(y * width + x) as usize];
+        image::Rgba([
+            (r * 255.0).round() as u8,
+            (g * 255.0).round() as u8,
+            (b * 255.0).round() as u8,
+            255,
+        ])
+    })
+}
+
+fn fill_mesh(
+    mesh: &Mesh,
+    clip: Rect,
+    width: u32,
+    pixels: &mut [[f32; 4]],
+    sample: &impl Fn(f32, f32) -> f32,
+) {
+    let edge = |a: Pos2, b: Pos2, p: Pos2| (b.x - a.x) * (p.y - a.y) - (b.y - a.y) * (p.x - a.x);
+
+    for tri in mesh.indices.chunks_exact(3) {
+        let [a, b, c] = [0, 1, 2].map(|i| &mesh.vertices[tri[i] as usize]);
+        let area = edge(a.pos, b.pos, c.pos);
+        if area.abs() < 1e-8 {
+            continue;
+        }
+
+        let x0 = a.pos.x.min(b.pos.x).min(c.pos.x).max(clip.min.x).floor() as i32;
+        let x1 = a.pos.x.max(b.pos.x).max(c.pos.x).min(clip.max.x).ceil() as i32;
+        let y0 = a.pos.y.min(b.pos.y).min(c.pos.y).max(clip.min.y).floor() as i32;
+        let y1 = a.pos.y.max(b.pos.y).max(c.pos.y).min(clip.max.y).ceil() as i32;
+
+        for y in y0.max(0)..y1 {
+            for x in x0.max(0)..x1 {
+                let p = pos2(x as f32 + 0.5, y as f32 + 0.5);
+                let wa = edge(b.pos, c.pos, p) / area;
+                let wb = edge(c.pos, a.pos, p) / area;
+                let wc = 1.0 - wa - wb;
+                if wa < 0.0 || wb < 0.0 || wc < 0.0 {
+                    continue;
+                }
+
+                let u = wa * a.uv.x + wb * b.uv.x + wc * c.uv.x;
+                let v = wa * a.uv.y + wb * b.uv.y + wc * c.uv.y;
+                let coverage = sample(u, v);
+
+                let src: [f32; 4] = std::array::from_fn(|i| {
+                    let channel = |vertex: &egui::epaint::Vertex| vertex.color[i] as f32 / 255.0;
+                    (wa * channel(a) + wb * channel(b) + wc * channel(c)) * coverage
+                });
+                let dst = &mut pixels[(y as u32 * width + x as u32) as usize];
+                for i in 0..4 {
+                    dst[i] = src[i] + dst[i] * (1.0 - src[3]);
+                }
+            }
+        }
+    }
+}
@@ -1,27 +1,47 @@
 mod acquisition;
 mod core;
+mod headless;
 mod ui;
 
 use eframe::egui;
+use std::time::Duration;
 
 fn main() -> eframe::Result {
+    core::logging::init();
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("render") {
+        if let Err(e) = headless::run(&args[1..]) {
+            eprintln!("✗ {:#}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     let rt = tokio::runtime::Builder::new_multi_thread()
         .enable_all()
         .build()
         .expect("Failed to build Tokio runtime");
-    let _guard = rt.enter();
+    let guard = rt.enter();
 
     let options = eframe::NativeOptions {
         renderer: eframe::Renderer::Wgpu,
         viewport: egui::ViewportBuilder::default()
             .with_inner_size([1280.0, 720.0])
             .with_title("TiPlot"),
+        // Must match `scene_renderer::DEPTH_FORMAT`.
+        depth_buffer: 24,
         ..Default::default()
     };
 
-    eframe::run_native(
+    let result = eframe::run_native(
         "TiPlot",
         options,
         Box::new(|cc| Ok(Box::new(ui::app::TiPlotApp::new(cc)))),
-    )
+    );
+
+    // Give network tasks a moment to see the shutdown instead of dropping them mid-write.
+    drop(guard);
+    rt.shutdown_timeout(Duration::from_secs(2));
+    result
 }
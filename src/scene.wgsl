@@ -0,0 +1,126 @@
+struct Camera {
+    view_proj: mat4x4<f32>,
+    // [width, height] of the viewport in points, near plane distance, unused
+    viewport: vec4<f32>,
+    // Direction towards the light in NED
+    light_dir: vec4<f32>,
+};
+
+@group(0) @binding(0) var<uniform> camera: Camera;
+
+// Lines are drawn as screen space quads, one instance per segment.
+
+struct LineOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+};
+
+@vertex
+fn vs_line(
+    @builtin(vertex_index) idx: u32,
+    @location(0) start: vec3<f32>,
+    @location(1) width: f32,
+    @location(2) color: vec4<f32>,
+    @location(3) end: vec3<f32>,
+) -> LineOutput {
+    var a = camera.view_proj * vec4<f32>(start, 1.0);
+    var b = camera.view_proj * vec4<f32>(end, 1.0);
+
+    var out: LineOutput;
+    out.color = color;
+
+    // Clip against the near plane so the screen space expansion stays valid.
+    let near = camera.viewport.z;
+    if (a.w < near && b.w < near) {
+        out.clip_position = vec4<f32>(0.0, 0.0, 2.0, 1.0);
+        return out;
+    }
+    if (a.w < near) {
+        a = mix(a, b, (near - a.w) / (b.w - a.w));
+    } else if (b.w < near) {
+        b = mix(b, a, (near - b.w) / (a.w - b.w));
+    }
+
+    let half_size = camera.viewport.xy * 0.5;
+    let sa = a.xy / a.w * half_size;
+    let sb = b.xy / b.w * half_size;
+    var dir = sb - sa;
+    let is_point = length(dir) < 1e-4;
+    if (is_point) {
+        dir = vec2<f32>(1.0, 0.0);
+    }
+    let normal = normalize(vec2<f32>(-dir.y, dir.x));
+
+    // Two triangles: (start, -), (start, +), (end, -), (end, -), (start, +), (end, +)
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(0.0, -1.0),
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(1.0, -1.0),
+        vec2<f32>(1.0, -1.0),
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(1.0, 1.0),
+    );
+    let corner = corners[idx];
+
+    var p = a;
+    if (corner.x > 0.5) {
+        p = b;
+    }
+    var offset = normal * corner.y * width * 0.5 / half_size;
+    // Zero length segments become squares so single points stay visible.
+    if (is_point) {
+        offset += normalize(dir) * (corner.x * 2.0 - 1.0) * width * 0.5 / half_size;
+    }
+    out.clip_position = vec4<f32>(p.xy + offset * p.w, p.z, p.w);
+    return out;
+}
+
+@fragment
+fn fs_line(in: LineOutput) -> @location(0) vec4<f32> {
+    return in.color;
+}
+
+// Meshes are lit with a single directional light and drawn once per instance.
+
+@group(1) @binding(0) var mesh_texture: texture_2d<f32>;
+@group(1) @binding(1) var mesh_sampler: sampler;
+
+struct MeshOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) normal: vec3<f32>,
+    @location(1) color: vec4<f32>,
+    @location(2) uv: vec2<f32>,
+};
+
+@vertex
+fn vs_mesh(
+    @location(0) position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+    @location(2) uv: vec2<f32>,
+    @location(3) color: vec4<f32>,
+    @location(4) model_0: vec4<f32>,
+    @location(5) model_1: vec4<f32>,
+    @location(6) model_2: vec4<f32>,
+    @location(7) model_3: vec4<f32>,
+    @location(8) tint: vec4<f32>,
+) -> MeshOutput {
+    let model = mat4x4<f32>(model_0, model_1, model_2, model_3);
+
+    var out: MeshOutput;
+    out.clip_position = camera.view_proj * model * vec4<f32>(position, 1.0);
+    out.normal = (model * vec4<f32>(normal, 0.0)).xyz;
+    out.color = color * tint;
+    out.uv = uv;
+    return out;
+}
+
+@fragment
+fn fs_mesh(in: MeshOutput) -> @location(0) vec4<f32> {
+    // Lit from both sides so meshes with inconsistent winding still read well.
+    let n = normalize(in.normal);
+    let diffuse = abs(dot(n, normalize(camera.light_dir.xyz)));
+    let shade = 0.4 + 0.6 * diffuse;
+
+    let base = in.color * textureSample(mesh_texture, mesh_sampler, in.uv);
+    return vec4<f32>(base.rgb * shade, base.a);
+}
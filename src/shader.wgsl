@@ -2,11 +2,13 @@ struct Uniforms {
     // [min_time, max_time, min_val, max_val]
     bounds: vec4<f32>, 
     color: vec4<f32>,
-    params: vec4<f32>,  // [point_size, unused, unused, unused]
+    params: vec4<f32>,  // [point_size, fill baseline, fill mode, unused]
+    math: vec4<f32>,    // [gain, offset, op, unit scale]
 };
 
 @group(0) @binding(0) var<uniform> uniforms: Uniforms;
 @group(0) @binding(1) var<storage, read> data: array<f32>; // T, V, T, V interleaved
+@group(0) @binding(2) var<storage, read> other: array<f32>; // second column for bands
 
 struct VertexOutput {
     @builtin(position) clip_position: vec4<f32>,
@@ -16,7 +18,7 @@ struct VertexOutput {
 fn vs_main(@builtin(vertex_index) idx: u32) -> VertexOutput {
     // Data is interleaved: [Time0, Val0, Time1, Val1, ...]
     let t = data[idx * 2u];
-    let v = data[idx * 2u + 1u];
+    let v = display(data[idx * 2u + 1u]);
 
     let min_t = uniforms.bounds.x;
     let max_t = uniforms.bounds.y;
@@ -36,6 +38,63 @@ fn vs_main(@builtin(vertex_index) idx: u32) -> VertexOutput {
     return out;
 }
 
+// Stored value in display units: gain * op(v * scale) + offset,
+// with op 0 = none, 1 = abs, 2 = negate, 3 = wrap to +-pi.
+fn display(v: f32) -> f32 {
+    let x = v * uniforms.math.w;
+    let op = u32(uniforms.math.z);
+    var y = x;
+    if op == 1u {
+        y = abs(x);
+    } else if op == 2u {
+        y = -x;
+    } else if op == 3u {
+        let tau = 6.2831855;
+        y = x - tau * floor((x + 3.1415927) / tau);
+    }
+    return uniforms.math.x * y + uniforms.math.y;
+}
+
+fn to_clip(t: f32, v: f32) -> vec4<f32> {
+    let t_norm = (t - uniforms.bounds.x) / (uniforms.bounds.y - uniforms.bounds.x);
+    let v_norm = (v - uniforms.bounds.z) / (uniforms.bounds.w - uniforms.bounds.z);
+    return vec4<f32>(t_norm * 2.0 - 1.0, v_norm * 2.0 - 1.0, 0.0, 1.0);
+}
+
+// Triangle strip alternating between the two edges of a filled area at each sample.
+// Fill modes: 0 = down to the baseline, 1 = up to the other column,
+// 2 = the value plus and minus the other column.
+@vertex
+fn vs_fill(@builtin(vertex_index) idx: u32) -> VertexOutput {
+    let i = idx / 2u;
+    let upper = (idx % 2u) == 1u;
+    let t = data[i * 2u];
+    let v = display(data[i * 2u + 1u]);
+    let mode = u32(uniforms.params.z);
+
+    var edge = v;
+    if mode == 0u {
+        if upper {
+            edge = uniforms.params.y;
+        }
+    } else if mode == 1u {
+        if upper {
+            edge = display(other[i * 2u + 1u]);
+        }
+    } else {
+        let spread = abs(uniforms.math.x) * uniforms.math.w * other[i * 2u + 1u];
+        if upper {
+            edge = v + spread;
+        } else {
+            edge = v - spread;
+        }
+    }
+
+    var out: VertexOutput;
+    out.clip_position = to_clip(t, edge);
+    return out;
+}
+
 @fragment
 fn fs_main() -> @location(0) vec4<f32> {
     return uniforms.color;
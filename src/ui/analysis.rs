@@ -0,0 +1,260 @@
+use crate::core::DataStore;
+
+/// One plot of an analysis preset.
+pub struct PresetPlot {
+    /// Traces as (topic, column). Columns missing from the log are left out.
+    pub traces: &'static [(&'static str, &'static str)],
+    /// Horizontal limit lines.
+    pub limits: &'static [f32],
+}
+
+/// A ready-made set of plots, derived columns and limit checks for a common log review.
+pub struct AnalysisPreset {
+    pub name: &'static str,
+    pub description: &'static str,
+    /// Topics that must be present for the preset to be offered.
+    pub required_topics: &'static [&'static str],
+    /// Script run when the preset is applied, computing derived columns and marking limit
+    /// violations on the timeline.
+    pub script: &'static str,
+    pub plots: &'static [PresetPlot],
+}
+
+impl AnalysisPreset {
+    pub fn missing_topics(&self, data_store: &DataStore) -> Vec<&'static str> {
+        self.required_topics
+            .iter()
+            .copied()
+            .filter(|topic| data_store.get_column(topic, "timestamp").is_none())
+            .collect()
+    }
+}
+
+pub const PRESETS: &[AnalysisPreset] = &[
+    AnalysisPreset {
+        name: "EKF Innovations (PX4)",
+        description: "Innovations and test ratios of the estimator. Ratios above 1 mean a \
+                      measurement was rejected.",
+        required_topics: &["estimator_innovations", "estimator_innovation_test_ratios"],
+        script: "\
+use_topic(\"estimator_innovation_test_ratios\");
+let hvel = max(col(\"gps_hvel[0]\"), col(\"gps_hvel[1]\"));
+mark(hvel > 1 | col(\"gps_vvel\") > 1, \"EKF rejected GPS velocity\");
+let hpos = max(col(\"gps_hpos[0]\"), col(\"gps_hpos[1]\"));
+mark(hpos > 1 | col(\"gps_vpos\") > 1, \"EKF rejected GPS position\");
+mark(col(\"baro_vpos\") > 1, \"EKF rejected baro height\");
+let mag = max(col(\"mag_field[0]\"), max(col(\"mag_field[1]\"), col(\"mag_field[2]\")));
+mark(mag > 1, \"EKF rejected magnetometer\");
+mark(col(\"heading\") > 1, \"EKF rejected heading\");
+",
+        plots: &[
+            PresetPlot {
+                traces: &[
+                    ("estimator_innovations", "gps_hvel[0]"),
+                    ("estimator_innovations", "gps_hvel[1]"),
+                    ("estimator_innovations", "gps_vvel"),
+                ],
+                limits: &[],
+            },
+            PresetPlot {
+                traces: &[
+                    ("estimator_innovations", "gps_hpos[0]"),
+                    ("estimator_innovations", "gps_hpos[1]"),
+                    ("estimator_innovations", "gps_vpos"),
+                    ("estimator_innovations", "baro_vpos"),
+                ],
+                limits: &[],
+            },
+            PresetPlot {
+                traces: &[
+                    ("estimator_innovations", "mag_field[0]"),
+                    ("estimator_innovations", "mag_field[1]"),
+                    ("estimator_innovations", "mag_field[2]"),
+                    ("estimator_innovations", "heading"),
+                ],
+                limits: &[],
+            },
+            PresetPlot {
+                traces: &[
+                    ("estimator_innovation_test_ratios", "gps_hvel[0]"),
+                    ("estimator_innovation_test_ratios", "gps_hvel[1]"),
+                    ("estimator_innovation_test_ratios", "gps_vvel"),
+                    ("estimator_innovation_test_ratios", "gps_hpos[0]"),
+                    ("estimator_innovation_test_ratios", "gps_hpos[1]"),
+                    ("estimator_innovation_test_ratios", "baro_vpos"),
+                    ("estimator_innovation_test_ratios", "mag_field[0]"),
+                    ("estimator_innovation_test_ratios", "heading"),
+                ],
+                limits: &[1.0],
+            },
+        ],
+    },
+    AnalysisPreset {
+        name: "Vibration (PX4)",
+        description: "Raw acceleration, its magnitude, the IMU vibration metrics and \
+                      clipping events.",
+        required_topics: &["sensor_combined", "vehicle_imu_status"],
+        script: "\
+use_topic(\"sensor_combined\");
+let x = col(\"accelerometer_m_s2[0]\");
+let y = col(\"accelerometer_m_s2[1]\");
+let z = col(\"accelerometer_m_s2[2]\");
+set_col(\"accel_norm\", sqrt(x ** 2 + y ** 2 + z ** 2));
+use_topic(\"vehicle_imu_status\");
+let clipping = col(\"accel_clipping[0]\") + col(\"accel_clipping[1]\") + col(\"accel_clipping[2]\");
+mark(diff(clipping) > 0, \"Accelerometer clipping\");
+",
+        plots: &[
+            PresetPlot {
+                traces: &[
+                    ("sensor_combined", "accelerometer_m_s2[0]"),
+                    ("sensor_combined", "accelerometer_m_s2[1]"),
+                    ("sensor_combined", "accelerometer_m_s2[2]"),
+                    ("sensor_combined", "accel_norm"),
+                ],
+                limits: &[],
+            },
+            PresetPlot {
+                traces: &[
+                    ("vehicle_imu_status", "accel_vibration_metric"),
+                    ("vehicle_imu_status", "gyro_vibration_metric"),
+                ],
+                limits: &[],
+            },
+            PresetPlot {
+                traces: &[
+                    ("vehicle_imu_status", "accel_clipping[0]"),
+                    ("vehicle_imu_status", "accel_clipping[1]"),
+                    ("vehicle_imu_status", "accel_clipping[2]"),
+                ],
+                limits: &[],
+            },
+        ],
+    },
+    AnalysisPreset {
+        name: "Actuator Saturation (PX4)",
+        description: "Normalized motor outputs, marking where any motor hits its limits.",
+        required_topics: &["actuator_motors"],
+        script: "\
+use_topic(\"actuator_motors\");
+let m0 = col(\"control[0]\");
+let m1 = col(\"control[1]\");
+let m2 = col(\"control[2]\");
+let m3 = col(\"control[3]\");
+mark(max(max(m0, m1), max(m2, m3)) >= 0.99, \"Motor saturated high\");
+mark(min(min(m0, m1), min(m2, m3)) <= 0.01, \"Motor saturated low\");
+",
+        plots: &[PresetPlot {
+            traces: &[
+                ("actuator_motors", "control[0]"),
+                ("actuator_motors", "control[1]"),
+                ("actuator_motors", "control[2]"),
+                ("actuator_motors", "control[3]"),
+                ("actuator_motors", "control[4]"),
+                ("actuator_motors", "control[5]"),
+                ("actuator_motors", "control[6]"),
+                ("actuator_motors", "control[7]"),
+            ],
+            limits: &[0.0, 1.0],
+        }],
+    },
+    AnalysisPreset {
+        name: "EKF Innovations (ArduPilot)",
+        description: "EKF3 innovations and test ratios. Ratios above 1 mean a measurement \
+                      was rejected.",
+        required_topics: &["xkf4"],
+        script: "\
+use_topic(\"xkf4\");
+mark(col(\"SV\") > 1, \"EKF velocity test failed\");
+mark(col(\"SP\") > 1, \"EKF position test failed\");
+mark(col(\"SH\") > 1, \"EKF height test failed\");
+mark(col(\"SM\") > 1, \"EKF magnetometer test failed\");
+",
+        plots: &[
+            PresetPlot {
+                traces: &[("xkf3", "IVN"), ("xkf3", "IVE"), ("xkf3", "IVD")],
+                limits: &[],
+            },
+            PresetPlot {
+                traces: &[("xkf3", "IPN"), ("xkf3", "IPE"), ("xkf3", "IPD")],
+                limits: &[],
+            },
+            PresetPlot {
+                traces: &[
+                    ("xkf4", "SV"),
+                    ("xkf4", "SP"),
+                    ("xkf4", "SH"),
+                    ("xkf4", "SM"),
+                ],
+                limits: &[1.0],
+            },
+        ],
+    },
+    AnalysisPreset {
+        name: "Vibration (ArduPilot)",
+        description: "Vibration levels and clipping. Below 30 m/s/s is normally fine, above \
+                      60 m/s/s almost always causes problems.",
+        required_topics: &["vibe"],
+        script: "\
+use_topic(\"vibe\");
+let vibe_max = max(col(\"VibeX\"), max(col(\"VibeY\"), col(\"VibeZ\")));
+set_col(\"vibe_max\", vibe_max);
+mark(vibe_max > 30, \"Vibration above 30 m/s/s\");
+mark(vibe_max > 60, \"Vibration above 60 m/s/s\");
+",
+        plots: &[
+            PresetPlot {
+                traces: &[("vibe", "VibeX"), ("vibe", "VibeY"), ("vibe", "VibeZ")],
+                limits: &[30.0, 60.0],
+            },
+            PresetPlot {
+                traces: &[
+                    ("vibe", "Clip"),
+                    ("vibe", "Clip0"),
+                    ("vibe", "Clip1"),
+                    ("vibe", "Clip2"),
+                ],
+                limits: &[],
+            },
+        ],
+    },
+    AnalysisPreset {
+        name: "Actuator Saturation (ArduPilot)",
+        description: "Motor PWM outputs, marking where any motor is close to the top of a \
+                      1000-2000 µs range.",
+        required_topics: &["rcou"],
+        script: "\
+use_topic(\"rcou\");
+let highest = max(max(col(\"C1\"), col(\"C2\")), max(col(\"C3\"), col(\"C4\")));
+mark(highest >= 1950, \"Motor near maximum output\");
+",
+        plots: &[PresetPlot {
+            traces: &[
+                ("rcou", "C1"),
+                ("rcou", "C2"),
+                ("rcou", "C3"),
+                ("rcou", "C4"),
+                ("rcou", "C5"),
+                ("rcou", "C6"),
+                ("rcou", "C7"),
+                ("rcou", "C8"),
+            ],
+            limits: &[1000.0, 1950.0],
+        }],
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn preset_scripts_compile() {
+        let engine = rhai::Engine::new();
+        for preset in PRESETS {
+            if let Err(e) = engine.compile(preset.script) {
+                panic!("{}: {}", preset.name, e);
+            }
+        }
+    }
+}
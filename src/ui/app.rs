@@ -1,20 +1,50 @@
-use crate::acquisition::{start_tcp_server, DataMessage};
+use crate::acquisition::{
+    start_file_load, start_follow, start_merge_load, start_session_load, start_tcp_server,
+    DataMessage, FolderWatcher, LoadedFile, MergeOptions, MirrorPublisher, MirrorStatus,
+    StreamControl, StreamStatus, SyntheticGenerator,
+};
+use crate::core::aliases;
+use crate::core::arrow_files;
+use crate::core::autosave;
+use crate::core::logging;
+use crate::core::script::{self, ScriptHost};
+use crate::core::{
+    Annotation, RetentionPolicy, SessionMetadata, SharedIngestFilter, SESSION_SEPARATOR,
+};
+use crate::ui::analysis::PRESETS;
 use crate::ui::app_state::AppState;
+use crate::ui::i18n::tr;
 use crate::ui::launch_loader;
-use crate::ui::menu::{render_menu_bar, MenuAction};
+use crate::ui::layout::LayoutData;
+use crate::ui::menu::{render_menu_bar, MenuAction, REOPEN_LAST_SHORTCUT};
+use crate::ui::panels::tabs::config::VehicleConfig;
 use crate::ui::panels::tabs::gltf_loader::ModelCache;
 use crate::ui::panels::{
-    render_config_window, render_timeline, render_topic_panel, render_view3d_panel,
+    apply_speed_preset, render_alias_panel, render_align_panel, render_command_palette,
+    render_config_window, render_health_panel, render_log_info_window, render_log_panel,
+    render_merge_panel, render_metadata_window, render_power_panel, render_profiler_overlay,
+    render_script_panel, render_stream_panel, render_synthetic_panel, render_timeline,
+    render_topic_panel, render_trigger_panel, render_view3d_panel, render_welcome, JumpTarget,
+    PaletteCommand, PaletteEntry, ProfileSection, StreamAction, SyntheticAction, TopicAction,
+    TopicMemory, COMMAND_PALETTE_SHORTCUT, SPEED_PRESETS,
 };
 use crate::ui::renderer::PlotRenderer;
-use crate::ui::tiles::TiPlotBehavior;
+use crate::ui::scene_renderer::SceneRenderer;
+use crate::ui::settings::{Confirmation, Settings};
+use crate::ui::tiles::plot_tile::TraceConfig;
+use crate::ui::tiles::{y_sync_ranges, PlotTile, TiPlotBehavior};
 use crossbeam_channel::unbounded;
-use eframe::egui;
+use eframe::{egui, egui_wgpu};
 use egui_phosphor::regular as icons;
-use std::path::PathBuf;
+use egui_tiles::Behavior;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
 
 pub struct TiPlotApp {
     state: AppState,
+    /// Kept to release GPU resources on exit, where no frame is available.
+    render_state: Option<egui_wgpu::RenderState>,
 }
 
 pub fn setup_fonts(ctx: &egui::Context) {
@@ -32,10 +62,30 @@ impl TiPlotApp {
                 .write()
                 .callback_resources
                 .insert(renderer);
+
+            let scene_renderer = SceneRenderer::new(
+                &wgpu_state.device,
+                &wgpu_state.queue,
+                wgpu_state.target_format,
+            );
+            wgpu_state
+                .renderer
+                .write()
+                .callback_resources
+                .insert(scene_renderer);
         }
 
+        let settings = Settings::load();
         let (tx, rx) = unbounded();
-        start_tcp_server(tx, cc.egui_ctx.clone());
+        let ingest_filter = SharedIngestFilter::default();
+        let stream = StreamControl::new();
+        start_tcp_server(
+            tx.clone(),
+            ingest_filter.clone(),
+            stream.clone(),
+            cc.egui_ctx.clone(),
+            settings.data.listen_addr.clone(),
+        );
 
         let mut model_cache = ModelCache::new();
 
@@ -44,115 +94,810 @@ impl TiPlotApp {
         const DELTA_WING_GLB: &[u8] = include_bytes!("../../assets/models/DeltaWing.glb");
 
         if let Err(e) = model_cache.load_from_bytes("FixedWing", FIXED_WING_GLB) {
-            eprintln!("✗ Failed to load Fixed Wing model: {}", e);
+            tracing::error!("Failed to load Fixed Wing model: {}", e);
         }
         if let Err(e) = model_cache.load_from_bytes("QuadCopter", QUAD_COPTER_GLB) {
-            eprintln!("✗ Failed to load Quadcopter model: {}", e);
+            tracing::error!("Failed to load Quadcopter model: {}", e);
         }
         if let Err(e) = model_cache.load_from_bytes("DeltaWing", DELTA_WING_GLB) {
-            eprintln!("✗ Failed to load Delta Wing model: {}", e);
+            tracing::error!("Failed to load Delta Wing model: {}", e);
         }
 
         setup_fonts(&cc.egui_ctx);
 
-        let layouts_dir = if let Some(storage) = cc.storage {
-            if let Some(path) = storage.get_string("layouts_dir") {
-                PathBuf::from(path)
-            } else {
-                get_default_layouts_dir()
-            }
-        } else {
-            get_default_layouts_dir()
-        };
+        let mut state = AppState::new(tx, rx, ingest_filter, stream, settings, model_cache);
+        state.ui.menu_state.recovery_saved_at = autosave::pending_recovery().map(|(_, t)| t);
 
         Self {
-            state: AppState::new(rx, layouts_dir, model_cache),
+            state,
+            render_state: cc.wgpu_render_state.clone(),
         }
     }
 
+    /// Frees trace and scene buffers and waits for queued GPU work before the device
+    /// goes away.
+    fn release_gpu_resources(render_state: &egui_wgpu::RenderState) {
+        let mut renderer = render_state.renderer.write();
+        if let Some(mut plot_renderer) = renderer.callback_resources.remove::<PlotRenderer>() {
+            plot_renderer.release();
+        }
+        if let Some(mut scene_renderer) = renderer.callback_resources.remove::<SceneRenderer>() {
+            scene_renderer.release();
+        }
+        drop(renderer);
+        render_state.device.poll(wgpu::Maintain::Wait);
+    }
+
     fn handle_menu_actions(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
         let action = self.state.ui.menu_state.show_save_dialog(ctx);
-        self.process_menu_action(action, frame);
+        self.process_menu_action(action, ctx, frame);
+
+        let selected_count = self.state.panels.topic_selection.selected.len();
+        let action = self
+            .state
+            .ui
+            .menu_state
+            .show_resample_dialog(ctx, selected_count);
+        self.process_menu_action(action, ctx, frame);
+
+        let action = self.state.ui.menu_state.show_ingest_filter_dialog(ctx);
+        self.process_menu_action(action, ctx, frame);
+
+        let action = self.state.ui.menu_state.show_preferences_dialog(ctx);
+        self.process_menu_action(action, ctx, frame);
+
+        let action = self.state.ui.menu_state.show_recovery_dialog(ctx);
+        self.process_menu_action(action, ctx, frame);
+
+        let action = self.state.ui.menu_state.show_new_log_dialog(ctx);
+        self.process_menu_action(action, ctx, frame);
+
+        let action = self.state.ui.menu_state.show_confirmation_dialog(ctx);
+        self.process_menu_action(action, ctx, frame);
+
+        if ctx.input_mut(|i| i.consume_shortcut(&COMMAND_PALETTE_SHORTCUT)) {
+            self.state.panels.command_palette.show();
+        }
+        if ctx.input_mut(|i| i.consume_shortcut(&REOPEN_LAST_SHORTCUT)) {
+            self.process_menu_action(MenuAction::ReopenLast, ctx, frame);
+        }
     }
 
-    fn process_menu_action(&mut self, action: MenuAction, frame: &mut eframe::Frame) {
+    fn save_settings(&mut self, settings: Settings) {
+        if settings.data.listen_addr != self.state.settings.data.listen_addr {
+            tracing::info!(
+                "Listen address changes to {} on restart",
+                settings.data.listen_addr
+            );
+        }
+        match settings.save() {
+            Ok(()) => tracing::info!("Settings saved"),
+            Err(e) => {
+                tracing::error!("Failed to save settings: {}", e);
+                self.state
+                    .ui
+                    .notifications
+                    .error(format!("Failed to save settings: {}", e));
+            }
+        }
+        self.state.apply_settings(settings);
+    }
+
+    /// The confirmation `action` needs before it runs, if any.
+    fn confirmation_for(&self, action: &MenuAction) -> Option<Confirmation> {
+        let kind = match action {
+            MenuAction::ClearData if !self.state.data.data_store.is_empty() => {
+                Confirmation::ClearData
+            }
+            MenuAction::ClearTraces(tile_id) if self.state.layout.has_traces(*tile_id) => {
+                Confirmation::ClearTraces
+            }
+            MenuAction::SaveLayout(name)
+                if LayoutData::file_path(&self.state.ui.layouts_dir, name).exists() =>
+            {
+                Confirmation::OverwriteLayout
+            }
+            MenuAction::Exit
+                if self
+                    .state
+                    .layout
+                    .has_unsaved_changes(&self.state.panels.view3d_panel.vehicles) =>
+            {
+                Confirmation::ExitUnsaved
+            }
+            _ => return None,
+        };
+        self.state.settings.confirms(kind).then_some(kind)
+    }
+
+    fn process_menu_action(
+        &mut self,
+        action: MenuAction,
+        ctx: &egui::Context,
+        frame: &mut eframe::Frame,
+    ) {
+        if let Some(kind) = self.confirmation_for(&action) {
+            self.state.ui.menu_state.request_confirmation(kind, action);
+            return;
+        }
+        self.execute_menu_action(action, ctx, frame);
+    }
+
+    fn execute_menu_action(
+        &mut self,
+        action: MenuAction,
+        ctx: &egui::Context,
+        frame: &mut eframe::Frame,
+    ) {
         match action {
             MenuAction::SaveLayout(name) => {
-                if let Err(e) = self.state.layout.save_layout(
+                let path = LayoutData::file_path(&self.state.ui.layouts_dir, &name);
+                match self.state.layout.save_layout(
                     name,
                     &self.state.ui.layouts_dir,
                     &self.state.panels.view3d_panel.vehicles,
                 ) {
-                    self.state.ui.menu_state.error_message = Some(e);
-                }
-            }
-            MenuAction::LoadLayout(path) => {
-                if let Err(e) = self
-                    .state
-                    .layout
-                    .load_layout(path, &mut self.state.panels.view3d_panel.vehicles)
-                {
-                    self.state.ui.menu_state.error_message = Some(e);
+                    Ok(()) => {
+                        self.state.panels.recent_files.push_layout(&path);
+                        self.state.ui.notifications.success("Layout saved");
+                    }
+                    Err(e) => self.state.ui.notifications.error(e),
                 }
             }
+            MenuAction::LoadLayout(path) => self.load_layout(path),
             MenuAction::SaveData => self.save_data(),
-            MenuAction::LoadData => self.load_data(frame),
+            MenuAction::ExportTopicFiles => self.export_topic_files(),
+            MenuAction::ExportTable => self.export_table(),
+            MenuAction::LoadData => self.load_data(ctx),
+            MenuAction::LoadDataFolder => self.load_data_folder(ctx),
+            MenuAction::AddLogSession => self.add_log_session(ctx),
+            MenuAction::MergeData => self.pick_merge_file(),
+            MenuAction::OpenDataFile(path) => self.open_data_file(path, ctx),
+            MenuAction::ReopenLast => self.reopen_last(ctx),
+            MenuAction::ClearRecentFiles => self.state.panels.recent_files.clear(),
             MenuAction::ClearData => self.state.clear_all(),
+            MenuAction::ClearTraces(tile_id) => self.state.layout.clear_traces(tile_id),
+            MenuAction::Exit => {
+                self.state.ui.exit_confirmed = true;
+                ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+            }
+            MenuAction::Confirmed {
+                kind,
+                action,
+                dont_ask_again,
+            } => {
+                if dont_ask_again {
+                    let mut settings = self.state.settings.clone();
+                    settings.set_confirms(kind, false);
+                    self.save_settings(settings);
+                }
+                self.execute_menu_action(*action, ctx, frame);
+            }
             MenuAction::LaunchLoader => {
                 if let Err(e) = launch_loader() {
-                    self.state.ui.menu_state.error_message = Some(e);
+                    self.state.ui.notifications.error(e);
                 }
             }
+            MenuAction::StartListener => self.state.data.stream.set_armed(true),
             MenuAction::SetInterpolationMode(mode) => {
                 self.state.layout.global_interpolation_mode = mode;
                 self.apply_interpolation_mode_to_all_tiles(mode);
             }
+            MenuAction::SetTracePalette(palette) => {
+                let settings = Settings {
+                    trace_palette: palette,
+                    ..self.state.settings.clone()
+                };
+                self.save_settings(settings);
+            }
+            MenuAction::OpenPreferences => {
+                self.state
+                    .ui
+                    .menu_state
+                    .open_preferences(&self.state.settings);
+            }
+            MenuAction::SaveSettings(settings) => {
+                self.save_settings(settings);
+            }
+            MenuAction::RestoreRecovery => match autosave::claim_recovery() {
+                Ok(path) => start_file_load(
+                    path,
+                    self.state.data.ingest_filter.clone(),
+                    self.memory_budget(),
+                    self.state.data.tx.clone(),
+                    ctx.clone(),
+                ),
+                Err(e) => {
+                    tracing::error!("Failed to restore autosaved data: {}", e);
+                    self.state
+                        .ui
+                        .notifications
+                        .error(format!("Failed to restore: {}", e));
+                }
+            },
+            MenuAction::DiscardRecovery => self.state.data.autosave.discard(),
+            MenuAction::RecolorTraces => {
+                self.state.layout.recolor_traces();
+            }
+            MenuAction::Resample {
+                name,
+                rate_hz,
+                mode,
+            } => self.resample_selected(&name, rate_hz, mode, frame),
+            MenuAction::SetIngestFilter(filter) => {
+                *self.state.data.ingest_filter.write().unwrap() = filter;
+            }
+            MenuAction::OpenColumnMetadata => self.state.panels.metadata_panel.open = true,
+            MenuAction::OpenLogInfo => self.state.panels.log_info_panel.open = true,
+            MenuAction::OpenScriptConsole => self.state.panels.script_panel.open = true,
+            MenuAction::OpenTriggers => self.state.panels.trigger_panel.open = true,
+            MenuAction::OpenLiveStatus => self.state.panels.health_panel.open = true,
+            MenuAction::OpenPowerAnalysis => self.state.panels.power_panel.open = true,
+            MenuAction::OpenSessionAlignment => self.state.panels.align_panel.open = true,
+            MenuAction::OpenTopicAliases => self
+                .state
+                .panels
+                .alias_panel
+                .show(&self.state.settings.alias_profiles),
+            MenuAction::OpenSyntheticData => self.state.panels.synthetic_panel.open = true,
+            MenuAction::OpenCommandPalette => self.state.panels.command_palette.show(),
+            MenuAction::ApplyAnalysisPreset(index) => self.apply_analysis_preset(index, frame),
             MenuAction::None => {}
         }
     }
 
+    fn resample_selected(
+        &mut self,
+        name: &str,
+        rate_hz: f32,
+        mode: crate::ui::tiles::InterpolationMode,
+        frame: &mut eframe::Frame,
+    ) {
+        let mut sources: Vec<(String, String)> = self
+            .state
+            .panels
+            .topic_selection
+            .selected
+            .iter()
+            .cloned()
+            .collect();
+        sources.sort_by(|a, b| {
+            natord::compare(&format!("{}/{}", a.0, a.1), &format!("{}/{}", b.0, b.1))
+        });
+
+        match self
+            .state
+            .data
+            .data_store
+            .resample_topics(name, &sources, rate_hz, mode)
+        {
+            Ok(_) => {
+                tracing::info!("Resampled {} column(s) into '{}'", sources.len(), name);
+                self.state.ui.notifications.success(format!(
+                    "Resampled {} column(s) into '{}'",
+                    sources.len(),
+                    name
+                ));
+                self.reupload_all_traces(frame);
+            }
+            Err(e) => {
+                tracing::error!("Failed to resample: {}", e);
+                self.state
+                    .ui
+                    .notifications
+                    .error(format!("Failed to resample: {}", e));
+            }
+        }
+    }
+
     fn save_data(&mut self) {
         if let Some(path) = rfd::FileDialog::new()
             .set_file_name("tiplot_data.arrow")
             .add_filter("Arrow Files", &["arrow"])
             .save_file()
         {
-            match self.state.data.data_store.save_to_arrow(&path) {
+            let session = self.session_metadata();
+
+            match self.state.data.data_store.save_to_arrow(
+                &path,
+                Some(&session),
+                self.state.settings.data.file_compression,
+            ) {
                 Ok(_) => {
                     self.state.data.data_file_path = Some(path.clone());
-                    println!("✓ Data saved to: {}", path.display());
+                    self.state.data.autosave.discard();
+                    tracing::info!("Data saved to: {}", path.display());
+                    self.state
+                        .ui
+                        .notifications
+                        .success(format!("Saved {}", display_name(&path)));
                 }
                 Err(e) => {
-                    eprintln!("✗ Failed to save data: {}", e);
-                    self.state.ui.menu_state.error_message = Some(format!("Failed to save: {}", e));
+                    tracing::error!("Failed to save data: {}", e);
+                    self.state
+                        .ui
+                        .notifications
+                        .error(format!("Failed to save: {}", e));
                 }
             }
         }
     }
 
-    fn load_data(&mut self, frame: &mut eframe::Frame) {
+    fn session_metadata(&self) -> SessionMetadata {
+        SessionMetadata {
+            annotations: self.state.timeline.annotations.clone(),
+            vehicles: self
+                .state
+                .panels
+                .view3d_panel
+                .vehicles
+                .iter()
+                .filter_map(|vehicle| serde_json::to_value(vehicle).ok())
+                .collect(),
+            columns: self.state.data.data_store.column_meta.clone(),
+            log_info: self.state.data.data_store.log_info.clone(),
+            transforms: self.state.data.data_store.transforms().clone(),
+        }
+    }
+
+    fn export_topic_files(&mut self) {
+        let Some(dir) = rfd::FileDialog::new().pick_folder() else {
+            return;
+        };
+        match arrow_files::export_topic_files(&self.state.data.data_store, &dir) {
+            Ok(count) => self.state.ui.notifications.success(format!(
+                "Exported {} topics to {}",
+                count,
+                display_name(&dir)
+            )),
+            Err(e) => {
+                tracing::error!("Failed to export Arrow files: {}", e);
+                self.state
+                    .ui
+                    .notifications
+                    .error(format!("Failed to export: {}", e));
+            }
+        }
+    }
+
+    fn export_table(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .set_file_name("tiplot_table.arrow")
+            .add_filter("Arrow Files", &["arrow", "feather"])
+            .save_file()
+        else {
+            return;
+        };
+        match arrow_files::export_table(&self.state.data.data_store, &path) {
+            Ok(()) => self
+                .state
+                .ui
+                .notifications
+                .success(format!("Exported {}", display_name(&path))),
+            Err(e) => {
+                tracing::error!("Failed to export Arrow table: {}", e);
+                self.state
+                    .ui
+                    .notifications
+                    .error(format!("Failed to export: {}", e));
+            }
+        }
+    }
+
+    fn autosave_if_due(&mut self) {
+        let interval = self.state.settings.data.autosave_interval_secs;
+        let result = match self.state.data.autosave.poll() {
+            Some(written) => written.map(|_| ()),
+            None if self.state.data.autosave.is_due(interval) => {
+                let session = self.session_metadata();
+                let data = &mut self.state.data;
+                data.autosave.save(&data.data_store, &session)
+            }
+            None => return,
+        };
+        if let Err(e) = result {
+            tracing::error!("Autosave failed: {}", e);
+            self.state
+                .ui
+                .notifications
+                .warning(format!("Autosave failed: {}", e));
+        }
+    }
+
+    fn load_layout(&mut self, path: PathBuf) {
+        match self
+            .state
+            .layout
+            .load_layout(path.clone(), &mut self.state.panels.view3d_panel.vehicles)
+        {
+            Ok(()) => {
+                self.state.panels.recent_files.push_layout(&path);
+                self.translate_layout_aliases();
+            }
+            Err(e) => self.state.ui.notifications.error(e),
+        }
+    }
+
+    fn load_data(&mut self, ctx: &egui::Context) {
+        if self.state.data.load_progress.is_some() {
+            return;
+        }
+
         if let Some(path) = rfd::FileDialog::new()
-            .add_filter("Arrow Files", &["arrow"])
+            .add_filter("Arrow Files", &["arrow", "feather", "ipc"])
             .pick_file()
         {
-            let mut data_store = crate::core::DataStore::new();
-            match data_store.load_from_arrow(&path) {
-                Ok(_) => {
-                    self.state.data.data_store = data_store;
-                    self.state.data.data_file_path = Some(path.clone());
-                    println!("✓ Data loaded from: {}", path.display());
+            start_file_load(
+                path,
+                self.state.data.ingest_filter.clone(),
+                self.memory_budget(),
+                self.state.data.tx.clone(),
+                ctx.clone(),
+            );
+        }
+    }
+
+    /// Keeps the folder watcher on the folder in the settings, and opens the newest
+    /// watched log in kiosk mode once no other load is running.
+    fn update_folder_watcher(&mut self, ctx: &egui::Context) {
+        let watch_dir = self.state.settings.watch_dir.as_deref();
+        let watched = self.state.data.folder_watcher.as_ref().map(|w| w.dir());
+        if watched != watch_dir {
+            self.state.data.folder_watcher = watch_dir.map(|dir| {
+                FolderWatcher::start(dir.to_path_buf(), self.state.data.tx.clone(), ctx.clone())
+            });
+            self.state.data.kiosk_log = None;
+        }
+
+        if self.state.data.load_progress.is_some() {
+            return;
+        }
+        let Some(path) = self.state.data.kiosk_log.take() else {
+            return;
+        };
+        if let Some(layout) = self.state.settings.default_layout.clone() {
+            self.load_layout(layout);
+        }
+        self.open_data_file(path, ctx);
+    }
+
+    fn open_data_file(&mut self, path: PathBuf, ctx: &egui::Context) {
+        if self.state.data.load_progress.is_some() {
+            return;
+        }
+
+        if !path.exists() {
+            self.state
+                .ui
+                .notifications
+                .error(format!("{} no longer exists", path.display()));
+            self.state.panels.recent_files.remove(&path);
+            return;
+        }
+        start_file_load(
+            path,
+            self.state.data.ingest_filter.clone(),
+            self.memory_budget(),
+            self.state.data.tx.clone(),
+            ctx.clone(),
+        );
+    }
 
-                    self.reupload_all_traces(frame);
-                    self.update_time_bounds();
+    /// Restores the last working state: the most recent layout, then the most recent
+    /// data file, so the layout's traces resolve against the loaded topics.
+    fn reopen_last(&mut self, ctx: &egui::Context) {
+        let recent = &self.state.panels.recent_files;
+        let (layout, data) = (
+            recent.layouts.first().cloned(),
+            recent.data.first().cloned(),
+        );
+        if layout.is_none() && data.is_none() {
+            self.state.ui.notifications.warning("No recent files");
+            return;
+        }
+
+        if let Some(path) = layout.filter(|p| p.exists()) {
+            self.load_layout(path);
+        }
+        if let Some(path) = data {
+            self.open_data_file(path, ctx);
+        }
+    }
+
+    fn load_data_folder(&mut self, ctx: &egui::Context) {
+        if self.state.data.load_progress.is_some() {
+            return;
+        }
+
+        if let Some(dir) = rfd::FileDialog::new().pick_folder() {
+            start_file_load(
+                dir,
+                self.state.data.ingest_filter.clone(),
+                self.memory_budget(),
+                self.state.data.tx.clone(),
+                ctx.clone(),
+            );
+        }
+    }
+
+    fn pick_merge_file(&mut self) {
+        if self.state.data.load_progress.is_some() {
+            return;
+        }
+
+        if let Some(path) = rfd::FileDialog::new()
+            .add_filter("Arrow Files", &["arrow", "feather", "ipc"])
+            .pick_file()
+        {
+            self.state.panels.merge_panel.open_for(path);
+        }
+    }
+
+    fn merge_data(&mut self, path: PathBuf, options: MergeOptions, ctx: &egui::Context) {
+        if self.state.data.load_progress.is_some() {
+            return;
+        }
+
+        let timeline = &self.state.timeline;
+        let bounds = (!self.state.data.data_store.is_empty())
+            .then_some((timeline.global_min, timeline.global_max));
+        self.state.data.load_progress = Some((0, 0));
+        start_merge_load(
+            path,
+            options,
+            bounds,
+            self.state.data.tx.clone(),
+            ctx.clone(),
+        );
+    }
+
+    fn finish_merge(&mut self, path: PathBuf, result: Result<Option<SessionMetadata>, String>) {
+        self.state.data.load_progress = None;
+        match result {
+            Ok(session) => {
+                if let Some(session) = session {
+                    for (topic, columns) in session.columns {
+                        let meta = self
+                            .state
+                            .data
+                            .data_store
+                            .column_meta
+                            .entry(topic)
+                            .or_default();
+                        for (col, column_meta) in columns {
+                            meta.entry(col).or_insert(column_meta);
+                        }
+                    }
+                    let data_store = &mut self.state.data.data_store;
+                    for (topic, transforms) in session.transforms {
+                        for transform in transforms {
+                            if data_store.has_transform(&topic, &transform) {
+                                continue;
+                            }
+                            if let Err(e) = data_store.add_transform(&topic, transform) {
+                                tracing::warn!("Skipping saved transform: {}", e);
+                            }
+                        }
+                    }
                 }
-                Err(e) => {
-                    eprintln!("✗ Failed to load data: {}", e);
-                    self.state.ui.menu_state.error_message = Some(format!("Failed to load: {}", e));
+                tracing::info!("Merged {}", path.display());
+                self.update_time_bounds();
+                self.state
+                    .ui
+                    .notifications
+                    .success(format!("Merged {}", display_name(&path)));
+            }
+            Err(e) => {
+                tracing::error!("Failed to merge {}: {}", path.display(), e);
+                self.state.ui.notifications.error(format!(
+                    "Failed to merge {}: {}",
+                    display_name(&path),
+                    e
+                ));
+            }
+        }
+    }
+
+    fn add_log_session(&mut self, ctx: &egui::Context) {
+        if self.state.data.load_progress.is_some() {
+            return;
+        }
+
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("Arrow Files", &["arrow", "feather", "ipc"])
+            .pick_file()
+        else {
+            return;
+        };
+        let stem = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().replace(SESSION_SEPARATOR, "_"))
+            .unwrap_or_else(|| "session".to_string());
+        let sessions = self.state.data.data_store.sessions();
+        let mut session = stem.clone();
+        let mut n = 2;
+        while sessions.contains(&session.as_str()) {
+            session = format!("{}_{}", stem, n);
+            n += 1;
+        }
+
+        self.state.data.load_progress = Some((0, 0));
+        start_session_load(path, session, self.state.data.tx.clone(), ctx.clone());
+    }
+
+    fn finish_load(
+        &mut self,
+        path: PathBuf,
+        result: Result<Box<LoadedFile>, String>,
+        frame: &mut eframe::Frame,
+    ) {
+        self.state.data.load_progress = None;
+
+        match result {
+            Ok(loaded) => {
+                let LoadedFile {
+                    data_store,
+                    session,
+                } = *loaded;
+                self.state.data.data_store = data_store;
+                self.state.timeline.annotations.clear();
+                if let Some(mirror) = &self.state.data.mirror {
+                    mirror.reset();
+                    mirror.publish_store(&self.state.data.data_store);
                 }
+                if let Some(session) = session {
+                    self.state.timeline.annotations = session.annotations;
+                    let vehicles: Vec<VehicleConfig> = session
+                        .vehicles
+                        .into_iter()
+                        .filter_map(|vehicle| match serde_json::from_value(vehicle) {
+                            Ok(vehicle) => Some(vehicle),
+                            Err(e) => {
+                                tracing::warn!("Ignoring unreadable vehicle: {}", e);
+                                None
+                            }
+                        })
+                        .collect();
+                    if !vehicles.is_empty() {
+                        self.state.panels.view3d_panel.vehicles = vehicles;
+                    }
+                    self.state.data.data_store.column_meta = session.columns;
+                    self.state.data.data_store.log_info = session.log_info;
+                    for (topic, transforms) in session.transforms {
+                        for transform in transforms {
+                            let data_store = &mut self.state.data.data_store;
+                            if let Err(e) = data_store.add_transform(&topic, transform) {
+                                tracing::warn!("Skipping saved transform: {}", e);
+                            }
+                        }
+                    }
+                }
+
+                self.state.panels.recent_files.push_data(&path);
+                self.state.data.data_file_path = Some(path.clone());
+                tracing::info!("Data loaded from: {}", path.display());
+                // Traces of topics the file lacks would otherwise keep drawing the old data.
+                self.clear_trace_buffers(frame);
+                self.reupload_all_traces(frame);
+                self.update_time_bounds();
+                self.translate_layout_aliases();
+
+                let warnings =
+                    logging::problem_count().saturating_sub(self.state.data.load_problem_baseline);
+                if warnings > 0 {
+                    self.state.ui.notifications.warning(format!(
+                        "Loaded {} with {} warning(s), see the log console",
+                        display_name(&path),
+                        warnings
+                    ));
+                } else {
+                    self.state
+                        .ui
+                        .notifications
+                        .success(format!("Loaded {}", display_name(&path)));
+                }
+            }
+            Err(e) => {
+                tracing::error!("Failed to load data: {}", e);
+                self.state
+                    .ui
+                    .notifications
+                    .error(format!("Failed to load: {}", e));
             }
         }
     }
 
+    /// Remaps traces of a layout made for another autopilot onto the loaded data.
+    fn translate_layout_aliases(&mut self) {
+        let changed = self.state.layout.translate_aliases(
+            &self.state.settings.alias_profiles,
+            &self.state.data.data_store,
+        );
+        if changed > 0 {
+            self.state
+                .ui
+                .notifications
+                .info(format!("Mapped {} trace(s) through topic aliases", changed));
+        }
+    }
+
+    /// Runs a preset's script and opens its plots in a new workspace.
+    fn apply_analysis_preset(&mut self, index: usize, frame: &mut eframe::Frame) {
+        let Some(preset) = PRESETS.get(index) else {
+            return;
+        };
+
+        let timeline = &mut self.state.timeline;
+        let markers_before = timeline.annotations.len();
+        let mut host = ScriptHost {
+            data_store: &mut self.state.data.data_store,
+            annotations: &mut timeline.annotations,
+            current_time: &mut timeline.current_time,
+            is_playing: &mut timeline.is_playing,
+            playback_speed: &mut timeline.playback_speed,
+            min_time: &mut timeline.min_time,
+            max_time: &mut timeline.max_time,
+        };
+        let output = script::run(preset.script, &mut host);
+        let markers = timeline.annotations.len() - markers_before;
+        if let Some(e) = &output.error {
+            tracing::warn!("Analysis preset '{}': {}", preset.name, e);
+        }
+
+        let data_store = &self.state.data.data_store;
+        let palette = self.state.layout.trace_palette;
+        let profiles = &self.state.settings.alias_profiles;
+        let plots: Vec<PlotTile> = preset
+            .plots
+            .iter()
+            .filter_map(|plot| {
+                let mut tile = PlotTile::new();
+                for &(topic, col) in plot.traces {
+                    let (topic, col) = match data_store.get_column(topic, col) {
+                        Some(_) => (topic.to_string(), col.to_string()),
+                        None => match aliases::translate(profiles, data_store, topic, col) {
+                            Some(translated) => translated,
+                            None => continue,
+                        },
+                    };
+                    let color = palette.next_color(&tile.trace_colors());
+                    tile.add_trace(topic, col, color);
+                }
+                tile.limits = plot.limits.to_vec();
+                tile.show_legend = true;
+                (!tile.traces.is_empty()).then_some(tile)
+            })
+            .collect();
+        if !plots.is_empty() {
+            self.state
+                .layout
+                .push_workspace(preset.name.to_string(), plots);
+        }
+
+        // Columns computed by the script are not on the GPU yet.
+        self.reupload_all_traces(frame);
+
+        match output.error {
+            Some(e) => self
+                .state
+                .ui
+                .notifications
+                .warning(format!("{}: {}", preset.name, e)),
+            None => self.state.ui.notifications.success(format!(
+                "{}: {} limit violations marked",
+                preset.name, markers
+            )),
+        }
+    }
+
+    fn clear_trace_buffers(&mut self, frame: &mut eframe::Frame) {
+        let wgpu_state = frame.wgpu_render_state().expect("WGPU not initialized");
+        let mut renderer_lock = wgpu_state.renderer.write();
+        if let Some(renderer) = renderer_lock.callback_resources.get_mut::<PlotRenderer>() {
+            renderer.clear_traces();
+        }
+    }
+
     fn reupload_all_traces(&mut self, frame: &mut eframe::Frame) {
         let wgpu_state = frame.wgpu_render_state().expect("WGPU not initialized");
         let device = &wgpu_state.device;
@@ -175,11 +920,264 @@ impl TiPlotApp {
         }
     }
 
+    fn plot_selected(&mut self, new_tile: bool) {
+        let mut items: Vec<(String, String)> = self
+            .state
+            .panels
+            .topic_selection
+            .selected
+            .iter()
+            .cloned()
+            .collect();
+        items.sort_by(|a, b| {
+            natord::compare(&format!("{}/{}", a.0, a.1), &format!("{}/{}", b.0, b.1))
+        });
+
+        let added = self.state.layout.add_traces(&items, new_tile);
+        self.state.panels.favorites.push_recent(&added);
+    }
+
+    fn handle_topic_action(&mut self, action: TopicAction, frame: &mut eframe::Frame) {
+        let data_store = &mut self.state.data.data_store;
+
+        let (result, stale, renamed) = match action {
+            TopicAction::None => return,
+            TopicAction::PlotSelected { new_tile } => {
+                self.plot_selected(new_tile);
+                return;
+            }
+            TopicAction::PlotColumn(topic, col) => {
+                let added = self.state.layout.add_traces(&[(topic, col)], false);
+                self.state.panels.favorites.push_recent(&added);
+                return;
+            }
+            TopicAction::Rename { from, to } => {
+                let stale = vec![(from.clone(), data_store.all_columns(&from))];
+                let result = data_store.rename_topic(&from, &to);
+                (result, stale, Some((from, to)))
+            }
+            TopicAction::Delete(topic) => {
+                let stale = vec![(topic.clone(), data_store.all_columns(&topic))];
+                data_store.delete_topic(&topic);
+                (Ok(()), stale, None)
+            }
+            TopicAction::DeleteColumn(topic, col) => {
+                let prefix = format!("{} [", col);
+                let cols = data_store
+                    .all_columns(&topic)
+                    .into_iter()
+                    .filter(|name| *name == col || name.starts_with(&prefix))
+                    .collect();
+                let result = data_store.delete_column(&topic, &col);
+                (result, vec![(topic, cols)], None)
+            }
+            TopicAction::Merge { source, target } => {
+                let stale = vec![
+                    (source.clone(), data_store.all_columns(&source)),
+                    (target.clone(), data_store.all_columns(&target)),
+                ];
+                let result = data_store.merge_topics(&source, &target);
+                (result, stale, Some((source, target)))
+            }
+            TopicAction::AddTransform(topic, transform) => {
+                let name = transform.name();
+                let result = data_store.add_transform(&topic, transform);
+                if result.is_ok() {
+                    tracing::info!("Added {} to {}", name, topic);
+                }
+                // Mapping the topic onto itself re-uploads it with the new columns.
+                (result, Vec::new(), Some((topic.clone(), topic)))
+            }
+        };
+
+        if let Err(e) = result {
+            tracing::error!("Topic operation failed: {}", e);
+            self.state.ui.notifications.error(e.to_string());
+            return;
+        }
+
+        // Traces and selections follow a renamed or merged topic; anything else that
+        // pointed at removed data is dropped.
+        let retarget = |topic: &mut String| {
+            if let Some((from, to)) = &renamed {
+                if topic == from {
+                    *topic = to.clone();
+                }
+            }
+        };
+
+        let data_store = &self.state.data.data_store;
+        for plot_tile in self.state.layout.panes_mut() {
+            for trace in &mut plot_tile.traces {
+                retarget(&mut trace.topic);
+            }
+            plot_tile
+                .traces
+                .retain(|trace| data_store.get_column(&trace.topic, &trace.col).is_some());
+            plot_tile.cached_tooltip_time = f32::NEG_INFINITY;
+            plot_tile.cached_tooltip_values.clear();
+        }
+
+        let selection = &mut self.state.panels.topic_selection;
+        selection.selected = selection
+            .selected
+            .drain()
+            .map(|(mut topic, col)| {
+                retarget(&mut topic);
+                (topic, col)
+            })
+            .filter(|(topic, col)| data_store.get_column(topic, col).is_some())
+            .collect();
+        selection.last_clicked = None;
+
+        let wgpu_state = frame.wgpu_render_state().expect("WGPU not initialized");
+        let device = &wgpu_state.device;
+
+        let mut renderer_lock = wgpu_state.renderer.write();
+        let renderer = renderer_lock
+            .callback_resources
+            .get_mut::<PlotRenderer>()
+            .unwrap();
+
+        for (topic, cols) in &stale {
+            for col in cols {
+                renderer.remove_trace(topic, col);
+            }
+        }
+
+        if let Some((_, topic)) = &renamed {
+            if let Some(cols) = data_store.topics.get(topic) {
+                if let Some(timestamps) = cols.get("timestamp") {
+                    for (col_name, values) in cols {
+                        if col_name == "timestamp" {
+                            continue;
+                        }
+                        renderer.upload_trace(device, topic, col_name, timestamps, values);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Recomputes unwrapped and filtered trace columns that are missing or stale and
+    /// uploads them.
+    fn update_derived_traces(&mut self, frame: &mut eframe::Frame) {
+        let derived: Vec<TraceConfig> = self
+            .state
+            .layout
+            .panes()
+            .flat_map(|plot_tile| &plot_tile.traces)
+            .filter(|trace| trace.filter.is_some() || trace.unwrap.is_some())
+            .cloned()
+            .collect();
+
+        let data_store = &mut self.state.data.data_store;
+
+        let active: HashSet<(String, String)> = derived
+            .iter()
+            .flat_map(|trace| {
+                trace
+                    .derived_cols()
+                    .into_iter()
+                    .map(|name| (trace.topic.clone(), name))
+            })
+            .collect();
+        data_store
+            .retain_derived(|topic, name| active.contains(&(topic.to_string(), name.to_string())));
+
+        let updated: Vec<(String, String)> = derived
+            .iter()
+            .flat_map(|trace| {
+                trace
+                    .ensure_derived(data_store)
+                    .into_iter()
+                    .map(|name| (trace.topic.clone(), name))
+            })
+            .collect();
+
+        if updated.is_empty() {
+            return;
+        }
+
+        let wgpu_state = frame.wgpu_render_state().expect("WGPU not initialized");
+        let device = &wgpu_state.device;
+
+        let mut renderer_lock = wgpu_state.renderer.write();
+        let renderer = renderer_lock
+            .callback_resources
+            .get_mut::<PlotRenderer>()
+            .unwrap();
+
+        for (topic, name) in updated {
+            if let (Some(timestamps), Some(values)) = (
+                data_store.get_column(&topic, "timestamp"),
+                data_store.get_column(&topic, &name),
+            ) {
+                renderer.upload_trace(device, &topic, &name, timestamps, values);
+            }
+        }
+    }
+
+    /// Memory budget set in the preferences, in bytes.
+    fn memory_budget(&self) -> Option<usize> {
+        self.state.settings.data.memory_budget()
+    }
+
+    /// Keeps topics used by plots, the 3D view and the selection in memory and spills the
+    /// rest once the memory budget is exceeded.
+    fn update_memory_budget(&mut self, frame: &mut eframe::Frame) {
+        let budget = self.memory_budget();
+        self.state.data.data_store.set_memory_budget(budget);
+
+        let mut hot: HashSet<String> = HashSet::new();
+        for plot_tile in self.state.layout.panes() {
+            hot.extend(plot_tile.traces.iter().map(|t| t.topic.clone()));
+        }
+        for vehicle in &self.state.panels.view3d_panel.vehicles {
+            hot.extend(vehicle.topics().into_iter().map(str::to_string));
+        }
+        hot.extend(
+            self.state
+                .panels
+                .topic_selection
+                .selected
+                .iter()
+                .map(|(topic, _)| topic.clone()),
+        );
+
+        let restored = self.state.data.data_store.touch_topics(&hot);
+        if restored.is_empty() {
+            return;
+        }
+
+        let wgpu_state = frame.wgpu_render_state().expect("WGPU not initialized");
+        let device = &wgpu_state.device;
+
+        let mut renderer_lock = wgpu_state.renderer.write();
+        let renderer = renderer_lock
+            .callback_resources
+            .get_mut::<PlotRenderer>()
+            .unwrap();
+
+        for topic in restored {
+            if let Some(cols) = self.state.data.data_store.topics.get(&topic) {
+                if let Some(timestamps) = cols.get("timestamp") {
+                    for (col_name, values) in cols {
+                        if col_name == "timestamp" {
+                            continue;
+                        }
+                        renderer.upload_trace(device, &topic, col_name, timestamps, values);
+                    }
+                }
+            }
+        }
+    }
+
     fn update_time_bounds(&mut self) {
         let mut min_time = f32::MAX;
         let mut max_time = f32::MIN;
 
-        for (_topic, cols) in &self.state.data.data_store.topics {
+        for cols in self.state.data.data_store.topics.values() {
             if let Some(timestamps) = cols.get("timestamp") {
                 if !timestamps.is_empty() {
                     min_time = min_time.min(timestamps[0]);
@@ -193,45 +1191,98 @@ impl TiPlotApp {
         }
     }
 
-    fn apply_interpolation_mode_to_all_tiles(&mut self, mode: crate::ui::tiles::InterpolationMode) {
-        fn update_tiles_recursive(
-            tiles: &mut egui_tiles::Tiles<crate::ui::tiles::PlotTile>,
-            tile_id: egui_tiles::TileId,
-            mode: crate::ui::tiles::InterpolationMode,
-        ) {
-            if let Some(tile) = tiles.get_mut(tile_id) {
-                match tile {
-                    egui_tiles::Tile::Pane(plot_tile) => {
-                        plot_tile.interpolation_mode = mode;
-                        plot_tile.cached_tooltip_time = f32::NEG_INFINITY;
-                        plot_tile.cached_tooltip_values.clear();
-                    }
-                    egui_tiles::Tile::Container(container) => {
-                        let children = match container {
-                            egui_tiles::Container::Linear(linear) => linear.children.clone(),
-                            egui_tiles::Container::Tabs(tabs) => tabs.children.clone(),
-                            egui_tiles::Container::Grid(grid) => grid.children().copied().collect(),
-                        };
-                        for child_id in children {
-                            update_tiles_recursive(tiles, child_id, mode);
-                        }
-                    }
-                }
-            }
+    /// Moves the start of the timeline up to the oldest sample still retained.
+    fn update_retained_time_bounds(&mut self) {
+        let earliest = self
+            .state
+            .data
+            .data_store
+            .topics
+            .values()
+            .filter_map(|cols| cols.get("timestamp")?.first().copied())
+            .reduce(f32::min);
+
+        if let Some(earliest) = earliest {
+            let timeline = &mut self.state.timeline;
+            timeline.global_min = earliest;
+            timeline.min_time = timeline.min_time.max(earliest);
+            timeline.current_time = timeline.current_time.max(earliest);
         }
+    }
 
-        if let Some(root_id) = self.state.layout.tree.root {
-            update_tiles_recursive(&mut self.state.layout.tree.tiles, root_id, mode);
+    fn apply_interpolation_mode_to_all_tiles(&mut self, mode: crate::ui::tiles::InterpolationMode) {
+        for plot_tile in self.state.layout.panes_mut() {
+            plot_tile.interpolation_mode = mode;
+            plot_tile.cached_tooltip_time = f32::NEG_INFINITY;
+            plot_tile.cached_tooltip_values.clear();
         }
     }
 
     fn handle_keyboard_input(&mut self, ctx: &egui::Context) {
+        if self.state.panels.command_palette.open {
+            return;
+        }
+        let typing = ctx.wants_keyboard_input();
         ctx.input(|i| {
-            if i.key_pressed(egui::Key::Space) {
+            // Digits pick a speed preset and R flips the playback direction.
+            if !typing && i.modifiers.is_none() {
+                let timeline = &mut self.state.timeline;
+                for (key, speed) in [
+                    egui::Key::Num1,
+                    egui::Key::Num2,
+                    egui::Key::Num3,
+                    egui::Key::Num4,
+                    egui::Key::Num5,
+                ]
+                .into_iter()
+                .zip(SPEED_PRESETS)
+                {
+                    if i.key_pressed(key) {
+                        apply_speed_preset(
+                            speed,
+                            &mut timeline.playback_speed,
+                            &mut timeline.playback_mode,
+                        );
+                    }
+                }
+                if i.key_pressed(egui::Key::R) {
+                    timeline.reverse = !timeline.reverse;
+                }
+            }
+
+            // Space toggles the selection while the topic panel has keyboard focus.
+            if !typing
+                && i.key_pressed(egui::Key::Space)
+                && !self.state.panels.topic_selection.keyboard_active
+            {
                 self.state.timeline.is_playing = !self.state.timeline.is_playing;
             }
 
-            if i.key_pressed(egui::Key::ArrowLeft) {
+            // Ctrl steps through clock topic samples, Shift through its value changes and
+            // Alt through annotations. Plain arrows also step by the clock topic once one is
+            // chosen, and by the shortest sample interval otherwise.
+            let jump_target = if typing {
+                None
+            } else if i.modifiers.command
+                || (i.modifiers.is_none() && self.state.timeline.clock_topic.is_some())
+            {
+                Some(JumpTarget::Sample)
+            } else if i.modifiers.shift {
+                Some(JumpTarget::Event)
+            } else if i.modifiers.alt {
+                Some(JumpTarget::Annotation)
+            } else {
+                None
+            };
+            if let Some(target) = jump_target {
+                for (key, forward) in [(egui::Key::ArrowLeft, false), (egui::Key::ArrowRight, true)]
+                {
+                    if i.key_pressed(key) {
+                        let data_store = &self.state.data.data_store;
+                        self.state.timeline.jump(target, forward, data_store);
+                    }
+                }
+            } else if !typing && i.key_pressed(egui::Key::ArrowLeft) {
                 let min_interval = self.estimate_min_sample_interval();
                 self.state.timeline.current_time = (self.state.timeline.current_time
                     - min_interval)
@@ -239,7 +1290,17 @@ impl TiPlotApp {
                 self.state.timeline.is_playing = false;
             }
 
-            if i.key_pressed(egui::Key::ArrowRight) {
+            if !typing && i.key_pressed(egui::Key::OpenBracket) {
+                let time = self.state.timeline.current_time;
+                self.state.timeline.loop_region.set_a(time);
+            }
+
+            if !typing && i.key_pressed(egui::Key::CloseBracket) {
+                let time = self.state.timeline.current_time;
+                self.state.timeline.loop_region.set_b(time);
+            }
+
+            if !typing && jump_target.is_none() && i.key_pressed(egui::Key::ArrowRight) {
                 let min_interval = self.estimate_min_sample_interval();
                 self.state.timeline.current_time = (self.state.timeline.current_time
                     + min_interval)
@@ -250,27 +1311,11 @@ impl TiPlotApp {
     }
 
     fn estimate_min_sample_interval(&self) -> f32 {
-        let mut min_interval = f32::MAX;
-
-        for (_topic_name, cols) in &self.state.data.data_store.topics {
-            if let Some(timestamps) = cols.get("timestamp") {
-                if timestamps.len() >= 2 {
-                    let samples_to_check = timestamps.len().min(100);
-                    for i in 1..samples_to_check {
-                        let interval = (timestamps[i] - timestamps[i - 1]).abs();
-                        if interval > 0.0 && interval < min_interval {
-                            min_interval = interval;
-                        }
-                    }
-                }
-            }
-        }
-
-        if min_interval == f32::MAX || min_interval <= 0.0 {
-            0.01
-        } else {
-            min_interval
-        }
+        self.state
+            .data
+            .data_store
+            .min_sample_interval()
+            .unwrap_or(0.01)
     }
 
     fn process_data(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
@@ -285,11 +1330,27 @@ impl TiPlotApp {
 
         let mut received_data = false;
         let mut batches_processed = 0;
+        let mut finished_load = None;
+        let mut trimmed = false;
         const MAX_BATCHES_PER_FRAME: usize = 5;
 
+        self.state
+            .data
+            .data_store
+            .set_ingest_filter(&self.state.data.ingest_filter.read().unwrap());
+
+        let retention = if self.state.data.load_progress.is_some() {
+            RetentionPolicy::Unlimited
+        } else {
+            self.state.ui.menu_state.retention
+        };
+
         while let Ok(msg) = self.state.data.rx.try_recv() {
             match msg {
                 DataMessage::Metadata(meta) => {
+                    if let Some(mirror) = &self.state.data.mirror {
+                        mirror.publish_metadata(meta);
+                    }
                     if let (Some(min), Some(max)) = (meta.min_timestamp, meta.max_timestamp) {
                         let raw_min = min as f64 / 1_000_000.0;
                         let raw_max = max as f64 / 1_000_000.0;
@@ -308,23 +1369,47 @@ impl TiPlotApp {
                             self.state.timeline.global_max =
                                 (raw_max - self.state.data.data_store.start_time as f64) as f32;
 
-                            if self.state.timeline.lock_viewport {
-                                self.state.timeline.max_time = self.state.timeline.global_max;
-                                self.state.timeline.min_time = self.state.timeline.max_time
-                                    - self.state.timeline.last_viewport_width;
-                            } else {
-                                self.state.timeline.max_time = self.state.timeline.global_max;
-                            }
+                            // A frozen view stays on whatever fired the trigger.
+                            if !self.state.timeline.frozen {
+                                if self.state.timeline.lock_viewport {
+                                    self.state.timeline.max_time = self.state.timeline.global_max;
+                                    self.state.timeline.min_time = self.state.timeline.max_time
+                                        - self.state.timeline.last_viewport_width;
+                                } else {
+                                    self.state.timeline.max_time = self.state.timeline.global_max;
+                                }
 
-                            if self.state.timeline.lock_to_last {
-                                self.state.timeline.current_time = self.state.timeline.max_time;
+                                if self.state.timeline.lock_to_last {
+                                    self.state.timeline.current_time = self.state.timeline.max_time;
+                                }
                             }
                         }
                     }
                     received_data = true;
                 }
+                DataMessage::LogInfo(info) => self.state.data.data_store.log_info.merge(info),
+                DataMessage::Cursor(time) => {
+                    let start_time = self.state.data.data_store.start_time as f64;
+                    self.state.timeline.current_time = (time - start_time) as f32;
+                    self.state.timeline.is_playing = false;
+                }
                 DataMessage::NewBatch(topic, batch) => {
+                    if self.state.data.load_progress.is_none() {
+                        self.state.data.autosave.mark_dirty();
+                        self.state
+                            .panels
+                            .health_panel
+                            .record(&topic, batch.num_rows());
+                    }
+                    if let Some(mirror) = &self.state.data.mirror {
+                        mirror.publish_batch(&topic, &batch);
+                    }
                     self.state.data.data_store.ingest(topic.clone(), batch);
+                    trimmed |= self
+                        .state
+                        .data
+                        .data_store
+                        .apply_retention(&topic, retention);
 
                     if let Some(cols) = self.state.data.data_store.topics.get(&topic) {
                         if let Some(timestamps) = cols.get("timestamp") {
@@ -344,12 +1429,69 @@ impl TiPlotApp {
                         break;
                     }
                 }
+                DataMessage::LoadStarted(path) => {
+                    tracing::info!("Loading data from: {}", path.display());
+                    self.state.data.load_problem_baseline = logging::problem_count();
+                    self.state.data.load_progress = Some((0, 0));
+                }
+                DataMessage::LoadProgress {
+                    bytes_read,
+                    total_bytes,
+                } => {
+                    self.state.data.load_progress = Some((bytes_read, total_bytes));
+                }
+                DataMessage::LoadFinished(path, result) => {
+                    finished_load = Some((path, result));
+                    break;
+                }
+                DataMessage::Merged(path, result) => {
+                    self.finish_merge(path, result);
+                    received_data = true;
+                }
+                DataMessage::LogAppeared(path) => {
+                    if self.state.settings.kiosk_mode {
+                        self.state.data.kiosk_log = Some(path);
+                    } else {
+                        self.state.ui.menu_state.new_logs.push(path);
+                    }
+                }
+                DataMessage::SessionLoaded(path, session, result) => {
+                    self.state.data.load_progress = None;
+                    match result {
+                        Ok(()) => {
+                            tracing::info!("Added {} as session '{}'", path.display(), session);
+                            self.update_time_bounds();
+                            self.state.ui.notifications.success(format!(
+                                "Added {} as session '{}'",
+                                display_name(&path),
+                                session
+                            ));
+                        }
+                        Err(e) => {
+                            tracing::error!("Failed to add log session: {}", e);
+                            self.state.ui.notifications.error(format!(
+                                "Failed to add {}: {}",
+                                display_name(&path),
+                                e
+                            ));
+                        }
+                    }
+                    received_data = true;
+                }
             }
         }
 
+        drop(renderer_lock);
+        if trimmed {
+            self.update_retained_time_bounds();
+        }
+        if let Some((path, result)) = finished_load {
+            self.finish_load(path, result, frame);
+        }
+
         if received_data {
             self.state.data.receiving_data = true;
-            self.state.data.last_data_time = Some(std::time::Instant::now());
+            self.state.data.last_data_time = Some(Instant::now());
             ctx.request_repaint();
         } else {
             if let Some(last_time) = self.state.data.last_data_time {
@@ -364,6 +1506,99 @@ impl TiPlotApp {
         }
     }
 
+    /// Runs the actions of triggers fired by the data received this frame.
+    fn poll_triggers(&mut self) {
+        let now = Instant::now();
+        let timeline = &mut self.state.timeline;
+        for trigger in &mut self.state.panels.trigger_panel.triggers {
+            let fired = trigger.poll(&self.state.data.data_store, now);
+            let Some(&first) = fired.first() else {
+                continue;
+            };
+
+            let text = format!("Trigger: {}", trigger.condition.describe());
+            if trigger.actions.annotate {
+                timeline
+                    .annotations
+                    .extend(fired.iter().map(|&time| Annotation {
+                        time,
+                        text: text.clone(),
+                    }));
+            }
+            if trigger.actions.freeze_view {
+                timeline.frozen = true;
+                timeline.current_time = first;
+            }
+            if trigger.actions.pause_stream {
+                self.state.data.stream.set_paused(true);
+            }
+            tracing::info!("{} at t={:.3}", text, first);
+            self.state.ui.notifications.warning(text);
+        }
+    }
+
+    /// Holds the window open while an unsaved layout needs confirmation to exit.
+    fn handle_close_request(&mut self, ctx: &egui::Context) {
+        if self.state.ui.exit_confirmed || !ctx.input(|i| i.viewport().close_requested()) {
+            return;
+        }
+
+        match self.confirmation_for(&MenuAction::Exit) {
+            Some(kind) => {
+                ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+                self.state
+                    .ui
+                    .menu_state
+                    .request_confirmation(kind, MenuAction::Exit);
+            }
+            None => self.state.ui.exit_confirmed = true,
+        }
+    }
+
+    /// Raises a toast when the stream or mirror connection changes in a way the user
+    /// would otherwise only notice in the data source panel.
+    fn notify_connection_changes(&mut self) {
+        let status = self.state.data.stream.status();
+        let previous = std::mem::replace(&mut self.state.data.last_stream_status, status.clone());
+        if status != previous {
+            let notifications = &mut self.state.ui.notifications;
+            match (&previous, &status) {
+                (_, StreamStatus::Error(e)) => notifications.error(format!("Data stream: {}", e)),
+                (StreamStatus::Following(addr), _) => {
+                    notifications.info(format!("Stopped following {}", addr))
+                }
+                (StreamStatus::Connected(addr), _) => {
+                    notifications.info(format!("Client {} disconnected", addr))
+                }
+                (_, StreamStatus::Connected(addr)) => {
+                    notifications.info(format!("Client {} connected", addr))
+                }
+                (_, StreamStatus::Following(addr)) => {
+                    notifications.success(format!("Following session at {}", addr))
+                }
+                _ => {}
+            }
+        }
+
+        for error in self.state.data.stream.take_errors() {
+            self.state
+                .ui
+                .notifications
+                .warning(format!("Data stream: {}", error));
+        }
+
+        let mirror_status = self.state.data.mirror.as_ref().map(MirrorPublisher::status);
+        if mirror_status != self.state.data.last_mirror_status {
+            if let Some(MirrorStatus::Error(e)) = &mirror_status {
+                self.state
+                    .ui
+                    .notifications
+                    .error(format!("Mirror failed: {}", e));
+            }
+            self.state.data.last_mirror_status = mirror_status;
+        }
+    }
+
     fn render_top_menu_bar(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
         egui::TopBottomPanel::top("menu_bar")
             .exact_height(28.0)
@@ -373,15 +1608,21 @@ impl TiPlotApp {
                         ui,
                         &mut self.state.ui.menu_state,
                         &self.state.ui.layouts_dir,
+                        &self.state.panels.recent_files,
                         self.state.layout.global_interpolation_mode,
+                        self.state.layout.trace_palette,
+                        &self.state.data.data_store,
                     );
-                    self.process_menu_action(action, frame);
+                    self.process_menu_action(action, ctx, frame);
 
                     ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                         ui.add_space(3.0);
 
                         let indicator_radius = 6.0;
-                        let indicator_color = if self.state.data.receiving_data {
+                        let paused = self.state.data.stream.is_paused();
+                        let indicator_color = if paused {
+                            egui::Color32::from_rgb(230, 180, 50)
+                        } else if self.state.data.receiving_data {
                             egui::Color32::from_rgb(255, 50, 50)
                         } else {
                             egui::Color32::from_rgb(128, 128, 128)
@@ -389,7 +1630,7 @@ impl TiPlotApp {
 
                         let (rect, response) = ui.allocate_exact_size(
                             egui::vec2(indicator_radius * 2.0 + 4.0, indicator_radius * 2.0),
-                            egui::Sense::hover(),
+                            egui::Sense::click(),
                         );
 
                         ui.painter().circle_filled(
@@ -398,11 +1639,55 @@ impl TiPlotApp {
                             indicator_color,
                         );
 
-                        response.on_hover_text(if self.state.data.receiving_data {
+                        let status = if paused {
+                            "Paused"
+                        } else if self.state.data.receiving_data {
                             "Receiving data..."
                         } else {
                             "Idle"
-                        });
+                        };
+                        if response
+                            .on_hover_text(format!("{} (click for data source controls)", status))
+                            .clicked()
+                        {
+                            self.state.panels.stream_panel.open =
+                                !self.state.panels.stream_panel.open;
+                        }
+
+                        ui.add_space(8.0);
+
+                        let log_panel = &mut self.state.panels.log_panel;
+                        let unseen = log_panel.unseen_problems();
+                        let log_button = if unseen > 0 {
+                            egui::Button::new(
+                                egui::RichText::new(format!("{} {}", icons::WARNING, unseen))
+                                    .color(egui::Color32::from_rgb(230, 180, 50)),
+                            )
+                        } else {
+                            egui::Button::new(icons::TERMINAL)
+                        };
+                        if ui
+                            .add(log_button.frame(false))
+                            .on_hover_text("Log console")
+                            .clicked()
+                        {
+                            log_panel.open = !log_panel.open;
+                        }
+
+                        if self.state.timeline.frozen {
+                            ui.add_space(8.0);
+                            let frozen = egui::Button::new(
+                                egui::RichText::new(icons::SNOWFLAKE)
+                                    .color(egui::Color32::from_rgb(120, 180, 255)),
+                            );
+                            if ui
+                                .add(frozen.frame(false))
+                                .on_hover_text("View frozen by a trigger (click to resume)")
+                                .clicked()
+                            {
+                                self.state.timeline.frozen = false;
+                            }
+                        }
 
                         ui.add_space(8.0);
 
@@ -415,7 +1700,32 @@ impl TiPlotApp {
                             egui::Color32::from_rgb(200, 100, 100)
                         };
 
-                        ui.label(egui::RichText::new(fps_text).color(fps_color).monospace());
+                        let fps_label = egui::Label::new(
+                            egui::RichText::new(fps_text).color(fps_color).monospace(),
+                        )
+                        .sense(egui::Sense::click());
+                        if ui
+                            .add(fps_label)
+                            .on_hover_text("Click for a frame time and memory breakdown")
+                            .clicked()
+                        {
+                            self.state.ui.profiler.open = !self.state.ui.profiler.open;
+                        }
+
+                        if let Some((bytes_read, total_bytes)) = self.state.data.load_progress {
+                            ui.add_space(8.0);
+                            let fraction = if total_bytes > 0 {
+                                bytes_read as f32 / total_bytes as f32
+                            } else {
+                                0.0
+                            };
+                            ui.add(
+                                egui::ProgressBar::new(fraction)
+                                    .desired_width(160.0)
+                                    .show_percentage(),
+                            )
+                            .on_hover_text("Loading data file...");
+                        }
                     });
                 });
             });
@@ -437,9 +1747,17 @@ impl TiPlotApp {
                     &mut self.state.timeline.current_time,
                     &mut self.state.timeline.is_playing,
                     &mut self.state.timeline.playback_speed,
+                    &mut self.state.timeline.reverse,
                     &mut self.state.timeline.lock_to_last,
                     &mut self.state.timeline.lock_viewport,
                     &mut self.state.timeline.always_show_playback_tooltip,
+                    &mut self.state.timeline.annotations,
+                    &mut self.state.timeline.loop_region,
+                    &mut self.state.timeline.playback_mode,
+                    &mut self.state.timeline.clock_topic,
+                    &mut self.state.timeline.time_format,
+                    &mut self.state.timeline.activity,
+                    &self.state.data.data_store,
                 );
             });
     }
@@ -453,7 +1771,7 @@ impl TiPlotApp {
                     ui.vertical_centered(|ui| {
                         ui.add_space(6.0);
                         if ui
-                            .add(egui::Button::new(format!("{}", icons::SIDEBAR)))
+                            .add(egui::Button::new(icons::SIDEBAR.to_string()))
                             .on_hover_text("Show topics panel")
                             .clicked()
                         {
@@ -483,7 +1801,7 @@ impl TiPlotApp {
                     ui.horizontal(|ui| {
                         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                             if ui
-                                .add(egui::Button::new(format!("{}", icons::CARET_LEFT)))
+                                .add(egui::Button::new(icons::CARET_LEFT.to_string()))
                                 .on_hover_text("Hide topics panel")
                                 .clicked()
                             {
@@ -492,15 +1810,21 @@ impl TiPlotApp {
                         });
                     });
                     ui.separator();
-                    render_topic_panel(
+                    let action = render_topic_panel(
                         ui,
                         &self.state.data.data_store,
                         &mut self.state.panels.topic_selection,
+                        &mut self.state.panels.favorites,
                         &mut self.state.layout.dragged_item,
                     );
+                    self.handle_topic_action(action, frame);
                 });
         }
 
+        if self.state.panels.view3d_detached {
+            return;
+        }
+
         if self.state.panels.view3d_panel_collapsed {
             egui::SidePanel::right("view3d_panel_collapsed")
                 .exact_width(30.0)
@@ -509,7 +1833,7 @@ impl TiPlotApp {
                     ui.vertical_centered(|ui| {
                         ui.add_space(6.0);
                         if ui
-                            .add(egui::Button::new(format!("{}", icons::CUBE_FOCUS)))
+                            .add(egui::Button::new(icons::CUBE_FOCUS.to_string()))
                             .on_hover_text("Show 3D view panel")
                             .clicked()
                         {
@@ -523,7 +1847,7 @@ impl TiPlotApp {
                             painter.text(
                                 center,
                                 egui::Align2::CENTER_CENTER,
-                                "3D View",
+                                tr("3D View"),
                                 egui::FontId::default(),
                                 ui.style().visuals.text_color(),
                             );
@@ -539,13 +1863,21 @@ impl TiPlotApp {
                     ui.horizontal(|ui| {
                         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                             if ui
-                                .add(egui::Button::new(format!("{}", icons::CARET_RIGHT)))
+                                .add(egui::Button::new(icons::CARET_RIGHT.to_string()))
                                 .on_hover_text("Hide 3D view panel")
                                 .clicked()
                             {
                                 self.state.panels.view3d_panel_collapsed = true;
                             }
 
+                            if ui
+                                .add(egui::Button::new(icons::ARROW_SQUARE_OUT.to_string()))
+                                .on_hover_text("Open in New Window")
+                                .clicked()
+                            {
+                                self.state.panels.view3d_detached = true;
+                            }
+
                             if ui
                                 .button(egui::RichText::new(icons::GEAR))
                                 .on_hover_text("Open Configuration")
@@ -557,20 +1889,130 @@ impl TiPlotApp {
                         });
                     });
                     ui.separator();
-                    render_view3d_panel(
+                    let start = Instant::now();
+                    if let Some(time) = render_view3d_panel(
                         ui,
                         frame,
                         &mut self.state.panels.view3d_panel,
                         &self.state.data.data_store,
                         self.state.timeline.current_time,
                         &self.state.model_cache,
-                    );
+                    ) {
+                        self.state.timeline.current_time = time;
+                    }
+                    self.state
+                        .ui
+                        .profiler
+                        .add_since(ProfileSection::Scene3D, start);
+                });
+        }
+    }
+
+    fn render_workspace_tabs(&mut self, ui: &mut egui::Ui) {
+        enum TabAction {
+            Switch(usize),
+            StartRename(usize),
+            Rename(usize, String),
+            CancelRename,
+            Remove(usize),
+            Add,
+        }
+
+        let layout = &mut self.state.layout;
+        let can_remove = layout.workspaces.len() > 1;
+        let mut action = None;
+        ui.horizontal(|ui| {
+            for (index, workspace) in layout.workspaces.iter().enumerate() {
+                if let Some((renaming, name)) = &mut layout.renaming_workspace {
+                    if *renaming == index {
+                        let response =
+                            ui.add(egui::TextEdit::singleline(name).desired_width(120.0));
+                        response.request_focus();
+                        if ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                            action = Some(TabAction::CancelRename);
+                        } else if response.lost_focus() {
+                            action = Some(TabAction::Rename(index, name.clone()));
+                        }
+                        continue;
+                    }
+                }
+
+                let response =
+                    ui.selectable_label(index == layout.active_workspace, &workspace.name);
+                if response.double_clicked() {
+                    action = Some(TabAction::StartRename(index));
+                } else if response.clicked() {
+                    action = Some(TabAction::Switch(index));
+                }
+                response.context_menu(|ui| {
+                    if ui
+                        .button(format!("{} Rename", icons::PENCIL_SIMPLE))
+                        .clicked()
+                    {
+                        action = Some(TabAction::StartRename(index));
+                        ui.close_menu();
+                    }
+                    if ui
+                        .add_enabled(
+                            can_remove,
+                            egui::Button::new(format!("{} Delete", icons::TRASH)),
+                        )
+                        .clicked()
+                    {
+                        action = Some(TabAction::Remove(index));
+                        ui.close_menu();
+                    }
                 });
+            }
+
+            if ui
+                .small_button(icons::PLUS.to_string())
+                .on_hover_text("New workspace")
+                .clicked()
+            {
+                action = Some(TabAction::Add);
+            }
+        });
+
+        match action {
+            Some(TabAction::Switch(index)) => layout.switch_workspace(index),
+            Some(TabAction::StartRename(index)) => {
+                layout.renaming_workspace = Some((index, layout.workspaces[index].name.clone()));
+            }
+            Some(TabAction::Rename(index, name)) => {
+                layout.rename_workspace(index, &name);
+                layout.renaming_workspace = None;
+            }
+            Some(TabAction::CancelRename) => layout.renaming_workspace = None,
+            Some(TabAction::Remove(index)) => layout.remove_workspace(index),
+            Some(TabAction::Add) => layout.add_workspace(),
+            None => {}
         }
     }
 
-    fn render_central_panel(&mut self, ctx: &egui::Context) {
+    /// Whether to show the welcome screen instead of an empty workspace.
+    fn shows_welcome(&self) -> bool {
+        self.state.data.data_store.is_empty()
+            && self.state.data.load_progress.is_none()
+            && self.state.layout.panes().all(|pane| pane.traces.is_empty())
+    }
+
+    fn render_central_panel(&mut self, ctx: &egui::Context, frame: &eframe::Frame) -> MenuAction {
+        let mut action = MenuAction::None;
         egui::CentralPanel::default().show(ctx, |ui| {
+            self.render_workspace_tabs(ui);
+            ui.separator();
+
+            if self.shows_welcome() {
+                action = render_welcome(
+                    ui,
+                    &self.state.panels.recent_files,
+                    &self.state.data.stream.status(),
+                );
+                return;
+            }
+
+            let mut added_traces = Vec::new();
             let mut behavior = TiPlotBehavior {
                 min_time: &mut self.state.timeline.min_time,
                 max_time: &mut self.state.timeline.max_time,
@@ -580,53 +2022,567 @@ impl TiPlotApp {
                 data_store: &self.state.data.data_store,
                 topic_selection: &self.state.panels.topic_selection,
                 split_request: &mut self.state.layout.split_request,
+                clear_traces_request: &mut self.state.layout.clear_traces_request,
                 dragged_item: &mut self.state.layout.dragged_item,
+                added_traces: &mut added_traces,
+                focused_tile: &mut self.state.layout.focused_tile,
+                map_tiles: &mut self.state.layout.map_tiles,
+                video_frames: &mut self.state.layout.video_frames,
+                image_frames: &mut self.state.layout.image_frames,
+                sonifier: &mut self.state.timeline.sonifier,
                 reset_sizes_request: &mut self.state.layout.reset_sizes_request,
+                arrange_grid_request: &mut self.state.layout.arrange_grid_request,
                 is_playing: &self.state.timeline.is_playing,
                 always_show_playback_tooltip: &self.state.timeline.always_show_playback_tooltip,
+                y_sync: &self.state.layout.y_sync,
+                shared_hover_time: self
+                    .state
+                    .timeline
+                    .shared_crosshair
+                    .then_some(self.state.timeline.hover_time)
+                    .flatten(),
+                next_hover_time: &mut self.state.timeline.next_hover_time,
+                frame,
+                vehicles: &mut self.state.panels.view3d_panel.vehicles,
+                model_cache: &self.state.model_cache,
+                time_format: self.state.timeline.time_format,
+                trace_palette: self.state.layout.trace_palette,
+                profiler: &mut self.state.ui.profiler,
+                detach_request: Some(&mut self.state.layout.detach_request),
             };
             self.state.layout.tree.ui(&mut behavior, ui);
+            self.state.panels.favorites.push_recent(&added_traces);
 
             if !ui.input(|i| i.pointer.primary_down()) {
                 self.state.layout.dragged_item = None;
             }
         });
+        action
+    }
+
+    fn palette_entries(&self) -> Vec<PaletteEntry> {
+        let menu = |label: &'static str, action: MenuAction| {
+            PaletteEntry::new(tr(label), "Command", PaletteCommand::Menu(action))
+        };
+        let mut entries = vec![
+            menu("Open File...", MenuAction::LoadData),
+            menu("Load Arrow Folder...", MenuAction::LoadDataFolder),
+            menu("Reopen Last", MenuAction::ReopenLast),
+            menu("Save Data...", MenuAction::SaveData),
+            menu("Clear Data", MenuAction::ClearData),
+            menu("Column Metadata...", MenuAction::OpenColumnMetadata),
+            menu("Log Info...", MenuAction::OpenLogInfo),
+            menu("Script Console...", MenuAction::OpenScriptConsole),
+            menu("Triggers...", MenuAction::OpenTriggers),
+            menu("Live Status...", MenuAction::OpenLiveStatus),
+            menu("Power Analysis...", MenuAction::OpenPowerAnalysis),
+            menu("Align Sessions...", MenuAction::OpenSessionAlignment),
+            menu("Topic Aliases...", MenuAction::OpenTopicAliases),
+            menu("Synthetic Data...", MenuAction::OpenSyntheticData),
+            menu("Preferences...", MenuAction::OpenPreferences),
+            PaletteEntry::new(
+                tr("Toggle Scatter Mode"),
+                "Plot",
+                PaletteCommand::ToggleScatter,
+            ),
+        ];
+
+        entries.extend(PRESETS.iter().enumerate().map(|(index, preset)| {
+            PaletteEntry::new(
+                preset.name,
+                "Analysis",
+                PaletteCommand::Menu(MenuAction::ApplyAnalysisPreset(index)),
+            )
+        }));
+
+        let layouts = LayoutData::list_layouts(&self.state.ui.layouts_dir).unwrap_or_default();
+        entries.extend(layouts.into_iter().map(|(name, path)| {
+            PaletteEntry::new(
+                format!("{} {}", tr("Load Layout"), name),
+                "Layout",
+                PaletteCommand::Menu(MenuAction::LoadLayout(path)),
+            )
+        }));
+
+        entries.extend(self.state.timeline.annotations.iter().map(|annotation| {
+            PaletteEntry::new(
+                format!(
+                    "{} {} ({})",
+                    tr("Jump to"),
+                    annotation.text,
+                    self.state.timeline.time_format.format(
+                        annotation.time,
+                        self.state.data.data_store.start_time,
+                        1
+                    )
+                ),
+                "Annotation",
+                PaletteCommand::JumpTo(annotation.time),
+            )
+        }));
+
+        let data_store = &self.state.data.data_store;
+        for topic in data_store.get_topics() {
+            entries.extend(data_store.get_columns(topic).into_iter().map(|col| {
+                PaletteEntry::new(
+                    format!("{}/{}", topic, col),
+                    "Plot",
+                    PaletteCommand::PlotColumn(topic.clone(), col.clone()),
+                )
+            }));
+        }
+        entries
+    }
+
+    fn render_command_palette(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
+        if !self.state.panels.command_palette.open {
+            return;
+        }
+
+        let entries = self.palette_entries();
+        let Some(command) =
+            render_command_palette(ctx, &mut self.state.panels.command_palette, entries)
+        else {
+            return;
+        };
+        match command {
+            PaletteCommand::Menu(action) => self.process_menu_action(action, ctx, frame),
+            PaletteCommand::PlotColumn(topic, col) => {
+                self.handle_topic_action(TopicAction::PlotColumn(topic, col), frame);
+            }
+            PaletteCommand::ToggleScatter => {
+                let layout = &mut self.state.layout;
+                let tile = layout
+                    .target_tile()
+                    .and_then(|tile_id| layout.tree.tiles.get_mut(tile_id));
+                if let Some(egui_tiles::Tile::Pane(tile)) = tile {
+                    tile.scatter_mode = !tile.scatter_mode;
+                }
+            }
+            PaletteCommand::JumpTo(time) => {
+                let timeline = &mut self.state.timeline;
+                timeline.current_time = time.clamp(timeline.global_min, timeline.global_max);
+                timeline.is_playing = false;
+            }
+        }
+    }
+
+    fn update_profiler_memory(&mut self, frame: &eframe::Frame) {
+        let profiler = &mut self.state.ui.profiler;
+        if !profiler.open || !profiler.memory_is_stale() {
+            return;
+        }
+
+        let data_store = &self.state.data.data_store;
+        let mut gpu = frame
+            .wgpu_render_state()
+            .and_then(|state| {
+                let renderer = state.renderer.read();
+                renderer
+                    .callback_resources
+                    .get::<PlotRenderer>()
+                    .map(PlotRenderer::memory_by_topic)
+            })
+            .unwrap_or_default();
+
+        let mut topics: Vec<TopicMemory> = data_store
+            .topic_memory_usage()
+            .into_iter()
+            .map(|(topic, host_bytes)| TopicMemory {
+                gpu_bytes: gpu.remove(&topic).unwrap_or(0),
+                spilled: data_store.is_spilled(&topic),
+                host_bytes,
+                topic,
+            })
+            .collect();
+        topics.extend(gpu.into_iter().map(|(topic, gpu_bytes)| TopicMemory {
+            spilled: data_store.is_spilled(&topic),
+            host_bytes: 0,
+            gpu_bytes,
+            topic,
+        }));
+        profiler.set_topic_memory(topics);
+    }
+
+    /// Shows detached plot tiles in native windows of their own, docking them back when
+    /// their window is closed.
+    fn render_detached_windows(&mut self, ctx: &egui::Context, frame: &eframe::Frame) {
+        if let Some(tile_id) = self.state.layout.detach_request.take() {
+            self.state.layout.detach_tile(tile_id);
+        }
+        if self.state.panels.view3d_detached {
+            self.render_detached_view3d(ctx, frame);
+        }
+        if self.state.layout.detached.is_empty() {
+            return;
+        }
+
+        // Splits, size resets and grid arrangement only apply to the tree, so they are ignored here.
+        let mut split_request = None;
+        let mut reset_sizes_request = false;
+        let mut arrange_grid_request = false;
+        let mut added_traces = Vec::new();
+        let mut behavior = TiPlotBehavior {
+            min_time: &mut self.state.timeline.min_time,
+            max_time: &mut self.state.timeline.max_time,
+            global_min: self.state.timeline.global_min,
+            global_max: self.state.timeline.global_max,
+            current_time: &mut self.state.timeline.current_time,
+            data_store: &self.state.data.data_store,
+            topic_selection: &self.state.panels.topic_selection,
+            split_request: &mut split_request,
+            clear_traces_request: &mut self.state.layout.clear_traces_request,
+            dragged_item: &mut self.state.layout.dragged_item,
+            added_traces: &mut added_traces,
+            focused_tile: &mut self.state.layout.focused_tile,
+            map_tiles: &mut self.state.layout.map_tiles,
+            video_frames: &mut self.state.layout.video_frames,
+            image_frames: &mut self.state.layout.image_frames,
+            sonifier: &mut self.state.timeline.sonifier,
+            reset_sizes_request: &mut reset_sizes_request,
+            arrange_grid_request: &mut arrange_grid_request,
+            is_playing: &self.state.timeline.is_playing,
+            always_show_playback_tooltip: &self.state.timeline.always_show_playback_tooltip,
+            y_sync: &self.state.layout.y_sync,
+            shared_hover_time: self
+                .state
+                .timeline
+                .shared_crosshair
+                .then_some(self.state.timeline.hover_time)
+                .flatten(),
+            next_hover_time: &mut self.state.timeline.next_hover_time,
+            frame,
+            vehicles: &mut self.state.panels.view3d_panel.vehicles,
+            model_cache: &self.state.model_cache,
+            time_format: self.state.timeline.time_format,
+            trace_palette: self.state.layout.trace_palette,
+            profiler: &mut self.state.ui.profiler,
+            detach_request: None,
+        };
+
+        let mut dock = Vec::new();
+        for (index, detached) in self.state.layout.detached.iter_mut().enumerate() {
+            let title = format!("TiPlot - {}", detached.tile.title());
+            let builder = egui::ViewportBuilder::default()
+                .with_title(&title)
+                .with_inner_size([800.0, 500.0]);
+
+            let closed =
+                ctx.show_viewport_immediate(detached.viewport_id, builder, |ctx, class| {
+                    let mut dock_clicked = false;
+                    let mut contents = |ui: &mut egui::Ui| {
+                        ui.horizontal(|ui| {
+                            if ui
+                                .button(format!("{} Dock", icons::ARROW_SQUARE_IN))
+                                .on_hover_text("Move back into the main window")
+                                .clicked()
+                            {
+                                dock_clicked = true;
+                            }
+                        });
+                        // Tab dragging has no meaning outside the tree.
+                        let _ = behavior.pane_ui(ui, detached.tile_id, &mut detached.tile);
+                    };
+
+                    if class == egui::ViewportClass::Embedded {
+                        // Without native multi-window support the tile floats in the main window.
+                        let mut open = true;
+                        egui::Window::new(&title)
+                            .id(egui::Id::new(detached.viewport_id))
+                            .open(&mut open)
+                            .default_size([640.0, 400.0])
+                            .show(ctx, |ui| contents(ui));
+                        dock_clicked || !open
+                    } else {
+                        egui::CentralPanel::default().show(ctx, |ui| contents(ui));
+                        dock_clicked || ctx.input(|i| i.viewport().close_requested())
+                    }
+                });
+            if closed {
+                dock.push(index);
+            }
+        }
+        self.state.panels.favorites.push_recent(&added_traces);
+
+        for index in dock.into_iter().rev() {
+            self.state.layout.attach_tile(index);
+        }
+    }
+
+    fn render_detached_view3d(&mut self, ctx: &egui::Context, frame: &eframe::Frame) {
+        let builder = egui::ViewportBuilder::default()
+            .with_title("TiPlot - 3D View")
+            .with_inner_size([900.0, 600.0]);
+
+        let closed = ctx.show_viewport_immediate(
+            egui::ViewportId::from_hash_of("detached_view3d"),
+            builder,
+            |ctx, class| {
+                let mut dock_clicked = false;
+                let mut contents = |ui: &mut egui::Ui| {
+                    ui.horizontal(|ui| {
+                        if ui
+                            .button(format!("{} Dock", icons::ARROW_SQUARE_IN))
+                            .on_hover_text("Move back into the main window")
+                            .clicked()
+                        {
+                            dock_clicked = true;
+                        }
+                        if ui
+                            .button(egui::RichText::new(icons::GEAR))
+                            .on_hover_text("Open Configuration")
+                            .clicked()
+                        {
+                            self.state.panels.view3d_panel.show_config_window =
+                                !self.state.panels.view3d_panel.show_config_window;
+                        }
+                    });
+                    ui.separator();
+                    let start = Instant::now();
+                    if let Some(time) = render_view3d_panel(
+                        ui,
+                        frame,
+                        &mut self.state.panels.view3d_panel,
+                        &self.state.data.data_store,
+                        self.state.timeline.current_time,
+                        &self.state.model_cache,
+                    ) {
+                        self.state.timeline.current_time = time;
+                    }
+                    self.state
+                        .ui
+                        .profiler
+                        .add_since(ProfileSection::Scene3D, start);
+                };
+
+                if class == egui::ViewportClass::Embedded {
+                    let mut open = true;
+                    egui::Window::new(tr("3D View"))
+                        .open(&mut open)
+                        .default_size([640.0, 480.0])
+                        .show(ctx, |ui| contents(ui));
+                    dock_clicked || !open
+                } else {
+                    egui::CentralPanel::default().show(ctx, |ui| contents(ui));
+                    dock_clicked || ctx.input(|i| i.viewport().close_requested())
+                }
+            },
+        );
+        if closed {
+            self.state.panels.view3d_detached = false;
+            self.state.panels.view3d_panel_collapsed = false;
+        }
     }
 
-    fn render_configuration_window(&mut self, ctx: &egui::Context) {
+    fn render_configuration_window(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
         render_config_window(
             ctx,
             &mut self.state.panels.view3d_panel,
             &self.state.data.data_store,
+            &self.state.settings.alias_profiles,
+        );
+        let stream_action = render_stream_panel(
+            ctx,
+            &mut self.state.panels.stream_panel,
+            &self.state.data.stream,
+            self.state.data.mirror.as_ref(),
+        );
+        match stream_action {
+            Some(StreamAction::StartMirror(addr)) => {
+                self.state.data.mirror = Some(MirrorPublisher::start(addr, ctx.clone()));
+            }
+            Some(StreamAction::StopMirror) => self.state.data.mirror = None,
+            Some(StreamAction::Follow(addr)) => start_follow(
+                addr,
+                self.state.data.tx.clone(),
+                self.state.data.ingest_filter.clone(),
+                self.state.data.stream.clone(),
+                ctx.clone(),
+            ),
+            None => {}
+        }
+        let synthetic_running = self
+            .state
+            .data
+            .synthetic
+            .as_ref()
+            .is_some_and(|generator| generator.is_running());
+        match render_synthetic_panel(
+            ctx,
+            &mut self.state.panels.synthetic_panel,
+            synthetic_running,
+        ) {
+            Some(SyntheticAction::Start(config)) => {
+                self.state.data.synthetic = Some(SyntheticGenerator::start(
+                    config,
+                    self.state.data.tx.clone(),
+                    ctx.clone(),
+                ));
+            }
+            Some(SyntheticAction::Stop) => self.state.data.synthetic = None,
+            None => {}
+        }
+        let has_data = !self.state.data.data_store.is_empty();
+        if let Some((path, options)) =
+            render_merge_panel(ctx, &mut self.state.panels.merge_panel, has_data)
+        {
+            self.merge_data(path, options, ctx);
+        }
+        render_metadata_window(
+            ctx,
+            &mut self.state.panels.metadata_panel,
+            &mut self.state.data.data_store,
+        );
+        render_log_info_window(
+            ctx,
+            &mut self.state.panels.log_info_panel,
+            &self.state.data.data_store.log_info,
+        );
+
+        let timeline = &mut self.state.timeline;
+        let mut host = ScriptHost {
+            data_store: &mut self.state.data.data_store,
+            annotations: &mut timeline.annotations,
+            current_time: &mut timeline.current_time,
+            is_playing: &mut timeline.is_playing,
+            playback_speed: &mut timeline.playback_speed,
+            min_time: &mut timeline.min_time,
+            max_time: &mut timeline.max_time,
+        };
+        render_script_panel(ctx, &mut self.state.panels.script_panel, &mut host);
+        render_health_panel(ctx, &mut self.state.panels.health_panel);
+        let loop_range = self.state.timeline.loop_region.range();
+        render_power_panel(
+            ctx,
+            &mut self.state.panels.power_panel,
+            &self.state.data.data_store,
+            loop_range.unwrap_or((self.state.timeline.min_time, self.state.timeline.max_time)),
+            loop_range.is_some(),
+        );
+        if let Some((session, offset)) = render_align_panel(
+            ctx,
+            &mut self.state.panels.align_panel,
+            &self.state.data.data_store,
+        ) {
+            self.state.data.data_store.shift_session(&session, offset);
+            tracing::info!("Shifted session '{}' by {:+.3} s", session, offset);
+            self.state
+                .ui
+                .notifications
+                .success(format!("Shifted session '{}' by {:+.3} s", session, offset));
+            self.update_time_bounds();
+            self.reupload_all_traces(frame);
+        }
+        if let Some(profiles) = render_alias_panel(
+            ctx,
+            &mut self.state.panels.alias_panel,
+            &self.state.data.data_store,
+        ) {
+            let mut settings = self.state.settings.clone();
+            settings.alias_profiles = profiles;
+            self.save_settings(settings);
+        }
+        render_trigger_panel(
+            ctx,
+            &mut self.state.panels.trigger_panel,
+            &self.state.data.data_store,
+            &mut self.state.timeline.frozen,
         );
     }
 }
 
 impl eframe::App for TiPlotApp {
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        tracing::info!("Shutting down");
+        self.state.data.stream.shutdown();
+        self.state.data.mirror = None;
+
+        let session = self.session_metadata();
+        let interval = self.state.settings.data.autosave_interval_secs;
+        let data = &mut self.state.data;
+        if let Err(e) = data.autosave.finish(&data.data_store, &session, interval) {
+            tracing::error!("Failed to save live data on exit: {}", e);
+        }
+
+        if let Some(render_state) = self.render_state.take() {
+            Self::release_gpu_resources(&render_state);
+        }
+    }
+
     fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
+        self.handle_close_request(ctx);
         self.state.ui.update_fps();
+        self.state.ui.profiler.begin_frame();
+
+        let start = Instant::now();
         self.process_data(ctx, frame);
+        self.poll_triggers();
+        self.autosave_if_due();
+        self.update_memory_budget(frame);
+        self.update_derived_traces(frame);
+        self.state
+            .ui
+            .profiler
+            .add_since(ProfileSection::DataProcessing, start);
         ctx.request_repaint();
 
         self.handle_keyboard_input(ctx);
-        self.state.timeline.update_playback(ctx);
+        self.state
+            .timeline
+            .update_playback(ctx, &self.state.data.data_store);
 
         self.handle_menu_actions(ctx, frame);
         self.render_top_menu_bar(ctx, frame);
         self.render_bottom_timeline_panel(ctx);
+        render_log_panel(ctx, &mut self.state.panels.log_panel);
         self.render_side_panels(ctx, frame);
-        self.render_central_panel(ctx);
-        self.render_configuration_window(ctx);
+        let plot_area = ctx.available_rect();
+        self.state.layout.y_sync = y_sync_ranges(
+            self.state.layout.panes(),
+            &self.state.data.data_store,
+            self.state.timeline.min_time,
+            self.state.timeline.max_time,
+        );
+        let action = self.render_central_panel(ctx, frame);
+        self.process_menu_action(action, ctx, frame);
+        self.render_command_palette(ctx, frame);
+        self.render_detached_windows(ctx, frame);
+        self.render_configuration_window(ctx, frame);
+        self.notify_connection_changes();
+        self.update_folder_watcher(ctx);
+        self.state.ui.notifications.show(ctx, plot_area);
+        self.update_profiler_memory(frame);
+        render_profiler_overlay(ctx, &mut self.state.ui.profiler, self.state.ui.current_fps);
+
+        if let Some(mirror) = &mut self.state.data.mirror {
+            let start_time = self.state.data.data_store.start_time as f64;
+            mirror.publish_cursor(start_time + self.state.timeline.current_time as f64);
+        }
+
+        if let Some(tile_id) = self.state.layout.clear_traces_request.take() {
+            self.process_menu_action(MenuAction::ClearTraces(tile_id), ctx, frame);
+        }
+        self.state
+            .layout
+            .settle_snapshot(&self.state.panels.view3d_panel.vehicles);
 
+        let hover_time = self.state.timeline.next_hover_time.take();
+        if hover_time != self.state.timeline.hover_time {
+            self.state.timeline.hover_time = hover_time;
+            if self.state.timeline.shared_crosshair {
+                ctx.request_repaint();
+            }
+        }
         self.state.layout.handle_split_request();
         self.state.layout.handle_reset_sizes_request();
+        self.state.layout.handle_arrange_grid_request();
+        self.state.ui.profiler.end_frame();
     }
 }
 
-fn get_default_layouts_dir() -> PathBuf {
-    if let Some(proj_dirs) = directories::ProjectDirs::from("io", "tilak", "TiPlot") {
-        proj_dirs.config_dir().join("layouts")
-    } else {
-        PathBuf::from("layouts")
-    }
+fn display_name(path: &Path) -> String {
+    path.file_name().map_or_else(
+        || path.display().to_string(),
+        |name| name.to_string_lossy().into_owned(),
+    )
 }
@@ -1,12 +1,24 @@
-use crate::core::DataStore;
-use crate::ui::layout::LayoutData;
+use crate::core::{AliasProfile, Annotation, DataStore, SharedIngestFilter};
+use crate::ui::layout::{workspace_tree_id, LayoutData};
+use crate::ui::notifications::Notifications;
+use crate::ui::palette::TracePalette;
 use crate::ui::panels::tabs::config::VehicleConfig;
 use crate::ui::panels::tabs::gltf_loader::ModelCache;
-use crate::ui::panels::{TopicPanelSelection, View3DPanel};
-use crate::ui::tiles::{InterpolationMode, PlotTile};
-use crossbeam_channel::Receiver;
+use crate::ui::panels::{
+    step_time, ActivityStrip, AliasPanel, AlignPanel, CommandPalette, FrameProfiler, HealthPanel,
+    JumpTarget, LogInfoPanel, LogPanel, LoopRegion, MergePanel, MetadataPanel, PlaybackMode,
+    PowerPanel, RecentFiles, ScriptPanel, StreamPanel, SyntheticPanel, TopicFavorites,
+    TopicPanelSelection, TriggerPanel, View3DPanel,
+};
+use crate::ui::settings::Settings;
+use crate::ui::sonify::Sonifier;
+use crate::ui::tiles::{
+    ImageFrameCache, InterpolationMode, MapTileCache, PlotTile, VideoFrameCache, YSyncRanges,
+};
+use crate::ui::time_format::TimeFormat;
+use crossbeam_channel::{Receiver, Sender};
 use egui_tiles::{LinearDir, TileId, Tiles, Tree};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 pub struct TimelineState {
     pub min_time: f32,
@@ -18,13 +30,32 @@ pub struct TimelineState {
     // Playback
     pub is_playing: bool,
     pub playback_speed: f32,
+    /// Play backwards, in every playback mode.
+    pub reverse: bool,
     pub last_update_time: Option<std::time::Instant>,
 
     // Timeline behavior
     pub lock_to_last: bool,
     pub lock_viewport: bool,
+    /// Set by triggers: the viewport and cursor stop following live data until resumed.
+    pub frozen: bool,
     pub always_show_playback_tooltip: bool,
+    pub shared_crosshair: bool,
+    /// Time under the mouse in a plot tile during the last frame.
+    pub hover_time: Option<f32>,
+    /// Collects `hover_time` while the current frame is drawn.
+    pub next_hover_time: Option<f32>,
     pub last_viewport_width: f32,
+
+    pub annotations: Vec<Annotation>,
+    pub loop_region: LoopRegion,
+    pub playback_mode: PlaybackMode,
+    /// Topic whose samples drive step playback.
+    pub clock_topic: Option<String>,
+    pub time_format: TimeFormat,
+    pub activity: ActivityStrip,
+    /// Trace played as a tone during playback.
+    pub sonifier: Sonifier,
 }
 
 impl TimelineState {
@@ -37,11 +68,23 @@ impl TimelineState {
             current_time: 0.0,
             is_playing: false,
             playback_speed: 10.0,
+            reverse: false,
             last_update_time: None,
             lock_to_last: true,
             lock_viewport: false,
+            frozen: false,
             always_show_playback_tooltip: false,
+            shared_crosshair: false,
+            hover_time: None,
+            next_hover_time: None,
             last_viewport_width: 10.0,
+            annotations: Vec::new(),
+            loop_region: LoopRegion::default(),
+            playback_mode: PlaybackMode::default(),
+            clock_topic: None,
+            time_format: TimeFormat::default(),
+            activity: ActivityStrip::default(),
+            sonifier: Sonifier::default(),
         }
     }
 
@@ -54,6 +97,9 @@ impl TimelineState {
         self.last_viewport_width = 10.0;
         self.is_playing = false;
         self.last_update_time = None;
+        self.annotations.clear();
+        self.loop_region.clear();
+        self.frozen = false;
     }
 
     pub fn update_bounds(&mut self, min: f32, max: f32) {
@@ -65,15 +111,37 @@ impl TimelineState {
         self.last_viewport_width = max;
     }
 
-    pub fn update_playback(&mut self, ctx: &egui::Context) {
+    pub fn update_playback(&mut self, ctx: &egui::Context, data_store: &DataStore) {
         if self.is_playing {
             let now = std::time::Instant::now();
             if let Some(last_time) = self.last_update_time {
                 let elapsed = now.duration_since(last_time).as_secs_f32();
-                let time_delta = elapsed * self.playback_speed;
-                self.current_time += time_delta;
-                if self.current_time > self.max_time {
-                    self.current_time = self.min_time;
+                let clock = self
+                    .clock_topic
+                    .as_ref()
+                    .and_then(|topic| data_store.get_column(topic, "timestamp"));
+                let direction = if self.reverse { -1.0 } else { 1.0 };
+                self.current_time = match (self.playback_mode, clock) {
+                    (PlaybackMode::RealTime, _) => self.current_time + elapsed * direction,
+                    (PlaybackMode::SampleStep, Some(times)) if self.reverse => {
+                        let previous = times.partition_point(|&t| t < self.current_time);
+                        previous
+                            .checked_sub(1)
+                            .map_or(f32::NEG_INFINITY, |i| times[i])
+                    }
+                    (PlaybackMode::SampleStep, Some(times)) => {
+                        let next = times.partition_point(|&t| t <= self.current_time);
+                        times.get(next).copied().unwrap_or(f32::INFINITY)
+                    }
+                    _ => self.current_time + elapsed * self.playback_speed * direction,
+                };
+                // Running off either end wraps around to the end playback starts from.
+                let (start, end) = self
+                    .loop_region
+                    .range()
+                    .unwrap_or((self.min_time, self.max_time));
+                if self.current_time > end || self.current_time < start {
+                    self.current_time = if self.reverse { end } else { start };
                 }
             }
             self.last_update_time = Some(now);
@@ -81,6 +149,26 @@ impl TimelineState {
         } else {
             self.last_update_time = None;
         }
+        self.sonifier
+            .update(data_store, self.current_time, self.is_playing);
+    }
+
+    /// Moves to the nearest `target` time after (or before) `current_time`.
+    pub fn jump(&mut self, target: JumpTarget, forward: bool, data_store: &DataStore) {
+        let times = match (target, &self.clock_topic) {
+            (JumpTarget::Sample, Some(topic)) => data_store
+                .get_column(topic, "timestamp")
+                .cloned()
+                .unwrap_or_default(),
+            (JumpTarget::Event, Some(topic)) => data_store.change_times(topic),
+            (JumpTarget::Annotation, _) => self.annotations.iter().map(|a| a.time).collect(),
+            (_, None) => return,
+        };
+
+        if let Some(t) = step_time(&times, self.current_time, forward) {
+            self.current_time = t.clamp(self.global_min, self.global_max);
+            self.is_playing = false;
+        }
     }
 }
 
@@ -93,8 +181,24 @@ impl Default for TimelineState {
 pub struct PanelState {
     pub topic_panel_collapsed: bool,
     pub view3d_panel_collapsed: bool,
+    pub view3d_detached: bool,
     pub topic_selection: TopicPanelSelection,
+    pub favorites: TopicFavorites,
+    pub recent_files: RecentFiles,
     pub view3d_panel: View3DPanel,
+    pub stream_panel: StreamPanel,
+    pub log_panel: LogPanel,
+    pub metadata_panel: MetadataPanel,
+    pub log_info_panel: LogInfoPanel,
+    pub script_panel: ScriptPanel,
+    pub trigger_panel: TriggerPanel,
+    pub health_panel: HealthPanel,
+    pub power_panel: PowerPanel,
+    pub align_panel: AlignPanel,
+    pub alias_panel: AliasPanel,
+    pub synthetic_panel: SyntheticPanel,
+    pub merge_panel: MergePanel,
+    pub command_palette: CommandPalette,
 }
 
 impl PanelState {
@@ -102,8 +206,24 @@ impl PanelState {
         Self {
             topic_panel_collapsed: false,
             view3d_panel_collapsed: true,
+            view3d_detached: false,
             topic_selection: TopicPanelSelection::default(),
+            favorites: TopicFavorites::load(),
+            recent_files: RecentFiles::load(),
             view3d_panel: View3DPanel::new(),
+            stream_panel: StreamPanel::new(),
+            log_panel: LogPanel::new(),
+            metadata_panel: MetadataPanel::default(),
+            log_info_panel: LogInfoPanel::default(),
+            script_panel: ScriptPanel::new(),
+            trigger_panel: TriggerPanel::new(),
+            health_panel: HealthPanel::new(),
+            power_panel: PowerPanel::new(),
+            align_panel: AlignPanel::new(),
+            alias_panel: AliasPanel::new(),
+            synthetic_panel: SyntheticPanel::default(),
+            merge_panel: MergePanel::default(),
+            command_palette: CommandPalette::default(),
         }
     }
 }
@@ -116,20 +236,57 @@ impl Default for PanelState {
 
 pub struct DataState {
     pub data_store: DataStore,
+    pub tx: Sender<crate::acquisition::DataMessage>,
     pub rx: Receiver<crate::acquisition::DataMessage>,
     pub receiving_data: bool,
     pub last_data_time: Option<std::time::Instant>,
     pub data_file_path: Option<PathBuf>,
+    /// Bytes read and total bytes of the file currently being loaded.
+    pub load_progress: Option<(u64, u64)>,
+    /// Shared with the TCP receiver so filtered topics are dropped before decoding.
+    pub ingest_filter: SharedIngestFilter,
+    pub stream: crate::acquisition::StreamControl,
+    /// Republishes ingested data and the cursor to following instances while set.
+    pub mirror: Option<crate::acquisition::MirrorPublisher>,
+    /// Generator of synthetic test data while it runs.
+    pub synthetic: Option<crate::acquisition::SyntheticGenerator>,
+    /// Watches the folder set in the settings for new logs.
+    pub folder_watcher: Option<crate::acquisition::FolderWatcher>,
+    /// Newest log from the watched folder waiting to be opened in kiosk mode.
+    pub kiosk_log: Option<PathBuf>,
+    pub autosave: crate::core::Autosave,
+    /// Stream and mirror status seen last frame, to notify when a connection drops.
+    pub last_stream_status: crate::acquisition::StreamStatus,
+    pub last_mirror_status: Option<crate::acquisition::MirrorStatus>,
+    /// Warnings logged before the current file load started.
+    pub load_problem_baseline: usize,
 }
 
 impl DataState {
-    pub fn new(rx: Receiver<crate::acquisition::DataMessage>) -> Self {
+    pub fn new(
+        tx: Sender<crate::acquisition::DataMessage>,
+        rx: Receiver<crate::acquisition::DataMessage>,
+        ingest_filter: SharedIngestFilter,
+        stream: crate::acquisition::StreamControl,
+    ) -> Self {
         Self {
             data_store: DataStore::new(),
+            tx,
             rx,
             receiving_data: false,
             last_data_time: None,
             data_file_path: None,
+            load_progress: None,
+            ingest_filter,
+            stream,
+            mirror: None,
+            synthetic: None,
+            folder_watcher: None,
+            kiosk_log: None,
+            autosave: crate::core::Autosave::new(),
+            last_stream_status: crate::acquisition::StreamStatus::Stopped,
+            last_mirror_status: None,
+            load_problem_baseline: 0,
         }
     }
 
@@ -138,48 +295,160 @@ impl DataState {
         self.data_file_path = None;
         self.receiving_data = false;
         self.last_data_time = None;
+        self.autosave.discard();
     }
 }
 
+/// A plot tile moved out of the tile tree into a native window of its own.
+pub struct DetachedTile {
+    pub viewport_id: egui::ViewportId,
+    /// Stands in for the tile's id in the tree, from a range the tree never hands out.
+    pub tile_id: TileId,
+    pub tile: PlotTile,
+}
+
+/// A named tab of the tile area with its own tile tree.
+pub struct Workspace {
+    pub name: String,
+    /// Only meaningful while the workspace is inactive, the active tree lives in
+    /// `LayoutState::tree`.
+    pub tree: Tree<PlotTile>,
+}
+
 pub struct LayoutState {
+    /// Tile tree of the active workspace.
     pub tree: Tree<PlotTile>,
+    pub workspaces: Vec<Workspace>,
+    pub active_workspace: usize,
+    next_workspace: u64,
+    /// Workspace whose tab is being renamed, with the edited name.
+    pub renaming_workspace: Option<(usize, String)>,
+    pub detached: Vec<DetachedTile>,
+    next_detached: u64,
+    pub detach_request: Option<TileId>,
     pub dragged_item: Option<(String, String)>,
     pub split_request: Option<(TileId, LinearDir)>,
+    pub clear_traces_request: Option<TileId>,
     pub reset_sizes_request: bool,
+    pub arrange_grid_request: bool,
     pub global_interpolation_mode: InterpolationMode,
+    /// Plot tile last clicked, used as the target when adding traces from the topic panel.
+    pub focused_tile: Option<TileId>,
+    pub map_tiles: MapTileCache,
+    pub video_frames: VideoFrameCache,
+    pub image_frames: ImageFrameCache,
+    pub trace_palette: TracePalette,
+    /// Value ranges of the Y-sync groups for the current frame.
+    pub y_sync: YSyncRanges,
+    /// Serialized layout as last saved or loaded.
+    saved_snapshot: Option<String>,
 }
 
 impl LayoutState {
     pub fn new() -> Self {
         let mut tiles = Tiles::default();
         let root = tiles.insert_pane(PlotTile::new());
-        let tree = Tree::new("main_tree", root, tiles);
+        let tree = Tree::new(workspace_tree_id(0), root, tiles);
 
         Self {
             tree,
+            workspaces: vec![Workspace {
+                name: "Main".to_string(),
+                tree: Tree::empty("inactive_workspace"),
+            }],
+            active_workspace: 0,
+            next_workspace: 1,
+            renaming_workspace: None,
+            detached: Vec::new(),
+            next_detached: 0,
+            detach_request: None,
             dragged_item: None,
             split_request: None,
+            clear_traces_request: None,
             reset_sizes_request: false,
+            arrange_grid_request: false,
             global_interpolation_mode: InterpolationMode::default(),
+            focused_tile: None,
+            map_tiles: MapTileCache::new(),
+            video_frames: VideoFrameCache::new(),
+            image_frames: ImageFrameCache::new(),
+            trace_palette: TracePalette::default(),
+            y_sync: YSyncRanges::new(),
+            saved_snapshot: None,
+        }
+    }
+
+    /// The focused plot tile if it still exists, otherwise the first visible one.
+    pub fn target_tile(&self) -> Option<TileId> {
+        let is_pane =
+            |id: &TileId| matches!(self.tree.tiles.get(*id), Some(egui_tiles::Tile::Pane(_)));
+
+        self.focused_tile
+            .filter(is_pane)
+            .or_else(|| self.tree.active_tiles().into_iter().find(is_pane))
+    }
+
+    /// Adds traces to the target tile, or to a new tile split off from it. Returns the
+    /// pairs that were not already plotted there.
+    pub fn add_traces(
+        &mut self,
+        items: &[(String, String)],
+        new_tile: bool,
+    ) -> Vec<(String, String)> {
+        let target = match (self.target_tile(), new_tile) {
+            (Some(tile_id), false) => tile_id,
+            (Some(tile_id), true) => self.split_tile(tile_id, LinearDir::Vertical),
+            (None, _) => {
+                let mut tile = PlotTile::new();
+                tile.interpolation_mode = self.global_interpolation_mode;
+                let tile_id = self.tree.tiles.insert_pane(tile);
+                self.tree.root = Some(tile_id);
+                tile_id
+            }
+        };
+        self.focused_tile = Some(target);
+
+        let Some(egui_tiles::Tile::Pane(tile)) = self.tree.tiles.get_mut(target) else {
+            return Vec::new();
+        };
+
+        let mut added = Vec::new();
+        for (topic, col) in items {
+            if tile
+                .traces
+                .iter()
+                .any(|t| t.topic == *topic && t.col == *col)
+            {
+                continue;
+            }
+            let color = self.trace_palette.next_color(&tile.trace_colors());
+            tile.add_trace(topic.clone(), col.clone(), color);
+            added.push((topic.clone(), col.clone()));
         }
+        added
     }
 
     pub fn save_layout(
-        &self,
+        &mut self,
         name: String,
-        layouts_dir: &PathBuf,
+        layouts_dir: &Path,
         vehicles: &[VehicleConfig],
     ) -> Result<(), String> {
-        let layout = LayoutData::from_tree(name, &self.tree, vehicles);
+        // Layouts only describe the tree, so detached tiles are docked first.
+        while !self.detached.is_empty() {
+            self.attach_tile(self.detached.len() - 1);
+        }
+        let layout = self.layout_data(name, vehicles);
 
         match layout.save_to_file(layouts_dir) {
             Ok(_) => {
-                println!("✓ Layout '{}' saved successfully", layout.name);
+                tracing::info!("Layout '{}' saved successfully", layout.name);
+                self.mark_saved();
                 Ok(())
             }
             Err(e) => {
                 let msg = format!("Failed to save: {}", e);
-                eprintln!("✗ {}", msg);
+                tracing::error!("{}", msg);
                 Err(msg)
             }
         }
@@ -191,136 +460,432 @@ impl LayoutState {
         vehicles: &mut Vec<VehicleConfig>,
     ) -> Result<(), String> {
         match LayoutData::load_from_file(&path) {
-            Ok(layout) => match layout.to_tree() {
-                Ok(tree) => {
-                    self.tree = tree;
+            Ok(layout) => match layout.to_trees() {
+                Ok(trees) => {
+                    self.workspaces = trees
+                        .into_iter()
+                        .map(|(name, tree)| Workspace { name, tree })
+                        .collect();
+                    self.active_workspace = layout.active_workspace.min(self.workspaces.len() - 1);
+                    self.tree = std::mem::replace(
+                        &mut self.workspaces[self.active_workspace].tree,
+                        Tree::empty("inactive_workspace"),
+                    );
+                    self.next_workspace = self.workspaces.len() as u64;
+                    self.renaming_workspace = None;
+                    self.focused_tile = None;
+                    self.detached.clear();
                     *vehicles = layout.vehicles;
-                    println!("✓ Layout '{}' loaded successfully", layout.name);
+                    self.mark_saved();
+                    tracing::info!("Layout '{}' loaded successfully", layout.name);
                     Ok(())
                 }
                 Err(e) => {
                     let msg = format!("Failed to reconstruct tree: {}", e);
-                    eprintln!("✗ {}", msg);
+                    tracing::error!("{}", msg);
                     Err(msg)
                 }
             },
             Err(e) => {
                 let msg = format!("Failed to load layout: {}", e);
-                eprintln!("✗ {}", msg);
+                tracing::error!("{}", msg);
                 Err(msg)
             }
         }
     }
 
+    /// Points traces at the columns of the loaded autopilot, see
+    /// [`PlotTile::translate_aliases`].
+    pub fn translate_aliases(
+        &mut self,
+        profiles: &[AliasProfile],
+        data_store: &DataStore,
+    ) -> usize {
+        self.panes_mut()
+            .map(|tile| tile.translate_aliases(profiles, data_store))
+            .sum()
+    }
+
+    /// Reassigns every trace a color from the current palette, in trace order.
+    pub fn recolor_traces(&mut self) {
+        let palette = self.trace_palette;
+        for plot_tile in self.panes_mut() {
+            let mut used = Vec::new();
+            for trace in &mut plot_tile.traces {
+                trace.color = palette.next_color(&used);
+                used.push(trace.color);
+            }
+        }
+    }
+
     pub fn clear_all_traces(&mut self) {
-        fn clear_tiles_recursive(tiles: &mut Tiles<PlotTile>, tile_id: TileId) {
-            if let Some(tile) = tiles.get_mut(tile_id) {
-                match tile {
-                    egui_tiles::Tile::Pane(plot_tile) => {
-                        plot_tile.traces.clear();
-                        plot_tile.cached_tooltip_values.clear();
-                        plot_tile.cached_tooltip_time = f32::NEG_INFINITY;
-                    }
-                    egui_tiles::Tile::Container(container) => {
-                        let children = match container {
-                            egui_tiles::Container::Linear(linear) => linear.children.clone(),
-                            egui_tiles::Container::Tabs(tabs) => tabs.children.clone(),
-                            egui_tiles::Container::Grid(grid) => grid.children().copied().collect(),
-                        };
-                        for child_id in children {
-                            clear_tiles_recursive(tiles, child_id);
-                        }
-                    }
-                }
+        for plot_tile in self.panes_mut() {
+            plot_tile.clear_traces();
+        }
+    }
+
+    /// Plot tiles of all workspaces and of detached windows.
+    pub fn panes(&self) -> impl Iterator<Item = &PlotTile> {
+        let active = self.active_workspace;
+        let inactive = self
+            .workspaces
+            .iter()
+            .enumerate()
+            .filter(move |(index, _)| *index != active)
+            .map(|(_, workspace)| &workspace.tree);
+        let docked = std::iter::once(&self.tree)
+            .chain(inactive)
+            .flat_map(|tree| tree.tiles.tiles())
+            .filter_map(|tile| match tile {
+                egui_tiles::Tile::Pane(plot_tile) => Some(plot_tile),
+                egui_tiles::Tile::Container(_) => None,
+            });
+        docked.chain(self.detached.iter().map(|d| &d.tile))
+    }
+
+    pub fn panes_mut(&mut self) -> impl Iterator<Item = &mut PlotTile> {
+        let active = self.active_workspace;
+        let inactive = self
+            .workspaces
+            .iter_mut()
+            .enumerate()
+            .filter(move |(index, _)| *index != active)
+            .map(|(_, workspace)| &mut workspace.tree);
+        let docked = std::iter::once(&mut self.tree)
+            .chain(inactive)
+            .flat_map(|tree| tree.tiles.tiles_mut())
+            .filter_map(|tile| match tile {
+                egui_tiles::Tile::Pane(plot_tile) => Some(plot_tile),
+                egui_tiles::Tile::Container(_) => None,
+            });
+        docked.chain(self.detached.iter_mut().map(|d| &mut d.tile))
+    }
+
+    pub fn switch_workspace(&mut self, index: usize) {
+        if index == self.active_workspace || index >= self.workspaces.len() {
+            return;
+        }
+        std::mem::swap(
+            &mut self.tree,
+            &mut self.workspaces[self.active_workspace].tree,
+        );
+        std::mem::swap(&mut self.tree, &mut self.workspaces[index].tree);
+        self.active_workspace = index;
+        self.focused_tile = None;
+    }
+
+    /// Appends a workspace with a single empty plot and switches to it.
+    pub fn add_workspace(&mut self) {
+        let mut number = self.workspaces.len() + 1;
+        let name = loop {
+            let name = format!("Workspace {}", number);
+            if self.workspaces.iter().all(|w| w.name != name) {
+                break name;
             }
+            number += 1;
+        };
+
+        self.push_workspace(name, vec![PlotTile::new()]);
+    }
+
+    /// Appends a workspace with the given plots stacked vertically and switches to it.
+    pub fn push_workspace(&mut self, name: String, plots: Vec<PlotTile>) {
+        let mut tiles = Tiles::default();
+        let panes: Vec<TileId> = plots
+            .into_iter()
+            .map(|mut tile| {
+                tile.interpolation_mode = self.global_interpolation_mode;
+                tiles.insert_pane(tile)
+            })
+            .collect();
+        let root = match panes[..] {
+            [single] => single,
+            _ => tiles.insert_vertical_tile(panes),
+        };
+        let tree = Tree::new(workspace_tree_id(self.next_workspace), root, tiles);
+        self.next_workspace += 1;
+
+        self.workspaces.push(Workspace { name, tree });
+        self.switch_workspace(self.workspaces.len() - 1);
+    }
+
+    /// Removes a workspace, keeping at least one.
+    pub fn remove_workspace(&mut self, index: usize) {
+        if self.workspaces.len() <= 1 || index >= self.workspaces.len() {
+            return;
+        }
+        if index == self.active_workspace {
+            self.switch_workspace(if index == 0 { 1 } else { index - 1 });
         }
+        self.workspaces.remove(index);
+        if self.active_workspace > index {
+            self.active_workspace -= 1;
+        }
+        self.renaming_workspace = None;
+    }
 
-        if let Some(root_id) = self.tree.root {
-            clear_tiles_recursive(&mut self.tree.tiles, root_id);
+    pub fn rename_workspace(&mut self, index: usize, name: &str) {
+        let name = name.trim();
+        if let Some(workspace) = self.workspaces.get_mut(index) {
+            if !name.is_empty() {
+                workspace.name = name.to_string();
+            }
+        }
+    }
+
+    fn pane_mut(&mut self, tile_id: TileId) -> Option<&mut PlotTile> {
+        if let Some(detached) = self.detached.iter_mut().find(|d| d.tile_id == tile_id) {
+            return Some(&mut detached.tile);
+        }
+        match self.tree.tiles.get_mut(tile_id) {
+            Some(egui_tiles::Tile::Pane(plot_tile)) => Some(plot_tile),
+            _ => None,
         }
     }
 
+    pub fn clear_traces(&mut self, tile_id: TileId) {
+        if let Some(plot_tile) = self.pane_mut(tile_id) {
+            plot_tile.clear_traces();
+        }
+    }
+
+    pub fn has_traces(&self, tile_id: TileId) -> bool {
+        let plot_tile = match self.tree.tiles.get(tile_id) {
+            Some(egui_tiles::Tile::Pane(plot_tile)) => Some(plot_tile),
+            _ => self
+                .detached
+                .iter()
+                .find(|d| d.tile_id == tile_id)
+                .map(|d| &d.tile),
+        };
+        plot_tile.is_some_and(|plot_tile| !plot_tile.traces.is_empty())
+    }
+
+    /// Moves a pane out of the tree into its own window. The tree keeps at least one
+    /// empty plot so the main window never goes blank.
+    pub fn detach_tile(&mut self, tile_id: TileId) {
+        if !matches!(
+            self.tree.tiles.get(tile_id),
+            Some(egui_tiles::Tile::Pane(_))
+        ) {
+            return;
+        }
+        let was_root = self.tree.root == Some(tile_id);
+        let Some(egui_tiles::Tile::Pane(tile)) = self.tree.remove_recursively(tile_id).pop() else {
+            return;
+        };
+        if was_root {
+            let mut empty = PlotTile::new();
+            empty.interpolation_mode = self.global_interpolation_mode;
+            self.tree.root = Some(self.tree.tiles.insert_pane(empty));
+        }
+        if self.focused_tile == Some(tile_id) {
+            self.focused_tile = None;
+        }
+
+        self.next_detached += 1;
+        self.detached.push(DetachedTile {
+            viewport_id: egui::ViewportId::from_hash_of(("detached_tile", self.next_detached)),
+            tile_id: TileId::from_u64(u64::MAX - self.next_detached),
+            tile,
+        });
+    }
+
+    /// Puts a detached tile back into the tree, next to the target tile.
+    pub fn attach_tile(&mut self, index: usize) {
+        let detached = self.detached.remove(index);
+        let tile_id = match self.target_tile() {
+            Some(target) => self.split_tile(target, LinearDir::Horizontal),
+            None => {
+                let tile_id = self.tree.tiles.insert_pane(PlotTile::new());
+                self.tree.root = Some(tile_id);
+                tile_id
+            }
+        };
+        self.tree
+            .tiles
+            .insert(tile_id, egui_tiles::Tile::Pane(detached.tile));
+        self.focused_tile = Some(tile_id);
+    }
+
+    fn layout_data(&self, name: String, vehicles: &[VehicleConfig]) -> LayoutData {
+        let workspaces = self
+            .workspaces
+            .iter()
+            .enumerate()
+            .map(|(index, workspace)| {
+                let tree = if index == self.active_workspace {
+                    &self.tree
+                } else {
+                    &workspace.tree
+                };
+                (workspace.name.as_str(), tree)
+            });
+        LayoutData::from_workspaces(name, workspaces, self.active_workspace, vehicles)
+    }
+
+    fn snapshot(&self, vehicles: &[VehicleConfig]) -> String {
+        let mut layout = self.layout_data(String::new(), vehicles);
+        // Switching tabs alone is not worth a save prompt.
+        layout.active_workspace = 0;
+        serde_json::to_string(&layout).unwrap_or_default()
+    }
+
+    /// Treats the current layout as saved. The snapshot is taken by `settle_snapshot`
+    /// once the tree has been shown, as egui_tiles simplifies freshly built trees.
+    pub fn mark_saved(&mut self) {
+        self.saved_snapshot = None;
+    }
+
+    pub fn settle_snapshot(&mut self, vehicles: &[VehicleConfig]) {
+        if self.saved_snapshot.is_none() {
+            self.saved_snapshot = Some(self.snapshot(vehicles));
+        }
+    }
+
+    pub fn has_unsaved_changes(&self, vehicles: &[VehicleConfig]) -> bool {
+        self.saved_snapshot
+            .as_ref()
+            .is_some_and(|saved| *saved != self.snapshot(vehicles))
+    }
+
     pub fn handle_split_request(&mut self) {
         if let Some((tile_id, direction)) = self.split_request.take() {
-            let mut new_tile = PlotTile::new();
-            new_tile.interpolation_mode = self.global_interpolation_mode;
-            let new_tile_id = self.tree.tiles.insert_pane(new_tile);
-            let parent_id = self.tree.tiles.parent_of(tile_id);
-
-            if let Some(parent_id) = parent_id {
-                let action = if let Some(egui_tiles::Tile::Container(parent_container)) =
-                    self.tree.tiles.get(parent_id)
-                {
-                    match parent_container {
-                        egui_tiles::Container::Linear(linear) => {
-                            if linear.dir == direction {
-                                linear
-                                    .children
-                                    .iter()
-                                    .position(|&id| id == tile_id)
-                                    .map(|pos| (false, pos))
-                            } else {
-                                linear
-                                    .children
-                                    .iter()
-                                    .position(|&id| id == tile_id)
-                                    .map(|pos| (true, pos))
-                            }
+            self.split_tile(tile_id, direction);
+        }
+    }
+
+    /// Inserts an empty plot tile next to `tile_id` and returns its id.
+    pub fn split_tile(&mut self, tile_id: TileId, direction: LinearDir) -> TileId {
+        let mut new_tile = PlotTile::new();
+        new_tile.interpolation_mode = self.global_interpolation_mode;
+        let new_tile_id = self.tree.tiles.insert_pane(new_tile);
+        let parent_id = self.tree.tiles.parent_of(tile_id);
+
+        if let Some(parent_id) = parent_id {
+            let action = if let Some(egui_tiles::Tile::Container(parent_container)) =
+                self.tree.tiles.get(parent_id)
+            {
+                match parent_container {
+                    egui_tiles::Container::Linear(linear) => {
+                        if linear.dir == direction {
+                            linear
+                                .children
+                                .iter()
+                                .position(|&id| id == tile_id)
+                                .map(|pos| (false, pos))
+                        } else {
+                            linear
+                                .children
+                                .iter()
+                                .position(|&id| id == tile_id)
+                                .map(|pos| (true, pos))
                         }
-                        egui_tiles::Container::Tabs(tabs) => tabs
-                            .children
-                            .iter()
-                            .position(|&id| id == tile_id)
-                            .map(|pos| (true, pos)),
-                        egui_tiles::Container::Grid(_) => Some((true, 0)),
                     }
-                } else {
-                    None
-                };
+                    egui_tiles::Container::Tabs(tabs) => tabs
+                        .children
+                        .iter()
+                        .position(|&id| id == tile_id)
+                        .map(|pos| (true, pos)),
+                    egui_tiles::Container::Grid(_) => Some((true, 0)),
+                }
+            } else {
+                None
+            };
+
+            if let Some((needs_new_container, pos)) = action {
+                if needs_new_container {
+                    let new_container = egui_tiles::Container::Linear(egui_tiles::Linear {
+                        children: vec![tile_id, new_tile_id],
+                        dir: direction,
+                        ..Default::default()
+                    });
+                    let container_id = self.tree.tiles.insert_container(new_container);
 
-                if let Some((needs_new_container, pos)) = action {
-                    if needs_new_container {
-                        let new_container = egui_tiles::Container::Linear(egui_tiles::Linear {
-                            children: vec![tile_id, new_tile_id],
-                            dir: direction,
-                            ..Default::default()
-                        });
-                        let container_id = self.tree.tiles.insert_container(new_container);
-
-                        if let Some(egui_tiles::Tile::Container(parent_container)) =
-                            self.tree.tiles.get_mut(parent_id)
-                        {
-                            match parent_container {
-                                egui_tiles::Container::Linear(linear) => {
-                                    linear.children[pos] = container_id;
-                                }
-                                egui_tiles::Container::Tabs(tabs) => {
-                                    tabs.children[pos] = container_id;
-                                }
-                                egui_tiles::Container::Grid(_) => {}
+                    if let Some(egui_tiles::Tile::Container(parent_container)) =
+                        self.tree.tiles.get_mut(parent_id)
+                    {
+                        match parent_container {
+                            egui_tiles::Container::Linear(linear) => {
+                                linear.children[pos] = container_id;
                             }
-                        }
-                    } else {
-                        if let Some(egui_tiles::Tile::Container(egui_tiles::Container::Linear(
-                            linear,
-                        ))) = self.tree.tiles.get_mut(parent_id)
-                        {
-                            linear.children.insert(pos + 1, new_tile_id);
+                            egui_tiles::Container::Tabs(tabs) => {
+                                tabs.children[pos] = container_id;
+                            }
+                            egui_tiles::Container::Grid(_) => {}
                         }
                     }
+                } else {
+                    if let Some(egui_tiles::Tile::Container(egui_tiles::Container::Linear(
+                        linear,
+                    ))) = self.tree.tiles.get_mut(parent_id)
+                    {
+                        linear.children.insert(pos + 1, new_tile_id);
+                    }
                 }
-            } else {
-                let new_container = egui_tiles::Container::Linear(egui_tiles::Linear {
-                    children: vec![tile_id, new_tile_id],
-                    dir: direction,
-                    ..Default::default()
-                });
-                let container_id = self.tree.tiles.insert_container(new_container);
-                self.tree.root = Some(container_id);
             }
+        } else {
+            let new_container = egui_tiles::Container::Linear(egui_tiles::Linear {
+                children: vec![tile_id, new_tile_id],
+                dir: direction,
+                ..Default::default()
+            });
+            let container_id = self.tree.tiles.insert_container(new_container);
+            self.tree.root = Some(container_id);
+        }
+
+        new_tile_id
+    }
+
+    pub fn handle_arrange_grid_request(&mut self) {
+        if std::mem::take(&mut self.arrange_grid_request) {
+            self.arrange_as_grid();
         }
     }
 
+    /// Replaces all containers of the tree with a single grid of its panes, in reading
+    /// order, using the squarest N×M arrangement.
+    pub fn arrange_as_grid(&mut self) {
+        fn visit(
+            tiles: &Tiles<PlotTile>,
+            id: TileId,
+            panes: &mut Vec<TileId>,
+            containers: &mut Vec<TileId>,
+        ) {
+            match tiles.get(id) {
+                Some(egui_tiles::Tile::Pane(_)) => panes.push(id),
+                Some(egui_tiles::Tile::Container(container)) => {
+                    containers.push(id);
+                    for &child in container.children() {
+                        visit(tiles, child, panes, containers);
+                    }
+                }
+                None => {}
+            }
+        }
+
+        let Some(root) = self.tree.root else {
+            return;
+        };
+        let mut panes = Vec::new();
+        let mut containers = Vec::new();
+        visit(&self.tree.tiles, root, &mut panes, &mut containers);
+        if panes.len() < 2 {
+            return;
+        }
+
+        for id in containers {
+            self.tree.tiles.remove(id);
+        }
+        let columns = (panes.len() as f32).sqrt().ceil() as usize;
+        let rows = panes.len().div_ceil(columns);
+        let mut grid = egui_tiles::Grid::new(panes);
+        grid.layout = egui_tiles::GridLayout::Columns(columns);
+        grid.col_shares = vec![1.0; columns];
+        grid.row_shares = vec![1.0; rows];
+        self.tree.root = Some(self.tree.tiles.insert_container(grid));
+    }
+
     pub fn handle_reset_sizes_request(&mut self) {
         if !self.reset_sizes_request {
             return;
@@ -355,7 +920,7 @@ impl LayoutState {
                             if num_children > 0 {
                                 let cols = (num_children as f32).sqrt().ceil() as usize;
                                 grid.col_shares = vec![1.0; cols];
-                                grid.row_shares = vec![1.0; (num_children + cols - 1) / cols];
+                                grid.row_shares = vec![1.0; num_children.div_ceil(cols)];
                             }
                         }
                     }
@@ -384,6 +949,10 @@ pub struct UIState {
     pub layouts_dir: PathBuf,
     pub frame_times: std::collections::VecDeque<std::time::Instant>,
     pub current_fps: f32,
+    pub profiler: FrameProfiler,
+    pub notifications: Notifications,
+    /// Set once closing the window no longer needs confirmation.
+    pub exit_confirmed: bool,
 }
 
 impl UIState {
@@ -393,6 +962,9 @@ impl UIState {
             layouts_dir,
             frame_times: std::collections::VecDeque::with_capacity(60),
             current_fps: 0.0,
+            profiler: FrameProfiler::new(),
+            notifications: Notifications::new(),
+            exit_confirmed: false,
         }
     }
 
@@ -420,22 +992,44 @@ pub struct AppState {
     pub layout: LayoutState,
     pub ui: UIState,
     pub model_cache: ModelCache,
+    pub settings: Settings,
 }
 
 impl AppState {
     pub fn new(
+        tx: Sender<crate::acquisition::DataMessage>,
         rx: Receiver<crate::acquisition::DataMessage>,
-        layouts_dir: PathBuf,
+        ingest_filter: SharedIngestFilter,
+        stream: crate::acquisition::StreamControl,
+        settings: Settings,
         model_cache: ModelCache,
     ) -> Self {
-        Self {
+        let mut state = Self {
             timeline: TimelineState::new(),
             panels: PanelState::new(),
-            data: DataState::new(rx),
+            data: DataState::new(tx, rx, ingest_filter, stream),
             layout: LayoutState::new(),
-            ui: UIState::new(layouts_dir),
+            ui: UIState::new(settings.layouts_dir()),
             model_cache,
-        }
+            settings: Settings::default(),
+        };
+        state.apply_settings(settings);
+        state
+    }
+
+    /// Makes `settings` current, updating the options they cover. The listen address
+    /// only takes effect on the next start.
+    pub fn apply_settings(&mut self, settings: Settings) {
+        self.layout.trace_palette = settings.trace_palette;
+        self.timeline.playback_speed = settings.playback_speed;
+        self.timeline.playback_mode = settings.playback_mode;
+        self.timeline.lock_to_last = settings.lock_to_last;
+        self.timeline.time_format = settings.time_format;
+        self.timeline.always_show_playback_tooltip = settings.always_show_playback_tooltip;
+        self.timeline.shared_crosshair = settings.shared_crosshair;
+        self.ui.layouts_dir = settings.layouts_dir();
+        crate::ui::i18n::set_language(settings.language);
+        self.settings = settings;
     }
 
     pub fn clear_all(&mut self) {
@@ -0,0 +1,76 @@
+use eframe::egui::Color32;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Colormap {
+    Viridis,
+    Plasma,
+    Turbo,
+    Coolwarm,
+    Grayscale,
+}
+
+impl Colormap {
+    pub const ALL: [Colormap; 5] = [
+        Colormap::Viridis,
+        Colormap::Plasma,
+        Colormap::Turbo,
+        Colormap::Coolwarm,
+        Colormap::Grayscale,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Colormap::Viridis => "Viridis",
+            Colormap::Plasma => "Plasma",
+            Colormap::Turbo => "Turbo",
+            Colormap::Coolwarm => "Cool-Warm",
+            Colormap::Grayscale => "Grayscale",
+        }
+    }
+
+    fn stops(&self) -> &'static [[u8; 3]] {
+        match self {
+            Colormap::Viridis => &[
+                [68, 1, 84],
+                [59, 82, 139],
+                [33, 145, 140],
+                [94, 201, 98],
+                [253, 231, 37],
+            ],
+            Colormap::Plasma => &[
+                [13, 8, 135],
+                [126, 3, 168],
+                [204, 71, 120],
+                [248, 149, 64],
+                [240, 249, 33],
+            ],
+            Colormap::Turbo => &[
+                [48, 18, 59],
+                [70, 134, 251],
+                [27, 229, 181],
+                [164, 252, 60],
+                [251, 185, 56],
+                [122, 4, 3],
+            ],
+            Colormap::Coolwarm => &[[59, 76, 192], [221, 221, 221], [180, 4, 38]],
+            Colormap::Grayscale => &[[30, 30, 30], [240, 240, 240]],
+        }
+    }
+
+    /// Color at `t` in `0..=1`, values outside are clamped.
+    pub fn sample(&self, t: f32) -> Color32 {
+        let stops = self.stops();
+        let t = if t.is_finite() {
+            t.clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let scaled = t * (stops.len() - 1) as f32;
+        let idx = (scaled as usize).min(stops.len() - 2);
+        let f = scaled - idx as f32;
+        let (a, b) = (stops[idx], stops[idx + 1]);
+        let lerp = |i: usize| (a[i] as f32 + (b[i] as f32 - a[i] as f32) * f).round() as u8;
+        Color32::from_rgb(lerp(0), lerp(1), lerp(2))
+    }
+}
@@ -0,0 +1,829 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+
+/// Languages the UI can be shown in. Strings are keyed by their English text, so English
+/// needs no catalog and any string missing from a catalog falls back to English.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    English,
+    German,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::German];
+
+    /// Name of the language in itself, so it can be found without reading the current one.
+    pub fn native_name(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::German => "Deutsch",
+        }
+    }
+
+    fn catalog(&self) -> Option<&'static HashMap<&'static str, &'static str>> {
+        static GERMAN_CATALOG: OnceLock<HashMap<&str, &str>> = OnceLock::new();
+        match self {
+            Language::English => None,
+            Language::German => {
+                Some(GERMAN_CATALOG.get_or_init(|| GERMAN.iter().copied().collect()))
+            }
+        }
+    }
+}
+
+static CURRENT: AtomicU8 = AtomicU8::new(0);
+
+pub fn set_language(language: Language) {
+    CURRENT.store(language as u8, Ordering::Relaxed);
+}
+
+pub fn language() -> Language {
+    match CURRENT.load(Ordering::Relaxed) {
+        1 => Language::German,
+        _ => Language::English,
+    }
+}
+
+/// `text` in the current language.
+pub fn tr(text: &'static str) -> &'static str {
+    language()
+        .catalog()
+        .and_then(|catalog| catalog.get(text).copied())
+        .unwrap_or(text)
+}
+
+const GERMAN: &[(&str, &str)] = &[
+    // Menu bar
+    ("File", "Datei"),
+    ("Edit", "Bearbeiten"),
+    ("Layout", "Layout"),
+    ("Launch Loader", "Loader starten"),
+    ("Data", "Daten"),
+    ("Save Data...", "Daten speichern..."),
+    ("Load Data...", "Daten laden..."),
+    ("Add Log Session...", "Log-Sitzung hinzufügen..."),
+    (
+        "Load another log next to this one, e.g. for multi-vehicle flights",
+        "Weiteres Log neben diesem laden, z. B. für Flüge mit mehreren Fahrzeugen",
+    ),
+    ("Ingest Filters...", "Importfilter..."),
+    ("Live Retention", "Live-Aufbewahrung"),
+    (
+        "Applied per topic to live data only.",
+        "Gilt pro Topic und nur für Live-Daten.",
+    ),
+    ("Clear", "Leeren"),
+    ("Preferences...", "Einstellungen..."),
+    ("Exit", "Beenden"),
+    ("Interpolation Method", "Interpolationsmethode"),
+    ("Trace Palette", "Farbpalette"),
+    ("Recolor Existing Traces", "Vorhandene Kurven neu einfärben"),
+    (
+        "Reassign colors of plotted traces from this palette",
+        "Farben der gezeichneten Kurven aus dieser Palette neu vergeben",
+    ),
+    ("Column Metadata...", "Spalten-Metadaten..."),
+    ("Resample Selected...", "Auswahl neu abtasten..."),
+    ("Save Layout", "Layout speichern"),
+    ("Load Layout", "Layout laden"),
+    ("No saved layouts", "Keine gespeicherten Layouts"),
+    // Dialogs
+    ("Layout Name:", "Layoutname:"),
+    ("Cancel", "Abbrechen"),
+    ("Save", "Speichern"),
+    ("Apply", "Übernehmen"),
+    ("Create", "Erstellen"),
+    ("Close", "Schließen"),
+    ("Default", "Standard"),
+    (
+        "Resample Selected Columns",
+        "Ausgewählte Spalten neu abtasten",
+    ),
+    ("New Topic:", "Neues Topic:"),
+    ("Rate:", "Rate:"),
+    ("Interpolation:", "Interpolation:"),
+    ("Ingest Filters", "Importfilter"),
+    (
+        "One pattern per line, as topic or topic/column. * and ? are wildcards.",
+        "Ein Muster pro Zeile, als Topic oder Topic/Spalte. * und ? sind Platzhalter.",
+    ),
+    (
+        "Include only (empty keeps everything):",
+        "Nur einschließen (leer behält alles):",
+    ),
+    ("Exclude:", "Ausschließen:"),
+    (
+        "Applies to data received or loaded from now on.",
+        "Gilt für ab jetzt empfangene oder geladene Daten.",
+    ),
+    ("Preferences", "Einstellungen"),
+    ("Data Source", "Datenquelle"),
+    ("Listen address:", "Empfangsadresse:"),
+    (
+        "Applied the next time TiPlot starts",
+        "Wird beim nächsten Start von TiPlot übernommen",
+    ),
+    ("Layouts folder:", "Layout-Ordner:"),
+    ("Plots", "Diagramme"),
+    ("Trace palette:", "Farbpalette:"),
+    ("Time format:", "Zeitformat:"),
+    ("Playback tooltip:", "Wiedergabe-Tooltip:"),
+    ("Always show", "Immer anzeigen"),
+    ("Playback", "Wiedergabe"),
+    ("Mode:", "Modus:"),
+    ("Speed:", "Geschwindigkeit:"),
+    ("Follow live data:", "Live-Daten folgen:"),
+    ("Lock to last", "Am Ende festhalten"),
+    ("Reset to Defaults", "Auf Standard zurücksetzen"),
+    ("Language:", "Sprache:"),
+    ("Column Metadata", "Spalten-Metadaten"),
+    ("Filter:", "Filter:"),
+    ("Column", "Spalte"),
+    ("Unit", "Einheit"),
+    ("Scale", "Skalierung"),
+    ("Description", "Beschreibung"),
+    ("Status:", "Status:"),
+    ("Received:", "Empfangen:"),
+    ("Dropped:", "Verworfen:"),
+    ("tables", "Tabellen"),
+    ("Last Error:", "Letzter Fehler:"),
+    ("Resume", "Fortsetzen"),
+    ("Pause", "Pausieren"),
+    ("Drop", "Trennen"),
+    (
+        "Close the current connection",
+        "Aktuelle Verbindung schließen",
+    ),
+    ("Stop", "Stoppen"),
+    (
+        "Stop listening for connections",
+        "Nicht mehr auf Verbindungen warten",
+    ),
+    ("Re-arm", "Wieder bereit"),
+    ("Trace Properties", "Kurveneigenschaften"),
+    ("Operation", "Operation"),
+    ("Gain", "Verstärkung"),
+    ("Offset", "Versatz"),
+    ("Color", "Farbe"),
+    ("Reset", "Zurücksetzen"),
+    ("Unwrap angle", "Winkel entfalten"),
+    ("Stored in", "Gespeichert in"),
+    ("Show in degrees", "In Grad anzeigen"),
+    ("Triggers", "Trigger"),
+    ("Triggers...", "Trigger..."),
+    ("Annotate", "Annotieren"),
+    ("Freeze view", "Ansicht einfrieren"),
+    ("Pause stream", "Stream pausieren"),
+    ("View frozen by a trigger", "Ansicht durch Trigger eingefroren"),
+    ("Resume View", "Ansicht fortsetzen"),
+    ("No triggers", "Keine Trigger"),
+    ("Holdoff", "Sperrzeit"),
+    ("New Trigger", "Neuer Trigger"),
+    ("Threshold", "Schwellwert"),
+    ("Topic stops", "Topic verstummt"),
+    ("for", "für"),
+    ("Add Trigger", "Trigger hinzufügen"),
+    ("Live Status", "Live-Status"),
+    ("Live Status...", "Live-Status..."),
+    ("Power Analysis", "Leistungsanalyse"),
+    ("Power Analysis...", "Leistungsanalyse..."),
+    ("Show Video...", "Video anzeigen..."),
+    ("Video Start", "Videostart"),
+    (
+        "Timeline time at which the video starts",
+        "Zeitpunkt auf der Zeitachse, an dem das Video beginnt",
+    ),
+    ("Start Video at Cursor", "Video am Cursor beginnen"),
+    ("Reload Video", "Video neu laden"),
+    ("Snap to Samples", "An Messpunkten einrasten"),
+    (
+        "Show the closest logged sample of each trace instead of interpolating",
+        "Den nächstgelegenen Messpunkt jedes Verlaufs statt eines interpolierten Werts zeigen",
+    ),
+    ("Format", "Format"),
+    ("Notation", "Schreibweise"),
+    ("Decimals", "Nachkommastellen"),
+    ("Significant Digits", "Signifikante Stellen"),
+    ("Scientific", "Wissenschaftlich"),
+    ("Digits", "Stellen"),
+    ("Unit Suffix", "Einheit anhängen"),
+    ("Value Labels", "Wertbezeichnungen"),
+    ("Custom Value Format", "Eigenes Werteformat"),
+    (
+        "Format this trace's values instead of using the column's format",
+        "Werte dieses Verlaufs statt mit dem Spaltenformat formatieren",
+    ),
+    ("Legend", "Legende"),
+    ("Show Values in Legend", "Werte in Legende anzeigen"),
+    (
+        "Value of each trace at the playback cursor",
+        "Wert jedes Verlaufs am Wiedergabecursor",
+    ),
+    ("Debug", "Debug"),
+    ("Synthetic Data...", "Synthetische Daten..."),
+    ("Synthetic Data", "Synthetische Daten"),
+    (
+        "Generate test topics without a log or loader",
+        "Testtopics ohne Log oder Loader erzeugen",
+    ),
+    (
+        "Topics with sine, noise and step columns for testing and benchmarks",
+        "Topics mit Sinus-, Rausch- und Sprungspalten für Tests und Benchmarks",
+    ),
+    ("Topics", "Topics"),
+    ("Stream Live", "Live streamen"),
+    ("samples/s", "Samples/s"),
+    ("samples", "Samples"),
+    ("columns", "Spalten"),
+    ("Generate", "Erzeugen"),
+    ("Sonify", "Vertonen"),
+    ("Pitch", "Tonhöhe"),
+    ("Volume", "Lautstärke"),
+    (
+        "Play a trace as a tone during playback",
+        "Einen Verlauf während der Wiedergabe als Ton abspielen",
+    ),
+    ("Show as Image Sequence", "Als Bildfolge anzeigen"),
+    ("No topics with image columns", "Keine Topics mit Bildspalten"),
+    ("Previous Frame", "Vorheriges Bild"),
+    ("Next Frame", "Nächstes Bild"),
+    ("Set Image Folder...", "Bildordner festlegen..."),
+    (
+        "Folder that relative image paths are resolved against",
+        "Ordner, auf den sich relative Bildpfade beziehen",
+    ),
+    ("Topic Aliases", "Topic-Aliase"),
+    ("Topic Aliases...", "Topic-Aliase..."),
+    (
+        "Canonical names let layouts, presets and vehicles work across autopilots",
+        "Kanonische Namen machen Layouts, Vorlagen und Fahrzeuge autopilotübergreifend nutzbar",
+    ),
+    ("Aliases", "Aliase"),
+    ("Canonical Name", "Kanonischer Name"),
+    ("Present in the loaded data", "In den geladenen Daten vorhanden"),
+    ("Add Alias", "Alias hinzufügen"),
+    ("Add Profile", "Profil hinzufügen"),
+    ("Remove Profile", "Profil entfernen"),
+    ("Restore Built-in", "Standard wiederherstellen"),
+    ("Voltage", "Spannung"),
+    ("Current", "Strom"),
+    ("Capacity", "Kapazität"),
+    ("Over the loop region", "Über den Schleifenbereich"),
+    ("Over the visible range", "Über den sichtbaren Bereich"),
+    ("No battery data in this range", "Keine Akkudaten in diesem Bereich"),
+    ("Duration", "Dauer"),
+    ("Energy", "Energie"),
+    ("Charge", "Ladung"),
+    ("Average current", "Mittlerer Strom"),
+    ("Peak current", "Spitzenstrom"),
+    ("Voltage sag under load", "Spannungseinbruch unter Last"),
+    ("Internal resistance", "Innenwiderstand"),
+    ("Remaining (logged)", "Verbleibend (geloggt)"),
+    ("Remaining (estimated)", "Verbleibend (geschätzt)"),
+    ("Time left at average current", "Restzeit bei mittlerem Strom"),
+    ("Copy Summary", "Zusammenfassung kopieren"),
+    (
+        "Copy as a Markdown table for reports",
+        "Als Markdown-Tabelle für Berichte kopieren",
+    ),
+    ("Stalled after", "Stillstand nach"),
+    ("No live data received yet", "Noch keine Live-Daten empfangen"),
+    ("topics stalled", "Topics stehen still"),
+    ("History", "Verlauf"),
+    ("Rate", "Rate"),
+    ("Last", "Zuletzt"),
+    ("Compare Windows", "Zeitfenster vergleichen"),
+    ("Overlay a second window", "Zweites Zeitfenster überlagern"),
+    ("Start at Cursor", "Am Cursor beginnen"),
+    (
+        "Compare the visible window with the one starting at the playback cursor",
+        "Das sichtbare Zeitfenster mit dem am Wiedergabe-Cursor beginnenden vergleichen",
+    ),
+    ("Ctrl+drag in the plot to align", "Strg+Ziehen im Plot zum Ausrichten"),
+    ("Limit Lines", "Grenzlinien"),
+    ("Add Limit", "Grenze hinzufügen"),
+    ("Analysis", "Analyse"),
+    ("Needs topics:", "Benötigt Topics:"),
+    ("Fill", "Füllung"),
+    ("Under Curve", "Unter der Kurve"),
+    ("Band to Column", "Band bis Spalte"),
+    ("Band ± Column", "Band ± Spalte"),
+    ("Stacked Lanes", "Gestapelte Spuren"),
+    (
+        "Each trace in its own lane with a separate value axis",
+        "Jede Kurve in einer eigenen Spur mit eigener Werteachse",
+    ),
+    ("Y-Sync Group", "Y-Synchrongruppe"),
+    ("Group", "Gruppe"),
+    ("None", "Keine"),
+    ("Hover crosshair:", "Fadenkreuz:"),
+    ("Show in all plots", "In allen Plots zeigen"),
+    ("Bring to Front", "In den Vordergrund"),
+    (
+        "Drag to reorder, the last trace is drawn on top",
+        "Zum Umordnen ziehen, die letzte Kurve liegt oben",
+    ),
+    ("Arrange as Grid", "Als Raster anordnen"),
+    ("Open in New Window", "In neuem Fenster öffnen"),
+    ("Clear Data", "Daten leeren"),
+    ("Overwrite Layout", "Layout überschreiben"),
+    ("Unsaved Layout", "Ungespeichertes Layout"),
+    ("All loaded data and plotted traces will be removed. This cannot be undone.", "Alle geladenen Daten und Kurven werden entfernt. Dies kann nicht rückgängig gemacht werden."),
+    ("All traces will be removed from this plot.", "Alle Kurven werden aus diesem Plot entfernt."),
+    ("A layout with this name already exists and will be replaced.", "Ein Layout mit diesem Namen existiert bereits und wird ersetzt."),
+    ("The current layout has unsaved changes. Exit anyway?", "Das aktuelle Layout hat ungespeicherte Änderungen. Trotzdem beenden?"),
+    ("Overwrite", "Überschreiben"),
+    ("Don't ask again", "Nicht mehr fragen"),
+    ("Confirmations", "Bestätigungen"),
+    ("Ask before clearing data", "Vor dem Leeren der Daten fragen"),
+    ("Ask before clearing a plot's traces", "Vor dem Entfernen der Kurven eines Plots fragen"),
+    ("Ask before overwriting a saved layout", "Vor dem Überschreiben eines Layouts fragen"),
+    ("Ask before exiting with an unsaved layout", "Vor dem Beenden mit ungespeichertem Layout fragen"),
+    ("Performance", "Leistung"),
+    ("Data processing", "Datenverarbeitung"),
+    ("Tooltip cache", "Tooltip-Cache"),
+    ("Plot rendering", "Plot-Darstellung"),
+    ("3D scene", "3D-Szene"),
+    ("Other UI", "Übrige Oberfläche"),
+    ("Slowest tiles", "Langsamste Kacheln"),
+    ("No visible tiles", "Keine sichtbaren Kacheln"),
+    ("Memory by topic", "Speicher nach Topic"),
+    ("Spilled to disk", "Auf Festplatte ausgelagert"),
+    ("on disk", "auf Festplatte"),
+    ("more", "weitere"),
+    ("Topic", "Topic"),
+    ("Total", "Gesamt"),
+    ("Log Console", "Protokollkonsole"),
+    ("Show info", "Infos anzeigen"),
+    ("Copy", "Kopieren"),
+    (
+        "Copy the shown messages to the clipboard",
+        "Angezeigte Meldungen in die Zwischenablage kopieren",
+    ),
+    ("No warnings or errors", "Keine Warnungen oder Fehler"),
+    ("Autosave live data:", "Live-Daten sichern:"),
+    ("File compression:", "Dateikomprimierung:"),
+    ("Memory budget:", "Speicherbudget:"),
+    (
+        "Least recently used topics beyond the limit are spilled to disk, 0 to disable",
+        "Am längsten ungenutzte Topics über dem Limit werden auf die Festplatte ausgelagert, 0 zum Deaktivieren",
+    ),
+    ("Export Arrow IPC", "Als Arrow IPC exportieren"),
+    ("One File per Topic...", "Eine Datei pro Topic..."),
+    ("A folder with a standard Arrow file for each topic", "Ein Ordner mit einer Standard-Arrow-Datei pro Topic"),
+    ("Single Table...", "Einzelne Tabelle..."),
+    ("One standard Arrow file with a topic column, e.g. for pandas or DuckDB", "Eine Standard-Arrow-Datei mit Topic-Spalte, z. B. für pandas oder DuckDB"),
+    ("Load Arrow Folder...", "Arrow-Ordner laden..."),
+    ("Load and Merge...", "Laden und zusammenführen..."),
+    ("Add a file's topics to the loaded data, e.g. to assemble a session from several files", "Die Topics einer Datei zu den geladenen Daten hinzufügen, z. B. um eine Sitzung aus mehreren Dateien zusammenzustellen"),
+    ("Load and Merge", "Laden und zusammenführen"),
+    ("Topic Prefix", "Topic-Präfix"),
+    ("Time", "Zeit"),
+    ("Merge", "Zusammenführen"),
+    ("Keep Timestamps", "Zeitstempel beibehalten"),
+    ("Align Starts", "Anfänge ausrichten"),
+    ("Append After End", "Nach dem Ende anhängen"),
+    ("Topics already loaded get the file's samples appended", "Bereits geladene Topics erhalten die Werte der Datei angehängt"),
+    ("Load a folder of standard Arrow files, one per topic", "Einen Ordner mit Standard-Arrow-Dateien laden, eine pro Topic"),
+    ("Recent Files", "Zuletzt geöffnet"),
+    ("Reopen Last", "Zuletzt Geöffnetes öffnen"),
+    ("No recent files", "Keine zuletzt geöffneten Dateien"),
+    ("Layouts", "Layouts"),
+    ("File not found", "Datei nicht gefunden"),
+    ("Clear Recent Files", "Liste leeren"),
+    ("New Log", "Neues Log"),
+    ("A new log appeared in the watched folder:", "Im überwachten Ordner ist ein neues Log erschienen:"),
+    ("more waiting", "weitere warten"),
+    ("Open", "Öffnen"),
+    ("Dismiss", "Verwerfen"),
+    ("Watch folder:", "Überwachter Ordner:"),
+    ("New logs appearing in this folder are offered to open", "Neue Logs in diesem Ordner werden zum Öffnen angeboten"),
+    ("Kiosk mode:", "Kioskmodus:"),
+    ("Open new logs automatically", "Neue Logs automatisch öffnen"),
+    ("Kiosk layout:", "Kiosk-Layout:"),
+    ("Keep current", "Aktuelles beibehalten"),
+    ("No data loaded", "Keine Daten geladen"),
+    ("Open a log or stream live data to get started", "Öffne ein Log oder streame Live-Daten, um zu beginnen"),
+    ("Open File...", "Datei öffnen..."),
+    ("Connect Loader", "Loader verbinden"),
+    ("Start the loader, which converts logs and streams them here", "Den Loader starten, der Logs konvertiert und hierher streamt"),
+    ("The loader was not found next to TiPlot", "Der Loader wurde neben TiPlot nicht gefunden"),
+    ("Listening on", "Lauscht auf"),
+    ("Connected to", "Verbunden mit"),
+    ("Start Live Listener", "Live-Empfang starten"),
+    ("Command Palette...", "Befehlspalette..."),
+    ("Type a command, topic or layout...", "Befehl, Topic oder Layout eingeben..."),
+    ("No matching commands", "Keine passenden Befehle"),
+    ("Command", "Befehl"),
+    ("Plot", "Plot"),
+    ("Annotation", "Annotation"),
+    ("Toggle Scatter Mode", "Streudiagramm umschalten"),
+    ("Jump to", "Springen zu"),
+    ("Samples", "Werte"),
+    ("Min", "Min"),
+    ("Mean", "Mittel"),
+    ("Max", "Max"),
+    (
+        "Interval between recovery checkpoints of streamed data, 0 to disable",
+        "Abstand zwischen Wiederherstellungspunkten gestreamter Daten, 0 zum Deaktivieren",
+    ),
+    ("Recover Live Data", "Live-Daten wiederherstellen"),
+    (
+        "Live data of the last session was not saved.",
+        "Live-Daten der letzten Sitzung wurden nicht gespeichert.",
+    ),
+    (
+        "Data streamed during that session can be restored.",
+        "In dieser Sitzung gestreamte Daten können wiederhergestellt werden.",
+    ),
+    (
+        "Minutes since the last autosave:",
+        "Minuten seit der letzten Sicherung:",
+    ),
+    ("Restore", "Wiederherstellen"),
+    ("Discard", "Verwerfen"),
+    ("Mirror", "Spiegeln"),
+    ("Stop Mirroring", "Spiegeln beenden"),
+    ("Publish", "Veröffentlichen"),
+    (
+        "Let other TiPlot instances follow this session and cursor",
+        "Anderen TiPlot-Instanzen erlauben, dieser Sitzung und dem Cursor zu folgen",
+    ),
+    ("Follow", "Folgen"),
+    (
+        "Mirror the session published by another TiPlot instance",
+        "Die von einer anderen TiPlot-Instanz veröffentlichte Sitzung spiegeln",
+    ),
+    (
+        "Start listening for connections again",
+        "Wieder auf Verbindungen warten",
+    ),
+    // Plot context menus
+    ("Clear All Traces", "Alle Kurven entfernen"),
+    ("Remove Trace", "Kurve entfernen"),
+    ("Trace Filter", "Kurvenfilter"),
+    ("Window", "Fenster"),
+    ("Cutoff", "Grenzfrequenz"),
+    ("Units", "Einheiten"),
+    (
+        "Set a unit in Column Metadata to convert",
+        "Zum Umrechnen eine Einheit in den Spalten-Metadaten festlegen",
+    ),
+    ("Split Horizontally", "Horizontal teilen"),
+    ("Split Vertically", "Vertikal teilen"),
+    ("Show Legend", "Legende anzeigen"),
+    ("Show Tooltip", "Tooltip anzeigen"),
+    ("Show Hover Circles", "Hover-Kreise anzeigen"),
+    ("Scatter Mode", "Streudiagramm"),
+    ("Reset Tile Sizes", "Kachelgrößen zurücksetzen"),
+    ("Reset View", "Ansicht zurücksetzen"),
+    ("Show as Map", "Als Karte anzeigen"),
+    (
+        "No topics with latitude/longitude columns",
+        "Keine Topics mit Breiten-/Längengrad-Spalten",
+    ),
+    ("Show as 3D Scene", "Als 3D-Szene anzeigen"),
+    ("Show as Plot", "Als Diagramm anzeigen"),
+    ("Show as Actuator Outputs", "Als Aktorausgänge anzeigen"),
+    (
+        "No topics with actuator output columns",
+        "Keine Topics mit Aktorausgangs-Spalten",
+    ),
+    ("Output Range", "Ausgangsbereich"),
+    ("Show as Tracking Analysis", "Als Regelgüte-Analyse anzeigen"),
+    (
+        "First trace is the setpoint, second the measurement",
+        "Erste Kurve ist der Sollwert, zweite der Messwert",
+    ),
+    ("Step Threshold", "Sprungschwelle"),
+    ("Fraction of the visible setpoint range", "Anteil des sichtbaren Sollwertbereichs"),
+    ("Settling Band", "Einschwingband"),
+    ("Fraction of the step size", "Anteil der Sprunghöhe"),
+    ("Swap Setpoint and Measurement", "Sollwert und Messwert tauschen"),
+    ("Plot Info", "Diagramminfo"),
+    ("Follow Vehicle", "Fahrzeug folgen"),
+    (
+        "Offline (cached tiles only)",
+        "Offline (nur zwischengespeicherte Kacheln)",
+    ),
+    ("Fit Trajectory", "Auf Flugbahn zoomen"),
+    ("Clear plot", "Diagramm leeren"),
+    // Timeline
+    (
+        "Click to jump to a time",
+        "Klicken, um zu einer Zeit zu springen",
+    ),
+    ("s, mm:ss or UTC date", "s, mm:ss oder UTC-Datum"),
+    ("Playing at wall-clock rate", "Wiedergabe in Echtzeit"),
+    ("Step", "Schritt"),
+    ("Speed Multiplier", "Geschwindigkeitsfaktor"),
+    ("Real Time (1x)", "Echtzeit (1x)"),
+    ("Step Through Clock Topic", "Schrittweise durch Takt-Topic"),
+    ("Seconds from Start", "Sekunden ab Start"),
+    ("UTC Wall Clock", "UTC-Uhrzeit"),
+    ("Elapsed (hh:mm:ss)", "Verstrichen (hh:mm:ss)"),
+    ("Off", "Aus"),
+    ("All Topics", "Alle Topics"),
+    ("Message Rate", "Nachrichtenrate"),
+    (
+        "Density or envelope drawn behind the timeline",
+        "Dichte oder Hüllkurve hinter der Zeitleiste",
+    ),
+    ("Lock to Last", "Am Ende festhalten"),
+    ("Lock Viewport", "Ansicht fixieren"),
+    (
+        "Always Show Playback Tooltip",
+        "Wiedergabe-Tooltip immer anzeigen",
+    ),
+    ("Add Marker at Cursor", "Marker an Cursor setzen"),
+    ("Clear Markers", "Marker entfernen"),
+    ("Set Loop A at Cursor", "Schleife A an Cursor setzen"),
+    ("Set Loop B at Cursor", "Schleife B an Cursor setzen"),
+    ("Shortcut: [", "Tastenkürzel: ["),
+    ("Shortcut: ]", "Tastenkürzel: ]"),
+    ("Loop A-B", "Schleife A-B"),
+    ("Clear Loop", "Schleife entfernen"),
+    // Topic panel
+    ("Sort:", "Sortierung:"),
+    ("Search values", "Werte durchsuchen"),
+    ("Plot selected:", "Auswahl zeichnen:"),
+    ("Current Tile", "Aktuelle Kachel"),
+    (
+        "Add the selected columns to the focused plot (Enter)",
+        "Ausgewählte Spalten zum fokussierten Diagramm hinzufügen (Enter)",
+    ),
+    ("New Tile", "Neue Kachel"),
+    (
+        "Add the selected columns to a new plot",
+        "Ausgewählte Spalten zu einem neuen Diagramm hinzufügen",
+    ),
+    ("No data loaded yet.", "Noch keine Daten geladen."),
+    ("(no columns)", "(keine Spalten)"),
+    ("Delete Column", "Spalte löschen"),
+    ("Rename", "Umbenennen"),
+    ("Merge Into", "Zusammenführen mit"),
+    ("Delete Topic", "Topic löschen"),
+    ("Starred", "Favoriten"),
+    ("Unstar", "Aus Favoriten entfernen"),
+    ("Recent", "Zuletzt verwendet"),
+    (
+        "Drag or double-click to add to a plot",
+        "Ziehen oder doppelklicken, um es einem Diagramm hinzuzufügen",
+    ),
+    (
+        "Not present in the loaded data",
+        "In den geladenen Daten nicht vorhanden",
+    ),
+    // Script console
+    ("Script Console...", "Skriptkonsole..."),
+    ("Script Console", "Skriptkonsole"),
+    ("Run", "Ausführen"),
+    ("Open...", "Öffnen..."),
+    ("Save...", "Speichern..."),
+    ("Clear Output", "Ausgabe leeren"),
+    ("Help", "Hilfe"),
+    // Trace palettes
+    (
+        "Okabe-Ito (Colorblind Safe)",
+        "Okabe-Ito (farbenblindsicher)",
+    ),
+    ("High Contrast", "Hoher Kontrast"),
+    // Trace groups
+    ("New group", "Neue Gruppe"),
+    ("Traces", "Kurven"),
+    ("Range", "Spanne"),
+    ("No samples in view", "Keine Werte im sichtbaren Bereich"),
+    ("Color Scheme", "Farbschema"),
+    ("Ungroup", "Gruppe auflösen"),
+    ("Remove from Group", "Aus Gruppe entfernen"),
+    // Robust auto-scale
+    ("Ignore Outliers", "Ausreißer ignorieren"),
+    (
+        "Fit the value axis to the 1st to 99th percentile of the visible samples",
+        "Werteachse an das 1. bis 99. Perzentil der sichtbaren Werte anpassen",
+    ),
+    // Cursor readout
+    ("Copy Time", "Zeit kopieren"),
+    ("Copy Value", "Wert kopieren"),
+    // Timeline zoom
+    ("Zoom to Full Range", "Auf Gesamtbereich zoomen"),
+    ("Zoom to Loop A-B", "Auf Schleife A-B zoomen"),
+    ("Zoom to Marker", "Auf Markierung zoomen"),
+    // Playback speed
+    ("Reverse", "Rückwärts"),
+    ("Shortcut", "Tastenkürzel"),
+    // Step by topic
+    ("Previous Sample", "Vorheriger Messwert"),
+    ("Next Sample", "Nächster Messwert"),
+    // Log info
+    ("Log Info", "Log-Informationen"),
+    ("Log Info...", "Log-Informationen..."),
+    (
+        "The loaded log has no version details or parameters",
+        "Das geladene Log enthält keine Versionsangaben oder Parameter",
+    ),
+    ("Export...", "Exportieren..."),
+    (
+        "Save the version details and parameters as JSON",
+        "Versionsangaben und Parameter als JSON speichern",
+    ),
+    ("Version", "Version"),
+    ("Parameters", "Parameter"),
+    // Parameter diff
+    ("Parameters:", "Parameter:"),
+    ("All", "Alle"),
+    ("Changed from Defaults", "Vom Standard abweichend"),
+    (
+        "The log did not record parameter defaults",
+        "Das Log enthält keine Standardwerte der Parameter",
+    ),
+    ("Compared with Log", "Mit Log verglichen"),
+    ("Compare with...", "Vergleichen mit..."),
+    (
+        "Pick a saved data file or Log Info export to compare parameters with",
+        "Gespeicherte Datendatei oder Log-Info-Export zum Vergleich der Parameter wählen",
+    ),
+    ("Differences from", "Unterschiede zu"),
+    ("No differences", "Keine Unterschiede"),
+    ("Parameter", "Parameter"),
+    ("This Log", "Dieses Log"),
+    // Session alignment
+    ("Align Sessions", "Sitzungen ausrichten"),
+    ("Align Sessions...", "Sitzungen ausrichten..."),
+    (
+        "Line up an added log session with this log by correlating a trace",
+        "Eine hinzugefügte Log-Sitzung per Korrelation einer Kurve an diesem Log ausrichten",
+    ),
+    (
+        "Add a log session to align it with this log",
+        "Log-Sitzung hinzufügen, um sie an diesem Log auszurichten",
+    ),
+    ("Reference", "Referenz"),
+    ("Session", "Sitzung"),
+    ("Trace", "Kurve"),
+    ("Max Shift", "Max. Verschiebung"),
+    ("Find Offset", "Versatz suchen"),
+    (
+        "Cross-correlate the session's trace with the reference",
+        "Kurve der Sitzung mit der Referenz kreuzkorrelieren",
+    ),
+    ("No alignment found", "Keine Ausrichtung gefunden"),
+    // Vehicle configuration
+    ("Add Vehicle", "Fahrzeug hinzufügen"),
+    ("Vehicle", "Fahrzeug"),
+    ("Auto-configure Vehicle", "Fahrzeug automatisch konfigurieren"),
+    (
+        "Detect attitude and position topics of PX4, ArduPilot or MAVLink logs through the topic aliases",
+        "Lage- und Positions-Topics von PX4-, ArduPilot- oder MAVLink-Logs über die Topic-Aliase erkennen",
+    ),
+    ("Remove Vehicle", "Fahrzeug entfernen"),
+    ("Type", "Typ"),
+    ("Vehicle Color", "Fahrzeugfarbe"),
+    ("Path Color", "Pfadfarbe"),
+    ("By Column", "Nach Spalte"),
+    ("Color the trail by a data column", "Spur nach einer Datenspalte einfärben"),
+    ("Log Session", "Log-Sitzung"),
+    ("Main log", "Haupt-Log"),
+    ("Time Offset", "Zeitversatz"),
+    (
+        "Shifts this vehicle's log to line it up with the timeline",
+        "Verschiebt das Log dieses Fahrzeugs, um es an der Zeitachse auszurichten",
+    ),
+    ("Orientation", "Ausrichtung"),
+    ("Vector Overlays", "Vektor-Overlays"),
+    ("Remove vector", "Vektor entfernen"),
+    ("Frame", "Bezugssystem"),
+    ("Body", "Körper"),
+    ("Add Vector", "Vektor hinzufügen"),
+    ("Point Clouds", "Punktwolken"),
+    ("Remove point cloud", "Punktwolke entfernen"),
+    ("Ranges", "Entfernungen"),
+    ("Increment (deg)", "Schrittweite (Grad)"),
+    ("Offset (deg)", "Versatz (Grad)"),
+    ("Range Scale", "Entfernungsskalierung"),
+    ("Max Range", "Max. Entfernung"),
+    ("Accumulate", "Akkumulieren"),
+    ("Keep every sample up to the current time", "Alle Samples bis zur aktuellen Zeit behalten"),
+    ("Point Size", "Punktgröße"),
+    ("Add Point Cloud", "Punktwolke hinzufügen"),
+    ("Select topic first", "Zuerst Topic wählen"),
+    ("Select Array...", "Array wählen..."),
+    ("No array columns", "Keine Array-Spalten"),
+    ("Color Topic", "Farb-Topic"),
+    ("Color Column", "Farbspalte"),
+    ("Colormap", "Farbskala"),
+    ("to", "bis"),
+    ("Setpoint Ghost", "Sollwert-Geist"),
+    ("Show", "Anzeigen"),
+    (
+        "Draw a translucent model at the setpoint pose",
+        "Ein durchscheinendes Modell an der Sollpose zeichnen",
+    ),
+    ("Opacity", "Deckkraft"),
+    ("Setpoint Attitude", "Soll-Lage"),
+    ("Actual", "Ist"),
+    ("Setpoint Position", "Soll-Position"),
+    ("Static", "Statisch"),
+    ("Uses Identity rotation", "Verwendet die Identitätsrotation"),
+    ("Orient. Topic", "Ausricht.-Topic"),
+    ("Angle Unit", "Winkeleinheit"),
+    ("Radians", "Bogenmaß"),
+    ("Degrees", "Grad"),
+    ("Declination", "Deklination"),
+    (
+        "Added to the heading, for attitudes relative to magnetic north",
+        "Wird zum Kurs addiert, für Lagen relativ zu magnetisch Nord",
+    ),
+    ("Local (NED)", "Lokal (NED)"),
+    ("Pos. Topic", "Pos.-Topic"),
+    ("North (X)", "Nord (X)"),
+    ("East (Y)", "Ost (Y)"),
+    ("Down (Z)", "Unten (Z)"),
+    ("Ref Latitude", "Ref.-Breitengrad"),
+    ("Ref Longitude", "Ref.-Längengrad"),
+    ("Ref Altitude", "Ref.-Höhe"),
+    ("Latitude", "Breitengrad"),
+    ("Longitude", "Längengrad"),
+    ("Altitude", "Höhe"),
+    ("Uses first position as origin", "Verwendet die erste Position als Ursprung"),
+    ("Select Topic...", "Topic wählen..."),
+    ("No matches", "Keine Treffer"),
+    ("Select Column...", "Spalte wählen..."),
+    // 3D view
+    ("3D View", "3D-Ansicht"),
+    ("Hide vehicle", "Fahrzeug ausblenden"),
+    ("Show vehicle", "Fahrzeug einblenden"),
+    ("Fixed Vehicle Scale", "Feste Fahrzeuggröße"),
+    (
+        "Keep vehicle size constant regardless of zoom level",
+        "Fahrzeuggröße unabhängig vom Zoom konstant halten",
+    ),
+    ("Wireframe", "Drahtgitter"),
+    (
+        "Draw vehicle models as edges instead of shaded surfaces",
+        "Fahrzeugmodelle als Kanten statt schattierter Flächen zeichnen",
+    ),
+    ("Chase", "Verfolgen"),
+    ("Top-Down", "Draufsicht"),
+    ("Free", "Frei"),
+    (
+        "Drag to orbit the vehicle, scroll to zoom",
+        "Ziehen, um das Fahrzeug zu umkreisen, scrollen zum Zoomen",
+    ),
+    (
+        "Follows behind the vehicle heading. Drag to adjust, scroll to zoom",
+        "Folgt hinter dem Fahrzeugkurs. Ziehen zum Anpassen, scrollen zum Zoomen",
+    ),
+    ("Onboard view using the vehicle attitude", "Bordansicht mit der Fahrzeuglage"),
+    (
+        "Orthographic map view. Drag to pan, scroll to zoom",
+        "Orthografische Kartenansicht. Ziehen zum Verschieben, scrollen zum Zoomen",
+    ),
+    (
+        "Drag to look, WASD to move, Q/E down/up, Shift to speed up",
+        "Ziehen zum Umsehen, WASD zum Bewegen, Q/E runter/hoch, Umschalt zum Beschleunigen",
+    ),
+    ("Terrain", "Gelände"),
+    ("Capture", "Aufnahme"),
+    ("The 3D view requires the wgpu renderer", "Die 3D-Ansicht benötigt den wgpu-Renderer"),
+    ("The 3D renderer is not initialized", "Der 3D-Renderer ist nicht initialisiert"),
+    ("Capture 3D View to PNG...", "3D-Ansicht als PNG speichern..."),
+    ("Camera Path", "Kamerapfad"),
+    ("Add Keyframe at", "Schlüsselbild hinzufügen bei"),
+    (
+        "Record the current view at the current time",
+        "Aktuelle Ansicht zur aktuellen Zeit aufzeichnen",
+    ),
+    ("Play Path", "Pfad abspielen"),
+    ("keyframes", "Schlüsselbilder"),
+    (
+        "Drive the camera from the keyframes as the log plays",
+        "Kamera während der Wiedergabe anhand der Schlüsselbilder führen",
+    ),
+    ("Load...", "Laden..."),
+    ("waypoints", "Wegpunkte"),
+    ("Load Plan File...", "Plandatei laden..."),
+    ("From Topic", "Aus Topic"),
+    ("Acceptance Radius", "Akzeptanzradius"),
+    ("Show Terrain", "Gelände anzeigen"),
+    ("Hide Grid", "Gitter ausblenden"),
+    ("Heightmap:", "Höhenkarte:"),
+    ("Ground Image:", "Bodenbild:"),
+    ("Size N/E (m):", "Größe N/O (m):"),
+    ("Center N/E (m):", "Mitte N/O (m):"),
+    ("Image Heights (m):", "Bildhöhen (m):"),
+    (
+        "Heights of black and white pixels in image heightmaps",
+        "Höhen schwarzer und weißer Pixel in Bild-Höhenkarten",
+    ),
+    ("Origin Altitude (m):", "Ursprungshöhe (m):"),
+    ("Terrain height at the local NED origin", "Geländehöhe am lokalen NED-Ursprung"),
+    ("From Vehicle", "Vom Fahrzeug"),
+    (
+        "Use the altitude of the vehicle's first position",
+        "Die Höhe der ersten Fahrzeugposition verwenden",
+    ),
+];
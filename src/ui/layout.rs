@@ -1,5 +1,11 @@
+use crate::core::{AngleUnit, SignalFilter, UnitConversion, ValueFormat};
+use crate::ui::panels::tabs::camera::CameraMode;
 use crate::ui::panels::tabs::config::VehicleConfig;
-use crate::ui::tiles::PlotTile;
+use crate::ui::panels::tabs::scene::SceneState;
+use crate::ui::tiles::plot_tile::{TraceFill, TraceGroup, TraceMath};
+use crate::ui::tiles::{
+    ActuatorView, ImageView, LegendCorner, MapView, PidView, PlotTile, VideoView,
+};
 use anyhow::{Context, Result};
 use egui_tiles::{Container, Tile, Tiles, Tree};
 use serde::{Deserialize, Serialize};
@@ -11,8 +17,40 @@ use std::path::{Path, PathBuf};
 pub struct SerializablePlotTile {
     pub traces: Vec<SerializableTrace>,
     pub show_legend: bool,
+    #[serde(default)]
+    pub show_legend_values: bool,
+    #[serde(default)]
+    pub legend_corner: LegendCorner,
+    #[serde(default)]
+    pub legend_collapsed: bool,
     pub show_hover_tooltip: bool,
     pub scatter_mode: bool,
+    #[serde(default)]
+    pub stacked: bool,
+    #[serde(default)]
+    pub robust_scale: bool,
+    #[serde(default)]
+    pub snap_to_samples: bool,
+    #[serde(default)]
+    pub map: Option<MapView>,
+    #[serde(default)]
+    pub scene: Option<CameraMode>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pid: Option<PidView>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actuators: Option<ActuatorView>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub video: Option<VideoView>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image: Option<ImageView>,
+    #[serde(default)]
+    pub y_sync_group: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compare_offset: Option<f32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub limits: Vec<f32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub groups: Vec<TraceGroup>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -20,6 +58,20 @@ pub struct SerializableTrace {
     pub topic: String,
     pub col: String,
     pub color: [f32; 4],
+    #[serde(default)]
+    pub filter: Option<SignalFilter>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unwrap: Option<AngleUnit>,
+    #[serde(default)]
+    pub conversion: Option<UnitConversion>,
+    #[serde(default)]
+    pub fill: Option<TraceFill>,
+    #[serde(default, skip_serializing_if = "TraceMath::is_identity")]
+    pub math: TraceMath,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<ValueFormat>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -30,25 +82,40 @@ pub struct SerializableTile {
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum SerializableTileKind {
-    Pane(SerializablePlotTile),
+    Pane(Box<SerializablePlotTile>),
     Container(SerializableContainer),
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SerializableContainer {
-    pub kind: String, // "Linear", "Tabs"
+    pub kind: String, // "Linear", "Tabs", "Grid"
     pub children: Vec<String>,
     pub direction: Option<String>, // "Horizontal", "Vertical"
     pub shares: Option<Vec<f32>>,
     pub active_tab: Option<usize>,
+    /// Fixed column count of a grid, `None` lets egui_tiles pick one.
+    #[serde(default)]
+    pub columns: Option<usize>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WorkspaceData {
+    pub name: String,
+    pub root_id: Option<String>,
+    pub tiles: HashMap<String, SerializableTile>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct LayoutData {
     pub name: String,
     pub version: u32,
+    /// Single tile tree of layouts saved before workspaces existed.
     pub root_id: Option<String>,
     pub tiles: HashMap<String, SerializableTile>,
+    #[serde(default)]
+    pub workspaces: Vec<WorkspaceData>,
+    #[serde(default)]
+    pub active_workspace: usize,
     pub vehicles: Vec<VehicleConfig>,
 }
 
@@ -56,18 +123,24 @@ impl LayoutData {
     pub fn new(name: String) -> Self {
         Self {
             name,
-            version: 1,
+            version: 2,
             root_id: None,
             tiles: HashMap::new(),
+            workspaces: Vec::new(),
+            active_workspace: 0,
             vehicles: Vec::new(),
         }
     }
 
+    /// Where a layout called `name` is saved in `layouts_dir`.
+    pub fn file_path(layouts_dir: &Path, name: &str) -> PathBuf {
+        layouts_dir.join(format!("{}.json", sanitize_filename(name)))
+    }
+
     pub fn save_to_file(&self, layouts_dir: &Path) -> Result<()> {
         fs::create_dir_all(layouts_dir).context("Failed to create layouts directory")?;
 
-        let filename = format!("{}.json", sanitize_filename(&self.name));
-        let path = layouts_dir.join(filename);
+        let path = Self::file_path(layouts_dir, &self.name);
 
         let json = serde_json::to_string_pretty(self).context("Failed to serialize layout")?;
         fs::write(&path, json).context("Failed to write layout file")?;
@@ -104,13 +177,27 @@ impl LayoutData {
         Ok(layouts)
     }
 
-    pub fn from_tree(name: String, tree: &Tree<PlotTile>, vehicles: &[VehicleConfig]) -> Self {
+    pub fn from_workspaces<'a>(
+        name: String,
+        workspaces: impl IntoIterator<Item = (&'a str, &'a Tree<PlotTile>)>,
+        active_workspace: usize,
+        vehicles: &[VehicleConfig],
+    ) -> Self {
         let mut layout = Self::new(name);
         layout.vehicles = vehicles.to_vec();
+        layout.active_workspace = active_workspace;
 
-        if let Some(root_id) = tree.root {
-            layout.root_id = Some(format!("{:?}", root_id));
-            Self::serialize_tile_recursive(root_id, &tree.tiles, &mut layout.tiles);
+        for (name, tree) in workspaces {
+            let mut workspace = WorkspaceData {
+                name: name.to_string(),
+                root_id: None,
+                tiles: HashMap::new(),
+            };
+            if let Some(root_id) = tree.root {
+                workspace.root_id = Some(format!("{:?}", root_id));
+                Self::serialize_tile_recursive(root_id, &tree.tiles, &mut workspace.tiles);
+            }
+            layout.workspaces.push(workspace);
         }
 
         layout
@@ -133,18 +220,41 @@ impl LayoutData {
                             topic: t.topic.clone(),
                             col: t.col.clone(),
                             color: t.color,
+                            filter: t.filter,
+                            unwrap: t.unwrap,
+                            conversion: t.conversion,
+                            fill: t.fill.clone(),
+                            math: t.math,
+                            format: t.format.clone(),
+                            group: t.group.clone(),
                         })
                         .collect();
 
-                    SerializableTileKind::Pane(SerializablePlotTile {
+                    SerializableTileKind::Pane(Box::new(SerializablePlotTile {
                         traces,
                         show_legend: plot_tile.show_legend,
+                        show_legend_values: plot_tile.show_legend_values,
+                        legend_corner: plot_tile.legend_corner,
+                        legend_collapsed: plot_tile.legend_collapsed,
                         show_hover_tooltip: plot_tile.show_hover_tooltip,
                         scatter_mode: plot_tile.scatter_mode,
-                    })
+                        stacked: plot_tile.stacked,
+                        robust_scale: plot_tile.robust_scale,
+                        snap_to_samples: plot_tile.snap_to_samples,
+                        map: plot_tile.map.clone(),
+                        scene: plot_tile.scene.as_ref().map(|s| s.camera.mode),
+                        pid: plot_tile.pid.clone(),
+                        actuators: plot_tile.actuators.clone(),
+                        video: plot_tile.video.clone(),
+                        image: plot_tile.image.clone(),
+                        y_sync_group: plot_tile.y_sync_group,
+                        compare_offset: plot_tile.compare_offset,
+                        limits: plot_tile.limits.clone(),
+                        groups: plot_tile.groups.clone(),
+                    }))
                 }
                 Tile::Container(container) => {
-                    let (kind, children, direction, shares, active_tab) = match container {
+                    let (kind, children, direction, shares, active_tab, columns) = match container {
                         Container::Linear(linear) => {
                             let dir = match linear.dir {
                                 egui_tiles::LinearDir::Horizontal => "Horizontal",
@@ -156,10 +266,11 @@ impl LayoutData {
 
                             (
                                 "Linear",
-                                &linear.children,
+                                linear.children.clone(),
                                 Some(dir.to_string()),
                                 Some(shares_vec),
                                 None,
+                                None,
                             )
                         }
                         Container::Tabs(tabs) => {
@@ -167,14 +278,19 @@ impl LayoutData {
                                 tabs.children.iter().position(|&id| id == active_id)
                             });
 
-                            ("Tabs", &tabs.children, None, None, active_idx)
+                            ("Tabs", tabs.children.clone(), None, None, active_idx, None)
                         }
-                        Container::Grid(_) => {
-                            return;
+                        Container::Grid(grid) => {
+                            let columns = match grid.layout {
+                                egui_tiles::GridLayout::Auto => None,
+                                egui_tiles::GridLayout::Columns(columns) => Some(columns),
+                            };
+                            let children = grid.children().copied().collect();
+                            ("Grid", children, None, None, None, columns)
                         }
                     };
 
-                    for &child_id in children {
+                    for &child_id in &children {
                         Self::serialize_tile_recursive(child_id, tiles, output);
                     }
 
@@ -184,6 +300,7 @@ impl LayoutData {
                         direction,
                         shares,
                         active_tab,
+                        columns,
                     })
                 }
             };
@@ -192,31 +309,83 @@ impl LayoutData {
         }
     }
 
-    pub fn to_tree(&self) -> Result<Tree<PlotTile>> {
+    /// Named tile trees of all workspaces, in tab order.
+    pub fn to_trees(&self) -> Result<Vec<(String, Tree<PlotTile>)>> {
+        if self.workspaces.is_empty() {
+            let tree = Self::build_tree(0, self.root_id.as_ref(), &self.tiles)?;
+            return Ok(vec![("Main".to_string(), tree)]);
+        }
+
+        self.workspaces
+            .iter()
+            .enumerate()
+            .map(|(index, workspace)| {
+                let tree = Self::build_tree(index, workspace.root_id.as_ref(), &workspace.tiles)
+                    .with_context(|| format!("Workspace '{}'", workspace.name))?;
+                Ok((workspace.name.clone(), tree))
+            })
+            .collect()
+    }
+
+    fn build_tree(
+        index: usize,
+        root_id: Option<&String>,
+        serialized: &HashMap<String, SerializableTile>,
+    ) -> Result<Tree<PlotTile>> {
         let mut tiles = Tiles::default();
         let mut id_map: HashMap<String, egui_tiles::TileId> = HashMap::new();
 
-        for (id_str, ser_tile) in &self.tiles {
+        for (id_str, ser_tile) in serialized {
             if let SerializableTileKind::Pane(plot_tile) = &ser_tile.kind {
                 let mut tile = PlotTile::new();
                 tile.show_legend = plot_tile.show_legend;
+                tile.show_legend_values = plot_tile.show_legend_values;
+                tile.legend_corner = plot_tile.legend_corner;
+                tile.legend_collapsed = plot_tile.legend_collapsed;
                 tile.show_hover_tooltip = plot_tile.show_hover_tooltip;
                 tile.scatter_mode = plot_tile.scatter_mode;
+                tile.stacked = plot_tile.stacked;
+                tile.robust_scale = plot_tile.robust_scale;
+                tile.snap_to_samples = plot_tile.snap_to_samples;
+                tile.map = plot_tile.map.clone();
+                tile.pid = plot_tile.pid.clone();
+                tile.actuators = plot_tile.actuators.clone();
+                tile.video = plot_tile.video.clone();
+                tile.image = plot_tile.image.clone();
+                tile.y_sync_group = plot_tile.y_sync_group;
+                tile.compare_offset = plot_tile.compare_offset;
+                tile.limits = plot_tile.limits.clone();
+                tile.scene = plot_tile.scene.map(|mode| {
+                    let mut scene = SceneState::default();
+                    scene.camera.mode = mode;
+                    Box::new(scene)
+                });
 
                 for trace in &plot_tile.traces {
                     tile.add_trace(trace.topic.clone(), trace.col.clone(), trace.color);
+                    if let Some(added) = tile.traces.last_mut() {
+                        added.filter = trace.filter;
+                        added.unwrap = trace.unwrap;
+                        added.conversion = trace.conversion;
+                        added.fill = trace.fill.clone();
+                        added.math = trace.math;
+                        added.format = trace.format.clone();
+                        added.group = trace.group.clone();
+                    }
                 }
+                tile.groups = plot_tile.groups.clone();
+                tile.regroup();
 
                 let tile_id = tiles.insert_pane(tile);
                 id_map.insert(id_str.clone(), tile_id);
             }
         }
 
-        let max_iterations = self.tiles.len();
+        let max_iterations = serialized.len();
         for _ in 0..max_iterations {
             let mut made_progress = false;
 
-            for (id_str, ser_tile) in &self.tiles {
+            for (id_str, ser_tile) in serialized {
                 if id_map.contains_key(id_str) {
                     continue;
                 }
@@ -258,6 +427,13 @@ impl LayoutData {
                             let tabs = egui_tiles::Tabs { children, active };
                             tiles.insert_container(tabs)
                         }
+                        "Grid" => {
+                            let mut grid = egui_tiles::Grid::new(children);
+                            if let Some(columns) = container.columns {
+                                grid.layout = egui_tiles::GridLayout::Columns(columns);
+                            }
+                            tiles.insert_container(grid)
+                        }
                         _ => continue,
                     };
 
@@ -271,16 +447,19 @@ impl LayoutData {
             }
         }
 
-        let root = self
-            .root_id
-            .as_ref()
+        let root = root_id
             .and_then(|id_str| id_map.get(id_str).copied())
             .context("No root tile found in layout")?;
 
-        Ok(Tree::new("main_tree", root, tiles))
+        Ok(Tree::new(workspace_tree_id(index as u64), root, tiles))
     }
 }
 
+/// Egui id of a workspace's tile tree, kept distinct so each tab remembers its own state.
+pub fn workspace_tree_id(n: u64) -> egui::Id {
+    egui::Id::new(("workspace_tree", n))
+}
+
 fn sanitize_filename(name: &str) -> String {
     name.chars()
         .map(|c| match c {
@@ -1,13 +1,75 @@
+use crate::core::{DataStore, IngestFilter, RetentionPolicy};
+use crate::ui::analysis::PRESETS;
+use crate::ui::i18n::{tr, Language};
+use crate::ui::palette::TracePalette;
+use crate::ui::panels::{PlaybackMode, RecentFiles, COMMAND_PALETTE_SHORTCUT};
+use crate::ui::settings::{default_layouts_dir, Confirmation, Settings};
+use crate::ui::time_format::TimeFormat;
 use crate::ui::{is_loader_available, layout::LayoutData, tiles::InterpolationMode};
 use eframe::egui;
 use egui_phosphor::regular as icons;
-use std::path::PathBuf;
+use egui_tiles::TileId;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use tiplot_client::protocol::Compression;
 
-#[derive(Default)]
 pub struct MenuState {
     pub save_dialog_open: bool,
     pub save_layout_name: String,
     pub error_message: Option<String>,
+
+    pub resample_dialog_open: bool,
+    pub resample_name: String,
+    pub resample_rate: f32,
+    pub resample_mode: InterpolationMode,
+
+    pub retention: RetentionPolicy,
+    pub retention_minutes: f32,
+    pub retention_samples: usize,
+
+    pub ingest_filter_dialog_open: bool,
+    pub ingest_include: String,
+    pub ingest_exclude: String,
+
+    pub preferences_open: bool,
+    /// Settings being edited, applied on save.
+    pub preferences_draft: Settings,
+
+    /// Save time of a checkpoint of unsaved live data left by the last session.
+    pub recovery_saved_at: Option<SystemTime>,
+
+    /// Logs that appeared in the watched folder, offered to open one at a time.
+    pub new_logs: Vec<PathBuf>,
+
+    /// Destructive action waiting for the user to confirm it.
+    pub pending_confirmation: Option<(Confirmation, MenuAction)>,
+    pub dont_ask_again: bool,
+}
+
+impl Default for MenuState {
+    fn default() -> Self {
+        Self {
+            save_dialog_open: false,
+            save_layout_name: String::new(),
+            error_message: None,
+            resample_dialog_open: false,
+            resample_name: "resampled".to_string(),
+            resample_rate: 50.0,
+            resample_mode: InterpolationMode::Linear,
+            retention: RetentionPolicy::Unlimited,
+            retention_minutes: 10.0,
+            retention_samples: 100_000,
+            ingest_filter_dialog_open: false,
+            ingest_include: String::new(),
+            ingest_exclude: String::new(),
+            preferences_open: false,
+            preferences_draft: Settings::default(),
+            recovery_saved_at: None,
+            new_logs: Vec::new(),
+            pending_confirmation: None,
+            dont_ask_again: false,
+        }
+    }
 }
 
 pub enum MenuAction {
@@ -15,10 +77,58 @@ pub enum MenuAction {
     SaveLayout(String),
     LoadLayout(PathBuf),
     SaveData,
+    /// Plain Arrow IPC files other tools open directly: one per topic in a folder.
+    ExportTopicFiles,
+    /// A single plain Arrow IPC file with a topic column.
+    ExportTable,
     LoadData,
+    /// Loads a folder of plain Arrow IPC files, one per topic.
+    LoadDataFolder,
+    /// Loads another log next to the current one, for replaying several vehicles together.
+    AddLogSession,
+    /// Appends a file's topics to the loaded data instead of replacing it.
+    MergeData,
+    OpenDataFile(PathBuf),
+    /// Loads the most recent layout and data file.
+    ReopenLast,
+    ClearRecentFiles,
     ClearData,
+    ClearTraces(TileId),
+    Exit,
+    /// A destructive action the user confirmed, optionally turning off its prompt.
+    Confirmed {
+        kind: Confirmation,
+        action: Box<MenuAction>,
+        dont_ask_again: bool,
+    },
     LaunchLoader,
+    /// Arms the TCP receiver after it was stopped or failed to bind.
+    StartListener,
     SetInterpolationMode(InterpolationMode),
+    SetTracePalette(TracePalette),
+    RecolorTraces,
+    Resample {
+        name: String,
+        rate_hz: f32,
+        mode: InterpolationMode,
+    },
+    SetIngestFilter(IngestFilter),
+    OpenColumnMetadata,
+    OpenLogInfo,
+    OpenScriptConsole,
+    OpenTriggers,
+    OpenLiveStatus,
+    OpenPowerAnalysis,
+    OpenSessionAlignment,
+    OpenTopicAliases,
+    OpenSyntheticData,
+    OpenCommandPalette,
+    /// Index into `analysis::PRESETS`.
+    ApplyAnalysisPreset(usize),
+    OpenPreferences,
+    SaveSettings(Settings),
+    RestoreRecovery,
+    DiscardRecovery,
 }
 
 impl MenuState {
@@ -30,14 +140,14 @@ impl MenuState {
         let mut action = MenuAction::None;
         let mut keep_open = true;
 
-        egui::Window::new("Save Layout")
+        egui::Window::new(tr("Save Layout"))
             .collapsible(false)
             .resizable(false)
             .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
             .show(ctx, |ui| {
                 ui.add_space(10.0);
 
-                ui.label("Layout Name:");
+                ui.label(tr("Layout Name:"));
                 let response = ui.text_edit_singleline(&mut self.save_layout_name);
 
                 if self.save_dialog_open {
@@ -52,13 +162,14 @@ impl MenuState {
                 }
 
                 ui.horizontal(|ui| {
-                    if ui.button("Cancel").clicked() {
+                    if ui.button(tr("Cancel")).clicked() {
                         keep_open = false;
                         self.save_layout_name.clear();
                         self.error_message = None;
                     }
 
-                    if ui.button("Save").clicked() || ui.input(|i| i.key_pressed(egui::Key::Enter))
+                    if ui.button(tr("Save")).clicked()
+                        || ui.input(|i| i.key_pressed(egui::Key::Enter))
                     {
                         if self.save_layout_name.trim().is_empty() {
                             self.error_message = Some("Layout name cannot be empty".to_string());
@@ -80,21 +191,812 @@ impl MenuState {
 
         action
     }
+
+    pub fn show_resample_dialog(
+        &mut self,
+        ctx: &egui::Context,
+        selected_count: usize,
+    ) -> MenuAction {
+        if !self.resample_dialog_open {
+            return MenuAction::None;
+        }
+
+        let mut action = MenuAction::None;
+        let mut keep_open = true;
+
+        egui::Window::new(tr("Resample Selected Columns"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.add_space(10.0);
+
+                ui.label(format!(
+                    "{} column(s) selected in the topic panel",
+                    selected_count
+                ));
+                ui.add_space(5.0);
+
+                egui::Grid::new("resample_grid")
+                    .num_columns(2)
+                    .spacing([20.0, 6.0])
+                    .show(ui, |ui| {
+                        ui.label(tr("New Topic:"));
+                        ui.text_edit_singleline(&mut self.resample_name);
+                        ui.end_row();
+
+                        ui.label(tr("Rate:"));
+                        ui.add(
+                            egui::DragValue::new(&mut self.resample_rate)
+                                .speed(1.0)
+                                .range(0.1..=10000.0)
+                                .suffix(" Hz"),
+                        );
+                        ui.end_row();
+
+                        ui.label(tr("Interpolation:"));
+                        egui::ComboBox::from_id_salt("resample_mode")
+                            .selected_text(interpolation_label(self.resample_mode))
+                            .show_ui(ui, |ui| {
+                                for mode in INTERPOLATION_MODES {
+                                    ui.selectable_value(
+                                        &mut self.resample_mode,
+                                        mode,
+                                        interpolation_label(mode),
+                                    );
+                                }
+                            });
+                        ui.end_row();
+                    });
+
+                ui.add_space(10.0);
+
+                if let Some(err) = &self.error_message {
+                    ui.colored_label(egui::Color32::RED, err);
+                    ui.add_space(5.0);
+                }
+
+                ui.horizontal(|ui| {
+                    if ui.button(tr("Cancel")).clicked() {
+                        keep_open = false;
+                        self.error_message = None;
+                    }
+
+                    if ui.button(tr("Create")).clicked() {
+                        if selected_count == 0 {
+                            self.error_message =
+                                Some("Select columns to resample first".to_string());
+                        } else {
+                            action = MenuAction::Resample {
+                                name: self.resample_name.trim().to_string(),
+                                rate_hz: self.resample_rate,
+                                mode: self.resample_mode,
+                            };
+                            keep_open = false;
+                            self.error_message = None;
+                        }
+                    }
+                });
+
+                ui.add_space(5.0);
+            });
+
+        if !keep_open {
+            self.resample_dialog_open = false;
+        }
+
+        action
+    }
+
+    pub fn show_ingest_filter_dialog(&mut self, ctx: &egui::Context) -> MenuAction {
+        if !self.ingest_filter_dialog_open {
+            return MenuAction::None;
+        }
+
+        let mut action = MenuAction::None;
+        let mut keep_open = true;
+
+        egui::Window::new(tr("Ingest Filters"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.add_space(10.0);
+
+                ui.label(
+                    egui::RichText::new(tr(
+                        "One pattern per line, as topic or topic/column. * and ? are wildcards.",
+                    ))
+                    .weak(),
+                );
+                ui.add_space(5.0);
+
+                ui.label(tr("Include only (empty keeps everything):"));
+                ui.add(
+                    egui::TextEdit::multiline(&mut self.ingest_include)
+                        .desired_rows(4)
+                        .hint_text("vehicle_*\nsensor_combined/gyro_*"),
+                );
+                ui.add_space(5.0);
+
+                ui.label(tr("Exclude:"));
+                ui.add(
+                    egui::TextEdit::multiline(&mut self.ingest_exclude)
+                        .desired_rows(4)
+                        .hint_text("*_status\nestimator_*"),
+                );
+
+                ui.add_space(10.0);
+                ui.label(
+                    egui::RichText::new(tr("Applies to data received or loaded from now on."))
+                        .small()
+                        .weak(),
+                );
+                ui.add_space(5.0);
+
+                ui.horizontal(|ui| {
+                    if ui.button(tr("Cancel")).clicked() {
+                        keep_open = false;
+                    }
+
+                    if ui.button(tr("Apply")).clicked() {
+                        action = MenuAction::SetIngestFilter(IngestFilter::parse(
+                            &self.ingest_include,
+                            &self.ingest_exclude,
+                        ));
+                        keep_open = false;
+                    }
+                });
+
+                ui.add_space(5.0);
+            });
+
+        if !keep_open {
+            self.ingest_filter_dialog_open = false;
+        }
+
+        action
+    }
+
+    pub fn show_recovery_dialog(&mut self, ctx: &egui::Context) -> MenuAction {
+        let Some(saved_at) = self.recovery_saved_at else {
+            return MenuAction::None;
+        };
+
+        let mut action = MenuAction::None;
+        let minutes_ago = saved_at.elapsed().map_or(0, |d| d.as_secs() / 60);
+
+        egui::Window::new(tr("Recover Live Data"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.add_space(10.0);
+                ui.label(tr("Live data of the last session was not saved."));
+                ui.label(tr("Data streamed during that session can be restored."));
+                ui.label(format!(
+                    "{} {}",
+                    tr("Minutes since the last autosave:"),
+                    minutes_ago
+                ));
+                ui.add_space(10.0);
+
+                ui.horizontal(|ui| {
+                    if ui
+                        .button(format!(
+                            "{} {}",
+                            icons::CLOCK_COUNTER_CLOCKWISE,
+                            tr("Restore")
+                        ))
+                        .clicked()
+                    {
+                        action = MenuAction::RestoreRecovery;
+                    }
+                    if ui
+                        .button(format!("{} {}", icons::TRASH, tr("Discard")))
+                        .clicked()
+                    {
+                        action = MenuAction::DiscardRecovery;
+                    }
+                });
+                ui.add_space(5.0);
+            });
+
+        if !matches!(action, MenuAction::None) {
+            self.recovery_saved_at = None;
+        }
+        action
+    }
+
+    pub fn show_new_log_dialog(&mut self, ctx: &egui::Context) -> MenuAction {
+        let Some(path) = self.new_logs.first().cloned() else {
+            return MenuAction::None;
+        };
+
+        let mut action = MenuAction::None;
+        let mut dismissed = false;
+
+        egui::Window::new(tr("New Log"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::RIGHT_BOTTOM, [-10.0, -40.0])
+            .show(ctx, |ui| {
+                ui.label(tr("A new log appeared in the watched folder:"));
+                ui.label(
+                    egui::RichText::new(path.file_name().map_or_else(
+                        || path.display().to_string(),
+                        |n| n.to_string_lossy().into_owned(),
+                    ))
+                    .monospace(),
+                )
+                .on_hover_text(path.display().to_string());
+                if self.new_logs.len() > 1 {
+                    ui.label(
+                        egui::RichText::new(format!(
+                            "{} {}",
+                            self.new_logs.len() - 1,
+                            tr("more waiting")
+                        ))
+                        .small()
+                        .weak(),
+                    );
+                }
+                ui.add_space(5.0);
+
+                ui.horizontal(|ui| {
+                    if ui
+                        .button(format!("{} {}", icons::FOLDER_OPEN, tr("Open")))
+                        .clicked()
+                    {
+                        action = MenuAction::OpenDataFile(path.clone());
+                    }
+                    if ui.button(tr("Dismiss")).clicked() {
+                        dismissed = true;
+                    }
+                });
+            });
+
+        if dismissed || !matches!(action, MenuAction::None) {
+            self.new_logs.remove(0);
+        }
+        action
+    }
+
+    pub fn request_confirmation(&mut self, kind: Confirmation, action: MenuAction) {
+        self.pending_confirmation = Some((kind, action));
+        self.dont_ask_again = false;
+    }
+
+    pub fn show_confirmation_dialog(&mut self, ctx: &egui::Context) -> MenuAction {
+        let Some((kind, _)) = &self.pending_confirmation else {
+            return MenuAction::None;
+        };
+        let kind = *kind;
+
+        let mut confirmed = false;
+        let mut keep_open = true;
+
+        egui::Window::new(tr(confirmation_title(kind)))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    ui.label(
+                        egui::RichText::new(icons::WARNING)
+                            .size(20.0)
+                            .color(egui::Color32::from_rgb(230, 180, 50)),
+                    );
+                    ui.label(tr(confirmation_message(kind)));
+                });
+                ui.add_space(10.0);
+                ui.checkbox(&mut self.dont_ask_again, tr("Don't ask again"));
+                ui.add_space(10.0);
+
+                ui.horizontal(|ui| {
+                    if ui.button(tr("Cancel")).clicked()
+                        || ui.input(|i| i.key_pressed(egui::Key::Escape))
+                    {
+                        keep_open = false;
+                    }
+
+                    let confirm = egui::Button::new(
+                        egui::RichText::new(tr(confirmation_button(kind)))
+                            .color(egui::Color32::from_rgb(230, 90, 90)),
+                    );
+                    if ui.add(confirm).clicked() {
+                        confirmed = true;
+                    }
+                });
+                ui.add_space(5.0);
+            });
+
+        if confirmed {
+            let (kind, action) = self.pending_confirmation.take().unwrap();
+            return MenuAction::Confirmed {
+                kind,
+                action: Box::new(action),
+                dont_ask_again: self.dont_ask_again,
+            };
+        }
+        if !keep_open {
+            self.pending_confirmation = None;
+        }
+        MenuAction::None
+    }
+
+    pub fn open_preferences(&mut self, settings: &Settings) {
+        self.preferences_draft = settings.clone();
+        self.preferences_open = true;
+    }
+
+    pub fn show_preferences_dialog(&mut self, ctx: &egui::Context) -> MenuAction {
+        if !self.preferences_open {
+            return MenuAction::None;
+        }
+
+        let mut action = MenuAction::None;
+        let mut keep_open = true;
+        let draft = &mut self.preferences_draft;
+
+        egui::Window::new(tr("Preferences"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.add_space(10.0);
+
+                ui.horizontal(|ui| {
+                    ui.label(tr("Language:"));
+                    egui::ComboBox::from_id_salt("preferences_language")
+                        .selected_text(draft.language.native_name())
+                        .show_ui(ui, |ui| {
+                            for language in Language::ALL {
+                                ui.selectable_value(
+                                    &mut draft.language,
+                                    language,
+                                    language.native_name(),
+                                );
+                            }
+                        });
+                });
+
+                ui.add_space(10.0);
+                ui.strong(tr("Data Source"));
+                egui::Grid::new("preferences_data_grid")
+                    .num_columns(2)
+                    .spacing([20.0, 6.0])
+                    .show(ui, |ui| {
+                        ui.label(tr("Listen address:"));
+                        ui.text_edit_singleline(&mut draft.data.listen_addr)
+                            .on_hover_text(tr("Applied the next time TiPlot starts"));
+                        ui.end_row();
+
+                        ui.label(tr("Autosave live data:"));
+                        ui.add(
+                            egui::DragValue::new(&mut draft.data.autosave_interval_secs)
+                                .range(0..=3600)
+                                .suffix(" s"),
+                        )
+                        .on_hover_text(tr(
+                            "Interval between recovery checkpoints of streamed data, 0 to disable",
+                        ));
+                        ui.end_row();
+
+                        ui.label(tr("File compression:"));
+                        egui::ComboBox::from_id_salt("preferences_file_compression")
+                            .selected_text(tr(draft.data.file_compression.label()))
+                            .show_ui(ui, |ui| {
+                                for compression in Compression::ALL {
+                                    ui.selectable_value(
+                                        &mut draft.data.file_compression,
+                                        compression,
+                                        tr(compression.label()),
+                                    );
+                                }
+                            });
+                        ui.end_row();
+
+                        ui.label(tr("Memory budget:"));
+                        ui.add(
+                            egui::DragValue::new(&mut draft.data.memory_budget_gb)
+                                .range(0.0..=1024.0)
+                                .speed(0.1)
+                                .suffix(" GB"),
+                        )
+                        .on_hover_text(tr(
+                            "Least recently used topics beyond the limit are spilled to disk, 0 to disable",
+                        ));
+                        ui.end_row();
+
+                        ui.label(tr("Watch folder:"));
+                        ui.horizontal(|ui| {
+                            match &draft.watch_dir {
+                                Some(dir) => ui.label(
+                                    egui::RichText::new(dir.display().to_string()).monospace(),
+                                ),
+                                None => ui.label(egui::RichText::new(tr("Off")).weak()),
+                            };
+                            if ui.button(icons::FOLDER_OPEN).clicked() {
+                                if let Some(path) = rfd::FileDialog::new().pick_folder() {
+                                    draft.watch_dir = Some(path);
+                                }
+                            }
+                            if draft.watch_dir.is_some() && ui.button(tr("Off")).clicked() {
+                                draft.watch_dir = None;
+                            }
+                        })
+                        .response
+                        .on_hover_text(tr("New logs appearing in this folder are offered to open"));
+                        ui.end_row();
+
+                        ui.label(tr("Kiosk mode:"));
+                        ui.add_enabled(
+                            draft.watch_dir.is_some(),
+                            egui::Checkbox::new(
+                                &mut draft.kiosk_mode,
+                                tr("Open new logs automatically"),
+                            ),
+                        );
+                        ui.end_row();
+
+                        ui.label(tr("Kiosk layout:"));
+                        let layout_name = |path: &Path| {
+                            path.file_stem()
+                                .map_or_else(String::new, |s| s.to_string_lossy().into_owned())
+                        };
+                        ui.add_enabled_ui(draft.kiosk_mode && draft.watch_dir.is_some(), |ui| {
+                            egui::ComboBox::from_id_salt("preferences_default_layout")
+                                .selected_text(
+                                    draft.default_layout.as_deref().map_or_else(
+                                        || tr("Keep current").to_string(),
+                                        layout_name,
+                                    ),
+                                )
+                                .show_ui(ui, |ui| {
+                                    ui.selectable_value(
+                                        &mut draft.default_layout,
+                                        None,
+                                        tr("Keep current"),
+                                    );
+                                    let layouts = LayoutData::list_layouts(&draft.layouts_dir())
+                                        .unwrap_or_default();
+                                    for (name, path) in layouts {
+                                        ui.selectable_value(
+                                            &mut draft.default_layout,
+                                            Some(path),
+                                            name,
+                                        );
+                                    }
+                                });
+                        });
+                        ui.end_row();
+
+                        ui.label(tr("Layouts folder:"));
+                        ui.horizontal(|ui| {
+                            let dir = draft
+                                .layouts_dir
+                                .clone()
+                                .unwrap_or_else(default_layouts_dir);
+                            ui.label(egui::RichText::new(dir.display().to_string()).monospace());
+                            if ui.button(icons::FOLDER_OPEN).clicked() {
+                                if let Some(path) =
+                                    rfd::FileDialog::new().set_directory(&dir).pick_folder()
+                                {
+                                    draft.layouts_dir = Some(path);
+                                }
+                            }
+                            if draft.layouts_dir.is_some() && ui.button(tr("Default")).clicked() {
+                                draft.layouts_dir = None;
+                            }
+                        });
+                        ui.end_row();
+                    });
+
+                ui.add_space(10.0);
+                ui.strong(tr("Plots"));
+                egui::Grid::new("preferences_plot_grid")
+                    .num_columns(2)
+                    .spacing([20.0, 6.0])
+                    .show(ui, |ui| {
+                        ui.label(tr("Trace palette:"));
+                        egui::ComboBox::from_id_salt("preferences_palette")
+                            .selected_text(draft.trace_palette.name())
+                            .show_ui(ui, |ui| {
+                                for palette in TracePalette::ALL {
+                                    ui.selectable_value(
+                                        &mut draft.trace_palette,
+                                        palette,
+                                        palette.name(),
+                                    );
+                                }
+                            });
+                        ui.end_row();
+
+                        ui.label(tr("Time format:"));
+                        egui::ComboBox::from_id_salt("preferences_time_format")
+                            .selected_text(draft.time_format.label())
+                            .show_ui(ui, |ui| {
+                                for format in TimeFormat::ALL {
+                                    ui.selectable_value(
+                                        &mut draft.time_format,
+                                        format,
+                                        format.label(),
+                                    );
+                                }
+                            });
+                        ui.end_row();
+
+                        ui.label(tr("Playback tooltip:"));
+                        ui.checkbox(&mut draft.always_show_playback_tooltip, tr("Always show"));
+                        ui.end_row();
+
+                        ui.label(tr("Hover crosshair:"));
+                        ui.checkbox(&mut draft.shared_crosshair, tr("Show in all plots"));
+                        ui.end_row();
+                    });
+
+                ui.add_space(10.0);
+                ui.strong(tr("Playback"));
+                egui::Grid::new("preferences_playback_grid")
+                    .num_columns(2)
+                    .spacing([20.0, 6.0])
+                    .show(ui, |ui| {
+                        ui.label(tr("Mode:"));
+                        egui::ComboBox::from_id_salt("preferences_playback_mode")
+                            .selected_text(draft.playback_mode.label())
+                            .show_ui(ui, |ui| {
+                                for mode in PlaybackMode::ALL {
+                                    ui.selectable_value(
+                                        &mut draft.playback_mode,
+                                        mode,
+                                        mode.label(),
+                                    );
+                                }
+                            });
+                        ui.end_row();
+
+                        ui.label(tr("Speed:"));
+                        ui.add(
+                            egui::DragValue::new(&mut draft.playback_speed)
+                                .speed(0.1)
+                                .range(0.01..=1000.0)
+                                .suffix("x"),
+                        );
+                        ui.end_row();
+
+                        ui.label(tr("Follow live data:"));
+                        ui.checkbox(&mut draft.lock_to_last, tr("Lock to last"));
+                        ui.end_row();
+                    });
+
+                ui.add_space(10.0);
+                ui.strong(tr("Confirmations"));
+                for kind in Confirmation::ALL {
+                    let mut confirms = draft.confirms(kind);
+                    if ui
+                        .checkbox(&mut confirms, tr(confirmation_preference(kind)))
+                        .changed()
+                    {
+                        draft.set_confirms(kind, confirms);
+                    }
+                }
+
+                ui.add_space(10.0);
+
+                ui.horizontal(|ui| {
+                    if ui.button(tr("Reset to Defaults")).clicked() {
+                        *draft = Settings::default();
+                    }
+
+                    if ui.button(tr("Cancel")).clicked() {
+                        keep_open = false;
+                    }
+
+                    if ui.button(tr("Save")).clicked() {
+                        action = MenuAction::SaveSettings(draft.clone());
+                        keep_open = false;
+                    }
+                });
+
+                ui.add_space(5.0);
+            });
+
+        if !keep_open {
+            self.preferences_open = false;
+        }
+
+        action
+    }
+}
+
+const INTERPOLATION_MODES: [InterpolationMode; 3] = [
+    InterpolationMode::PreviousPoint,
+    InterpolationMode::Linear,
+    InterpolationMode::NextPoint,
+];
+
+fn interpolation_label(mode: InterpolationMode) -> &'static str {
+    match mode {
+        InterpolationMode::PreviousPoint => "Previous Point",
+        InterpolationMode::Linear => "Linear",
+        InterpolationMode::NextPoint => "Next Point",
+    }
+}
+
+fn confirmation_title(kind: Confirmation) -> &'static str {
+    match kind {
+        Confirmation::ClearData => "Clear Data",
+        Confirmation::ClearTraces => "Clear All Traces",
+        Confirmation::OverwriteLayout => "Overwrite Layout",
+        Confirmation::ExitUnsaved => "Unsaved Layout",
+    }
+}
+
+fn confirmation_message(kind: Confirmation) -> &'static str {
+    match kind {
+        Confirmation::ClearData => {
+            "All loaded data and plotted traces will be removed. This cannot be undone."
+        }
+        Confirmation::ClearTraces => "All traces will be removed from this plot.",
+        Confirmation::OverwriteLayout => {
+            "A layout with this name already exists and will be replaced."
+        }
+        Confirmation::ExitUnsaved => "The current layout has unsaved changes. Exit anyway?",
+    }
+}
+
+fn confirmation_button(kind: Confirmation) -> &'static str {
+    match kind {
+        Confirmation::ClearData | Confirmation::ClearTraces => "Clear",
+        Confirmation::OverwriteLayout => "Overwrite",
+        Confirmation::ExitUnsaved => "Exit",
+    }
+}
+
+fn confirmation_preference(kind: Confirmation) -> &'static str {
+    match kind {
+        Confirmation::ClearData => "Ask before clearing data",
+        Confirmation::ClearTraces => "Ask before clearing a plot's traces",
+        Confirmation::OverwriteLayout => "Ask before overwriting a saved layout",
+        Confirmation::ExitUnsaved => "Ask before exiting with an unsaved layout",
+    }
+}
+
+fn render_retention_menu(ui: &mut egui::Ui, menu_state: &mut MenuState) {
+    if ui
+        .radio(
+            menu_state.retention == RetentionPolicy::Unlimited,
+            "Keep everything",
+        )
+        .clicked()
+    {
+        menu_state.retention = RetentionPolicy::Unlimited;
+    }
+
+    ui.horizontal(|ui| {
+        let selected = matches!(menu_state.retention, RetentionPolicy::Duration(_));
+        let radio = ui.radio(selected, "Last");
+        let value = ui.add(
+            egui::DragValue::new(&mut menu_state.retention_minutes)
+                .range(0.1..=1440.0)
+                .speed(0.5)
+                .suffix(" min"),
+        );
+        if radio.clicked() || (selected && value.changed()) {
+            menu_state.retention = RetentionPolicy::Duration(menu_state.retention_minutes * 60.0);
+        }
+    });
+
+    ui.horizontal(|ui| {
+        let selected = matches!(menu_state.retention, RetentionPolicy::Samples(_));
+        let radio = ui.radio(selected, "Last");
+        let value = ui.add(
+            egui::DragValue::new(&mut menu_state.retention_samples)
+                .range(100..=100_000_000)
+                .speed(1000.0)
+                .suffix(" samples"),
+        );
+        if radio.clicked() || (selected && value.changed()) {
+            menu_state.retention = RetentionPolicy::Samples(menu_state.retention_samples);
+        }
+    });
+
+    ui.label(
+        egui::RichText::new(tr("Applied per topic to live data only."))
+            .small()
+            .weak(),
+    );
+}
+
+/// Loads the most recent layout and data file from anywhere in the app.
+pub const REOPEN_LAST_SHORTCUT: egui::KeyboardShortcut = egui::KeyboardShortcut::new(
+    egui::Modifiers {
+        shift: true,
+        ..egui::Modifiers::COMMAND
+    },
+    egui::Key::O,
+);
+
+fn render_recent_files_menu(ui: &mut egui::Ui, recent: &RecentFiles, action: &mut MenuAction) {
+    let reopen = egui::Button::new(format!(
+        "{} {}",
+        icons::ARROW_COUNTER_CLOCKWISE,
+        tr("Reopen Last")
+    ))
+    .shortcut_text(ui.ctx().format_shortcut(&REOPEN_LAST_SHORTCUT));
+    if ui.add_enabled(!recent.is_empty(), reopen).clicked() {
+        *action = MenuAction::ReopenLast;
+        ui.close_menu();
+    }
+
+    if recent.is_empty() {
+        ui.label(egui::RichText::new(tr("No recent files")).italics().weak());
+        return;
+    }
+
+    let sections = [
+        (tr("Data"), &recent.data, false),
+        (tr("Layouts"), &recent.layouts, true),
+    ];
+    for (title, paths, is_layout) in sections {
+        if paths.is_empty() {
+            continue;
+        }
+        ui.separator();
+        ui.label(egui::RichText::new(title).small().weak());
+        for path in paths {
+            let name = if is_layout {
+                path.file_stem()
+            } else {
+                path.file_name()
+            }
+            .map_or_else(
+                || path.display().to_string(),
+                |n| n.to_string_lossy().into_owned(),
+            );
+            let button = ui
+                .add_enabled(path.exists(), egui::Button::new(name))
+                .on_hover_text(path.display().to_string())
+                .on_disabled_hover_text(format!("{}: {}", tr("File not found"), path.display()));
+            if button.clicked() {
+                *action = if is_layout {
+                    MenuAction::LoadLayout(path.clone())
+                } else {
+                    MenuAction::OpenDataFile(path.clone())
+                };
+                ui.close_menu();
+            }
+        }
+    }
+
+    ui.separator();
+    if ui
+        .button(format!("{} {}", icons::BROOM, tr("Clear Recent Files")))
+        .clicked()
+    {
+        *action = MenuAction::ClearRecentFiles;
+        ui.close_menu();
+    }
 }
 
 pub fn render_menu_bar(
     ui: &mut egui::Ui,
     menu_state: &mut MenuState,
-    layouts_dir: &PathBuf,
+    layouts_dir: &Path,
+    recent_files: &RecentFiles,
     current_interpolation_mode: InterpolationMode,
+    current_trace_palette: TracePalette,
+    data_store: &DataStore,
 ) -> MenuAction {
     let mut action = MenuAction::None;
 
     egui::menu::bar(ui, |ui| {
-        ui.menu_button("File", |ui| {
+        ui.menu_button(tr("File"), |ui| {
             if is_loader_available() {
                 if ui
-                    .button(format!("{} Launch Loader", icons::ROCKET_LAUNCH))
+                    .button(format!("{} {}", icons::ROCKET_LAUNCH, tr("Launch Loader")))
                     .clicked()
                 {
                     action = MenuAction::LaunchLoader;
@@ -103,26 +1005,108 @@ pub fn render_menu_bar(
                 ui.separator();
             }
 
-            ui.menu_button(format!("{} Data", icons::DATABASE), |ui| {
+            ui.menu_button(format!("{} {}", icons::CLOCK, tr("Recent Files")), |ui| {
+                render_recent_files_menu(ui, recent_files, &mut action)
+            });
+
+            ui.menu_button(format!("{} {}", icons::DATABASE, tr("Data")), |ui| {
                 if ui
-                    .button(format!("{} Save Data...", icons::FLOPPY_DISK))
+                    .button(format!("{} {}", icons::FLOPPY_DISK, tr("Save Data...")))
                     .clicked()
                 {
                     action = MenuAction::SaveData;
                     ui.close_menu();
                 }
 
+                ui.menu_button(format!("{} {}", icons::EXPORT, tr("Export Arrow IPC")), |ui| {
+                    if ui
+                        .button(tr("One File per Topic..."))
+                        .on_hover_text(tr("A folder with a standard Arrow file for each topic"))
+                        .clicked()
+                    {
+                        action = MenuAction::ExportTopicFiles;
+                        ui.close_menu();
+                    }
+                    if ui
+                        .button(tr("Single Table..."))
+                        .on_hover_text(tr(
+                            "One standard Arrow file with a topic column, e.g. for pandas or DuckDB",
+                        ))
+                        .clicked()
+                    {
+                        action = MenuAction::ExportTable;
+                        ui.close_menu();
+                    }
+                });
+
                 if ui
-                    .button(format!("{} Load Data...", icons::FOLDER_OPEN))
+                    .button(format!("{} {}", icons::FOLDER_OPEN, tr("Load Data...")))
                     .clicked()
                 {
                     action = MenuAction::LoadData;
                     ui.close_menu();
                 }
 
+                if ui
+                    .button(format!("{} {}", icons::FOLDERS, tr("Load Arrow Folder...")))
+                    .on_hover_text(tr("Load a folder of standard Arrow files, one per topic"))
+                    .clicked()
+                {
+                    action = MenuAction::LoadDataFolder;
+                    ui.close_menu();
+                }
+
+                if ui
+                    .button(format!(
+                        "{} {}",
+                        icons::FOLDER_PLUS,
+                        tr("Add Log Session...")
+                    ))
+                    .on_hover_text(tr(
+                        "Load another log next to this one, e.g. for multi-vehicle flights",
+                    ))
+                    .clicked()
+                {
+                    action = MenuAction::AddLogSession;
+                    ui.close_menu();
+                }
+
+                if ui
+                    .button(format!("{} {}", icons::GIT_MERGE, tr("Load and Merge...")))
+                    .on_hover_text(tr(
+                        "Add a file's topics to the loaded data, e.g. to assemble a session from several files",
+                    ))
+                    .clicked()
+                {
+                    action = MenuAction::MergeData;
+                    ui.close_menu();
+                }
+
+                ui.separator();
+
+                if ui
+                    .button(format!("{} {}", icons::FUNNEL, tr("Ingest Filters...")))
+                    .clicked()
+                {
+                    menu_state.ingest_filter_dialog_open = true;
+                    ui.close_menu();
+                }
+
+                ui.menu_button(
+                    format!(
+                        "{} {}",
+                        icons::CLOCK_COUNTER_CLOCKWISE,
+                        tr("Live Retention")
+                    ),
+                    |ui| render_retention_menu(ui, menu_state),
+                );
+
                 ui.separator();
 
-                if ui.button(format!("{} Clear", icons::TRASH)).clicked() {
+                if ui
+                    .button(format!("{} {}", icons::TRASH, tr("Clear")))
+                    .clicked()
+                {
                     action = MenuAction::ClearData;
                     ui.close_menu();
                 }
@@ -130,24 +1114,48 @@ pub fn render_menu_bar(
 
             ui.separator();
 
-            if ui.button(format!("{} Exit", icons::SIGN_OUT)).clicked() {
-                std::process::exit(0);
+            if ui
+                .button(format!("{} {}", icons::GEAR_SIX, tr("Preferences...")))
+                .clicked()
+            {
+                action = MenuAction::OpenPreferences;
+                ui.close_menu();
+            }
+
+            ui.separator();
+
+            if ui
+                .button(format!("{} {}", icons::SIGN_OUT, tr("Exit")))
+                .clicked()
+            {
+                action = MenuAction::Exit;
+                ui.close_menu();
             }
         });
 
-        ui.menu_button("Edit", |ui| {
+        ui.menu_button(tr("Edit"), |ui| {
+            let palette = egui::Button::new(format!(
+                "{} {}",
+                icons::MAGNIFYING_GLASS,
+                tr("Command Palette...")
+            ))
+            .shortcut_text(ui.ctx().format_shortcut(&COMMAND_PALETTE_SHORTCUT));
+            if ui.add(palette).clicked() {
+                action = MenuAction::OpenCommandPalette;
+                ui.close_menu();
+            }
+
+            ui.separator();
+
             ui.menu_button(
-                format!("{} Interpolation Method", icons::CHART_LINE),
+                format!("{} {}", icons::CHART_LINE, tr("Interpolation Method")),
                 |ui| {
-                    let modes = [
-                        (InterpolationMode::PreviousPoint, "Previous Point"),
-                        (InterpolationMode::Linear, "Linear"),
-                        (InterpolationMode::NextPoint, "Next Point"),
-                    ];
-
-                    for (mode, label) in modes {
+                    for mode in INTERPOLATION_MODES {
                         if ui
-                            .selectable_label(current_interpolation_mode == mode, label)
+                            .selectable_label(
+                                current_interpolation_mode == mode,
+                                interpolation_label(mode),
+                            )
                             .clicked()
                         {
                             action = MenuAction::SetInterpolationMode(mode);
@@ -156,11 +1164,129 @@ pub fn render_menu_bar(
                     }
                 },
             );
+
+            ui.menu_button(
+                format!("{} {}", icons::PALETTE, tr("Trace Palette")),
+                |ui| {
+                    for palette in TracePalette::ALL {
+                        if ui
+                            .selectable_label(current_trace_palette == palette, palette.name())
+                            .clicked()
+                        {
+                            action = MenuAction::SetTracePalette(palette);
+                            ui.close_menu();
+                        }
+                    }
+                    ui.separator();
+                    if ui
+                        .button(tr("Recolor Existing Traces"))
+                        .on_hover_text(tr("Reassign colors of plotted traces from this palette"))
+                        .clicked()
+                    {
+                        action = MenuAction::RecolorTraces;
+                        ui.close_menu();
+                    }
+                },
+            );
+
+            ui.separator();
+
+            if ui
+                .button(format!("{} {}", icons::RULER, tr("Column Metadata...")))
+                .clicked()
+            {
+                action = MenuAction::OpenColumnMetadata;
+                ui.close_menu();
+            }
+
+            if ui
+                .button(format!("{} {}", icons::INFO, tr("Log Info...")))
+                .clicked()
+            {
+                action = MenuAction::OpenLogInfo;
+                ui.close_menu();
+            }
+
+            if ui
+                .button(format!(
+                    "{} {}",
+                    icons::TERMINAL_WINDOW,
+                    tr("Script Console...")
+                ))
+                .clicked()
+            {
+                action = MenuAction::OpenScriptConsole;
+                ui.close_menu();
+            }
+
+            if ui
+                .button(format!("{} {}", icons::SIREN, tr("Triggers...")))
+                .clicked()
+            {
+                action = MenuAction::OpenTriggers;
+                ui.close_menu();
+            }
+
+            if ui
+                .button(format!("{} {}", icons::HEARTBEAT, tr("Live Status...")))
+                .clicked()
+            {
+                action = MenuAction::OpenLiveStatus;
+                ui.close_menu();
+            }
+
+            if ui
+                .button(format!(
+                    "{} {}",
+                    icons::BATTERY_CHARGING,
+                    tr("Power Analysis...")
+                ))
+                .clicked()
+            {
+                action = MenuAction::OpenPowerAnalysis;
+                ui.close_menu();
+            }
+
+            let align = egui::Button::new(format!(
+                "{} {}",
+                icons::ARROWS_LEFT_RIGHT,
+                tr("Align Sessions...")
+            ));
+            if ui
+                .add_enabled(!data_store.sessions().is_empty(), align)
+                .on_hover_text(tr(
+                    "Line up an added log session with this log by correlating a trace",
+                ))
+                .clicked()
+            {
+                action = MenuAction::OpenSessionAlignment;
+                ui.close_menu();
+            }
+
+            if ui
+                .button(format!("{} {}", icons::SWAP, tr("Topic Aliases...")))
+                .clicked()
+            {
+                action = MenuAction::OpenTopicAliases;
+                ui.close_menu();
+            }
+
+            if ui
+                .button(format!(
+                    "{} {}",
+                    icons::WAVEFORM,
+                    tr("Resample Selected...")
+                ))
+                .clicked()
+            {
+                menu_state.resample_dialog_open = true;
+                ui.close_menu();
+            }
         });
 
-        ui.menu_button("Layout", |ui| {
+        ui.menu_button(tr("Layout"), |ui| {
             if ui
-                .button(format!("{} Save Layout", icons::FLOPPY_DISK))
+                .button(format!("{} {}", icons::FLOPPY_DISK, tr("Save Layout")))
                 .clicked()
             {
                 menu_state.save_dialog_open = true;
@@ -169,11 +1295,12 @@ pub fn render_menu_bar(
 
             ui.separator();
 
-            ui.menu_button(format!("{} Load Layout", icons::FOLDER_OPEN), |ui| {
-                match LayoutData::list_layouts(layouts_dir) {
+            ui.menu_button(
+                format!("{} {}", icons::FOLDER_OPEN, tr("Load Layout")),
+                |ui| match LayoutData::list_layouts(layouts_dir) {
                     Ok(layouts) => {
                         if layouts.is_empty() {
-                            ui.label(egui::RichText::new("No saved layouts").italics().weak());
+                            ui.label(egui::RichText::new(tr("No saved layouts")).italics().weak());
                         } else {
                             for (name, path) in layouts {
                                 if ui.button(&name).clicked() {
@@ -188,8 +1315,40 @@ pub fn render_menu_bar(
                             egui::RichText::new(format!("Error: {}", e)).color(egui::Color32::RED),
                         );
                     }
+                },
+            );
+        });
+
+        ui.menu_button(tr("Analysis"), |ui| {
+            for (index, preset) in PRESETS.iter().enumerate() {
+                let missing = preset.missing_topics(data_store);
+                let button = ui
+                    .add_enabled(
+                        missing.is_empty(),
+                        egui::Button::new(format!("{} {}", icons::STETHOSCOPE, preset.name)),
+                    )
+                    .on_hover_text(preset.description)
+                    .on_disabled_hover_text(format!(
+                        "{} {}",
+                        tr("Needs topics:"),
+                        missing.join(", ")
+                    ));
+                if button.clicked() {
+                    action = MenuAction::ApplyAnalysisPreset(index);
+                    ui.close_menu();
                 }
-            });
+            }
+        });
+
+        ui.menu_button(tr("Debug"), |ui| {
+            if ui
+                .button(format!("{} {}", icons::FLASK, tr("Synthetic Data...")))
+                .on_hover_text(tr("Generate test topics without a log or loader"))
+                .clicked()
+            {
+                action = MenuAction::OpenSyntheticData;
+                ui.close_menu();
+            }
         });
     });
 
@@ -1,30 +1,22 @@
+pub mod analysis;
 pub mod app;
 pub mod app_state;
+pub mod colormap;
+pub mod i18n;
 pub mod layout;
 pub mod menu;
+pub mod notifications;
+pub mod palette;
 pub mod panels;
 pub mod renderer;
+pub mod scene_renderer;
+pub mod settings;
+pub mod sonify;
 pub mod tiles;
+pub mod time_format;
 
 use std::process::Command;
 
-const COLOR_PALETTE: [[f32; 4]; 10] = [
-    [0.12, 0.47, 0.71, 1.0], // Blue
-    [1.00, 0.50, 0.05, 1.0], // Orange
-    [0.17, 0.63, 0.17, 1.0], // Green
-    [0.84, 0.15, 0.16, 1.0], // Red
-    [0.58, 0.40, 0.74, 1.0], // Purple
-    [0.55, 0.34, 0.29, 1.0], // Brown
-    [0.89, 0.47, 0.76, 1.0], // Pink
-    [0.50, 0.50, 0.50, 1.0], // Gray
-    [0.74, 0.74, 0.13, 1.0], // Yellow
-    [0.09, 0.75, 0.81, 1.0], // Cyan
-];
-
-pub fn get_trace_color(index: usize) -> [f32; 4] {
-    COLOR_PALETTE[index % COLOR_PALETTE.len()]
-}
-
 pub fn calculate_grid_step(range: f32, target_steps: usize) -> f32 {
     if range == 0.0 {
         return 1.0;
@@ -45,6 +37,12 @@ pub fn calculate_grid_step(range: f32, target_steps: usize) -> f32 {
     nice_step * mag
 }
 
+/// Gesture of two or more fingers that started in `rect`, e.g. on a tablet.
+pub fn multi_touch(ui: &egui::Ui, rect: egui::Rect) -> Option<egui::MultiTouchInfo> {
+    ui.input(|i| i.multi_touch())
+        .filter(|touch| rect.contains(touch.start_pos))
+}
+
 fn is_loader_available() -> bool {
     if std::env::var("TIPLOT_LOADER_COMMAND").is_ok() {
         return true;
@@ -90,12 +88,12 @@ fn launch_command(cmd: &str) -> Result<(), String> {
 
     match result {
         Ok(_) => {
-            eprintln!("✓ Launched loader: {}", cmd);
+            tracing::info!("Launched loader: {}", cmd);
             Ok(())
         }
         Err(e) => {
             let msg = format!("Failed to launch command '{}': {}", cmd, e);
-            eprintln!("✗ {}", msg);
+            tracing::error!("{}", msg);
             Err(msg)
         }
     }
@@ -120,18 +118,18 @@ fn launch_loader_executable() -> Result<(), String> {
             "No loader found. Set TIPLOT_LOADER_COMMAND or place 'tiplot-loader' executable in: {}",
             exe_dir.display()
         );
-        eprintln!("✗ {}", msg);
+        tracing::error!("{}", msg);
         return Err(msg);
     }
 
     match Command::new(&loader_path).spawn() {
         Ok(_) => {
-            eprintln!("✓ Launched loader: {}", loader_path.display());
+            tracing::info!("Launched loader: {}", loader_path.display());
             Ok(())
         }
         Err(e) => {
             let msg = format!("Failed to launch '{}': {}", loader_path.display(), e);
-            eprintln!("✗ {}", msg);
+            tracing::error!("{}", msg);
             Err(msg)
         }
     }
@@ -0,0 +1,157 @@
+use eframe::egui;
+use egui_phosphor::regular as icons;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Older toasts are dropped once this many are queued.
+const MAX_TOASTS: usize = 5;
+const TOAST_WIDTH: f32 = 340.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ToastLevel {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl ToastLevel {
+    fn icon(self) -> &'static str {
+        match self {
+            ToastLevel::Info => icons::INFO,
+            ToastLevel::Success => icons::CHECK_CIRCLE,
+            ToastLevel::Warning => icons::WARNING,
+            ToastLevel::Error => icons::X_CIRCLE,
+        }
+    }
+
+    fn color(self) -> egui::Color32 {
+        match self {
+            ToastLevel::Info => egui::Color32::from_rgb(90, 150, 230),
+            ToastLevel::Success => egui::Color32::from_rgb(100, 200, 100),
+            ToastLevel::Warning => egui::Color32::from_rgb(230, 180, 50),
+            ToastLevel::Error => egui::Color32::from_rgb(230, 90, 90),
+        }
+    }
+
+    /// Problems stay up longer so they are not missed.
+    fn lifetime(self) -> Duration {
+        match self {
+            ToastLevel::Info | ToastLevel::Success => Duration::from_secs(3),
+            ToastLevel::Warning => Duration::from_secs(6),
+            ToastLevel::Error => Duration::from_secs(10),
+        }
+    }
+}
+
+struct Toast {
+    level: ToastLevel,
+    message: String,
+    /// How often the same message was raised in a row.
+    repeats: usize,
+    shown_at: Instant,
+}
+
+/// Transient messages shown in the corner of the plot area. They expire on their own,
+/// stay while hovered and are dismissed by clicking.
+#[derive(Default)]
+pub struct Notifications {
+    toasts: VecDeque<Toast>,
+}
+
+impl Notifications {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn info(&mut self, message: impl Into<String>) {
+        self.push(ToastLevel::Info, message.into());
+    }
+
+    pub fn success(&mut self, message: impl Into<String>) {
+        self.push(ToastLevel::Success, message.into());
+    }
+
+    pub fn warning(&mut self, message: impl Into<String>) {
+        self.push(ToastLevel::Warning, message.into());
+    }
+
+    pub fn error(&mut self, message: impl Into<String>) {
+        self.push(ToastLevel::Error, message.into());
+    }
+
+    pub fn push(&mut self, level: ToastLevel, message: String) {
+        if let Some(last) = self.toasts.back_mut() {
+            if last.level == level && last.message == message {
+                last.repeats += 1;
+                last.shown_at = Instant::now();
+                return;
+            }
+        }
+
+        if self.toasts.len() == MAX_TOASTS {
+            self.toasts.pop_front();
+        }
+        self.toasts.push_back(Toast {
+            level,
+            message,
+            repeats: 1,
+            shown_at: Instant::now(),
+        });
+    }
+
+    /// Draws the toasts stacked upwards from the bottom-right corner of `area`.
+    pub fn show(&mut self, ctx: &egui::Context, area: egui::Rect) {
+        self.toasts
+            .retain(|toast| toast.shown_at.elapsed() < toast.level.lifetime());
+        if self.toasts.is_empty() {
+            return;
+        }
+
+        let mut dismissed = None;
+        egui::Area::new(egui::Id::new("toasts"))
+            .order(egui::Order::Foreground)
+            .pivot(egui::Align2::RIGHT_BOTTOM)
+            .fixed_pos(area.right_bottom() - egui::vec2(12.0, 12.0))
+            .interactable(true)
+            .show(ctx, |ui| {
+                ui.set_max_width(TOAST_WIDTH);
+                for (index, toast) in self.toasts.iter_mut().enumerate().rev() {
+                    let response = egui::Frame::popup(ui.style())
+                        .stroke(egui::Stroke::new(1.0, toast.level.color()))
+                        .show(ui, |ui| {
+                            ui.set_width(TOAST_WIDTH);
+                            ui.horizontal_wrapped(|ui| {
+                                ui.label(
+                                    egui::RichText::new(toast.level.icon())
+                                        .color(toast.level.color())
+                                        .size(16.0),
+                                );
+                                let text = if toast.repeats > 1 {
+                                    format!("{} (×{})", toast.message, toast.repeats)
+                                } else {
+                                    toast.message.clone()
+                                };
+                                ui.label(text);
+                            });
+                        })
+                        .response
+                        .interact(egui::Sense::click())
+                        .on_hover_text("Click to dismiss");
+
+                    if response.hovered() {
+                        toast.shown_at = Instant::now();
+                    }
+                    if response.clicked() {
+                        dismissed = Some(index);
+                    }
+                    ui.add_space(4.0);
+                }
+            });
+
+        if let Some(index) = dismissed {
+            self.toasts.remove(index);
+        }
+        ctx.request_repaint_after(Duration::from_millis(250));
+    }
+}
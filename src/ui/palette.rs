@@ -0,0 +1,105 @@
+use crate::ui::colormap::Colormap;
+use crate::ui::i18n::tr;
+use serde::{Deserialize, Serialize};
+
+/// Color sets new traces are drawn from.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TracePalette {
+    #[default]
+    Default,
+    /// Okabe-Ito, distinguishable with the common forms of color blindness.
+    OkabeIto,
+    /// Evenly spaced samples of the Viridis colormap, readable in grayscale too.
+    Viridis,
+    /// Saturated colors that hold up on projectors and against the dark background.
+    HighContrast,
+}
+
+const DEFAULT: [[f32; 4]; 10] = [
+    [0.12, 0.47, 0.71, 1.0], // Blue
+    [1.00, 0.50, 0.05, 1.0], // Orange
+    [0.17, 0.63, 0.17, 1.0], // Green
+    [0.84, 0.15, 0.16, 1.0], // Red
+    [0.58, 0.40, 0.74, 1.0], // Purple
+    [0.55, 0.34, 0.29, 1.0], // Brown
+    [0.89, 0.47, 0.76, 1.0], // Pink
+    [0.50, 0.50, 0.50, 1.0], // Gray
+    [0.74, 0.74, 0.13, 1.0], // Yellow
+    [0.09, 0.75, 0.81, 1.0], // Cyan
+];
+
+const OKABE_ITO: [[f32; 4]; 8] = [
+    [0.90, 0.62, 0.00, 1.0], // Orange
+    [0.34, 0.71, 0.91, 1.0], // Sky blue
+    [0.00, 0.62, 0.45, 1.0], // Bluish green
+    [0.94, 0.89, 0.26, 1.0], // Yellow
+    [0.00, 0.45, 0.70, 1.0], // Blue
+    [0.84, 0.37, 0.00, 1.0], // Vermillion
+    [0.80, 0.47, 0.65, 1.0], // Reddish purple
+    [0.90, 0.90, 0.90, 1.0], // Light gray, in place of black on the dark background
+];
+
+const HIGH_CONTRAST: [[f32; 4]; 8] = [
+    [1.00, 1.00, 1.00, 1.0], // White
+    [1.00, 0.90, 0.00, 1.0], // Yellow
+    [0.00, 1.00, 1.00, 1.0], // Cyan
+    [1.00, 0.20, 1.00, 1.0], // Magenta
+    [0.20, 1.00, 0.20, 1.0], // Lime
+    [1.00, 0.50, 0.00, 1.0], // Orange
+    [1.00, 0.25, 0.25, 1.0], // Red
+    [0.40, 0.60, 1.00, 1.0], // Light blue
+];
+
+/// Viridis samples per palette cycle.
+const VIRIDIS_STEPS: usize = 6;
+
+impl TracePalette {
+    pub const ALL: [TracePalette; 4] = [
+        TracePalette::Default,
+        TracePalette::OkabeIto,
+        TracePalette::Viridis,
+        TracePalette::HighContrast,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TracePalette::Default => tr("Default"),
+            TracePalette::OkabeIto => tr("Okabe-Ito (Colorblind Safe)"),
+            TracePalette::Viridis => "Viridis",
+            TracePalette::HighContrast => tr("High Contrast"),
+        }
+    }
+
+    pub fn colors(&self) -> Vec<[f32; 4]> {
+        match self {
+            TracePalette::Default => DEFAULT.to_vec(),
+            TracePalette::OkabeIto => OKABE_ITO.to_vec(),
+            TracePalette::HighContrast => HIGH_CONTRAST.to_vec(),
+            TracePalette::Viridis => (0..VIRIDIS_STEPS)
+                .map(|i| {
+                    // Viridis' darkest end is hard to see on the dark plot background.
+                    let t = 0.2 + 0.8 * i as f32 / (VIRIDIS_STEPS - 1) as f32;
+                    let c = Colormap::Viridis.sample(t);
+                    [
+                        c.r() as f32 / 255.0,
+                        c.g() as f32 / 255.0,
+                        c.b() as f32 / 255.0,
+                        1.0,
+                    ]
+                })
+                .collect(),
+        }
+    }
+
+    /// Color for a new trace next to traces already drawn in `used`: the palette entry
+    /// used the fewest times, earliest first, so removing a trace frees its color.
+    pub fn next_color(&self, used: &[[f32; 4]]) -> [f32; 4] {
+        let colors = self.colors();
+        let uses = |color: &[f32; 4]| used.iter().filter(|u| *u == color).count();
+        colors
+            .iter()
+            .copied()
+            .min_by_key(|color| uses(color))
+            .unwrap_or([1.0; 4])
+    }
+}
@@ -0,0 +1,168 @@
+use crate::core::{Alias, AliasProfile, DataStore};
+use crate::ui::i18n::tr;
+use eframe::egui;
+use egui_phosphor::regular as icons;
+
+/// Editor for the topic alias profiles. Edits a copy that replaces the settings on save.
+pub struct AliasPanel {
+    pub open: bool,
+    draft: Vec<AliasProfile>,
+}
+
+impl AliasPanel {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            draft: Vec::new(),
+        }
+    }
+
+    pub fn show(&mut self, profiles: &[AliasProfile]) {
+        self.draft = profiles.to_vec();
+        self.open = true;
+    }
+}
+
+impl Default for AliasPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn text_cell(ui: &mut egui::Ui, text: &mut String, width: f32) {
+    ui.add(egui::TextEdit::singleline(text).desired_width(width));
+}
+
+/// Returns the edited profiles when the user saves them.
+pub fn render_alias_panel(
+    ctx: &egui::Context,
+    panel: &mut AliasPanel,
+    data_store: &DataStore,
+) -> Option<Vec<AliasProfile>> {
+    if !panel.open {
+        return None;
+    }
+
+    let mut open = panel.open;
+    let mut saved = None;
+    egui::Window::new(tr("Topic Aliases"))
+        .open(&mut open)
+        .default_width(560.0)
+        .default_height(480.0)
+        .resizable(true)
+        .collapsible(false)
+        .show(ctx, |ui| {
+            ui.label(
+                egui::RichText::new(tr(
+                    "Canonical names let layouts, presets and vehicles work across autopilots",
+                ))
+                .weak(),
+            );
+            ui.separator();
+
+            let mut remove_profile = None;
+            egui::ScrollArea::vertical()
+                .max_height(ui.available_height() - 40.0)
+                .show(ui, |ui| {
+                    for (p, profile) in panel.draft.iter_mut().enumerate() {
+                        ui.push_id(p, |ui| {
+                            ui.horizontal(|ui| {
+                                ui.checkbox(&mut profile.enabled, "");
+                                text_cell(ui, &mut profile.name, 160.0);
+                                if ui
+                                    .small_button(icons::TRASH)
+                                    .on_hover_text(tr("Remove Profile"))
+                                    .clicked()
+                                {
+                                    remove_profile = Some(p);
+                                }
+                            });
+                            render_profile(ui, profile, data_store);
+                        });
+                        ui.separator();
+                    }
+                });
+            if let Some(p) = remove_profile {
+                panel.draft.remove(p);
+            }
+
+            ui.horizontal(|ui| {
+                if ui
+                    .button(format!("{} {}", icons::PLUS, tr("Add Profile")))
+                    .clicked()
+                {
+                    panel.draft.push(AliasProfile {
+                        name: "Custom".to_string(),
+                        enabled: true,
+                        aliases: Vec::new(),
+                    });
+                }
+                if ui
+                    .button(format!(
+                        "{} {}",
+                        icons::ARROW_COUNTER_CLOCKWISE,
+                        tr("Restore Built-in")
+                    ))
+                    .clicked()
+                {
+                    panel.draft = AliasProfile::builtin();
+                }
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.button(tr("Save")).clicked() {
+                        saved = Some(panel.draft.clone());
+                    }
+                });
+            });
+        });
+    panel.open = open && saved.is_none();
+    saved
+}
+
+fn render_profile(ui: &mut egui::Ui, profile: &mut AliasProfile, data_store: &DataStore) {
+    let mut remove = None;
+    egui::CollapsingHeader::new(format!("{} ({})", tr("Aliases"), profile.aliases.len()))
+        .id_salt("aliases")
+        .show(ui, |ui| {
+            egui::Grid::new("alias_grid")
+                .num_columns(5)
+                .striped(true)
+                .spacing([6.0, 2.0])
+                .show(ui, |ui| {
+                    ui.strong(tr("Canonical Name"));
+                    ui.strong(tr("Topic"));
+                    ui.strong(tr("Column"));
+                    ui.label("");
+                    ui.label("");
+                    ui.end_row();
+
+                    for (i, alias) in profile.aliases.iter_mut().enumerate() {
+                        text_cell(ui, &mut alias.canonical, 150.0);
+                        text_cell(ui, &mut alias.topic, 150.0);
+                        text_cell(ui, &mut alias.col, 110.0);
+                        if data_store.get_column(&alias.topic, &alias.col).is_some() {
+                            ui.label(icons::CHECK)
+                                .on_hover_text(tr("Present in the loaded data"));
+                        } else {
+                            ui.weak("–");
+                        }
+                        if ui.small_button(icons::X).clicked() {
+                            remove = Some(i);
+                        }
+                        ui.end_row();
+                    }
+                });
+            if ui
+                .small_button(format!("{} {}", icons::PLUS, tr("Add Alias")))
+                .clicked()
+            {
+                profile.aliases.push(Alias {
+                    canonical: String::new(),
+                    topic: String::new(),
+                    col: String::new(),
+                });
+            }
+        });
+    if let Some(i) = remove {
+        profile.aliases.remove(i);
+    }
+}
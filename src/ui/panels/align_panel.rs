@@ -0,0 +1,272 @@
+use crate::core::filters::best_offset;
+use crate::core::{session_topic, DataStore, SESSION_SEPARATOR};
+use crate::ui::i18n::tr;
+use eframe::egui;
+use egui_phosphor::regular as icons;
+
+/// Largest shift searched unless changed, in seconds.
+const DEFAULT_MAX_SHIFT: f32 = 60.0;
+
+/// Lines up an added log session with a reference trace by cross-correlating one of the
+/// session's traces with it, then shifts the whole session by the offset found.
+pub struct AlignPanel {
+    pub open: bool,
+    reference_topic: String,
+    reference_col: String,
+    session: String,
+    /// Topic of `session` without the session prefix.
+    topic: String,
+    col: String,
+    max_shift: f32,
+    /// Shift found for the current selection and its correlation coefficient, or `None`
+    /// inside when the search found no match.
+    suggestion: Option<Option<(f32, f32)>>,
+}
+
+impl AlignPanel {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            reference_topic: String::new(),
+            reference_col: String::new(),
+            session: String::new(),
+            topic: String::new(),
+            col: String::new(),
+            max_shift: DEFAULT_MAX_SHIFT,
+            suggestion: None,
+        }
+    }
+
+    /// Picks the first session if the chosen one is gone, and the reference's topic and
+    /// column within it if none is chosen yet.
+    fn detect(&mut self, data_store: &DataStore) {
+        let sessions = data_store.sessions();
+        if !sessions.contains(&self.session.as_str()) {
+            self.session = sessions.first().map(|s| s.to_string()).unwrap_or_default();
+            self.topic.clear();
+            self.suggestion = None;
+        }
+        if self.topic.is_empty() && !self.reference_topic.is_empty() {
+            let bare = self
+                .reference_topic
+                .split_once(SESSION_SEPARATOR)
+                .map_or(self.reference_topic.as_str(), |(_, topic)| topic);
+            let topic = session_topic(&self.session, bare);
+            if data_store.get_column(&topic, &self.reference_col).is_some() {
+                self.topic = bare.to_string();
+                self.col = self.reference_col.clone();
+            }
+        }
+    }
+}
+
+impl Default for AlignPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn series<'a>(data_store: &'a DataStore, topic: &str, col: &str) -> Option<(&'a [f32], &'a [f32])> {
+    Some((
+        data_store.get_column(topic, "timestamp")?,
+        data_store.get_column(topic, col)?,
+    ))
+}
+
+/// Combo box listing `topics`, shown without `strip`. Returns true if the pick changed.
+fn topic_combo<'a>(
+    ui: &mut egui::Ui,
+    id: &str,
+    topics: impl Iterator<Item = &'a String>,
+    strip: &str,
+    topic: &mut String,
+) -> bool {
+    let mut changed = false;
+    egui::ComboBox::from_id_salt(id)
+        .selected_text(topic.as_str())
+        .width(160.0)
+        .show_ui(ui, |ui| {
+            for name in topics {
+                let name = name.strip_prefix(strip).unwrap_or(name);
+                if ui.selectable_label(topic == name, name).clicked() {
+                    *topic = name.to_string();
+                    changed = true;
+                }
+            }
+        });
+    changed
+}
+
+/// Combo box of the columns of `topic`. Returns true if the pick changed.
+fn column_combo(
+    ui: &mut egui::Ui,
+    id: &str,
+    data_store: &DataStore,
+    topic: &str,
+    col: &mut String,
+) -> bool {
+    let mut changed = false;
+    egui::ComboBox::from_id_salt(id)
+        .selected_text(col.as_str())
+        .width(160.0)
+        .show_ui(ui, |ui| {
+            for name in data_store.get_columns(topic) {
+                if ui.selectable_label(col == name, name.as_str()).clicked() {
+                    *col = name.clone();
+                    changed = true;
+                }
+            }
+        });
+    changed
+}
+
+/// Returns the session to shift and by how many seconds once the user applies a match.
+pub fn render_align_panel(
+    ctx: &egui::Context,
+    panel: &mut AlignPanel,
+    data_store: &DataStore,
+) -> Option<(String, f32)> {
+    if !panel.open {
+        return None;
+    }
+    panel.detect(data_store);
+
+    let mut open = panel.open;
+    let mut apply = None;
+    egui::Window::new(tr("Align Sessions"))
+        .open(&mut open)
+        .resizable(false)
+        .collapsible(false)
+        .show(ctx, |ui| {
+            let sessions = data_store.sessions();
+            if sessions.is_empty() {
+                ui.label(
+                    egui::RichText::new(tr("Add a log session to align it with this log"))
+                        .italics()
+                        .weak(),
+                );
+                return;
+            }
+
+            let prefix = session_topic(&panel.session, "");
+            let mut changed = false;
+            egui::Grid::new("align_grid")
+                .num_columns(3)
+                .spacing([10.0, 6.0])
+                .show(ui, |ui| {
+                    ui.label(tr("Reference"));
+                    changed |= topic_combo(
+                        ui,
+                        "align_reference_topic",
+                        data_store.get_topics().into_iter(),
+                        "",
+                        &mut panel.reference_topic,
+                    );
+                    changed |= column_combo(
+                        ui,
+                        "align_reference_col",
+                        data_store,
+                        &panel.reference_topic,
+                        &mut panel.reference_col,
+                    );
+                    ui.end_row();
+
+                    ui.label(tr("Session"));
+                    egui::ComboBox::from_id_salt("align_session")
+                        .selected_text(panel.session.as_str())
+                        .width(160.0)
+                        .show_ui(ui, |ui| {
+                            for session in &sessions {
+                                if ui
+                                    .selectable_label(panel.session == *session, *session)
+                                    .clicked()
+                                {
+                                    panel.session = session.to_string();
+                                    panel.topic.clear();
+                                    changed = true;
+                                }
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label(tr("Trace"));
+                    changed |= topic_combo(
+                        ui,
+                        "align_topic",
+                        data_store
+                            .get_topics()
+                            .into_iter()
+                            .filter(|topic| topic.starts_with(&prefix)),
+                        &prefix,
+                        &mut panel.topic,
+                    );
+                    changed |= column_combo(
+                        ui,
+                        "align_col",
+                        data_store,
+                        &session_topic(&panel.session, &panel.topic),
+                        &mut panel.col,
+                    );
+                    ui.end_row();
+
+                    ui.label(tr("Max Shift"));
+                    changed |= ui
+                        .add(
+                            egui::DragValue::new(&mut panel.max_shift)
+                                .speed(1.0)
+                                .range(0.1..=86_400.0)
+                                .suffix(" s"),
+                        )
+                        .changed();
+                    ui.end_row();
+                });
+            if changed {
+                panel.suggestion = None;
+            }
+            ui.separator();
+
+            let reference = series(data_store, &panel.reference_topic, &panel.reference_col);
+            let target = series(
+                data_store,
+                &session_topic(&panel.session, &panel.topic),
+                &panel.col,
+            );
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(
+                        reference.is_some() && target.is_some(),
+                        egui::Button::new(format!(
+                            "{} {}",
+                            icons::MAGNIFYING_GLASS,
+                            tr("Find Offset")
+                        )),
+                    )
+                    .on_hover_text(tr("Cross-correlate the session's trace with the reference"))
+                    .clicked()
+                {
+                    panel.suggestion = Some(
+                        reference
+                            .zip(target)
+                            .and_then(|(r, t)| best_offset(r, t, panel.max_shift)),
+                    );
+                }
+
+                match panel.suggestion {
+                    Some(Some((offset, r))) => {
+                        ui.label(format!("{:+.3} s (r = {:.2})", offset, r));
+                        if ui.button(tr("Apply")).clicked() {
+                            apply = Some((panel.session.clone(), offset));
+                            panel.suggestion = None;
+                        }
+                    }
+                    Some(None) => {
+                        ui.label(egui::RichText::new(tr("No alignment found")).weak());
+                    }
+                    None => {}
+                }
+            });
+        });
+
+    panel.open = open;
+    apply
+}
@@ -0,0 +1,190 @@
+use crate::ui::i18n::tr;
+use crate::ui::menu::MenuAction;
+use eframe::egui;
+use egui_phosphor::regular as icons;
+
+pub const COMMAND_PALETTE_SHORTCUT: egui::KeyboardShortcut =
+    egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::P);
+
+const MAX_RESULTS: usize = 50;
+const WIDTH: f32 = 520.0;
+
+pub enum PaletteCommand {
+    Menu(MenuAction),
+    PlotColumn(String, String),
+    ToggleScatter,
+    /// Moves the cursor to a time in seconds, e.g. of an annotation.
+    JumpTo(f32),
+}
+
+pub struct PaletteEntry {
+    pub label: String,
+    /// Kind of command, shown next to the label.
+    pub category: &'static str,
+    pub command: PaletteCommand,
+}
+
+impl PaletteEntry {
+    pub fn new(label: impl Into<String>, category: &'static str, command: PaletteCommand) -> Self {
+        Self {
+            label: label.into(),
+            category,
+            command,
+        }
+    }
+}
+
+/// Ctrl+P search over plotting, layout, navigation and menu commands.
+#[derive(Default)]
+pub struct CommandPalette {
+    pub open: bool,
+    query: String,
+    selected: usize,
+}
+
+impl CommandPalette {
+    pub fn show(&mut self) {
+        self.open = true;
+        self.query.clear();
+        self.selected = 0;
+    }
+}
+
+/// Draws the palette over `entries` and returns the command picked with Enter or a
+/// click.
+pub fn render_command_palette(
+    ctx: &egui::Context,
+    palette: &mut CommandPalette,
+    entries: Vec<PaletteEntry>,
+) -> Option<PaletteCommand> {
+    if !palette.open {
+        return None;
+    }
+
+    let mut matches: Vec<(i32, usize)> = entries
+        .iter()
+        .enumerate()
+        .filter_map(|(index, entry)| Some((fuzzy_score(&palette.query, &entry.label)?, index)))
+        .collect();
+    matches.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    matches.truncate(MAX_RESULTS);
+
+    let (up, down, enter, escape) = ctx.input_mut(|i| {
+        (
+            i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowUp),
+            i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown),
+            i.consume_key(egui::Modifiers::NONE, egui::Key::Enter),
+            i.consume_key(egui::Modifiers::NONE, egui::Key::Escape),
+        )
+    });
+    if down {
+        palette.selected += 1;
+    }
+    if up {
+        palette.selected = palette.selected.saturating_sub(1);
+    }
+    palette.selected = palette.selected.min(matches.len().saturating_sub(1));
+
+    let mut picked = enter
+        .then_some(palette.selected)
+        .filter(|_| !matches.is_empty());
+    let mut clicked_outside = false;
+
+    let response = egui::Window::new("command_palette")
+        .title_bar(false)
+        .resizable(false)
+        .fixed_size([WIDTH, 0.0])
+        .anchor(egui::Align2::CENTER_TOP, [0.0, 60.0])
+        .show(ctx, |ui| {
+            let query_changed = ui
+                .horizontal(|ui| {
+                    ui.label(icons::MAGNIFYING_GLASS);
+                    let edit = ui.add(
+                        egui::TextEdit::singleline(&mut palette.query)
+                            .hint_text(tr("Type a command, topic or layout..."))
+                            .desired_width(f32::INFINITY),
+                    );
+                    edit.request_focus();
+                    edit.changed()
+                })
+                .inner;
+            if query_changed {
+                palette.selected = 0;
+            }
+            ui.separator();
+
+            if matches.is_empty() {
+                ui.label(
+                    egui::RichText::new(tr("No matching commands"))
+                        .italics()
+                        .weak(),
+                );
+                return;
+            }
+            egui::ScrollArea::vertical()
+                .max_height(360.0)
+                .show(ui, |ui| {
+                    for (row, &(_, index)) in matches.iter().enumerate() {
+                        let entry = &entries[index];
+                        let is_selected = row == palette.selected;
+                        let response = ui
+                            .horizontal(|ui| {
+                                let label = ui.selectable_label(is_selected, &entry.label);
+                                ui.with_layout(
+                                    egui::Layout::right_to_left(egui::Align::Center),
+                                    |ui| ui.label(egui::RichText::new(tr(entry.category)).weak()),
+                                );
+                                label
+                            })
+                            .inner;
+                        if is_selected && (up || down) {
+                            response.scroll_to_me(None);
+                        }
+                        if response.clicked() {
+                            picked = Some(row);
+                        }
+                    }
+                });
+        });
+
+    if let Some(response) = response {
+        clicked_outside = response.response.clicked_elsewhere();
+    }
+
+    let command = picked
+        .and_then(|row| matches.get(row))
+        .map(|&(_, index)| index)
+        .and_then(|index| entries.into_iter().nth(index))
+        .map(|entry| entry.command);
+    if command.is_some() || escape || clicked_outside {
+        palette.open = false;
+    }
+    command
+}
+
+/// Score of `query` as a case-insensitive subsequence of `text`, higher for matches at
+/// word starts and for runs of consecutive characters. `None` if it does not match.
+fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let text: Vec<char> = text.chars().flat_map(char::to_lowercase).collect();
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous: Option<usize> = None;
+
+    for c in query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+    {
+        let found = (position..text.len()).find(|&i| text[i] == c)?;
+        score += 1;
+        if previous.is_some_and(|p| p + 1 == found) {
+            score += 5;
+        } else if found == 0 || !text[found - 1].is_alphanumeric() {
+            score += 3;
+        }
+        score -= (found - position).min(3) as i32;
+        previous = Some(found);
+        position = found + 1;
+    }
+    Some(score - text.len() as i32 / 16)
+}
@@ -0,0 +1,193 @@
+use super::TopicPanelSelection;
+use crate::core::DataStore;
+use crate::ui::i18n::tr;
+use eframe::egui;
+use egui_phosphor::regular as icons;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+const MAX_RECENT: usize = 10;
+
+/// Starred and recently plotted topic/column pairs, kept across sessions.
+#[derive(Default, Serialize, Deserialize)]
+pub struct TopicFavorites {
+    #[serde(default)]
+    pub starred: Vec<(String, String)>,
+    #[serde(default)]
+    pub recent: Vec<(String, String)>,
+    #[serde(skip)]
+    path: Option<PathBuf>,
+}
+
+impl TopicFavorites {
+    pub fn load() -> Self {
+        let Some(path) = favorites_path() else {
+            return Self::default();
+        };
+
+        let mut favorites = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|json| match serde_json::from_str::<Self>(&json) {
+                Ok(favorites) => Some(favorites),
+                Err(e) => {
+                    tracing::error!("Failed to parse favorites: {}", e);
+                    None
+                }
+            })
+            .unwrap_or_default();
+        favorites.path = Some(path);
+        favorites
+    }
+
+    fn save(&self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+
+        let result = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .map_err(|e| e.to_string())
+            .and_then(|_| serde_json::to_string_pretty(self).map_err(|e| e.to_string()))
+            .and_then(|json| std::fs::write(path, json).map_err(|e| e.to_string()));
+        if let Err(e) = result {
+            tracing::error!("Failed to save favorites: {}", e);
+        }
+    }
+
+    pub fn is_starred(&self, topic: &str, col: &str) -> bool {
+        self.starred.iter().any(|(t, c)| t == topic && c == col)
+    }
+
+    pub fn toggle_star(&mut self, topic: &str, col: &str) {
+        if self.is_starred(topic, col) {
+            self.starred.retain(|(t, c)| t != topic || c != col);
+        } else {
+            self.starred.push((topic.to_string(), col.to_string()));
+        }
+        self.save();
+    }
+
+    pub fn push_recent(&mut self, items: &[(String, String)]) {
+        if items.is_empty() {
+            return;
+        }
+
+        for item in items {
+            self.recent.retain(|existing| existing != item);
+            self.recent.insert(0, item.clone());
+        }
+        self.recent.truncate(MAX_RECENT);
+        self.save();
+    }
+}
+
+fn favorites_path() -> Option<PathBuf> {
+    directories::ProjectDirs::from("io", "tilak", "TiPlot")
+        .map(|dirs| dirs.config_dir().join("favorites.json"))
+}
+
+/// Draws the starred and recent sections at the top of the topic panel. Returns the
+/// entry that was double-clicked, if any.
+pub fn render_favorites(
+    ui: &mut egui::Ui,
+    favorites: &mut TopicFavorites,
+    data_store: &DataStore,
+    selection: &mut TopicPanelSelection,
+    dragged_item: &mut Option<(String, String)>,
+) -> Option<(String, String)> {
+    if favorites.starred.is_empty() && favorites.recent.is_empty() {
+        return None;
+    }
+
+    let mut unstar = None;
+    let mut plotted = None;
+
+    if !favorites.starred.is_empty() {
+        egui::CollapsingHeader::new(format!("{} {}", icons::STAR, tr("Starred")))
+            .id_salt("favorites_starred")
+            .default_open(true)
+            .show(ui, |ui| {
+                for (topic, col) in &favorites.starred {
+                    let response =
+                        render_entry(ui, data_store, selection, dragged_item, topic, col);
+                    response.context_menu(|ui| {
+                        if ui
+                            .button(format!("{} {}", icons::STAR, tr("Unstar")))
+                            .clicked()
+                        {
+                            unstar = Some((topic.clone(), col.clone()));
+                            ui.close_menu();
+                        }
+                    });
+                }
+            });
+    }
+
+    if !favorites.recent.is_empty() {
+        egui::CollapsingHeader::new(format!(
+            "{} {}",
+            icons::CLOCK_COUNTER_CLOCKWISE,
+            tr("Recent")
+        ))
+        .id_salt("favorites_recent")
+        .default_open(false)
+        .show(ui, |ui| {
+            for (topic, col) in &favorites.recent {
+                let response = render_entry(ui, data_store, selection, dragged_item, topic, col);
+                if response.double_clicked() && data_store.get_column(topic, col).is_some() {
+                    plotted = Some((topic.clone(), col.clone()));
+                }
+            }
+        });
+    }
+
+    if let Some((topic, col)) = unstar {
+        favorites.toggle_star(&topic, &col);
+    }
+
+    ui.separator();
+    plotted
+}
+
+fn render_entry(
+    ui: &mut egui::Ui,
+    data_store: &DataStore,
+    selection: &mut TopicPanelSelection,
+    dragged_item: &mut Option<(String, String)>,
+    topic: &str,
+    col: &str,
+) -> egui::Response {
+    let available = data_store.get_column(topic, col).is_some();
+    let is_selected = selection
+        .selected
+        .contains(&(topic.to_string(), col.to_string()));
+
+    let label = format!("{}/{}", topic, col);
+    let text = if available {
+        egui::RichText::new(label)
+    } else {
+        egui::RichText::new(label).weak()
+    };
+
+    let mut response = ui.selectable_label(is_selected, text);
+    if available {
+        response = response.interact(egui::Sense::drag());
+    }
+
+    if response.clicked() && available {
+        selection.clear();
+        selection.select(topic, col);
+    }
+
+    if response.dragged() {
+        *dragged_item = Some((topic.to_string(), col.to_string()));
+        ui.output_mut(|o| o.cursor_icon = egui::CursorIcon::Grabbing);
+    }
+
+    if available {
+        response.on_hover_text(tr("Drag or double-click to add to a plot"))
+    } else {
+        response.on_hover_text(tr("Not present in the loaded data"))
+    }
+}
@@ -0,0 +1,230 @@
+use crate::ui::i18n::tr;
+use eframe::egui;
+use egui_phosphor::regular as icons;
+use std::collections::{BTreeMap, VecDeque};
+use std::time::{Duration, Instant};
+
+/// Seconds of history kept for each topic's sparkline.
+const HISTORY_SECS: usize = 30;
+/// Seconds the message rate is averaged over.
+const RATE_SECS: usize = 5;
+
+const STALLED_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 80, 80);
+const HEALTHY_COLOR: egui::Color32 = egui::Color32::from_rgb(100, 200, 100);
+
+struct TopicHealth {
+    last_received: Instant,
+    /// Rows received in each second of the history, the current second last.
+    buckets: VecDeque<u32>,
+    bucket_start: Instant,
+    total: u64,
+}
+
+impl TopicHealth {
+    fn new(now: Instant) -> Self {
+        Self {
+            last_received: now,
+            buckets: VecDeque::from([0]),
+            bucket_start: now,
+            total: 0,
+        }
+    }
+
+    /// Starts a new bucket for every whole second passed since the current one began.
+    fn advance(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.bucket_start).as_secs() as usize;
+        if elapsed == 0 {
+            return;
+        }
+        for _ in 0..elapsed.min(HISTORY_SECS + 1) {
+            self.buckets.push_back(0);
+        }
+        while self.buckets.len() > HISTORY_SECS + 1 {
+            self.buckets.pop_front();
+        }
+        self.bucket_start += Duration::from_secs(elapsed as u64);
+    }
+
+    fn record(&mut self, rows: usize, now: Instant) {
+        self.advance(now);
+        if let Some(current) = self.buckets.back_mut() {
+            *current += rows as u32;
+        }
+        self.last_received = now;
+        self.total += rows as u64;
+    }
+
+    /// Completed seconds of history, oldest first.
+    fn history(&self) -> impl DoubleEndedIterator<Item = u32> + '_ {
+        self.buckets.iter().take(self.buckets.len() - 1).copied()
+    }
+
+    /// Messages per second over the last few completed seconds.
+    fn rate(&self) -> f32 {
+        let recent: Vec<u32> = self.history().rev().take(RATE_SECS).collect();
+        if recent.is_empty() {
+            return 0.0;
+        }
+        recent.iter().sum::<u32>() as f32 / recent.len() as f32
+    }
+}
+
+/// Message rate and last-received age of every topic received live.
+pub struct HealthPanel {
+    pub open: bool,
+    topics: BTreeMap<String, TopicHealth>,
+    /// Topics silent for longer than this are shown as stalled.
+    stall_secs: f32,
+}
+
+impl HealthPanel {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            topics: BTreeMap::new(),
+            stall_secs: 2.0,
+        }
+    }
+
+    pub fn record(&mut self, topic: &str, rows: usize) {
+        let now = Instant::now();
+        self.topics
+            .entry(topic.to_string())
+            .or_insert_with(|| TopicHealth::new(now))
+            .record(rows, now);
+    }
+
+    pub fn clear(&mut self) {
+        self.topics.clear();
+    }
+}
+
+impl Default for HealthPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn sparkline(ui: &mut egui::Ui, history: &[u32], color: egui::Color32) {
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(120.0, 16.0), egui::Sense::hover());
+    let max = history.iter().copied().max().unwrap_or(0).max(1) as f32;
+    let step = rect.width() / (HISTORY_SECS - 1) as f32;
+    // Right-aligned, so a topic seen for only a few seconds fills the newest part.
+    let offset = HISTORY_SECS.saturating_sub(history.len()) as f32 * step;
+
+    let points: Vec<egui::Pos2> = history
+        .iter()
+        .enumerate()
+        .map(|(i, &count)| {
+            egui::pos2(
+                rect.left() + offset + i as f32 * step,
+                rect.bottom() - count as f32 / max * rect.height(),
+            )
+        })
+        .collect();
+    ui.painter().line_segment(
+        [rect.left_bottom(), rect.right_bottom()],
+        egui::Stroke::new(1.0, ui.visuals().weak_text_color()),
+    );
+    if points.len() > 1 {
+        ui.painter()
+            .add(egui::Shape::line(points, egui::Stroke::new(1.5, color)));
+    }
+}
+
+fn format_age(age: Duration) -> String {
+    let secs = age.as_secs_f32();
+    if secs < 1.0 {
+        format!("{:.0} ms", secs * 1000.0)
+    } else if secs < 120.0 {
+        format!("{:.1} s", secs)
+    } else {
+        format!("{:.0} min", secs / 60.0)
+    }
+}
+
+pub fn render_health_panel(ctx: &egui::Context, panel: &mut HealthPanel) {
+    if !panel.open {
+        return;
+    }
+
+    let now = Instant::now();
+    for health in panel.topics.values_mut() {
+        health.advance(now);
+    }
+
+    let mut open = panel.open;
+    egui::Window::new(tr("Live Status"))
+        .open(&mut open)
+        .default_width(460.0)
+        .resizable(true)
+        .collapsible(false)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(tr("Stalled after"));
+                ui.add(
+                    egui::DragValue::new(&mut panel.stall_secs)
+                        .speed(0.1)
+                        .range(0.1..=600.0)
+                        .suffix(" s"),
+                );
+                if ui
+                    .button(format!("{} {}", icons::ERASER, tr("Reset")))
+                    .clicked()
+                {
+                    panel.clear();
+                }
+            });
+            ui.separator();
+
+            if panel.topics.is_empty() {
+                ui.label(egui::RichText::new(tr("No live data received yet")).weak());
+                return;
+            }
+
+            let stalled = panel
+                .topics
+                .values()
+                .filter(|h| now.duration_since(h.last_received).as_secs_f32() > panel.stall_secs)
+                .count();
+            if stalled > 0 {
+                ui.colored_label(
+                    STALLED_COLOR,
+                    format!("{} {} {}", icons::WARNING, stalled, tr("topics stalled")),
+                );
+            }
+
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                egui::Grid::new("live_status_grid")
+                    .num_columns(5)
+                    .striped(true)
+                    .spacing([12.0, 4.0])
+                    .show(ui, |ui| {
+                        ui.strong(tr("Topic"));
+                        ui.strong(tr("Rate"));
+                        ui.strong(tr("Last"));
+                        ui.strong(tr("History"));
+                        ui.strong(tr("Total"));
+                        ui.end_row();
+
+                        for (topic, health) in &panel.topics {
+                            let age = now.duration_since(health.last_received);
+                            let color = if age.as_secs_f32() > panel.stall_secs {
+                                STALLED_COLOR
+                            } else {
+                                HEALTHY_COLOR
+                            };
+
+                            ui.colored_label(color, topic);
+                            ui.label(format!("{:.1} /s", health.rate()));
+                            ui.colored_label(color, format_age(age));
+                            let history: Vec<u32> = health.history().collect();
+                            sparkline(ui, &history, color);
+                            ui.label(health.total.to_string());
+                            ui.end_row();
+                        }
+                    });
+            });
+        });
+    panel.open = open;
+}
@@ -0,0 +1,280 @@
+use crate::core::{parameter_changes, LogInfo};
+use crate::ui::i18n::tr;
+use eframe::egui;
+use egui_phosphor::regular as icons;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Which parameters the Log Info window lists.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ParameterView {
+    #[default]
+    All,
+    /// Parameters set to something other than their firmware default.
+    ChangedFromDefaults,
+    /// Parameters that differ from those of another log.
+    ComparedWithLog,
+}
+
+/// Version details and parameters from the header of the loaded log.
+#[derive(Default)]
+pub struct LogInfoPanel {
+    pub open: bool,
+    pub filter: String,
+    pub view: ParameterView,
+    /// File name and log info of the log compared against.
+    reference: Option<(String, LogInfo)>,
+}
+
+impl LogInfoPanel {
+    fn pick_reference(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("TiPlot Data or Log Info", &["arrow", "json"])
+            .pick_file()
+        else {
+            return;
+        };
+        match LogInfo::read(&path) {
+            Ok(info) if info.parameters.is_empty() => {
+                tracing::warn!("{} has no parameters to compare", path.display());
+            }
+            Ok(info) => {
+                self.reference = Some((file_name(&path), info));
+                self.view = ParameterView::ComparedWithLog;
+            }
+            Err(e) => tracing::error!("Failed to read log info: {}", e),
+        }
+    }
+}
+
+pub fn render_log_info_window(ctx: &egui::Context, panel: &mut LogInfoPanel, info: &LogInfo) {
+    if !panel.open {
+        return;
+    }
+
+    let mut open = panel.open;
+
+    egui::Window::new(tr("Log Info"))
+        .open(&mut open)
+        .default_width(500.0)
+        .default_height(500.0)
+        .resizable(true)
+        .collapsible(false)
+        .show(ctx, |ui| {
+            if info.is_empty() {
+                ui.label(
+                    egui::RichText::new(tr("The loaded log has no version details or parameters"))
+                        .italics()
+                        .weak(),
+                );
+                return;
+            }
+
+            ui.horizontal(|ui| {
+                ui.label(tr("Filter:"));
+                ui.text_edit_singleline(&mut panel.filter);
+                if ui.button("✖").clicked() {
+                    panel.filter.clear();
+                }
+                if ui
+                    .button(format!("{} {}", icons::EXPORT, tr("Export...")))
+                    .on_hover_text(tr("Save the version details and parameters as JSON"))
+                    .clicked()
+                {
+                    export(info);
+                }
+            });
+            ui.separator();
+
+            ui.horizontal(|ui| {
+                ui.label(tr("Parameters:"));
+                ui.selectable_value(&mut panel.view, ParameterView::All, tr("All"));
+                ui.add_enabled_ui(!info.parameter_defaults.is_empty(), |ui| {
+                    ui.selectable_value(
+                        &mut panel.view,
+                        ParameterView::ChangedFromDefaults,
+                        tr("Changed from Defaults"),
+                    )
+                    .on_disabled_hover_text(tr("The log did not record parameter defaults"));
+                });
+                let compared = panel.reference.is_some();
+                ui.add_enabled_ui(compared, |ui| {
+                    ui.selectable_value(
+                        &mut panel.view,
+                        ParameterView::ComparedWithLog,
+                        tr("Compared with Log"),
+                    );
+                });
+                if ui
+                    .button(format!("{} {}", icons::FOLDER_OPEN, tr("Compare with...")))
+                    .on_hover_text(tr(
+                        "Pick a saved data file or Log Info export to compare parameters with",
+                    ))
+                    .clicked()
+                {
+                    panel.pick_reference();
+                }
+            });
+            ui.separator();
+
+            let filter = panel.filter.to_lowercase();
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                entry_grid(
+                    ui,
+                    tr("Version"),
+                    "log_version_grid",
+                    &info.version_info,
+                    &filter,
+                );
+                match (panel.view, &panel.reference) {
+                    (ParameterView::ChangedFromDefaults, _)
+                        if !info.parameter_defaults.is_empty() =>
+                    {
+                        diff_grid(
+                            ui,
+                            tr("Changed from Defaults"),
+                            tr("Default"),
+                            &info.parameters,
+                            &info.parameter_defaults,
+                            true,
+                            &filter,
+                        );
+                    }
+                    (ParameterView::ComparedWithLog, Some((name, reference))) => {
+                        diff_grid(
+                            ui,
+                            &format!("{} {}", tr("Differences from"), name),
+                            name,
+                            &info.parameters,
+                            &reference.parameters,
+                            false,
+                            &filter,
+                        );
+                    }
+                    _ => entry_grid(
+                        ui,
+                        tr("Parameters"),
+                        "log_parameter_grid",
+                        &info.parameters,
+                        &filter,
+                    ),
+                }
+            });
+        });
+
+    panel.open = open;
+}
+
+/// Collapsible table of the entries whose key or value contains `filter`.
+fn entry_grid(
+    ui: &mut egui::Ui,
+    heading: &str,
+    id: &str,
+    entries: &BTreeMap<String, String>,
+    filter: &str,
+) {
+    let rows: Vec<_> = entries
+        .iter()
+        .filter(|(key, value)| {
+            filter.is_empty()
+                || key.to_lowercase().contains(filter)
+                || value.to_lowercase().contains(filter)
+        })
+        .collect();
+
+    egui::CollapsingHeader::new(format!("{} ({})", heading, rows.len()))
+        .id_salt(id)
+        .default_open(true)
+        .show(ui, |ui| {
+            egui::Grid::new(id)
+                .num_columns(2)
+                .striped(true)
+                .spacing([10.0, 4.0])
+                .show(ui, |ui| {
+                    for (key, value) in rows {
+                        ui.label(key);
+                        ui.label(egui::RichText::new(value).monospace());
+                        ui.end_row();
+                    }
+                });
+        });
+}
+
+/// Collapsible table of the parameters that differ between `values` and `reference`
+/// and whose name contains `filter`. With `shared_only`, parameters missing from
+/// `reference` are left out, as defaults are often only known for some of them.
+fn diff_grid(
+    ui: &mut egui::Ui,
+    heading: &str,
+    reference_label: &str,
+    values: &BTreeMap<String, String>,
+    reference: &BTreeMap<String, String>,
+    shared_only: bool,
+    filter: &str,
+) {
+    let changes: Vec<_> = parameter_changes(values, reference)
+        .into_iter()
+        .filter(|change| !shared_only || change.reference.is_some())
+        .filter(|change| filter.is_empty() || change.name.to_lowercase().contains(filter))
+        .collect();
+
+    egui::CollapsingHeader::new(format!("{} ({})", heading, changes.len()))
+        .id_salt("log_parameter_diff")
+        .default_open(true)
+        .show(ui, |ui| {
+            if changes.is_empty() {
+                ui.label(egui::RichText::new(tr("No differences")).italics().weak());
+                return;
+            }
+            egui::Grid::new("log_parameter_diff_grid")
+                .num_columns(3)
+                .striped(true)
+                .spacing([10.0, 4.0])
+                .show(ui, |ui| {
+                    ui.strong(tr("Parameter"));
+                    ui.strong(tr("This Log"));
+                    ui.strong(reference_label);
+                    ui.end_row();
+
+                    let missing = || egui::RichText::new("—").weak();
+                    for change in changes {
+                        ui.label(change.name);
+                        match change.value {
+                            Some(value) => {
+                                ui.label(egui::RichText::new(value).monospace().strong())
+                            }
+                            None => ui.label(missing()),
+                        };
+                        match change.reference {
+                            Some(value) => ui.label(egui::RichText::new(value).monospace()),
+                            None => ui.label(missing()),
+                        };
+                        ui.end_row();
+                    }
+                });
+        });
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name().map_or_else(
+        || path.display().to_string(),
+        |n| n.to_string_lossy().into_owned(),
+    )
+}
+
+fn export(info: &LogInfo) {
+    let Some(path) = rfd::FileDialog::new()
+        .add_filter("JSON", &["json"])
+        .set_file_name("log_info.json")
+        .save_file()
+    else {
+        return;
+    };
+    let result = serde_json::to_string_pretty(info)
+        .map_err(|e| e.to_string())
+        .and_then(|json| std::fs::write(&path, json).map_err(|e| e.to_string()));
+    match result {
+        Ok(()) => tracing::info!("Exported log info to {}", path.display()),
+        Err(e) => tracing::error!("Failed to export log info: {}", e),
+    }
+}
@@ -0,0 +1,114 @@
+use crate::core::logging::{self, LogRecord};
+use crate::ui::i18n::tr;
+use eframe::egui;
+use egui_phosphor::regular as icons;
+use tracing::Level;
+
+/// Console docked above the timeline listing warnings and errors, so problems can be
+/// reported without a terminal attached.
+pub struct LogPanel {
+    pub open: bool,
+    show_info: bool,
+    /// Problem count when the console was last looked at.
+    seen_problems: usize,
+}
+
+impl LogPanel {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            show_info: false,
+            seen_problems: 0,
+        }
+    }
+
+    /// Warnings and errors logged since the console was last open.
+    pub fn unseen_problems(&self) -> usize {
+        logging::problem_count().saturating_sub(self.seen_problems)
+    }
+
+    fn shows(&self, record: &LogRecord) -> bool {
+        record.level <= Level::WARN || (self.show_info && record.level == Level::INFO)
+    }
+}
+
+impl Default for LogPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn level_color(level: Level) -> egui::Color32 {
+    match level {
+        Level::ERROR => egui::Color32::from_rgb(230, 90, 90),
+        Level::WARN => egui::Color32::from_rgb(230, 180, 50),
+        Level::INFO => egui::Color32::from_gray(190),
+        _ => egui::Color32::from_gray(130),
+    }
+}
+
+pub fn render_log_panel(ctx: &egui::Context, panel: &mut LogPanel) {
+    if !panel.open {
+        return;
+    }
+    panel.seen_problems = logging::problem_count();
+
+    let records: Vec<LogRecord> = logging::records()
+        .into_iter()
+        .filter(|r| panel.shows(r))
+        .collect();
+
+    egui::TopBottomPanel::bottom("log_console")
+        .resizable(true)
+        .default_height(160.0)
+        .min_height(80.0)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.strong(format!("{} {}", icons::TERMINAL, tr("Log Console")));
+                ui.checkbox(&mut panel.show_info, tr("Show info"));
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.button(icons::X).on_hover_text(tr("Close")).clicked() {
+                        panel.open = false;
+                    }
+                    if ui
+                        .button(format!("{} {}", icons::TRASH, tr("Clear")))
+                        .clicked()
+                    {
+                        logging::clear();
+                    }
+                    if ui
+                        .add_enabled(
+                            !records.is_empty(),
+                            egui::Button::new(format!("{} {}", icons::COPY, tr("Copy"))),
+                        )
+                        .on_hover_text(tr("Copy the shown messages to the clipboard"))
+                        .clicked()
+                    {
+                        let text: Vec<String> = records.iter().map(LogRecord::format).collect();
+                        ui.ctx().copy_text(text.join("\n"));
+                    }
+                });
+            });
+            ui.separator();
+
+            if records.is_empty() {
+                ui.weak(tr("No warnings or errors"));
+                return;
+            }
+
+            let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+            egui::ScrollArea::both()
+                .auto_shrink([false, false])
+                .stick_to_bottom(true)
+                .show_rows(ui, row_height, records.len(), |ui, range| {
+                    for record in &records[range] {
+                        ui.label(
+                            egui::RichText::new(record.format())
+                                .monospace()
+                                .color(level_color(record.level)),
+                        );
+                    }
+                });
+        });
+}
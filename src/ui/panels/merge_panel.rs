@@ -0,0 +1,103 @@
+use crate::acquisition::{MergeAlignment, MergeOptions};
+use crate::ui::i18n::tr;
+use eframe::egui;
+use egui_phosphor::regular as icons;
+use std::path::PathBuf;
+
+/// Options for merging a file into the loaded data, shown after the file is picked.
+#[derive(Default)]
+pub struct MergePanel {
+    pub open: bool,
+    path: Option<PathBuf>,
+    options: MergeOptions,
+}
+
+impl MergePanel {
+    pub fn open_for(&mut self, path: PathBuf) {
+        self.path = Some(path);
+        self.open = true;
+    }
+}
+
+pub fn render_merge_panel(
+    ctx: &egui::Context,
+    panel: &mut MergePanel,
+    has_data: bool,
+) -> Option<(PathBuf, MergeOptions)> {
+    if !panel.open {
+        return None;
+    }
+    let path = panel.path.clone()?;
+
+    let mut open = panel.open;
+    let mut merge = None;
+    egui::Window::new(tr("Load and Merge"))
+        .open(&mut open)
+        .resizable(false)
+        .collapsible(false)
+        .show(ctx, |ui| {
+            ui.label(
+                egui::RichText::new(path.display().to_string())
+                    .monospace()
+                    .weak(),
+            );
+            ui.separator();
+
+            let options = &mut panel.options;
+            egui::Grid::new("merge_grid")
+                .num_columns(2)
+                .spacing([12.0, 6.0])
+                .show(ui, |ui| {
+                    ui.label(tr("Topic Prefix"));
+                    ui.add(
+                        egui::TextEdit::singleline(&mut options.prefix)
+                            .hint_text("run2_")
+                            .desired_width(140.0),
+                    );
+                    ui.end_row();
+
+                    ui.label(tr("Time"));
+                    egui::ComboBox::from_id_salt("merge_alignment")
+                        .selected_text(tr(options.alignment.label()))
+                        .show_ui(ui, |ui| {
+                            for alignment in MergeAlignment::ALL {
+                                ui.selectable_value(
+                                    &mut options.alignment,
+                                    alignment,
+                                    tr(alignment.label()),
+                                );
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label(tr("Offset"));
+                    ui.add(
+                        egui::DragValue::new(&mut options.offset)
+                            .speed(0.1)
+                            .suffix(" s"),
+                    );
+                    ui.end_row();
+                });
+
+            if options.prefix.is_empty() && has_data {
+                ui.label(
+                    egui::RichText::new(tr(
+                        "Topics already loaded get the file's samples appended",
+                    ))
+                    .small()
+                    .weak(),
+                );
+            }
+            ui.separator();
+
+            if ui
+                .button(format!("{} {}", icons::GIT_MERGE, tr("Merge")))
+                .clicked()
+            {
+                merge = Some((path.clone(), options.clone()));
+            }
+        });
+
+    panel.open = open && merge.is_none();
+    merge
+}
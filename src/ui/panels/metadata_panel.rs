@@ -0,0 +1,153 @@
+use crate::core::{DataStore, Notation, ValueFormat};
+use crate::ui::i18n::tr;
+use eframe::egui;
+
+#[derive(Default)]
+pub struct MetadataPanel {
+    pub open: bool,
+    pub filter: String,
+}
+
+pub fn render_metadata_window(
+    ctx: &egui::Context,
+    panel: &mut MetadataPanel,
+    data_store: &mut DataStore,
+) {
+    if !panel.open {
+        return;
+    }
+
+    let mut open = panel.open;
+
+    egui::Window::new(tr("Column Metadata"))
+        .open(&mut open)
+        .default_width(600.0)
+        .default_height(500.0)
+        .resizable(true)
+        .collapsible(false)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(tr("Filter:"));
+                ui.text_edit_singleline(&mut panel.filter);
+                if ui.button("✖").clicked() {
+                    panel.filter.clear();
+                }
+            });
+            ui.separator();
+
+            let filter = panel.filter.to_lowercase();
+            let rows: Vec<(String, String)> = data_store
+                .get_topics()
+                .into_iter()
+                .flat_map(|topic| {
+                    data_store
+                        .get_columns(topic)
+                        .into_iter()
+                        .map(move |col| (topic.clone(), col.clone()))
+                })
+                .filter(|(topic, col)| {
+                    filter.is_empty()
+                        || format!("{}/{}", topic, col)
+                            .to_lowercase()
+                            .contains(&filter)
+                })
+                .collect();
+
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                egui::Grid::new("column_metadata_grid")
+                    .num_columns(5)
+                    .striped(true)
+                    .spacing([10.0, 4.0])
+                    .show(ui, |ui| {
+                        ui.strong(tr("Column"));
+                        ui.strong(tr("Unit"));
+                        ui.strong(tr("Scale"));
+                        ui.strong(tr("Description"));
+                        ui.strong(tr("Format"));
+                        ui.end_row();
+
+                        for (topic, col) in rows {
+                            ui.label(format!("{}/{}", topic, col));
+
+                            let meta = data_store.column_meta_mut(&topic, &col);
+                            ui.add(egui::TextEdit::singleline(&mut meta.unit).desired_width(60.0));
+                            ui.add(egui::DragValue::new(&mut meta.scale).speed(0.01));
+                            ui.add(
+                                egui::TextEdit::singleline(&mut meta.description)
+                                    .desired_width(220.0),
+                            );
+                            let unit = Some(meta.unit.as_str()).filter(|u| !u.is_empty());
+                            ui.menu_button(meta.format.format(FORMAT_PREVIEW, unit), |ui| {
+                                value_format_editor(
+                                    ui,
+                                    egui::Id::new(("column_format", &topic, &col)),
+                                    &mut meta.format,
+                                );
+                            });
+                            ui.end_row();
+                        }
+                    });
+            });
+        });
+
+    // Editing creates entries for every visible row; keep only meaningful ones.
+    data_store.prune_column_meta();
+    panel.open = open;
+}
+
+/// Sample value shown to preview a format.
+const FORMAT_PREVIEW: f32 = 1234.5678;
+
+/// Controls of a value format. Returns whether it changed.
+pub fn value_format_editor(ui: &mut egui::Ui, id: egui::Id, format: &mut ValueFormat) -> bool {
+    let mut changed = false;
+    egui::Grid::new(id)
+        .num_columns(2)
+        .spacing([12.0, 4.0])
+        .show(ui, |ui| {
+            ui.label(tr("Notation"));
+            egui::ComboBox::from_id_salt(id.with("notation"))
+                .selected_text(tr(format.notation.label()))
+                .show_ui(ui, |ui| {
+                    for notation in Notation::ALL {
+                        changed |= ui
+                            .selectable_value(&mut format.notation, notation, tr(notation.label()))
+                            .changed();
+                    }
+                });
+            ui.end_row();
+
+            ui.label(tr("Digits"));
+            changed |= ui
+                .add(egui::DragValue::new(&mut format.digits).range(0..=12))
+                .changed();
+            ui.end_row();
+
+            ui.label(tr("Unit Suffix"));
+            changed |= ui.checkbox(&mut format.show_unit, "").changed();
+            ui.end_row();
+
+            // The text is kept while editing so partial entries are not reformatted away.
+            ui.label(tr("Value Labels"));
+            let text_id = id.with("labels");
+            let mut text = ui
+                .data(|d| d.get_temp::<String>(text_id))
+                .unwrap_or_else(|| format.labels_text());
+            let response = ui.add(
+                egui::TextEdit::singleline(&mut text)
+                    .hint_text("0=Disarmed, 1=Armed")
+                    .desired_width(200.0),
+            );
+            if response.changed() {
+                format.set_labels_text(&text);
+                changed = true;
+            }
+            if response.has_focus() {
+                ui.data_mut(|d| d.insert_temp(text_id, text));
+            } else {
+                ui.data_mut(|d| d.remove::<String>(text_id));
+            }
+            ui.end_row();
+        });
+    changed
+}
@@ -1,8 +1,47 @@
+pub mod alias_panel;
+pub mod align_panel;
+pub mod command_palette;
+pub mod favorites;
+pub mod health_panel;
+pub mod log_info_panel;
+pub mod log_panel;
+pub mod merge_panel;
+pub mod metadata_panel;
+pub mod power_panel;
+pub mod profiler_panel;
+pub mod recent_files;
+pub mod script_panel;
+pub mod stream_panel;
+pub mod synthetic_panel;
 pub mod tabs;
 pub mod timeline_panel;
 pub mod topic_panel;
+pub mod trigger_panel;
 pub mod view3d_panel;
+pub mod welcome_panel;
 
-pub use timeline_panel::render_timeline;
-pub use topic_panel::{render_topic_panel, TopicPanelSelection};
+pub use alias_panel::{render_alias_panel, AliasPanel};
+pub use align_panel::{render_align_panel, AlignPanel};
+pub use command_palette::{
+    render_command_palette, CommandPalette, PaletteCommand, PaletteEntry, COMMAND_PALETTE_SHORTCUT,
+};
+pub use favorites::TopicFavorites;
+pub use health_panel::{render_health_panel, HealthPanel};
+pub use log_info_panel::{render_log_info_window, LogInfoPanel};
+pub use log_panel::{render_log_panel, LogPanel};
+pub use merge_panel::{render_merge_panel, MergePanel};
+pub use metadata_panel::{render_metadata_window, value_format_editor, MetadataPanel};
+pub use power_panel::{render_power_panel, PowerPanel};
+pub use profiler_panel::{render_profiler_overlay, FrameProfiler, ProfileSection, TopicMemory};
+pub use recent_files::RecentFiles;
+pub use script_panel::{render_script_panel, ScriptPanel};
+pub use stream_panel::{render_stream_panel, StreamAction, StreamPanel};
+pub use synthetic_panel::{render_synthetic_panel, SyntheticAction, SyntheticPanel};
+pub use timeline_panel::{
+    apply_speed_preset, render_timeline, step_time, ActivityStrip, JumpTarget, LoopRegion,
+    PlaybackMode, SPEED_PRESETS,
+};
+pub use topic_panel::{render_topic_panel, TopicAction, TopicPanelSelection};
+pub use trigger_panel::{render_trigger_panel, TriggerPanel};
 pub use view3d_panel::{render_config_window, render_view3d_panel, View3DPanel};
+pub use welcome_panel::render_welcome;
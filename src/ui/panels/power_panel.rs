@@ -0,0 +1,375 @@
+use crate::core::DataStore;
+use crate::ui::i18n::tr;
+use eframe::egui;
+use egui_phosphor::regular as icons;
+
+/// Battery topics recognised per autopilot.
+const BATTERY_SOURCES: [BatterySource; 2] = [
+    BatterySource {
+        topic: "battery_status",
+        voltage: "voltage_v",
+        current: "current_a",
+        remaining: Some(("remaining", 100.0)),
+        capacity: Some("capacity"),
+    },
+    BatterySource {
+        topic: "bat",
+        voltage: "Volt",
+        current: "Curr",
+        remaining: Some(("RemPct", 1.0)),
+        capacity: None,
+    },
+];
+
+/// Share of samples with the lowest and highest current compared to find the voltage sag.
+const LOAD_QUANTILE: f32 = 0.1;
+
+struct BatterySource {
+    topic: &'static str,
+    voltage: &'static str,
+    current: &'static str,
+    /// Remaining charge column and the factor taking it into percent.
+    remaining: Option<(&'static str, f32)>,
+    /// Column with the pack capacity in mAh.
+    capacity: Option<&'static str>,
+}
+
+/// Energy and load figures of a battery over a time window.
+pub struct PowerSummary {
+    pub topic: String,
+    pub start: f32,
+    pub end: f32,
+    pub energy_wh: f32,
+    pub charge_mah: f32,
+    pub avg_current: f32,
+    pub peak_current: f32,
+    pub min_voltage: f32,
+    pub start_voltage: f32,
+    pub end_voltage: f32,
+    /// Voltage at light load minus voltage at heavy load.
+    pub sag: f32,
+    /// Sag divided by the current difference it was measured over.
+    pub resistance_mohm: Option<f32>,
+    /// Remaining charge at the end of the window as logged by the autopilot, in percent.
+    pub logged_remaining: Option<f32>,
+    /// Capacity left at the end of the window, from the configured capacity and the charge
+    /// drawn since the start of the log.
+    pub remaining_mah: Option<f32>,
+    /// Flight time `remaining_mah` lasts at the window's average current.
+    pub remaining_secs: Option<f32>,
+}
+
+impl PowerSummary {
+    pub fn compute(
+        data_store: &DataStore,
+        panel: &PowerPanel,
+        start: f32,
+        end: f32,
+    ) -> Option<Self> {
+        let times = data_store.get_column(&panel.topic, "timestamp")?;
+        let voltage = data_store.get_column(&panel.topic, &panel.voltage)?;
+        let current = data_store.get_column(&panel.topic, &panel.current)?;
+        let len = times.len().min(voltage.len()).min(current.len());
+        let first = times[..len].partition_point(|&t| t < start);
+        let last = times[..len].partition_point(|&t| t <= end);
+
+        let samples: Vec<(f32, f32, f32)> = (first..last)
+            .map(|i| (times[i], voltage[i], current[i]))
+            .filter(|(_, v, i)| v.is_finite() && i.is_finite())
+            .collect();
+        let (&(t0, v0, _), &(t1, v1, _)) = (samples.first()?, samples.last()?);
+        let duration = t1 - t0;
+        if duration <= 0.0 {
+            return None;
+        }
+
+        let mut energy_ws = 0.0f64;
+        let mut charge_as = 0.0f64;
+        for pair in samples.windows(2) {
+            let ((ta, va, ia), (tb, vb, ib)) = (pair[0], pair[1]);
+            let dt = (tb - ta) as f64;
+            energy_ws += (va * ia + vb * ib) as f64 / 2.0 * dt;
+            charge_as += (ia + ib) as f64 / 2.0 * dt;
+        }
+        let avg_current = (charge_as / duration as f64) as f32;
+
+        let mut by_load = samples.clone();
+        by_load.sort_by(|a, b| a.2.total_cmp(&b.2));
+        let n = ((by_load.len() as f32 * LOAD_QUANTILE).ceil() as usize).max(1);
+        let mean = |s: &[(f32, f32, f32)], f: fn(&(f32, f32, f32)) -> f32| {
+            s.iter().map(f).sum::<f32>() / s.len() as f32
+        };
+        let (light, heavy) = (&by_load[..n], &by_load[by_load.len() - n..]);
+        let sag = mean(light, |s| s.1) - mean(heavy, |s| s.1);
+        let load_delta = mean(heavy, |s| s.2) - mean(light, |s| s.2);
+        let resistance_mohm = (load_delta > 0.1).then(|| sag / load_delta * 1000.0);
+
+        let source = BATTERY_SOURCES.iter().find(|s| s.topic == panel.topic);
+        let logged_remaining = source.and_then(|s| s.remaining).and_then(|(col, factor)| {
+            let values = data_store.get_column(&panel.topic, col)?;
+            let value = values[first..last.min(values.len())]
+                .iter()
+                .rev()
+                .find(|v| v.is_finite())?;
+            Some(value * factor)
+        });
+
+        let remaining_mah = (panel.capacity_mah > 0.0).then(|| {
+            let used: f64 = (1..last)
+                .map(|i| {
+                    (current[i - 1] + current[i]) as f64 / 2.0 * (times[i] - times[i - 1]) as f64
+                })
+                .filter(|charge| charge.is_finite())
+                .sum();
+            (panel.capacity_mah - (used / 3.6) as f32).max(0.0)
+        });
+        let remaining_secs = remaining_mah
+            .filter(|_| avg_current > 0.0)
+            .map(|mah| mah * 3.6 / avg_current);
+
+        Some(Self {
+            topic: panel.topic.clone(),
+            start: t0,
+            end: t1,
+            energy_wh: (energy_ws / 3600.0) as f32,
+            charge_mah: (charge_as / 3.6) as f32,
+            avg_current,
+            peak_current: samples.iter().map(|s| s.2).fold(f32::MIN, f32::max),
+            min_voltage: samples.iter().map(|s| s.1).fold(f32::MAX, f32::min),
+            start_voltage: v0,
+            end_voltage: v1,
+            sag,
+            resistance_mohm,
+            logged_remaining,
+            remaining_mah,
+            remaining_secs,
+        })
+    }
+
+    /// Label and value of every figure, in display order.
+    fn rows(&self) -> Vec<(&'static str, String)> {
+        let mut rows = vec![
+            ("Duration", format!("{:.1} s", self.end - self.start)),
+            ("Energy", format!("{:.2} Wh", self.energy_wh)),
+            ("Charge", format!("{:.0} mAh", self.charge_mah)),
+            ("Average current", format!("{:.2} A", self.avg_current)),
+            ("Peak current", format!("{:.2} A", self.peak_current)),
+            (
+                "Voltage",
+                format!(
+                    "{:.2} V → {:.2} V (min {:.2} V)",
+                    self.start_voltage, self.end_voltage, self.min_voltage
+                ),
+            ),
+            ("Voltage sag under load", format!("{:.2} V", self.sag)),
+        ];
+        if let Some(r) = self.resistance_mohm {
+            rows.push(("Internal resistance", format!("{:.0} mΩ", r)));
+        }
+        if let Some(pct) = self.logged_remaining {
+            rows.push(("Remaining (logged)", format!("{:.0} %", pct)));
+        }
+        if let Some(mah) = self.remaining_mah {
+            rows.push(("Remaining (estimated)", format!("{:.0} mAh", mah)));
+        }
+        if let Some(secs) = self.remaining_secs {
+            let secs = secs as u32;
+            rows.push((
+                "Time left at average current",
+                format!("{} min {:02} s", secs / 60, secs % 60),
+            ));
+        }
+        rows
+    }
+
+    /// Markdown block for pasting into a flight report.
+    pub fn markdown(&self) -> String {
+        let mut md = format!(
+            "### Power summary ({}, {:.1} s – {:.1} s)\n\n| Metric | Value |\n|---|---|\n",
+            self.topic, self.start, self.end
+        );
+        for (label, value) in self.rows() {
+            md.push_str(&format!("| {} | {} |\n", label, value));
+        }
+        md
+    }
+}
+
+/// Energy, current and voltage figures of a battery topic over the visible range or the
+/// loop region.
+pub struct PowerPanel {
+    pub open: bool,
+    pub topic: String,
+    pub voltage: String,
+    pub current: String,
+    /// Pack capacity used to estimate what is left; zero when unknown.
+    pub capacity_mah: f32,
+}
+
+impl PowerPanel {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            topic: String::new(),
+            voltage: String::new(),
+            current: String::new(),
+            capacity_mah: 0.0,
+        }
+    }
+
+    /// Picks the first known battery topic in the log, unless one is already chosen.
+    fn detect(&mut self, data_store: &DataStore) {
+        if data_store.get_column(&self.topic, "timestamp").is_some() {
+            return;
+        }
+        let Some(source) = BATTERY_SOURCES.iter().find(|s| {
+            data_store.get_column(s.topic, s.voltage).is_some()
+                && data_store.get_column(s.topic, s.current).is_some()
+        }) else {
+            return;
+        };
+        self.topic = source.topic.to_string();
+        self.voltage = source.voltage.to_string();
+        self.current = source.current.to_string();
+        self.capacity_mah = source
+            .capacity
+            .and_then(|col| data_store.get_column(source.topic, col))
+            .and_then(|values| values.iter().copied().find(|v| v.is_finite() && *v > 0.0))
+            .unwrap_or(0.0);
+    }
+}
+
+impl Default for PowerPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn column_combo(
+    ui: &mut egui::Ui,
+    id: &str,
+    data_store: &DataStore,
+    topic: &str,
+    col: &mut String,
+) {
+    egui::ComboBox::from_id_salt(id)
+        .selected_text(col.as_str())
+        .width(140.0)
+        .show_ui(ui, |ui| {
+            for name in data_store.get_columns(topic) {
+                if ui.selectable_label(col == name, name.as_str()).clicked() {
+                    *col = name.clone();
+                }
+            }
+        });
+}
+
+/// `window` is the time range analysed; `is_loop` tells whether it is the loop region
+/// rather than the visible range.
+pub fn render_power_panel(
+    ctx: &egui::Context,
+    panel: &mut PowerPanel,
+    data_store: &DataStore,
+    window: (f32, f32),
+    is_loop: bool,
+) {
+    if !panel.open {
+        return;
+    }
+    panel.detect(data_store);
+
+    let mut open = panel.open;
+    egui::Window::new(tr("Power Analysis"))
+        .open(&mut open)
+        .default_width(380.0)
+        .resizable(true)
+        .collapsible(false)
+        .show(ctx, |ui| {
+            egui::Grid::new("power_sources")
+                .num_columns(2)
+                .spacing([10.0, 4.0])
+                .show(ui, |ui| {
+                    ui.label(tr("Topic"));
+                    egui::ComboBox::from_id_salt("power_topic")
+                        .selected_text(&panel.topic)
+                        .width(140.0)
+                        .show_ui(ui, |ui| {
+                            for topic in data_store.get_topics() {
+                                if ui
+                                    .selectable_label(&panel.topic == topic, topic.as_str())
+                                    .clicked()
+                                {
+                                    panel.topic = topic.clone();
+                                }
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label(tr("Voltage"));
+                    column_combo(
+                        ui,
+                        "power_voltage",
+                        data_store,
+                        &panel.topic,
+                        &mut panel.voltage,
+                    );
+                    ui.end_row();
+
+                    ui.label(tr("Current"));
+                    column_combo(
+                        ui,
+                        "power_current",
+                        data_store,
+                        &panel.topic,
+                        &mut panel.current,
+                    );
+                    ui.end_row();
+
+                    ui.label(tr("Capacity"));
+                    ui.add(
+                        egui::DragValue::new(&mut panel.capacity_mah)
+                            .speed(10.0)
+                            .range(0.0..=1_000_000.0)
+                            .suffix(" mAh"),
+                    );
+                    ui.end_row();
+                });
+            ui.separator();
+
+            ui.label(
+                egui::RichText::new(if is_loop {
+                    tr("Over the loop region")
+                } else {
+                    tr("Over the visible range")
+                })
+                .weak(),
+            );
+
+            let Some(summary) = PowerSummary::compute(data_store, panel, window.0, window.1) else {
+                ui.label(egui::RichText::new(tr("No battery data in this range")).weak());
+                return;
+            };
+
+            egui::Grid::new("power_summary")
+                .num_columns(2)
+                .striped(true)
+                .spacing([12.0, 4.0])
+                .show(ui, |ui| {
+                    for (label, value) in summary.rows() {
+                        ui.label(tr(label));
+                        ui.label(value);
+                        ui.end_row();
+                    }
+                });
+
+            ui.separator();
+            if ui
+                .button(format!("{} {}", icons::COPY, tr("Copy Summary")))
+                .on_hover_text(tr("Copy as a Markdown table for reports"))
+                .clicked()
+            {
+                ui.ctx().copy_text(summary.markdown());
+            }
+        });
+    panel.open = open;
+}
@@ -0,0 +1,288 @@
+use super::stream_panel::format_bytes;
+use crate::ui::i18n::tr;
+use eframe::egui;
+use egui_tiles::TileId;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Weight of the newest frame in the smoothed timings.
+const SMOOTHING: f32 = 0.1;
+/// Rows shown in the tile and topic tables.
+const MAX_ROWS: usize = 10;
+/// Memory figures are refreshed at most this often while the overlay is open.
+const MEMORY_REFRESH: Duration = Duration::from_millis(500);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProfileSection {
+    DataProcessing,
+    TooltipCache,
+    PlotRendering,
+    Scene3D,
+}
+
+impl ProfileSection {
+    pub const ALL: [ProfileSection; 4] = [
+        ProfileSection::DataProcessing,
+        ProfileSection::TooltipCache,
+        ProfileSection::PlotRendering,
+        ProfileSection::Scene3D,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            ProfileSection::DataProcessing => "Data processing",
+            ProfileSection::TooltipCache => "Tooltip cache",
+            ProfileSection::PlotRendering => "Plot rendering",
+            ProfileSection::Scene3D => "3D scene",
+        }
+    }
+
+    fn color(self) -> egui::Color32 {
+        match self {
+            ProfileSection::DataProcessing => egui::Color32::from_rgb(90, 150, 230),
+            ProfileSection::TooltipCache => egui::Color32::from_rgb(230, 180, 50),
+            ProfileSection::PlotRendering => egui::Color32::from_rgb(100, 200, 100),
+            ProfileSection::Scene3D => egui::Color32::from_rgb(200, 110, 200),
+        }
+    }
+}
+
+struct TileTiming {
+    label: String,
+    ms: f32,
+    seen: bool,
+}
+
+pub struct TopicMemory {
+    pub topic: String,
+    /// Raw and derived columns held in RAM.
+    pub host_bytes: usize,
+    /// Vertex buffers uploaded for plotting.
+    pub gpu_bytes: u64,
+    pub spilled: bool,
+}
+
+/// CPU time spent on each part of a frame, smoothed over recent frames, plus the
+/// memory held per topic. Shown as an overlay to find what slows down big logs.
+pub struct FrameProfiler {
+    pub open: bool,
+    frame_start: Option<Instant>,
+    current: [Duration; 4],
+    section_ms: [f32; 4],
+    frame_ms: f32,
+    tiles: HashMap<TileId, TileTiming>,
+    topic_memory: Vec<TopicMemory>,
+    memory_refreshed: Option<Instant>,
+}
+
+impl FrameProfiler {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            frame_start: None,
+            current: [Duration::ZERO; 4],
+            section_ms: [0.0; 4],
+            frame_ms: 0.0,
+            tiles: HashMap::new(),
+            topic_memory: Vec::new(),
+            memory_refreshed: None,
+        }
+    }
+
+    pub fn begin_frame(&mut self) {
+        self.frame_start = Some(Instant::now());
+        self.current = [Duration::ZERO; 4];
+    }
+
+    pub fn end_frame(&mut self) {
+        let Some(start) = self.frame_start.take() else {
+            return;
+        };
+        self.frame_ms = smooth(self.frame_ms, start.elapsed());
+        for (ms, spent) in self.section_ms.iter_mut().zip(self.current) {
+            *ms = smooth(*ms, spent);
+        }
+
+        // Tiles that were closed or hidden behind another tab drop out of the table.
+        self.tiles
+            .retain(|_, timing| std::mem::take(&mut timing.seen));
+    }
+
+    pub fn add(&mut self, section: ProfileSection, spent: Duration) {
+        self.current[section as usize] += spent;
+    }
+
+    pub fn add_since(&mut self, section: ProfileSection, start: Instant) {
+        self.add(section, start.elapsed());
+    }
+
+    /// Time recorded for `section` so far in this frame.
+    pub fn spent(&self, section: ProfileSection) -> Duration {
+        self.current[section as usize]
+    }
+
+    /// Records the time a single tile took; also counted towards `section`.
+    pub fn record_tile(
+        &mut self,
+        tile_id: TileId,
+        label: String,
+        section: ProfileSection,
+        spent: Duration,
+    ) {
+        self.add(section, spent);
+        let timing = self.tiles.entry(tile_id).or_insert(TileTiming {
+            label: String::new(),
+            ms: spent.as_secs_f32() * 1000.0,
+            seen: false,
+        });
+        timing.label = label;
+        timing.ms = smooth(timing.ms, spent);
+        timing.seen = true;
+    }
+
+    pub fn memory_is_stale(&self) -> bool {
+        self.memory_refreshed
+            .is_none_or(|refreshed| refreshed.elapsed() >= MEMORY_REFRESH)
+    }
+
+    pub fn set_topic_memory(&mut self, mut topics: Vec<TopicMemory>) {
+        topics.sort_by_key(|t| std::cmp::Reverse(t.host_bytes as u64 + t.gpu_bytes));
+        self.topic_memory = topics;
+        self.memory_refreshed = Some(Instant::now());
+    }
+}
+
+impl Default for FrameProfiler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn smooth(average_ms: f32, spent: Duration) -> f32 {
+    let ms = spent.as_secs_f32() * 1000.0;
+    average_ms + (ms - average_ms) * SMOOTHING
+}
+
+fn breakdown_bar(ui: &mut egui::Ui, profiler: &FrameProfiler) {
+    let (rect, _) =
+        ui.allocate_exact_size(egui::vec2(ui.available_width(), 10.0), egui::Sense::hover());
+    ui.painter()
+        .rect_filled(rect, 2.0, egui::Color32::from_gray(50));
+
+    let total = profiler.frame_ms.max(f32::EPSILON);
+    let mut x = rect.min.x;
+    for section in ProfileSection::ALL {
+        let width = rect.width() * (profiler.section_ms[section as usize] / total).min(1.0);
+        let segment = egui::Rect::from_min_size(egui::pos2(x, rect.min.y), egui::vec2(width, 10.0))
+            .intersect(rect);
+        ui.painter().rect_filled(segment, 0.0, section.color());
+        x += width;
+    }
+}
+
+pub fn render_profiler_overlay(ctx: &egui::Context, profiler: &mut FrameProfiler, fps: f32) {
+    if !profiler.open {
+        return;
+    }
+
+    let mut open = true;
+    egui::Window::new(tr("Performance"))
+        .id(egui::Id::new("performance_profiler"))
+        .open(&mut open)
+        .resizable(false)
+        .default_width(320.0)
+        .anchor(egui::Align2::RIGHT_TOP, egui::vec2(-10.0, 40.0))
+        .show(ctx, |ui| {
+            ui.label(
+                egui::RichText::new(format!(
+                    "{:.2} ms CPU per frame · {:.0} FPS",
+                    profiler.frame_ms, fps
+                ))
+                .monospace(),
+            );
+            ui.add_space(4.0);
+            breakdown_bar(ui, profiler);
+            ui.add_space(4.0);
+
+            let accounted: f32 = profiler.section_ms.iter().sum();
+            egui::Grid::new("profiler_sections")
+                .num_columns(3)
+                .spacing([12.0, 2.0])
+                .show(ui, |ui| {
+                    for section in ProfileSection::ALL {
+                        let ms = profiler.section_ms[section as usize];
+                        ui.colored_label(section.color(), tr(section.label()));
+                        ui.monospace(format!("{:.2} ms", ms));
+                        ui.monospace(format!(
+                            "{:.0}%",
+                            100.0 * ms / profiler.frame_ms.max(f32::EPSILON)
+                        ));
+                        ui.end_row();
+                    }
+                    ui.weak(tr("Other UI"));
+                    ui.monospace(format!(
+                        "{:.2} ms",
+                        (profiler.frame_ms - accounted).max(0.0)
+                    ));
+                    ui.end_row();
+                });
+
+            ui.separator();
+            ui.strong(tr("Slowest tiles"));
+            let mut tiles: Vec<&TileTiming> = profiler.tiles.values().collect();
+            tiles.sort_by(|a, b| b.ms.total_cmp(&a.ms));
+            if tiles.is_empty() {
+                ui.weak(tr("No visible tiles"));
+            }
+            egui::Grid::new("profiler_tiles")
+                .num_columns(2)
+                .spacing([12.0, 2.0])
+                .show(ui, |ui| {
+                    for timing in tiles.iter().take(MAX_ROWS) {
+                        ui.label(&timing.label);
+                        ui.monospace(format!("{:.2} ms", timing.ms));
+                        ui.end_row();
+                    }
+                });
+
+            ui.separator();
+            ui.strong(tr("Memory by topic"));
+            let host_total: usize = profiler.topic_memory.iter().map(|t| t.host_bytes).sum();
+            let gpu_total: u64 = profiler.topic_memory.iter().map(|t| t.gpu_bytes).sum();
+            egui::Grid::new("profiler_memory")
+                .num_columns(3)
+                .spacing([12.0, 2.0])
+                .show(ui, |ui| {
+                    ui.weak(tr("Topic"));
+                    ui.weak("RAM");
+                    ui.weak("GPU");
+                    ui.end_row();
+                    for topic in profiler.topic_memory.iter().take(MAX_ROWS) {
+                        if topic.spilled {
+                            ui.label(topic.topic.as_str())
+                                .on_hover_text(tr("Spilled to disk"));
+                            ui.weak(tr("on disk"));
+                        } else {
+                            ui.label(topic.topic.as_str());
+                            ui.monospace(format_bytes(topic.host_bytes as f64));
+                        }
+                        ui.monospace(format_bytes(topic.gpu_bytes as f64));
+                        ui.end_row();
+                    }
+                    if profiler.topic_memory.len() > MAX_ROWS {
+                        ui.weak(format!(
+                            "+{} {}",
+                            profiler.topic_memory.len() - MAX_ROWS,
+                            tr("more")
+                        ));
+                        ui.end_row();
+                    }
+                    ui.strong(tr("Total"));
+                    ui.monospace(format_bytes(host_total as f64));
+                    ui.monospace(format_bytes(gpu_total as f64));
+                    ui.end_row();
+                });
+        });
+
+    profiler.open = open;
+}
@@ -0,0 +1,91 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+const MAX_RECENT: usize = 10;
+
+/// Recently loaded data files and layouts, most recent first, kept across sessions.
+#[derive(Default, Serialize, Deserialize)]
+pub struct RecentFiles {
+    #[serde(default)]
+    pub data: Vec<PathBuf>,
+    #[serde(default)]
+    pub layouts: Vec<PathBuf>,
+    #[serde(skip)]
+    path: Option<PathBuf>,
+}
+
+impl RecentFiles {
+    pub fn load() -> Self {
+        let Some(path) = recent_files_path() else {
+            return Self::default();
+        };
+
+        let mut recent = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|json| match serde_json::from_str::<Self>(&json) {
+                Ok(recent) => Some(recent),
+                Err(e) => {
+                    tracing::error!("Failed to parse recent files: {}", e);
+                    None
+                }
+            })
+            .unwrap_or_default();
+        recent.path = Some(path);
+        recent
+    }
+
+    fn save(&self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+
+        let result = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .map_err(|e| e.to_string())
+            .and_then(|_| serde_json::to_string_pretty(self).map_err(|e| e.to_string()))
+            .and_then(|json| std::fs::write(path, json).map_err(|e| e.to_string()));
+        if let Err(e) = result {
+            tracing::error!("Failed to save recent files: {}", e);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty() && self.layouts.is_empty()
+    }
+
+    pub fn push_data(&mut self, path: &Path) {
+        push_front(&mut self.data, path);
+        self.save();
+    }
+
+    pub fn push_layout(&mut self, path: &Path) {
+        push_front(&mut self.layouts, path);
+        self.save();
+    }
+
+    /// Forgets `path`, e.g. after it failed to open because it was moved or deleted.
+    pub fn remove(&mut self, path: &Path) {
+        self.data.retain(|p| p != path);
+        self.layouts.retain(|p| p != path);
+        self.save();
+    }
+
+    pub fn clear(&mut self) {
+        self.data.clear();
+        self.layouts.clear();
+        self.save();
+    }
+}
+
+fn push_front(paths: &mut Vec<PathBuf>, path: &Path) {
+    let path = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    paths.retain(|p| *p != path);
+    paths.insert(0, path);
+    paths.truncate(MAX_RECENT);
+}
+
+fn recent_files_path() -> Option<PathBuf> {
+    directories::ProjectDirs::from("io", "tilak", "TiPlot")
+        .map(|dirs| dirs.config_dir().join("recent_files.json"))
+}
@@ -0,0 +1,181 @@
+use crate::core::script::{self, ScriptHost, HELP};
+use crate::ui::i18n::tr;
+use eframe::egui;
+use egui_phosphor::regular as icons;
+use std::path::PathBuf;
+
+const EXAMPLE: &str = "\
+// Ground speed from the local position estimate
+use_topic(\"vehicle_local_position\");
+let ground_speed = sqrt(col(\"vx\") ** 2 + col(\"vy\") ** 2);
+set_col(\"ground_speed\", ground_speed);
+print(max(ground_speed));
+mark(ground_speed > 15, \"Fast\");
+";
+
+/// Editor and output log of the script console.
+pub struct ScriptPanel {
+    pub open: bool,
+    source: String,
+    /// Printed lines, flagged true for errors.
+    output: Vec<(String, bool)>,
+    path: Option<PathBuf>,
+    show_help: bool,
+}
+
+impl ScriptPanel {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            source: EXAMPLE.to_string(),
+            output: Vec::new(),
+            path: None,
+            show_help: false,
+        }
+    }
+
+    fn run(&mut self, host: &mut ScriptHost) {
+        let result = script::run(&self.source, host);
+        self.output
+            .extend(result.lines.into_iter().map(|line| (line, false)));
+        match result.error {
+            Some(e) => self.output.push((e, true)),
+            None => self.output.push(("✓ Done".to_string(), false)),
+        }
+    }
+
+    fn save(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("Rhai Script", &["rhai"])
+            .set_file_name("script.rhai")
+            .save_file()
+        else {
+            return;
+        };
+        match std::fs::write(&path, &self.source) {
+            Ok(()) => {
+                tracing::info!("Saved script to {:?}", path);
+                self.path = Some(path);
+            }
+            Err(e) => tracing::error!("Failed to save script: {}", e),
+        }
+    }
+
+    fn load(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("Rhai Script", &["rhai"])
+            .pick_file()
+        else {
+            return;
+        };
+        match std::fs::read_to_string(&path) {
+            Ok(source) => {
+                self.source = source;
+                self.path = Some(path);
+            }
+            Err(e) => tracing::error!("Failed to load script: {}", e),
+        }
+    }
+}
+
+impl Default for ScriptPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn render_script_panel(ctx: &egui::Context, panel: &mut ScriptPanel, host: &mut ScriptHost) {
+    if !panel.open {
+        return;
+    }
+
+    let mut open = panel.open;
+    let title = match &panel.path {
+        Some(path) => format!(
+            "{} - {}",
+            tr("Script Console"),
+            path.file_name().unwrap_or_default().to_string_lossy()
+        ),
+        None => tr("Script Console").to_string(),
+    };
+
+    egui::Window::new(title)
+        .id(egui::Id::new("script_console"))
+        .open(&mut open)
+        .default_width(560.0)
+        .default_height(480.0)
+        .resizable(true)
+        .collapsible(false)
+        .show(ctx, |ui| {
+            let run_shortcut =
+                ui.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::Enter));
+
+            ui.horizontal(|ui| {
+                let run_clicked = ui
+                    .button(format!("{} {}", icons::PLAY, tr("Run")))
+                    .on_hover_text("Ctrl+Enter")
+                    .clicked();
+                if run_clicked || run_shortcut {
+                    panel.run(host);
+                }
+                if ui
+                    .button(format!("{} {}", icons::FOLDER_OPEN, tr("Open...")))
+                    .clicked()
+                {
+                    panel.load();
+                }
+                if ui
+                    .button(format!("{} {}", icons::FLOPPY_DISK, tr("Save...")))
+                    .clicked()
+                {
+                    panel.save();
+                }
+                if ui
+                    .button(format!("{} {}", icons::ERASER, tr("Clear Output")))
+                    .clicked()
+                {
+                    panel.output.clear();
+                }
+                ui.toggle_value(
+                    &mut panel.show_help,
+                    format!("{} {}", icons::QUESTION, tr("Help")),
+                );
+            });
+
+            if panel.show_help {
+                ui.label(egui::RichText::new(HELP).monospace().small());
+            }
+            ui.separator();
+
+            egui::ScrollArea::vertical()
+                .id_salt("script_source")
+                .max_height(ui.available_height() * 0.6)
+                .show(ui, |ui| {
+                    ui.add(
+                        egui::TextEdit::multiline(&mut panel.source)
+                            .code_editor()
+                            .desired_rows(12)
+                            .desired_width(f32::INFINITY),
+                    );
+                });
+
+            ui.separator();
+
+            egui::ScrollArea::vertical()
+                .id_salt("script_output")
+                .stick_to_bottom(true)
+                .auto_shrink([false, false])
+                .show(ui, |ui| {
+                    for (line, is_error) in &panel.output {
+                        let text = egui::RichText::new(line).monospace();
+                        if *is_error {
+                            ui.label(text.color(egui::Color32::from_rgb(255, 100, 100)));
+                        } else {
+                            ui.label(text);
+                        }
+                    }
+                });
+        });
+
+    panel.open = open;
+}
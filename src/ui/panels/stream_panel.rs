@@ -0,0 +1,252 @@
+use crate::acquisition::{MirrorPublisher, MirrorStatus, StreamControl, StreamStatus};
+use crate::ui::i18n::tr;
+use eframe::egui;
+use egui_phosphor::regular as icons;
+use std::time::Instant;
+
+const DEFAULT_MIRROR_ADDR: &str = "0.0.0.0:9998";
+
+pub enum StreamAction {
+    StartMirror(String),
+    StopMirror,
+    Follow(String),
+}
+
+pub struct StreamPanel {
+    pub open: bool,
+    mirror_addr: String,
+    follow_addr: String,
+    last_sample: Option<(Instant, u64)>,
+    bytes_per_sec: f64,
+}
+
+impl StreamPanel {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            mirror_addr: DEFAULT_MIRROR_ADDR.to_string(),
+            follow_addr: "127.0.0.1:9998".to_string(),
+            last_sample: None,
+            bytes_per_sec: 0.0,
+        }
+    }
+
+    fn update_rate(&mut self, bytes_received: u64) {
+        let now = Instant::now();
+        match self.last_sample {
+            Some((time, bytes)) => {
+                let elapsed = now.duration_since(time).as_secs_f64();
+                if elapsed >= 1.0 {
+                    self.bytes_per_sec = bytes_received.saturating_sub(bytes) as f64 / elapsed;
+                    self.last_sample = Some((now, bytes_received));
+                }
+            }
+            None => self.last_sample = Some((now, bytes_received)),
+        }
+    }
+}
+
+impl Default for StreamPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub(crate) fn format_bytes(bytes: f64) -> String {
+    if bytes >= 1024.0 * 1024.0 * 1024.0 {
+        format!("{:.2} GB", bytes / (1024.0 * 1024.0 * 1024.0))
+    } else if bytes >= 1024.0 * 1024.0 {
+        format!("{:.2} MB", bytes / (1024.0 * 1024.0))
+    } else if bytes >= 1024.0 {
+        format!("{:.1} KB", bytes / 1024.0)
+    } else {
+        format!("{:.0} B", bytes)
+    }
+}
+
+pub fn render_stream_panel(
+    ctx: &egui::Context,
+    panel: &mut StreamPanel,
+    control: &StreamControl,
+    mirror: Option<&MirrorPublisher>,
+) -> Option<StreamAction> {
+    let bytes_received = control.bytes_received();
+    panel.update_rate(bytes_received);
+
+    if !panel.open {
+        return None;
+    }
+
+    let mut action = None;
+
+    let mut open = panel.open;
+    egui::Window::new(tr("Data Source"))
+        .open(&mut open)
+        .resizable(false)
+        .collapsible(false)
+        .default_width(280.0)
+        .show(ctx, |ui| {
+            let status = control.status();
+            let (status_text, status_color) = match &status {
+                StreamStatus::Stopped => ("Stopped".to_string(), egui::Color32::GRAY),
+                StreamStatus::Listening(addr) => (
+                    format!("Listening on {}", addr),
+                    egui::Color32::from_rgb(200, 200, 100),
+                ),
+                StreamStatus::Connected(addr) => (
+                    format!("Connected to {}", addr),
+                    egui::Color32::from_rgb(100, 200, 100),
+                ),
+                StreamStatus::Following(addr) => (
+                    format!("Following {}", addr),
+                    egui::Color32::from_rgb(100, 200, 100),
+                ),
+                StreamStatus::Error(e) => (format!("Error: {}", e), egui::Color32::RED),
+            };
+
+            egui::Grid::new("stream_status_grid")
+                .num_columns(2)
+                .spacing([20.0, 6.0])
+                .show(ui, |ui| {
+                    ui.label(tr("Status:"));
+                    ui.colored_label(status_color, status_text);
+                    ui.end_row();
+
+                    ui.label(tr("Rate:"));
+                    ui.label(format!("{}/s", format_bytes(panel.bytes_per_sec)));
+                    ui.end_row();
+
+                    ui.label(tr("Received:"));
+                    ui.label(format_bytes(bytes_received as f64));
+                    ui.end_row();
+
+                    let dropped = control.dropped_tables();
+                    ui.label(tr("Dropped:"));
+                    let dropped_text = format!("{} {}", dropped, tr("tables"));
+                    if dropped > 0 {
+                        ui.colored_label(egui::Color32::from_rgb(230, 160, 60), dropped_text);
+                    } else {
+                        ui.label(dropped_text);
+                    }
+                    ui.end_row();
+
+                    if let Some(error) = control.last_error() {
+                        ui.label(tr("Last Error:"));
+                        ui.add(
+                            egui::Label::new(
+                                egui::RichText::new(error).color(egui::Color32::RED).small(),
+                            )
+                            .wrap(),
+                        );
+                        ui.end_row();
+                    }
+                });
+
+            ui.add_space(8.0);
+
+            ui.horizontal(|ui| {
+                if control.is_paused() {
+                    if ui
+                        .button(format!("{} {}", icons::PLAY, tr("Resume")))
+                        .clicked()
+                    {
+                        control.set_paused(false);
+                    }
+                } else if ui
+                    .button(format!("{} {}", icons::PAUSE, tr("Pause")))
+                    .clicked()
+                {
+                    control.set_paused(true);
+                }
+
+                let connected = matches!(
+                    status,
+                    StreamStatus::Connected(_) | StreamStatus::Following(_)
+                );
+                if ui
+                    .add_enabled(
+                        connected,
+                        egui::Button::new(format!("{} {}", icons::PLUGS, tr("Drop"))),
+                    )
+                    .on_hover_text(tr("Close the current connection"))
+                    .clicked()
+                {
+                    control.drop_connection();
+                }
+
+                if control.is_armed() {
+                    if ui
+                        .button(format!("{} {}", icons::STOP, tr("Stop")))
+                        .on_hover_text(tr("Stop listening for connections"))
+                        .clicked()
+                    {
+                        control.set_armed(false);
+                    }
+                } else if ui
+                    .button(format!("{} {}", icons::ARROW_CLOCKWISE, tr("Re-arm")))
+                    .on_hover_text(tr("Start listening for connections again"))
+                    .clicked()
+                {
+                    control.set_armed(true);
+                }
+            });
+
+            ui.separator();
+            ui.label(egui::RichText::new(tr("Mirror")).strong());
+
+            match mirror {
+                Some(mirror) => {
+                    let (text, color) = match mirror.status() {
+                        MirrorStatus::Starting => ("Starting...".to_string(), egui::Color32::GRAY),
+                        MirrorStatus::Serving(addr) => (
+                            format!("Serving on {} ({} followers)", addr, mirror.followers()),
+                            egui::Color32::from_rgb(100, 200, 100),
+                        ),
+                        MirrorStatus::Error(e) => (format!("Error: {}", e), egui::Color32::RED),
+                    };
+                    ui.colored_label(color, text);
+                    if ui
+                        .button(format!("{} {}", icons::STOP, tr("Stop Mirroring")))
+                        .clicked()
+                    {
+                        action = Some(StreamAction::StopMirror);
+                    }
+                }
+                None => {
+                    ui.horizontal(|ui| {
+                        ui.add(
+                            egui::TextEdit::singleline(&mut panel.mirror_addr).desired_width(140.0),
+                        );
+                        if ui
+                            .button(format!("{} {}", icons::BROADCAST, tr("Publish")))
+                            .on_hover_text(tr(
+                                "Let other TiPlot instances follow this session and cursor",
+                            ))
+                            .clicked()
+                        {
+                            action = Some(StreamAction::StartMirror(panel.mirror_addr.clone()));
+                        }
+                    });
+                }
+            }
+
+            ui.horizontal(|ui| {
+                ui.add(egui::TextEdit::singleline(&mut panel.follow_addr).desired_width(140.0));
+                let following = matches!(status, StreamStatus::Following(_));
+                if ui
+                    .add_enabled(
+                        !following,
+                        egui::Button::new(format!("{} {}", icons::EYE, tr("Follow"))),
+                    )
+                    .on_hover_text(tr(
+                        "Mirror the session published by another TiPlot instance",
+                    ))
+                    .clicked()
+                {
+                    action = Some(StreamAction::Follow(panel.follow_addr.clone()));
+                }
+            });
+        });
+    panel.open = open;
+    action
+}
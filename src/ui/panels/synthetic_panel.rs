@@ -0,0 +1,111 @@
+use crate::acquisition::SyntheticConfig;
+use crate::ui::i18n::tr;
+use eframe::egui;
+use egui_phosphor::regular as icons;
+
+pub enum SyntheticAction {
+    Start(SyntheticConfig),
+    Stop,
+}
+
+/// Debug window generating synthetic topics to exercise the UI without a log.
+#[derive(Default)]
+pub struct SyntheticPanel {
+    pub open: bool,
+    config: SyntheticConfig,
+}
+
+pub fn render_synthetic_panel(
+    ctx: &egui::Context,
+    panel: &mut SyntheticPanel,
+    running: bool,
+) -> Option<SyntheticAction> {
+    if !panel.open {
+        return None;
+    }
+
+    let mut open = panel.open;
+    let mut action = None;
+    egui::Window::new(tr("Synthetic Data"))
+        .open(&mut open)
+        .resizable(false)
+        .collapsible(false)
+        .show(ctx, |ui| {
+            ui.label(
+                egui::RichText::new(tr(
+                    "Topics with sine, noise and step columns for testing and benchmarks",
+                ))
+                .weak(),
+            );
+            ui.separator();
+
+            let config = &mut panel.config;
+            ui.add_enabled_ui(!running, |ui| {
+                egui::Grid::new("synthetic_grid")
+                    .num_columns(2)
+                    .spacing([12.0, 6.0])
+                    .show(ui, |ui| {
+                        ui.label(tr("Topics"));
+                        ui.add(egui::DragValue::new(&mut config.topics).range(1..=1000));
+                        ui.end_row();
+
+                        ui.label(tr("Rate"));
+                        ui.add(
+                            egui::DragValue::new(&mut config.rate_hz)
+                                .range(1.0..=10_000.0)
+                                .suffix(" Hz"),
+                        );
+                        ui.end_row();
+
+                        ui.label(tr("Stream Live"));
+                        ui.checkbox(&mut config.live, "");
+                        ui.end_row();
+
+                        ui.label(tr("Duration"));
+                        ui.add_enabled(
+                            !config.live,
+                            egui::DragValue::new(&mut config.duration)
+                                .range(1.0..=86_400.0)
+                                .suffix(" s"),
+                        );
+                        ui.end_row();
+                    });
+            });
+
+            let samples = if config.live {
+                config.topics as f32 * config.rate_hz
+            } else {
+                config.topics as f32 * config.rate_hz * config.duration
+            };
+            let unit = if config.live {
+                tr("samples/s")
+            } else {
+                tr("samples")
+            };
+            ui.label(
+                egui::RichText::new(format!("{:.0} {} × 3 {}", samples, unit, tr("columns")))
+                    .small()
+                    .weak(),
+            );
+            ui.separator();
+
+            ui.horizontal(|ui| {
+                if running {
+                    ui.spinner();
+                    if ui
+                        .button(format!("{} {}", icons::STOP, tr("Stop")))
+                        .clicked()
+                    {
+                        action = Some(SyntheticAction::Stop);
+                    }
+                } else if ui
+                    .button(format!("{} {}", icons::PLAY, tr("Generate")))
+                    .clicked()
+                {
+                    action = Some(SyntheticAction::Start(config.clone()));
+                }
+            });
+        });
+    panel.open = open;
+    action
+}
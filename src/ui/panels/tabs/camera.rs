@@ -0,0 +1,376 @@
+use eframe::egui;
+use glam::{Mat4, Quat, Vec3};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+pub const NEAR_PLANE: f32 = 0.1;
+const FAR_PLANE: f32 = 10000.0;
+const FOV_Y: f32 = 45.0 * std::f32::consts::PI / 180.0;
+const TRANSITION_SECONDS: f32 = 0.6;
+/// Higher values make the chase camera catch up with the vehicle faster.
+const CHASE_STIFFNESS: f32 = 4.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CameraMode {
+    Orbit,
+    Chase,
+    Fpv,
+    TopDown,
+    Free,
+}
+
+impl CameraMode {
+    pub const ALL: [CameraMode; 5] = [
+        CameraMode::Orbit,
+        CameraMode::Chase,
+        CameraMode::Fpv,
+        CameraMode::TopDown,
+        CameraMode::Free,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            CameraMode::Orbit => "Orbit",
+            CameraMode::Chase => "Chase",
+            CameraMode::Fpv => "FPV",
+            CameraMode::TopDown => "Top-Down",
+            CameraMode::Free => "Free",
+        }
+    }
+
+    pub fn hint(&self) -> &'static str {
+        match self {
+            CameraMode::Orbit => "Drag to orbit the vehicle, scroll to zoom",
+            CameraMode::Chase => {
+                "Follows behind the vehicle heading. Drag to adjust, scroll to zoom"
+            }
+            CameraMode::Fpv => "Onboard view using the vehicle attitude",
+            CameraMode::TopDown => "Orthographic map view. Drag to pan, scroll to zoom",
+            CameraMode::Free => "Drag to look, WASD to move, Q/E down/up, Shift to speed up",
+        }
+    }
+}
+
+/// Where the camera is and what it looks at. `ortho` blends from perspective (0) to
+/// orthographic (1) projection showing `half_height` meters above and below the center.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct CameraPose {
+    eye: Vec3,
+    look: Vec3,
+    up: Vec3,
+    ortho: f32,
+    half_height: f32,
+}
+
+impl CameraPose {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        let up = self.up.lerp(other.up, t);
+        Self {
+            eye: self.eye.lerp(other.eye, t),
+            look: self.look.lerp(other.look, t),
+            up: if up.length_squared() > 1e-6 {
+                up
+            } else {
+                other.up
+            },
+            ortho: self.ortho + (other.ortho - self.ortho) * t,
+            half_height: self.half_height + (other.half_height - self.half_height) * t,
+        }
+    }
+}
+
+/// Camera pose recorded at a log time.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct CameraKeyframe {
+    pub time: f32,
+    pub pose: CameraPose,
+}
+
+#[derive(Clone)]
+pub struct Camera {
+    pub mode: CameraMode,
+    pub yaw: f32,
+    pub pitch: f32,
+    pub distance: f32,
+    /// Free camera speed in meters per second.
+    pub free_speed: f32,
+    pan: Vec3,
+    chase_eye: Option<Vec3>,
+    free_eye: Vec3,
+    free_yaw: f32,
+    free_pitch: f32,
+    /// Keyframes sorted by time. While `play_path` is set they drive the camera, so the
+    /// same view is reproduced every time the log is played.
+    pub path: Vec<CameraKeyframe>,
+    pub play_path: bool,
+    last_pose: Option<CameraPose>,
+    transition: Option<(CameraPose, f32)>,
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        Self {
+            mode: CameraMode::Orbit,
+            yaw: 45.0f32.to_radians(),
+            pitch: 30.0f32.to_radians(),
+            distance: 500.0,
+            free_speed: 50.0,
+            pan: Vec3::ZERO,
+            chase_eye: None,
+            free_eye: Vec3::ZERO,
+            free_yaw: 0.0,
+            free_pitch: 0.0,
+            path: Vec::new(),
+            play_path: false,
+            last_pose: None,
+            transition: None,
+        }
+    }
+}
+
+impl Camera {
+    /// Switches modes, animating from the current view to the new one.
+    pub fn set_mode(&mut self, mode: CameraMode) {
+        if mode == self.mode {
+            return;
+        }
+        self.mode = mode;
+        self.pan = Vec3::ZERO;
+        self.chase_eye = None;
+        self.transition = self.last_pose.map(|pose| (pose, 0.0));
+
+        match mode {
+            CameraMode::Chase => self.yaw = 0.0,
+            CameraMode::Free => {
+                if let Some(pose) = self.last_pose {
+                    let dir = (pose.look - pose.eye).normalize_or(Vec3::X);
+                    self.free_eye = pose.eye;
+                    self.free_yaw = dir.y.atan2(dir.x);
+                    self.free_pitch = (-dir.z).asin().clamp(-1.5, 1.5);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn handle_input(&mut self, ui: &egui::Ui, response: &egui::Response, rect: egui::Rect) {
+        let dt = ui.input(|i| i.stable_dt).min(0.1);
+
+        if response.dragged_by(egui::PointerButton::Primary) {
+            let delta = response.drag_delta();
+            match self.mode {
+                CameraMode::Orbit | CameraMode::Chase => {
+                    self.yaw += delta.x * 0.01;
+                    self.pitch = (self.pitch + delta.y * 0.01).clamp(0.01, 1.55);
+                }
+                CameraMode::TopDown => {
+                    let meters_per_point = 2.0 * self.ortho_half_height() / rect.height();
+                    self.pan.x += delta.y * meters_per_point;
+                    self.pan.y -= delta.x * meters_per_point;
+                }
+                CameraMode::Free => {
+                    self.free_yaw += delta.x * 0.005;
+                    self.free_pitch = (self.free_pitch - delta.y * 0.005).clamp(-1.5, 1.5);
+                }
+                CameraMode::Fpv => {}
+            }
+        }
+
+        if !response.hovered() {
+            return;
+        }
+
+        let scroll = ui.input(|i| i.smooth_scroll_delta.y);
+        if self.mode == CameraMode::Free {
+            self.free_speed = (self.free_speed * (1.0 + scroll * 0.002)).clamp(1.0, 1000.0);
+
+            let (forward, right) = self.free_axes();
+            let mut movement = Vec3::ZERO;
+            ui.input(|i| {
+                let axis = |pos: egui::Key, neg: egui::Key| {
+                    i.key_down(pos) as i32 as f32 - i.key_down(neg) as i32 as f32
+                };
+                movement += forward * axis(egui::Key::W, egui::Key::S);
+                movement += right * axis(egui::Key::D, egui::Key::A);
+                movement += -Vec3::Z * axis(egui::Key::E, egui::Key::Q);
+                if i.modifiers.shift {
+                    movement *= 4.0;
+                }
+            });
+            if movement != Vec3::ZERO {
+                self.free_eye += movement * self.free_speed * dt;
+                ui.ctx().request_repaint();
+            }
+        } else {
+            self.distance -= scroll * (self.distance * 0.01);
+            self.distance = self.distance.clamp(1.0, 5000.0);
+        }
+    }
+
+    /// Returns the view-projection matrix for this frame, given the followed vehicle's pose.
+    /// Keeps requesting repaints while the camera is moving.
+    pub fn view_proj(
+        &mut self,
+        ctx: &egui::Context,
+        target: Vec3,
+        rotation: Quat,
+        aspect: f32,
+        time: f32,
+    ) -> Mat4 {
+        let dt = ctx.input(|i| i.stable_dt).min(0.1);
+        let mut pose = match self.path_pose(time) {
+            Some(pose) if self.play_path => pose,
+            _ => self.desired_pose(ctx, target, rotation, dt),
+        };
+
+        if let Some((from, progress)) = &mut self.transition {
+            *progress += dt / TRANSITION_SECONDS;
+            if *progress >= 1.0 {
+                self.transition = None;
+            } else {
+                let t = *progress * *progress * (3.0 - 2.0 * *progress);
+                pose = from.lerp(&pose, t);
+            }
+            ctx.request_repaint();
+        }
+        self.last_pose = Some(pose);
+
+        let view = Mat4::look_at_rh(pose.eye, pose.look, pose.up);
+        let perspective = Mat4::perspective_rh(FOV_Y, aspect, NEAR_PLANE, FAR_PLANE);
+        let proj = if pose.ortho > 0.0 {
+            let half_width = pose.half_height * aspect;
+            let ortho = Mat4::orthographic_rh(
+                -half_width,
+                half_width,
+                -pose.half_height,
+                pose.half_height,
+                -FAR_PLANE,
+                FAR_PLANE,
+            );
+            perspective * (1.0 - pose.ortho) + ortho * pose.ortho
+        } else {
+            perspective
+        };
+
+        proj * view
+    }
+
+    /// Records the current view at `time`, replacing a keyframe already at that time.
+    pub fn add_keyframe(&mut self, time: f32) {
+        let Some(pose) = self.last_pose else {
+            return;
+        };
+        self.path.retain(|k| (k.time - time).abs() > 1e-3);
+        let idx = self.path.partition_point(|k| k.time < time);
+        self.path.insert(idx, CameraKeyframe { time, pose });
+    }
+
+    pub fn save_path(&self, file: &Path) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(&self.path).map_err(|e| e.to_string())?;
+        std::fs::write(file, json).map_err(|e| e.to_string())
+    }
+
+    pub fn load_path(&mut self, file: &Path) -> Result<(), String> {
+        let json = std::fs::read_to_string(file).map_err(|e| e.to_string())?;
+        let mut path: Vec<CameraKeyframe> =
+            serde_json::from_str(&json).map_err(|e| e.to_string())?;
+        path.sort_by(|a, b| a.time.total_cmp(&b.time));
+        self.path = path;
+        Ok(())
+    }
+
+    /// Pose on the recorded path at `time`, holding the first and last keyframes outside it.
+    fn path_pose(&self, time: f32) -> Option<CameraPose> {
+        let first = self.path.first()?;
+        let idx = self.path.partition_point(|k| k.time <= time);
+        if idx == 0 {
+            return Some(first.pose);
+        }
+        let from = &self.path[idx - 1];
+        let Some(to) = self.path.get(idx) else {
+            return Some(from.pose);
+        };
+        let t = (time - from.time) / (to.time - from.time);
+        Some(from.pose.lerp(&to.pose, t * t * (3.0 - 2.0 * t)))
+    }
+
+    /// Half the visible height in orthographic mode, matching the perspective view at the
+    /// target so switching between them keeps the same framing.
+    fn ortho_half_height(&self) -> f32 {
+        self.distance * (FOV_Y * 0.5).tan()
+    }
+
+    fn orbit_offset(&self) -> Vec3 {
+        let height = self.distance * self.pitch.sin();
+        let ground_dist = self.distance * self.pitch.cos();
+        Vec3::new(
+            -ground_dist * self.yaw.cos(),
+            -ground_dist * self.yaw.sin(),
+            -height,
+        )
+    }
+
+    fn free_axes(&self) -> (Vec3, Vec3) {
+        let forward = Vec3::new(
+            self.free_pitch.cos() * self.free_yaw.cos(),
+            self.free_pitch.cos() * self.free_yaw.sin(),
+            -self.free_pitch.sin(),
+        );
+        let right = Vec3::new(-self.free_yaw.sin(), self.free_yaw.cos(), 0.0);
+        (forward, right)
+    }
+
+    fn desired_pose(
+        &mut self,
+        ctx: &egui::Context,
+        target: Vec3,
+        rotation: Quat,
+        dt: f32,
+    ) -> CameraPose {
+        let half_height = self.ortho_half_height();
+        let perspective = |eye: Vec3, look: Vec3, up: Vec3| CameraPose {
+            eye,
+            look,
+            up,
+            ortho: 0.0,
+            half_height,
+        };
+
+        match self.mode {
+            CameraMode::Orbit => perspective(target + self.orbit_offset(), target, -Vec3::Z),
+            CameraMode::Chase => {
+                // Only the heading is followed so the horizon stays level.
+                let forward = rotation * Vec3::X;
+                let heading = Quat::from_rotation_z(forward.y.atan2(forward.x));
+                let desired = target + heading * self.orbit_offset();
+
+                let eye = match self.chase_eye {
+                    Some(eye) => eye.lerp(desired, 1.0 - (-CHASE_STIFFNESS * dt).exp()),
+                    None => desired,
+                };
+                if eye.distance(desired) > self.distance * 1e-3 {
+                    ctx.request_repaint();
+                }
+                self.chase_eye = Some(eye);
+                perspective(eye, target, -Vec3::Z)
+            }
+            CameraMode::Fpv => {
+                perspective(target, target + rotation * Vec3::X, rotation * -Vec3::Z)
+            }
+            CameraMode::TopDown => {
+                let look = target + self.pan;
+                CameraPose {
+                    eye: look - Vec3::Z * self.distance,
+                    look,
+                    up: Vec3::X,
+                    ortho: 1.0,
+                    half_height,
+                }
+            }
+            CameraMode::Free => {
+                let (forward, _) = self.free_axes();
+                perspective(self.free_eye, self.free_eye + forward, -Vec3::Z)
+            }
+        }
+    }
+}
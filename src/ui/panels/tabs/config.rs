@@ -1,11 +1,14 @@
-use crate::core::DataStore;
+use crate::core::aliases;
+use crate::core::geo::gps_to_ned;
+use crate::core::{session_topic, AliasProfile, DataStore, SESSION_SEPARATOR};
+use crate::ui::colormap::Colormap;
+use crate::ui::i18n::tr;
+use crate::ui::panels::tabs::point_cloud::{array_fields, PointCloud, PointCloudSource};
 use eframe::egui;
 use egui_phosphor::regular as icons;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-const EARTH_RADIUS: f64 = 6378137.0;
-
 fn fuzzy_match(target: &str, query: &str) -> bool {
     if query.is_empty() {
         return true;
@@ -68,6 +71,57 @@ pub enum AngleUnit {
     Degrees,
 }
 
+/// Axes a local position is logged in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum WorldFrame {
+    /// North, east, down, as logged by PX4 and ArduPilot.
+    #[default]
+    Ned,
+    /// East, north, up, as logged by ROS.
+    Enu,
+}
+
+impl WorldFrame {
+    pub fn to_ned(self, v: glam::Vec3) -> glam::Vec3 {
+        match self {
+            WorldFrame::Ned => v,
+            WorldFrame::Enu => glam::Vec3::new(v.y, v.x, -v.z),
+        }
+    }
+}
+
+/// World and body axes an attitude is logged in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AttitudeFrame {
+    /// Body forward-right-down relative to NED, as logged by PX4 and ArduPilot.
+    #[default]
+    NedFrd,
+    /// Body forward-left-up relative to ENU, as logged by ROS.
+    EnuFlu,
+}
+
+impl AttitudeFrame {
+    /// The logged attitude as a rotation from body FRD to NED, turned by the magnetic
+    /// `declination` in degrees for headings logged relative to magnetic north.
+    pub fn to_ned_frd(self, q: glam::Quat, declination: f32) -> glam::Quat {
+        let q = match self {
+            AttitudeFrame::NedFrd => q,
+            AttitudeFrame::EnuFlu => {
+                // Half turns swapping east/north and flipping up, and flipping left/up.
+                let h = std::f32::consts::FRAC_1_SQRT_2;
+                let enu_to_ned = glam::Quat::from_xyzw(h, h, 0.0, 0.0);
+                let frd_to_flu = glam::Quat::from_xyzw(1.0, 0.0, 0.0, 0.0);
+                enu_to_ned * q * frd_to_flu
+            }
+        };
+        if declination == 0.0 {
+            q
+        } else {
+            glam::Quat::from_rotation_z(declination.to_radians()) * q
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum OrientationMode {
     Static,
@@ -77,6 +131,11 @@ pub enum OrientationMode {
         qy: String,
         qz: String,
         qw: String,
+        #[serde(default)]
+        frame: AttitudeFrame,
+        /// Degrees added to the heading.
+        #[serde(default)]
+        declination: f32,
     },
     Euler {
         topic: String,
@@ -84,10 +143,14 @@ pub enum OrientationMode {
         pitch: String,
         yaw: String,
         angle_unit: AngleUnit,
+        #[serde(default)]
+        frame: AttitudeFrame,
+        #[serde(default)]
+        declination: f32,
     },
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum PositionMode {
     LocalNED {
         topic: String,
@@ -97,6 +160,8 @@ pub enum PositionMode {
         lat_ref: String,
         lon_ref: String,
         alt_ref: String,
+        #[serde(default)]
+        frame: WorldFrame,
     },
     GlobalGPS {
         topic: String,
@@ -117,6 +182,204 @@ pub struct VehicleConfig {
     pub orientation: OrientationMode,
     pub position: PositionMode,
     pub visible: bool,
+    #[serde(default)]
+    pub ghost: Option<GhostConfig>,
+    #[serde(default)]
+    pub vectors: Vec<VectorOverlay>,
+    #[serde(default)]
+    pub trail_colormap: Option<TrailColorMap>,
+    #[serde(default)]
+    pub point_clouds: Vec<PointCloud>,
+    /// Added log session the topics are read from, `None` for the main log.
+    #[serde(default)]
+    pub session: Option<String>,
+    /// Seconds the session's log is shifted by to line up with the timeline.
+    #[serde(default)]
+    pub time_offset: f32,
+}
+
+/// Colors the trail by a data column instead of `path_color`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TrailColorMap {
+    pub enabled: bool,
+    pub topic: String,
+    pub column: String,
+    pub colormap: Colormap,
+    /// Fixed value range, or `None` to span the whole column.
+    pub range: Option<[f32; 2]>,
+}
+
+impl Default for TrailColorMap {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            topic: String::new(),
+            column: String::new(),
+            colormap: Colormap::Viridis,
+            range: None,
+        }
+    }
+}
+
+impl TrailColorMap {
+    pub fn value_range(&self, ds: &DataStore) -> Option<[f32; 2]> {
+        if let Some(range) = self.range {
+            return Some(range);
+        }
+        let len = ds.get_column(&self.topic, &self.column)?.len();
+        let stats = ds.column_range_stats(&self.topic, &self.column, 0, len)?;
+        Some([stats.min, stats.max])
+    }
+
+    /// Color at each of `times`, taken from the last column sample at or before it.
+    pub fn colors(&self, ds: &DataStore, times: &[f32]) -> Option<Vec<egui::Color32>> {
+        let timestamps = ds.get_column(&self.topic, "timestamp")?;
+        let values = ds.get_column(&self.topic, &self.column)?;
+        let [min, max] = self.value_range(ds)?;
+        let span = if max > min { max - min } else { 1.0 };
+
+        let mut idx = 0;
+        let colors = times
+            .iter()
+            .map(|&t| {
+                while idx + 1 < timestamps.len() && timestamps[idx + 1] <= t {
+                    idx += 1;
+                }
+                let value = values.get(idx).copied().unwrap_or(f32::NAN);
+                self.colormap.sample((value - min) / span)
+            })
+            .collect();
+        Some(colors)
+    }
+}
+
+/// Translucent second model driven by setpoint topics, drawn alongside the vehicle.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GhostConfig {
+    pub enabled: bool,
+    /// `None` uses the vehicle's actual attitude.
+    pub orientation: Option<OrientationMode>,
+    /// `None` uses the vehicle's actual position.
+    pub position: Option<PositionMode>,
+    pub opacity: f32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum VectorFrame {
+    Ned,
+    Body,
+}
+
+/// Arrow drawn from the vehicle origin, e.g. velocity or thrust. Empty component columns
+/// read as zero.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct VectorOverlay {
+    pub name: String,
+    pub topic: String,
+    pub x: String,
+    pub y: String,
+    pub z: String,
+    pub frame: VectorFrame,
+    /// Arrow length in meters per unit of the vector.
+    pub scale: f32,
+    pub color: [f32; 3],
+    pub visible: bool,
+}
+
+impl VectorOverlay {
+    fn new(name: &str, topic: &str, cols: [&str; 3], frame: VectorFrame, color: [f32; 3]) -> Self {
+        Self {
+            name: name.to_string(),
+            topic: topic.to_string(),
+            x: cols[0].to_string(),
+            y: cols[1].to_string(),
+            z: cols[2].to_string(),
+            frame,
+            scale: 1.0,
+            color,
+            visible: true,
+        }
+    }
+
+    pub fn presets() -> Vec<Self> {
+        vec![
+            Self::new(
+                "Velocity",
+                "vehicle_local_position",
+                ["vx", "vy", "vz"],
+                VectorFrame::Ned,
+                [0.2, 0.8, 1.0],
+            ),
+            Self::new(
+                "Acceleration",
+                "vehicle_local_position",
+                ["ax", "ay", "az"],
+                VectorFrame::Ned,
+                [1.0, 0.8, 0.2],
+            ),
+            Self::new(
+                "Thrust",
+                "vehicle_thrust_setpoint",
+                ["xyz[0]", "xyz[1]", "xyz[2]"],
+                VectorFrame::Body,
+                [1.0, 0.3, 0.3],
+            ),
+            Self::new(
+                "Wind",
+                "wind",
+                ["windspeed_north", "windspeed_east", ""],
+                VectorFrame::Ned,
+                [0.7, 0.7, 1.0],
+            ),
+            Self::new(
+                "Custom",
+                "",
+                ["", "", ""],
+                VectorFrame::Ned,
+                [1.0, 1.0, 1.0],
+            ),
+        ]
+    }
+
+    /// Value in NED at time `t`, or `None` if the topic has no samples.
+    pub fn evaluate(&self, ds: &DataStore, t: f32, rotation: glam::Quat) -> Option<glam::Vec3> {
+        if ds.get_column(&self.topic, "timestamp")?.is_empty() {
+            return None;
+        }
+
+        let component = |col: &str| {
+            if col.is_empty() {
+                0.0
+            } else {
+                VehicleConfig::get_value_at(ds, &self.topic, col, t)
+            }
+        };
+        let value = glam::Vec3::new(component(&self.x), component(&self.y), component(&self.z));
+
+        Some(match self.frame {
+            VectorFrame::Ned => value,
+            VectorFrame::Body => rotation * value,
+        })
+    }
+}
+
+impl Default for GhostConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            orientation: Some(OrientationMode::Quaternion {
+                topic: "vehicle_attitude_setpoint".to_string(),
+                qx: "q_d[1]".to_string(),
+                qy: "q_d[2]".to_string(),
+                qz: "q_d[3]".to_string(),
+                qw: "q_d[0]".to_string(),
+                frame: AttitudeFrame::NedFrd,
+                declination: 0.0,
+            }),
+            position: None,
+            opacity: 0.35,
+        }
+    }
 }
 
 impl Default for VehicleConfig {
@@ -134,6 +397,8 @@ impl Default for VehicleConfig {
                 qy: "q[2]".to_string(),
                 qz: "q[3]".to_string(),
                 qw: "q[0]".to_string(),
+                frame: AttitudeFrame::NedFrd,
+                declination: 0.0,
             },
             position: PositionMode::LocalNED {
                 topic: "vehicle_local_position".to_string(),
@@ -143,19 +408,299 @@ impl Default for VehicleConfig {
                 lat_ref: "ref_lat".to_string(),
                 lon_ref: "ref_lon".to_string(),
                 alt_ref: "ref_alt".to_string(),
+                frame: WorldFrame::Ned,
             },
             visible: true,
+            ghost: None,
+            vectors: Vec::new(),
+            trail_colormap: None,
+            point_clouds: Vec::new(),
+            session: None,
+            time_offset: 0.0,
         }
     }
 }
 
 impl VehicleConfig {
+    /// Default vehicle reading its attitude and position from the signals the topic aliases
+    /// find in the log. Falls back to the PX4 topics for whatever is not found.
+    pub fn from_aliases(profiles: &[AliasProfile], data_store: &DataStore) -> Self {
+        let mut vehicle = Self::default();
+        vehicle.auto_configure(profiles, data_store);
+        vehicle
+    }
+
+    /// Points orientation and position at the attitude and position topics the aliases
+    /// find in the log, leaving either unchanged when none is found. Returns whether
+    /// anything was detected.
+    pub fn auto_configure(&mut self, profiles: &[AliasProfile], data_store: &DataStore) -> bool {
+        let find = |canonical: &str| aliases::resolve(profiles, data_store, canonical);
+        // All components of a signal must come from the same topic.
+        let group = |prefix: &str, axes: &[&str]| -> Option<(String, Vec<String>)> {
+            let resolved: Vec<(&str, &str)> = axes
+                .iter()
+                .map(|axis| find(&format!("{}.{}", prefix, axis)))
+                .collect::<Option<_>>()?;
+            let topic = resolved[0].0;
+            resolved.iter().all(|(t, _)| *t == topic).then(|| {
+                let cols = resolved.iter().map(|(_, c)| c.to_string()).collect();
+                (topic.to_string(), cols)
+            })
+        };
+
+        let mut detected = false;
+        if let Some((topic, q)) = group("attitude.q", &["w", "x", "y", "z"]) {
+            self.orientation = OrientationMode::Quaternion {
+                topic,
+                qw: q[0].clone(),
+                qx: q[1].clone(),
+                qy: q[2].clone(),
+                qz: q[3].clone(),
+                frame: AttitudeFrame::NedFrd,
+                declination: 0.0,
+            };
+            detected = true;
+        } else if let Some((topic, e)) = group("attitude", &["roll", "pitch", "yaw"]) {
+            // Yaw beyond a full turn in radians can only be degrees.
+            let degrees = data_store
+                .get_column(&topic, &e[2])
+                .is_some_and(|yaw| yaw.iter().any(|v| v.abs() > std::f32::consts::TAU));
+            self.orientation = OrientationMode::Euler {
+                topic,
+                roll: e[0].clone(),
+                pitch: e[1].clone(),
+                yaw: e[2].clone(),
+                angle_unit: if degrees {
+                    AngleUnit::Degrees
+                } else {
+                    AngleUnit::Radians
+                },
+                frame: AttitudeFrame::NedFrd,
+                declination: 0.0,
+            };
+            detected = true;
+        }
+
+        if let Some((topic, ned)) = group("position.ned", &["x", "y", "z"]) {
+            // The origin is only usable when logged alongside the position.
+            let refs = group("position.ref", &["x", "y", "z"])
+                .filter(|(ref_topic, _)| *ref_topic == topic)
+                .map(|(_, cols)| cols)
+                .unwrap_or_else(|| vec![String::new(); 3]);
+            self.position = PositionMode::LocalNED {
+                topic,
+                north: ned[0].clone(),
+                east: ned[1].clone(),
+                down: ned[2].clone(),
+                lat_ref: refs[0].clone(),
+                lon_ref: refs[1].clone(),
+                alt_ref: refs[2].clone(),
+                frame: WorldFrame::Ned,
+            };
+            detected = true;
+        } else if let Some((topic, gps)) = group("position.gps", &["x", "y", "z"]) {
+            self.position = PositionMode::GlobalGPS {
+                topic,
+                lat: gps[0].clone(),
+                lon: gps[1].clone(),
+                alt: gps[2].clone(),
+            };
+            detected = true;
+        }
+        detected
+    }
+
+    /// Time in the vehicle's log at timeline time `t`.
+    pub fn local_time(&self, t: f32) -> f32 {
+        t - self.time_offset
+    }
+
+    /// Timeline time of time `t` in the vehicle's log.
+    pub fn timeline_time(&self, t: f32) -> f32 {
+        t + self.time_offset
+    }
+
+    /// Moves every topic of the vehicle over to the same topic in another log session.
+    pub fn set_session(&mut self, session: Option<String>) {
+        let from = self.session.take();
+        for topic in self.topics_mut() {
+            if topic.is_empty() {
+                continue;
+            }
+            let bare = match &from {
+                Some(from) => topic
+                    .strip_prefix(from.as_str())
+                    .and_then(|rest| rest.strip_prefix(SESSION_SEPARATOR))
+                    .unwrap_or(topic),
+                None => topic,
+            }
+            .to_string();
+            *topic = match &session {
+                Some(session) => session_topic(session, &bare),
+                None => bare,
+            };
+        }
+        self.session = session;
+    }
+
+    fn topics_mut(&mut self) -> Vec<&mut String> {
+        fn orientation_topic(mode: &mut OrientationMode) -> Option<&mut String> {
+            match mode {
+                OrientationMode::Quaternion { topic, .. }
+                | OrientationMode::Euler { topic, .. } => Some(topic),
+                OrientationMode::Static => None,
+            }
+        }
+        fn position_topic(mode: &mut PositionMode) -> &mut String {
+            match mode {
+                PositionMode::LocalNED { topic, .. } | PositionMode::GlobalGPS { topic, .. } => {
+                    topic
+                }
+            }
+        }
+
+        let mut topics: Vec<&mut String> = vec![position_topic(&mut self.position)];
+        topics.extend(orientation_topic(&mut self.orientation));
+        if let Some(ghost) = &mut self.ghost {
+            topics.extend(ghost.orientation.as_mut().and_then(orientation_topic));
+            topics.extend(ghost.position.as_mut().map(position_topic));
+        }
+        topics.extend(self.vectors.iter_mut().map(|v| &mut v.topic));
+        topics.extend(self.trail_colormap.iter_mut().map(|c| &mut c.topic));
+        topics.extend(self.point_clouds.iter_mut().map(|c| &mut c.topic));
+        topics
+    }
+
+    /// Topics this vehicle reads its position and orientation from.
+    pub fn topics(&self) -> Vec<&str> {
+        let mut topics = vec![match &self.position {
+            PositionMode::LocalNED { topic, .. } | PositionMode::GlobalGPS { topic, .. } => {
+                topic.as_str()
+            }
+        }];
+        match &self.orientation {
+            OrientationMode::Quaternion { topic, .. } | OrientationMode::Euler { topic, .. } => {
+                topics.push(topic.as_str())
+            }
+            OrientationMode::Static => {}
+        }
+        topics
+    }
+
     pub fn evaluate_at(&self, data_store: &DataStore, t: f32) -> (glam::Vec3, glam::Quat) {
-        let pos = self.evaluate_position(data_store, t);
-        let rot = self.evaluate_orientation(data_store, t);
+        let pos = Self::evaluate_position(&self.position, data_store, t);
+        let rot = Self::evaluate_orientation(&self.orientation, data_store, t);
         (pos, rot)
     }
 
+    /// Latitude, longitude and altitude of the local NED origin.
+    pub fn gps_reference(&self, data_store: &DataStore) -> Option<(f64, f64, f64)> {
+        let first = |topic: &str, col: &str| {
+            data_store
+                .get_column(topic, col)?
+                .iter()
+                .find(|v| v.is_finite())
+                .map(|&v| v as f64)
+        };
+        match &self.position {
+            PositionMode::LocalNED {
+                topic,
+                lat_ref,
+                lon_ref,
+                alt_ref,
+                ..
+            } => Some((
+                first(topic, lat_ref)?,
+                first(topic, lon_ref)?,
+                first(topic, alt_ref)?,
+            )),
+            PositionMode::GlobalGPS {
+                topic,
+                lat,
+                lon,
+                alt,
+            } => Some((first(topic, lat)?, first(topic, lon)?, first(topic, alt)?)),
+        }
+    }
+
+    /// Pose of the setpoint ghost, if enabled. Sources left unset follow the actual state.
+    pub fn evaluate_ghost_at(
+        &self,
+        data_store: &DataStore,
+        t: f32,
+    ) -> Option<(glam::Vec3, glam::Quat)> {
+        let ghost = self.ghost.as_ref().filter(|g| g.enabled)?;
+        let position = ghost.position.as_ref().unwrap_or(&self.position);
+        let orientation = ghost.orientation.as_ref().unwrap_or(&self.orientation);
+        Some((
+            Self::evaluate_position(position, data_store, t),
+            Self::evaluate_orientation(orientation, data_store, t),
+        ))
+    }
+
+    /// Timestamps of the position topic, one per trail point.
+    pub fn position_timestamps<'a>(&self, data_store: &'a DataStore) -> Option<&'a [f32]> {
+        match &self.position {
+            PositionMode::LocalNED { topic, .. } | PositionMode::GlobalGPS { topic, .. } => {
+                data_store.get_column(topic, "timestamp").map(Vec::as_slice)
+            }
+        }
+    }
+
+    /// Timestamps and NED positions of every sample of the position topic.
+    pub fn trail<'a>(&self, data_store: &'a DataStore) -> Option<(&'a [f32], Vec<glam::Vec3>)> {
+        match &self.position {
+            PositionMode::LocalNED {
+                topic,
+                north,
+                east,
+                down,
+                ..
+            } => {
+                let t = data_store.get_column(topic, "timestamp")?;
+                let x = data_store.get_column(topic, north)?;
+                let y = data_store.get_column(topic, east)?;
+                let z = data_store.get_column(topic, down)?;
+                let points = x
+                    .iter()
+                    .zip(y)
+                    .zip(z)
+                    .map(|((&x, &y), &z)| glam::Vec3::new(x, y, z))
+                    .collect();
+                Some((t, points))
+            }
+            PositionMode::GlobalGPS {
+                topic,
+                lat,
+                lon,
+                alt,
+            } => {
+                let t = data_store.get_column(topic, "timestamp")?;
+                let lat_vals = data_store.get_column(topic, lat)?;
+                let lon_vals = data_store.get_column(topic, lon)?;
+                let alt_vals = data_store.get_column(topic, alt)?;
+                let (&lat_ref, &lon_ref, &alt_ref) =
+                    (lat_vals.first()?, lon_vals.first()?, alt_vals.first()?);
+                let points = lat_vals
+                    .iter()
+                    .zip(lon_vals)
+                    .zip(alt_vals)
+                    .map(|((&lat, &lon), &alt)| {
+                        gps_to_ned(
+                            lat as f64,
+                            lon as f64,
+                            alt as f64,
+                            lat_ref as f64,
+                            lon_ref as f64,
+                            alt_ref as f64,
+                        )
+                    })
+                    .collect();
+                Some((t, points))
+            }
+        }
+    }
+
     fn get_value_at(data_store: &DataStore, topic: &str, col: &str, t: f32) -> f32 {
         if let Some(timestamps) = data_store.get_column(topic, "timestamp") {
             if let Some(values) = data_store.get_column(topic, col) {
@@ -170,42 +715,20 @@ impl VehicleConfig {
         0.0
     }
 
-    pub fn gps_to_ned(
-        lat: f64,
-        lon: f64,
-        alt: f64,
-        lat_ref: f64,
-        lon_ref: f64,
-        alt_ref: f64,
-    ) -> glam::Vec3 {
-        let lat_rad = lat.to_radians();
-        let lon_rad = lon.to_radians();
-        let lat_ref_rad = lat_ref.to_radians();
-        let lon_ref_rad = lon_ref.to_radians();
-
-        let d_lat = lat_rad - lat_ref_rad;
-        let d_lon = lon_rad - lon_ref_rad;
-
-        let north = (d_lat * EARTH_RADIUS) as f32;
-        let east = (d_lon * EARTH_RADIUS * lat_ref_rad.cos()) as f32;
-        let down = -(alt - alt_ref) as f32;
-
-        glam::Vec3::new(north, east, down)
-    }
-
-    fn evaluate_position(&self, ds: &DataStore, t: f32) -> glam::Vec3 {
-        match &self.position {
+    fn evaluate_position(mode: &PositionMode, ds: &DataStore, t: f32) -> glam::Vec3 {
+        match mode {
             PositionMode::LocalNED {
                 topic,
                 north,
                 east,
                 down,
+                frame,
                 ..
             } => {
                 let x = Self::get_value_at(ds, topic, north, t);
                 let y = Self::get_value_at(ds, topic, east, t);
                 let z = Self::get_value_at(ds, topic, down, t);
-                glam::Vec3::new(x, y, z)
+                frame.to_ned(glam::Vec3::new(x, y, z))
             }
             PositionMode::GlobalGPS {
                 topic,
@@ -232,13 +755,13 @@ impl VehicleConfig {
                 let lon_val = Self::get_value_at(ds, topic, lon, t) as f64;
                 let alt_val = Self::get_value_at(ds, topic, alt, t) as f64;
 
-                Self::gps_to_ned(lat_val, lon_val, alt_val, lat_ref, lon_ref, alt_ref)
+                gps_to_ned(lat_val, lon_val, alt_val, lat_ref, lon_ref, alt_ref)
             }
         }
     }
 
-    fn evaluate_orientation(&self, ds: &DataStore, t: f32) -> glam::Quat {
-        match &self.orientation {
+    fn evaluate_orientation(mode: &OrientationMode, ds: &DataStore, t: f32) -> glam::Quat {
+        match mode {
             OrientationMode::Static => glam::Quat::IDENTITY,
             OrientationMode::Quaternion {
                 topic,
@@ -246,6 +769,8 @@ impl VehicleConfig {
                 qy,
                 qz,
                 qw,
+                frame,
+                declination,
             } => {
                 let x = Self::get_value_at(ds, topic, qx, t);
                 let y = Self::get_value_at(ds, topic, qy, t);
@@ -256,7 +781,7 @@ impl VehicleConfig {
                 if q.length_squared() < 1e-6 {
                     glam::Quat::IDENTITY
                 } else {
-                    q.normalize()
+                    frame.to_ned_frd(q.normalize(), *declination)
                 }
             }
             OrientationMode::Euler {
@@ -265,6 +790,8 @@ impl VehicleConfig {
                 pitch,
                 yaw,
                 angle_unit,
+                frame,
+                declination,
             } => {
                 let mut r = Self::get_value_at(ds, topic, roll, t);
                 let mut p = Self::get_value_at(ds, topic, pitch, t);
@@ -276,7 +803,8 @@ impl VehicleConfig {
                     y = y.to_radians();
                 }
 
-                glam::Quat::from_euler(glam::EulerRot::XYZ, r, p, y)
+                let q = glam::Quat::from_euler(glam::EulerRot::XYZ, r, p, y);
+                frame.to_ned_frd(q, *declination)
             }
         }
     }
@@ -286,10 +814,14 @@ pub fn render_configuration_tab(
     ui: &mut egui::Ui,
     vehicles: &mut Vec<VehicleConfig>,
     data_store: &DataStore,
+    alias_profiles: &[AliasProfile],
 ) {
     ui.add_space(10.0);
-    if ui.button(format!("{} Add Vehicle", icons::PLUS)).clicked() {
-        vehicles.push(VehicleConfig::default());
+    if ui
+        .button(format!("{} {}", icons::PLUS, tr("Add Vehicle")))
+        .clicked()
+    {
+        vehicles.push(VehicleConfig::from_aliases(alias_profiles, data_store));
     }
     ui.separator();
 
@@ -299,7 +831,7 @@ pub fn render_configuration_tab(
         for (idx, vehicle) in vehicles.iter_mut().enumerate() {
             let vehicle_id = vehicle.id;
             ui.push_id(vehicle_id, |ui| {
-                let header_text = format!("Vehicle #{}", idx + 1);
+                let header_text = format!("{} #{}", tr("Vehicle"), idx + 1);
 
                 egui::CollapsingHeader::new(header_text)
                     .id_salt(vehicle_id)
@@ -309,12 +841,30 @@ pub fn render_configuration_tab(
 
                         ui.add_space(10.0);
 
-                        if ui
-                            .button(format!("{} Remove Vehicle", icons::TRASH))
-                            .clicked()
-                        {
-                            remove_idx = Some(idx);
-                        }
+                        ui.horizontal(|ui| {
+                            if ui
+                                .button(format!(
+                                    "{} {}",
+                                    icons::MAGIC_WAND,
+                                    tr("Auto-configure Vehicle")
+                                ))
+                                .on_hover_text(tr(
+                                    "Detect attitude and position topics of PX4, ArduPilot \
+                                     or MAVLink logs through the topic aliases",
+                                ))
+                                .clicked()
+                                && !vehicle.auto_configure(alias_profiles, data_store)
+                            {
+                                tracing::warn!("No known attitude or position topics found");
+                            }
+
+                            if ui
+                                .button(format!("{} {}", icons::TRASH, tr("Remove Vehicle")))
+                                .clicked()
+                            {
+                                remove_idx = Some(idx);
+                            }
+                        });
                     });
             });
             ui.separator();
@@ -332,11 +882,11 @@ fn render_vehicle_config(ui: &mut egui::Ui, vehicle: &mut VehicleConfig, ds: &Da
         .spacing([40.0, 8.0])
         .striped(true)
         .show(ui, |ui| {
-            ui.label("Name");
+            ui.label(tr("Name"));
             ui.text_edit_singleline(&mut vehicle.name);
             ui.end_row();
 
-            ui.label("Type");
+            ui.label(tr("Type"));
             egui::ComboBox::from_id_salt("v_type")
                 .selected_text(format!("{:?}", vehicle.vehicle_type))
                 .show_ui(ui, |ui| {
@@ -373,15 +923,32 @@ fn render_vehicle_config(ui: &mut egui::Ui, vehicle: &mut VehicleConfig, ds: &Da
                 });
             ui.end_row();
 
-            ui.label("Vehicle Color");
+            ui.label(tr("Vehicle Color"));
             ui.color_edit_button_rgb(&mut vehicle.color);
             ui.end_row();
 
-            ui.label("Path Color");
-            ui.color_edit_button_rgb(&mut vehicle.path_color);
+            ui.label(tr("Path Color"));
+            ui.horizontal(|ui| {
+                ui.color_edit_button_rgb(&mut vehicle.path_color);
+                let mut by_column = vehicle.trail_colormap.as_ref().is_some_and(|c| c.enabled);
+                if ui
+                    .checkbox(&mut by_column, tr("By Column"))
+                    .on_hover_text(tr("Color the trail by a data column"))
+                    .changed()
+                {
+                    vehicle
+                        .trail_colormap
+                        .get_or_insert_with(TrailColorMap::default)
+                        .enabled = by_column;
+                }
+            });
             ui.end_row();
 
-            ui.label("Scale");
+            if let Some(colormap) = vehicle.trail_colormap.as_mut().filter(|c| c.enabled) {
+                render_trail_colormap_config(ui, ds, colormap);
+            }
+
+            ui.label(tr("Scale"));
             ui.allocate_ui_with_layout(
                 egui::vec2(ui.available_width(), ui.spacing().interact_size.y),
                 egui::Layout::left_to_right(egui::Align::Center),
@@ -396,162 +963,623 @@ fn render_vehicle_config(ui: &mut egui::Ui, vehicle: &mut VehicleConfig, ds: &Da
             );
             ui.end_row();
 
-            ui.label(egui::RichText::new("Orientation").strong());
+            let sessions = ds.sessions();
+            if !sessions.is_empty() || vehicle.session.is_some() {
+                ui.label(tr("Log Session"));
+                let mut session = vehicle.session.clone();
+                egui::ComboBox::from_id_salt("v_session")
+                    .selected_text(session.as_deref().unwrap_or(tr("Main log")))
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut session, None, tr("Main log"));
+                        for name in sessions {
+                            ui.selectable_value(&mut session, Some(name.to_string()), name);
+                        }
+                    });
+                if session != vehicle.session {
+                    vehicle.set_session(session);
+                }
+                ui.end_row();
+
+                ui.label(tr("Time Offset"));
+                ui.add(
+                    egui::DragValue::new(&mut vehicle.time_offset)
+                        .speed(0.05)
+                        .suffix(" s"),
+                )
+                .on_hover_text(tr(
+                    "Shifts this vehicle's log to line it up with the timeline",
+                ));
+                ui.end_row();
+            }
+
+            ui.label(egui::RichText::new(tr("Orientation")).strong());
             ui.horizontal(|ui| {
-                ui.selectable_value(&mut vehicle.orientation, OrientationMode::Static, "Static");
-
-                let is_quat = matches!(vehicle.orientation, OrientationMode::Quaternion { .. });
-                if ui.selectable_label(is_quat, "Quaternion").clicked() {
-                    vehicle.orientation = OrientationMode::Quaternion {
-                        topic: "".to_string(),
-                        qx: "qx".to_string(),
-                        qy: "qy".to_string(),
-                        qz: "qz".to_string(),
-                        qw: "qw".to_string(),
-                    };
+                if let Some(mode) = orientation_mode_buttons(ui, Some(&vehicle.orientation)) {
+                    vehicle.orientation = mode;
                 }
+            });
+            ui.end_row();
+            render_orientation_columns(ui, ds, &mut vehicle.orientation, "");
 
-                let is_euler = matches!(vehicle.orientation, OrientationMode::Euler { .. });
-                if ui.selectable_label(is_euler, "Euler").clicked() {
-                    vehicle.orientation = OrientationMode::Euler {
-                        topic: "".to_string(),
-                        roll: "roll".to_string(),
-                        pitch: "pitch".to_string(),
-                        yaw: "yaw".to_string(),
-                        angle_unit: AngleUnit::Radians,
-                    };
+            ui.label("");
+            ui.end_row();
+
+            ui.label(egui::RichText::new(tr("Position")).strong());
+            ui.horizontal(|ui| {
+                if let Some(mode) = position_mode_buttons(ui, Some(&vehicle.position)) {
+                    vehicle.position = mode;
                 }
             });
             ui.end_row();
+            render_position_columns(ui, ds, &mut vehicle.position, "");
 
-            match &mut vehicle.orientation {
-                OrientationMode::Static => {
-                    ui.label("Info");
-                    ui.label("Uses Identity rotation");
-                    ui.end_row();
+            ui.label("");
+            ui.end_row();
+
+            render_ghost_config(ui, ds, &mut vehicle.ghost);
+        });
+
+    ui.add_space(8.0);
+    render_vector_overlays(ui, ds, &mut vehicle.vectors);
+
+    ui.add_space(8.0);
+    render_point_clouds(ui, ds, &mut vehicle.point_clouds);
+}
+
+fn render_vector_overlays(ui: &mut egui::Ui, ds: &DataStore, vectors: &mut Vec<VectorOverlay>) {
+    ui.label(egui::RichText::new(tr("Vector Overlays")).strong());
+
+    let mut remove_idx = None;
+    for (idx, vector) in vectors.iter_mut().enumerate() {
+        ui.push_id(idx, |ui| {
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut vector.visible, "");
+                ui.add(egui::TextEdit::singleline(&mut vector.name).desired_width(120.0));
+                ui.color_edit_button_rgb(&mut vector.color);
+                if ui
+                    .button(icons::TRASH)
+                    .on_hover_text(tr("Remove vector"))
+                    .clicked()
+                {
+                    remove_idx = Some(idx);
                 }
-                OrientationMode::Quaternion {
-                    topic,
-                    qx,
-                    qy,
-                    qz,
-                    qw,
-                } => {
-                    render_topic_selector(ui, ds, topic, "Orient. Topic");
-                    ui.end_row();
-                    render_col_selector(ui, ds, topic, qx, "QX");
-                    ui.end_row();
-                    render_col_selector(ui, ds, topic, qy, "QY");
+            });
+
+            egui::Grid::new("vector_grid")
+                .num_columns(2)
+                .spacing([40.0, 8.0])
+                .show(ui, |ui| {
+                    render_topic_selector(ui, ds, &mut vector.topic, tr("Topic"));
                     ui.end_row();
-                    render_col_selector(ui, ds, topic, qz, "QZ");
+                    render_col_selector(ui, ds, &vector.topic, &mut vector.x, "X");
                     ui.end_row();
-                    render_col_selector(ui, ds, topic, qw, "QW");
+                    render_col_selector(ui, ds, &vector.topic, &mut vector.y, "Y");
                     ui.end_row();
-                }
-                OrientationMode::Euler {
-                    topic,
-                    roll,
-                    pitch,
-                    yaw,
-                    angle_unit,
-                } => {
-                    render_topic_selector(ui, ds, topic, "Orient. Topic");
+                    render_col_selector(ui, ds, &vector.topic, &mut vector.z, "Z");
                     ui.end_row();
 
-                    ui.label("Angle Unit");
+                    ui.label(tr("Frame"));
                     ui.horizontal(|ui| {
-                        ui.selectable_value(angle_unit, AngleUnit::Radians, "Radians");
-                        ui.selectable_value(angle_unit, AngleUnit::Degrees, "Degrees");
+                        ui.selectable_value(&mut vector.frame, VectorFrame::Ned, "NED");
+                        ui.selectable_value(&mut vector.frame, VectorFrame::Body, tr("Body"));
                     });
                     ui.end_row();
 
-                    render_col_selector(ui, ds, topic, roll, "Roll");
-                    ui.end_row();
-                    render_col_selector(ui, ds, topic, pitch, "Pitch");
-                    ui.end_row();
-                    render_col_selector(ui, ds, topic, yaw, "Yaw");
+                    ui.label(tr("Scale"));
+                    ui.add(
+                        egui::DragValue::new(&mut vector.scale)
+                            .speed(0.01)
+                            .range(0.001..=1000.0)
+                            .suffix(" m/unit"),
+                    );
                     ui.end_row();
-                }
+                });
+        });
+        ui.separator();
+    }
+
+    if let Some(idx) = remove_idx {
+        vectors.remove(idx);
+    }
+
+    ui.menu_button(format!("{} {}", icons::PLUS, tr("Add Vector")), |ui| {
+        for preset in VectorOverlay::presets() {
+            if ui.button(&preset.name).clicked() {
+                vectors.push(preset);
+                ui.close_menu();
             }
+        }
+    });
+}
 
-            ui.label("");
-            ui.end_row();
+fn render_point_clouds(ui: &mut egui::Ui, ds: &DataStore, clouds: &mut Vec<PointCloud>) {
+    ui.label(egui::RichText::new(tr("Point Clouds")).strong());
 
-            ui.label(egui::RichText::new("Position").strong());
+    let mut remove_idx = None;
+    for (idx, cloud) in clouds.iter_mut().enumerate() {
+        ui.push_id(("point_cloud", idx), |ui| {
             ui.horizontal(|ui| {
-                let is_ned = matches!(vehicle.position, PositionMode::LocalNED { .. });
-                if ui.selectable_label(is_ned, "Local (NED)").clicked() {
-                    vehicle.position = PositionMode::LocalNED {
-                        topic: "".to_string(),
-                        north: "x".to_string(),
-                        east: "y".to_string(),
-                        down: "z".to_string(),
-                        lat_ref: "ref_lat".to_string(),
-                        lon_ref: "ref_lon".to_string(),
-                        alt_ref: "ref_alt".to_string(),
-                    };
-                }
-
-                let is_gps = matches!(vehicle.position, PositionMode::GlobalGPS { .. });
-                if ui.selectable_label(is_gps, "Global (GPS)").clicked() {
-                    vehicle.position = PositionMode::GlobalGPS {
-                        topic: "".to_string(),
-                        lat: "lat".to_string(),
-                        lon: "lon".to_string(),
-                        alt: "alt".to_string(),
-                    };
+                ui.checkbox(&mut cloud.visible, "");
+                ui.add(egui::TextEdit::singleline(&mut cloud.name).desired_width(120.0));
+                ui.color_edit_button_rgb(&mut cloud.color);
+                if ui
+                    .button(icons::TRASH)
+                    .on_hover_text(tr("Remove point cloud"))
+                    .clicked()
+                {
+                    remove_idx = Some(idx);
                 }
             });
-            ui.end_row();
 
-            match &mut vehicle.position {
-                PositionMode::LocalNED {
-                    topic,
-                    north,
-                    east,
-                    down,
-                    lat_ref,
-                    lon_ref,
-                    alt_ref,
-                } => {
-                    render_topic_selector(ui, ds, topic, "Pos. Topic");
-                    ui.end_row();
-                    render_col_selector(ui, ds, topic, north, "North (X)");
-                    ui.end_row();
-                    render_col_selector(ui, ds, topic, east, "East (Y)");
+            egui::Grid::new("point_cloud_grid")
+                .num_columns(2)
+                .spacing([40.0, 8.0])
+                .show(ui, |ui| {
+                    render_topic_selector(ui, ds, &mut cloud.topic, tr("Topic"));
                     ui.end_row();
-                    render_col_selector(ui, ds, topic, down, "Down (Z)");
-                    ui.end_row();
-                    render_col_selector(ui, ds, topic, lat_ref, "Ref Latitude");
-                    ui.end_row();
-                    render_col_selector(ui, ds, topic, lon_ref, "Ref Longitude");
-                    ui.end_row();
-                    render_col_selector(ui, ds, topic, alt_ref, "Ref Altitude");
-                    ui.end_row();
-                }
-                PositionMode::GlobalGPS {
-                    topic,
-                    lat,
-                    lon,
-                    alt,
-                } => {
-                    render_topic_selector(ui, ds, topic, "Pos. Topic");
-                    ui.end_row();
-                    render_col_selector(ui, ds, topic, lat, "Latitude");
-                    ui.end_row();
-                    render_col_selector(ui, ds, topic, lon, "Longitude");
+
+                    match &mut cloud.source {
+                        PointCloudSource::Polar {
+                            ranges,
+                            increment,
+                            angle_offset,
+                            range_scale,
+                            max_range,
+                        } => {
+                            render_array_selector(ui, ds, &cloud.topic, ranges, tr("Ranges"));
+                            ui.end_row();
+                            render_col_selector(
+                                ui,
+                                ds,
+                                &cloud.topic,
+                                increment,
+                                tr("Increment (deg)"),
+                            );
+                            ui.end_row();
+                            render_col_selector(
+                                ui,
+                                ds,
+                                &cloud.topic,
+                                angle_offset,
+                                tr("Offset (deg)"),
+                            );
+                            ui.end_row();
+
+                            ui.label(tr("Range Scale"));
+                            ui.add(
+                                egui::DragValue::new(range_scale)
+                                    .speed(0.001)
+                                    .range(0.0001..=1000.0)
+                                    .suffix(" m/unit"),
+                            );
+                            ui.end_row();
+
+                            ui.label(tr("Max Range"));
+                            ui.add(
+                                egui::DragValue::new(max_range)
+                                    .speed(0.5)
+                                    .range(0.1..=10000.0)
+                                    .suffix(" m"),
+                            );
+                            ui.end_row();
+                        }
+                        PointCloudSource::Cartesian { x, y, z } => {
+                            render_array_selector(ui, ds, &cloud.topic, x, "X");
+                            ui.end_row();
+                            render_array_selector(ui, ds, &cloud.topic, y, "Y");
+                            ui.end_row();
+                            render_array_selector(ui, ds, &cloud.topic, z, "Z");
+                            ui.end_row();
+                        }
+                    }
+
+                    ui.label(tr("Frame"));
+                    ui.horizontal(|ui| {
+                        ui.selectable_value(&mut cloud.frame, VectorFrame::Ned, "NED");
+                        ui.selectable_value(&mut cloud.frame, VectorFrame::Body, tr("Body"));
+                    });
                     ui.end_row();
-                    render_col_selector(ui, ds, topic, alt, "Altitude");
+
+                    ui.label(tr("History"));
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut cloud.accumulate, tr("Accumulate"))
+                            .on_hover_text(tr("Keep every sample up to the current time"));
+                        ui.add_enabled(
+                            !cloud.accumulate,
+                            egui::DragValue::new(&mut cloud.decay)
+                                .speed(0.1)
+                                .range(0.0..=600.0)
+                                .suffix(" s decay"),
+                        );
+                    });
                     ui.end_row();
 
-                    ui.label("Info");
-                    ui.label("Uses first position as origin");
+                    ui.label(tr("Point Size"));
+                    ui.add(
+                        egui::DragValue::new(&mut cloud.point_size)
+                            .speed(0.1)
+                            .range(1.0..=20.0),
+                    );
                     ui.end_row();
+                });
+        });
+        ui.separator();
+    }
+
+    if let Some(idx) = remove_idx {
+        clouds.remove(idx);
+    }
+
+    ui.menu_button(format!("{} {}", icons::PLUS, tr("Add Point Cloud")), |ui| {
+        for preset in PointCloud::presets() {
+            if ui.button(&preset.name).clicked() {
+                clouds.push(preset);
+                ui.close_menu();
+            }
+        }
+    });
+}
+
+/// Picks an array field of `topic`, stored without the `[i]` suffix.
+fn render_array_selector(
+    ui: &mut egui::Ui,
+    ds: &DataStore,
+    topic: &str,
+    selected: &mut String,
+    label: &str,
+) {
+    ui.label(label);
+
+    if topic.is_empty() {
+        ui.label(
+            egui::RichText::new(tr("Select topic first"))
+                .italics()
+                .weak(),
+        );
+        return;
+    }
+
+    let selected_text = if selected.is_empty() {
+        tr("Select Array...")
+    } else {
+        selected.as_str()
+    };
+    egui::ComboBox::from_id_salt(label)
+        .selected_text(selected_text.to_string())
+        .show_ui(ui, |ui| {
+            let fields = array_fields(ds, topic);
+            if fields.is_empty() {
+                ui.label(tr("No array columns"));
+            }
+            for field in fields {
+                let checked = *selected == field;
+                if ui.selectable_label(checked, &field).clicked() {
+                    *selected = field;
                 }
             }
         });
 }
 
+fn render_trail_colormap_config(ui: &mut egui::Ui, ds: &DataStore, colormap: &mut TrailColorMap) {
+    render_topic_selector(ui, ds, &mut colormap.topic, tr("Color Topic"));
+    ui.end_row();
+    render_col_selector(
+        ui,
+        ds,
+        &colormap.topic,
+        &mut colormap.column,
+        tr("Color Column"),
+    );
+    ui.end_row();
+
+    ui.label(tr("Colormap"));
+    egui::ComboBox::from_id_salt("trail_colormap")
+        .selected_text(colormap.colormap.name())
+        .show_ui(ui, |ui| {
+            for map in Colormap::ALL {
+                ui.selectable_value(&mut colormap.colormap, map, map.name());
+            }
+        });
+    ui.end_row();
+
+    ui.label(tr("Range"));
+    ui.horizontal(|ui| {
+        let mut auto = colormap.range.is_none();
+        if ui.checkbox(&mut auto, tr("Auto")).changed() {
+            colormap.range = if auto {
+                None
+            } else {
+                Some(colormap.value_range(ds).unwrap_or([0.0, 1.0]))
+            };
+        }
+        if let Some(range) = &mut colormap.range {
+            ui.add(egui::DragValue::new(&mut range[0]).speed(0.1));
+            ui.label(tr("to"));
+            ui.add(egui::DragValue::new(&mut range[1]).speed(0.1));
+        }
+    });
+    ui.end_row();
+}
+
+fn render_ghost_config(ui: &mut egui::Ui, ds: &DataStore, ghost: &mut Option<GhostConfig>) {
+    ui.label(egui::RichText::new(tr("Setpoint Ghost")).strong());
+    let mut enabled = ghost.as_ref().is_some_and(|g| g.enabled);
+    if ui
+        .checkbox(&mut enabled, tr("Show"))
+        .on_hover_text(tr("Draw a translucent model at the setpoint pose"))
+        .changed()
+    {
+        ghost.get_or_insert_with(GhostConfig::default).enabled = enabled;
+    }
+    ui.end_row();
+
+    let Some(ghost) = ghost.as_mut().filter(|g| g.enabled) else {
+        return;
+    };
+
+    ui.label(tr("Opacity"));
+    ui.add(egui::Slider::new(&mut ghost.opacity, 0.05..=1.0));
+    ui.end_row();
+
+    ui.label(tr("Setpoint Attitude"));
+    ui.horizontal(|ui| {
+        if ui
+            .selectable_label(ghost.orientation.is_none(), tr("Actual"))
+            .clicked()
+        {
+            ghost.orientation = None;
+        }
+        if let Some(mode) = orientation_mode_buttons(ui, ghost.orientation.as_ref()) {
+            ghost.orientation = Some(mode);
+        }
+    });
+    ui.end_row();
+    if let Some(mode) = &mut ghost.orientation {
+        render_orientation_columns(ui, ds, mode, "SP ");
+    }
+
+    ui.label(tr("Setpoint Position"));
+    ui.horizontal(|ui| {
+        if ui
+            .selectable_label(ghost.position.is_none(), tr("Actual"))
+            .clicked()
+        {
+            ghost.position = None;
+        }
+        if let Some(mode) = position_mode_buttons(ui, ghost.position.as_ref()) {
+            ghost.position = Some(mode);
+        }
+    });
+    ui.end_row();
+    if let Some(mode) = &mut ghost.position {
+        render_position_columns(ui, ds, mode, "SP ");
+    }
+}
+
+/// Mode buttons for an orientation source. Returns the newly selected mode.
+fn orientation_mode_buttons(
+    ui: &mut egui::Ui,
+    current: Option<&OrientationMode>,
+) -> Option<OrientationMode> {
+    let mut selected = None;
+
+    let is_static = matches!(current, Some(OrientationMode::Static));
+    if ui.selectable_label(is_static, tr("Static")).clicked() {
+        selected = Some(OrientationMode::Static);
+    }
+
+    let is_quat = matches!(current, Some(OrientationMode::Quaternion { .. }));
+    if ui.selectable_label(is_quat, tr("Quaternion")).clicked() {
+        selected = Some(OrientationMode::Quaternion {
+            topic: "".to_string(),
+            qx: "qx".to_string(),
+            qy: "qy".to_string(),
+            qz: "qz".to_string(),
+            qw: "qw".to_string(),
+            frame: AttitudeFrame::NedFrd,
+            declination: 0.0,
+        });
+    }
+
+    let is_euler = matches!(current, Some(OrientationMode::Euler { .. }));
+    if ui.selectable_label(is_euler, tr("Euler")).clicked() {
+        selected = Some(OrientationMode::Euler {
+            topic: "".to_string(),
+            roll: "roll".to_string(),
+            pitch: "pitch".to_string(),
+            yaw: "yaw".to_string(),
+            angle_unit: AngleUnit::Radians,
+            frame: AttitudeFrame::NedFrd,
+            declination: 0.0,
+        });
+    }
+
+    selected
+}
+
+/// Grid rows for the columns of an orientation source. `prefix` keeps the selector ids
+/// of several sources in one grid apart.
+fn render_orientation_columns(
+    ui: &mut egui::Ui,
+    ds: &DataStore,
+    mode: &mut OrientationMode,
+    prefix: &str,
+) {
+    let label = |name: &str| format!("{}{}", prefix, name);
+    match mode {
+        OrientationMode::Static => {
+            ui.label(tr("Info"));
+            ui.label(tr("Uses Identity rotation"));
+            ui.end_row();
+        }
+        OrientationMode::Quaternion {
+            topic,
+            qx,
+            qy,
+            qz,
+            qw,
+            frame,
+            declination,
+        } => {
+            render_topic_selector(ui, ds, topic, &label(tr("Orient. Topic")));
+            ui.end_row();
+            render_col_selector(ui, ds, topic, qx, &label("QX"));
+            ui.end_row();
+            render_col_selector(ui, ds, topic, qy, &label("QY"));
+            ui.end_row();
+            render_col_selector(ui, ds, topic, qz, &label("QZ"));
+            ui.end_row();
+            render_col_selector(ui, ds, topic, qw, &label("QW"));
+            ui.end_row();
+            render_attitude_frame(ui, frame, declination);
+        }
+        OrientationMode::Euler {
+            topic,
+            roll,
+            pitch,
+            yaw,
+            angle_unit,
+            frame,
+            declination,
+        } => {
+            render_topic_selector(ui, ds, topic, &label(tr("Orient. Topic")));
+            ui.end_row();
+
+            ui.label(tr("Angle Unit"));
+            ui.horizontal(|ui| {
+                ui.selectable_value(angle_unit, AngleUnit::Radians, tr("Radians"));
+                ui.selectable_value(angle_unit, AngleUnit::Degrees, tr("Degrees"));
+            });
+            ui.end_row();
+
+            render_col_selector(ui, ds, topic, roll, &label("Roll"));
+            ui.end_row();
+            render_col_selector(ui, ds, topic, pitch, &label("Pitch"));
+            ui.end_row();
+            render_col_selector(ui, ds, topic, yaw, &label("Yaw"));
+            ui.end_row();
+            render_attitude_frame(ui, frame, declination);
+        }
+    }
+}
+
+fn render_attitude_frame(ui: &mut egui::Ui, frame: &mut AttitudeFrame, declination: &mut f32) {
+    ui.label(tr("Frame"));
+    ui.horizontal(|ui| {
+        ui.selectable_value(frame, AttitudeFrame::NedFrd, "NED / FRD");
+        ui.selectable_value(frame, AttitudeFrame::EnuFlu, "ENU / FLU (ROS)");
+    });
+    ui.end_row();
+
+    ui.label(tr("Declination"));
+    ui.add(
+        egui::DragValue::new(declination)
+            .speed(0.1)
+            .range(-180.0..=180.0)
+            .suffix("°"),
+    )
+    .on_hover_text(tr(
+        "Added to the heading, for attitudes relative to magnetic north",
+    ));
+    ui.end_row();
+}
+
+/// Mode buttons for a position source. Returns the newly selected mode.
+fn position_mode_buttons(
+    ui: &mut egui::Ui,
+    current: Option<&PositionMode>,
+) -> Option<PositionMode> {
+    let mut selected = None;
+
+    let is_ned = matches!(current, Some(PositionMode::LocalNED { .. }));
+    if ui.selectable_label(is_ned, tr("Local (NED)")).clicked() {
+        selected = Some(PositionMode::LocalNED {
+            topic: "".to_string(),
+            north: "x".to_string(),
+            east: "y".to_string(),
+            down: "z".to_string(),
+            lat_ref: "ref_lat".to_string(),
+            lon_ref: "ref_lon".to_string(),
+            alt_ref: "ref_alt".to_string(),
+            frame: WorldFrame::Ned,
+        });
+    }
+
+    let is_gps = matches!(current, Some(PositionMode::GlobalGPS { .. }));
+    if ui.selectable_label(is_gps, tr("Global (GPS)")).clicked() {
+        selected = Some(PositionMode::GlobalGPS {
+            topic: "".to_string(),
+            lat: "lat".to_string(),
+            lon: "lon".to_string(),
+            alt: "alt".to_string(),
+        });
+    }
+
+    selected
+}
+
+/// Grid rows for the columns of a position source, see [`render_orientation_columns`].
+fn render_position_columns(
+    ui: &mut egui::Ui,
+    ds: &DataStore,
+    mode: &mut PositionMode,
+    prefix: &str,
+) {
+    let label = |name: &str| format!("{}{}", prefix, name);
+    match mode {
+        PositionMode::LocalNED {
+            topic,
+            north,
+            east,
+            down,
+            lat_ref,
+            lon_ref,
+            alt_ref,
+            frame,
+        } => {
+            render_topic_selector(ui, ds, topic, &label(tr("Pos. Topic")));
+            ui.end_row();
+            ui.label(tr("Frame"));
+            ui.horizontal(|ui| {
+                ui.selectable_value(frame, WorldFrame::Ned, "NED");
+                ui.selectable_value(frame, WorldFrame::Enu, "ENU (ROS)");
+            });
+            ui.end_row();
+            render_col_selector(ui, ds, topic, north, &label(tr("North (X)")));
+            ui.end_row();
+            render_col_selector(ui, ds, topic, east, &label(tr("East (Y)")));
+            ui.end_row();
+            render_col_selector(ui, ds, topic, down, &label(tr("Down (Z)")));
+            ui.end_row();
+            render_col_selector(ui, ds, topic, lat_ref, &label(tr("Ref Latitude")));
+            ui.end_row();
+            render_col_selector(ui, ds, topic, lon_ref, &label(tr("Ref Longitude")));
+            ui.end_row();
+            render_col_selector(ui, ds, topic, alt_ref, &label(tr("Ref Altitude")));
+            ui.end_row();
+        }
+        PositionMode::GlobalGPS {
+            topic,
+            lat,
+            lon,
+            alt,
+        } => {
+            render_topic_selector(ui, ds, topic, &label(tr("Pos. Topic")));
+            ui.end_row();
+            render_col_selector(ui, ds, topic, lat, &label(tr("Latitude")));
+            ui.end_row();
+            render_col_selector(ui, ds, topic, lon, &label(tr("Longitude")));
+            ui.end_row();
+            render_col_selector(ui, ds, topic, alt, &label(tr("Altitude")));
+            ui.end_row();
+
+            ui.label(tr("Info"));
+            ui.label(tr("Uses first position as origin"));
+            ui.end_row();
+        }
+    }
+}
+
 fn render_topic_selector(ui: &mut egui::Ui, ds: &DataStore, selected: &mut String, label: &str) {
     ui.label(label);
 
@@ -559,7 +1587,7 @@ fn render_topic_selector(ui: &mut egui::Ui, ds: &DataStore, selected: &mut Strin
     let filter_id = ui.make_persistent_id(format!("topic_filter_{}", label));
 
     let button_text = if selected.is_empty() {
-        "Select Topic...".to_string()
+        tr("Select Topic...").to_string()
     } else {
         selected.clone()
     };
@@ -609,7 +1637,7 @@ fn render_topic_selector(ui: &mut egui::Ui, ds: &DataStore, selected: &mut Strin
                 for topic in topics {
                     if fuzzy_match(&topic.to_lowercase(), &filter_lower) {
                         found_any = true;
-                        if ui.selectable_label(*selected == *topic, &*topic).clicked() {
+                        if ui.selectable_label(*selected == *topic, topic).clicked() {
                             *selected = topic.clone();
                             ui.memory_mut(|mem| {
                                 mem.close_popup();
@@ -620,7 +1648,7 @@ fn render_topic_selector(ui: &mut egui::Ui, ds: &DataStore, selected: &mut Strin
                 }
 
                 if !found_any && !filter.is_empty() {
-                    ui.label(egui::RichText::new("No matches").italics().weak());
+                    ui.label(egui::RichText::new(tr("No matches")).italics().weak());
                 }
             });
         },
@@ -637,7 +1665,11 @@ fn render_col_selector(
     ui.label(label);
 
     if topic.is_empty() {
-        ui.label(egui::RichText::new("Select topic first").italics().weak());
+        ui.label(
+            egui::RichText::new(tr("Select topic first"))
+                .italics()
+                .weak(),
+        );
         return;
     }
 
@@ -645,7 +1677,7 @@ fn render_col_selector(
     let filter_id = ui.make_persistent_id(format!("col_filter_{}_{}", topic, label));
 
     let button_text = if selected.is_empty() {
-        "Select Column...".to_string()
+        tr("Select Column...").to_string()
     } else {
         selected.clone()
     };
@@ -695,7 +1727,7 @@ fn render_col_selector(
                 for col in cols {
                     if fuzzy_match(&col.to_lowercase(), &filter_lower) {
                         found_any = true;
-                        if ui.selectable_label(*selected == *col, &*col).clicked() {
+                        if ui.selectable_label(*selected == *col, col).clicked() {
                             *selected = col.clone();
                             ui.memory_mut(|mem| {
                                 mem.close_popup();
@@ -706,7 +1738,7 @@ fn render_col_selector(
                 }
 
                 if !found_any && !filter.is_empty() {
-                    ui.label(egui::RichText::new("No matches").italics().weak());
+                    ui.label(egui::RichText::new(tr("No matches")).italics().weak());
                 }
             });
         },
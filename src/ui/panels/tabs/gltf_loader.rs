@@ -1,10 +1,40 @@
+use crate::ui::scene_renderer::{MeshData, MeshVertex};
 use glam::Vec3;
 use std::collections::{HashMap, HashSet};
 
 #[derive(Clone, Debug)]
 pub struct Model {
     pub vertices: Vec<Vec3>,
+    pub normals: Vec<Vec3>,
+    /// Material base color of each vertex.
+    pub colors: Vec<[f32; 4]>,
+    pub triangles: Vec<[u32; 3]>,
     pub lines: Vec<[u32; 2]>,
+    /// False when every primitive uses the default material, in which case the model is
+    /// tinted with the vehicle color.
+    pub has_materials: bool,
+}
+
+impl Model {
+    pub fn mesh_data(&self) -> MeshData {
+        let vertices = self
+            .vertices
+            .iter()
+            .zip(&self.normals)
+            .zip(&self.colors)
+            .map(|((position, normal), color)| MeshVertex {
+                position: position.to_array(),
+                normal: normal.to_array(),
+                uv: [0.0, 0.0],
+                color: *color,
+            })
+            .collect();
+
+        MeshData {
+            vertices,
+            indices: self.triangles.iter().flatten().copied().collect(),
+        }
+    }
 }
 
 pub struct ModelCache {
@@ -39,7 +69,11 @@ impl ModelCache {
         buffers: Vec<gltf::buffer::Data>,
     ) -> Result<Model, Box<dyn std::error::Error>> {
         let mut all_vertices = Vec::new();
+        let mut all_normals = Vec::new();
+        let mut all_colors = Vec::new();
+        let mut triangles: Vec<[u32; 3]> = Vec::new();
         let mut unique_edges: HashSet<(u32, u32)> = HashSet::new();
+        let mut has_materials = false;
 
         for mesh in document.meshes() {
             for primitive in mesh.primitives() {
@@ -57,12 +91,27 @@ impl ModelCache {
                     continue;
                 }
 
+                // Missing normals are filled in from the faces below.
+                if let Some(normals) = reader.read_normals() {
+                    all_normals.extend(normals.map(Vec3::from));
+                }
+                all_normals.resize(all_vertices.len(), Vec3::ZERO);
+
+                let material = primitive.material();
+                has_materials |= material.index().is_some();
+                let base_color = material.pbr_metallic_roughness().base_color_factor();
+                all_colors.resize(all_vertices.len(), base_color);
+
                 let indices: Vec<u32> = if let Some(iter) = reader.read_indices() {
                     iter.into_u32().collect()
                 } else {
                     (0..vert_count).collect()
                 };
 
+                let mut add_triangle = |i1: u32, i2: u32, i3: u32| {
+                    triangles.push([base_index + i1, base_index + i2, base_index + i3]);
+                };
+
                 let mut add_edge = |i1: u32, i2: u32| {
                     let a = base_index + i1;
                     let b = base_index + i2;
@@ -77,6 +126,7 @@ impl ModelCache {
                     gltf::mesh::Mode::Triangles => {
                         for chunk in indices.chunks(3) {
                             if chunk.len() == 3 {
+                                add_triangle(chunk[0], chunk[1], chunk[2]);
                                 add_edge(chunk[0], chunk[1]);
                                 add_edge(chunk[1], chunk[2]);
                                 add_edge(chunk[2], chunk[0]);
@@ -85,6 +135,7 @@ impl ModelCache {
                     }
                     gltf::mesh::Mode::TriangleStrip => {
                         for i in 0..indices.len().saturating_sub(2) {
+                            add_triangle(indices[i], indices[i + 1], indices[i + 2]);
                             add_edge(indices[i], indices[i + 1]);
                             add_edge(indices[i + 1], indices[i + 2]);
                             add_edge(indices[i + 2], indices[i]);
@@ -92,6 +143,7 @@ impl ModelCache {
                     }
                     gltf::mesh::Mode::TriangleFan => {
                         for i in 1..indices.len().saturating_sub(1) {
+                            add_triangle(indices[0], indices[i], indices[i + 1]);
                             add_edge(indices[0], indices[i]);
                             add_edge(indices[i], indices[i + 1]);
                             add_edge(indices[i + 1], indices[0]);
@@ -130,11 +182,26 @@ impl ModelCache {
             }
         }
 
+        triangles.retain(|t| t.iter().all(|&i| (i as usize) < all_vertices.len()));
+        for [a, b, c] in &triangles {
+            let (a, b, c) = (*a as usize, *b as usize, *c as usize);
+            let face = (all_vertices[b] - all_vertices[a]).cross(all_vertices[c] - all_vertices[a]);
+            for i in [a, b, c] {
+                if all_normals[i] == Vec3::ZERO {
+                    all_normals[i] = face.normalize_or_zero();
+                }
+            }
+        }
+
         let lines: Vec<[u32; 2]> = unique_edges.into_iter().map(|(a, b)| [a, b]).collect();
 
         Ok(Model {
             vertices: all_vertices,
+            normals: all_normals,
+            colors: all_colors,
+            triangles,
             lines,
+            has_materials,
         })
     }
 
@@ -0,0 +1,217 @@
+use crate::core::DataStore;
+use serde_json::Value;
+use std::path::Path;
+
+/// MAVLink `MAV_FRAME_GLOBAL_RELATIVE_ALT` and its `_INT` variant.
+const FRAME_RELATIVE_ALT: [u64; 2] = [3, 6];
+const MAV_CMD_NAV_WAYPOINT: u64 = 16;
+
+#[derive(Clone, Debug)]
+pub struct Waypoint {
+    pub lat: f64,
+    pub lon: f64,
+    /// Altitude above mean sea level in meters.
+    pub alt: f64,
+    /// Zero when the mission doesn't specify one.
+    pub acceptance_radius: f32,
+}
+
+/// Planned waypoints shown next to the flown trajectory in the 3D scene.
+#[derive(Clone, Debug)]
+pub struct Mission {
+    pub name: String,
+    pub waypoints: Vec<Waypoint>,
+}
+
+impl Mission {
+    /// Loads a QGroundControl `.plan` file or a MAVLink `QGC WPL` waypoint list.
+    pub fn load_file(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+
+        let waypoints = if text.trim_start().starts_with("QGC WPL") {
+            parse_wpl(&text)?
+        } else {
+            parse_plan(&text)?
+        };
+
+        if waypoints.is_empty() {
+            return Err(format!("No waypoints in {}", path.display()));
+        }
+
+        Ok(Self {
+            name: path
+                .file_name()
+                .map_or("Mission".to_string(), |n| n.to_string_lossy().into_owned()),
+            waypoints,
+        })
+    }
+
+    /// Builds a mission from a topic with one row per mission item, such as PX4's
+    /// `navigator_mission_item`. Repeated rows for the same item are dropped.
+    pub fn from_topic(data_store: &DataStore, topic: &str) -> Result<Self, String> {
+        let columns = data_store.get_columns(topic);
+        let find = |names: &[&str]| {
+            names
+                .iter()
+                .find(|name| columns.iter().any(|c| c == *name))
+                .and_then(|name| data_store.get_column(topic, name))
+        };
+
+        let lat = find(&["latitude", "lat"]).ok_or("Topic has no latitude column")?;
+        let lon = find(&["longitude", "lon"]).ok_or("Topic has no longitude column")?;
+        let alt = find(&["altitude", "alt"]).ok_or("Topic has no altitude column")?;
+        let radius = find(&["acceptance_radius"]);
+
+        let mut waypoints: Vec<Waypoint> = Vec::new();
+        for i in 0..lat.len().min(lon.len()).min(alt.len()) {
+            let waypoint = Waypoint {
+                lat: lat[i] as f64,
+                lon: lon[i] as f64,
+                alt: alt[i] as f64,
+                acceptance_radius: radius.and_then(|r| r.get(i).copied()).unwrap_or(0.0),
+            };
+            if !waypoint.lat.is_finite() || !waypoint.lon.is_finite() {
+                continue;
+            }
+            let repeated = waypoints
+                .last()
+                .is_some_and(|last| last.lat == waypoint.lat && last.lon == waypoint.lon);
+            if !repeated {
+                waypoints.push(waypoint);
+            }
+        }
+
+        if waypoints.is_empty() {
+            return Err(format!("No waypoints in {}", topic));
+        }
+
+        Ok(Self {
+            name: topic.to_string(),
+            waypoints,
+        })
+    }
+}
+
+fn parse_plan(text: &str) -> Result<Vec<Waypoint>, String> {
+    let plan: Value = serde_json::from_str(text).map_err(|e| format!("Invalid plan: {}", e))?;
+    let mission = plan.get("mission").ok_or("Plan has no mission")?;
+
+    let home_alt = mission
+        .get("plannedHomePosition")
+        .and_then(|p| p.get(2))
+        .and_then(Value::as_f64)
+        .unwrap_or(0.0);
+
+    let items = mission
+        .get("items")
+        .and_then(Value::as_array)
+        .ok_or("Plan mission has no items")?;
+
+    let mut waypoints = Vec::new();
+    for item in items {
+        // Complex items (surveys, corridor scans) are generated by QGC and skipped here.
+        if item.get("type").and_then(Value::as_str) != Some("SimpleItem") {
+            continue;
+        }
+
+        let params: Vec<f64> = item
+            .get("params")
+            .and_then(Value::as_array)
+            .map(|p| p.iter().map(|v| v.as_f64().unwrap_or(f64::NAN)).collect())
+            .unwrap_or_default();
+        if params.len() < 7 {
+            continue;
+        }
+
+        let command = item.get("command").and_then(Value::as_u64).unwrap_or(0);
+        let frame = item.get("frame").and_then(Value::as_u64).unwrap_or(0);
+        let alt = item
+            .get("Altitude")
+            .and_then(Value::as_f64)
+            .unwrap_or(params[6]);
+
+        push_waypoint(
+            &mut waypoints,
+            command,
+            frame,
+            [params[1], params[4], params[5], alt],
+            home_alt,
+        );
+    }
+
+    Ok(waypoints)
+}
+
+/// Parses the tab separated `QGC WPL 110` format. The first item is the home position.
+fn parse_wpl(text: &str) -> Result<Vec<Waypoint>, String> {
+    let mut home_alt = 0.0;
+    let mut waypoints = Vec::new();
+
+    for (line_no, line) in text.lines().enumerate().skip(1) {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.is_empty() {
+            continue;
+        }
+        if fields.len() < 11 {
+            return Err(format!("Line {}: expected 12 fields", line_no + 1));
+        }
+
+        let num = |i: usize| {
+            fields[i]
+                .parse::<f64>()
+                .map_err(|e| format!("Line {}: {}", line_no + 1, e))
+        };
+        let seq = num(0)? as u64;
+        let frame = num(2)? as u64;
+        let command = num(3)? as u64;
+
+        if seq == 0 {
+            home_alt = num(10)?;
+            continue;
+        }
+
+        push_waypoint(
+            &mut waypoints,
+            command,
+            frame,
+            [num(5)?, num(8)?, num(9)?, num(10)?],
+            home_alt,
+        );
+    }
+
+    Ok(waypoints)
+}
+
+/// `values` holds param2, latitude, longitude and altitude of a mission item. Items
+/// without a position (speed changes, ROI, ...) are ignored.
+fn push_waypoint(
+    waypoints: &mut Vec<Waypoint>,
+    command: u64,
+    frame: u64,
+    values: [f64; 4],
+    home_alt: f64,
+) {
+    let [param2, lat, lon, alt] = values;
+    if !lat.is_finite() || !lon.is_finite() || (lat == 0.0 && lon == 0.0) {
+        return;
+    }
+
+    let alt = if FRAME_RELATIVE_ALT.contains(&frame) {
+        home_alt + alt
+    } else {
+        alt
+    };
+    let acceptance_radius = if command == MAV_CMD_NAV_WAYPOINT && param2.is_finite() {
+        param2 as f32
+    } else {
+        0.0
+    };
+
+    waypoints.push(Waypoint {
+        lat,
+        lon,
+        alt,
+        acceptance_radius,
+    });
+}
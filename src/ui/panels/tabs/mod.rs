@@ -1,3 +1,7 @@
+pub mod camera;
 pub mod config;
 pub mod gltf_loader;
+pub mod mission;
+pub mod point_cloud;
 pub mod scene;
+pub mod terrain;
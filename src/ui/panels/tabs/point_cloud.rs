@@ -0,0 +1,227 @@
+use crate::core::DataStore;
+use crate::ui::panels::tabs::config::{VectorFrame, VehicleConfig};
+use glam::Vec3;
+use serde::{Deserialize, Serialize};
+
+/// Rows drawn at most per cloud, keeping the most recent ones.
+const MAX_SAMPLES: usize = 500;
+
+/// How the points of one row are laid out relative to the vehicle.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum PointCloudSource {
+    /// Ranges spread around the vehicle in the horizontal plane, like PX4's
+    /// `obstacle_distance`. Empty angle columns fall back to a full circle starting north.
+    Polar {
+        ranges: String,
+        increment: String,
+        angle_offset: String,
+        /// Meters per range unit, e.g. 0.01 for centimeters.
+        range_scale: f32,
+        /// Ranges at or beyond this many meters mean "nothing detected".
+        max_range: f32,
+    },
+    /// One array column per axis.
+    Cartesian { x: String, y: String, z: String },
+}
+
+/// Per-timestep spatial data, e.g. obstacle distances or lidar slices, drawn around the
+/// vehicle pose at the time of each sample.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PointCloud {
+    pub name: String,
+    pub topic: String,
+    pub source: PointCloudSource,
+    pub frame: VectorFrame,
+    /// Seconds of older samples kept on screen, fading out with age.
+    pub decay: f32,
+    /// Keep every sample since the start of the log instead of decaying.
+    pub accumulate: bool,
+    pub point_size: f32,
+    pub color: [f32; 3],
+    pub visible: bool,
+}
+
+impl PointCloud {
+    pub fn presets() -> Vec<Self> {
+        vec![
+            Self {
+                name: "Obstacle Distance".to_string(),
+                topic: "obstacle_distance".to_string(),
+                source: PointCloudSource::Polar {
+                    ranges: "distances".to_string(),
+                    increment: "increment".to_string(),
+                    angle_offset: "angle_offset".to_string(),
+                    range_scale: 0.01,
+                    max_range: 50.0,
+                },
+                frame: VectorFrame::Body,
+                decay: 1.0,
+                accumulate: false,
+                point_size: 4.0,
+                color: [1.0, 0.4, 0.2],
+                visible: true,
+            },
+            Self {
+                name: "Custom".to_string(),
+                topic: String::new(),
+                source: PointCloudSource::Cartesian {
+                    x: String::new(),
+                    y: String::new(),
+                    z: String::new(),
+                },
+                frame: VectorFrame::Body,
+                decay: 0.0,
+                accumulate: false,
+                point_size: 3.0,
+                color: [0.4, 1.0, 0.6],
+                visible: true,
+            },
+        ]
+    }
+
+    /// NED points visible at time `t`, with an opacity that fades older samples out.
+    pub fn points(&self, ds: &DataStore, vehicle: &VehicleConfig, t: f32) -> Vec<(Vec3, f32)> {
+        let Some(times) = ds.get_column(&self.topic, "timestamp") else {
+            return Vec::new();
+        };
+        let end = times.partition_point(|&ts| ts <= t);
+        if end == 0 {
+            return Vec::new();
+        }
+
+        let start = if self.accumulate {
+            0
+        } else {
+            times
+                .partition_point(|&ts| ts < t - self.decay)
+                .min(end - 1)
+        };
+        let start = start.max(end.saturating_sub(MAX_SAMPLES));
+
+        let columns = self.columns(ds);
+        let mut points = Vec::new();
+        for (row, &sample_time) in times.iter().enumerate().take(end).skip(start) {
+            let local = self.sample(&columns, row);
+            if local.is_empty() {
+                continue;
+            }
+
+            let (pos, rot) = vehicle.evaluate_at(ds, sample_time);
+            let opacity = if self.accumulate || self.decay <= 0.0 {
+                1.0
+            } else {
+                (1.0 - (t - sample_time) / self.decay).max(0.15)
+            };
+            points.extend(local.into_iter().map(|p| {
+                let p = match self.frame {
+                    VectorFrame::Ned => p,
+                    VectorFrame::Body => rot * p,
+                };
+                (pos + p, opacity)
+            }));
+        }
+        points
+    }
+
+    /// Looks up the source columns once so rows can be read without string lookups.
+    fn columns<'a>(&self, ds: &'a DataStore) -> SourceColumns<'a> {
+        let scalar = |col: &str| ds.get_column(&self.topic, col);
+        match &self.source {
+            PointCloudSource::Polar {
+                ranges,
+                increment,
+                angle_offset,
+                ..
+            } => SourceColumns {
+                arrays: [
+                    array_columns(ds, &self.topic, ranges),
+                    Vec::new(),
+                    Vec::new(),
+                ],
+                scalars: [scalar(increment), scalar(angle_offset)],
+            },
+            PointCloudSource::Cartesian { x, y, z } => SourceColumns {
+                arrays: [
+                    array_columns(ds, &self.topic, x),
+                    array_columns(ds, &self.topic, y),
+                    array_columns(ds, &self.topic, z),
+                ],
+                scalars: [None, None],
+            },
+        }
+    }
+
+    /// Points of one row relative to the vehicle.
+    fn sample(&self, columns: &SourceColumns, row: usize) -> Vec<Vec3> {
+        let [a, b, c] = columns.arrays.each_ref().map(|cols| {
+            cols.iter()
+                .map(|col| col.get(row).copied().unwrap_or(f32::NAN))
+        });
+        let [increment, offset] = columns.scalars.map(|col| {
+            col.and_then(|c| c.get(row).copied())
+                .filter(|v| v.is_finite())
+        });
+
+        match &self.source {
+            PointCloudSource::Polar {
+                range_scale,
+                max_range,
+                ..
+            } => {
+                let count = columns.arrays[0].len();
+                let increment = increment
+                    .filter(|&v| v > 0.0)
+                    .unwrap_or(360.0 / count.max(1) as f32);
+                let offset = offset.unwrap_or(0.0);
+
+                a.enumerate()
+                    .filter_map(|(i, range)| {
+                        let range = range * range_scale;
+                        if !range.is_finite() || range <= 0.0 || range >= *max_range {
+                            return None;
+                        }
+                        let angle = (offset + i as f32 * increment).to_radians();
+                        Some(Vec3::new(angle.cos(), angle.sin(), 0.0) * range)
+                    })
+                    .collect()
+            }
+            PointCloudSource::Cartesian { .. } => a
+                .zip(b)
+                .zip(c.chain(std::iter::repeat(0.0)))
+                .map(|((x, y), z)| Vec3::new(x, y, z))
+                .filter(|p| p.is_finite())
+                .collect(),
+        }
+    }
+}
+
+/// Array columns (ranges, or x/y/z) and scalar columns (increment, angle offset) of a
+/// point cloud source.
+struct SourceColumns<'a> {
+    arrays: [Vec<&'a Vec<f32>>; 3],
+    scalars: [Option<&'a Vec<f32>>; 2],
+}
+
+/// Columns `base[0]`, `base[1]`, ... of an array field, in index order.
+fn array_columns<'a>(ds: &'a DataStore, topic: &str, base: &str) -> Vec<&'a Vec<f32>> {
+    if base.is_empty() {
+        return Vec::new();
+    }
+    (0..)
+        .map_while(|i| ds.get_column(topic, &format!("{}[{}]", base, i)))
+        .collect()
+}
+
+/// Names of the array fields of `topic`, e.g. `distances` for `distances[0]`.
+pub fn array_fields(ds: &DataStore, topic: &str) -> Vec<String> {
+    let mut fields: Vec<String> = Vec::new();
+    for col in ds.get_columns(topic) {
+        let Some(base) = col.strip_suffix("[0]") else {
+            continue;
+        };
+        if !fields.iter().any(|f| f == base) {
+            fields.push(base.to_string());
+        }
+    }
+    fields
+}
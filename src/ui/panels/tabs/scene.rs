@@ -1,44 +1,87 @@
+use crate::core::geo::gps_to_ned;
 use crate::core::DataStore;
-use crate::ui::panels::tabs::config::VehicleConfig;
-use crate::ui::panels::tabs::gltf_loader::ModelCache;
-use eframe::egui::{self, Color32, Pos2, Shape, Stroke};
+use crate::ui::i18n::tr;
+use crate::ui::panels::tabs::camera::{Camera, CameraMode, NEAR_PLANE};
+use crate::ui::panels::tabs::config::{PositionMode, TrailColorMap, VehicleConfig};
+use crate::ui::panels::tabs::gltf_loader::{Model, ModelCache};
+use crate::ui::panels::tabs::mission::Mission;
+use crate::ui::panels::tabs::terrain::{Terrain, TerrainSettings};
+use crate::ui::scene_renderer::{LinePoint, MeshInstance, SceneCallback, SceneRenderer};
+use eframe::egui::{self, Color32, Pos2};
 use egui_phosphor::regular as icons;
 use glam::{Mat4, Quat, Vec3, Vec4};
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+const MISSION_COLOR: Color32 = Color32::from_rgb(255, 200, 60);
 
 #[derive(Clone)]
 pub struct SceneState {
-    pub yaw: f32,
-    pub pitch: f32,
-    pub distance: f32,
+    pub camera: Camera,
     pub target: Vec3,
     pub follow_index: usize,
-    pub lock_camera: bool,
     pub fixed_vehicle_scale: bool,
+    pub wireframe: bool,
+    pub terrain: TerrainSettings,
+    pub mission: Option<Mission>,
+    mission_error: Option<String>,
+    pub show_acceptance_radius: bool,
+    /// Destination of a requested screenshot, cropped to the scene once it arrives.
+    pending_capture: Option<PathBuf>,
+    /// Trail point picked by the last click and the `ui` time it was picked at.
+    seek_flash: Option<(Vec3, f64)>,
+    /// Renderer key of this scene's terrain, unique per scene so several views can show
+    /// different terrain.
+    terrain_key: String,
+    terrain_mesh: Option<Terrain>,
+    terrain_error: Option<String>,
+    terrain_dirty: bool,
+    terrain_uploaded: Option<TerrainSettings>,
+}
+
+impl std::fmt::Debug for SceneState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SceneState")
+            .field("camera", &self.camera.mode)
+            .field("follow_index", &self.follow_index)
+            .finish_non_exhaustive()
+    }
 }
 
 impl Default for SceneState {
     fn default() -> Self {
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
         Self {
-            yaw: 45.0f32.to_radians(),
-            pitch: 30.0f32.to_radians(),
-            distance: 500.0,
+            camera: Camera::default(),
             target: Vec3::ZERO,
             follow_index: 0,
-            lock_camera: false,
             fixed_vehicle_scale: false,
+            wireframe: false,
+            terrain: TerrainSettings::default(),
+            mission: None,
+            mission_error: None,
+            show_acceptance_radius: true,
+            pending_capture: None,
+            seek_flash: None,
+            terrain_key: format!("terrain/{}", NEXT_ID.fetch_add(1, Ordering::Relaxed)),
+            terrain_mesh: None,
+            terrain_error: None,
+            terrain_dirty: false,
+            terrain_uploaded: None,
         }
     }
 }
 
 pub fn render_scene_tab(
     ui: &mut egui::Ui,
-    _frame: &eframe::Frame,
+    frame: &eframe::Frame,
     vehicles: &mut [VehicleConfig],
     data_store: &DataStore,
     current_time: f32,
     state: &mut SceneState,
     model_cache: &ModelCache,
-) {
+) -> egui::InnerResponse<Option<f32>> {
     ui.horizontal(|ui| {
         if !vehicles.is_empty() {
             egui::ComboBox::from_id_salt("cam_follow_selector")
@@ -46,7 +89,7 @@ pub fn render_scene_tab(
                     vehicles
                         .get(state.follow_index)
                         .map(|v| v.name.as_str())
-                        .unwrap_or("None"),
+                        .unwrap_or(tr("None")),
                 )
                 .show_ui(ui, |ui| {
                     for (i, v) in vehicles.iter_mut().enumerate() {
@@ -66,9 +109,9 @@ pub fn render_scene_tab(
                             if ui
                                 .button(egui::RichText::new(eye_icon).color(eye_color))
                                 .on_hover_text(if v.visible {
-                                    "Hide vehicle"
+                                    tr("Hide vehicle")
                                 } else {
-                                    "Show vehicle"
+                                    tr("Show vehicle")
                                 })
                                 .clicked()
                             {
@@ -81,17 +124,112 @@ pub fn render_scene_tab(
                 });
 
             ui.separator();
-            ui.checkbox(&mut state.lock_camera, "🔒 Lock Camera")
-                .on_hover_text(
-                    "Lock camera position to vehicle body, but keep horizon level (Chase View)",
-                );
-
-            ui.checkbox(&mut state.fixed_vehicle_scale, "📏 Fixed Vehicle Scale")
-                .on_hover_text("Keep vehicle size constant regardless of zoom level");
+            ui.checkbox(
+                &mut state.fixed_vehicle_scale,
+                format!("📏 {}", tr("Fixed Vehicle Scale")),
+            )
+            .on_hover_text(tr("Keep vehicle size constant regardless of zoom level"));
+
+            ui.checkbox(&mut state.wireframe, tr("Wireframe"))
+                .on_hover_text(tr(
+                    "Draw vehicle models as edges instead of shaded surfaces",
+                ));
         }
+
+        egui::ComboBox::from_id_salt("camera_mode_selector")
+            .selected_text(format!(
+                "{} {}",
+                icons::VIDEO_CAMERA,
+                tr(state.camera.mode.name())
+            ))
+            .show_ui(ui, |ui| {
+                for mode in CameraMode::ALL {
+                    if ui
+                        .selectable_label(state.camera.mode == mode, tr(mode.name()))
+                        .on_hover_text(tr(mode.hint()))
+                        .clicked()
+                    {
+                        state.camera.set_mode(mode);
+                    }
+                }
+            })
+            .response
+            .on_hover_text(tr(state.camera.mode.hint()));
+
+        ui.menu_button(format!("{} {}", icons::MOUNTAINS, tr("Terrain")), |ui| {
+            render_terrain_menu(ui, state, vehicles, data_store);
+        });
+
+        ui.menu_button(format!("{} {}", icons::FLAG, tr("Mission")), |ui| {
+            render_mission_menu(ui, state, data_store);
+        });
+
+        ui.menu_button(format!("{} {}", icons::CAMERA, tr("Capture")), |ui| {
+            render_capture_menu(ui, state, current_time);
+        });
     });
     ui.separator();
 
+    let Some(render_state) = frame.wgpu_render_state() else {
+        let label = ui.label(tr("The 3D view requires the wgpu renderer"));
+        return egui::InnerResponse::new(None, label);
+    };
+
+    {
+        let mut renderer = render_state.renderer.write();
+        let Some(scene_renderer) = renderer.callback_resources.get_mut::<SceneRenderer>() else {
+            let label = ui.label(tr("The 3D renderer is not initialized"));
+            return egui::InnerResponse::new(None, label);
+        };
+
+        if state.terrain_dirty {
+            state.terrain_dirty = false;
+            state.terrain_mesh = None;
+            state.terrain_error = None;
+            state.terrain_uploaded = None;
+            if state.terrain.enabled {
+                match Terrain::load(&state.terrain) {
+                    Ok(terrain) => {
+                        scene_renderer.upload_texture(
+                            &render_state.device,
+                            &render_state.queue,
+                            &state.terrain_key,
+                            terrain.texture.as_deref(),
+                        );
+                        state.terrain_mesh = Some(terrain);
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to load terrain: {}", e);
+                        state.terrain_error = Some(e);
+                    }
+                }
+            }
+        }
+
+        if let Some(terrain) = &state.terrain_mesh {
+            if state.terrain_uploaded.as_ref() != Some(&state.terrain) {
+                scene_renderer.upload_mesh(
+                    &render_state.device,
+                    &state.terrain_key,
+                    &terrain.mesh_data(&state.terrain),
+                );
+                state.terrain_uploaded = Some(state.terrain.clone());
+            }
+        }
+
+        for (i, vehicle) in vehicles.iter().enumerate() {
+            upload_trail(scene_renderer, &render_state.device, i, vehicle, data_store);
+
+            let model_path = vehicle.vehicle_type.model_path();
+            let key = model_key(&model_path);
+            if !scene_renderer.has_mesh(&key) {
+                if let Some(model) = model_cache.get_model(&model_path) {
+                    scene_renderer.upload_mesh(&render_state.device, &key, &model.mesh_data());
+                }
+            }
+        }
+    }
+
     let mut vehicle_rotation = Quat::IDENTITY;
 
     if !vehicles.is_empty() {
@@ -100,7 +238,7 @@ pub fn render_scene_tab(
         }
 
         let vehicle = &vehicles[state.follow_index];
-        let (pos, rot) = vehicle.evaluate_at(data_store, current_time);
+        let (pos, rot) = vehicle.evaluate_at(data_store, vehicle.local_time(current_time));
 
         state.target = pos;
         vehicle_rotation = rot;
@@ -113,213 +251,99 @@ pub fn render_scene_tab(
         egui::Layout::top_down(egui::Align::LEFT),
         |ui| {
             let rect = ui.max_rect();
-            let response = ui.allocate_rect(rect, egui::Sense::drag());
+            let response = ui.allocate_rect(rect, egui::Sense::click_and_drag());
 
             let painter = ui.painter_at(rect);
 
             painter.rect_filled(rect, 0.0, Color32::from_rgb(20, 20, 20));
 
-            if response.dragged_by(egui::PointerButton::Primary) {
-                state.yaw += response.drag_delta().x * 0.01;
-                state.pitch += response.drag_delta().y * 0.01;
-                state.pitch = state.pitch.clamp(0.01, 1.55);
-            }
-
-            if response.hovered() {
-                let scroll = ui.input(|i| i.smooth_scroll_delta.y);
-                state.distance -= scroll * (state.distance * 0.01);
-                state.distance = state.distance.clamp(1.0, 5000.0);
-            }
-
-            let height = state.distance * state.pitch.sin();
-            let ground_dist = state.distance * state.pitch.cos();
-
-            let local_offset_x = -ground_dist * state.yaw.cos();
-            let local_offset_y = -ground_dist * state.yaw.sin();
-            let local_offset_z = -height;
-
-            let raw_offset = Vec3::new(local_offset_x, local_offset_y, local_offset_z);
-
-            let (eye, up) = if state.lock_camera {
-                let rotated_offset = vehicle_rotation * raw_offset;
-                (state.target + rotated_offset, -Vec3::Z)
-            } else {
-                (state.target + raw_offset, -Vec3::Z)
-            };
-
-            let view = Mat4::look_at_rh(eye, state.target, up);
+            state.camera.handle_input(ui, &response, rect);
 
             let aspect = rect.width() / rect.height();
-            let proj = Mat4::perspective_rh(45.0f32.to_radians(), aspect, 0.1, 10000.0);
-            let view_proj = proj * view;
+            let view_proj = state.camera.view_proj(
+                ui.ctx(),
+                state.target,
+                vehicle_rotation,
+                aspect,
+                current_time,
+            );
 
-            let project = |pos: Vec3| -> Option<(Pos2, f32, f32)> {
+            let project = |pos: Vec3| -> Option<Pos2> {
                 let clip = view_proj * Vec4::from((pos, 1.0));
-                let w = clip.w;
-
-                if w.abs() < 0.0001 {
+                if clip.w < NEAR_PLANE {
                     return None;
                 }
 
-                let ndc = clip.truncate() / w;
+                let ndc = clip.truncate() / clip.w;
                 let x = rect.min.x + (1.0 + ndc.x) * 0.5 * rect.width();
                 let y = rect.min.y + (1.0 - ndc.y) * 0.5 * rect.height();
 
-                Some((Pos2::new(x, y), clip.z, w))
+                Some(Pos2::new(x, y))
             };
 
-            let mut draw_clipped_line = |p1: Vec3, p2: Vec3, stroke: Stroke| {
-                let clip1 = view_proj * Vec4::from((p1, 1.0));
-                let clip2 = view_proj * Vec4::from((p2, 1.0));
-
-                let w1 = clip1.w;
-                let w2 = clip2.w;
-
-                let near_threshold = 0.1;
-
-                if w1 < near_threshold && w2 < near_threshold {
-                    return;
-                }
-
-                let (p1_use, _w1_use, p2_use, _w2_use) =
-                    if w1 < near_threshold && w2 >= near_threshold {
-                        let t = (near_threshold - w1) / (w2 - w1);
-                        let clipped_p1 = p1.lerp(p2, t);
-                        (clipped_p1, near_threshold, p2, w2)
-                    } else if w2 < near_threshold && w1 >= near_threshold {
-                        let t = (near_threshold - w2) / (w1 - w2);
-                        let clipped_p2 = p2.lerp(p1, t);
-                        (p1, w1, clipped_p2, near_threshold)
-                    } else {
-                        (p1, w1, p2, w2)
-                    };
-
-                if let (Some((s1, _, _)), Some((s2, _, _))) = (project(p1_use), project(p2_use)) {
-                    painter.line_segment([s1, s2], stroke);
-                }
+            let mut scene = SceneCallback {
+                view_proj,
+                viewport: [rect.width(), rect.height()],
+                near: NEAR_PLANE,
+                light_dir: Vec3::new(0.5, -0.5, -1.0),
+                lines: Vec::new(),
+                strips: Vec::new(),
+                meshes: Vec::new(),
+                translucent_meshes: Vec::new(),
             };
 
-            let grid_extent = (state.distance * 3.0).max(400.0);
-
-            draw_grid_and_axes(
-                &painter,
-                &mut draw_clipped_line,
-                &project,
-                grid_extent,
-                state.target,
-            );
-
-            if vehicles.is_empty() {
-                return;
+            let mut show_grid = true;
+            if state.terrain.enabled && state.terrain_mesh.is_some() {
+                scene.meshes.push((
+                    state.terrain_key.clone(),
+                    vec![MeshInstance::new(Mat4::IDENTITY, [1.0; 4])],
+                ));
+                show_grid = !state.terrain.hide_grid;
             }
 
-            let mut model_draw_list: Vec<(f32, Shape)> = Vec::new();
+            let mut labels = Vec::new();
+            if show_grid {
+                let grid_extent = (state.camera.distance * 3.0).max(400.0);
+                labels = grid_and_axes(&mut scene.lines, grid_extent, state.target);
+            }
 
-            for vehicle in vehicles.iter() {
+            for (i, vehicle) in vehicles.iter().enumerate() {
                 if !vehicle.visible {
                     continue;
                 }
+                let current_time = vehicle.local_time(current_time);
 
                 let (pos, rot) = vehicle.evaluate_at(data_store, current_time);
 
-                match &vehicle.position {
-                    crate::ui::panels::tabs::config::PositionMode::LocalNED {
-                        topic,
-                        north,
-                        east,
-                        down,
-                        ..
-                    } => {
-                        if let (Some(x), Some(y), Some(z), Some(t)) = (
-                            data_store.get_column(topic, north),
-                            data_store.get_column(topic, east),
-                            data_store.get_column(topic, down),
-                            data_store.get_column(topic, "timestamp"),
-                        ) {
-                            let end_idx = t.partition_point(|&val| val <= current_time);
-                            let trail_color = Color32::from_rgb(
-                                (vehicle.path_color[0] * 255.0) as u8,
-                                (vehicle.path_color[1] * 255.0) as u8,
-                                (vehicle.path_color[2] * 255.0) as u8,
-                            );
-                            let stroke = Stroke::new(1.5, trail_color);
-
-                            let step = if end_idx > 2000 { end_idx / 2000 } else { 1 };
-
-                            for i in (0..end_idx.saturating_sub(step)).step_by(step) {
-                                let p1 = Vec3::new(x[i], y[i], z[i]);
-                                let p2 = Vec3::new(x[i + step], y[i + step], z[i + step]);
-                                draw_clipped_line(p1, p2, stroke);
-                            }
+                if let Some(t) = vehicle.position_timestamps(data_store) {
+                    let end_idx = t.partition_point(|&val| val <= current_time);
+                    scene.strips.push((trail_key(i), end_idx as u32));
+
+                    if end_idx > 0 {
+                        let last_time = t[end_idx - 1];
+                        let (last_pos, _) = vehicle.evaluate_at(data_store, last_time);
+                        let trail_color = trail_colors(vehicle, data_store, &[last_time])
+                            .and_then(|c| c.first().copied())
+                            .unwrap_or_else(|| color_from_rgb(vehicle.path_color));
+                        scene.lines.push(LinePoint::new(last_pos, 1.5, trail_color));
+                        scene.lines.push(LinePoint::new(pos, 1.5, trail_color));
+                    }
+                }
 
-                            if end_idx > 0 {
-                                let last_idx = end_idx - 1;
-                                let p_last = Vec3::new(x[last_idx], y[last_idx], z[last_idx]);
-                                draw_clipped_line(p_last, pos, stroke);
-                            }
-                        }
+                for vector in vehicle.vectors.iter().filter(|v| v.visible) {
+                    if let Some(value) = vector.evaluate(data_store, current_time, rot) {
+                        let color = color_from_rgb(vector.color);
+                        push_arrow(&mut scene.lines, pos, value * vector.scale, color);
                     }
-                    crate::ui::panels::tabs::config::PositionMode::GlobalGPS {
-                        topic,
-                        lat,
-                        lon,
-                        alt,
-                    } => {
-                        if let (Some(lat_vals), Some(lon_vals), Some(alt_vals), Some(t)) = (
-                            data_store.get_column(topic, lat),
-                            data_store.get_column(topic, lon),
-                            data_store.get_column(topic, alt),
-                            data_store.get_column(topic, "timestamp"),
-                        ) {
-                            if !t.is_empty() {
-                                let lat_ref = lat_vals[0] as f64;
-                                let lon_ref = lon_vals[0] as f64;
-                                let alt_ref = alt_vals[0] as f64;
-
-                                let end_idx = t.partition_point(|&val| val <= current_time);
-                                let trail_color = Color32::from_rgb(
-                                    (vehicle.path_color[0] * 255.0) as u8,
-                                    (vehicle.path_color[1] * 255.0) as u8,
-                                    (vehicle.path_color[2] * 255.0) as u8,
-                                );
-                                let stroke = Stroke::new(1.5, trail_color);
-
-                                let step = if end_idx > 2000 { end_idx / 2000 } else { 1 };
-
-                                for i in (0..end_idx.saturating_sub(step)).step_by(step) {
-                                    let pos1 = VehicleConfig::gps_to_ned(
-                                        lat_vals[i] as f64,
-                                        lon_vals[i] as f64,
-                                        alt_vals[i] as f64,
-                                        lat_ref,
-                                        lon_ref,
-                                        alt_ref,
-                                    );
-                                    let pos2 = VehicleConfig::gps_to_ned(
-                                        lat_vals[i + step] as f64,
-                                        lon_vals[i + step] as f64,
-                                        alt_vals[i + step] as f64,
-                                        lat_ref,
-                                        lon_ref,
-                                        alt_ref,
-                                    );
-                                    draw_clipped_line(pos1, pos2, stroke);
-                                }
-
-                                if end_idx > 0 {
-                                    let last_idx = end_idx - 1;
-                                    let p_last = VehicleConfig::gps_to_ned(
-                                        lat_vals[last_idx] as f64,
-                                        lon_vals[last_idx] as f64,
-                                        alt_vals[last_idx] as f64,
-                                        lat_ref,
-                                        lon_ref,
-                                        alt_ref,
-                                    );
-                                    draw_clipped_line(p_last, pos, stroke);
-                                }
-                            }
-                        }
+                }
+
+                for cloud in vehicle.point_clouds.iter().filter(|c| c.visible) {
+                    let color = color_from_rgb(cloud.color);
+                    for (point, opacity) in cloud.points(data_store, vehicle, current_time) {
+                        // Zero length segments are drawn as squares.
+                        let point =
+                            LinePoint::new(point, cloud.point_size, color.gamma_multiply(opacity));
+                        scene.lines.extend([point, point]);
                     }
                 }
 
@@ -331,7 +355,7 @@ pub fn render_scene_tab(
                 let final_correction = base_correction * specific_correction;
 
                 let effective_scale = if state.fixed_vehicle_scale {
-                    vehicle.scale * (state.distance / 500.0)
+                    vehicle.scale * (state.camera.distance / 500.0)
                 } else {
                     vehicle.scale
                 };
@@ -340,120 +364,733 @@ pub fn render_scene_tab(
                     Mat4::from_scale_rotation_translation(Vec3::splat(effective_scale), rot, pos)
                         * final_correction;
 
-                let vehicle_color = Color32::from_rgb(
-                    (vehicle.color[0] * 255.0) as u8,
-                    (vehicle.color[1] * 255.0) as u8,
-                    (vehicle.color[2] * 255.0) as u8,
-                );
-                let stroke = Stroke::new(1.5, vehicle_color);
-
-                if let Some(model) =
-                    model_cache.get_model(vehicle.vehicle_type.model_path().as_str())
-                {
-                    let transformed_verts: Vec<Vec3> = model
-                        .vertices
-                        .iter()
-                        .map(|&v| model_mat.transform_point3(v))
-                        .collect();
-
-                    let projected_verts: Vec<Option<(Pos2, f32, f32)>> =
-                        transformed_verts.iter().map(|&v| project(v)).collect();
-
-                    for line_indices in &model.lines {
-                        let idx1 = line_indices[0] as usize;
-                        let idx2 = line_indices[1] as usize;
-
-                        let p1_world = transformed_verts[idx1];
-                        let p2_world = transformed_verts[idx2];
-
-                        let proj1 = projected_verts[idx1];
-                        let proj2 = projected_verts[idx2];
-
-                        let should_draw = match (proj1, proj2) {
-                            (Some((s1, d1, w1)), Some((s2, d2, w2))) => {
-                                if w1 > 0.0 && w2 > 0.0 {
-                                    let avg_depth = (d1 + d2) * 0.5;
-                                    let visible = rect.expand(200.0).contains(s1)
-                                        || rect.expand(200.0).contains(s2);
-
-                                    if visible {
-                                        model_draw_list.push((
-                                            avg_depth,
-                                            Shape::line_segment([s1, s2], stroke),
-                                        ));
-                                    }
-                                    false
-                                } else if w1 > 0.0 || w2 > 0.0 {
-                                    true
-                                } else {
-                                    false
-                                }
-                            }
-                            (Some(_), None) | (None, Some(_)) => true,
-                            (None, None) => false,
-                        };
+                let vehicle_color = color_from_rgb(vehicle.color);
+                let model_path = vehicle.vehicle_type.model_path();
 
-                        if should_draw {
-                            let clip1 = view_proj * Vec4::from((p1_world, 1.0));
-                            let clip2 = view_proj * Vec4::from((p2_world, 1.0));
+                let Some(model) = model_cache.get_model(&model_path) else {
+                    continue;
+                };
 
-                            let w1 = clip1.w;
-                            let w2 = clip2.w;
+                let ghost =
+                    vehicle
+                        .evaluate_ghost_at(data_store, current_time)
+                        .map(|(pos, rot)| {
+                            let opacity = vehicle.ghost.as_ref().map_or(1.0, |g| g.opacity);
+                            let mat = Mat4::from_scale_rotation_translation(
+                                Vec3::splat(effective_scale),
+                                rot,
+                                pos,
+                            ) * final_correction;
+                            (mat, opacity)
+                        });
 
-                            let near_clip = 0.12;
+                // The onboard camera sits inside the followed vehicle's model.
+                let hide_model = state.camera.mode == CameraMode::Fpv && i == state.follow_index;
 
-                            if w1 < near_clip && w2 < near_clip {
-                                continue;
-                            }
+                if !state.wireframe {
+                    let tint = if model.has_materials {
+                        [1.0; 4]
+                    } else {
+                        vehicle_color.to_normalized_gamma_f32()
+                    };
+                    let key = model_key(&model_path);
+                    if !hide_model {
+                        push_instance(&mut scene.meshes, &key, MeshInstance::new(model_mat, tint));
+                    }
 
-                            let (clipped_p1, clipped_p2) = if w1 < near_clip && w2 >= near_clip {
-                                let t = (near_clip - w1) / (w2 - w1);
-                                let new_p1 = p1_world + t * (p2_world - p1_world);
-                                (new_p1, p2_world)
-                            } else if w2 < near_clip && w1 >= near_clip {
-                                let t = (near_clip - w2) / (w1 - w2);
-                                let new_p2 = p2_world + t * (p1_world - p2_world);
-                                (p1_world, new_p2)
-                            } else {
-                                (p1_world, p2_world)
-                            };
+                    if let Some((ghost_mat, opacity)) = ghost {
+                        let [r, g, b, _] = tint;
+                        let instance = MeshInstance::new(ghost_mat, [r, g, b, opacity]);
+                        push_instance(&mut scene.translucent_meshes, &key, instance);
+                    }
+                } else {
+                    if !hide_model {
+                        push_wireframe(&mut scene.lines, model, model_mat, vehicle_color);
+                    }
 
-                            if let (Some((s1, d1, _)), Some((s2, d2, _))) =
-                                (project(clipped_p1), project(clipped_p2))
-                            {
-                                let avg_depth = (d1 + d2) * 0.5;
-                                let visible = rect.expand(200.0).contains(s1)
-                                    || rect.expand(200.0).contains(s2);
-
-                                if visible {
-                                    model_draw_list
-                                        .push((avg_depth, Shape::line_segment([s1, s2], stroke)));
-                                }
-                            }
-                        }
+                    if let Some((ghost_mat, opacity)) = ghost {
+                        let ghost_color = vehicle_color.gamma_multiply(opacity);
+                        push_wireframe(&mut scene.lines, model, ghost_mat, ghost_color);
                     }
                 }
             }
 
-            model_draw_list
-                .sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+            let mut markers = Vec::new();
+            let reference = vehicles
+                .get(state.follow_index)
+                .and_then(|v| v.gps_reference(data_store));
+            if let (Some(mission), Some(reference)) = (&state.mission, reference) {
+                markers = mission_lines(
+                    &mut scene.lines,
+                    mission,
+                    reference,
+                    state.show_acceptance_radius,
+                );
+            }
+
+            painter.add(eframe::egui_wgpu::Callback::new_paint_callback(rect, scene));
+
+            for (i, pos) in markers.into_iter().enumerate() {
+                if let Some(screen) = project(pos) {
+                    painter.circle(
+                        screen,
+                        8.0,
+                        MISSION_COLOR.gamma_multiply(0.8),
+                        egui::Stroke::new(1.0, Color32::BLACK),
+                    );
+                    painter.text(
+                        screen,
+                        egui::Align2::CENTER_CENTER,
+                        (i + 1).to_string(),
+                        egui::FontId::proportional(10.0),
+                        Color32::BLACK,
+                    );
+                }
+            }
+
+            let mut legend_top = rect.top() + 10.0;
+            for vehicle in vehicles.iter().filter(|v| v.visible) {
+                if let Some(colormap) = vehicle.trail_colormap.as_ref().filter(|c| c.enabled) {
+                    legend_top =
+                        draw_colormap_legend(&painter, rect, legend_top, colormap, data_store);
+                }
+            }
+
+            for (pos, text, color) in labels {
+                if let Some(screen) = project(pos) {
+                    painter.text(
+                        screen,
+                        egui::Align2::CENTER_CENTER,
+                        text,
+                        egui::FontId::proportional(12.0),
+                        color,
+                    );
+                }
+            }
+
+            draw_seek_flash(ui, &painter, &mut state.seek_flash, project);
 
-            for (_, shape) in model_draw_list {
-                painter.add(shape);
+            if state.pending_capture.is_some() {
+                let screenshot = ui.input(|i| {
+                    i.events.iter().find_map(|e| match e {
+                        egui::Event::Screenshot { image, .. } => Some(image.clone()),
+                        _ => None,
+                    })
+                });
+                if let (Some(image), Some(path)) = (screenshot, state.pending_capture.take()) {
+                    let region = image.region(&rect, Some(ui.ctx().pixels_per_point()));
+                    match save_png(&region, &path) {
+                        Ok(()) => tracing::info!("Saved 3D view to {}", path.display()),
+                        Err(e) => tracing::error!("Failed to save 3D view: {}", e),
+                    }
+                }
             }
+
+            let seek_time = response
+                .interact_pointer_pos()
+                .filter(|_| response.clicked())
+                .and_then(|pointer| {
+                    pick_trail_sample(vehicles, data_store, current_time, pointer, project)
+                })
+                .map(|(time, pos)| {
+                    state.seek_flash = Some((pos, ui.input(|i| i.time)));
+                    time
+                });
+            egui::InnerResponse::new(seek_time, response)
         },
-    );
+    )
+    .inner
+}
+
+/// Finds the drawn trail sample closest to `pointer` on screen, within a few points.
+fn pick_trail_sample(
+    vehicles: &[VehicleConfig],
+    data_store: &DataStore,
+    current_time: f32,
+    pointer: Pos2,
+    project: impl Fn(Vec3) -> Option<Pos2>,
+) -> Option<(f32, Vec3)> {
+    let mut best: Option<(f32, f32, Vec3)> = None;
+    for vehicle in vehicles.iter().filter(|v| v.visible) {
+        let Some((times, points)) = vehicle.trail(data_store) else {
+            continue;
+        };
+        let end_idx = times.partition_point(|&t| t <= vehicle.local_time(current_time));
+        for (&time, &pos) in times[..end_idx].iter().zip(&points) {
+            let Some(screen) = project(pos) else {
+                continue;
+            };
+            let distance = screen.distance(pointer);
+            if best.is_none_or(|(d, _, _)| distance < d) {
+                best = Some((distance, vehicle.timeline_time(time), pos));
+            }
+        }
+    }
+
+    best.filter(|(distance, _, _)| *distance < 12.0)
+        .map(|(_, time, pos)| (time, pos))
 }
 
-fn draw_grid_and_axes(
+/// Expanding ring marking the last picked trail point, fading out over a second.
+fn draw_seek_flash(
+    ui: &egui::Ui,
     painter: &egui::Painter,
-    draw_line: &mut impl FnMut(Vec3, Vec3, Stroke),
-    project: &impl Fn(Vec3) -> Option<(Pos2, f32, f32)>,
+    flash: &mut Option<(Vec3, f64)>,
+    project: impl Fn(Vec3) -> Option<Pos2>,
+) {
+    let Some((pos, start)) = *flash else {
+        return;
+    };
+    let age = (ui.input(|i| i.time) - start) as f32;
+    if age > 1.0 {
+        *flash = None;
+        return;
+    }
+
+    if let Some(screen) = project(pos) {
+        let color = Color32::WHITE.gamma_multiply(1.0 - age);
+        painter.circle_stroke(screen, 6.0 + age * 24.0, egui::Stroke::new(2.0, color));
+        painter.circle_filled(screen, 4.0, color);
+    }
+    ui.ctx().request_repaint();
+}
+
+/// Draws a gradient bar with the column name and value range in the top right corner of
+/// `rect`. Returns the top of the next legend.
+fn draw_colormap_legend(
+    painter: &egui::Painter,
+    rect: egui::Rect,
+    top: f32,
+    colormap: &TrailColorMap,
+    data_store: &DataStore,
+) -> f32 {
+    let Some([min, max]) = colormap.value_range(data_store) else {
+        return top;
+    };
+
+    let width = 140.0;
+    let bar = egui::Rect::from_min_size(
+        egui::pos2(rect.right() - width - 10.0, top + 16.0),
+        egui::vec2(width, 10.0),
+    );
+    let font = egui::FontId::proportional(11.0);
+
+    painter.text(
+        egui::pos2(bar.left(), top),
+        egui::Align2::LEFT_TOP,
+        &colormap.column,
+        font.clone(),
+        Color32::WHITE,
+    );
+
+    let steps = 32;
+    let step_width = bar.width() / steps as f32;
+    for i in 0..steps {
+        let x = bar.left() + i as f32 * step_width;
+        let slice = egui::Rect::from_min_max(
+            egui::pos2(x, bar.top()),
+            egui::pos2(x + step_width + 0.5, bar.bottom()),
+        );
+        let t = (i as f32 + 0.5) / steps as f32;
+        painter.rect_filled(slice, 0.0, colormap.colormap.sample(t));
+    }
+    painter.rect_stroke(bar, 0.0, egui::Stroke::new(1.0, Color32::GRAY));
+
+    painter.text(
+        egui::pos2(bar.left(), bar.bottom() + 2.0),
+        egui::Align2::LEFT_TOP,
+        format!("{:.2}", min),
+        font.clone(),
+        Color32::LIGHT_GRAY,
+    );
+    painter.text(
+        egui::pos2(bar.right(), bar.bottom() + 2.0),
+        egui::Align2::RIGHT_TOP,
+        format!("{:.2}", max),
+        font,
+        Color32::LIGHT_GRAY,
+    );
+
+    bar.bottom() + 22.0
+}
+
+fn color_from_rgb(rgb: [f32; 3]) -> Color32 {
+    Color32::from_rgb(
+        (rgb[0] * 255.0) as u8,
+        (rgb[1] * 255.0) as u8,
+        (rgb[2] * 255.0) as u8,
+    )
+}
+
+fn push_instance(meshes: &mut Vec<(String, Vec<MeshInstance>)>, key: &str, instance: MeshInstance) {
+    match meshes.iter_mut().find(|(k, _)| k == key) {
+        Some((_, instances)) => instances.push(instance),
+        None => meshes.push((key.to_string(), vec![instance])),
+    }
+}
+
+fn push_wireframe(lines: &mut Vec<LinePoint>, model: &Model, transform: Mat4, color: Color32) {
+    for [i1, i2] in &model.lines {
+        let p1 = transform.transform_point3(model.vertices[*i1 as usize]);
+        let p2 = transform.transform_point3(model.vertices[*i2 as usize]);
+        lines.push(LinePoint::new(p1, 1.5, color));
+        lines.push(LinePoint::new(p2, 1.5, color));
+    }
+}
+
+/// Adds an arrow from `origin` along `vector` with a head a fifth of its length.
+fn push_arrow(lines: &mut Vec<LinePoint>, origin: Vec3, vector: Vec3, color: Color32) {
+    let length = vector.length();
+    if length < 1e-3 {
+        return;
+    }
+
+    let tip = origin + vector;
+    let dir = vector / length;
+    let side = dir.any_orthonormal_vector();
+    let head = length * 0.2;
+
+    let mut segment = |a: Vec3, b: Vec3| {
+        lines.push(LinePoint::new(a, 2.5, color));
+        lines.push(LinePoint::new(b, 2.5, color));
+    };
+    segment(origin, tip);
+    segment(tip, tip - dir * head + side * head * 0.5);
+    segment(tip, tip - dir * head - side * head * 0.5);
+}
+
+/// Adds mission legs and acceptance circles to `lines`. Returns the waypoint positions in
+/// NED for the numbered markers.
+fn mission_lines(
+    lines: &mut Vec<LinePoint>,
+    mission: &Mission,
+    reference: (f64, f64, f64),
+    show_acceptance_radius: bool,
+) -> Vec<Vec3> {
+    let (lat_ref, lon_ref, alt_ref) = reference;
+    let points: Vec<Vec3> = mission
+        .waypoints
+        .iter()
+        .map(|wp| gps_to_ned(wp.lat, wp.lon, wp.alt, lat_ref, lon_ref, alt_ref))
+        .collect();
+
+    for pair in points.windows(2) {
+        lines.push(LinePoint::new(pair[0], 1.5, MISSION_COLOR));
+        lines.push(LinePoint::new(pair[1], 1.5, MISSION_COLOR));
+    }
+
+    if show_acceptance_radius {
+        // Three great circles per waypoint read as a sphere without hiding the trail.
+        const SEGMENTS: usize = 32;
+        let color = MISSION_COLOR.gamma_multiply(0.5);
+        for (wp, center) in mission.waypoints.iter().zip(&points) {
+            let r = wp.acceptance_radius;
+            if r <= 0.0 {
+                continue;
+            }
+            for (u, v) in [(Vec3::X, Vec3::Y), (Vec3::X, Vec3::Z), (Vec3::Y, Vec3::Z)] {
+                for i in 0..SEGMENTS {
+                    let a = i as f32 / SEGMENTS as f32 * std::f32::consts::TAU;
+                    let b = (i + 1) as f32 / SEGMENTS as f32 * std::f32::consts::TAU;
+                    let p1 = *center + (u * a.cos() + v * a.sin()) * r;
+                    let p2 = *center + (u * b.cos() + v * b.sin()) * r;
+                    lines.push(LinePoint::new(p1, 1.0, color));
+                    lines.push(LinePoint::new(p2, 1.0, color));
+                }
+            }
+        }
+    }
+
+    points
+}
+
+fn render_capture_menu(ui: &mut egui::Ui, state: &mut SceneState, current_time: f32) {
+    if ui
+        .button(format!(
+            "{} {}",
+            icons::IMAGE,
+            tr("Capture 3D View to PNG...")
+        ))
+        .clicked()
+    {
+        ui.close_menu();
+        if let Some(path) = rfd::FileDialog::new()
+            .add_filter("PNG", &["png"])
+            .set_file_name("scene.png")
+            .save_file()
+        {
+            state.pending_capture = Some(path);
+            ui.ctx()
+                .send_viewport_cmd(egui::ViewportCommand::Screenshot);
+        }
+    }
+
+    ui.separator();
+    ui.label(egui::RichText::new(tr("Camera Path")).strong());
+
+    let camera = &mut state.camera;
+    if ui
+        .button(format!(
+            "{} {} {:.2}s",
+            icons::PLUS,
+            tr("Add Keyframe at"),
+            current_time
+        ))
+        .on_hover_text(tr("Record the current view at the current time"))
+        .clicked()
+    {
+        camera.add_keyframe(current_time);
+    }
+
+    ui.add_enabled(
+        !camera.path.is_empty(),
+        egui::Checkbox::new(
+            &mut camera.play_path,
+            format!(
+                "{} ({} {})",
+                tr("Play Path"),
+                camera.path.len(),
+                tr("keyframes")
+            ),
+        ),
+    )
+    .on_hover_text(tr("Drive the camera from the keyframes as the log plays"));
+
+    ui.horizontal(|ui| {
+        if ui
+            .add_enabled(!camera.path.is_empty(), egui::Button::new(tr("Save...")))
+            .clicked()
+        {
+            ui.close_menu();
+            if let Some(path) = rfd::FileDialog::new()
+                .add_filter("Camera Path", &["json"])
+                .set_file_name("camera_path.json")
+                .save_file()
+            {
+                match camera.save_path(&path) {
+                    Ok(()) => tracing::info!("Saved camera path to {}", path.display()),
+                    Err(e) => tracing::error!("Failed to save camera path: {}", e),
+                }
+            }
+        }
+
+        if ui.button(tr("Load...")).clicked() {
+            ui.close_menu();
+            if let Some(path) = rfd::FileDialog::new()
+                .add_filter("Camera Path", &["json"])
+                .pick_file()
+            {
+                match camera.load_path(&path) {
+                    Ok(()) => tracing::info!("Loaded camera path from {}", path.display()),
+                    Err(e) => tracing::error!("Failed to load camera path: {}", e),
+                }
+            }
+        }
+
+        if ui
+            .add_enabled(!camera.path.is_empty(), egui::Button::new(tr("Clear")))
+            .clicked()
+        {
+            camera.path.clear();
+            camera.play_path = false;
+        }
+    });
+}
+
+fn save_png(image: &egui::ColorImage, path: &Path) -> Result<(), String> {
+    let [width, height] = image.size;
+    let buffer = image::RgbaImage::from_raw(width as u32, height as u32, image.as_raw().to_vec())
+        .ok_or("Screenshot has an unexpected size")?;
+    buffer.save(path).map_err(|e| e.to_string())
+}
+
+fn render_mission_menu(ui: &mut egui::Ui, state: &mut SceneState, data_store: &DataStore) {
+    if let Some(mission) = &state.mission {
+        ui.label(format!(
+            "{} ({} {})",
+            mission.name,
+            mission.waypoints.len(),
+            tr("waypoints")
+        ));
+    }
+
+    if ui
+        .button(format!(
+            "{} {}",
+            icons::FOLDER_OPEN,
+            tr("Load Plan File...")
+        ))
+        .clicked()
+    {
+        ui.close_menu();
+        if let Some(path) = rfd::FileDialog::new()
+            .add_filter("Missions", &["plan", "waypoints", "txt"])
+            .pick_file()
+        {
+            set_mission(state, Mission::load_file(&path));
+        }
+    }
+
+    ui.menu_button(format!("{} {}", icons::LIST, tr("From Topic")), |ui| {
+        egui::ScrollArea::vertical()
+            .max_height(300.0)
+            .show(ui, |ui| {
+                for topic in data_store.get_topics() {
+                    if ui.button(topic).clicked() {
+                        set_mission(state, Mission::from_topic(data_store, topic));
+                        ui.close_menu();
+                    }
+                }
+            });
+    });
+
+    ui.checkbox(&mut state.show_acceptance_radius, tr("Acceptance Radius"));
+
+    if state.mission.is_some()
+        && ui
+            .button(format!("{} {}", icons::TRASH, tr("Clear")))
+            .clicked()
+    {
+        state.mission = None;
+        ui.close_menu();
+    }
+
+    if let Some(error) = &state.mission_error {
+        ui.colored_label(Color32::RED, error);
+    }
+}
+
+fn set_mission(state: &mut SceneState, result: Result<Mission, String>) {
+    match result {
+        Ok(mission) => {
+            tracing::info!(
+                "✓ Loaded mission {} with {} waypoints",
+                mission.name,
+                mission.waypoints.len()
+            );
+            state.mission = Some(mission);
+            state.mission_error = None;
+        }
+        Err(e) => {
+            tracing::error!("Failed to load mission: {}", e);
+            state.mission_error = Some(e);
+        }
+    }
+}
+
+fn model_key(model_path: &str) -> String {
+    format!("model/{}", model_path)
+}
+
+fn trail_key(vehicle_index: usize) -> String {
+    format!("trail/{}", vehicle_index)
+}
+
+/// Re-uploads a vehicle trail when its samples or color changed since the last upload.
+fn upload_trail(
+    renderer: &mut SceneRenderer,
+    device: &wgpu::Device,
+    vehicle_index: usize,
+    vehicle: &VehicleConfig,
+    data_store: &DataStore,
+) {
+    let key = trail_key(vehicle_index);
+    let samples = vehicle
+        .position_timestamps(data_store)
+        .map_or(0, |t| t.len());
+
+    let colormap = vehicle.trail_colormap.as_ref().filter(|c| c.enabled);
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    (
+        samples,
+        vehicle.path_color.map(f32::to_bits),
+        &vehicle.position,
+    )
+        .hash(&mut hasher);
+    if let Some(colormap) = colormap {
+        (
+            &colormap.topic,
+            &colormap.column,
+            colormap.colormap,
+            colormap.range.map(|r| r.map(f32::to_bits)),
+            data_store
+                .get_column(&colormap.topic, &colormap.column)
+                .map_or(0, |v| v.len()),
+        )
+            .hash(&mut hasher);
+    }
+    let version = hasher.finish();
+    if renderer.has_lines(&key, version) {
+        return;
+    }
+
+    let points: Vec<LinePoint> = vehicle
+        .trail(data_store)
+        .map(|(t, points)| {
+            let path_color = color_from_rgb(vehicle.path_color);
+            let colors = trail_colors(vehicle, data_store, t);
+            points
+                .into_iter()
+                .enumerate()
+                .map(|(i, p)| {
+                    let color = colors.as_ref().map_or(path_color, |c| c[i]);
+                    LinePoint::new(p, 1.5, color)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    renderer.upload_lines(device, &key, &points, version);
+}
+
+/// Colormapped trail colors at `times`, if the vehicle colors its trail by a column.
+fn trail_colors(
+    vehicle: &VehicleConfig,
+    data_store: &DataStore,
+    times: &[f32],
+) -> Option<Vec<Color32>> {
+    vehicle
+        .trail_colormap
+        .as_ref()
+        .filter(|c| c.enabled)?
+        .colors(data_store, times)
+}
+
+fn render_terrain_menu(
+    ui: &mut egui::Ui,
+    state: &mut SceneState,
+    vehicles: &[VehicleConfig],
+    data_store: &DataStore,
+) {
+    let settings = &mut state.terrain;
+    let mut changed = ui
+        .checkbox(&mut settings.enabled, tr("Show Terrain"))
+        .changed();
+    ui.checkbox(&mut settings.hide_grid, tr("Hide Grid"));
+    ui.separator();
+
+    let file_name = |path: &Option<std::path::PathBuf>| {
+        path.as_ref()
+            .and_then(|p| p.file_name())
+            .map_or(tr("None").to_string(), |n| n.to_string_lossy().into_owned())
+    };
+
+    egui::Grid::new("terrain_settings_grid")
+        .num_columns(2)
+        .spacing([10.0, 4.0])
+        .show(ui, |ui| {
+            ui.label(tr("Heightmap:"));
+            ui.horizontal(|ui| {
+                ui.label(file_name(&settings.heightmap));
+                if ui.button(icons::FOLDER_OPEN).clicked() {
+                    if let Some(path) = rfd::FileDialog::new()
+                        .add_filter("Heightmaps", &["asc", "png", "jpg", "jpeg"])
+                        .pick_file()
+                    {
+                        settings.heightmap = Some(path);
+                        changed = true;
+                    }
+                }
+                if settings.heightmap.is_some() && ui.button("✖").clicked() {
+                    settings.heightmap = None;
+                    changed = true;
+                }
+            });
+            ui.end_row();
+
+            ui.label(tr("Ground Image:"));
+            ui.horizontal(|ui| {
+                ui.label(file_name(&settings.texture));
+                if ui.button(icons::FOLDER_OPEN).clicked() {
+                    if let Some(path) = rfd::FileDialog::new()
+                        .add_filter("Images", &["png", "jpg", "jpeg"])
+                        .pick_file()
+                    {
+                        settings.texture = Some(path);
+                        changed = true;
+                    }
+                }
+                if settings.texture.is_some() && ui.button("✖").clicked() {
+                    settings.texture = None;
+                    changed = true;
+                }
+            });
+            ui.end_row();
+
+            ui.label(tr("Size N/E (m):"));
+            ui.horizontal(|ui| {
+                ui.add(egui::DragValue::new(&mut settings.size[0]).range(1.0..=1.0e6));
+                ui.add(egui::DragValue::new(&mut settings.size[1]).range(1.0..=1.0e6));
+            });
+            ui.end_row();
+
+            ui.label(tr("Center N/E (m):"));
+            ui.horizontal(|ui| {
+                ui.add(egui::DragValue::new(&mut settings.center[0]));
+                ui.add(egui::DragValue::new(&mut settings.center[1]));
+            });
+            ui.end_row();
+
+            ui.label(tr("Image Heights (m):"))
+                .on_hover_text(tr("Heights of black and white pixels in image heightmaps"));
+            ui.horizontal(|ui| {
+                changed |= ui
+                    .add(egui::DragValue::new(&mut settings.height_range[0]))
+                    .changed();
+                changed |= ui
+                    .add(egui::DragValue::new(&mut settings.height_range[1]))
+                    .changed();
+            });
+            ui.end_row();
+
+            ui.label(tr("Origin Altitude (m):"))
+                .on_hover_text(tr("Terrain height at the local NED origin"));
+            ui.horizontal(|ui| {
+                ui.add(egui::DragValue::new(&mut settings.datum));
+                if let Some(altitude) = vehicles
+                    .get(state.follow_index)
+                    .and_then(|v| start_altitude(v, data_store))
+                {
+                    if ui
+                        .button(tr("From Vehicle"))
+                        .on_hover_text(tr("Use the altitude of the vehicle's first position"))
+                        .clicked()
+                    {
+                        settings.datum = altitude;
+                    }
+                }
+            });
+            ui.end_row();
+        });
+
+    if let Some(error) = &state.terrain_error {
+        ui.colored_label(Color32::RED, error);
+    }
+
+    if changed {
+        state.terrain_dirty = true;
+    }
+}
+
+/// Altitude the NED origin of a vehicle corresponds to, if it can be read from the data.
+fn start_altitude(vehicle: &VehicleConfig, data_store: &DataStore) -> Option<f32> {
+    let (topic, col) = match &vehicle.position {
+        PositionMode::GlobalGPS { topic, alt, .. } => (topic, alt),
+        PositionMode::LocalNED { topic, alt_ref, .. } => (topic, alt_ref),
+    };
+    data_store
+        .get_column(topic, col)?
+        .iter()
+        .copied()
+        .find(|v| v.is_finite())
+}
+
+/// Adds the ground grid and NED axes to `lines`. Returns the axis labels to draw on top.
+fn grid_and_axes(
+    lines: &mut Vec<LinePoint>,
     extent: f32,
     center: Vec3,
-) {
+) -> Vec<(Vec3, &'static str, Color32)> {
     let grid_color = Color32::from_gray(50);
-    let grid_stroke = Stroke::new(1.0, grid_color);
 
     let raw_step = extent / 10.0;
     let magnitude = 10.0f32.powf(raw_step.log10().floor());
@@ -473,91 +1110,44 @@ fn draw_grid_and_axes(
     let grid_center_y = (center.y / step).round() * step;
     let grid_z = 0.0;
 
-    let segments = 5;
+    let mut line = |p1: Vec3, p2: Vec3, width: f32, color: Color32| {
+        lines.push(LinePoint::new(p1, width, color));
+        lines.push(LinePoint::new(p2, width, color));
+    };
 
     let mut x = grid_center_x - start;
     while x <= grid_center_x + start {
-        for seg in 0..segments {
-            let t1 = seg as f32 / segments as f32;
-            let t2 = (seg + 1) as f32 / segments as f32;
-            let y1 = grid_center_y - start + t1 * (2.0 * start);
-            let y2 = grid_center_y - start + t2 * (2.0 * start);
-            draw_line(
-                Vec3::new(x, y1, grid_z),
-                Vec3::new(x, y2, grid_z),
-                grid_stroke,
-            );
-        }
+        line(
+            Vec3::new(x, grid_center_y - start, grid_z),
+            Vec3::new(x, grid_center_y + start, grid_z),
+            1.0,
+            grid_color,
+        );
         x += step;
     }
 
     let mut y = grid_center_y - start;
     while y <= grid_center_y + start {
-        for seg in 0..segments {
-            let t1 = seg as f32 / segments as f32;
-            let t2 = (seg + 1) as f32 / segments as f32;
-            let x1 = grid_center_x - start + t1 * (2.0 * start);
-            let x2 = grid_center_x - start + t2 * (2.0 * start);
-            draw_line(
-                Vec3::new(x1, y, grid_z),
-                Vec3::new(x2, y, grid_z),
-                grid_stroke,
-            );
-        }
+        line(
+            Vec3::new(grid_center_x - start, y, grid_z),
+            Vec3::new(grid_center_x + start, y, grid_z),
+            1.0,
+            grid_color,
+        );
         y += step;
     }
 
     let axis_len = step;
-    let origin = Vec3::ZERO;
-
-    draw_line(
-        origin,
-        Vec3::new(axis_len, 0.0, 0.0),
-        Stroke::new(2.0, Color32::RED),
-    );
-    if let Some((pos, _, w)) = project(Vec3::new(axis_len * 1.1, 0.0, 0.0)) {
-        if w > 0.0 {
-            painter.text(
-                pos,
-                egui::Align2::CENTER_CENTER,
-                "N",
-                egui::FontId::proportional(12.0),
-                Color32::RED,
-            );
-        }
-    }
-
-    draw_line(
-        origin,
-        Vec3::new(0.0, axis_len, 0.0),
-        Stroke::new(2.0, Color32::GREEN),
-    );
-    if let Some((pos, _, w)) = project(Vec3::new(0.0, axis_len * 1.1, 0.0)) {
-        if w > 0.0 {
-            painter.text(
-                pos,
-                egui::Align2::CENTER_CENTER,
-                "E",
-                egui::FontId::proportional(12.0),
-                Color32::GREEN,
-            );
-        }
-    }
-
-    draw_line(
-        origin,
-        Vec3::new(0.0, 0.0, axis_len),
-        Stroke::new(2.0, Color32::BLUE),
-    );
-    if let Some((pos, _, w)) = project(Vec3::new(0.0, 0.0, axis_len * 1.1)) {
-        if w > 0.0 {
-            painter.text(
-                pos,
-                egui::Align2::CENTER_CENTER,
-                "D",
-                egui::FontId::proportional(12.0),
-                Color32::BLUE,
-            );
-        }
-    }
+    let axes = [
+        (Vec3::X, "N", Color32::RED),
+        (Vec3::Y, "E", Color32::GREEN),
+        (Vec3::Z, "D", Color32::BLUE),
+    ];
+
+    axes.into_iter()
+        .map(|(dir, label, color)| {
+            line(Vec3::ZERO, dir * axis_len, 2.0, color);
+            (dir * axis_len * 1.1, label, color)
+        })
+        .collect()
 }
@@ -0,0 +1,271 @@
+use crate::ui::scene_renderer::{MeshData, MeshVertex};
+use glam::Vec3;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Heightmaps are resampled to at most this many vertices per side.
+const MAX_GRID_SIZE: usize = 129;
+
+/// Where the ground layer comes from and how it is placed in the local NED frame.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TerrainSettings {
+    pub enabled: bool,
+    /// Esri ASCII grid (`.asc`) or grayscale image. Without one the ground is flat.
+    pub heightmap: Option<PathBuf>,
+    /// Image draped over the ground, north up.
+    pub texture: Option<PathBuf>,
+    /// North and east extent in meters.
+    pub size: [f32; 2],
+    /// North and east position of the terrain center in meters.
+    pub center: [f32; 2],
+    /// Heights mapped to black and white in image heightmaps.
+    pub height_range: [f32; 2],
+    /// Terrain height at the NED origin, usually the altitude of the first position.
+    pub datum: f32,
+    pub hide_grid: bool,
+}
+
+impl Default for TerrainSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            heightmap: None,
+            texture: None,
+            size: [1000.0, 1000.0],
+            center: [0.0, 0.0],
+            height_range: [0.0, 100.0],
+            datum: 0.0,
+            hide_grid: false,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Terrain {
+    rows: usize,
+    cols: usize,
+    heights: Vec<f32>,
+    pub texture: Option<Arc<image::RgbaImage>>,
+}
+
+impl Terrain {
+    pub fn load(settings: &TerrainSettings) -> Result<Self, String> {
+        let (rows, cols, heights) = match &settings.heightmap {
+            Some(path) => load_heightmap(path, settings.height_range)?,
+            None => (2, 2, vec![0.0; 4]),
+        };
+
+        let texture = match &settings.texture {
+            Some(path) => Some(Arc::new(
+                image::open(path)
+                    .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?
+                    .to_rgba8(),
+            )),
+            None => None,
+        };
+
+        Ok(Self {
+            rows,
+            cols,
+            heights,
+            texture,
+        })
+    }
+
+    fn height(&self, row: usize, col: usize) -> f32 {
+        self.heights[row * self.cols + col]
+    }
+
+    fn position(&self, settings: &TerrainSettings, row: usize, col: usize) -> Vec3 {
+        let v = row as f32 / (self.rows - 1) as f32;
+        let u = col as f32 / (self.cols - 1) as f32;
+        // Flat ground always sits on the NED origin.
+        let datum = if settings.heightmap.is_some() {
+            settings.datum
+        } else {
+            0.0
+        };
+        Vec3::new(
+            settings.center[0] + settings.size[0] * (0.5 - v),
+            settings.center[1] + settings.size[1] * (u - 0.5),
+            -(self.height(row, col) - datum),
+        )
+    }
+
+    /// Upward surface normal in NED from central differences.
+    fn normal(&self, settings: &TerrainSettings, row: usize, col: usize) -> Vec3 {
+        let cell_n = settings.size[0] / (self.rows - 1) as f32;
+        let cell_e = settings.size[1] / (self.cols - 1) as f32;
+
+        let dh_n = (self.height(row.saturating_sub(1), col)
+            - self.height((row + 1).min(self.rows - 1), col))
+            / (2.0 * cell_n);
+        let dh_e = (self.height(row, (col + 1).min(self.cols - 1))
+            - self.height(row, col.saturating_sub(1)))
+            / (2.0 * cell_e);
+        Vec3::new(dh_n, dh_e, -1.0).normalize()
+    }
+
+    /// Builds the terrain mesh in the local NED frame. Untextured terrain is colored by
+    /// height.
+    pub fn mesh_data(&self, settings: &TerrainSettings) -> MeshData {
+        let mut vertices = Vec::with_capacity(self.rows * self.cols);
+        for row in 0..self.rows {
+            for col in 0..self.cols {
+                let color = if self.texture.is_some() {
+                    [1.0; 4]
+                } else {
+                    let [r, g, b] = height_color(self.height(row, col), settings.height_range);
+                    [r / 255.0, g / 255.0, b / 255.0, 1.0]
+                };
+                vertices.push(MeshVertex {
+                    position: self.position(settings, row, col).to_array(),
+                    normal: self.normal(settings, row, col).to_array(),
+                    uv: [
+                        col as f32 / (self.cols - 1) as f32,
+                        row as f32 / (self.rows - 1) as f32,
+                    ],
+                    color,
+                });
+            }
+        }
+
+        let mut indices = Vec::with_capacity((self.rows - 1) * (self.cols - 1) * 6);
+        for row in 0..self.rows - 1 {
+            for col in 0..self.cols - 1 {
+                let a = (row * self.cols + col) as u32;
+                let b = a + 1;
+                let c = a + self.cols as u32;
+                let d = c + 1;
+                indices.extend_from_slice(&[a, b, c, b, d, c]);
+            }
+        }
+
+        MeshData { vertices, indices }
+    }
+}
+
+fn height_color(height: f32, range: [f32; 2]) -> [f32; 3] {
+    let span = (range[1] - range[0]).max(1e-3);
+    let t = ((height - range[0]) / span).clamp(0.0, 1.0);
+
+    const STOPS: [[f32; 3]; 4] = [
+        [60.0, 110.0, 60.0],
+        [130.0, 150.0, 80.0],
+        [140.0, 110.0, 80.0],
+        [230.0, 230.0, 230.0],
+    ];
+    let scaled = t * (STOPS.len() - 1) as f32;
+    let idx = (scaled as usize).min(STOPS.len() - 2);
+    let f = scaled - idx as f32;
+    let (a, b) = (STOPS[idx], STOPS[idx + 1]);
+    [
+        a[0] + (b[0] - a[0]) * f,
+        a[1] + (b[1] - a[1]) * f,
+        a[2] + (b[2] - a[2]) * f,
+    ]
+}
+
+/// Reads a heightmap and resamples it to at most `MAX_GRID_SIZE` vertices per side.
+/// Returns rows, columns and row-major heights, with row 0 at the north edge.
+fn load_heightmap(path: &Path, height_range: [f32; 2]) -> Result<(usize, usize, Vec<f32>), String> {
+    let is_ascii_grid = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("asc"));
+
+    let (rows, cols, heights) = if is_ascii_grid {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        parse_ascii_grid(&text)?
+    } else {
+        let image = image::open(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?
+            .to_luma16();
+        let (w, h) = (image.width() as usize, image.height() as usize);
+        let heights = image
+            .pixels()
+            .map(|p| {
+                let t = p.0[0] as f32 / u16::MAX as f32;
+                height_range[0] + t * (height_range[1] - height_range[0])
+            })
+            .collect();
+        (h, w, heights)
+    };
+
+    if rows < 2 || cols < 2 {
+        return Err("Heightmap needs at least 2x2 samples".to_string());
+    }
+
+    let out_rows = rows.min(MAX_GRID_SIZE);
+    let out_cols = cols.min(MAX_GRID_SIZE);
+    let mut resampled = Vec::with_capacity(out_rows * out_cols);
+    for r in 0..out_rows {
+        let src_r = r * (rows - 1) / (out_rows - 1);
+        for c in 0..out_cols {
+            let src_c = c * (cols - 1) / (out_cols - 1);
+            resampled.push(heights[src_r * cols + src_c]);
+        }
+    }
+
+    Ok((out_rows, out_cols, resampled))
+}
+
+/// Parses an Esri ASCII grid. NODATA cells take the lowest valid height.
+fn parse_ascii_grid(text: &str) -> Result<(usize, usize, Vec<f32>), String> {
+    let mut tokens = text.split_whitespace().peekable();
+    let mut rows = None;
+    let mut cols = None;
+    let mut nodata = None;
+
+    while let Some(key) = tokens.peek() {
+        if key.parse::<f32>().is_ok() {
+            break;
+        }
+        let key = tokens.next().unwrap_or_default().to_ascii_lowercase();
+        let value = tokens
+            .next()
+            .ok_or_else(|| format!("Missing value for '{}'", key))?;
+        match key.as_str() {
+            "nrows" => rows = value.parse::<usize>().ok(),
+            "ncols" => cols = value.parse::<usize>().ok(),
+            "nodata_value" => nodata = value.parse::<f32>().ok(),
+            _ => {}
+        }
+    }
+
+    let (Some(rows), Some(cols)) = (rows, cols) else {
+        return Err("ASCII grid header is missing nrows or ncols".to_string());
+    };
+
+    let mut heights: Vec<f32> = tokens
+        .take(rows * cols)
+        .map(|t| {
+            t.parse::<f32>()
+                .map_err(|e| format!("Invalid height '{}': {}", t, e))
+        })
+        .collect::<Result<_, _>>()?;
+    if heights.len() != rows * cols {
+        return Err(format!(
+            "Expected {} heights, found {}",
+            rows * cols,
+            heights.len()
+        ));
+    }
+
+    let is_nodata = |h: f32| nodata.is_some_and(|n| h == n);
+    let lowest = heights
+        .iter()
+        .copied()
+        .filter(|&h| !is_nodata(h))
+        .fold(f32::INFINITY, f32::min);
+    let fill = if lowest.is_finite() { lowest } else { 0.0 };
+    for h in &mut heights {
+        if is_nodata(*h) {
+            *h = fill;
+        }
+    }
+
+    Ok((rows, cols, heights))
+}
@@ -1,6 +1,345 @@
-use crate::ui::calculate_grid_step;
+use crate::core::{Annotation, DataStore};
+use crate::ui::i18n::tr;
+use crate::ui::time_format::TimeFormat;
+use crate::ui::{calculate_grid_step, multi_touch};
 use eframe::egui;
+use serde::{Deserialize, Serialize};
+
+/// How playback advances `current_time` each frame.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PlaybackMode {
+    /// Elapsed time times the speed multiplier.
+    #[default]
+    Scaled,
+    /// Elapsed wall-clock time, so the log plays at the rate it was recorded.
+    RealTime,
+    /// One sample of the clock topic per frame.
+    SampleStep,
+}
+
+impl PlaybackMode {
+    pub const ALL: [PlaybackMode; 3] = [
+        PlaybackMode::Scaled,
+        PlaybackMode::RealTime,
+        PlaybackMode::SampleStep,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PlaybackMode::Scaled => tr("Speed Multiplier"),
+            PlaybackMode::RealTime => tr("Real Time (1x)"),
+            PlaybackMode::SampleStep => tr("Step Through Clock Topic"),
+        }
+    }
+}
+
+/// Speed multipliers offered as buttons in the timeline menu and on keys 1 to 5.
+pub const SPEED_PRESETS: [f32; 5] = [0.1, 0.5, 1.0, 2.0, 10.0];
+
+/// Switches to scaled playback at `speed`.
+pub fn apply_speed_preset(speed: f32, playback_speed: &mut f32, playback_mode: &mut PlaybackMode) {
+    *playback_speed = speed;
+    *playback_mode = PlaybackMode::Scaled;
+}
+
+/// What the strip behind the timeline ticks summarizes.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum ActivityStrip {
+    Off,
+    /// Messages per second summed over all topics.
+    #[default]
+    AllTopics,
+    /// Messages per second of one topic.
+    Topic(String),
+    /// Min/max envelope of one column.
+    Column {
+        topic: String,
+        col: String,
+    },
+}
+
+impl ActivityStrip {
+    pub fn label(&self) -> String {
+        match self {
+            ActivityStrip::Off => "Off".to_string(),
+            ActivityStrip::AllTopics => "All Topics".to_string(),
+            ActivityStrip::Topic(topic) => topic.clone(),
+            ActivityStrip::Column { topic, col } => format!("{}/{}", topic, col),
+        }
+    }
+}
+
+/// A/B markers that playback repeats between once both are set.
+#[derive(Clone, Debug, Default)]
+pub struct LoopRegion {
+    pub a: Option<f32>,
+    pub b: Option<f32>,
+    pub enabled: bool,
+}
+
+impl LoopRegion {
+    /// Start and end of the active loop, in order.
+    pub fn range(&self) -> Option<(f32, f32)> {
+        let (a, b) = (self.a?, self.b?);
+        (self.enabled && a != b).then(|| (a.min(b), a.max(b)))
+    }
+
+    pub fn set_a(&mut self, time: f32) {
+        self.a = Some(time);
+        self.enabled = self.b.is_some();
+    }
+
+    pub fn set_b(&mut self, time: f32) {
+        self.b = Some(time);
+        self.enabled = self.a.is_some();
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// What the jump shortcuts step `current_time` between.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JumpTarget {
+    /// Samples of the clock topic.
+    Sample,
+    /// Samples of the clock topic where any of its fields changes value.
+    Event,
+    Annotation,
+}
+
+/// The nearest of `times` after (or before) `current`, in any order.
+pub fn step_time(times: &[f32], current: f32, forward: bool) -> Option<f32> {
+    let candidates = times.iter().copied();
+    if forward {
+        candidates.filter(|&t| t > current).min_by(f32::total_cmp)
+    } else {
+        candidates.filter(|&t| t < current).max_by(f32::total_cmp)
+    }
+}
+
+/// Message density or a column envelope across the whole log, one bin per couple of
+/// pixels, so busy or interesting stretches stand out before zooming in.
+fn render_activity_strip(
+    ui: &egui::Ui,
+    rect: egui::Rect,
+    global_min: f32,
+    global_max: f32,
+    activity: &ActivityStrip,
+    data_store: &DataStore,
+) {
+    let time_span = global_max - global_min;
+    if time_span <= 0.0 || *activity == ActivityStrip::Off {
+        return;
+    }
+
+    let bins = (rect.width() / 2.0).max(1.0) as usize;
+    let bin_width = rect.width() / bins as f32;
+    let mut edges: Vec<f32> = (0..bins)
+        .map(|i| global_min + time_span * i as f32 / bins as f32)
+        .collect();
+    edges.push(f32::INFINITY);
+    let bin_rows = |times: &[f32]| -> Vec<usize> {
+        edges
+            .iter()
+            .map(|&edge| times.partition_point(|&t| t < edge))
+            .collect()
+    };
+
+    match activity {
+        ActivityStrip::Off => {}
+        ActivityStrip::AllTopics | ActivityStrip::Topic(_) => {
+            let topics = match activity {
+                ActivityStrip::Topic(topic) => vec![topic],
+                _ => data_store.get_topics(),
+            };
+            let mut counts = vec![0usize; bins];
+            for topic in topics {
+                let Some(times) = data_store.get_column(topic, "timestamp") else {
+                    continue;
+                };
+                for (count, rows) in counts.iter_mut().zip(bin_rows(times).windows(2)) {
+                    *count += rows[1] - rows[0];
+                }
+            }
+
+            let max_count = counts.iter().copied().max().unwrap_or(0);
+            if max_count == 0 {
+                return;
+            }
+            for (bin, &count) in counts.iter().enumerate() {
+                if count == 0 {
+                    continue;
+                }
+                // Square root keeps sparse stretches visible next to bursts.
+                let level = (count as f32 / max_count as f32).sqrt();
+                let height = rect.height() * level;
+                let bar = egui::Rect::from_min_size(
+                    egui::pos2(rect.min.x + bin as f32 * bin_width, rect.max.y - height),
+                    egui::vec2(bin_width, height),
+                );
+                let alpha = (40.0 + 100.0 * level) as u8;
+                ui.painter().rect_filled(
+                    bar,
+                    0.0,
+                    egui::Color32::from_rgba_unmultiplied(255, 170, 60, alpha),
+                );
+            }
+        }
+        ActivityStrip::Column { topic, col } => {
+            let Some(times) = data_store.get_column(topic, "timestamp") else {
+                return;
+            };
+            let envelope: Vec<Option<(f32, f32)>> = bin_rows(times)
+                .windows(2)
+                .map(|rows| {
+                    let stats = data_store.column_range_stats(topic, col, rows[0], rows[1])?;
+                    (stats.min.is_finite() && stats.max.is_finite())
+                        .then_some((stats.min, stats.max))
+                })
+                .collect();
+
+            let (lo, hi) = envelope.iter().flatten().fold(
+                (f32::INFINITY, f32::NEG_INFINITY),
+                |(lo, hi), &(min, max)| (lo.min(min), hi.max(max)),
+            );
+            if lo > hi {
+                return;
+            }
+            let range = (hi - lo).max(f32::EPSILON);
+            let to_y = |v: f32| rect.max.y - (v - lo) / range * rect.height();
+
+            for (bin, &(min, max)) in envelope
+                .iter()
+                .enumerate()
+                .filter_map(|(bin, e)| e.as_ref().map(|e| (bin, e)))
+            {
+                let x = rect.min.x + bin as f32 * bin_width;
+                let band = egui::Rect::from_x_y_ranges(
+                    x..=x + bin_width,
+                    to_y(max)..=(to_y(min)).max(to_y(max) + 1.0),
+                );
+                ui.painter().rect_filled(
+                    band,
+                    0.0,
+                    egui::Color32::from_rgba_unmultiplied(80, 200, 255, 110),
+                );
+            }
+        }
+    }
+}
+
+/// Current time, which turns into a text field on click to jump to a typed time.
+#[allow(clippy::too_many_arguments)]
+fn render_time_readout(
+    ui: &mut egui::Ui,
+    rect: egui::Rect,
+    global_min: f32,
+    global_max: f32,
+    current_time: &mut f32,
+    is_playing: &mut bool,
+    time_format: TimeFormat,
+    start_time: f32,
+) {
+    let id = ui.id().with("time_jump");
+    let error_id = id.with("error");
+    let mut editing: Option<String> = ui.data(|d| d.get_temp(id));
+
+    let Some(text) = editing.as_mut() else {
+        let response = ui
+            .interact(rect, id.with("readout"), egui::Sense::click())
+            .on_hover_text(tr("Click to jump to a time"));
+        let bg_color = if response.hovered() {
+            egui::Color32::from_rgb(70, 70, 70)
+        } else {
+            egui::Color32::from_rgb(50, 50, 50)
+        };
+        ui.painter().rect_filled(rect, 4.0, bg_color);
+        ui.painter().rect_stroke(
+            rect,
+            4.0,
+            egui::Stroke::new(1.0, egui::Color32::from_gray(100)),
+        );
+        ui.painter().text(
+            rect.center(),
+            egui::Align2::CENTER_CENTER,
+            time_format.format_full(*current_time, start_time),
+            egui::FontId::monospace(11.0),
+            egui::Color32::WHITE,
+        );
+        if response.clicked() {
+            let text = time_format.format_full(*current_time, start_time);
+            ui.data_mut(|d| {
+                d.insert_temp(id, text);
+                d.remove::<String>(error_id);
+            });
+            ui.memory_mut(|mem| mem.request_focus(id.with("edit")));
+        }
+        return;
+    };
+
+    let error: Option<String> = ui.data(|d| d.get_temp(error_id));
+    let mut edit = egui::TextEdit::singleline(text)
+        .id(id.with("edit"))
+        .font(egui::FontId::monospace(11.0))
+        .hint_text(tr("s, mm:ss or UTC date"));
+    if error.is_some() {
+        edit = edit.text_color(egui::Color32::from_rgb(255, 100, 100));
+    }
+    let response = ui.put(rect, edit);
+    let response = match &error {
+        Some(error) => response.on_hover_text(error),
+        None => response,
+    };
+
+    if response.lost_focus() {
+        if ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+            match time_format.parse(text, start_time) {
+                Ok(t) => {
+                    *current_time = t.clamp(global_min, global_max);
+                    *is_playing = false;
+                    editing = None;
+                }
+                Err(e) => {
+                    ui.data_mut(|d| d.insert_temp(error_id, e));
+                    ui.memory_mut(|mem| mem.request_focus(response.id));
+                }
+            }
+        } else {
+            editing = None;
+        }
+    } else if response.changed() {
+        ui.data_mut(|d| d.remove::<String>(error_id));
+    }
 
+    ui.data_mut(|d| match editing {
+        Some(text) => d.insert_temp(id, text),
+        None => {
+            d.remove::<String>(id);
+            d.remove::<String>(error_id);
+        }
+    });
+}
+
+/// Widest view a marker is zoomed to, in seconds.
+const MARKER_ZOOM_SPAN: f32 = 10.0;
+
+/// Sets the view to `start..end`, shifted and clipped to the extent of the data.
+fn zoom_to(
+    min_time: &mut f32,
+    max_time: &mut f32,
+    start: f32,
+    end: f32,
+    global_min: f32,
+    global_max: f32,
+) {
+    let shift = (global_min - start).max(0.0) - (end - global_max).max(0.0);
+    *min_time = (start + shift).max(global_min);
+    *max_time = (end + shift).min(global_max);
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn render_timeline(
     ui: &mut egui::Ui,
     global_min: f32,
@@ -10,18 +349,34 @@ pub fn render_timeline(
     current_time: &mut f32,
     is_playing: &mut bool,
     playback_speed: &mut f32,
+    reverse: &mut bool,
     lock_to_last: &mut bool,
     lock_viewport: &mut bool,
     always_show_playback_tooltip: &mut bool,
+    annotations: &mut Vec<Annotation>,
+    loop_region: &mut LoopRegion,
+    playback_mode: &mut PlaybackMode,
+    clock_topic: &mut Option<String>,
+    time_format: &mut TimeFormat,
+    activity: &mut ActivityStrip,
+    data_store: &DataStore,
 ) {
     let available_rect = ui.available_rect_before_wrap();
     let timeline_height = 40.0;
     let play_button_width = 40.0;
     let speed_control_width = 60.0;
     let menu_button_width = 30.0;
+    let readout_width = match time_format {
+        TimeFormat::Seconds => 80.0,
+        TimeFormat::Elapsed => 100.0,
+        TimeFormat::Utc => 170.0,
+    };
     let controls_padding = 8.0;
-    let controls_width =
-        play_button_width + speed_control_width + menu_button_width + controls_padding * 4.0;
+    let controls_width = play_button_width
+        + speed_control_width
+        + menu_button_width
+        + readout_width
+        + controls_padding * 5.0;
 
     let (full_rect, _) = ui.allocate_exact_size(
         egui::vec2(available_rect.width(), timeline_height),
@@ -70,7 +425,11 @@ pub fn render_timeline(
         egui::Stroke::new(1.0, egui::Color32::from_gray(100)),
     );
 
-    let button_text = if *is_playing { "⏸" } else { "▶" };
+    let button_text = match (*is_playing, *reverse) {
+        (true, _) => "⏸",
+        (false, true) => "◀",
+        (false, false) => "▶",
+    };
     ui.painter().text(
         button_rect.center(),
         egui::Align2::CENTER_CENTER,
@@ -99,12 +458,26 @@ pub fn render_timeline(
         ),
         |ui| {
             ui.spacing_mut().item_spacing = egui::vec2(0.0, 0.0);
-            ui.add(
-                egui::DragValue::new(playback_speed)
-                    .speed(0.1)
-                    .range(0.01..=1000.0)
-                    .suffix("x"),
-            );
+            match playback_mode {
+                PlaybackMode::Scaled => {
+                    ui.add(
+                        egui::DragValue::new(playback_speed)
+                            .speed(0.1)
+                            .range(0.01..=1000.0)
+                            .suffix("x"),
+                    );
+                }
+                PlaybackMode::RealTime => {
+                    ui.label("1x")
+                        .on_hover_text(tr("Playing at wall-clock rate"));
+                }
+                PlaybackMode::SampleStep => {
+                    ui.label(tr("Step")).on_hover_text(format!(
+                        "Stepping through {}",
+                        clock_topic.as_deref().unwrap_or("no clock topic")
+                    ));
+                }
+            }
         },
     );
 
@@ -145,6 +518,21 @@ pub fn render_timeline(
         ui.memory_mut(|mem| mem.toggle_popup(ui.id().with("timeline_menu_popup")));
     }
 
+    let readout_rect = egui::Rect::from_min_size(
+        egui::pos2(menu_button_rect.max.x + controls_padding, control_y),
+        egui::vec2(readout_width, control_height),
+    );
+    render_time_readout(
+        ui,
+        readout_rect,
+        global_min,
+        global_max,
+        current_time,
+        is_playing,
+        *time_format,
+        data_store.start_time,
+    );
+
     egui::popup_above_or_below_widget(
         ui,
         ui.id().with("timeline_menu_popup"),
@@ -153,18 +541,164 @@ pub fn render_timeline(
         egui::PopupCloseBehavior::CloseOnClickOutside,
         |ui| {
             ui.set_min_width(150.0);
-            if ui.checkbox(lock_to_last, "Lock to Last").clicked() {
+            for mode in PlaybackMode::ALL {
+                ui.radio_value(playback_mode, mode, mode.label());
+            }
+            ui.horizontal(|ui| {
+                for (key, speed) in SPEED_PRESETS.into_iter().enumerate() {
+                    let selected =
+                        *playback_mode == PlaybackMode::Scaled && *playback_speed == speed;
+                    if ui
+                        .selectable_label(selected, format!("{speed}x"))
+                        .on_hover_text(format!("{}: {}", tr("Shortcut"), key + 1))
+                        .clicked()
+                    {
+                        apply_speed_preset(speed, playback_speed, playback_mode);
+                    }
+                }
+            });
+            ui.checkbox(reverse, tr("Reverse"))
+                .on_hover_text(format!("{}: R", tr("Shortcut")));
+            ui.horizontal(|ui| {
+                let clock = clock_topic
+                    .as_ref()
+                    .and_then(|topic| data_store.get_column(topic, "timestamp"));
+                let previous = clock.and_then(|times| step_time(times, *current_time, false));
+                let next = clock.and_then(|times| step_time(times, *current_time, true));
+
+                let previous_clicked = ui
+                    .add_enabled(previous.is_some(), egui::Button::new("⏴"))
+                    .on_hover_text(tr("Previous Sample"))
+                    .clicked();
+                egui::ComboBox::from_id_salt("clock_topic")
+                    .selected_text(clock_topic.as_deref().unwrap_or("Clock Topic..."))
+                    .show_ui(ui, |ui| {
+                        for topic in data_store.get_topics() {
+                            if ui
+                                .selectable_label(clock_topic.as_ref() == Some(topic), topic)
+                                .clicked()
+                            {
+                                *clock_topic = Some(topic.clone());
+                            }
+                        }
+                    })
+                    .response
+                    .on_hover_text(
+                        "Topic whose samples step mode and the arrow keys advance through.\n\
+                         Ctrl+Arrows: previous/next sample\n\
+                         Shift+Arrows: previous/next value change\n\
+                         Alt+Arrows: previous/next marker",
+                    );
+                let next_clicked = ui
+                    .add_enabled(next.is_some(), egui::Button::new("⏵"))
+                    .on_hover_text(tr("Next Sample"))
+                    .clicked();
+
+                let target = if previous_clicked {
+                    previous
+                } else if next_clicked {
+                    next
+                } else {
+                    None
+                };
+                if let Some(t) = target {
+                    *current_time = t.clamp(global_min, global_max);
+                    *is_playing = false;
+                }
+            });
+            ui.menu_button(format!("Activity: {}", activity.label()), |ui| {
+                ui.radio_value(activity, ActivityStrip::Off, tr("Off"));
+                ui.radio_value(activity, ActivityStrip::AllTopics, tr("All Topics"));
+                ui.separator();
+                egui::ScrollArea::vertical()
+                    .max_height(300.0)
+                    .show(ui, |ui| {
+                        for topic in data_store.get_topics() {
+                            ui.menu_button(topic, |ui| {
+                                ui.radio_value(
+                                    activity,
+                                    ActivityStrip::Topic(topic.clone()),
+                                    tr("Message Rate"),
+                                );
+                                ui.separator();
+                                for col in data_store.get_columns(topic) {
+                                    let strip = ActivityStrip::Column {
+                                        topic: topic.clone(),
+                                        col: col.clone(),
+                                    };
+                                    ui.radio_value(activity, strip, col.as_str());
+                                }
+                            });
+                        }
+                    });
+            })
+            .response
+            .on_hover_text(tr("Density or envelope drawn behind the timeline"));
+            ui.separator();
+            for format in TimeFormat::ALL {
+                ui.radio_value(time_format, format, format.label());
+            }
+            ui.separator();
+            if ui.checkbox(lock_to_last, tr("Lock to Last")).clicked() {
+                ui.memory_mut(|mem| mem.close_popup());
+            }
+            if ui.checkbox(lock_viewport, tr("Lock Viewport")).clicked() {
+                ui.memory_mut(|mem| mem.close_popup());
+            }
+            if ui
+                .checkbox(
+                    always_show_playback_tooltip,
+                    tr("Always Show Playback Tooltip"),
+                )
+                .clicked()
+            {
+                ui.memory_mut(|mem| mem.close_popup());
+            }
+            ui.separator();
+            if ui.button(tr("Add Marker at Cursor")).clicked() {
+                annotations.push(Annotation {
+                    time: *current_time,
+                    text: format!("Marker {}", annotations.len() + 1),
+                });
+                ui.memory_mut(|mem| mem.close_popup());
+            }
+            if !annotations.is_empty() && ui.button(tr("Clear Markers")).clicked() {
+                annotations.clear();
+                ui.memory_mut(|mem| mem.close_popup());
+            }
+            ui.separator();
+            if ui
+                .button(tr("Set Loop A at Cursor"))
+                .on_hover_text(tr("Shortcut: ["))
+                .clicked()
+            {
+                loop_region.set_a(*current_time);
                 ui.memory_mut(|mem| mem.close_popup());
             }
-            if ui.checkbox(lock_viewport, "Lock Viewport").clicked() {
+            if ui
+                .button(tr("Set Loop B at Cursor"))
+                .on_hover_text(tr("Shortcut: ]"))
+                .clicked()
+            {
+                loop_region.set_b(*current_time);
                 ui.memory_mut(|mem| mem.close_popup());
             }
+            let has_loop = loop_region.a.is_some() && loop_region.b.is_some();
             if ui
-                .checkbox(always_show_playback_tooltip, "Always Show Playback Tooltip")
+                .add_enabled(
+                    has_loop,
+                    egui::Checkbox::new(&mut loop_region.enabled, tr("Loop A-B")),
+                )
                 .clicked()
             {
                 ui.memory_mut(|mem| mem.close_popup());
             }
+            if (loop_region.a.is_some() || loop_region.b.is_some())
+                && ui.button(tr("Clear Loop")).clicked()
+            {
+                loop_region.clear();
+                ui.memory_mut(|mem| mem.close_popup());
+            }
         },
     );
 
@@ -173,6 +707,7 @@ pub fn render_timeline(
 
     ui.painter()
         .rect_filled(bar_rect, 2.0, egui::Color32::from_rgb(50, 50, 50));
+    render_activity_strip(ui, bar_rect, global_min, global_max, activity, data_store);
 
     let time_span = global_max - global_min;
     if time_span > 0.0 {
@@ -208,7 +743,7 @@ pub fn render_timeline(
                 ui.painter().text(
                     egui::pos2(x_px, bar_rect.center().y),
                     egui::Align2::CENTER_CENTER,
-                    format!("{:.1}s", t),
+                    time_format.format(t, data_store.start_time, 1),
                     egui::FontId::proportional(9.0),
                     egui::Color32::from_gray(180),
                 );
@@ -236,6 +771,72 @@ pub fn render_timeline(
         );
     }
 
+    if time_span > 0.0 {
+        let to_x = |t: f32| bar_rect.min.x + (t - global_min) / time_span * bar_rect.width();
+        let loop_color = egui::Color32::from_rgb(220, 120, 220);
+
+        if let (Some(a), Some(b)) = (loop_region.a, loop_region.b) {
+            let fill = if loop_region.enabled {
+                egui::Color32::from_rgba_unmultiplied(220, 120, 220, 50)
+            } else {
+                egui::Color32::from_rgba_unmultiplied(150, 150, 150, 30)
+            };
+            let loop_rect = egui::Rect::from_x_y_ranges(
+                to_x(a.min(b))..=to_x(a.max(b)),
+                bar_rect.min.y..=bar_rect.max.y,
+            );
+            ui.painter().rect_filled(loop_rect, 0.0, fill);
+        }
+
+        for (time, label) in [(loop_region.a, "A"), (loop_region.b, "B")] {
+            let Some(time) = time else {
+                continue;
+            };
+            let x_px = to_x(time);
+            ui.painter().line_segment(
+                [
+                    egui::pos2(x_px, bar_rect.min.y),
+                    egui::pos2(x_px, bar_rect.max.y),
+                ],
+                egui::Stroke::new(1.5, loop_color),
+            );
+            ui.painter().text(
+                egui::pos2(x_px + 2.0, bar_rect.min.y),
+                egui::Align2::LEFT_TOP,
+                label,
+                egui::FontId::proportional(9.0),
+                loop_color,
+            );
+        }
+    }
+
+    if time_span > 0.0 {
+        let marker_color = egui::Color32::from_rgb(120, 220, 120);
+        for annotation in annotations.iter() {
+            if annotation.time < global_min || annotation.time > global_max {
+                continue;
+            }
+
+            let x_norm = (annotation.time - global_min) / time_span;
+            let x_px = bar_rect.min.x + x_norm * bar_rect.width();
+
+            ui.painter().line_segment(
+                [
+                    egui::pos2(x_px, bar_rect.min.y),
+                    egui::pos2(x_px, bar_rect.max.y),
+                ],
+                egui::Stroke::new(1.0, marker_color),
+            );
+            ui.painter().text(
+                egui::pos2(x_px + 2.0, bar_rect.max.y),
+                egui::Align2::LEFT_BOTTOM,
+                &annotation.text,
+                egui::FontId::proportional(8.0),
+                marker_color,
+            );
+        }
+    }
+
     if time_span > 0.0 {
         let cursor_norm = (*current_time - global_min) / time_span;
         let cursor_x = bar_rect.min.x + cursor_norm * bar_rect.width();
@@ -267,7 +868,11 @@ pub fn render_timeline(
         egui::Sense::click_and_drag(),
     );
 
-    if (response.clicked() || response.dragged()) && ui.input(|i| i.pointer.primary_down()) {
+    let touching = multi_touch(ui, bar_rect).is_some();
+    if (response.clicked() || response.dragged())
+        && ui.input(|i| i.pointer.primary_down())
+        && !touching
+    {
         if let Some(pointer_pos) = response.interact_pointer_pos() {
             if bar_rect.contains(pointer_pos) {
                 let x_norm = ((pointer_pos.x - bar_rect.min.x) / bar_rect.width()).clamp(0.0, 1.0);
@@ -277,24 +882,108 @@ pub fn render_timeline(
         }
     }
 
-    if (response.clicked() || response.dragged()) && ui.input(|i| i.pointer.secondary_down()) {
-        if let Some(pointer_pos) = response.interact_pointer_pos() {
-            if bar_rect.contains(pointer_pos) {
-                let x_norm = ((pointer_pos.x - bar_rect.min.x) / bar_rect.width()).clamp(0.0, 1.0);
-                let current_t = global_min + x_norm * time_span;
-                let dist_to_min = current_t - *min_time;
-                let dist_to_max = current_t - *max_time;
-
-                if dist_to_max.abs() > dist_to_min.abs() {
-                    *min_time = current_t;
-                } else {
-                    *max_time = current_t;
-                }
+    // Right-dragging selects a range to zoom to.
+    let selection_id = ui.id().with("timeline_selection");
+    let to_time =
+        |x: f32| global_min + ((x - bar_rect.min.x) / bar_rect.width()).clamp(0.0, 1.0) * time_span;
+    if response.drag_started_by(egui::PointerButton::Secondary) {
+        if let Some(pos) = response.interact_pointer_pos() {
+            ui.data_mut(|d| d.insert_temp(selection_id, to_time(pos.x)));
+        }
+    }
+    let selection_start = ui.data(|d| d.get_temp::<f32>(selection_id));
+    if let (Some(start), Some(pos)) = (selection_start, response.interact_pointer_pos()) {
+        let end = to_time(pos.x);
+        let to_x = |t: f32| bar_rect.min.x + (t - global_min) / time_span * bar_rect.width();
+        let selection = egui::Rect::from_x_y_ranges(
+            to_x(start.min(end))..=to_x(start.max(end)),
+            bar_rect.y_range(),
+        );
+        ui.painter().rect(
+            selection,
+            0.0,
+            egui::Color32::from_rgba_unmultiplied(255, 255, 255, 30),
+            egui::Stroke::new(1.0, egui::Color32::from_gray(200)),
+        );
+        if response.drag_stopped_by(egui::PointerButton::Secondary) {
+            if (end - start).abs() > time_span * 0.001 {
+                zoom_to(
+                    min_time,
+                    max_time,
+                    start.min(end),
+                    start.max(end),
+                    global_min,
+                    global_max,
+                );
             }
+            ui.data_mut(|d| d.remove::<f32>(selection_id));
         }
     }
 
-    if response.dragged() && ui.input(|i| i.pointer.middle_down()) {
+    // A right-click or long press offers zooming to the whole log, the loop or a marker.
+    response.context_menu(|ui| {
+        if ui.button(tr("Zoom to Full Range")).clicked() {
+            zoom_to(
+                min_time, max_time, global_min, global_max, global_min, global_max,
+            );
+            ui.close_menu();
+        }
+        if let (Some(a), Some(b)) = (loop_region.a, loop_region.b) {
+            if a != b && ui.button(tr("Zoom to Loop A-B")).clicked() {
+                zoom_to(
+                    min_time,
+                    max_time,
+                    a.min(b),
+                    a.max(b),
+                    global_min,
+                    global_max,
+                );
+                ui.close_menu();
+            }
+        }
+        if annotations.is_empty() {
+            return;
+        }
+        ui.menu_button(tr("Zoom to Marker"), |ui| {
+            egui::ScrollArea::vertical()
+                .max_height(300.0)
+                .show(ui, |ui| {
+                    for annotation in annotations.iter() {
+                        let label = format!(
+                            "{}  {}",
+                            time_format.format(annotation.time, data_store.start_time, 1),
+                            annotation.text
+                        );
+                        if ui.button(label).clicked() {
+                            let span = (*max_time - *min_time).min(MARKER_ZOOM_SPAN);
+                            let start = annotation.time - span / 2.0;
+                            zoom_to(
+                                min_time,
+                                max_time,
+                                start,
+                                start + span,
+                                global_min,
+                                global_max,
+                            );
+                            *current_time = annotation.time;
+                            ui.close_menu();
+                        }
+                    }
+                });
+        });
+    });
+
+    // Touch screens: pinching zooms the view around the fingers, moving them together
+    // pans it.
+    if let Some(touch) = multi_touch(ui, bar_rect).filter(|_| time_span > 0.0) {
+        let anchor = to_time(touch.start_pos.x).clamp(*min_time, *max_time);
+        let zoom = touch.zoom_delta_2d.x.max(0.01);
+        let dt = touch.translation_delta.x / bar_rect.width() * time_span;
+
+        let new_min = anchor - (anchor - *min_time) / zoom + dt;
+        let new_max = anchor + (*max_time - anchor) / zoom + dt;
+        zoom_to(min_time, max_time, new_min, new_max, global_min, global_max);
+    } else if response.dragged() && ui.input(|i| i.pointer.middle_down()) {
         let delta = response.drag_delta();
         let width = bar_rect.width();
 
@@ -1,5 +1,8 @@
-use crate::core::DataStore;
+use super::favorites::{render_favorites, TopicFavorites};
+use crate::core::{DataStore, RangeStats, TopicStats, Transform};
+use crate::ui::i18n::tr;
 use eframe::egui;
+use egui_phosphor::regular as icons;
 use rustc_hash::FxHashSet;
 
 fn fuzzy_match(target: &str, query: &str) -> bool {
@@ -25,12 +28,68 @@ fn fuzzy_match(target: &str, query: &str) -> bool {
     false
 }
 
+/// Parses a value search query: a single number or an inclusive `min..max` range.
+fn parse_value_query(query: &str) -> Option<(f32, f32)> {
+    match query.split_once("..") {
+        Some((lo, hi)) => {
+            let lo = lo.trim().parse::<f32>().ok()?;
+            let hi = hi.trim().parse::<f32>().ok()?;
+            Some((lo.min(hi), lo.max(hi)))
+        }
+        None => {
+            let value = query.trim().parse::<f32>().ok()?;
+            Some((value, value))
+        }
+    }
+}
+
+#[derive(Default, Clone, Copy, PartialEq)]
+pub enum TopicSort {
+    #[default]
+    Name,
+    SampleCount,
+    Rate,
+}
+
+impl TopicSort {
+    const ALL: [TopicSort; 3] = [TopicSort::Name, TopicSort::SampleCount, TopicSort::Rate];
+
+    fn label(&self) -> &'static str {
+        match self {
+            TopicSort::Name => "Name",
+            TopicSort::SampleCount => "Samples",
+            TopicSort::Rate => "Rate",
+        }
+    }
+}
+
 #[derive(Default, Clone)]
 pub struct TopicPanelSelection {
     pub selected: FxHashSet<(String, String)>,
     pub last_clicked: Option<(String, String)>,
     pub filter: String,
+    pub sort: TopicSort,
+    /// Match the filter against column value ranges instead of names.
+    pub search_values: bool,
+    /// Set while the pointer is over the panel, so arrow keys and space navigate the list.
+    pub keyboard_active: bool,
+    cursor: Option<(String, String)>,
+    scroll_to_cursor: bool,
     was_filtering: bool,
+    rename_target: Option<String>,
+    rename_buffer: String,
+    hover_card: Option<ColumnCard>,
+}
+
+pub enum TopicAction {
+    None,
+    Rename { from: String, to: String },
+    Delete(String),
+    DeleteColumn(String, String),
+    Merge { source: String, target: String },
+    AddTransform(String, Transform),
+    PlotSelected { new_tile: bool },
+    PlotColumn(String, String),
 }
 
 impl TopicPanelSelection {
@@ -106,60 +165,294 @@ fn format_value(value: f32) -> String {
     }
 }
 
+/// A topic with its stats and the columns that passed the filter.
+type MatchingTopic = (String, Option<TopicStats>, Vec<(String, ColumnInfo)>);
+
 #[derive(Clone)]
 struct ColumnInfo {
     value_text: String,
+    meta_text: Option<String>,
 }
 
 impl ColumnInfo {
-    fn compute(data_store: &DataStore, topic: &str, col: &str) -> Self {
-        if let Some(data) = data_store.get_column(topic, col) {
-            if data.is_empty() {
-                Self {
-                    value_text: "<empty>".to_string(),
-                }
-            } else if data.len() == 1 {
-                Self {
-                    value_text: format!("[{}]", format_value(data[0])),
-                }
-            } else {
-                Self {
-                    value_text: format!(
-                        "[{} .. {}]",
-                        format_value(data[0]),
-                        format_value(data[data.len() - 1])
+    fn compute(data_store: &DataStore, topic: &str, col: &str, show_range: bool) -> Self {
+        let meta_text = data_store.column_meta(topic, col).and_then(|meta| {
+            let mut parts = Vec::new();
+            if !meta.unit.is_empty() {
+                parts.push(format!("Unit: {}", meta.unit));
+            }
+            if meta.scale != 1.0 {
+                parts.push(format!("Scale: {}", meta.scale));
+            }
+            if !meta.description.is_empty() {
+                parts.push(meta.description.clone());
+            }
+            (!parts.is_empty()).then(|| parts.join("\n"))
+        });
+
+        let format_value = |value: f32| format_column_value(data_store, topic, col, value);
+        let value_text = match data_store.get_column(topic, col) {
+            Some(data) if data.is_empty() => "<empty>".to_string(),
+            Some(data) if show_range => {
+                match data_store.column_range_stats(topic, col, 0, data.len()) {
+                    Some(stats) => format!(
+                        "{} ≤ x ≤ {}",
+                        format_value(stats.min),
+                        format_value(stats.max)
                     ),
+                    None => "<no values>".to_string(),
                 }
             }
-        } else {
-            Self {
-                value_text: "<no data>".to_string(),
-            }
+            Some(data) if data.len() == 1 => format!("[{}]", format_value(data[0])),
+            Some(data) => format!(
+                "[{} .. {}]",
+                format_value(data[0]),
+                format_value(data[data.len() - 1])
+            ),
+            None => "<no data>".to_string(),
+        };
+
+        Self {
+            value_text,
+            meta_text,
+        }
+    }
+}
+
+/// `value` in the column's configured format, if any.
+fn format_column_value(data_store: &DataStore, topic: &str, col: &str, value: f32) -> String {
+    match data_store.column_meta(topic, col) {
+        Some(meta) if !meta.format.is_default() => {
+            let unit = Some(meta.unit.as_str()).filter(|u| !u.is_empty());
+            meta.format.format(value, unit)
         }
+        _ => format_value(value),
     }
 }
 
+const SPARKLINE_BUCKETS: usize = 80;
+const SPARKLINE_SIZE: egui::Vec2 = egui::vec2(240.0, 36.0);
+
+/// Whole-log statistics and an overview of a column, shown when hovering it. Computed
+/// when first hovered and kept until another column is hovered or samples are added.
+#[derive(Clone)]
+struct ColumnCard {
+    topic: String,
+    col: String,
+    samples: usize,
+    rate_hz: f32,
+    stats: Option<RangeStats>,
+    /// Min and max of consecutive sample ranges, across the whole column.
+    sparkline: Vec<(f32, f32)>,
+}
+
+impl ColumnCard {
+    fn compute(data_store: &DataStore, topic: &str, col: &str) -> Self {
+        let samples = data_store.get_column(topic, col).map_or(0, Vec::len);
+        let buckets = SPARKLINE_BUCKETS.min(samples);
+        let sparkline = (0..buckets)
+            .filter_map(|i| {
+                let start = i * samples / buckets;
+                let end = (i + 1) * samples / buckets;
+                let stats = data_store.column_range_stats(topic, col, start, end)?;
+                Some((stats.min, stats.max))
+            })
+            .collect();
+
+        Self {
+            topic: topic.to_string(),
+            col: col.to_string(),
+            samples,
+            rate_hz: data_store.topic_stats(topic).map_or(0.0, |s| s.rate_hz),
+            stats: data_store.column_range_stats(topic, col, 0, samples),
+            sparkline,
+        }
+    }
+
+    fn is_current(&self, data_store: &DataStore, topic: &str, col: &str) -> bool {
+        self.topic == topic
+            && self.col == col
+            && data_store.get_column(topic, col).map_or(0, Vec::len) == self.samples
+    }
+
+    fn ui(&self, ui: &mut egui::Ui, data_store: &DataStore) {
+        let format = |value: f32| format_column_value(data_store, &self.topic, &self.col, value);
+
+        ui.strong(format!("{}/{}", self.topic, self.col));
+        egui::Grid::new("column_card_grid")
+            .num_columns(2)
+            .spacing([12.0, 2.0])
+            .show(ui, |ui| {
+                ui.label(tr("Samples"));
+                ui.monospace(self.samples.to_string());
+                ui.end_row();
+
+                ui.label(tr("Rate"));
+                ui.monospace(format!("{:.1} Hz", self.rate_hz));
+                ui.end_row();
+
+                if let Some(stats) = &self.stats {
+                    for (label, value) in [
+                        (tr("Min"), stats.min),
+                        (tr("Mean"), stats.mean),
+                        (tr("Max"), stats.max),
+                    ] {
+                        ui.label(label);
+                        ui.monospace(format(value));
+                        ui.end_row();
+                    }
+                }
+            });
+
+        let Some(stats) = self.stats.filter(|_| !self.sparkline.is_empty()) else {
+            return;
+        };
+        let (rect, _) = ui.allocate_exact_size(SPARKLINE_SIZE, egui::Sense::hover());
+        let painter = ui.painter_at(rect);
+        painter.rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+
+        let span = stats.max - stats.min;
+        let y = |value: f32| {
+            let t = if span > 0.0 {
+                (value - stats.min) / span
+            } else {
+                0.5
+            };
+            rect.bottom() - 2.0 - t * (rect.height() - 4.0)
+        };
+        let stroke = egui::Stroke::new(1.5, egui::Color32::from_rgb(100, 170, 255));
+        let width = rect.width() / self.sparkline.len() as f32;
+        for (i, &(min, max)) in self.sparkline.iter().enumerate() {
+            let x = rect.left() + (i as f32 + 0.5) * width;
+            let (top, bottom) = (y(max), y(min));
+            painter.line_segment(
+                [egui::pos2(x, top), egui::pos2(x, bottom.max(top + 1.0))],
+                stroke,
+            );
+        }
+    }
+}
+
+fn format_duration(seconds: f32) -> String {
+    if seconds >= 3600.0 {
+        format!("{:.1} h", seconds / 3600.0)
+    } else if seconds >= 60.0 {
+        format!("{:.1} min", seconds / 60.0)
+    } else {
+        format!("{:.1} s", seconds)
+    }
+}
+
+/// Topic name followed by its rate, sample count and duration in a smaller, dimmer font.
+fn topic_header(
+    ui: &egui::Ui,
+    data_store: &DataStore,
+    topic: &str,
+    stats: &Option<TopicStats>,
+) -> egui::text::LayoutJob {
+    let style = ui.style();
+    let spilled = data_store.is_spilled(topic);
+    let name_color = if spilled {
+        style.visuals.weak_text_color()
+    } else {
+        style.visuals.text_color()
+    };
+
+    let mut job = egui::text::LayoutJob::default();
+    let name = if spilled {
+        format!("{} {}", icons::HARD_DRIVES, topic)
+    } else {
+        topic.to_string()
+    };
+    job.append(
+        &name,
+        0.0,
+        egui::TextFormat::simple(egui::TextStyle::Body.resolve(style), name_color),
+    );
+
+    if let Some(stats) = stats.filter(|s| s.samples > 0) {
+        job.append(
+            &format!(
+                "{:.0} Hz · {} · {}",
+                stats.rate_hz,
+                stats.samples,
+                format_duration(stats.duration)
+            ),
+            8.0,
+            egui::TextFormat::simple(egui::FontId::monospace(10.0), egui::Color32::GRAY),
+        );
+    }
+
+    job
+}
+
 pub fn render_topic_panel(
     ui: &mut egui::Ui,
     data_store: &DataStore,
     selection: &mut TopicPanelSelection,
+    favorites: &mut TopicFavorites,
     dragged_item: &mut Option<(String, String)>,
-) {
+) -> TopicAction {
+    let mut action = TopicAction::None;
     ui.set_max_width(350.0);
+    let panel_rect = ui.max_rect();
 
     ui.horizontal(|ui| {
-        ui.label("Filter:");
+        ui.label(tr("Filter:"));
         ui.text_edit_singleline(&mut selection.filter);
         if ui.button("✖").clicked() {
             selection.filter.clear();
         }
     });
+    ui.horizontal(|ui| {
+        ui.label(tr("Sort:"));
+        egui::ComboBox::from_id_salt("topic_sort")
+            .selected_text(selection.sort.label())
+            .show_ui(ui, |ui| {
+                for sort in TopicSort::ALL {
+                    ui.selectable_value(&mut selection.sort, sort, sort.label());
+                }
+            });
+        ui.checkbox(&mut selection.search_values, tr("Search values"))
+            .on_hover_text(
+                "Match columns whose value range contains a number or overlaps 'min..max'",
+            );
+    });
+    ui.horizontal(|ui| {
+        let has_selection = !selection.selected.is_empty();
+        ui.label(tr("Plot selected:"));
+        if ui
+            .add_enabled(
+                has_selection,
+                egui::Button::new(format!("{} {}", icons::PLUS, tr("Current Tile"))),
+            )
+            .on_hover_text(tr("Add the selected columns to the focused plot (Enter)"))
+            .clicked()
+        {
+            action = TopicAction::PlotSelected { new_tile: false };
+        }
+        if ui
+            .add_enabled(
+                has_selection,
+                egui::Button::new(format!(
+                    "{} {}",
+                    icons::SQUARE_SPLIT_VERTICAL,
+                    tr("New Tile")
+                )),
+            )
+            .on_hover_text(tr("Add the selected columns to a new plot"))
+            .clicked()
+        {
+            action = TopicAction::PlotSelected { new_tile: true };
+        }
+    });
     ui.separator();
 
     if data_store.is_empty() {
-        ui.label("No data loaded yet.");
+        selection.keyboard_active = false;
+        ui.label(tr("No data loaded yet."));
         ui.separator();
-        return;
+        return action;
     }
 
     let topics = data_store.get_topics();
@@ -176,25 +469,49 @@ pub fn render_topic_panel(
         (selection.filter.to_lowercase(), None)
     };
 
-    let mut matching_items: Vec<(String, Vec<(String, ColumnInfo)>)> = Vec::new();
+    let value_query = if selection.search_values {
+        parse_value_query(&selection.filter)
+    } else {
+        None
+    };
+    let value_matches = |topic: &str, col: &str| {
+        let Some((lo, hi)) = value_query else {
+            return false;
+        };
+        let len = data_store.get_column(topic, col).map_or(0, |c| c.len());
+        data_store
+            .column_range_stats(topic, col, 0, len)
+            .is_some_and(|stats| stats.min <= hi && stats.max >= lo)
+    };
+
+    let mut matching_items: Vec<MatchingTopic> = Vec::new();
+    let mut visible_items: Vec<(String, String)> = Vec::new();
 
     for topic in &topics {
         let topic_matches = is_filtering && fuzzy_match(&topic.to_lowercase(), &topic_filter);
         let columns = data_store.get_columns(topic);
+        let show_range = value_query.is_some();
 
         let matching_columns: Vec<(String, ColumnInfo)> = if is_filtering {
             columns
                 .iter()
                 .filter_map(|col| {
-                    let col_lower = col.to_lowercase();
-                    let matches = if let Some(ref col_filter) = column_filter {
-                        topic_matches && fuzzy_match(&col_lower, col_filter)
+                    let matches = if value_query.is_some() {
+                        value_matches(topic, col)
                     } else {
-                        topic_matches || fuzzy_match(&col_lower, &topic_filter)
+                        let col_lower = col.to_lowercase();
+                        if let Some(ref col_filter) = column_filter {
+                            topic_matches && fuzzy_match(&col_lower, col_filter)
+                        } else {
+                            topic_matches || fuzzy_match(&col_lower, &topic_filter)
+                        }
                     };
 
                     if matches {
-                        Some(((*col).clone(), ColumnInfo::compute(data_store, topic, col)))
+                        Some((
+                            (*col).clone(),
+                            ColumnInfo::compute(data_store, topic, col, show_range),
+                        ))
                     } else {
                         None
                     }
@@ -203,12 +520,32 @@ pub fn render_topic_panel(
         } else {
             columns
                 .iter()
-                .map(|col| ((*col).clone(), ColumnInfo::compute(data_store, topic, col)))
+                .map(|col| {
+                    (
+                        (*col).clone(),
+                        ColumnInfo::compute(data_store, topic, col, false),
+                    )
+                })
                 .collect()
         };
 
         if !matching_columns.is_empty() {
-            matching_items.push(((*topic).clone(), matching_columns));
+            matching_items.push((
+                (*topic).clone(),
+                data_store.topic_stats(topic),
+                matching_columns,
+            ));
+        }
+    }
+
+    let rate = |stats: &Option<TopicStats>| stats.map_or(0.0, |s| s.rate_hz);
+    match selection.sort {
+        TopicSort::Name => {}
+        TopicSort::SampleCount => {
+            matching_items.sort_by_key(|item| std::cmp::Reverse(item.1.map_or(0, |s| s.samples)));
+        }
+        TopicSort::Rate => {
+            matching_items.sort_by(|a, b| rate(&b.1).total_cmp(&rate(&a.1)));
         }
     }
 
@@ -217,8 +554,19 @@ pub fn render_topic_panel(
         .show(ui, |ui| {
             ui.style_mut().interaction.selectable_labels = false;
 
-            for (topic, columns) in &matching_items {
-                egui::CollapsingHeader::new(topic.as_str())
+            if !is_filtering {
+                if let Some((topic, col)) =
+                    render_favorites(ui, favorites, data_store, selection, dragged_item)
+                {
+                    action = TopicAction::PlotColumn(topic, col);
+                }
+            }
+
+            for (topic, stats, columns) in &matching_items {
+                let header = topic_header(ui, data_store, topic, stats);
+
+                let collapsing = egui::CollapsingHeader::new(header)
+                    .id_salt(topic.as_str())
                     .default_open(false)
                     .open(if is_filtering {
                         Some(true)
@@ -229,7 +577,7 @@ pub fn render_topic_panel(
                     })
                     .show(ui, |ui| {
                         if columns.is_empty() {
-                            ui.label("(no columns)");
+                            ui.label(tr("(no columns)"));
                             return;
                         }
 
@@ -239,8 +587,11 @@ pub fn render_topic_panel(
                             .collect();
 
                         for (col, col_info) in columns {
-                            let is_selected =
-                                selection.selected.contains(&(topic.clone(), col.clone()));
+                            let item = (topic.clone(), col.clone());
+                            let is_selected = selection.selected.contains(&item);
+                            let is_cursor = selection.keyboard_active
+                                && selection.cursor.as_ref() == Some(&item);
+                            visible_items.push(item);
                             let value_text = &col_info.value_text;
 
                             let (rect, response) = ui.allocate_exact_size(
@@ -257,6 +608,17 @@ pub fn render_topic_panel(
                                     );
                                 }
 
+                                if is_cursor {
+                                    ui.painter().rect_stroke(
+                                        rect.shrink(1.0),
+                                        0.0,
+                                        egui::Stroke::new(
+                                            1.0,
+                                            egui::Color32::from_rgb(120, 170, 240),
+                                        ),
+                                    );
+                                }
+
                                 let col_color = if is_selected {
                                     egui::Color32::WHITE
                                 } else {
@@ -288,7 +650,13 @@ pub fn render_topic_panel(
                                 );
                             }
 
+                            if is_cursor && selection.scroll_to_cursor {
+                                ui.scroll_to_rect(rect, None);
+                                selection.scroll_to_cursor = false;
+                            }
+
                             if response.clicked() {
+                                selection.cursor = Some((topic.clone(), col.clone()));
                                 let modifiers = ui.input(|i| i.modifiers);
 
                                 if modifiers.shift {
@@ -301,6 +669,10 @@ pub fn render_topic_panel(
                                 }
                             }
 
+                            if response.double_clicked() {
+                                action = TopicAction::PlotColumn(topic.clone(), col.clone());
+                            }
+
                             if response.dragged() {
                                 *dragged_item = Some((topic.clone(), col.clone()));
                                 ui.output_mut(|o| o.cursor_icon = egui::CursorIcon::Grabbing);
@@ -324,6 +696,33 @@ pub fn render_topic_panel(
                                 );
                             }
 
+                            response.context_menu(|ui| {
+                                let starred = favorites.is_starred(topic, col);
+                                let star_label = if starred { "Unstar" } else { "Star" };
+                                if ui
+                                    .button(format!("{} {}", icons::STAR, star_label))
+                                    .clicked()
+                                {
+                                    favorites.toggle_star(topic, col);
+                                    ui.close_menu();
+                                }
+
+                                if ui
+                                    .add_enabled(
+                                        col != "timestamp",
+                                        egui::Button::new(format!(
+                                            "{} {}",
+                                            icons::TRASH,
+                                            tr("Delete Column")
+                                        )),
+                                    )
+                                    .clicked()
+                                {
+                                    action = TopicAction::DeleteColumn(topic.clone(), col.clone());
+                                    ui.close_menu();
+                                }
+                            });
+
                             if response.hovered() && dragged_item.is_none() {
                                 let hover_text = if is_selected && selection.selected.len() > 1 {
                                     format!(
@@ -331,12 +730,157 @@ pub fn render_topic_panel(
                                         selection.selected.len()
                                     )
                                 } else {
-                                    format!("Drag to add {} to a plot", col)
+                                    format!("Drag or double-click to add {} to a plot", col)
                                 };
-                                response.on_hover_text(hover_text);
+                                if !selection
+                                    .hover_card
+                                    .as_ref()
+                                    .is_some_and(|card| card.is_current(data_store, topic, col))
+                                {
+                                    selection.hover_card =
+                                        Some(ColumnCard::compute(data_store, topic, col));
+                                }
+                                let card = selection.hover_card.as_ref();
+                                response.on_hover_ui(|ui| {
+                                    if let Some(card) = card {
+                                        card.ui(ui, data_store);
+                                    }
+                                    if let Some(meta) = &col_info.meta_text {
+                                        ui.separator();
+                                        ui.label(meta);
+                                    }
+                                    ui.separator();
+                                    ui.label(egui::RichText::new(hover_text).small().weak());
+                                });
                             }
                         }
                     });
+
+                collapsing.header_response.context_menu(|ui| {
+                    if selection.rename_target.as_ref() != Some(topic) {
+                        selection.rename_target = Some(topic.clone());
+                        selection.rename_buffer = topic.clone();
+                    }
+
+                    ui.menu_button(format!("{} {}", icons::PENCIL_SIMPLE, tr("Rename")), |ui| {
+                        let edit = ui.text_edit_singleline(&mut selection.rename_buffer);
+                        let submitted =
+                            edit.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                        if ui.button(tr("Rename")).clicked() || submitted {
+                            action = TopicAction::Rename {
+                                from: topic.clone(),
+                                to: selection.rename_buffer.trim().to_string(),
+                            };
+                            selection.rename_target = None;
+                            ui.close_menu();
+                        }
+                    });
+
+                    ui.menu_button(format!("{} {}", icons::GIT_MERGE, tr("Merge Into")), |ui| {
+                        egui::ScrollArea::vertical()
+                            .max_height(300.0)
+                            .show(ui, |ui| {
+                                for other in topics.iter().filter(|t| **t != topic) {
+                                    if ui.button(other.as_str()).clicked() {
+                                        action = TopicAction::Merge {
+                                            source: topic.clone(),
+                                            target: (*other).clone(),
+                                        };
+                                        ui.close_menu();
+                                    }
+                                }
+                            });
+                    });
+
+                    let columns = data_store.get_columns(topic);
+                    for transform in Transform::detect(&columns)
+                        .into_iter()
+                        .filter(|t| !data_store.has_transform(topic, t))
+                    {
+                        if ui
+                            .button(format!("{} Add {}", icons::FUNCTION, transform.name()))
+                            .on_hover_text(transform.description())
+                            .clicked()
+                        {
+                            action = TopicAction::AddTransform(topic.clone(), transform);
+                            ui.close_menu();
+                        }
+                    }
+
+                    ui.separator();
+
+                    if ui
+                        .button(format!("{} {}", icons::TRASH, tr("Delete Topic")))
+                        .clicked()
+                    {
+                        action = TopicAction::Delete(topic.clone());
+                        ui.close_menu();
+                    }
+                });
             }
         });
+
+    selection.keyboard_active =
+        ui.rect_contains_pointer(panel_rect) && !ui.ctx().wants_keyboard_input();
+    if selection.keyboard_active {
+        if let Some(keyboard_action) = handle_keyboard(ui, selection, &visible_items) {
+            action = keyboard_action;
+        }
+    }
+
+    action
+}
+
+/// Arrow keys move the cursor, shift extends the selection, space toggles the item under
+/// the cursor and enter plots the selection.
+fn handle_keyboard(
+    ui: &egui::Ui,
+    selection: &mut TopicPanelSelection,
+    visible_items: &[(String, String)],
+) -> Option<TopicAction> {
+    let (down, up, space, enter, escape, shift) = ui.input(|i| {
+        (
+            i.key_pressed(egui::Key::ArrowDown),
+            i.key_pressed(egui::Key::ArrowUp),
+            i.key_pressed(egui::Key::Space),
+            i.key_pressed(egui::Key::Enter),
+            i.key_pressed(egui::Key::Escape),
+            i.modifiers.shift,
+        )
+    });
+
+    if visible_items.is_empty() {
+        return None;
+    }
+
+    if down || up {
+        let current = selection
+            .cursor
+            .as_ref()
+            .and_then(|cursor| visible_items.iter().position(|item| item == cursor));
+        let next = match current {
+            Some(pos) if down => (pos + 1).min(visible_items.len() - 1),
+            Some(pos) => pos.saturating_sub(1),
+            None => 0,
+        };
+        let (topic, col) = visible_items[next].clone();
+        if shift {
+            selection.select(&topic, &col);
+        }
+        selection.cursor = Some((topic, col));
+        selection.scroll_to_cursor = true;
+    }
+
+    if space {
+        if let Some((topic, col)) = selection.cursor.clone() {
+            selection.toggle(&topic, &col);
+        }
+    }
+
+    if escape {
+        selection.clear();
+    }
+
+    (enter && !selection.selected.is_empty())
+        .then_some(TopicAction::PlotSelected { new_tile: false })
 }
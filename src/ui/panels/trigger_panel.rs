@@ -0,0 +1,236 @@
+use crate::core::{Crossing, DataStore, Trigger, TriggerActions, TriggerCondition};
+use crate::ui::i18n::tr;
+use eframe::egui;
+use egui_phosphor::regular as icons;
+
+#[derive(Clone, Copy, PartialEq)]
+enum ConditionKind {
+    Threshold,
+    Stale,
+}
+
+/// Live-mode triggers and the form for adding new ones.
+pub struct TriggerPanel {
+    pub open: bool,
+    pub triggers: Vec<Trigger>,
+    kind: ConditionKind,
+    topic: String,
+    col: String,
+    threshold: f32,
+    crossing: Crossing,
+    timeout_secs: f32,
+    actions: TriggerActions,
+}
+
+impl TriggerPanel {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            triggers: Vec::new(),
+            kind: ConditionKind::Threshold,
+            topic: String::new(),
+            col: String::new(),
+            threshold: 0.0,
+            crossing: Crossing::Rising,
+            timeout_secs: 2.0,
+            actions: TriggerActions::default(),
+        }
+    }
+
+    fn draft_condition(&self) -> Option<TriggerCondition> {
+        if self.topic.is_empty() {
+            return None;
+        }
+        match self.kind {
+            ConditionKind::Threshold if !self.col.is_empty() => Some(TriggerCondition::Threshold {
+                topic: self.topic.clone(),
+                col: self.col.clone(),
+                threshold: self.threshold,
+                crossing: self.crossing,
+            }),
+            ConditionKind::Threshold => None,
+            ConditionKind::Stale => Some(TriggerCondition::Stale {
+                topic: self.topic.clone(),
+                timeout_secs: self.timeout_secs,
+            }),
+        }
+    }
+}
+
+impl Default for TriggerPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn actions_ui(ui: &mut egui::Ui, actions: &mut TriggerActions) {
+    ui.checkbox(&mut actions.annotate, tr("Annotate"));
+    ui.checkbox(&mut actions.freeze_view, tr("Freeze view"));
+    ui.checkbox(&mut actions.pause_stream, tr("Pause stream"));
+}
+
+pub fn render_trigger_panel(
+    ctx: &egui::Context,
+    panel: &mut TriggerPanel,
+    data_store: &DataStore,
+    frozen: &mut bool,
+) {
+    if !panel.open {
+        return;
+    }
+
+    let mut open = panel.open;
+    egui::Window::new(tr("Triggers"))
+        .open(&mut open)
+        .default_width(420.0)
+        .resizable(true)
+        .collapsible(false)
+        .show(ctx, |ui| {
+            if *frozen {
+                ui.horizontal(|ui| {
+                    ui.colored_label(
+                        egui::Color32::from_rgb(120, 180, 255),
+                        format!("{} {}", icons::SNOWFLAKE, tr("View frozen by a trigger")),
+                    );
+                    if ui
+                        .button(format!("{} {}", icons::PLAY, tr("Resume View")))
+                        .clicked()
+                    {
+                        *frozen = false;
+                    }
+                });
+                ui.separator();
+            }
+
+            if panel.triggers.is_empty() {
+                ui.label(egui::RichText::new(tr("No triggers")).weak());
+            }
+
+            let mut remove = None;
+            egui::Grid::new("trigger_list")
+                .num_columns(4)
+                .striped(true)
+                .spacing([10.0, 4.0])
+                .show(ui, |ui| {
+                    for (idx, trigger) in panel.triggers.iter_mut().enumerate() {
+                        if ui.checkbox(&mut trigger.enabled, "").changed() && trigger.enabled {
+                            trigger.arm(data_store);
+                        }
+                        ui.label(trigger.condition.describe());
+                        let fired = match trigger.last_fired {
+                            Some(time) => format!("{}× (t={:.2})", trigger.fire_count, time),
+                            None => format!("{}×", trigger.fire_count),
+                        };
+                        ui.label(fired);
+                        ui.horizontal(|ui| {
+                            ui.menu_button(icons::GEAR, |ui| {
+                                actions_ui(ui, &mut trigger.actions);
+                                ui.horizontal(|ui| {
+                                    ui.label(tr("Holdoff"));
+                                    ui.add(
+                                        egui::DragValue::new(&mut trigger.holdoff_secs)
+                                            .speed(0.1)
+                                            .range(0.0..=3600.0)
+                                            .suffix(" s"),
+                                    );
+                                });
+                            });
+                            if ui.button(icons::TRASH).clicked() {
+                                remove = Some(idx);
+                            }
+                        });
+                        ui.end_row();
+                    }
+                });
+            if let Some(idx) = remove {
+                panel.triggers.remove(idx);
+            }
+
+            ui.separator();
+            ui.label(egui::RichText::new(tr("New Trigger")).strong());
+
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut panel.kind, ConditionKind::Threshold, tr("Threshold"));
+                ui.radio_value(&mut panel.kind, ConditionKind::Stale, tr("Topic stops"));
+            });
+
+            ui.horizontal(|ui| {
+                egui::ComboBox::from_id_salt("trigger_topic")
+                    .selected_text(&panel.topic)
+                    .width(160.0)
+                    .show_ui(ui, |ui| {
+                        for topic in data_store.get_topics() {
+                            if ui
+                                .selectable_label(&panel.topic == topic, topic.as_str())
+                                .clicked()
+                            {
+                                panel.topic = topic.clone();
+                                panel.col.clear();
+                            }
+                        }
+                    });
+
+                match panel.kind {
+                    ConditionKind::Threshold => {
+                        egui::ComboBox::from_id_salt("trigger_col")
+                            .selected_text(&panel.col)
+                            .width(140.0)
+                            .show_ui(ui, |ui| {
+                                for col in data_store.get_columns(&panel.topic) {
+                                    if ui
+                                        .selectable_label(&panel.col == col, col.as_str())
+                                        .clicked()
+                                    {
+                                        panel.col = col.clone();
+                                    }
+                                }
+                            });
+                    }
+                    ConditionKind::Stale => {
+                        ui.label(tr("for"));
+                        ui.add(
+                            egui::DragValue::new(&mut panel.timeout_secs)
+                                .speed(0.1)
+                                .range(0.1..=3600.0)
+                                .suffix(" s"),
+                        );
+                    }
+                }
+            });
+
+            if panel.kind == ConditionKind::Threshold {
+                ui.horizontal(|ui| {
+                    egui::ComboBox::from_id_salt("trigger_crossing")
+                        .selected_text(panel.crossing.label())
+                        .show_ui(ui, |ui| {
+                            for crossing in Crossing::ALL {
+                                ui.selectable_value(
+                                    &mut panel.crossing,
+                                    crossing,
+                                    crossing.label(),
+                                );
+                            }
+                        });
+                    ui.add(egui::DragValue::new(&mut panel.threshold).speed(0.1));
+                });
+            }
+
+            ui.horizontal(|ui| actions_ui(ui, &mut panel.actions));
+
+            let condition = panel.draft_condition();
+            if ui
+                .add_enabled(
+                    condition.is_some(),
+                    egui::Button::new(format!("{} {}", icons::PLUS, tr("Add Trigger"))),
+                )
+                .clicked()
+            {
+                if let Some(condition) = condition {
+                    let mut trigger = Trigger::new(condition, panel.actions);
+                    trigger.arm(data_store);
+                    panel.triggers.push(trigger);
+                }
+            }
+        });
+    panel.open = open;
+}
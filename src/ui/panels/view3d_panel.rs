@@ -1,4 +1,4 @@
-use crate::core::DataStore;
+use crate::core::{AliasProfile, DataStore};
 use crate::ui::panels::tabs::config::{render_configuration_tab, VehicleConfig};
 use crate::ui::panels::tabs::gltf_loader::ModelCache;
 use crate::ui::panels::tabs::scene::{render_scene_tab, SceneState};
@@ -35,7 +35,7 @@ pub fn render_view3d_panel(
     data_store: &DataStore,
     current_time: f32,
     model_cache: &ModelCache,
-) {
+) -> Option<f32> {
     render_scene_tab(
         ui,
         frame,
@@ -44,13 +44,15 @@ pub fn render_view3d_panel(
         current_time,
         &mut panel_state.scene_state,
         model_cache,
-    );
+    )
+    .inner
 }
 
 pub fn render_config_window(
     ctx: &egui::Context,
     panel_state: &mut View3DPanel,
     data_store: &DataStore,
+    alias_profiles: &[AliasProfile],
 ) {
     egui::Window::new("Vehicle Configuration")
         .id(egui::Id::new("vehicle_config_window"))
@@ -62,6 +64,6 @@ pub fn render_config_window(
         .scroll([false, true])
         .order(egui::Order::Foreground)
         .show(ctx, |ui| {
-            render_configuration_tab(ui, &mut panel_state.vehicles, data_store);
+            render_configuration_tab(ui, &mut panel_state.vehicles, data_store, alias_profiles);
         });
 }
@@ -0,0 +1,127 @@
+use super::RecentFiles;
+use crate::acquisition::StreamStatus;
+use crate::ui::i18n::tr;
+use crate::ui::is_loader_available;
+use crate::ui::menu::{MenuAction, REOPEN_LAST_SHORTCUT};
+use eframe::egui;
+use egui_phosphor::regular as icons;
+
+const MAX_RECENT_SHOWN: usize = 5;
+const BUTTON_SIZE: egui::Vec2 = egui::vec2(220.0, 32.0);
+
+/// Shown in place of the plots while nothing is loaded, with the ways to get data in.
+pub fn render_welcome(
+    ui: &mut egui::Ui,
+    recent_files: &RecentFiles,
+    stream_status: &StreamStatus,
+) -> MenuAction {
+    let mut action = MenuAction::None;
+
+    ui.vertical_centered(|ui| {
+        ui.add_space((ui.available_height() * 0.2).max(20.0));
+        ui.label(egui::RichText::new(icons::CHART_LINE).size(48.0).weak());
+        ui.heading(tr("No data loaded"));
+        ui.label(egui::RichText::new(tr("Open a log or stream live data to get started")).weak());
+        ui.add_space(20.0);
+
+        let button = |text: String| egui::Button::new(text).min_size(BUTTON_SIZE);
+
+        if ui
+            .add(button(format!(
+                "{} {}",
+                icons::FOLDER_OPEN,
+                tr("Open File...")
+            )))
+            .clicked()
+        {
+            action = MenuAction::LoadData;
+        }
+
+        if ui
+            .add_enabled(
+                is_loader_available(),
+                button(format!("{} {}", icons::ROCKET_LAUNCH, tr("Connect Loader"))),
+            )
+            .on_hover_text(tr(
+                "Start the loader, which converts logs and streams them here",
+            ))
+            .on_disabled_hover_text(tr("The loader was not found next to TiPlot"))
+            .clicked()
+        {
+            action = MenuAction::LaunchLoader;
+        }
+
+        match stream_status {
+            StreamStatus::Listening(addr) => {
+                ui.add_enabled(
+                    false,
+                    button(format!(
+                        "{} {} {}",
+                        icons::BROADCAST,
+                        tr("Listening on"),
+                        addr
+                    )),
+                );
+            }
+            StreamStatus::Connected(addr) | StreamStatus::Following(addr) => {
+                ui.add_enabled(
+                    false,
+                    button(format!(
+                        "{} {} {}",
+                        icons::PLUGS_CONNECTED,
+                        tr("Connected to"),
+                        addr
+                    )),
+                );
+            }
+            StreamStatus::Stopped | StreamStatus::Error(_) => {
+                let response = ui.add(button(format!(
+                    "{} {}",
+                    icons::BROADCAST,
+                    tr("Start Live Listener")
+                )));
+                let response = match stream_status {
+                    StreamStatus::Error(e) => response.on_hover_text(e),
+                    _ => response,
+                };
+                if response.clicked() {
+                    action = MenuAction::StartListener;
+                }
+            }
+        }
+
+        if recent_files.data.is_empty() {
+            return;
+        }
+        ui.add_space(20.0);
+        ui.label(egui::RichText::new(tr("Recent Files")).strong());
+        for path in recent_files.data.iter().take(MAX_RECENT_SHOWN) {
+            let name = path.file_name().map_or_else(
+                || path.display().to_string(),
+                |n| n.to_string_lossy().into_owned(),
+            );
+            if ui
+                .add_enabled(path.exists(), egui::Link::new(name))
+                .on_hover_text(path.display().to_string())
+                .on_disabled_hover_text(format!("{}: {}", tr("File not found"), path.display()))
+                .clicked()
+            {
+                action = MenuAction::OpenDataFile(path.clone());
+            }
+        }
+        ui.add_space(5.0);
+        if ui
+            .button(format!(
+                "{} {} ({})",
+                icons::ARROW_COUNTER_CLOCKWISE,
+                tr("Reopen Last"),
+                ui.ctx().format_shortcut(&REOPEN_LAST_SHORTCUT)
+            ))
+            .clicked()
+        {
+            action = MenuAction::ReopenLast;
+        }
+    });
+
+    action
+}
@@ -1,3 +1,5 @@
+use crate::ui::scene_renderer::no_depth_state;
+use crate::ui::tiles::plot_tile::{MathOp, TraceFill, TraceMath};
 use eframe::egui;
 use eframe::egui_wgpu::{CallbackResources, CallbackTrait};
 use std::collections::{HashMap, VecDeque};
@@ -12,6 +14,7 @@ pub struct TraceGpuResource {
 pub struct PlotRenderer {
     pub pipeline: wgpu::RenderPipeline,
     pub point_pipeline: wgpu::RenderPipeline,
+    pub fill_pipeline: wgpu::RenderPipeline,
 
     pub bind_group_layout: wgpu::BindGroupLayout,
 
@@ -50,6 +53,16 @@ impl PlotRenderer {
                     },
                     count: None,
                 },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::VERTEX,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: true },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
             ],
         });
 
@@ -87,7 +100,7 @@ impl PlotRenderer {
                 unclipped_depth: false,
                 conservative: false,
             },
-            depth_stencil: None,
+            depth_stencil: Some(no_depth_state()),
             multisample: wgpu::MultisampleState::default(),
             multiview: None,
             cache: None,
@@ -121,7 +134,41 @@ impl PlotRenderer {
                 unclipped_depth: false,
                 conservative: false,
             },
-            depth_stencil: None,
+            depth_stencil: Some(no_depth_state()),
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        let fill_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Plot Fill Render Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: "vs_fill",
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: "fs_main",
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleStrip,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: None,
+                polygon_mode: wgpu::PolygonMode::Fill,
+                unclipped_depth: false,
+                conservative: false,
+            },
+            depth_stencil: Some(no_depth_state()),
             multisample: wgpu::MultisampleState::default(),
             multiview: None,
             cache: None,
@@ -130,6 +177,7 @@ impl PlotRenderer {
         Self {
             pipeline,
             point_pipeline,
+            fill_pipeline,
             bind_group_layout,
             buffers: HashMap::new(),
             paint_jobs: Mutex::new(VecDeque::new()),
@@ -172,6 +220,35 @@ impl PlotRenderer {
         );
     }
 
+    /// Drops every trace buffer, e.g. before the traces of newly loaded data go up.
+    pub fn clear_traces(&mut self) {
+        self.buffers.clear();
+    }
+
+    pub fn remove_trace(&mut self, topic: &str, col: &str) {
+        self.buffers.remove(&format!("{}/{}", topic, col));
+    }
+
+    /// Bytes of trace buffers per topic.
+    pub fn memory_by_topic(&self) -> HashMap<String, u64> {
+        let mut usage: HashMap<String, u64> = HashMap::new();
+        for (key, resource) in &self.buffers {
+            if let Some((topic, _)) = key.rsplit_once('/') {
+                *usage.entry(topic.to_string()).or_default() += resource.buffer.size();
+            }
+        }
+        usage
+    }
+
+    /// Frees all trace buffers right away instead of when the device is dropped.
+    pub fn release(&mut self) {
+        for resource in self.buffers.values() {
+            resource.buffer.destroy();
+        }
+        self.buffers.clear();
+        self.paint_jobs.lock().unwrap().clear();
+    }
+
     pub fn _get_trace(&self, topic: &str, col: &str) -> Option<&TraceGpuResource> {
         let key = format!("{}/{}", topic, col);
         self.buffers.get(&key)
@@ -181,9 +258,23 @@ impl PlotRenderer {
 pub struct RealPlotCallback {
     pub topic: String,
     pub col: String,
-    pub bounds: [f32; 4], // [min_time, max_time, min_val, max_val]
+    pub bounds: [f32; 4], // [min_time, max_time, min_val, max_val] in display units
     pub color: [f32; 4],  // RGBA
     pub scatter_mode: bool,
+    /// Factor taking stored values into display units.
+    pub scale: f32,
+    pub math: TraceMath,
+}
+
+/// Uniform values for `display` in the shader.
+fn math_uniform(math: &TraceMath, scale: f32) -> [f32; 4] {
+    let op = match math.op {
+        MathOp::None => 0.0,
+        MathOp::Abs => 1.0,
+        MathOp::Negate => 2.0,
+        MathOp::WrapPi => 3.0,
+    };
+    [math.gain, math.offset, op, scale]
 }
 
 impl CallbackTrait for RealPlotCallback {
@@ -206,6 +297,7 @@ impl CallbackTrait for RealPlotCallback {
                 .chain(self.color.iter())
                 .cloned()
                 .chain([point_size, 0.0, 0.0, 0.0].iter().cloned()) // params vec4
+                .chain(math_uniform(&self.math, self.scale))
                 .collect();
 
             let uniform_buf = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
@@ -226,6 +318,10 @@ impl CallbackTrait for RealPlotCallback {
                         binding: 1,
                         resource: trace_res.buffer.as_entire_binding(),
                     },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: trace_res.buffer.as_entire_binding(),
+                    },
                 ],
             });
 
@@ -260,3 +356,117 @@ impl CallbackTrait for RealPlotCallback {
         }
     }
 }
+
+/// Filled area of a trace, drawn below its line.
+pub struct FillPlotCallback {
+    pub topic: String,
+    pub col: String,
+    pub fill: TraceFill,
+    pub bounds: [f32; 4], // [min_time, max_time, min_val, max_val] in display units
+    pub color: [f32; 4],
+    pub scale: f32,
+    pub math: TraceMath,
+}
+
+impl FillPlotCallback {
+    fn other_key(&self) -> String {
+        match &self.fill {
+            TraceFill::UnderCurve => format!("{}/{}", self.topic, self.col),
+            TraceFill::Between(other) | TraceFill::PlusMinus(other) => {
+                format!("{}/{}", self.topic, other)
+            }
+        }
+    }
+
+    /// Vertices of the triangle strip, two per sample both columns have.
+    fn vertex_count(&self, renderer: &PlotRenderer) -> Option<u32> {
+        let trace = renderer
+            .buffers
+            .get(&format!("{}/{}", self.topic, self.col))?;
+        let other = renderer.buffers.get(&self.other_key())?;
+        Some(trace.count.min(other.count) * 2)
+    }
+}
+
+impl CallbackTrait for FillPlotCallback {
+    fn prepare(
+        &self,
+        device: &wgpu::Device,
+        _queue: &wgpu::Queue,
+        _screen: &eframe::egui_wgpu::ScreenDescriptor,
+        _encoder: &mut wgpu::CommandEncoder,
+        resources: &mut CallbackResources,
+    ) -> Vec<wgpu::CommandBuffer> {
+        let renderer = resources.get::<PlotRenderer>().unwrap();
+        let (Some(trace_res), Some(other_res)) = (
+            renderer
+                .buffers
+                .get(&format!("{}/{}", self.topic, self.col)),
+            renderer.buffers.get(&self.other_key()),
+        ) else {
+            return Vec::new();
+        };
+
+        // Fill down to zero, or to the nearest edge when zero is out of view.
+        let baseline = 0.0f32.clamp(self.bounds[2], self.bounds[3]);
+        let mode = match self.fill {
+            TraceFill::UnderCurve => 0.0,
+            TraceFill::Between(_) => 1.0,
+            TraceFill::PlusMinus(_) => 2.0,
+        };
+        let uniforms_data: Vec<f32> = self
+            .bounds
+            .iter()
+            .chain(self.color.iter())
+            .cloned()
+            .chain([0.0, baseline, mode, 0.0])
+            .chain(math_uniform(&self.math, self.scale))
+            .collect();
+
+        let uniform_buf = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Plot Fill Uniform Buffer"),
+            contents: bytemuck::cast_slice(&uniforms_data),
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Plot Fill Bind Group"),
+            layout: &renderer.bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: uniform_buf.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: trace_res.buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: other_res.buffer.as_entire_binding(),
+                },
+            ],
+        });
+
+        renderer.paint_jobs.lock().unwrap().push_back(bind_group);
+        Vec::new()
+    }
+
+    fn paint<'a>(
+        &'a self,
+        _info: egui::PaintCallbackInfo,
+        render_pass: &mut wgpu::RenderPass<'static>,
+        resources: &'a CallbackResources,
+    ) {
+        let renderer = resources.get::<PlotRenderer>().unwrap();
+        let Some(count) = self.vertex_count(renderer) else {
+            return;
+        };
+
+        if let Some(bg) = renderer.paint_jobs.lock().unwrap().pop_front() {
+            render_pass.set_pipeline(&renderer.fill_pipeline);
+            render_pass.set_bind_group(0, &bg, &[]);
+            render_pass.draw(0..count, 0..1);
+        }
+    }
+}
@@ -0,0 +1,603 @@
+use eframe::egui;
+use eframe::egui_wgpu::{CallbackResources, CallbackTrait};
+use glam::{Mat4, Vec3};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use wgpu::util::DeviceExt;
+
+/// Depth buffer format requested from eframe. Every pipeline drawn into the egui render
+/// pass has to declare it.
+pub const DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth24Plus;
+
+/// One end of a line segment. Line lists store segments as pairs, line strips share
+/// points between neighbouring segments.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct LinePoint {
+    pub position: [f32; 3],
+    pub width: f32,
+    pub color: [f32; 4],
+}
+
+impl LinePoint {
+    pub fn new(position: Vec3, width: f32, color: egui::Color32) -> Self {
+        Self {
+            position: position.to_array(),
+            width,
+            color: color.to_srgba_unmultiplied().map(|c| c as f32 / 255.0),
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct MeshVertex {
+    pub position: [f32; 3],
+    pub normal: [f32; 3],
+    pub uv: [f32; 2],
+    pub color: [f32; 4],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct MeshInstance {
+    pub model: [[f32; 4]; 4],
+    pub tint: [f32; 4],
+}
+
+impl MeshInstance {
+    pub fn new(model: Mat4, tint: [f32; 4]) -> Self {
+        Self {
+            model: model.to_cols_array_2d(),
+            tint,
+        }
+    }
+}
+
+pub struct MeshData {
+    pub vertices: Vec<MeshVertex>,
+    pub indices: Vec<u32>,
+}
+
+struct GpuMesh {
+    vertex_buffer: wgpu::Buffer,
+    index_buffer: wgpu::Buffer,
+    index_count: u32,
+}
+
+struct GpuLines {
+    buffer: wgpu::Buffer,
+    count: u32,
+    version: u64,
+}
+
+struct ScenePaintJob {
+    camera: wgpu::BindGroup,
+    lines: Option<(wgpu::Buffer, u32)>,
+    meshes: Vec<(String, wgpu::Buffer, u32)>,
+    translucent_meshes: Vec<(String, wgpu::Buffer, u32)>,
+}
+
+/// Depth tested renderer for the 3D scene. Static geometry (meshes, trails) is uploaded
+/// once and referenced by key from [`SceneCallback`], everything else is streamed per frame.
+pub struct SceneRenderer {
+    line_list_pipeline: wgpu::RenderPipeline,
+    line_strip_pipeline: wgpu::RenderPipeline,
+    mesh_pipeline: wgpu::RenderPipeline,
+    translucent_mesh_pipeline: wgpu::RenderPipeline,
+
+    camera_layout: wgpu::BindGroupLayout,
+    texture_layout: wgpu::BindGroupLayout,
+    sampler: wgpu::Sampler,
+    white_texture: wgpu::BindGroup,
+
+    meshes: HashMap<String, GpuMesh>,
+    textures: HashMap<String, wgpu::BindGroup>,
+    lines: HashMap<String, GpuLines>,
+
+    paint_jobs: Mutex<VecDeque<ScenePaintJob>>,
+}
+
+impl SceneRenderer {
+    /// Frees uploaded meshes and lines right away instead of when the device is dropped.
+    pub fn release(&mut self) {
+        for mesh in self.meshes.values() {
+            mesh.vertex_buffer.destroy();
+            mesh.index_buffer.destroy();
+        }
+        for lines in self.lines.values() {
+            lines.buffer.destroy();
+        }
+        self.meshes.clear();
+        self.lines.clear();
+        self.textures.clear();
+        self.paint_jobs.lock().unwrap().clear();
+    }
+
+    pub fn new(device: &wgpu::Device, queue: &wgpu::Queue, format: wgpu::TextureFormat) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Scene Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("../scene.wgsl").into()),
+        });
+
+        let camera_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Scene Camera Layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+
+        let texture_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Scene Texture Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+
+        let line_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Scene Line Pipeline Layout"),
+            bind_group_layouts: &[&camera_layout],
+            push_constant_ranges: &[],
+        });
+
+        let mesh_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Scene Mesh Pipeline Layout"),
+            bind_group_layouts: &[&camera_layout, &texture_layout],
+            push_constant_ranges: &[],
+        });
+
+        let color_target = [Some(wgpu::ColorTargetState {
+            format,
+            blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+            write_mask: wgpu::ColorWrites::ALL,
+        })];
+
+        let line_pipeline = |label: &str, stride: u64| {
+            let start_attributes =
+                wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32, 2 => Float32x4];
+            let end_attributes = wgpu::vertex_attr_array![3 => Float32x3];
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some(label),
+                layout: Some(&line_layout),
+                vertex: wgpu::VertexState {
+                    module: &shader,
+                    entry_point: "vs_line",
+                    buffers: &[
+                        wgpu::VertexBufferLayout {
+                            array_stride: stride,
+                            step_mode: wgpu::VertexStepMode::Instance,
+                            attributes: &start_attributes,
+                        },
+                        wgpu::VertexBufferLayout {
+                            array_stride: stride,
+                            step_mode: wgpu::VertexStepMode::Instance,
+                            attributes: &end_attributes,
+                        },
+                    ],
+                    compilation_options: Default::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader,
+                    entry_point: "fs_line",
+                    targets: &color_target,
+                    compilation_options: Default::default(),
+                }),
+                primitive: wgpu::PrimitiveState::default(),
+                depth_stencil: Some(depth_state(true, wgpu::CompareFunction::LessEqual)),
+                multisample: wgpu::MultisampleState::default(),
+                multiview: None,
+                cache: None,
+            })
+        };
+
+        let point_size = std::mem::size_of::<LinePoint>() as u64;
+        let line_list_pipeline = line_pipeline("Scene Line List Pipeline", 2 * point_size);
+        let line_strip_pipeline = line_pipeline("Scene Line Strip Pipeline", point_size);
+
+        let vertex_attributes = wgpu::vertex_attr_array![
+            0 => Float32x3, 1 => Float32x3, 2 => Float32x2, 3 => Float32x4
+        ];
+        let instance_attributes = wgpu::vertex_attr_array![
+            4 => Float32x4, 5 => Float32x4, 6 => Float32x4, 7 => Float32x4, 8 => Float32x4
+        ];
+        let mesh_pipeline = |label: &str, depth_write: bool| {
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some(label),
+                layout: Some(&mesh_layout),
+                vertex: wgpu::VertexState {
+                    module: &shader,
+                    entry_point: "vs_mesh",
+                    buffers: &[
+                        wgpu::VertexBufferLayout {
+                            array_stride: std::mem::size_of::<MeshVertex>() as u64,
+                            step_mode: wgpu::VertexStepMode::Vertex,
+                            attributes: &vertex_attributes,
+                        },
+                        wgpu::VertexBufferLayout {
+                            array_stride: std::mem::size_of::<MeshInstance>() as u64,
+                            step_mode: wgpu::VertexStepMode::Instance,
+                            attributes: &instance_attributes,
+                        },
+                    ],
+                    compilation_options: Default::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader,
+                    entry_point: "fs_mesh",
+                    targets: &color_target,
+                    compilation_options: Default::default(),
+                }),
+                primitive: wgpu::PrimitiveState::default(),
+                depth_stencil: Some(depth_state(depth_write, wgpu::CompareFunction::Less)),
+                multisample: wgpu::MultisampleState::default(),
+                multiview: None,
+                cache: None,
+            })
+        };
+
+        // Translucent meshes are drawn last and don't occlude each other.
+        let translucent_mesh_pipeline = mesh_pipeline("Scene Translucent Mesh Pipeline", false);
+        let mesh_pipeline = mesh_pipeline("Scene Mesh Pipeline", true);
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Scene Sampler"),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        let white_texture =
+            create_texture(device, queue, &texture_layout, &sampler, 1, 1, &[255; 4]);
+
+        Self {
+            line_list_pipeline,
+            line_strip_pipeline,
+            mesh_pipeline,
+            translucent_mesh_pipeline,
+            camera_layout,
+            texture_layout,
+            sampler,
+            white_texture,
+            meshes: HashMap::new(),
+            textures: HashMap::new(),
+            lines: HashMap::new(),
+            paint_jobs: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    pub fn upload_mesh(&mut self, device: &wgpu::Device, key: &str, mesh: &MeshData) {
+        if mesh.vertices.is_empty() || mesh.indices.is_empty() {
+            self.meshes.remove(key);
+            return;
+        }
+
+        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some(&format!("Scene Mesh Vertices: {}", key)),
+            contents: bytemuck::cast_slice(&mesh.vertices),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+        let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some(&format!("Scene Mesh Indices: {}", key)),
+            contents: bytemuck::cast_slice(&mesh.indices),
+            usage: wgpu::BufferUsages::INDEX,
+        });
+
+        self.meshes.insert(
+            key.to_string(),
+            GpuMesh {
+                vertex_buffer,
+                index_buffer,
+                index_count: mesh.indices.len() as u32,
+            },
+        );
+    }
+
+    /// Sets the RGBA8 texture sampled by the mesh stored under `key`. Meshes without a
+    /// texture are drawn with their vertex colors only.
+    pub fn upload_texture(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        key: &str,
+        texture: Option<&image::RgbaImage>,
+    ) {
+        match texture {
+            Some(image) => {
+                let bind_group = create_texture(
+                    device,
+                    queue,
+                    &self.texture_layout,
+                    &self.sampler,
+                    image.width(),
+                    image.height(),
+                    image,
+                );
+                self.textures.insert(key.to_string(), bind_group);
+            }
+            None => {
+                self.textures.remove(key);
+            }
+        }
+    }
+
+    pub fn has_mesh(&self, key: &str) -> bool {
+        self.meshes.contains_key(key)
+    }
+
+    /// Uploads a line strip. `version` identifies the data so callers can skip re-uploading
+    /// unchanged strips, see [`SceneRenderer::has_lines`].
+    pub fn upload_lines(
+        &mut self,
+        device: &wgpu::Device,
+        key: &str,
+        points: &[LinePoint],
+        version: u64,
+    ) {
+        if points.is_empty() {
+            self.lines.remove(key);
+            return;
+        }
+
+        let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some(&format!("Scene Lines: {}", key)),
+            contents: bytemuck::cast_slice(points),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+
+        self.lines.insert(
+            key.to_string(),
+            GpuLines {
+                buffer,
+                count: points.len() as u32,
+                version,
+            },
+        );
+    }
+
+    pub fn has_lines(&self, key: &str, version: u64) -> bool {
+        self.lines.get(key).is_some_and(|l| l.version == version)
+    }
+
+    fn draw_meshes(
+        &self,
+        render_pass: &mut wgpu::RenderPass<'static>,
+        meshes: &[(String, wgpu::Buffer, u32)],
+    ) {
+        for (key, instances, count) in meshes {
+            let Some(mesh) = self.meshes.get(key) else {
+                continue;
+            };
+            let texture = self.textures.get(key).unwrap_or(&self.white_texture);
+            render_pass.set_bind_group(1, texture, &[]);
+            render_pass.set_vertex_buffer(0, mesh.vertex_buffer.slice(..));
+            render_pass.set_vertex_buffer(1, instances.slice(..));
+            render_pass.set_index_buffer(mesh.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+            render_pass.draw_indexed(0..mesh.index_count, 0, 0..*count);
+        }
+    }
+}
+
+fn create_texture(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    layout: &wgpu::BindGroupLayout,
+    sampler: &wgpu::Sampler,
+    width: u32,
+    height: u32,
+    rgba: &[u8],
+) -> wgpu::BindGroup {
+    let texture = device.create_texture_with_data(
+        queue,
+        &wgpu::TextureDescriptor {
+            label: Some("Scene Texture"),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        },
+        wgpu::util::TextureDataOrder::LayerMajor,
+        rgba,
+    );
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+    device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("Scene Texture Bind Group"),
+        layout,
+        entries: &[
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::TextureView(&view),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: wgpu::BindingResource::Sampler(sampler),
+            },
+        ],
+    })
+}
+
+fn depth_state(write: bool, compare: wgpu::CompareFunction) -> wgpu::DepthStencilState {
+    wgpu::DepthStencilState {
+        format: DEPTH_FORMAT,
+        depth_write_enabled: write,
+        depth_compare: compare,
+        stencil: wgpu::StencilState::default(),
+        bias: wgpu::DepthBiasState::default(),
+    }
+}
+
+/// Depth state for pipelines that draw into the egui pass without using the depth buffer.
+pub fn no_depth_state() -> wgpu::DepthStencilState {
+    depth_state(false, wgpu::CompareFunction::Always)
+}
+
+pub struct SceneCallback {
+    pub view_proj: Mat4,
+    /// Size of the scene rect in points, used to give lines a constant screen width.
+    pub viewport: [f32; 2],
+    pub near: f32,
+    pub light_dir: Vec3,
+    /// Per-frame segments, stored as start/end pairs.
+    pub lines: Vec<LinePoint>,
+    /// Uploaded line strips and how many of their leading points to draw.
+    pub strips: Vec<(String, u32)>,
+    /// Uploaded meshes and the instances to draw them with.
+    pub meshes: Vec<(String, Vec<MeshInstance>)>,
+    /// Like `meshes`, but drawn after everything else without writing depth.
+    pub translucent_meshes: Vec<(String, Vec<MeshInstance>)>,
+}
+
+impl CallbackTrait for SceneCallback {
+    fn prepare(
+        &self,
+        device: &wgpu::Device,
+        _queue: &wgpu::Queue,
+        _screen: &eframe::egui_wgpu::ScreenDescriptor,
+        _encoder: &mut wgpu::CommandEncoder,
+        resources: &mut CallbackResources,
+    ) -> Vec<wgpu::CommandBuffer> {
+        let Some(renderer) = resources.get::<SceneRenderer>() else {
+            return Vec::new();
+        };
+
+        let uniforms_data: Vec<f32> = self
+            .view_proj
+            .to_cols_array()
+            .into_iter()
+            .chain([self.viewport[0], self.viewport[1], self.near, 0.0])
+            .chain([self.light_dir.x, self.light_dir.y, self.light_dir.z, 0.0])
+            .collect();
+
+        let uniform_buf = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Scene Camera Buffer"),
+            contents: bytemuck::cast_slice(&uniforms_data),
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+
+        let camera = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Scene Camera Bind Group"),
+            layout: &renderer.camera_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: uniform_buf.as_entire_binding(),
+            }],
+        });
+
+        let lines = (self.lines.len() >= 2).then(|| {
+            let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("Scene Frame Lines"),
+                contents: bytemuck::cast_slice(&self.lines),
+                usage: wgpu::BufferUsages::VERTEX,
+            });
+            (buffer, self.lines.len() as u32 / 2)
+        });
+
+        let instance_buffers = |meshes: &[(String, Vec<MeshInstance>)]| {
+            meshes
+                .iter()
+                .filter(|(key, instances)| !instances.is_empty() && renderer.has_mesh(key))
+                .map(|(key, instances)| {
+                    let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                        label: Some("Scene Mesh Instances"),
+                        contents: bytemuck::cast_slice(instances),
+                        usage: wgpu::BufferUsages::VERTEX,
+                    });
+                    (key.clone(), buffer, instances.len() as u32)
+                })
+                .collect()
+        };
+        let meshes = instance_buffers(&self.meshes);
+        let translucent_meshes = instance_buffers(&self.translucent_meshes);
+
+        renderer
+            .paint_jobs
+            .lock()
+            .unwrap()
+            .push_back(ScenePaintJob {
+                camera,
+                lines,
+                meshes,
+                translucent_meshes,
+            });
+
+        Vec::new()
+    }
+
+    fn paint<'a>(
+        &'a self,
+        _info: egui::PaintCallbackInfo,
+        render_pass: &mut wgpu::RenderPass<'static>,
+        resources: &'a CallbackResources,
+    ) {
+        let Some(renderer) = resources.get::<SceneRenderer>() else {
+            return;
+        };
+        let Some(job) = renderer.paint_jobs.lock().unwrap().pop_front() else {
+            return;
+        };
+
+        render_pass.set_bind_group(0, &job.camera, &[]);
+
+        // Opaque meshes first so lines drawn on top of them are depth tested correctly.
+        render_pass.set_pipeline(&renderer.mesh_pipeline);
+        renderer.draw_meshes(render_pass, &job.meshes);
+
+        render_pass.set_pipeline(&renderer.line_strip_pipeline);
+        let point_size = std::mem::size_of::<LinePoint>() as u64;
+        for (key, points) in &self.strips {
+            let Some(strip) = renderer.lines.get(key) else {
+                continue;
+            };
+            let segments = (*points).min(strip.count).saturating_sub(1);
+            if segments == 0 {
+                continue;
+            }
+            render_pass.set_vertex_buffer(0, strip.buffer.slice(..));
+            render_pass.set_vertex_buffer(1, strip.buffer.slice(point_size..));
+            render_pass.draw(0..6, 0..segments);
+        }
+
+        if let Some((buffer, segments)) = &job.lines {
+            render_pass.set_pipeline(&renderer.line_list_pipeline);
+            render_pass.set_vertex_buffer(0, buffer.slice(..));
+            render_pass.set_vertex_buffer(1, buffer.slice(point_size..));
+            render_pass.draw(0..6, 0..*segments);
+        }
+
+        if !job.translucent_meshes.is_empty() {
+            render_pass.set_pipeline(&renderer.translucent_mesh_pipeline);
+            renderer.draw_meshes(render_pass, &job.translucent_meshes);
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use crate::core::{AliasProfile, DataSettings};
+use crate::ui::i18n::Language;
+use crate::ui::palette::TracePalette;
+use crate::ui::panels::PlaybackMode;
+use crate::ui::time_format::TimeFormat;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Destructive actions that ask for confirmation unless turned off.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Confirmation {
+    ClearData,
+    ClearTraces,
+    OverwriteLayout,
+    ExitUnsaved,
+}
+
+impl Confirmation {
+    pub const ALL: [Confirmation; 4] = [
+        Confirmation::ClearData,
+        Confirmation::ClearTraces,
+        Confirmation::OverwriteLayout,
+        Confirmation::ExitUnsaved,
+    ];
+}
+
+/// User preferences kept across sessions in `settings.json` in the config directory.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    #[serde(flatten)]
+    pub data: DataSettings,
+    /// Where layouts are saved and listed, `None` for the config directory.
+    pub layouts_dir: Option<PathBuf>,
+    pub trace_palette: TracePalette,
+    pub playback_speed: f32,
+    pub playback_mode: PlaybackMode,
+    pub lock_to_last: bool,
+    pub time_format: TimeFormat,
+    pub always_show_playback_tooltip: bool,
+    /// Draw the hover crosshair in every plot, not only the hovered one.
+    pub shared_crosshair: bool,
+    pub language: Language,
+    /// Folder watched for new logs, `None` to not watch.
+    pub watch_dir: Option<PathBuf>,
+    /// Open new logs in the watched folder right away instead of asking, for unattended
+    /// displays at the flight line.
+    pub kiosk_mode: bool,
+    /// Layout applied to logs opened in kiosk mode, `None` to keep the current one.
+    pub default_layout: Option<PathBuf>,
+    /// Confirmations the user chose not to be asked again.
+    pub skip_confirmations: Vec<Confirmation>,
+    /// Mappings between the topic names of different autopilots.
+    pub alias_profiles: Vec<AliasProfile>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            data: DataSettings::default(),
+            layouts_dir: None,
+            trace_palette: TracePalette::default(),
+            playback_speed: 10.0,
+            playback_mode: PlaybackMode::default(),
+            lock_to_last: true,
+            time_format: TimeFormat::default(),
+            always_show_playback_tooltip: false,
+            shared_crosshair: false,
+            language: Language::default(),
+            watch_dir: None,
+            kiosk_mode: false,
+            default_layout: None,
+            skip_confirmations: Vec::new(),
+            alias_profiles: AliasProfile::builtin(),
+        }
+    }
+}
+
+impl Settings {
+    pub fn load() -> Self {
+        let Some(path) = settings_path() else {
+            return Self::default();
+        };
+        let Ok(json) = std::fs::read_to_string(&path) else {
+            return Self::default();
+        };
+
+        match serde_json::from_str(&json) {
+            Ok(settings) => settings,
+            Err(e) => {
+                tracing::error!("Failed to parse settings: {}", e);
+                Self::default()
+            }
+        }
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let path = settings_path().ok_or("No config directory available")?;
+        path.parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .map_err(|e| e.to_string())?;
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        std::fs::write(&path, json).map_err(|e| e.to_string())
+    }
+
+    pub fn confirms(&self, kind: Confirmation) -> bool {
+        !self.skip_confirmations.contains(&kind)
+    }
+
+    pub fn set_confirms(&mut self, kind: Confirmation, confirm: bool) {
+        self.skip_confirmations.retain(|&k| k != kind);
+        if !confirm {
+            self.skip_confirmations.push(kind);
+        }
+    }
+
+    pub fn layouts_dir(&self) -> PathBuf {
+        self.layouts_dir.clone().unwrap_or_else(default_layouts_dir)
+    }
+}
+
+pub fn default_layouts_dir() -> PathBuf {
+    match config_dir() {
+        Some(dir) => dir.join("layouts"),
+        None => PathBuf::from("layouts"),
+    }
+}
+
+fn config_dir() -> Option<PathBuf> {
+    directories::ProjectDirs::from("io", "tilak", "TiPlot").map(|dirs| dirs.config_dir().into())
+}
+
+fn settings_path() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("settings.json"))
+}
@@ -0,0 +1,215 @@
+use crate::core::DataStore;
+use std::io::Write;
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+const SAMPLE_RATE: u32 = 22050;
+/// Samples written ahead of the wall clock, bounding the audio latency.
+const LEAD: f32 = 0.08;
+/// Lowest tone in pitch mode; the value range spans three octaves above it.
+const BASE_FREQUENCY: f32 = 220.0;
+const OCTAVES: f32 = 3.0;
+const VOLUME_FREQUENCY: f32 = 440.0;
+const MAX_AMPLITUDE: f32 = 0.3;
+
+/// What the value of the sonified trace controls.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SonifyMode {
+    Pitch,
+    Volume,
+}
+
+impl SonifyMode {
+    pub const ALL: [SonifyMode; 2] = [SonifyMode::Pitch, SonifyMode::Volume];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SonifyMode::Pitch => "Pitch",
+            SonifyMode::Volume => "Volume",
+        }
+    }
+}
+
+/// Trace played as a tone during playback.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sonification {
+    pub topic: String,
+    pub col: String,
+    pub mode: SonifyMode,
+}
+
+/// Tone parameters shared with the generator thread, stored as `f32` bits.
+#[derive(Default)]
+struct ToneParams {
+    frequency: AtomicU32,
+    amplitude: AtomicU32,
+    stop: AtomicBool,
+}
+
+struct Player {
+    child: Child,
+    params: Arc<ToneParams>,
+}
+
+impl Drop for Player {
+    fn drop(&mut self) {
+        self.params.stop.store(true, Ordering::Relaxed);
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Plays the selected trace through `ffplay` while the timeline is playing.
+#[derive(Default)]
+pub struct Sonifier {
+    target: Option<Sonification>,
+    /// Value range the tone is scaled to, widened as new values arrive.
+    range: Option<(f32, f32)>,
+    player: Option<Player>,
+    pub error: Option<String>,
+}
+
+impl Sonifier {
+    pub fn target(&self) -> Option<&Sonification> {
+        self.target.as_ref()
+    }
+
+    pub fn set_target(&mut self, target: Option<Sonification>) {
+        if self.target.as_ref().map(|t| (&t.topic, &t.col))
+            != target.as_ref().map(|t| (&t.topic, &t.col))
+        {
+            self.range = None;
+        }
+        if target.is_none() {
+            self.player = None;
+        }
+        self.target = target;
+        self.error = None;
+    }
+
+    /// Updates the tone for the value at `time`; silent while paused.
+    pub fn update(&mut self, data_store: &DataStore, time: f32, playing: bool) {
+        let Some(target) = &self.target else {
+            return;
+        };
+        if !playing {
+            if let Some(player) = &self.player {
+                player
+                    .params
+                    .amplitude
+                    .store(0f32.to_bits(), Ordering::Relaxed);
+            }
+            return;
+        }
+        if self.player.is_none() && self.error.is_none() {
+            match spawn_player() {
+                Ok(player) => self.player = Some(player),
+                Err(e) => {
+                    tracing::error!("Failed to start audio output: {}", e);
+                    self.error = Some(e);
+                }
+            }
+        }
+        let Some(player) = &self.player else {
+            return;
+        };
+
+        let value = value_at(data_store, &target.topic, &target.col, time);
+        let (frequency, amplitude) = match value {
+            Some(value) => {
+                let (min, max) = self.range.map_or((value, value), |(min, max)| {
+                    (min.min(value), max.max(value))
+                });
+                self.range = Some((min, max));
+                let norm = if max > min {
+                    (value - min) / (max - min)
+                } else {
+                    0.5
+                };
+                match target.mode {
+                    SonifyMode::Pitch => (BASE_FREQUENCY * (norm * OCTAVES).exp2(), MAX_AMPLITUDE),
+                    SonifyMode::Volume => (VOLUME_FREQUENCY, norm * MAX_AMPLITUDE),
+                }
+            }
+            None => (BASE_FREQUENCY, 0.0),
+        };
+        player
+            .params
+            .frequency
+            .store(frequency.to_bits(), Ordering::Relaxed);
+        player
+            .params
+            .amplitude
+            .store(amplitude.to_bits(), Ordering::Relaxed);
+    }
+}
+
+/// Linearly interpolated value of a column, `None` outside the logged time span.
+fn value_at(data_store: &DataStore, topic: &str, col: &str, time: f32) -> Option<f32> {
+    let times = data_store.get_column(topic, "timestamp")?;
+    let values = data_store.get_column(topic, col)?;
+    let after = times.partition_point(|&t| t < time);
+    if after == 0 || after >= times.len().min(values.len()) {
+        return None;
+    }
+    let (t0, t1) = (times[after - 1], times[after]);
+    let (v0, v1) = (values[after - 1], values[after]);
+    let alpha = if t1 > t0 {
+        (time - t0) / (t1 - t0)
+    } else {
+        0.0
+    };
+    Some(v0 + (v1 - v0) * alpha).filter(|v| v.is_finite())
+}
+
+fn spawn_player() -> Result<Player, String> {
+    let mut child = Command::new("ffplay")
+        .args(["-nodisp", "-loglevel", "quiet", "-fflags", "nobuffer"])
+        .args(["-f", "s16le", "-ar", &SAMPLE_RATE.to_string(), "-i", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("ffplay not available: {}", e))?;
+    let stdin = child.stdin.take().ok_or("no audio pipe")?;
+    let params = Arc::new(ToneParams::default());
+    let thread_params = params.clone();
+    std::thread::spawn(move || generate(stdin, &thread_params));
+    Ok(Player { child, params })
+}
+
+/// Writes a sine tone following `params`, paced by the wall clock so the pipe never
+/// buffers more than `LEAD` seconds.
+fn generate(mut stdin: ChildStdin, params: &ToneParams) {
+    const CHUNK: usize = 256;
+    let start = Instant::now();
+    let mut written = 0u64;
+    let mut phase = 0.0f32;
+    let mut amplitude = 0.0f32;
+    let mut buffer = Vec::with_capacity(CHUNK * 2);
+
+    while !params.stop.load(Ordering::Relaxed) {
+        let due = ((start.elapsed().as_secs_f32() + LEAD) * SAMPLE_RATE as f32) as u64;
+        if written >= due {
+            std::thread::sleep(Duration::from_millis(5));
+            continue;
+        }
+
+        let frequency = f32::from_bits(params.frequency.load(Ordering::Relaxed));
+        let wanted = f32::from_bits(params.amplitude.load(Ordering::Relaxed));
+        buffer.clear();
+        for _ in 0..CHUNK {
+            // Ramp the amplitude to avoid clicks when the value jumps.
+            amplitude += (wanted - amplitude) * 0.01;
+            phase = (phase + frequency / SAMPLE_RATE as f32).fract();
+            let sample = (phase * std::f32::consts::TAU).sin() * amplitude;
+            buffer.extend_from_slice(&((sample * i16::MAX as f32) as i16).to_le_bytes());
+        }
+        if stdin.write_all(&buffer).is_err() {
+            return;
+        }
+        written += CHUNK as u64;
+    }
+}
@@ -0,0 +1,343 @@
+use super::pid_tile::polyline;
+use super::{padded_bounds, InterpolationMode};
+use crate::core::DataStore;
+use crate::ui::palette::TracePalette;
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+
+/// Array columns recognised as actuator outputs, e.g. `output[3]`.
+const OUTPUT_ARRAYS: [&str; 2] = ["output", "control"];
+/// ArduPilot servo output topic, whose channels are named `C1`, `C2`, …
+const CHANNEL_TOPIC: &str = "rcou";
+/// Share of the output range at either end that counts as saturated.
+const SATURATION_MARGIN: f32 = 0.02;
+
+const HIGH_COLOR: egui::Color32 = egui::Color32::from_rgb(230, 90, 90);
+const LOW_COLOR: egui::Color32 = egui::Color32::from_rgb(90, 140, 230);
+
+/// Configuration of a plot tile shown as the outputs of an actuator topic.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ActuatorView {
+    pub topic: String,
+    pub columns: Vec<String>,
+    /// Output range; values at either end of it count as saturated.
+    pub min: f32,
+    pub max: f32,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Saturation {
+    None,
+    Low,
+    High,
+}
+
+impl ActuatorView {
+    /// Finds actuator output columns in a topic, dropping outputs that are never used.
+    pub fn detect(data_store: &DataStore, topic: &str) -> Option<Self> {
+        let mut outputs: Vec<(usize, &String)> = data_store
+            .get_columns(topic)
+            .into_iter()
+            .filter_map(|col| output_index(topic, col).map(|index| (index, col)))
+            .collect();
+        outputs.sort_by_key(|&(index, _)| index);
+
+        // PX4 logs how many of the array entries are in use.
+        let used = data_store
+            .get_column(topic, "noutputs")
+            .and_then(|n| n.iter().copied().filter(|n| n.is_finite()).reduce(f32::max));
+        if let Some(used) = used.filter(|&n| n >= 1.0) {
+            outputs.retain(|&(index, _)| index < used as usize);
+        }
+        outputs.retain(|(_, col)| {
+            data_store
+                .get_column(topic, col)
+                .is_some_and(|values| values.iter().any(|v| v.is_finite()))
+        });
+        if outputs.len() < 2 {
+            return None;
+        }
+
+        let columns: Vec<String> = outputs.into_iter().map(|(_, col)| col.clone()).collect();
+        let (min, max) = default_range(data_store, topic, &columns);
+        Some(Self {
+            topic: topic.to_string(),
+            columns,
+            min,
+            max,
+        })
+    }
+
+    fn saturation(&self, value: f32) -> Saturation {
+        let margin = SATURATION_MARGIN * (self.max - self.min);
+        if value >= self.max - margin {
+            Saturation::High
+        } else if value <= self.min + margin {
+            Saturation::Low
+        } else {
+            Saturation::None
+        }
+    }
+}
+
+fn output_index(topic: &str, col: &str) -> Option<usize> {
+    for array in OUTPUT_ARRAYS {
+        let index = col
+            .strip_prefix(array)
+            .and_then(|rest| rest.strip_prefix('['))
+            .and_then(|rest| rest.strip_suffix(']'));
+        if let Some(index) = index {
+            return index.parse().ok();
+        }
+    }
+    if topic == CHANNEL_TOPIC {
+        return col.strip_prefix('C')?.parse().ok();
+    }
+    None
+}
+
+/// PWM range for outputs in microseconds, otherwise a normalized range.
+fn default_range(data_store: &DataStore, topic: &str, columns: &[String]) -> (f32, f32) {
+    let values = columns
+        .iter()
+        .filter_map(|col| data_store.get_column(topic, col))
+        .flatten()
+        .copied()
+        .filter(|v| v.is_finite());
+    let (lo, hi) = values.fold((0.0f32, 0.0f32), |(lo, hi), v| (lo.min(v), hi.max(v)));
+    if hi > 100.0 {
+        (1000.0, 2000.0)
+    } else if lo < -0.05 {
+        (-1.0, 1.0)
+    } else {
+        (0.0, 1.0)
+    }
+}
+
+/// Draws a bar chart of the outputs at the playback cursor above a time plot of all
+/// outputs, shaded where any of them is saturated. Returns the time that was clicked in
+/// the time plot, if any.
+#[allow(clippy::too_many_arguments)]
+pub fn render_actuators(
+    ui: &mut egui::Ui,
+    rect: egui::Rect,
+    response: &egui::Response,
+    view: &ActuatorView,
+    data_store: &DataStore,
+    palette: TracePalette,
+    min_time: f32,
+    max_time: f32,
+    current_time: f32,
+) -> Option<f32> {
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 0.0, egui::Color32::from_rgb(20, 20, 20));
+
+    let times = data_store.get_column(&view.topic, "timestamp");
+    let outputs: Vec<(&String, &Vec<f32>)> = view
+        .columns
+        .iter()
+        .filter_map(|col| Some((col, data_store.get_column(&view.topic, col)?)))
+        .collect();
+    let (Some(times), false) = (times, outputs.is_empty()) else {
+        painter.text(
+            rect.center(),
+            egui::Align2::CENTER_CENTER,
+            format!("No actuator outputs in {}", view.topic),
+            egui::FontId::proportional(14.0),
+            egui::Color32::GRAY,
+        );
+        return None;
+    };
+    let time_span = max_time - min_time;
+    if time_span <= 0.0 || view.max <= view.min {
+        return None;
+    }
+
+    let colors = palette.colors();
+    let color = |i: usize| {
+        let c = colors[i % colors.len()];
+        egui::Color32::from_rgb(
+            (c[0] * 255.0) as u8,
+            (c[1] * 255.0) as u8,
+            (c[2] * 255.0) as u8,
+        )
+    };
+    let small = egui::FontId::proportional(10.0);
+
+    let split = rect.top() + rect.height() * 0.4;
+    let bar_rect = egui::Rect::from_x_y_ranges(rect.x_range(), rect.top()..=split)
+        .shrink2(egui::vec2(8.0, 16.0));
+    let plot_rect = egui::Rect::from_x_y_ranges(rect.x_range(), split..=rect.bottom());
+    painter.line_segment(
+        [plot_rect.left_top(), plot_rect.right_top()],
+        egui::Stroke::new(1.0, egui::Color32::from_gray(60)),
+    );
+
+    // Bar chart at the playback cursor.
+    let bar_y = |v: f32| {
+        let f = ((v - view.min) / (view.max - view.min)).clamp(0.0, 1.0);
+        bar_rect.bottom() - f * bar_rect.height()
+    };
+    let baseline = bar_y(if view.min < 0.0 { 0.0 } else { view.min });
+    for v in [view.min, view.max] {
+        painter.extend(egui::Shape::dashed_line(
+            &[
+                egui::pos2(bar_rect.left(), bar_y(v)),
+                egui::pos2(bar_rect.right(), bar_y(v)),
+            ],
+            egui::Stroke::new(1.0, egui::Color32::from_gray(80)),
+            4.0,
+            4.0,
+        ));
+    }
+    let slot = bar_rect.width() / outputs.len() as f32;
+    for (i, (col, values)) in outputs.iter().enumerate() {
+        let center = bar_rect.left() + (i as f32 + 0.5) * slot;
+        let value = InterpolationMode::PreviousPoint.sample(times, values, current_time);
+        if let Some(value) = value.filter(|v| v.is_finite()) {
+            let fill = match view.saturation(value) {
+                Saturation::High => HIGH_COLOR,
+                Saturation::Low => LOW_COLOR,
+                Saturation::None => color(i),
+            };
+            let y = bar_y(value);
+            painter.rect_filled(
+                egui::Rect::from_x_y_ranges(
+                    center - slot * 0.35..=center + slot * 0.35,
+                    y.min(baseline)..=y.max(baseline),
+                ),
+                1.0,
+                fill,
+            );
+            painter.text(
+                egui::pos2(center, y.min(baseline) - 1.0),
+                egui::Align2::CENTER_BOTTOM,
+                format!("{:.2}", value),
+                small.clone(),
+                egui::Color32::from_gray(200),
+            );
+        }
+        painter.text(
+            egui::pos2(center, bar_rect.bottom() + 2.0),
+            egui::Align2::CENTER_TOP,
+            col.as_str(),
+            small.clone(),
+            egui::Color32::GRAY,
+        );
+    }
+
+    // Time plot, shaded where any output is saturated.
+    let len = outputs
+        .iter()
+        .map(|(_, values)| values.len())
+        .fold(times.len(), usize::min);
+    let start = times[..len].partition_point(|&t| t < min_time);
+    let end = times[..len].partition_point(|&t| t <= max_time);
+    let x_of = |t: f32| plot_rect.left() + (t - min_time) / time_span * plot_rect.width();
+
+    let min_gap = time_span / plot_rect.width().max(1.0);
+    let mut runs: Vec<(f32, f32, Saturation)> = Vec::new();
+    for i in start..end {
+        let states = outputs.iter().map(|(_, values)| view.saturation(values[i]));
+        let state = states.fold(Saturation::None, |a, b| match (a, b) {
+            (Saturation::High, _) | (_, Saturation::High) => Saturation::High,
+            (Saturation::Low, _) | (_, Saturation::Low) => Saturation::Low,
+            _ => Saturation::None,
+        });
+        if state == Saturation::None {
+            continue;
+        }
+        let until = times.get(i + 1).copied().unwrap_or(times[i]).min(max_time);
+        match runs.last_mut() {
+            Some(run) if run.2 == state && times[i] - run.1 <= min_gap => run.1 = until,
+            _ => runs.push((times[i], until, state)),
+        }
+    }
+    for (from, until, state) in runs {
+        let color = match state {
+            Saturation::High => HIGH_COLOR,
+            _ => LOW_COLOR,
+        };
+        painter.rect_filled(
+            egui::Rect::from_x_y_ranges(
+                x_of(from)..=x_of(until).max(x_of(from) + 1.0),
+                plot_rect.y_range(),
+            ),
+            0.0,
+            color.gamma_multiply(0.2),
+        );
+    }
+
+    let data_range = outputs
+        .iter()
+        .flat_map(|(_, values)| &values[start..end])
+        .copied()
+        .filter(|v| v.is_finite())
+        .fold((view.min, view.max), |(lo, hi), v| (lo.min(v), hi.max(v)));
+    let (lo, hi) = padded_bounds(Some(data_range));
+    let to_screen = |t: f32, v: f32| {
+        let y = plot_rect.bottom() - (v - lo) / (hi - lo) * plot_rect.height();
+        egui::pos2(x_of(t), y)
+    };
+    for v in [view.min, view.max] {
+        let y = to_screen(min_time, v).y;
+        painter.extend(egui::Shape::dashed_line(
+            &[
+                egui::pos2(plot_rect.left(), y),
+                egui::pos2(plot_rect.right(), y),
+            ],
+            egui::Stroke::new(1.0, egui::Color32::from_gray(90)),
+            6.0,
+            4.0,
+        ));
+        painter.text(
+            egui::pos2(plot_rect.left() + 4.0, y - 1.0),
+            egui::Align2::LEFT_BOTTOM,
+            format!("{}", v),
+            small.clone(),
+            egui::Color32::GRAY,
+        );
+    }
+    for (i, (_, values)) in outputs.iter().enumerate() {
+        painter.add(egui::Shape::line(
+            polyline(
+                times,
+                |j| values[j],
+                start..end,
+                to_screen,
+                plot_rect.width(),
+            ),
+            egui::Stroke::new(1.0, color(i)),
+        ));
+    }
+
+    if current_time >= min_time && current_time <= max_time {
+        let x = x_of(current_time);
+        painter.line_segment(
+            [
+                egui::pos2(x, plot_rect.top()),
+                egui::pos2(x, plot_rect.bottom()),
+            ],
+            egui::Stroke::new(1.5, egui::Color32::from_rgb(255, 200, 0)),
+        );
+    }
+
+    if let Some(pos) = response.hover_pos().filter(|pos| plot_rect.contains(*pos)) {
+        painter.line_segment(
+            [
+                egui::pos2(pos.x, plot_rect.top()),
+                egui::pos2(pos.x, plot_rect.bottom()),
+            ],
+            egui::Stroke::new(1.0, egui::Color32::from_white_alpha(140)),
+        );
+    }
+
+    if response.clicked() {
+        let pos = response
+            .interact_pointer_pos()
+            .filter(|pos| plot_rect.contains(*pos))?;
+        return Some(min_time + (pos.x - plot_rect.left()) / plot_rect.width() * time_span);
+    }
+
+    None
+}
@@ -0,0 +1,155 @@
+use crate::core::{DataStore, ImageColumn, ImageRef};
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Configuration of a plot tile shown as the camera frames of an image column.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ImageView {
+    pub topic: String,
+    pub col: String,
+    /// Folder that relative image paths are resolved against.
+    #[serde(default)]
+    pub folder: Option<PathBuf>,
+}
+
+impl ImageView {
+    pub fn new(topic: String, col: String) -> Self {
+        Self {
+            topic,
+            col,
+            folder: None,
+        }
+    }
+
+    fn column<'a>(&self, data_store: &'a DataStore) -> Option<&'a ImageColumn> {
+        data_store.images.get(&self.topic)?.get(&self.col)
+    }
+
+    /// Time of the frame `step` frames away from the one shown at `time`.
+    pub fn step(&self, data_store: &DataStore, time: f32, step: isize) -> Option<f32> {
+        let column = self.column(data_store)?;
+        let index = column.nearest(time)?.checked_add_signed(step)?;
+        column.times.get(index).copied()
+    }
+}
+
+/// Decoded frame of each image tile, keyed by topic and column.
+#[derive(Default)]
+pub struct ImageFrameCache {
+    frames: HashMap<(String, String), (usize, Result<egui::TextureHandle, String>)>,
+}
+
+impl ImageFrameCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Drops decoded frames, e.g. after the image folder changed.
+    pub fn clear(&mut self) {
+        self.frames.clear();
+    }
+
+    fn get(
+        &mut self,
+        ctx: &egui::Context,
+        view: &ImageView,
+        index: usize,
+        frame: &ImageRef,
+    ) -> &Result<egui::TextureHandle, String> {
+        let key = (view.topic.clone(), view.col.clone());
+        let cached = self.frames.get(&key).is_some_and(|(i, _)| *i == index);
+        if !cached {
+            let texture = decode(frame, view.folder.as_ref()).map(|image| {
+                let name = format!("image_{}_{}", view.topic, view.col);
+                ctx.load_texture(name, image, egui::TextureOptions::LINEAR)
+            });
+            if let Err(e) = &texture {
+                tracing::warn!("Failed to load frame of {}/{}: {}", view.topic, view.col, e);
+            }
+            self.frames.insert(key.clone(), (index, texture));
+        }
+        &self.frames[&key].1
+    }
+}
+
+fn decode(frame: &ImageRef, folder: Option<&PathBuf>) -> Result<egui::ColorImage, String> {
+    let image = match frame {
+        ImageRef::Encoded(bytes) => image::load_from_memory(bytes),
+        ImageRef::Path(path) => {
+            let path = PathBuf::from(path);
+            let path = match folder {
+                Some(folder) if path.is_relative() => folder.join(path),
+                _ => path,
+            };
+            image::open(&path)
+        }
+    }
+    .map_err(|e| e.to_string())?
+    .to_rgba8();
+    let size = [image.width() as usize, image.height() as usize];
+    Ok(egui::ColorImage::from_rgba_unmultiplied(
+        size,
+        image.as_raw(),
+    ))
+}
+
+/// Draws the frame closest to the playback cursor, letterboxed into `rect`.
+pub fn render_image_sequence(
+    ui: &mut egui::Ui,
+    rect: egui::Rect,
+    view: &ImageView,
+    data_store: &DataStore,
+    cache: &mut ImageFrameCache,
+    current_time: f32,
+) {
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 0.0, egui::Color32::BLACK);
+    let message = |text: String| {
+        painter.text(
+            rect.center(),
+            egui::Align2::CENTER_CENTER,
+            text,
+            egui::FontId::proportional(14.0),
+            egui::Color32::GRAY,
+        );
+    };
+
+    let Some(column) = view.column(data_store) else {
+        message(format!("No images in {}/{}", view.topic, view.col));
+        return;
+    };
+    let Some(index) = column.nearest(current_time) else {
+        return;
+    };
+
+    match cache.get(ui.ctx(), view, index, &column.frames[index]) {
+        Ok(texture) => {
+            let size = texture.size_vec2();
+            let scale = (rect.width() / size.x).min(rect.height() / size.y);
+            painter.image(
+                texture.id(),
+                egui::Rect::from_center_size(rect.center(), size * scale),
+                egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0)),
+                egui::Color32::WHITE,
+            );
+        }
+        Err(e) => message(e.clone()),
+    }
+
+    painter.text(
+        rect.left_bottom() + egui::vec2(6.0, -6.0),
+        egui::Align2::LEFT_BOTTOM,
+        format!(
+            "{}/{}  frame {}/{}  {:+.3} s",
+            view.topic,
+            view.col,
+            index + 1,
+            column.frames.len(),
+            column.times[index] - current_time
+        ),
+        egui::FontId::monospace(11.0),
+        egui::Color32::from_gray(200),
+    );
+}
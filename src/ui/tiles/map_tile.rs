@@ -0,0 +1,614 @@
+use crate::core::DataStore;
+use crossbeam_channel::{unbounded, Receiver, Sender};
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
+use std::f64::consts::PI;
+use std::io::Read;
+use std::path::PathBuf;
+
+const TILE_SIZE: f64 = 256.0;
+const MIN_ZOOM: f32 = 1.0;
+const MAX_ZOOM: f32 = 19.0;
+const MAX_CONCURRENT_DOWNLOADS: usize = 4;
+const MAX_TILE_BYTES: u64 = 4 * 1024 * 1024;
+const MAX_TRAJECTORY_POINTS: usize = 5000;
+/// Parent levels searched for a lower resolution stand-in while a tile loads.
+const MAX_FALLBACK_LEVELS: u8 = 4;
+const USER_AGENT: &str = concat!("TiPlot/", env!("CARGO_PKG_VERSION"));
+const MAX_LATITUDE: f64 = 85.051_128;
+
+/// Latitude/longitude column pairs recognised when offering a map view.
+const GPS_COLUMNS: [(&str, &str); 3] = [
+    ("lat", "lon"),
+    ("latitude_deg", "longitude_deg"),
+    ("latitude", "longitude"),
+];
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MapSource {
+    #[default]
+    OpenStreetMap,
+    Satellite,
+}
+
+impl MapSource {
+    pub const ALL: [MapSource; 2] = [MapSource::OpenStreetMap, MapSource::Satellite];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            MapSource::OpenStreetMap => "OpenStreetMap",
+            MapSource::Satellite => "Satellite",
+        }
+    }
+
+    fn url(&self, z: u8, x: u32, y: u32) -> String {
+        match self {
+            MapSource::OpenStreetMap => {
+                format!("https://tile.openstreetmap.org/{}/{}/{}.png", z, x, y)
+            }
+            MapSource::Satellite => format!(
+                "https://server.arcgisonline.com/ArcGIS/rest/services/World_Imagery/MapServer/tile/{}/{}/{}",
+                z, y, x
+            ),
+        }
+    }
+
+    fn attribution(&self) -> &'static str {
+        match self {
+            MapSource::OpenStreetMap => "© OpenStreetMap contributors",
+            MapSource::Satellite => "Tiles © Esri",
+        }
+    }
+
+    fn cache_name(&self) -> &'static str {
+        match self {
+            MapSource::OpenStreetMap => "osm",
+            MapSource::Satellite => "satellite",
+        }
+    }
+}
+
+/// Configuration of a plot tile shown as a map of a latitude/longitude trajectory.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MapView {
+    pub topic: String,
+    pub lat: String,
+    pub lon: String,
+    #[serde(default)]
+    pub source: MapSource,
+    /// Keep the vehicle centered while the playback cursor moves.
+    #[serde(default)]
+    pub follow: bool,
+    #[serde(default = "default_zoom")]
+    pub zoom: f32,
+    /// View center in normalized Web Mercator coordinates; `None` fits the trajectory.
+    #[serde(default)]
+    pub center: Option<[f64; 2]>,
+}
+
+fn default_zoom() -> f32 {
+    MAX_ZOOM - 3.0
+}
+
+impl MapView {
+    pub fn new(topic: String, lat: String, lon: String) -> Self {
+        Self {
+            topic,
+            lat,
+            lon,
+            source: MapSource::default(),
+            follow: false,
+            zoom: default_zoom(),
+            center: None,
+        }
+    }
+
+    /// Finds latitude/longitude columns in a topic.
+    pub fn detect(data_store: &DataStore, topic: &str) -> Option<Self> {
+        GPS_COLUMNS
+            .iter()
+            .find(|(lat, lon)| {
+                data_store.get_column(topic, lat).is_some()
+                    && data_store.get_column(topic, lon).is_some()
+            })
+            .map(|(lat, lon)| Self::new(topic.to_string(), lat.to_string(), lon.to_string()))
+    }
+
+    /// Screen pixels per normalized world unit.
+    fn scale(&self) -> f64 {
+        TILE_SIZE * 2f64.powf(self.zoom as f64)
+    }
+
+    fn to_screen(&self, rect: egui::Rect, center: [f64; 2], world: [f64; 2]) -> egui::Pos2 {
+        let scale = self.scale();
+        rect.center()
+            + egui::vec2(
+                ((world[0] - center[0]) * scale) as f32,
+                ((world[1] - center[1]) * scale) as f32,
+            )
+    }
+
+    fn to_world(&self, rect: egui::Rect, center: [f64; 2], pos: egui::Pos2) -> [f64; 2] {
+        let scale = self.scale();
+        let offset = pos - rect.center();
+        [
+            center[0] + offset.x as f64 / scale,
+            center[1] + offset.y as f64 / scale,
+        ]
+    }
+
+    /// Centers and zooms the view on `points`.
+    fn fit(&mut self, rect: egui::Rect, points: &[[f64; 2]]) {
+        let Some(first) = points.first() else {
+            return;
+        };
+
+        let (mut min, mut max) = (*first, *first);
+        for p in points {
+            min = [min[0].min(p[0]), min[1].min(p[1])];
+            max = [max[0].max(p[0]), max[1].max(p[1])];
+        }
+
+        let span_x = (max[0] - min[0]).max(1e-9) * TILE_SIZE;
+        let span_y = (max[1] - min[1]).max(1e-9) * TILE_SIZE;
+        let zoom_x = (rect.width() as f64 * 0.8 / span_x).log2();
+        let zoom_y = (rect.height() as f64 * 0.8 / span_y).log2();
+
+        self.zoom = (zoom_x.min(zoom_y) as f32).clamp(MIN_ZOOM, MAX_ZOOM);
+        self.center = Some([(min[0] + max[0]) / 2.0, (min[1] + max[1]) / 2.0]);
+    }
+}
+
+/// Normalized Web Mercator coordinates of a position, both in 0..1.
+fn project(lat: f64, lon: f64) -> [f64; 2] {
+    let lat = lat.clamp(-MAX_LATITUDE, MAX_LATITUDE).to_radians();
+    [
+        (lon + 180.0) / 360.0,
+        (1.0 - (lat.tan() + 1.0 / lat.cos()).ln() / PI) / 2.0,
+    ]
+}
+
+/// Factor converting stored coordinates into degrees. Some logs store them as
+/// integers in units of 1e-7 degrees.
+fn degree_scale(values: &[f32]) -> f64 {
+    match values.iter().find(|v| v.is_finite() && **v != 0.0) {
+        Some(v) if v.abs() > 180.0 => 1e-7,
+        _ => 1.0,
+    }
+}
+
+type TileKey = (MapSource, u8, u32, u32);
+
+enum TileState {
+    Queued,
+    Loading,
+    Ready(egui::TextureHandle),
+    Failed,
+}
+
+/// Map tile textures shared by all map views. Tiles are read from the on-disk cache or
+/// downloaded on background threads; downloads are written back to the cache.
+pub struct MapTileCache {
+    tiles: HashMap<TileKey, TileState>,
+    queue: VecDeque<TileKey>,
+    in_flight: usize,
+    frame: u64,
+    tx: Sender<(TileKey, Result<egui::ColorImage, String>)>,
+    rx: Receiver<(TileKey, Result<egui::ColorImage, String>)>,
+    cache_dir: Option<PathBuf>,
+    offline: bool,
+}
+
+impl MapTileCache {
+    pub fn new() -> Self {
+        let (tx, rx) = unbounded();
+        let cache_dir = directories::ProjectDirs::from("io", "tilak", "TiPlot")
+            .map(|dirs| dirs.cache_dir().join("map_tiles"));
+
+        Self {
+            tiles: HashMap::new(),
+            queue: VecDeque::new(),
+            in_flight: 0,
+            frame: u64::MAX,
+            tx,
+            rx,
+            cache_dir,
+            offline: false,
+        }
+    }
+
+    pub fn is_offline(&self) -> bool {
+        self.offline
+    }
+
+    /// In offline mode only cached tiles are shown. Tiles that failed are retried when
+    /// going back online.
+    pub fn set_offline(&mut self, offline: bool) {
+        self.offline = offline;
+        self.tiles
+            .retain(|_, state| !matches!(state, TileState::Failed));
+    }
+
+    /// Collects finished loads and forgets requests from previous frames, so only tiles
+    /// that are still visible get fetched.
+    fn begin_frame(&mut self, ctx: &egui::Context) {
+        while let Ok((key, result)) = self.rx.try_recv() {
+            self.in_flight = self.in_flight.saturating_sub(1);
+            let state = match result {
+                Ok(image) => {
+                    let name = format!("map_tile_{:?}_{}_{}_{}", key.0, key.1, key.2, key.3);
+                    TileState::Ready(ctx.load_texture(name, image, egui::TextureOptions::LINEAR))
+                }
+                Err(e) => {
+                    if !self.offline {
+                        tracing::error!("Failed to load map tile {:?}: {}", key, e);
+                    }
+                    TileState::Failed
+                }
+            };
+            self.tiles.insert(key, state);
+        }
+
+        if self.frame != ctx.cumulative_pass_nr() {
+            self.frame = ctx.cumulative_pass_nr();
+            self.queue.clear();
+            self.tiles
+                .retain(|_, state| !matches!(state, TileState::Queued));
+        }
+    }
+
+    fn ready(&self, key: TileKey) -> Option<&egui::TextureHandle> {
+        match self.tiles.get(&key) {
+            Some(TileState::Ready(texture)) => Some(texture),
+            _ => None,
+        }
+    }
+
+    fn request(&mut self, key: TileKey) {
+        if let Entry::Vacant(entry) = self.tiles.entry(key) {
+            entry.insert(TileState::Queued);
+            self.queue.push_back(key);
+        }
+    }
+
+    fn start_loads(&mut self, ctx: &egui::Context) {
+        while self.in_flight < MAX_CONCURRENT_DOWNLOADS {
+            let Some(key) = self.queue.pop_front() else {
+                break;
+            };
+
+            self.tiles.insert(key, TileState::Loading);
+            self.in_flight += 1;
+
+            let tx = self.tx.clone();
+            let ctx = ctx.clone();
+            let cache_dir = self.cache_dir.clone();
+            let offline = self.offline;
+            std::thread::spawn(move || {
+                let result = load_tile(key, cache_dir, offline);
+                let _ = tx.send((key, result));
+                ctx.request_repaint();
+            });
+        }
+    }
+}
+
+impl Default for MapTileCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn load_tile(
+    key: TileKey,
+    cache_dir: Option<PathBuf>,
+    offline: bool,
+) -> Result<egui::ColorImage, String> {
+    let (source, z, x, y) = key;
+    let path = cache_dir.map(|dir| {
+        dir.join(source.cache_name())
+            .join(z.to_string())
+            .join(x.to_string())
+            .join(format!("{}.tile", y))
+    });
+
+    let bytes = match path.as_ref().and_then(|p| std::fs::read(p).ok()) {
+        Some(bytes) => bytes,
+        None if offline => return Err("not in the offline cache".to_string()),
+        None => {
+            let bytes = download(&source.url(z, x, y))?;
+            if let Some(path) = &path {
+                let written = path
+                    .parent()
+                    .map_or(Ok(()), std::fs::create_dir_all)
+                    .and_then(|_| std::fs::write(path, &bytes));
+                if let Err(e) = written {
+                    tracing::error!("Failed to cache map tile: {}", e);
+                }
+            }
+            bytes
+        }
+    };
+
+    let image = image::load_from_memory(&bytes)
+        .map_err(|e| e.to_string())?
+        .to_rgba8();
+    let size = [image.width() as usize, image.height() as usize];
+    Ok(egui::ColorImage::from_rgba_unmultiplied(
+        size,
+        image.as_raw(),
+    ))
+}
+
+fn download(url: &str) -> Result<Vec<u8>, String> {
+    let response = ureq::get(url)
+        .set("User-Agent", USER_AGENT)
+        .call()
+        .map_err(|e| e.to_string())?;
+
+    let mut bytes = Vec::new();
+    response
+        .into_reader()
+        .take(MAX_TILE_BYTES)
+        .read_to_end(&mut bytes)
+        .map_err(|e| e.to_string())?;
+    Ok(bytes)
+}
+
+/// Trajectory of a map view in world coordinates, decimated for drawing.
+struct Trajectory {
+    times: Vec<f32>,
+    points: Vec<[f64; 2]>,
+}
+
+impl Trajectory {
+    fn load(view: &MapView, data_store: &DataStore) -> Option<Self> {
+        let times = data_store.get_column(&view.topic, "timestamp")?;
+        let lats = data_store.get_column(&view.topic, &view.lat)?;
+        let lons = data_store.get_column(&view.topic, &view.lon)?;
+
+        let len = times.len().min(lats.len()).min(lons.len());
+        let step = len.div_ceil(MAX_TRAJECTORY_POINTS).max(1);
+        let lat_scale = degree_scale(lats);
+        let lon_scale = degree_scale(lons);
+
+        let mut trajectory = Self {
+            times: Vec::with_capacity(len / step + 1),
+            points: Vec::with_capacity(len / step + 1),
+        };
+        for i in (0..len).step_by(step) {
+            let (lat, lon) = (lats[i], lons[i]);
+            // Missing fixes are commonly logged as zeros.
+            if !lat.is_finite() || !lon.is_finite() || (lat == 0.0 && lon == 0.0) {
+                continue;
+            }
+            trajectory.times.push(times[i]);
+            trajectory
+                .points
+                .push(project(lat as f64 * lat_scale, lon as f64 * lon_scale));
+        }
+
+        Some(trajectory)
+    }
+
+    /// Position at `time`, interpolated between neighbouring samples.
+    fn position_at(&self, time: f32) -> Option<[f64; 2]> {
+        let idx = self.times.partition_point(|&t| t < time);
+        if idx == 0 || idx >= self.times.len() {
+            return None;
+        }
+
+        let (t0, t1) = (self.times[idx - 1], self.times[idx]);
+        let (p0, p1) = (self.points[idx - 1], self.points[idx]);
+        let f = if t1 > t0 {
+            ((time - t0) / (t1 - t0)) as f64
+        } else {
+            0.0
+        };
+        Some([p0[0] + (p1[0] - p0[0]) * f, p0[1] + (p1[1] - p0[1]) * f])
+    }
+}
+
+/// Draws a map view into `rect` and handles panning, zooming and clicks. Returns the time
+/// of the trajectory sample that was clicked, if any.
+pub fn render_map(
+    ui: &mut egui::Ui,
+    rect: egui::Rect,
+    response: &egui::Response,
+    view: &mut MapView,
+    data_store: &DataStore,
+    cache: &mut MapTileCache,
+    current_time: f32,
+) -> Option<f32> {
+    let ctx = ui.ctx().clone();
+    cache.begin_frame(&ctx);
+
+    let trajectory = Trajectory::load(view, data_store);
+    let points = trajectory.as_ref().map_or(&[][..], |t| &t.points[..]);
+    let vehicle = trajectory
+        .as_ref()
+        .and_then(|t| t.position_at(current_time));
+
+    if view.center.is_none() || response.double_clicked() {
+        view.fit(rect, points);
+    }
+    if view.follow {
+        if let Some(position) = vehicle {
+            view.center = Some(position);
+        }
+    }
+    let mut center = view.center.unwrap_or([0.5, 0.5]);
+
+    if response.dragged_by(egui::PointerButton::Primary) {
+        let delta = response.drag_delta();
+        let scale = view.scale();
+        center[0] -= delta.x as f64 / scale;
+        center[1] -= delta.y as f64 / scale;
+        view.follow = false;
+    }
+
+    if response.hovered() {
+        let scroll = ui.input(|i| i.smooth_scroll_delta.y);
+        if scroll != 0.0 {
+            let anchor = response.hover_pos().unwrap_or(rect.center());
+            let before = view.to_world(rect, center, anchor);
+            view.zoom = (view.zoom + scroll * 0.005).clamp(MIN_ZOOM, MAX_ZOOM);
+            let after = view.to_world(rect, center, anchor);
+            if !view.follow {
+                center[0] += before[0] - after[0];
+                center[1] += before[1] - after[1];
+            }
+        }
+    }
+
+    center[0] = center[0].clamp(0.0, 1.0);
+    center[1] = center[1].clamp(0.0, 1.0);
+    // Without data there is nothing to fit yet; keep waiting for it.
+    if view.center.is_some() {
+        view.center = Some(center);
+    }
+
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 0.0, egui::Color32::from_rgb(30, 30, 34));
+    draw_tiles(&painter, rect, view, center, cache);
+    cache.start_loads(&ctx);
+
+    let screen: Vec<egui::Pos2> = points
+        .iter()
+        .map(|p| view.to_screen(rect, center, *p))
+        .collect();
+
+    if let Some(trajectory) = &trajectory {
+        let split = trajectory
+            .times
+            .partition_point(|&t| t <= current_time)
+            .min(screen.len());
+        painter.add(egui::Shape::line(
+            screen[split.saturating_sub(1)..].to_vec(),
+            egui::Stroke::new(2.0, egui::Color32::from_rgba_unmultiplied(255, 140, 0, 110)),
+        ));
+        painter.add(egui::Shape::line(
+            screen[..split].to_vec(),
+            egui::Stroke::new(2.5, egui::Color32::from_rgb(255, 140, 0)),
+        ));
+    }
+
+    if let Some(position) = vehicle {
+        let pos = view.to_screen(rect, center, position);
+        painter.circle(
+            pos,
+            6.0,
+            egui::Color32::from_rgb(70, 160, 255),
+            egui::Stroke::new(2.0, egui::Color32::WHITE),
+        );
+    }
+
+    let attribution = view.source.attribution();
+    let galley = painter.layout_no_wrap(
+        attribution.to_string(),
+        egui::FontId::proportional(10.0),
+        egui::Color32::from_gray(220),
+    );
+    let text_rect = egui::Align2::RIGHT_BOTTOM
+        .anchor_size(rect.right_bottom() - egui::vec2(2.0, 2.0), galley.size())
+        .expand(2.0);
+    painter.rect_filled(text_rect, 2.0, egui::Color32::from_black_alpha(160));
+    painter.galley(
+        text_rect.min + egui::vec2(2.0, 2.0),
+        galley,
+        egui::Color32::WHITE,
+    );
+
+    if points.is_empty() {
+        painter.text(
+            rect.center(),
+            egui::Align2::CENTER_CENTER,
+            format!("No position data in {}", view.topic),
+            egui::FontId::proportional(14.0),
+            egui::Color32::GRAY,
+        );
+    }
+
+    if response.clicked() {
+        let pointer = response.interact_pointer_pos()?;
+        let trajectory = trajectory?;
+        let (idx, distance) = screen
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (i, p.distance(pointer)))
+            .min_by(|a, b| a.1.total_cmp(&b.1))?;
+        if distance < 12.0 {
+            return Some(trajectory.times[idx]);
+        }
+    }
+
+    None
+}
+
+fn draw_tiles(
+    painter: &egui::Painter,
+    rect: egui::Rect,
+    view: &MapView,
+    center: [f64; 2],
+    cache: &mut MapTileCache,
+) {
+    let level = (view.zoom.round() as u8).min(MAX_ZOOM as u8);
+    let count = 1u32 << level;
+
+    let top_left = view.to_world(rect, center, rect.min);
+    let bottom_right = view.to_world(rect, center, rect.max);
+    let tile_range = |min: f64, max: f64| {
+        let first = (min * count as f64).floor().max(0.0) as u32;
+        let last = ((max * count as f64).floor().max(0.0) as u32).min(count - 1);
+        first..=last
+    };
+
+    for ty in tile_range(top_left[1], bottom_right[1]) {
+        for tx in tile_range(top_left[0], bottom_right[0]) {
+            let key = (view.source, level, tx, ty);
+            let world_min = [tx as f64 / count as f64, ty as f64 / count as f64];
+            let world_max = [
+                (tx + 1) as f64 / count as f64,
+                (ty + 1) as f64 / count as f64,
+            ];
+            let tile_rect = egui::Rect::from_min_max(
+                view.to_screen(rect, center, world_min),
+                view.to_screen(rect, center, world_max),
+            );
+
+            if let Some(texture) = cache.ready(key) {
+                painter.image(
+                    texture.id(),
+                    tile_rect,
+                    egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0)),
+                    egui::Color32::WHITE,
+                );
+                continue;
+            }
+
+            cache.request(key);
+
+            // Show part of a coarser tile until this one arrives.
+            for up in 1..=MAX_FALLBACK_LEVELS.min(level) {
+                let parent = (view.source, level - up, tx >> up, ty >> up);
+                if let Some(texture) = cache.ready(parent) {
+                    let cells = (1u32 << up) as f32;
+                    let u = (tx & ((1 << up) - 1)) as f32 / cells;
+                    let v = (ty & ((1 << up) - 1)) as f32 / cells;
+                    painter.image(
+                        texture.id(),
+                        tile_rect,
+                        egui::Rect::from_min_size(
+                            egui::pos2(u, v),
+                            egui::vec2(1.0 / cells, 1.0 / cells),
+                        ),
+                        egui::Color32::WHITE,
+                    );
+                    break;
+                }
+            }
+        }
+    }
+}
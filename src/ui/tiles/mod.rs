@@ -1,8 +1,21 @@
+pub mod actuator_tile;
+pub mod image_tile;
+pub mod map_tile;
+pub mod pid_tile;
 pub mod plot_tile;
 pub mod tile_behavior;
-
-pub use plot_tile::{InterpolationMode, PlotTile};
+pub mod video_tile;
+
+pub use crate::core::InterpolationMode;
+pub use actuator_tile::ActuatorView;
+pub use image_tile::{ImageFrameCache, ImageView};
+pub use map_tile::{MapSource, MapTileCache, MapView};
+pub use pid_tile::PidView;
+pub use plot_tile::{
+    padded_bounds, y_sync_ranges, LegendCorner, PlotTile, YSyncRanges, Y_SYNC_GROUPS,
+};
 pub use tile_behavior::TiPlotBehavior;
+pub use video_tile::{VideoFrameCache, VideoView};
 
 use eframe::egui;
 
@@ -21,8 +34,8 @@ fn calculate_tooltip_layout(ui: &egui::Ui, num_traces: usize, max_height: f32) -
     let max_rows_per_column = (available_height / line_height).floor() as usize;
     let max_rows_per_column = max_rows_per_column.max(3);
 
-    let num_columns = ((num_traces + max_rows_per_column - 1) / max_rows_per_column).max(1);
-    let items_per_column = (num_traces + num_columns - 1) / num_columns;
+    let num_columns = num_traces.div_ceil(max_rows_per_column).max(1);
+    let items_per_column = num_traces.div_ceil(num_columns);
 
     (num_columns, items_per_column)
 }
@@ -90,7 +103,22 @@ fn render_tooltip_content(
                                 ),
                             );
 
-                            ui.label(format!("{}: {:.4}", trace.col, val));
+                            ui.label(format!(
+                                "{}: {}",
+                                trace.display_name(),
+                                trace.format_value(val)
+                            ));
+
+                            if let Some(Some(sample)) = tile.cached_tooltip_samples.get(i) {
+                                ui.label(
+                                    egui::RichText::new(format!(
+                                        "#{} raw {}",
+                                        sample.index, sample.raw
+                                    ))
+                                    .weak()
+                                    .size(10.0),
+                                );
+                            }
                         });
                     }
                 }
@@ -105,7 +133,7 @@ pub fn render_cursor_tooltip(
     ui: &mut egui::Ui,
     plot_rect: egui::Rect,
     pointer_pos: egui::Pos2,
-    hover_time: f32,
+    time_label: &str,
     tile: &mut PlotTile,
 ) {
     let tooltip_padding = 6.0;
@@ -163,7 +191,7 @@ pub fn render_cursor_tooltip(
             .inner_margin(tooltip_padding)
             .show(ui, |ui| {
                 ui.label(
-                    egui::RichText::new(format!("Time: {:.3}s", hover_time))
+                    egui::RichText::new(format!("Time: {}", time_label))
                         .strong()
                         .size(12.0),
                 );
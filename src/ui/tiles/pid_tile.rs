@@ -0,0 +1,534 @@
+use super::{padded_bounds, InterpolationMode};
+use crate::core::DataStore;
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+
+/// Setpoint samples that keep moving the same way within this many seconds of a jump
+/// belong to the same step.
+const RAMP_TIME: f32 = 0.1;
+const MAX_TABLE_ROWS: usize = 8;
+
+const SETPOINT_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 180, 60);
+const MEASUREMENT_COLOR: egui::Color32 = egui::Color32::from_rgb(80, 160, 255);
+const ERROR_COLOR: egui::Color32 = egui::Color32::from_rgb(230, 90, 90);
+
+/// Configuration of a plot tile shown as a tracking analysis of a setpoint and the
+/// measurement following it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PidView {
+    /// Setpoint as (topic, column).
+    pub setpoint: (String, String),
+    /// Measurement as (topic, column).
+    pub measurement: (String, String),
+    /// Smallest setpoint jump counted as a step, as a fraction of the visible setpoint range.
+    #[serde(default = "default_step_threshold")]
+    pub step_threshold: f32,
+    /// Half-width of the settling band around the new setpoint, as a fraction of the step.
+    #[serde(default = "default_settle_band")]
+    pub settle_band: f32,
+}
+
+fn default_step_threshold() -> f32 {
+    0.2
+}
+
+fn default_settle_band() -> f32 {
+    0.05
+}
+
+impl PidView {
+    pub fn new(setpoint: (String, String), measurement: (String, String)) -> Self {
+        Self {
+            setpoint,
+            measurement,
+            step_threshold: default_step_threshold(),
+            settle_band: default_settle_band(),
+        }
+    }
+}
+
+/// How the measurement followed one setpoint step.
+pub struct StepResponse {
+    pub time: f32,
+    pub size: f32,
+    /// Time from 10 % to 90 % of the step, `None` if 90 % was never reached.
+    pub rise_time: Option<f32>,
+    /// Largest excursion past the new setpoint, in percent of the step.
+    pub overshoot: f32,
+    /// Time after the step until the measurement stays inside the settling band, `None`
+    /// if it did not settle before the next step or the end of the view.
+    pub settling_time: Option<f32>,
+    /// End of the stretch the step was evaluated over.
+    pub end: f32,
+}
+
+/// A column in display units, with its timestamps.
+struct Series<'a> {
+    times: &'a [f32],
+    values: Vec<f32>,
+    unit: Option<String>,
+}
+
+impl<'a> Series<'a> {
+    fn load(data_store: &'a DataStore, (topic, col): &(String, String)) -> Option<Self> {
+        let times = data_store.get_column(topic, "timestamp")?;
+        let values = data_store.get_column(topic, col)?;
+        let meta = data_store.column_meta(topic, col);
+        let scale = meta.map_or(1.0, |m| m.scale);
+        Some(Self {
+            times,
+            values: values.iter().map(|v| v * scale).collect(),
+            unit: meta.map(|m| m.unit.clone()).filter(|u| !u.is_empty()),
+        })
+    }
+
+    /// Indices of the samples between `min_time` and `max_time`.
+    fn window(&self, min_time: f32, max_time: f32) -> std::ops::Range<usize> {
+        let len = self.times.len().min(self.values.len());
+        let start = self.times[..len].partition_point(|&t| t < min_time);
+        let end = self.times[..len].partition_point(|&t| t <= max_time);
+        start..end
+    }
+
+    fn range(&self, window: std::ops::Range<usize>) -> Option<(f32, f32)> {
+        self.values[window]
+            .iter()
+            .filter(|v| v.is_finite())
+            .fold(None, |range, &v| match range {
+                None => Some((v, v)),
+                Some((lo, hi)) => Some((lo.min(v), hi.max(v))),
+            })
+    }
+}
+
+/// Finds setpoint steps between `min_time` and `max_time` and measures the response to
+/// each of them.
+pub fn analyze_steps(
+    view: &PidView,
+    data_store: &DataStore,
+    min_time: f32,
+    max_time: f32,
+) -> Vec<StepResponse> {
+    let (Some(setpoint), Some(measurement)) = (
+        Series::load(data_store, &view.setpoint),
+        Series::load(data_store, &view.measurement),
+    ) else {
+        return Vec::new();
+    };
+    find_steps(&setpoint, view.step_threshold, min_time, max_time)
+        .into_iter()
+        .map(|(time, from, to, end)| {
+            measure_response(&measurement, time, from, to, end, view.settle_band)
+        })
+        .collect()
+}
+
+/// Setpoint jumps as (time, value before, value after, end of the step's stretch).
+fn find_steps(
+    setpoint: &Series,
+    threshold: f32,
+    min_time: f32,
+    max_time: f32,
+) -> Vec<(f32, f32, f32, f32)> {
+    let window = setpoint.window(min_time, max_time);
+    let Some((lo, hi)) = setpoint.range(window.clone()) else {
+        return Vec::new();
+    };
+    let min_jump = threshold * (hi - lo);
+    if min_jump <= 0.0 {
+        return Vec::new();
+    }
+
+    let (times, values) = (setpoint.times, &setpoint.values);
+    let mut steps: Vec<(f32, f32, f32, f32)> = Vec::new();
+    let mut i = window.start + 1;
+    while i < window.end {
+        let jump = values[i] - values[i - 1];
+        if jump.abs() < min_jump {
+            i += 1;
+            continue;
+        }
+
+        let time = times[i];
+        let mut last = i;
+        while last + 1 < window.end
+            && times[last + 1] - time < RAMP_TIME
+            && (values[last + 1] - values[last]) * jump.signum() > 0.0
+        {
+            last += 1;
+        }
+
+        if let Some(previous) = steps.last_mut() {
+            previous.3 = time;
+        }
+        steps.push((time, values[i - 1], values[last], max_time));
+        i = last + 1;
+    }
+    steps
+}
+
+fn measure_response(
+    measurement: &Series,
+    time: f32,
+    from: f32,
+    to: f32,
+    end: f32,
+    settle_band: f32,
+) -> StepResponse {
+    let size = to - from;
+    let window = measurement.window(time, end);
+    let samples = window
+        .map(|i| (measurement.times[i], measurement.values[i]))
+        .filter(|(_, v)| v.is_finite());
+
+    // 0 at the old setpoint, 1 at the new one.
+    let progress = |v: f32| (v - from) / size;
+
+    let mut t10 = None;
+    let mut t90 = None;
+    let mut peak = f32::NEG_INFINITY;
+    let mut settled_at = None;
+    let mut any = false;
+    for (t, v) in samples {
+        any = true;
+        let p = progress(v);
+        if t10.is_none() && p >= 0.1 {
+            t10 = Some(t);
+        }
+        if t90.is_none() && p >= 0.9 {
+            t90 = Some(t);
+        }
+        peak = peak.max(p);
+        if (p - 1.0).abs() > settle_band {
+            settled_at = None;
+        } else if settled_at.is_none() {
+            settled_at = Some(t);
+        }
+    }
+
+    StepResponse {
+        time,
+        size,
+        rise_time: t10.zip(t90).map(|(a, b)| b - a),
+        overshoot: if any {
+            (peak - 1.0).max(0.0) * 100.0
+        } else {
+            0.0
+        },
+        settling_time: settled_at.map(|t| t - time),
+        end,
+    }
+}
+
+/// Screen positions of the samples in `window`, thinned to about two per pixel.
+pub(super) fn polyline(
+    times: &[f32],
+    values: impl Fn(usize) -> f32,
+    window: std::ops::Range<usize>,
+    to_screen: impl Fn(f32, f32) -> egui::Pos2,
+    width: f32,
+) -> Vec<egui::Pos2> {
+    let stride = (window.len() / (width * 2.0).max(1.0) as usize).max(1);
+    window
+        .step_by(stride)
+        .map(|i| (times[i], values(i)))
+        .filter(|(_, v)| v.is_finite())
+        .map(|(t, v)| to_screen(t, v))
+        .collect()
+}
+
+fn format_seconds(value: Option<f32>) -> String {
+    match value {
+        Some(s) => format!("{:.3} s", s),
+        None => "—".to_string(),
+    }
+}
+
+fn mean(values: impl Iterator<Item = f32>) -> Option<f32> {
+    let (sum, count) = values.fold((0.0, 0), |(sum, count), v| (sum + v, count + 1));
+    (count > 0).then(|| sum / count as f32)
+}
+
+/// Draws the setpoint, the measurement and the tracking error into `rect`, together with
+/// the response to each setpoint step. Returns the time that was clicked, if any.
+#[allow(clippy::too_many_arguments)]
+pub fn render_pid(
+    ui: &mut egui::Ui,
+    rect: egui::Rect,
+    response: &egui::Response,
+    view: &PidView,
+    data_store: &DataStore,
+    min_time: f32,
+    max_time: f32,
+    current_time: f32,
+) -> Option<f32> {
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 0.0, egui::Color32::from_rgb(20, 20, 20));
+
+    let time_span = max_time - min_time;
+    let (Some(setpoint), Some(measurement)) = (
+        Series::load(data_store, &view.setpoint),
+        Series::load(data_store, &view.measurement),
+    ) else {
+        painter.text(
+            rect.center(),
+            egui::Align2::CENTER_CENTER,
+            format!(
+                "No data for {}/{} or {}/{}",
+                view.setpoint.0, view.setpoint.1, view.measurement.0, view.measurement.1
+            ),
+            egui::FontId::proportional(14.0),
+            egui::Color32::GRAY,
+        );
+        return None;
+    };
+    if time_span <= 0.0 {
+        return None;
+    }
+
+    let sp_window = setpoint.window(min_time, max_time);
+    let m_window = measurement.window(min_time, max_time);
+    let errors: Vec<f32> = measurement
+        .times
+        .iter()
+        .zip(&measurement.values)
+        .map(|(&t, &m)| {
+            InterpolationMode::PreviousPoint
+                .sample(setpoint.times, &setpoint.values, t)
+                .map_or(f32::NAN, |sp| sp - m)
+        })
+        .collect();
+
+    let value_range = match (
+        setpoint.range(sp_window.clone()),
+        measurement.range(m_window.clone()),
+    ) {
+        (Some(a), Some(b)) => Some((a.0.min(b.0), a.1.max(b.1))),
+        (a, b) => a.or(b),
+    };
+    let error_range = m_window
+        .clone()
+        .map(|i| errors[i])
+        .filter(|e| e.is_finite())
+        .fold(None, |range: Option<(f32, f32)>, e| match range {
+            None => Some((e.min(0.0), e.max(0.0))),
+            Some((lo, hi)) => Some((lo.min(e), hi.max(e))),
+        });
+
+    let split = rect.top() + rect.height() * 0.65;
+    let value_rect = egui::Rect::from_x_y_ranges(rect.x_range(), rect.top()..=split);
+    let error_rect = egui::Rect::from_x_y_ranges(rect.x_range(), split..=rect.bottom());
+    let x_of = |t: f32| rect.left() + (t - min_time) / time_span * rect.width();
+    let mapper = |lane: egui::Rect, (lo, hi): (f32, f32)| {
+        move |t: f32, v: f32| {
+            let y = lane.bottom() - (v - lo) / (hi - lo).max(f32::EPSILON) * lane.height();
+            egui::pos2(x_of(t), y)
+        }
+    };
+    let value_bounds = padded_bounds(value_range);
+    let error_bounds = padded_bounds(error_range);
+    let value_to_screen = mapper(value_rect, value_bounds);
+    let error_to_screen = mapper(error_rect, error_bounds);
+
+    painter.line_segment(
+        [error_rect.left_top(), error_rect.right_top()],
+        egui::Stroke::new(1.0, egui::Color32::from_gray(60)),
+    );
+    let zero = error_to_screen(min_time, 0.0).y;
+    painter.line_segment(
+        [
+            egui::pos2(rect.left(), zero),
+            egui::pos2(rect.right(), zero),
+        ],
+        egui::Stroke::new(1.0, egui::Color32::from_gray(50)),
+    );
+
+    let steps = analyze_steps(view, data_store, min_time, max_time);
+    for (n, step) in steps.iter().enumerate() {
+        let x = x_of(step.time);
+        let (until, fill) = match step.settling_time {
+            Some(settle) => (
+                step.time + settle,
+                egui::Color32::from_rgba_unmultiplied(90, 200, 120, 25),
+            ),
+            None => (
+                step.end,
+                egui::Color32::from_rgba_unmultiplied(230, 90, 90, 25),
+            ),
+        };
+        painter.rect_filled(
+            egui::Rect::from_x_y_ranges(x..=x_of(until), rect.y_range()),
+            0.0,
+            fill,
+        );
+        painter.extend(egui::Shape::dashed_line(
+            &[egui::pos2(x, rect.top()), egui::pos2(x, rect.bottom())],
+            egui::Stroke::new(1.0, egui::Color32::from_gray(140)),
+            4.0,
+            4.0,
+        ));
+        painter.text(
+            egui::pos2(x + 3.0, rect.top() + 18.0),
+            egui::Align2::LEFT_TOP,
+            format!("{}", n + 1),
+            egui::FontId::monospace(10.0),
+            egui::Color32::from_gray(200),
+        );
+    }
+
+    let width = rect.width();
+    painter.add(egui::Shape::line(
+        polyline(
+            measurement.times,
+            |i| measurement.values[i],
+            m_window.clone(),
+            value_to_screen,
+            width,
+        ),
+        egui::Stroke::new(1.5, MEASUREMENT_COLOR),
+    ));
+    // Setpoints are held between samples, so draw them as steps.
+    let mut setpoint_points: Vec<egui::Pos2> = Vec::new();
+    for pos in polyline(
+        setpoint.times,
+        |i| setpoint.values[i],
+        sp_window,
+        value_to_screen,
+        width,
+    ) {
+        if let Some(&last) = setpoint_points.last() {
+            setpoint_points.push(egui::pos2(pos.x, last.y));
+        }
+        setpoint_points.push(pos);
+    }
+    painter.add(egui::Shape::line(
+        setpoint_points,
+        egui::Stroke::new(1.5, SETPOINT_COLOR),
+    ));
+    painter.add(egui::Shape::line(
+        polyline(
+            measurement.times,
+            |i| errors[i],
+            m_window,
+            error_to_screen,
+            width,
+        ),
+        egui::Stroke::new(1.0, ERROR_COLOR),
+    ));
+
+    let small = egui::FontId::proportional(10.0);
+    for (lane, (lo, hi)) in [(value_rect, value_bounds), (error_rect, error_bounds)] {
+        painter.text(
+            lane.left_top() + egui::vec2(4.0, 2.0),
+            egui::Align2::LEFT_TOP,
+            format!("{:.3}", hi),
+            small.clone(),
+            egui::Color32::GRAY,
+        );
+        painter.text(
+            lane.left_bottom() + egui::vec2(4.0, -2.0),
+            egui::Align2::LEFT_BOTTOM,
+            format!("{:.3}", lo),
+            small.clone(),
+            egui::Color32::GRAY,
+        );
+    }
+
+    let unit = measurement.unit.or(setpoint.unit);
+    let with_unit = |name: &str| match &unit {
+        Some(unit) => format!("{} [{}]", name, unit),
+        None => name.to_string(),
+    };
+    let mut legend_pos = rect.left_top() + egui::vec2(60.0, 4.0);
+    for (label, color) in [
+        (with_unit(&view.setpoint.1), SETPOINT_COLOR),
+        (with_unit(&view.measurement.1), MEASUREMENT_COLOR),
+    ] {
+        let galley = painter.layout_no_wrap(label, egui::FontId::proportional(11.0), color);
+        let size = galley.size();
+        painter.galley(legend_pos, galley, color);
+        legend_pos.x += size.x + 12.0;
+    }
+    painter.text(
+        error_rect.left_top() + egui::vec2(60.0, 2.0),
+        egui::Align2::LEFT_TOP,
+        "error",
+        egui::FontId::proportional(11.0),
+        ERROR_COLOR,
+    );
+
+    draw_summary(&painter, rect, &steps);
+
+    if current_time >= min_time && current_time <= max_time {
+        let x = x_of(current_time);
+        painter.line_segment(
+            [egui::pos2(x, rect.top()), egui::pos2(x, rect.bottom())],
+            egui::Stroke::new(1.5, egui::Color32::from_rgb(255, 200, 0)),
+        );
+    }
+
+    if let Some(pos) = response.hover_pos() {
+        painter.line_segment(
+            [
+                egui::pos2(pos.x, rect.top()),
+                egui::pos2(pos.x, rect.bottom()),
+            ],
+            egui::Stroke::new(1.0, egui::Color32::from_white_alpha(140)),
+        );
+    }
+
+    if response.clicked() {
+        let pos = response.interact_pointer_pos()?;
+        return Some(min_time + (pos.x - rect.left()) / rect.width() * time_span);
+    }
+
+    None
+}
+
+/// Table of the step responses in the top right corner.
+fn draw_summary(painter: &egui::Painter, rect: egui::Rect, steps: &[StepResponse]) {
+    let mut text = format!(
+        "{:>3} {:>9} {:>9} {:>8} {:>9}\n",
+        "#", "step", "rise", "over", "settle"
+    );
+    for (n, step) in steps.iter().enumerate().take(MAX_TABLE_ROWS) {
+        text.push_str(&format!(
+            "{:>3} {:>9.3} {:>9} {:>7.1}% {:>9}\n",
+            n + 1,
+            step.size,
+            format_seconds(step.rise_time),
+            step.overshoot,
+            format_seconds(step.settling_time),
+        ));
+    }
+    if steps.len() > MAX_TABLE_ROWS {
+        text.push_str(&format!("  … {} more\n", steps.len() - MAX_TABLE_ROWS));
+    }
+    if steps.is_empty() {
+        text.push_str("  no steps in view\n");
+    } else {
+        text.push_str(&format!(
+            "{:>3} {:>9} {:>9} {:>7.1}% {:>9}",
+            "avg",
+            "",
+            format_seconds(mean(steps.iter().filter_map(|s| s.rise_time))),
+            mean(steps.iter().map(|s| s.overshoot)).unwrap_or(0.0),
+            format_seconds(mean(steps.iter().filter_map(|s| s.settling_time))),
+        ));
+    }
+
+    let galley = painter.layout_no_wrap(
+        text.trim_end().to_string(),
+        egui::FontId::monospace(10.0),
+        egui::Color32::from_gray(220),
+    );
+    let box_rect = egui::Align2::RIGHT_TOP
+        .anchor_size(rect.right_top() + egui::vec2(-6.0, 6.0), galley.size())
+        .expand(4.0);
+    painter.rect_filled(box_rect, 3.0, egui::Color32::from_black_alpha(180));
+    painter.galley(
+        box_rect.min + egui::vec2(4.0, 4.0),
+        galley,
+        egui::Color32::WHITE,
+    );
+}
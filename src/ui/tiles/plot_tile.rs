@@ -1,16 +1,217 @@
-use crate::core::DataStore;
+use super::{ActuatorView, ImageView, MapView, PidView, VideoView};
+use crate::core::aliases;
+use crate::core::{
+    AliasProfile, AngleUnit, DataStore, InterpolationMode, SignalFilter, UnitConversion,
+    ValueFormat,
+};
+use crate::ui::palette::TracePalette;
+use crate::ui::panels::tabs::scene::SceneState;
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
-#[derive(Clone, Debug, Copy, PartialEq)]
-pub enum InterpolationMode {
-    PreviousPoint,
-    Linear,
-    NextPoint,
+/// Number of Y-sync groups tiles can be tagged with.
+pub const Y_SYNC_GROUPS: u8 = 6;
+
+/// Share of samples left out at each end of the range when auto-scaling ignores outliers.
+const ROBUST_PERCENTILE: f32 = 0.01;
+/// Most samples per trace the outlier-robust range is estimated from.
+const ROBUST_MAX_SAMPLES: usize = 20_000;
+
+/// Value range shared by the tiles of each Y-sync group, keyed by group number.
+pub type YSyncRanges = HashMap<u8, (f32, f32)>;
+
+/// Axis bounds around a value range, with some room above and below.
+pub fn padded_bounds(range: Option<(f32, f32)>) -> (f32, f32) {
+    let Some((min_y, max_y)) = range else {
+        return (-1.0, 1.0);
+    };
+    let range = max_y - min_y;
+    let pad = if range == 0.0 { 1.0 } else { range * 0.1 };
+    (min_y - pad, max_y + pad)
+}
+
+/// Union of the value ranges of all tiles in each Y-sync group.
+pub fn y_sync_ranges<'a>(
+    tiles: impl IntoIterator<Item = &'a PlotTile>,
+    data_store: &DataStore,
+    min_time: f32,
+    max_time: f32,
+) -> YSyncRanges {
+    let mut ranges = YSyncRanges::new();
+    for tile in tiles {
+        let Some(group) = tile.y_sync_group else {
+            continue;
+        };
+        if let Some((min, max)) = tile.value_range(data_store, min_time, max_time) {
+            let range = ranges.entry(group).or_insert((min, max));
+            range.0 = range.0.min(min);
+            range.1 = range.1.max(max);
+        }
+    }
+    ranges
+}
+
+/// Corner of the plot the legend is drawn in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LegendCorner {
+    TopLeft,
+    #[default]
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+/// Logged sample a hover snapped to.
+#[derive(Clone, Copy, Debug)]
+pub struct HoverSample {
+    pub index: usize,
+    pub time: f32,
+    /// Value as stored, before the trace's scale and conversion.
+    pub raw: f32,
+}
+
+/// Index of the sample logged closest to `time`.
+fn nearest_sample(times: &[f32], time: f32) -> Option<usize> {
+    let after = times.partition_point(|&t| t < time);
+    match (after.checked_sub(1), times.get(after)) {
+        (Some(before), Some(&t)) if time - times[before] <= t - time => Some(before),
+        (_, Some(_)) => Some(after),
+        (before, None) => before,
+    }
+}
+
+/// Area drawn below a trace's line.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum TraceFill {
+    /// Between the trace and zero.
+    UnderCurve,
+    /// Between the trace and another column of the same topic.
+    Between(String),
+    /// The trace plus and minus another column of the same topic, e.g. a standard
+    /// deviation.
+    PlusMinus(String),
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum MathOp {
+    #[default]
+    None,
+    Abs,
+    Negate,
+    /// Wraps angles in radians into ±π.
+    WrapPi,
+}
+
+impl MathOp {
+    pub const ALL: [MathOp; 4] = [MathOp::None, MathOp::Abs, MathOp::Negate, MathOp::WrapPi];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            MathOp::None => "x",
+            MathOp::Abs => "|x|",
+            MathOp::Negate => "-x",
+            MathOp::WrapPi => "wrap(x) to ±π",
+        }
+    }
+
+    fn apply(self, x: f32) -> f32 {
+        match self {
+            MathOp::None => x,
+            MathOp::Abs => x.abs(),
+            MathOp::Negate => -x,
+            MathOp::WrapPi => {
+                let tau = std::f32::consts::TAU;
+                x - tau * ((x + std::f32::consts::PI) / tau).floor()
+            }
+        }
+    }
 }
 
-impl Default for InterpolationMode {
+/// Quick adjustment of a trace's displayed values: `gain · op(x) + offset`.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TraceMath {
+    pub gain: f32,
+    pub offset: f32,
+    pub op: MathOp,
+}
+
+impl Default for TraceMath {
     fn default() -> Self {
-        Self::PreviousPoint
+        Self {
+            gain: 1.0,
+            offset: 0.0,
+            op: MathOp::None,
+        }
+    }
+}
+
+impl TraceMath {
+    pub fn is_identity(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn apply(&self, x: f32) -> f32 {
+        self.gain * self.op.apply(x) + self.offset
     }
+
+    /// Range of `apply` over values between `min` and `max`.
+    pub fn apply_range(&self, (min, max): (f32, f32)) -> (f32, f32) {
+        let (lo, hi) = match self.op {
+            MathOp::None => (min, max),
+            MathOp::Negate => (-max, -min),
+            MathOp::Abs if min >= 0.0 => (min, max),
+            MathOp::Abs if max <= 0.0 => (-max, -min),
+            MathOp::Abs => (0.0, max.max(-min)),
+            MathOp::WrapPi => {
+                let (lo, hi) = (self.op.apply(min), self.op.apply(max));
+                if max - min < std::f32::consts::TAU && lo <= hi {
+                    (lo, hi)
+                } else {
+                    (-std::f32::consts::PI, std::f32::consts::PI)
+                }
+            }
+        };
+        let (a, b) = (self.gain * lo + self.offset, self.gain * hi + self.offset);
+        (a.min(b), a.max(b))
+    }
+
+    /// Formula shown next to the trace name, `None` when values are unchanged.
+    pub fn label(&self) -> Option<String> {
+        if self.is_identity() {
+            return None;
+        }
+        let mut label = match self.op {
+            MathOp::WrapPi => "wrap(x)".to_string(),
+            op => op.label().to_string(),
+        };
+        if self.gain != 1.0 {
+            label = format!("{}·{}", self.gain, label);
+        }
+        if self.offset != 0.0 {
+            let sign = if self.offset < 0.0 { '-' } else { '+' };
+            label = format!("{} {} {}", label, sign, self.offset.abs());
+        }
+        Some(label)
+    }
+}
+
+/// Named set of traces in a tile, e.g. "gyro" or "accel", shown and colored together.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TraceGroup {
+    pub name: String,
+    #[serde(default)]
+    pub hidden: bool,
+    /// Palette the group's traces were last recolored from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub palette: Option<TracePalette>,
+}
+
+/// Trace count and combined value range of a group.
+#[derive(Clone, Copy, Debug)]
+pub struct GroupStats {
+    pub traces: usize,
+    pub range: Option<(f32, f32)>,
 }
 
 #[derive(Clone, Debug)]
@@ -20,44 +221,573 @@ pub struct TraceConfig {
     pub col: String,
 
     pub color: [f32; 4],
+
+    pub filter: Option<SignalFilter>,
+
+    /// Set for angle traces that are unwrapped across the ±π / ±180° seam, with the unit
+    /// the angle is stored in.
+    pub unwrap: Option<AngleUnit>,
+
+    pub conversion: Option<UnitConversion>,
+
+    pub fill: Option<TraceFill>,
+
+    pub math: TraceMath,
+
+    /// Display unit and the factor taking stored values into it, refreshed from the
+    /// column metadata every frame.
+    pub unit: Option<String>,
+    pub scale: f32,
+
+    /// Replaces the column's value format for this trace.
+    pub format: Option<ValueFormat>,
+    /// Value format from the column metadata, refreshed with the unit.
+    pub column_format: ValueFormat,
+
+    /// Name of the tile's trace group the trace belongs to.
+    pub group: Option<String>,
+}
+
+impl TraceConfig {
+    /// Column the plotted samples are read from: the raw column, or its unwrapped and/or
+    /// filtered copy.
+    pub fn data_col(&self) -> String {
+        let col = match self.unwrap {
+            Some(unit) => DataStore::unwrapped_column_name(&self.col, unit),
+            None => self.col.clone(),
+        };
+        match &self.filter {
+            Some(filter) => DataStore::derived_column_name(&col, filter),
+            None => col,
+        }
+    }
+
+    /// Derived columns the trace is drawn from, in the order they are computed.
+    pub fn derived_cols(&self) -> Vec<String> {
+        let mut cols = Vec::new();
+        if let Some(unit) = self.unwrap {
+            cols.push(DataStore::unwrapped_column_name(&self.col, unit));
+        }
+        if self.filter.is_some() {
+            cols.push(self.data_col());
+        }
+        cols
+    }
+
+    /// Computes the derived columns of the trace that are missing or stale, returning the
+    /// names of those that were (re)computed.
+    pub fn ensure_derived(&self, data_store: &mut DataStore) -> Vec<String> {
+        let mut updated = Vec::new();
+        let mut col = self.col.clone();
+        if let Some(unit) = self.unwrap {
+            updated.extend(data_store.ensure_unwrapped(&self.topic, &col, unit));
+            col = DataStore::unwrapped_column_name(&col, unit);
+        }
+        if let Some(filter) = &self.filter {
+            // A recomputed source invalidates the filtered copy even if its length held.
+            if !updated.is_empty() {
+                if let Some(derived) = data_store.derived.get_mut(&self.topic) {
+                    derived.remove(&DataStore::derived_column_name(&col, filter));
+                }
+            }
+            updated.extend(data_store.ensure_derived(&self.topic, &col, filter));
+        }
+        updated
+    }
+
+    pub fn display_name(&self) -> String {
+        let mut name = self.col.clone();
+        if self.unwrap.is_some() {
+            name.push_str(" [unwrap]");
+        }
+        if let Some(filter) = &self.filter {
+            name = format!("{} [{}]", name, filter.label());
+        }
+        match self.math.label() {
+            Some(math) => format!("{} ({})", name, math),
+            None => name,
+        }
+    }
+
+    /// A stored sample in display units, with the trace's math applied.
+    pub fn display_value(&self, raw: f32) -> f32 {
+        self.math.apply(raw * self.scale)
+    }
+
+    /// Smallest and largest displayed value between `min_time` and `max_time`.
+    pub fn value_range(
+        &self,
+        data_store: &DataStore,
+        min_time: f32,
+        max_time: f32,
+    ) -> Option<(f32, f32)> {
+        let times = data_store.get_column(&self.topic, "timestamp")?;
+        let start = times.partition_point(|&t| t < min_time);
+        let end = times.partition_point(|&t| t <= max_time);
+        let stats = data_store.column_range_stats(&self.topic, &self.data_col(), start, end)?;
+        if stats.count == 0 {
+            return None;
+        }
+        let (a, b) = (stats.min * self.scale, stats.max * self.scale);
+        Some(self.math.apply_range((a.min(b), a.max(b))))
+    }
+
+    /// Displayed range from the 1st to the 99th percentile of the samples between
+    /// `min_time` and `max_time`, so a few glitches do not flatten the rest of the trace.
+    pub fn robust_range(
+        &self,
+        data_store: &DataStore,
+        min_time: f32,
+        max_time: f32,
+    ) -> Option<(f32, f32)> {
+        let times = data_store.get_column(&self.topic, "timestamp")?;
+        let values = data_store.get_column(&self.topic, &self.data_col())?;
+        let end = times.partition_point(|&t| t <= max_time).min(values.len());
+        let start = times.partition_point(|&t| t < min_time).min(end);
+        let stride = ((end - start) / ROBUST_MAX_SAMPLES).max(1);
+        let mut samples: Vec<f32> = values[start..end]
+            .iter()
+            .step_by(stride)
+            .copied()
+            .filter(|v| v.is_finite())
+            .collect();
+        if samples.is_empty() {
+            return None;
+        }
+
+        let last = samples.len() - 1;
+        let low = (last as f32 * ROBUST_PERCENTILE).round() as usize;
+        let high = (last as f32 * (1.0 - ROBUST_PERCENTILE)).round() as usize;
+        let low = *samples.select_nth_unstable_by(low, f32::total_cmp).1;
+        let high = *samples.select_nth_unstable_by(high, f32::total_cmp).1;
+        let (a, b) = (low * self.scale, high * self.scale);
+        Some(self.math.apply_range((a.min(b), a.max(b))))
+    }
+
+    /// `value` formatted with the trace's value format and display unit.
+    pub fn format_value(&self, value: f32) -> String {
+        self.format
+            .as_ref()
+            .unwrap_or(&self.column_format)
+            .format(value, self.unit.as_deref())
+    }
+
+    fn refresh_units(&mut self, data_store: &DataStore) -> bool {
+        let meta = data_store.column_meta(&self.topic, &self.col);
+
+        let mut scale = meta.map_or(1.0, |m| m.scale);
+        let mut unit = meta.map(|m| m.unit.clone()).filter(|u| !u.is_empty());
+
+        if let Some(conversion) = self.conversion {
+            scale *= conversion.factor();
+            unit = Some(conversion.target_unit().to_string());
+        }
+
+        if let Some(format) = meta.map(|m| &m.format) {
+            if *format != self.column_format {
+                self.column_format = format.clone();
+            }
+        }
+
+        let changed = scale != self.scale || unit != self.unit;
+        self.scale = scale;
+        self.unit = unit;
+        changed
+    }
 }
 
 #[derive(Clone, Debug)]
 pub struct PlotTile {
     pub traces: Vec<TraceConfig>,
+    /// Groups the traces refer to by name. Members of a group are kept next to each
+    /// other in `traces`.
+    pub groups: Vec<TraceGroup>,
 
     pub show_legend: bool,
+    /// Show each trace's value at the playback cursor in the legend.
+    pub show_legend_values: bool,
+    pub legend_corner: LegendCorner,
+    pub legend_collapsed: bool,
+    /// First legend row shown when the traces do not fit, in rows.
+    pub legend_scroll: f32,
+    /// Where the legend was drawn last frame, so scrolling over it does not zoom.
+    pub legend_rect: Option<egui::Rect>,
     pub show_hover_tooltip: bool,
     pub show_hover_circles: bool,
     pub scatter_mode: bool,
+    /// Draw every trace in its own lane with a separate value axis.
+    pub stacked: bool,
+    /// Fit the value axis to the bulk of the samples, ignoring outliers.
+    pub robust_scale: bool,
 
     pub cached_tooltip_time: f32,
     pub cached_tooltip_values: Vec<Option<f32>>,
+    /// Sample behind each cached value when snapping to samples.
+    pub cached_tooltip_samples: Vec<Option<HoverSample>>,
+    /// Hover values come from the closest logged sample instead of interpolation.
+    pub snap_to_samples: bool,
 
     pub show_info_window: bool,
+    /// Trace whose properties window is open.
+    pub editing_trace: Option<usize>,
     pub cached_for_playback: bool,
 
     pub interpolation_mode: InterpolationMode,
+
+    /// When set, the tile shows a map of this trajectory instead of time series.
+    pub map: Option<MapView>,
+    /// When set, the tile shows a 3D scene of the configured vehicles.
+    pub scene: Option<Box<SceneState>>,
+    /// When set, the tile shows how a measurement tracks its setpoint.
+    pub pid: Option<PidView>,
+    /// When set, the tile shows the outputs of an actuator topic.
+    pub actuators: Option<ActuatorView>,
+    pub video: Option<VideoView>,
+    /// When set, the tile shows the camera frame of an image column nearest the cursor.
+    pub image: Option<ImageView>,
+    /// Tiles of the same group share their value axis.
+    pub y_sync_group: Option<u8>,
+    /// When set, every trace is overlaid a second time from the window this many seconds
+    /// later, to compare two stretches of the same log.
+    pub compare_offset: Option<f32>,
+    /// Horizontal limit lines, kept in view by the value axis.
+    pub limits: Vec<f32>,
 }
 
 impl PlotTile {
     pub fn new() -> Self {
         Self {
             traces: Vec::new(),
+            groups: Vec::new(),
             show_legend: false,
+            show_legend_values: false,
+            legend_corner: LegendCorner::default(),
+            legend_collapsed: false,
+            legend_scroll: 0.0,
+            legend_rect: None,
             show_hover_tooltip: true,
             show_hover_circles: true,
             scatter_mode: false,
+            stacked: false,
+            robust_scale: false,
             cached_tooltip_time: f32::NEG_INFINITY,
             cached_tooltip_values: Vec::new(),
+            cached_tooltip_samples: Vec::new(),
+            snap_to_samples: false,
             show_info_window: false,
+            editing_trace: None,
             cached_for_playback: false,
             interpolation_mode: InterpolationMode::default(),
+            map: None,
+            scene: None,
+            pid: None,
+            actuators: None,
+            video: None,
+            image: None,
+            y_sync_group: None,
+            compare_offset: None,
+            limits: Vec::new(),
         }
     }
 
+    pub fn trace_colors(&self) -> Vec<[f32; 4]> {
+        self.traces.iter().map(|t| t.color).collect()
+    }
+
     pub fn add_trace(&mut self, topic: String, col: String, color: [f32; 4]) {
-        self.traces.push(TraceConfig { topic, col, color });
+        self.traces.push(TraceConfig {
+            topic,
+            col,
+            color,
+            filter: None,
+            unwrap: None,
+            conversion: None,
+            fill: None,
+            math: TraceMath::default(),
+            unit: None,
+            scale: 1.0,
+            format: None,
+            column_format: ValueFormat::default(),
+            group: None,
+        });
+    }
+
+    pub fn group(&self, name: &str) -> Option<&TraceGroup> {
+        self.groups.iter().find(|g| g.name == name)
+    }
+
+    /// Whether the trace is drawn, i.e. not in a hidden group.
+    pub fn is_visible(&self, trace: &TraceConfig) -> bool {
+        trace
+            .group
+            .as_deref()
+            .and_then(|name| self.group(name))
+            .is_none_or(|group| !group.hidden)
+    }
+
+    /// Puts a trace in the named group, creating the group if needed, or takes it out of
+    /// its group with `None`.
+    pub fn set_trace_group(&mut self, index: usize, group: Option<String>) {
+        let group = group
+            .map(|g| g.trim().to_string())
+            .filter(|g| !g.is_empty());
+        let Some(trace) = self.traces.get_mut(index) else {
+            return;
+        };
+        trace.group = group;
+        self.regroup();
+    }
+
+    /// Moves group members next to each other, at the position of the group's first
+    /// trace, and keeps `groups` in step with the names the traces refer to.
+    pub fn regroup(&mut self) {
+        // Stable order: each group sorts at the position of its first trace.
+        let mut keys: Vec<usize> = Vec::with_capacity(self.traces.len());
+        for (index, trace) in self.traces.iter().enumerate() {
+            let first = trace.group.as_ref().and_then(|name| {
+                self.traces
+                    .iter()
+                    .position(|t| t.group.as_ref() == Some(name))
+            });
+            keys.push(first.unwrap_or(index));
+        }
+        let mut order: Vec<usize> = (0..self.traces.len()).collect();
+        order.sort_by_key(|&index| keys[index]);
+
+        if order.iter().enumerate().any(|(to, &from)| to != from) {
+            let mut traces: Vec<Option<TraceConfig>> = std::mem::take(&mut self.traces)
+                .into_iter()
+                .map(Some)
+                .collect();
+            self.traces = order
+                .iter()
+                .filter_map(|&from| traces[from].take())
+                .collect();
+            self.editing_trace = self
+                .editing_trace
+                .and_then(|editing| order.iter().position(|&from| from == editing));
+        }
+
+        let traces = &self.traces;
+        self.groups.retain(|g| {
+            traces
+                .iter()
+                .any(|t| t.group.as_deref() == Some(g.name.as_str()))
+        });
+        for name in traces.iter().filter_map(|t| t.group.as_ref()) {
+            if self.groups.iter().all(|g| g.name != *name) {
+                self.groups.push(TraceGroup {
+                    name: name.clone(),
+                    hidden: false,
+                    palette: None,
+                });
+            }
+        }
+        self.cached_tooltip_values.clear();
+        self.cached_tooltip_time = f32::NEG_INFINITY;
+    }
+
+    pub fn toggle_group(&mut self, name: &str) {
+        if let Some(group) = self.groups.iter_mut().find(|g| g.name == name) {
+            group.hidden = !group.hidden;
+        }
+    }
+
+    /// Gives the group's traces consecutive colors of `palette`.
+    pub fn recolor_group(&mut self, name: &str, palette: TracePalette) {
+        let Some(group) = self.groups.iter_mut().find(|g| g.name == name) else {
+            return;
+        };
+        group.palette = Some(palette);
+        let colors = palette.colors();
+        let members = self
+            .traces
+            .iter_mut()
+            .filter(|t| t.group.as_deref() == Some(name));
+        for (trace, color) in members.zip(colors.iter().cycle()) {
+            trace.color = *color;
+        }
+    }
+
+    /// Removes the group, keeping its traces.
+    pub fn ungroup(&mut self, name: &str) {
+        for trace in &mut self.traces {
+            if trace.group.as_deref() == Some(name) {
+                trace.group = None;
+            }
+        }
+        self.groups.retain(|g| g.name != name);
+    }
+
+    pub fn group_stats(
+        &self,
+        name: &str,
+        data_store: &DataStore,
+        min_time: f32,
+        max_time: f32,
+    ) -> GroupStats {
+        let members: Vec<&TraceConfig> = self
+            .traces
+            .iter()
+            .filter(|t| t.group.as_deref() == Some(name))
+            .collect();
+        GroupStats {
+            traces: members.len(),
+            range: members
+                .iter()
+                .filter_map(|trace| self.trace_range(trace, data_store, min_time, max_time))
+                .reduce(|a, b| (a.0.min(b.0), a.1.max(b.1))),
+        }
+    }
+
+    /// Points traces whose columns are missing from the loaded data at the same signal as
+    /// logged by another autopilot. Returns how many traces were changed.
+    pub fn translate_aliases(
+        &mut self,
+        profiles: &[AliasProfile],
+        data_store: &DataStore,
+    ) -> usize {
+        let mut changed = 0;
+        for trace in &mut self.traces {
+            if let Some((topic, col)) =
+                aliases::translate(profiles, data_store, &trace.topic, &trace.col)
+            {
+                trace.topic = topic;
+                trace.col = col;
+                changed += 1;
+            }
+        }
+        if let Some(pid) = &mut self.pid {
+            for (topic, col) in [&mut pid.setpoint, &mut pid.measurement] {
+                if let Some(translated) = aliases::translate(profiles, data_store, topic, col) {
+                    (*topic, *col) = translated;
+                    changed += 1;
+                }
+            }
+        }
+        if changed > 0 {
+            self.cached_tooltip_time = f32::NEG_INFINITY;
+            self.cached_tooltip_values.clear();
+        }
+        changed
+    }
+
+    /// Re-reads units and scale factors of all traces, dropping cached values if any changed.
+    pub fn refresh_units(&mut self, data_store: &DataStore) {
+        let mut changed = false;
+        for trace in &mut self.traces {
+            changed |= trace.refresh_units(data_store);
+        }
+
+        if changed {
+            self.cached_tooltip_time = f32::NEG_INFINITY;
+            self.cached_tooltip_values.clear();
+        }
+    }
+
+    /// Unit shared by every trace, used to label the value axis.
+    pub fn common_unit(&self) -> Option<&str> {
+        let first = self.traces.first()?.unit.as_deref()?;
+        self.traces
+            .iter()
+            .all(|t| t.unit.as_deref() == Some(first))
+            .then_some(first)
+    }
+
+    pub fn title(&self) -> String {
+        if self.scene.is_some() {
+            return "3D Scene".to_string();
+        }
+        if let Some(video) = &self.video {
+            return format!("Video ({})", video.file_name());
+        }
+        if let Some(image) = &self.image {
+            return format!("Images ({})", image.col);
+        }
+        if let Some(actuators) = &self.actuators {
+            return format!("Actuators ({})", actuators.topic);
+        }
+        if let Some(pid) = &self.pid {
+            return format!("Tracking ({})", pid.measurement.1);
+        }
+        match &self.map {
+            Some(map) => format!("Map ({})", map.topic),
+            None => format!("Graph ({})", self.trace_count()),
+        }
+    }
+
+    /// Smallest and largest displayed value of all traces between `min_time` and
+    /// `max_time`.
+    pub fn value_range(
+        &self,
+        data_store: &DataStore,
+        min_time: f32,
+        max_time: f32,
+    ) -> Option<(f32, f32)> {
+        self.traces
+            .iter()
+            .filter(|trace| self.is_visible(trace))
+            .filter_map(|trace| self.trace_range(trace, data_store, min_time, max_time))
+            .chain(self.limits.iter().map(|&limit| (limit, limit)))
+            .reduce(|a, b| (a.0.min(b.0), a.1.max(b.1)))
+    }
+
+    /// Displayed value range of one trace, including the compared window if there is one.
+    pub fn trace_range(
+        &self,
+        trace: &TraceConfig,
+        data_store: &DataStore,
+        min_time: f32,
+        max_time: f32,
+    ) -> Option<(f32, f32)> {
+        let range_at = |offset: f32| {
+            let (min_time, max_time) = (min_time + offset, max_time + offset);
+            if self.robust_scale {
+                trace.robust_range(data_store, min_time, max_time)
+            } else {
+                trace.value_range(data_store, min_time, max_time)
+            }
+        };
+        let range = range_at(0.0);
+        let compared = self.compare_offset.and_then(range_at);
+        match (range, compared) {
+            (Some(a), Some(b)) => Some((a.0.min(b.0), a.1.max(b.1))),
+            (a, b) => a.or(b),
+        }
+    }
+
+    /// Padded bounds of the value axis, taken from the tile's Y-sync group if it has one.
+    pub fn y_bounds(
+        &self,
+        data_store: &DataStore,
+        min_time: f32,
+        max_time: f32,
+        y_sync: &YSyncRanges,
+    ) -> (f32, f32) {
+        let range = match self.y_sync_group.and_then(|group| y_sync.get(&group)) {
+            Some(&range) => Some(range),
+            None => self.value_range(data_store, min_time, max_time),
+        };
+        padded_bounds(range)
+    }
+
+    /// Moves a trace to another position. Traces are drawn in order, so the last one is
+    /// on top.
+    pub fn move_trace(&mut self, from: usize, to: usize) {
+        if from >= self.traces.len() || from == to {
+            return;
+        }
+        let trace = self.traces.remove(from);
+        self.traces.insert(to.min(self.traces.len()), trace);
+        self.regroup();
+    }
+
+    pub fn clear_traces(&mut self) {
+        self.traces.clear();
+        self.groups.clear();
+        self.editing_trace = None;
+        self.cached_tooltip_values.clear();
+        self.cached_tooltip_time = f32::NEG_INFINITY;
     }
 
     pub fn _is_empty(&self) -> bool {
@@ -85,82 +815,57 @@ impl PlotTile {
         self.cached_tooltip_time = hover_time;
         self.cached_for_playback = for_playback;
         self.cached_tooltip_values.clear();
+        self.cached_tooltip_samples.clear();
 
         for trace in &self.traces {
-            let value = if let (Some(times), Some(values)) = (
+            // Hidden traces have no value to show.
+            let (value, sample) = if !self.is_visible(trace) {
+                (None, None)
+            } else if let (Some(times), Some(values)) = (
                 data_store.get_column(&trace.topic, "timestamp"),
-                data_store.get_column(&trace.topic, &trace.col),
+                data_store.get_column(&trace.topic, &trace.data_col()),
             ) {
                 if times.is_empty() {
-                    None
+                    (None, None)
+                } else if self.snap_to_samples {
+                    let sample = nearest_sample(times, hover_time).and_then(|index| {
+                        Some(HoverSample {
+                            index,
+                            time: times[index],
+                            raw: *values.get(index)?,
+                        })
+                    });
+                    (sample.map(|s| trace.display_value(s.raw)), sample)
                 } else {
-                    self.interpolate_value(times, values, hover_time)
+                    let value = self
+                        .interpolate_value(times, values, hover_time)
+                        .map(|v| trace.display_value(v));
+                    (value, None)
                 }
             } else {
-                None
+                (None, None)
             };
 
             self.cached_tooltip_values.push(value);
+            self.cached_tooltip_samples.push(sample);
         }
     }
 
+    /// Displayed value of a trace at `time`, interpolated like the tooltip.
+    pub fn trace_value(
+        &self,
+        trace: &TraceConfig,
+        data_store: &DataStore,
+        time: f32,
+    ) -> Option<f32> {
+        let times = data_store.get_column(&trace.topic, "timestamp")?;
+        let values = data_store.get_column(&trace.topic, &trace.data_col())?;
+        self.interpolate_value(times, values, time)
+            .map(|v| trace.display_value(v))
+    }
+
     fn interpolate_value(&self, times: &[f32], values: &[f32], hover_time: f32) -> Option<f32> {
-        match self.interpolation_mode {
-            InterpolationMode::PreviousPoint => {
-                let idx = times.partition_point(|&t| t < hover_time);
-                if idx == 0 {
-                    None
-                } else {
-                    let prev_idx = idx - 1;
-                    if prev_idx < values.len() {
-                        Some(values[prev_idx])
-                    } else {
-                        None
-                    }
-                }
-            }
-            InterpolationMode::NextPoint => {
-                let idx = times.partition_point(|&t| t <= hover_time);
-                if idx >= times.len() {
-                    None
-                } else if idx < values.len() {
-                    Some(values[idx])
-                } else {
-                    None
-                }
-            }
-            InterpolationMode::Linear => {
-                let idx = times.partition_point(|&t| t < hover_time);
-
-                if idx == 0 {
-                    None
-                } else if idx >= times.len() {
-                    if !times.is_empty() && times.len() == values.len() {
-                        Some(values[values.len() - 1])
-                    } else {
-                        None
-                    }
-                } else {
-                    // Between two points - interpolate
-                    let prev_idx = idx - 1;
-                    if prev_idx < values.len() && idx < values.len() {
-                        let t0 = times[prev_idx];
-                        let t1 = times[idx];
-                        let v0 = values[prev_idx];
-                        let v1 = values[idx];
-
-                        if (t1 - t0).abs() < 1e-6 {
-                            Some(v0)
-                        } else {
-                            let t = (hover_time - t0) / (t1 - t0);
-                            Some(v0 + t * (v1 - v0))
-                        }
-                    } else {
-                        None
-                    }
-                }
-            }
-        }
+        self.interpolation_mode.sample(times, values, hover_time)
     }
 }
 
@@ -1,12 +1,29 @@
-use super::PlotTile;
-use crate::core::DataStore;
-use crate::ui::panels::TopicPanelSelection;
-use crate::ui::renderer::RealPlotCallback;
+use super::actuator_tile::render_actuators;
+use super::image_tile::render_image_sequence;
+use super::map_tile::render_map;
+use super::pid_tile::render_pid;
+use super::plot_tile::{MathOp, TraceFill, TraceGroup, TraceMath};
+use super::video_tile::render_video;
+use super::{
+    padded_bounds, ActuatorView, ImageFrameCache, ImageView, LegendCorner, MapSource, MapTileCache,
+    MapView, PidView, PlotTile, VideoFrameCache, VideoView, YSyncRanges, Y_SYNC_GROUPS,
+};
+use crate::core::{AngleUnit, DataStore, SignalFilter, UnitConversion};
+use crate::ui::i18n::tr;
+use crate::ui::palette::TracePalette;
+use crate::ui::panels::tabs::config::VehicleConfig;
+use crate::ui::panels::tabs::gltf_loader::ModelCache;
+use crate::ui::panels::tabs::scene::render_scene_tab;
+use crate::ui::panels::{value_format_editor, FrameProfiler, ProfileSection, TopicPanelSelection};
+use crate::ui::renderer::{FillPlotCallback, RealPlotCallback};
+use crate::ui::sonify::{Sonification, Sonifier, SonifyMode};
 use crate::ui::tiles::render_cursor_tooltip;
-use crate::ui::{calculate_grid_step, get_trace_color};
+use crate::ui::time_format::TimeFormat;
+use crate::ui::{calculate_grid_step, multi_touch};
 use eframe::egui;
 use egui_phosphor::regular as icons;
 use egui_tiles::{Behavior, LinearDir, TileId, UiResponse};
+use std::time::Instant;
 
 pub struct TiPlotBehavior<'a> {
     pub min_time: &'a mut f32,
@@ -17,27 +34,142 @@ pub struct TiPlotBehavior<'a> {
     pub data_store: &'a DataStore,
     pub topic_selection: &'a TopicPanelSelection,
     pub dragged_item: &'a mut Option<(String, String)>,
+    /// Topic/column pairs dropped onto a tile this frame.
+    pub added_traces: &'a mut Vec<(String, String)>,
+    pub focused_tile: &'a mut Option<TileId>,
+    pub map_tiles: &'a mut MapTileCache,
+    pub video_frames: &'a mut VideoFrameCache,
+    pub image_frames: &'a mut ImageFrameCache,
+    pub sonifier: &'a mut Sonifier,
     pub split_request: &'a mut Option<(TileId, LinearDir)>,
+    /// Tile whose traces the user asked to clear, pending confirmation.
+    pub clear_traces_request: &'a mut Option<TileId>,
     pub reset_sizes_request: &'a mut bool,
+    pub arrange_grid_request: &'a mut bool,
     pub is_playing: &'a bool,
     pub always_show_playback_tooltip: &'a bool,
+    pub y_sync: &'a YSyncRanges,
+    /// Hover time of the last frame, drawn in tiles the mouse is not over.
+    pub shared_hover_time: Option<f32>,
+    pub next_hover_time: &'a mut Option<f32>,
+    pub frame: &'a eframe::Frame,
+    pub vehicles: &'a mut [VehicleConfig],
+    pub model_cache: &'a ModelCache,
+    pub time_format: TimeFormat,
+    pub trace_palette: TracePalette,
+    pub profiler: &'a mut FrameProfiler,
+    /// Tile to move into a window of its own; `None` while drawing a detached window.
+    pub detach_request: Option<&'a mut Option<TileId>>,
 }
 
 impl<'a> Behavior<PlotTile> for TiPlotBehavior<'a> {
     fn tab_title_for_pane(&mut self, pane: &PlotTile) -> egui::WidgetText {
-        format!("Graph ({})", pane.trace_count()).into()
+        pane.title().into()
     }
 
     fn pane_ui(&mut self, ui: &mut egui::Ui, tile_id: TileId, tile: &mut PlotTile) -> UiResponse {
-        let rect = ui.available_rect_before_wrap();
+        let start = Instant::now();
+        let tooltip_before = self.profiler.spent(ProfileSection::TooltipCache);
+
+        let (section, response) = if tile.scene.is_some() {
+            (
+                ProfileSection::Scene3D,
+                self.scene_pane_ui(ui, tile_id, tile),
+            )
+        } else if tile.map.is_some() {
+            (
+                ProfileSection::PlotRendering,
+                self.map_pane_ui(ui, tile_id, tile),
+            )
+        } else if tile.video.is_some() {
+            (
+                ProfileSection::PlotRendering,
+                self.video_pane_ui(ui, tile_id, tile),
+            )
+        } else if tile.image.is_some() {
+            (
+                ProfileSection::PlotRendering,
+                self.image_pane_ui(ui, tile_id, tile),
+            )
+        } else if tile.actuators.is_some() {
+            (
+                ProfileSection::PlotRendering,
+                self.actuator_pane_ui(ui, tile_id, tile),
+            )
+        } else if tile.pid.is_some() {
+            (
+                ProfileSection::PlotRendering,
+                self.pid_pane_ui(ui, tile_id, tile),
+            )
+        } else {
+            (
+                ProfileSection::PlotRendering,
+                self.plot_pane_ui(ui, tile_id, tile),
+            )
+        };
 
-        ui.painter()
-            .rect_filled(rect, 0.0, egui::Color32::from_rgb(20, 20, 20));
-        ui.painter().rect_stroke(
-            rect,
-            0.0,
-            egui::Stroke::new(1.0, egui::Color32::from_gray(60)),
+        // Tooltip lookups were already counted on their own.
+        let tooltip = self.profiler.spent(ProfileSection::TooltipCache) - tooltip_before;
+        let label = self.profile_label(tile);
+        self.profiler.record_tile(
+            tile_id,
+            label,
+            section,
+            start.elapsed().saturating_sub(tooltip),
         );
+        response
+    }
+
+    fn is_tab_closable(&self, tiles: &egui_tiles::Tiles<PlotTile>, _tile_id: TileId) -> bool {
+        let pane_count = tiles
+            .tiles()
+            .filter(|tile| matches!(tile, egui_tiles::Tile::Pane(_)))
+            .count();
+
+        pane_count > 1
+    }
+
+    fn tab_bar_color(&self, _visuals: &egui::Visuals) -> egui::Color32 {
+        egui::Color32::from_rgb(30, 30, 30)
+    }
+
+    fn drag_preview_color(&self, _visuals: &egui::Visuals) -> egui::Color32 {
+        egui::Color32::from_rgba_unmultiplied(100, 150, 255, 180)
+    }
+
+    fn retain_pane(&mut self, _pane: &PlotTile) -> bool {
+        true
+    }
+
+    fn simplification_options(&self) -> egui_tiles::SimplificationOptions {
+        egui_tiles::SimplificationOptions {
+            all_panes_must_have_tabs: true,
+            ..Default::default()
+        }
+    }
+}
+
+impl<'a> TiPlotBehavior<'a> {
+    fn plot_pane_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        tile_id: TileId,
+        tile: &mut PlotTile,
+    ) -> UiResponse {
+        let outer = ui.available_rect_before_wrap();
+        let rect = plot_area(outer);
+        tile.refresh_units(self.data_store);
+
+        ui.painter()
+            .rect_filled(outer, 0.0, egui::Color32::from_rgb(20, 20, 20));
+        // The focused tile receives traces added from the topic panel.
+        let border_color = if *self.focused_tile == Some(tile_id) {
+            egui::Color32::from_rgb(70, 120, 200)
+        } else {
+            egui::Color32::from_gray(60)
+        };
+        ui.painter()
+            .rect_stroke(rect, 0.0, egui::Stroke::new(1.0, border_color));
 
         let response = ui.interact(
             rect,
@@ -45,56 +177,367 @@ impl<'a> Behavior<PlotTile> for TiPlotBehavior<'a> {
             egui::Sense::click_and_drag(),
         );
 
+        if response.clicked() || response.secondary_clicked() {
+            *self.focused_tile = Some(tile_id);
+        }
+
         let right_mouse_down = ui.input(|i| i.pointer.secondary_down());
 
         let mut context_menu_showing = false;
 
+        // Time and value where the context menu was opened, for copying.
+        let readout_id = ui.id().with("context_readout");
+        if response.secondary_clicked() {
+            let lanes = self.trace_lanes(rect, tile);
+            let readout = response
+                .interact_pointer_pos()
+                .and_then(|pos| self.readout_at(rect, &lanes, pos));
+            ui.data_mut(|d| d.insert_temp(readout_id, readout));
+        }
+
         response.context_menu(|ui| {
             context_menu_showing = true;
 
+            if let Some(Some((time, value))) =
+                ui.data(|d| d.get_temp::<Option<(String, String)>>(readout_id))
+            {
+                if ui
+                    .button(format!("{} {} {}", icons::COPY, tr("Copy Time"), time))
+                    .clicked()
+                {
+                    ui.ctx().copy_text(time);
+                    ui.close_menu();
+                }
+                if ui
+                    .button(format!("{} {} {}", icons::COPY, tr("Copy Value"), value))
+                    .clicked()
+                {
+                    ui.ctx().copy_text(value);
+                    ui.close_menu();
+                }
+                ui.separator();
+            }
+
             if ui
-                .button(format!("{} Clear All Traces", icons::TRASH))
+                .button(format!("{} {}", icons::TRASH, tr("Clear All Traces")))
                 .clicked()
             {
-                tile.traces.clear();
-                tile.cached_tooltip_values.clear();
-                tile.cached_tooltip_time = f32::NEG_INFINITY;
+                *self.clear_traces_request = Some(tile_id);
                 ui.close_menu();
             }
 
             if !tile.traces.is_empty() {
-                ui.menu_button(format!("{} Remove Trace", icons::MINUS_CIRCLE), |ui| {
-                    let mut trace_to_remove: Option<usize> = None;
+                ui.menu_button(
+                    format!("{} {}", icons::MINUS_CIRCLE, tr("Remove Trace")),
+                    |ui| {
+                        let mut trace_to_remove: Option<usize> = None;
+
+                        for (idx, trace) in tile.traces.iter().enumerate() {
+                            let trace_label = format!("{}/{}", trace.topic, trace.display_name());
+
+                            ui.horizontal(|ui| {
+                                let swatch_size = egui::vec2(10.0, 10.0);
+                                let (swatch_rect, _) =
+                                    ui.allocate_exact_size(swatch_size, egui::Sense::hover());
+                                ui.painter().rect_filled(
+                                    swatch_rect,
+                                    2.0,
+                                    egui::Color32::from_rgb(
+                                        (trace.color[0] * 255.0) as u8,
+                                        (trace.color[1] * 255.0) as u8,
+                                        (trace.color[2] * 255.0) as u8,
+                                    ),
+                                );
 
-                    for (idx, trace) in tile.traces.iter().enumerate() {
-                        let trace_label = format!("{}/{}", trace.topic, trace.col);
+                                if ui.button(&trace_label).clicked() {
+                                    trace_to_remove = Some(idx);
+                                }
+                            });
+                        }
 
-                        ui.horizontal(|ui| {
-                            let swatch_size = egui::vec2(10.0, 10.0);
-                            let (swatch_rect, _) =
-                                ui.allocate_exact_size(swatch_size, egui::Sense::hover());
-                            ui.painter().rect_filled(
-                                swatch_rect,
-                                2.0,
-                                egui::Color32::from_rgb(
-                                    (trace.color[0] * 255.0) as u8,
-                                    (trace.color[1] * 255.0) as u8,
-                                    (trace.color[2] * 255.0) as u8,
-                                ),
-                            );
-
-                            if ui.button(&trace_label).clicked() {
-                                trace_to_remove = Some(idx);
+                        if let Some(idx) = trace_to_remove {
+                            tile.traces.remove(idx);
+                            tile.regroup();
+                            ui.close_menu();
+                        }
+                    },
+                );
+
+                if tile.traces.len() > 1 {
+                    ui.menu_button(format!("{} {}", icons::STACK, tr("Bring to Front")), |ui| {
+                        let top = tile.traces.len() - 1;
+                        let mut trace_to_raise: Option<usize> = None;
+
+                        for (idx, trace) in tile.traces.iter().enumerate() {
+                            let trace_label = format!("{}/{}", trace.topic, trace.display_name());
+                            ui.horizontal(|ui| {
+                                color_swatch(ui, trace.color, 10.0);
+                                if ui
+                                    .add_enabled(idx != top, egui::Button::new(&trace_label))
+                                    .clicked()
+                                {
+                                    trace_to_raise = Some(idx);
+                                }
+                            });
+                        }
+
+                        if let Some(idx) = trace_to_raise {
+                            tile.move_trace(idx, top);
+                            ui.close_menu();
+                        }
+                    });
+                }
+
+                ui.menu_button(format!("{} {}", icons::FUNNEL, tr("Trace Filter")), |ui| {
+                    let mut filters_changed = false;
+
+                    for trace in tile.traces.iter_mut() {
+                        let trace_label = format!("{}/{}", trace.topic, trace.display_name());
+
+                        ui.menu_button(trace_label, |ui| {
+                            if ui
+                                .selectable_label(trace.filter.is_none(), "None")
+                                .clicked()
+                            {
+                                trace.filter = None;
+                                filters_changed = true;
+                            }
+
+                            for default in SignalFilter::DEFAULTS {
+                                let active = trace.filter.is_some_and(|f| f.same_kind(&default));
+                                if ui.selectable_label(active, default.name()).clicked() && !active
+                                {
+                                    trace.filter = Some(default);
+                                    filters_changed = true;
+                                }
+                            }
+
+                            match &mut trace.filter {
+                                Some(SignalFilter::MovingAverage { window })
+                                | Some(SignalFilter::Median { window }) => {
+                                    ui.separator();
+                                    ui.horizontal(|ui| {
+                                        ui.label(tr("Window"));
+                                        if ui
+                                            .add(egui::DragValue::new(window).range(1..=501))
+                                            .changed()
+                                        {
+                                            filters_changed = true;
+                                        }
+                                    });
+                                }
+                                Some(SignalFilter::LowPass { cutoff_hz }) => {
+                                    ui.separator();
+                                    ui.horizontal(|ui| {
+                                        ui.label(tr("Cutoff"));
+                                        if ui
+                                            .add(
+                                                egui::DragValue::new(cutoff_hz)
+                                                    .speed(0.1)
+                                                    .range(0.01..=1000.0)
+                                                    .suffix(" Hz"),
+                                            )
+                                            .changed()
+                                        {
+                                            filters_changed = true;
+                                        }
+                                    });
+                                }
+                                _ => {}
+                            }
+                        });
+                    }
+
+                    if filters_changed {
+                        tile.cached_tooltip_values.clear();
+                        tile.cached_tooltip_time = f32::NEG_INFINITY;
+                    }
+                });
+
+                ui.menu_button(format!("{} {}", icons::RULER, tr("Units")), |ui| {
+                    let mut unwrap_changed = false;
+
+                    for trace in tile.traces.iter_mut() {
+                        let trace_label = format!("{}/{}", trace.topic, trace.display_name());
+                        let source_unit = self
+                            .data_store
+                            .column_meta(&trace.topic, &trace.col)
+                            .map(|m| m.unit.clone())
+                            .unwrap_or_default();
+                        let conversions = UnitConversion::available_for(&source_unit);
+
+                        ui.menu_button(trace_label, |ui| {
+                            let native = if source_unit.is_empty() {
+                                "Native".to_string()
+                            } else {
+                                format!("Native ({})", source_unit)
+                            };
+                            if ui
+                                .selectable_label(trace.conversion.is_none(), native)
+                                .clicked()
+                            {
+                                trace.conversion = None;
+                            }
+
+                            for conversion in conversions {
+                                if ui
+                                    .selectable_label(
+                                        trace.conversion == Some(conversion),
+                                        conversion.label(),
+                                    )
+                                    .clicked()
+                                {
+                                    trace.conversion = Some(conversion);
+                                }
+                            }
+
+                            if source_unit.is_empty() {
+                                ui.label(
+                                    egui::RichText::new(tr(
+                                        "Set a unit in Column Metadata to convert",
+                                    ))
+                                    .small()
+                                    .weak(),
+                                );
+                            }
+
+                            ui.separator();
+                            let mut unwrap = trace.unwrap.is_some();
+                            if ui.checkbox(&mut unwrap, tr("Unwrap angle")).changed() {
+                                trace.unwrap = unwrap.then(|| AngleUnit::from_unit(&source_unit));
+                                unwrap_changed = true;
+                            }
+                            if let Some(unit) = &mut trace.unwrap {
+                                ui.horizontal(|ui| {
+                                    ui.label(tr("Stored in"));
+                                    for choice in [AngleUnit::Radians, AngleUnit::Degrees] {
+                                        unwrap_changed |=
+                                            ui.radio_value(unit, choice, choice.label()).changed();
+                                    }
+                                });
+                                if *unit == AngleUnit::Radians {
+                                    let mut degrees =
+                                        trace.conversion == Some(UnitConversion::RadToDeg);
+                                    if ui.checkbox(&mut degrees, tr("Show in degrees")).changed() {
+                                        trace.conversion =
+                                            degrees.then_some(UnitConversion::RadToDeg);
+                                    }
+                                }
                             }
                         });
                     }
 
-                    if let Some(idx) = trace_to_remove {
-                        tile.traces.remove(idx);
+                    if unwrap_changed {
                         tile.cached_tooltip_values.clear();
                         tile.cached_tooltip_time = f32::NEG_INFINITY;
+                    }
+                });
+
+                ui.menu_button(
+                    format!("{} {}", icons::SLIDERS_HORIZONTAL, tr("Trace Properties")),
+                    |ui| {
+                        for (idx, trace) in tile.traces.iter().enumerate() {
+                            let trace_label = format!("{}/{}", trace.topic, trace.display_name());
+                            let clicked = ui
+                                .horizontal(|ui| {
+                                    color_swatch(ui, trace.color, 10.0);
+                                    ui.button(&trace_label).clicked()
+                                })
+                                .inner;
+                            if clicked {
+                                tile.editing_trace = Some(idx);
+                                ui.close_menu();
+                            }
+                        }
+                    },
+                );
+
+                ui.menu_button(format!("{} {}", icons::SPEAKER_HIGH, tr("Sonify")), |ui| {
+                    if let Some(e) = &self.sonifier.error {
+                        ui.colored_label(ui.visuals().error_fg_color, e);
+                    }
+                    let current = self.sonifier.target().cloned();
+                    if ui.selectable_label(current.is_none(), tr("Off")).clicked() {
+                        self.sonifier.set_target(None);
                         ui.close_menu();
                     }
+                    for trace in &tile.traces {
+                        let trace_label = format!("{}/{}", trace.topic, trace.display_name());
+                        ui.menu_button(trace_label, |ui| {
+                            for mode in SonifyMode::ALL {
+                                let target = Sonification {
+                                    topic: trace.topic.clone(),
+                                    col: trace.col.clone(),
+                                    mode,
+                                };
+                                let selected = current.as_ref() == Some(&target);
+                                if ui.selectable_label(selected, tr(mode.label())).clicked() {
+                                    self.sonifier.set_target(Some(target));
+                                    ui.close_menu();
+                                }
+                            }
+                        });
+                    }
+                })
+                .response
+                .on_hover_text(tr("Play a trace as a tone during playback"));
+
+                ui.menu_button(format!("{} {}", icons::PAINT_BUCKET, tr("Fill")), |ui| {
+                    for trace in tile.traces.iter_mut() {
+                        let trace_label = format!("{}/{}", trace.topic, trace.display_name());
+                        let columns: Vec<String> = self
+                            .data_store
+                            .get_columns(&trace.topic)
+                            .into_iter()
+                            .filter(|col| **col != trace.col)
+                            .cloned()
+                            .collect();
+
+                        ui.menu_button(trace_label, |ui| {
+                            if ui
+                                .selectable_label(trace.fill.is_none(), tr("None"))
+                                .clicked()
+                            {
+                                trace.fill = None;
+                            }
+                            if ui
+                                .selectable_label(
+                                    trace.fill == Some(TraceFill::UnderCurve),
+                                    tr("Under Curve"),
+                                )
+                                .clicked()
+                            {
+                                trace.fill = Some(TraceFill::UnderCurve);
+                            }
+
+                            for (label, plus_minus) in
+                                [("Band to Column", false), ("Band ± Column", true)]
+                            {
+                                ui.menu_button(tr(label), |ui| {
+                                    egui::ScrollArea::vertical()
+                                        .max_height(300.0)
+                                        .show(ui, |ui| {
+                                            for col in &columns {
+                                                let fill = if plus_minus {
+                                                    TraceFill::PlusMinus(col.clone())
+                                                } else {
+                                                    TraceFill::Between(col.clone())
+                                                };
+                                                if ui
+                                                    .selectable_label(
+                                                        trace.fill.as_ref() == Some(&fill),
+                                                        col,
+                                                    )
+                                                    .clicked()
+                                                {
+                                                    trace.fill = Some(fill);
+                                                }
+                                            }
+                                        });
+                                });
+                            }
+                        });
+                    }
                 });
             }
 
@@ -102,8 +545,9 @@ impl<'a> Behavior<PlotTile> for TiPlotBehavior<'a> {
 
             if ui
                 .button(format!(
-                    "{} Split Horizontally",
-                    icons::SQUARE_SPLIT_HORIZONTAL
+                    "{} {}",
+                    icons::SQUARE_SPLIT_HORIZONTAL,
+                    tr("Split Horizontally")
                 ))
                 .clicked()
             {
@@ -112,37 +556,158 @@ impl<'a> Behavior<PlotTile> for TiPlotBehavior<'a> {
             }
 
             if ui
-                .button(format!("{} Split Vertically", icons::SQUARE_SPLIT_VERTICAL))
+                .button(format!(
+                    "{} {}",
+                    icons::SQUARE_SPLIT_VERTICAL,
+                    tr("Split Vertically")
+                ))
                 .clicked()
             {
                 *self.split_request = Some((tile_id, LinearDir::Vertical));
                 ui.close_menu();
             }
 
+            ui.menu_button(
+                format!("{} {}", icons::ARROWS_VERTICAL, tr("Y-Sync Group")),
+                |ui| {
+                    if ui.radio(tile.y_sync_group.is_none(), tr("None")).clicked() {
+                        tile.y_sync_group = None;
+                        ui.close_menu();
+                    }
+                    for group in 1..=Y_SYNC_GROUPS {
+                        let label = format!("{} {}", tr("Group"), group);
+                        if ui.radio(tile.y_sync_group == Some(group), label).clicked() {
+                            tile.y_sync_group = Some(group);
+                            ui.close_menu();
+                        }
+                    }
+                },
+            );
+
+            ui.menu_button(
+                format!("{} {}", icons::LINE_SEGMENT, tr("Limit Lines")),
+                |ui| {
+                    let mut remove = None;
+                    for (idx, limit) in tile.limits.iter_mut().enumerate() {
+                        ui.horizontal(|ui| {
+                            ui.add(egui::DragValue::new(limit).speed(0.01));
+                            if ui.button(icons::TRASH).clicked() {
+                                remove = Some(idx);
+                            }
+                        });
+                    }
+                    if let Some(idx) = remove {
+                        tile.limits.remove(idx);
+                    }
+                    if ui
+                        .button(format!("{} {}", icons::PLUS, tr("Add Limit")))
+                        .clicked()
+                    {
+                        let range =
+                            tile.value_range(self.data_store, *self.min_time, *self.max_time);
+                        tile.limits.push(range.map_or(0.0, |(_, max)| max));
+                    }
+                },
+            );
+
+            ui.menu_button(
+                format!("{} {}", icons::ARROWS_LEFT_RIGHT, tr("Compare Windows")),
+                |ui| {
+                    let mut compare = tile.compare_offset.is_some();
+                    if ui
+                        .checkbox(&mut compare, tr("Overlay a second window"))
+                        .changed()
+                    {
+                        // Start with the window right after the visible one.
+                        tile.compare_offset =
+                            compare.then_some(*self.max_time - *self.min_time);
+                    }
+                    if let Some(offset) = &mut tile.compare_offset {
+                        ui.horizontal(|ui| {
+                            ui.label(tr("Offset"));
+                            let speed = (*self.max_time - *self.min_time) * 0.002;
+                            ui.add(egui::DragValue::new(offset).speed(speed).suffix(" s"));
+                        });
+                        if ui
+                            .button(format!("{} {}", icons::CROSSHAIR, tr("Start at Cursor")))
+                            .on_hover_text(tr(
+                                "Compare the visible window with the one starting at the playback cursor",
+                            ))
+                            .clicked()
+                        {
+                            *offset = *self.current_time - *self.min_time;
+                            ui.close_menu();
+                        }
+                        ui.label(
+                            egui::RichText::new(tr("Ctrl+drag in the plot to align"))
+                                .small()
+                                .weak(),
+                        );
+                    }
+                },
+            );
+
             ui.separator();
 
             if ui
-                .checkbox(&mut tile.show_legend, format!("Show Legend"))
+                .checkbox(&mut tile.show_legend, tr("Show Legend"))
+                .clicked()
+            {
+                ui.close_menu();
+            }
+            if ui
+                .checkbox(&mut tile.show_legend_values, tr("Show Values in Legend"))
+                .on_hover_text(tr("Value of each trace at the playback cursor"))
+                .clicked()
+            {
+                tile.show_legend |= tile.show_legend_values;
+                ui.close_menu();
+            }
+            if ui
+                .checkbox(&mut tile.show_hover_tooltip, tr("Show Tooltip"))
+                .clicked()
+            {
+                ui.close_menu();
+            }
+
+            if ui
+                .checkbox(&mut tile.show_hover_circles, tr("Show Hover Circles"))
+                .clicked()
+            {
+                ui.close_menu();
+            }
+
+            if ui
+                .checkbox(&mut tile.snap_to_samples, tr("Snap to Samples"))
+                .on_hover_text(tr(
+                    "Show the closest logged sample of each trace instead of interpolating",
+                ))
                 .clicked()
             {
+                tile.cached_tooltip_time = f32::NEG_INFINITY;
                 ui.close_menu();
             }
+
             if ui
-                .checkbox(&mut tile.show_hover_tooltip, format!("Show Tooltip"))
+                .checkbox(&mut tile.scatter_mode, tr("Scatter Mode"))
                 .clicked()
             {
                 ui.close_menu();
             }
 
             if ui
-                .checkbox(&mut tile.show_hover_circles, format!("Show Hover Circles"))
+                .checkbox(&mut tile.stacked, tr("Stacked Lanes"))
+                .on_hover_text(tr("Each trace in its own lane with a separate value axis"))
                 .clicked()
             {
                 ui.close_menu();
             }
 
             if ui
-                .checkbox(&mut tile.scatter_mode, "Scatter Mode")
+                .checkbox(&mut tile.robust_scale, tr("Ignore Outliers"))
+                .on_hover_text(tr(
+                    "Fit the value axis to the 1st to 99th percentile of the visible samples",
+                ))
                 .clicked()
             {
                 ui.close_menu();
@@ -151,7 +716,7 @@ impl<'a> Behavior<PlotTile> for TiPlotBehavior<'a> {
             ui.separator();
 
             if ui
-                .button(format!("{} Reset Tile Sizes", icons::ARROWS_OUT))
+                .button(format!("{} {}", icons::ARROWS_OUT, tr("Reset Tile Sizes")))
                 .clicked()
             {
                 *self.reset_sizes_request = true;
@@ -159,7 +724,19 @@ impl<'a> Behavior<PlotTile> for TiPlotBehavior<'a> {
             }
 
             if ui
-                .button(format!("{} Reset View", icons::ARROWS_OUT_LINE_HORIZONTAL))
+                .button(format!("{} {}", icons::GRID_FOUR, tr("Arrange as Grid")))
+                .clicked()
+            {
+                *self.arrange_grid_request = true;
+                ui.close_menu();
+            }
+
+            if ui
+                .button(format!(
+                    "{} {}",
+                    icons::ARROWS_OUT_LINE_HORIZONTAL,
+                    tr("Reset View")
+                ))
                 .clicked()
             {
                 *self.min_time = self.global_min;
@@ -167,9 +744,112 @@ impl<'a> Behavior<PlotTile> for TiPlotBehavior<'a> {
                 ui.close_menu();
             }
 
+            self.detach_button(ui, tile_id);
+
             ui.separator();
 
-            if ui.button(format!("{} Plot Info", icons::INFO)).clicked() {
+            ui.menu_button(
+                format!("{} {}", icons::MAP_TRIFOLD, tr("Show as Map")),
+                |ui| {
+                    let candidates: Vec<MapView> = self
+                        .data_store
+                        .get_topics()
+                        .into_iter()
+                        .filter_map(|topic| MapView::detect(self.data_store, topic))
+                        .collect();
+                    if candidates.is_empty() {
+                        ui.label(tr("No topics with latitude/longitude columns"));
+                    }
+                    for view in candidates {
+                        if ui.button(&view.topic).clicked() {
+                            tile.map = Some(view);
+                            ui.close_menu();
+                        }
+                    }
+                },
+            );
+
+            if ui
+                .button(format!("{} {}", icons::CUBE, tr("Show as 3D Scene")))
+                .clicked()
+            {
+                tile.scene = Some(Box::default());
+                ui.close_menu();
+            }
+
+            ui.menu_button(
+                format!("{} {}", icons::FAN, tr("Show as Actuator Outputs")),
+                |ui| {
+                    let candidates: Vec<ActuatorView> = self
+                        .data_store
+                        .get_topics()
+                        .into_iter()
+                        .filter_map(|topic| ActuatorView::detect(self.data_store, topic))
+                        .collect();
+                    if candidates.is_empty() {
+                        ui.label(tr("No topics with actuator output columns"));
+                    }
+                    for view in candidates {
+                        if ui.button(&view.topic).clicked() {
+                            tile.actuators = Some(view);
+                            ui.close_menu();
+                        }
+                    }
+                },
+            );
+
+            if ui
+                .button(format!("{} {}", icons::FILM_STRIP, tr("Show Video...")))
+                .clicked()
+            {
+                if let Some(path) = rfd::FileDialog::new()
+                    .add_filter("Video", &["mp4", "mkv", "mov", "avi", "webm"])
+                    .pick_file()
+                {
+                    tile.video = Some(VideoView::new(path, self.global_min));
+                }
+                ui.close_menu();
+            }
+
+            ui.menu_button(
+                format!("{} {}", icons::IMAGES, tr("Show as Image Sequence")),
+                |ui| {
+                    let columns = self.data_store.image_columns();
+                    if columns.is_empty() {
+                        ui.label(tr("No topics with image columns"));
+                    }
+                    for (topic, col) in columns {
+                        if ui.button(format!("{}/{}", topic, col)).clicked() {
+                            tile.image = Some(ImageView::new(topic.clone(), col.clone()));
+                            ui.close_menu();
+                        }
+                    }
+                },
+            );
+
+            let tracking = ui
+                .add_enabled(
+                    tile.traces.len() >= 2,
+                    egui::Button::new(format!(
+                        "{} {}",
+                        icons::TARGET,
+                        tr("Show as Tracking Analysis")
+                    )),
+                )
+                .on_hover_text(tr("First trace is the setpoint, second the measurement"));
+            if tracking.clicked() {
+                let (setpoint, measurement) = (&tile.traces[0], &tile.traces[1]);
+                tile.pid = Some(PidView::new(
+                    (setpoint.topic.clone(), setpoint.col.clone()),
+                    (measurement.topic.clone(), measurement.col.clone()),
+                ));
+                ui.close_menu();
+            }
+
+            if ui
+                .button(format!("{} {}", icons::INFO, tr("Plot Info")))
+                .clicked()
+            {
                 tile.show_info_window = true;
                 ui.close_menu();
             }
@@ -187,76 +867,40 @@ impl<'a> Behavior<PlotTile> for TiPlotBehavior<'a> {
             ui.output_mut(|o| o.cursor_icon = egui::CursorIcon::PointingHand);
         }
 
-        if response.dragged() && !modifiers.alt {
-            let delta = response.drag_delta();
+        let aligning = modifiers.command && tile.compare_offset.is_some();
+        if response.dragged() && aligning {
+            let width = rect.width();
+            if let (Some(offset), true) = (&mut tile.compare_offset, width > 0.0) {
+                *offset -= response.drag_delta().x * (*self.max_time - *self.min_time) / width;
+            }
+        } else if let Some(touch) = multi_touch(ui, rect) {
+            // Pinching zooms the time axis around the fingers, moving them together pans.
+            let width = rect.width();
+            if width > 0.0 {
+                let anchor = (touch.start_pos.x - rect.left()) / width;
+                self.zoom_time(anchor, 1.0 / touch.zoom_delta_2d.x.max(0.01));
+                self.pan_time(
+                    -touch.translation_delta.x * (*self.max_time - *self.min_time) / width,
+                );
+            }
+        } else if response.dragged() && !modifiers.alt {
             let width = rect.width();
             if width > 0.0 {
                 let view_width = *self.max_time - *self.min_time;
-                let dt = -delta.x * (view_width / width);
-
-                let mut new_min = *self.min_time + dt;
-                let mut new_max = *self.max_time + dt;
-
-                if new_min < self.global_min {
-                    let offset = self.global_min - new_min;
-                    new_min = self.global_min;
-                    new_max += offset;
-                }
-                if new_max > self.global_max {
-                    let offset = new_max - self.global_max;
-                    new_max = self.global_max;
-                    new_min -= offset;
-                }
-
-                new_min = new_min.max(self.global_min);
-                new_max = new_max.min(self.global_max);
-
-                *self.min_time = new_min;
-                *self.max_time = new_max;
+                self.pan_time(-response.drag_delta().x * (view_width / width));
             }
         }
 
-        if response.hovered() {
+        let over_legend = tile
+            .legend_rect
+            .zip(response.hover_pos())
+            .is_some_and(|(legend, pointer)| legend.contains(pointer));
+        if response.hovered() && !over_legend {
             let scroll = ui.input(|i| i.smooth_scroll_delta.y);
             if scroll != 0.0 {
-                let factor = 1.0 - (scroll * 0.01);
-
                 if let Some(pointer_pos) = response.hover_pos() {
-                    let t = (pointer_pos.x - rect.left()) / rect.width();
-                    let center = *self.min_time + t * (*self.max_time - *self.min_time);
-
-                    let span = *self.max_time - *self.min_time;
-                    let new_span = span * factor;
-
-                    let mut new_min = center - new_span * t;
-                    let mut new_max = center + new_span * (1.0 - t);
-
-                    let global_span = self.global_max - self.global_min;
-                    if new_max - new_min > global_span {
-                        new_min = self.global_min;
-                        new_max = self.global_max;
-                    } else {
-                        if new_min < self.global_min {
-                            let offset = self.global_min - new_min;
-                            new_min = self.global_min;
-                            new_max += offset;
-                        }
-                        if new_max > self.global_max {
-                            let offset = new_max - self.global_max;
-                            new_max = self.global_max;
-                            new_min -= offset;
-                        }
-                        new_min = new_min.max(self.global_min);
-                        new_max = new_max.min(self.global_max);
-                    }
-
-                    let min_sample_interval = self.estimate_min_sample_interval();
-                    let min_span = min_sample_interval * 2.0;
-
-                    if new_max - new_min >= min_span {
-                        *self.min_time = new_min;
-                        *self.max_time = new_max;
-                    }
+                    let anchor = (pointer_pos.x - rect.left()) / rect.width();
+                    self.zoom_time(anchor, 1.0 - (scroll * 0.01));
                 }
             }
         }
@@ -266,6 +910,7 @@ impl<'a> Behavior<PlotTile> for TiPlotBehavior<'a> {
                 .rect_stroke(rect, 0.0, egui::Stroke::new(2.0, egui::Color32::GOLD));
             if ui.input(|i| i.pointer.any_released()) {
                 if let Some((topic, col)) = self.dragged_item.take() {
+                    *self.focused_tile = Some(tile_id);
                     if self
                         .topic_selection
                         .selected
@@ -285,13 +930,15 @@ impl<'a> Behavior<PlotTile> for TiPlotBehavior<'a> {
                                 .iter()
                                 .any(|t| t.topic == sel_topic && t.col == sel_col)
                             {
-                                let color = get_trace_color(tile.traces.len());
+                                let color = self.trace_palette.next_color(&tile.trace_colors());
+                                self.added_traces.push((sel_topic.clone(), sel_col.clone()));
                                 tile.add_trace(sel_topic, sel_col, color);
                             }
                         }
                     } else {
                         if !tile.traces.iter().any(|t| t.topic == topic && t.col == col) {
-                            let color = get_trace_color(tile.traces.len());
+                            let color = self.trace_palette.next_color(&tile.trace_colors());
+                            self.added_traces.push((topic.clone(), col.clone()));
                             tile.add_trace(topic, col, color);
                         }
                     }
@@ -299,24 +946,127 @@ impl<'a> Behavior<PlotTile> for TiPlotBehavior<'a> {
             }
         }
 
-        let (min_y, max_y) = self.calculate_y_bounds(tile);
-
-        self.draw_grid(ui, rect, min_y, max_y);
-
-        for trace in &tile.traces {
+        let lanes = self.trace_lanes(rect, tile);
+        self.draw_time_axis(ui, rect);
+        if tile.stacked && tile.traces.len() > 1 {
+            for (lane, trace) in lanes.iter().zip(&tile.traces) {
+                if !tile.is_visible(trace) {
+                    continue;
+                }
+                self.draw_value_axis(ui, lane);
+                let title = match &trace.unit {
+                    Some(unit) => format!("{} [{}]", trace.display_name(), unit),
+                    None => trace.display_name(),
+                };
+                draw_axis_title(ui, outer, lane.rect, &title, color32(trace.color));
+                if lane.rect.max.y < rect.max.y {
+                    ui.painter().hline(
+                        rect.x_range(),
+                        lane.rect.max.y,
+                        egui::Stroke::new(1.0, egui::Color32::from_gray(70)),
+                    );
+                }
+            }
+        } else if let Some(lane) = lanes.first() {
+            self.draw_value_axis(ui, lane);
+            if let Some(title) = value_axis_title(tile) {
+                draw_axis_title(ui, outer, rect, &title, egui::Color32::from_gray(170));
+            }
+        }
+
+        if let Some(group) = tile.y_sync_group {
+            ui.painter().text(
+                rect.left_bottom() + egui::vec2(4.0, -4.0),
+                egui::Align2::LEFT_BOTTOM,
+                format!("{} {}", icons::ARROWS_VERTICAL, group),
+                egui::FontId::proportional(10.0),
+                egui::Color32::from_gray(150),
+            );
+        }
+
+        if let Some(offset) = tile.compare_offset {
+            ui.painter().text(
+                rect.right_bottom() + egui::vec2(-4.0, -4.0),
+                egui::Align2::RIGHT_BOTTOM,
+                format!("{} B = A {:+.2} s", icons::ARROWS_LEFT_RIGHT, offset),
+                egui::FontId::proportional(10.0),
+                egui::Color32::from_gray(150),
+            );
+
+            // The compared window is drawn fainter and underneath the visible one.
+            for (lane, trace) in lanes.iter().zip(&tile.traces) {
+                if !tile.is_visible(trace) {
+                    continue;
+                }
+                let [r, g, b, a] = trace.color;
+                let cb = eframe::egui_wgpu::Callback::new_paint_callback(
+                    lane.rect,
+                    RealPlotCallback {
+                        topic: trace.topic.clone(),
+                        col: trace.data_col(),
+                        bounds: [
+                            *self.min_time + offset,
+                            *self.max_time + offset,
+                            lane.min_y,
+                            lane.max_y,
+                        ],
+                        color: [r, g, b, a * 0.4],
+                        scatter_mode: tile.scatter_mode,
+                        scale: trace.scale,
+                        math: trace.math,
+                    },
+                );
+                ui.painter().add(cb);
+            }
+        }
+
+        for (lane, trace) in lanes.iter().zip(&tile.traces) {
+            if !tile.is_visible(trace) {
+                continue;
+            }
+            let bounds = [*self.min_time, *self.max_time, lane.min_y, lane.max_y];
+            if let Some(fill) = &trace.fill {
+                let [r, g, b, a] = trace.color;
+                ui.painter()
+                    .add(eframe::egui_wgpu::Callback::new_paint_callback(
+                        lane.rect,
+                        FillPlotCallback {
+                            topic: trace.topic.clone(),
+                            col: trace.data_col(),
+                            fill: fill.clone(),
+                            bounds,
+                            color: [r, g, b, a * 0.3],
+                            scale: trace.scale,
+                            math: trace.math,
+                        },
+                    ));
+            }
             let cb = eframe::egui_wgpu::Callback::new_paint_callback(
-                rect,
+                lane.rect,
                 RealPlotCallback {
                     topic: trace.topic.clone(),
-                    col: trace.col.clone(),
-                    bounds: [*self.min_time, *self.max_time, min_y, max_y],
+                    col: trace.data_col(),
+                    bounds,
                     color: trace.color,
                     scatter_mode: tile.scatter_mode,
+                    scale: trace.scale,
+                    math: trace.math,
                 },
             );
             ui.painter().add(cb);
         }
 
+        if !tile.limits.is_empty() {
+            let lanes = if tile.stacked {
+                &lanes[..]
+            } else {
+                &lanes[..1]
+            };
+            for lane in lanes.iter().filter(|lane| lane.rect.height() > 0.0) {
+                draw_limits(ui, lane, &tile.limits);
+            }
+        }
+
         if *self.current_time >= *self.min_time && *self.current_time <= *self.max_time {
             let time_span = *self.max_time - *self.min_time;
             if time_span > 0.0 {
@@ -334,12 +1084,12 @@ impl<'a> Behavior<PlotTile> for TiPlotBehavior<'a> {
         }
 
         if *self.always_show_playback_tooltip || modifiers.alt {
-            self.handle_playback_cursor(ui, rect, tile, min_y, max_y);
+            self.handle_playback_cursor(ui, rect, tile, &lanes);
         } else if !context_menu_showing {
             if *self.is_playing {
-                self.handle_playback_cursor(ui, rect, tile, min_y, max_y);
+                self.handle_playback_cursor(ui, rect, tile, &lanes);
             } else if !right_mouse_down {
-                self.handle_cursor(ui, rect, tile, min_y, max_y);
+                self.handle_cursor(ui, rect, tile, &lanes);
             }
         }
 
@@ -353,203 +1103,939 @@ impl<'a> Behavior<PlotTile> for TiPlotBehavior<'a> {
                 .max_height(600.0)
                 .show(ui.ctx(), |ui| {
                     ui.label(format!("Total: {} trace(s)", tile.traces.len()));
-                    if tile.traces.len() > 0 {
+                    if !tile.traces.is_empty() {
                         ui.separator();
                     }
 
+                    if tile.traces.len() > 1 {
+                        ui.weak(tr("Drag to reorder, the last trace is drawn on top"));
+                    }
+
+                    let mut trace_move: Option<(usize, usize)> = None;
                     egui::ScrollArea::vertical()
                         .max_height(500.0)
                         .show(ui, |ui| {
                             for (idx, trace) in tile.traces.iter().enumerate() {
-                                ui.horizontal(|ui| {
-                                    let swatch_size = egui::vec2(12.0, 12.0);
-                                    let (swatch_rect, _) =
-                                        ui.allocate_exact_size(swatch_size, egui::Sense::hover());
-                                    ui.painter().rect_filled(
-                                        swatch_rect,
-                                        2.0,
-                                        egui::Color32::from_rgb(
-                                            (trace.color[0] * 255.0) as u8,
-                                            (trace.color[1] * 255.0) as u8,
-                                            (trace.color[2] * 255.0) as u8,
-                                        ),
+                                let row_id = egui::Id::new(("plot_info_trace", tile_id, idx));
+                                let row = ui
+                                    .dnd_drag_source(row_id, idx, |ui| {
+                                        ui.horizontal(|ui| {
+                                            ui.label(icons::DOTS_SIX_VERTICAL);
+                                            color_swatch(ui, trace.color, 12.0);
+                                            ui.label(format!(
+                                                "{} / {}",
+                                                trace.topic,
+                                                trace.display_name()
+                                            ));
+                                        });
+                                    })
+                                    .response;
+
+                                if row.dnd_hover_payload::<usize>().is_some() {
+                                    let y = row.rect.center().y;
+                                    ui.painter().hline(
+                                        row.rect.x_range(),
+                                        y,
+                                        egui::Stroke::new(1.0, ui.visuals().selection.bg_fill),
                                     );
-
-                                    ui.label(format!("{} / {}", trace.topic, trace.col));
-                                });
+                                }
+                                if let Some(from) = row.dnd_release_payload::<usize>() {
+                                    trace_move = Some((*from, idx));
+                                }
 
                                 if idx < tile.traces.len() - 1 {
                                     ui.add_space(4.0);
                                 }
                             }
                         });
+                    if let Some((from, to)) = trace_move {
+                        tile.move_trace(from, to);
+                    }
 
-                    if tile.traces.len() > 0 {
+                    if !tile.traces.is_empty() {
                         ui.separator();
                     }
                     ui.horizontal(|ui| {
-                        if ui.button("Close").clicked() {
+                        if ui.button(tr("Close")).clicked() {
                             tile.show_info_window = false;
                         }
                     });
                 });
         }
 
+        if let Some(idx) = tile.editing_trace {
+            self.trace_properties_window(ui, tile_id, tile, idx);
+        }
+
         UiResponse::None
     }
 
-    fn is_tab_closable(&self, tiles: &egui_tiles::Tiles<PlotTile>, _tile_id: TileId) -> bool {
-        let pane_count = tiles
-            .tiles()
-            .filter(|tile| matches!(tile, egui_tiles::Tile::Pane(_)))
-            .count();
+    fn trace_properties_window(
+        &mut self,
+        ui: &mut egui::Ui,
+        tile_id: TileId,
+        tile: &mut PlotTile,
+        idx: usize,
+    ) {
+        let Some(trace) = tile.traces.get_mut(idx) else {
+            tile.editing_trace = None;
+            return;
+        };
 
-        pane_count > 1
+        let before = trace.math;
+        let groups = &tile.groups;
+        let mut regroup: Option<Option<String>> = None;
+        let mut open = true;
+        egui::Window::new(tr("Trace Properties"))
+            .id(egui::Id::new(("trace_properties", tile_id)))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ui.ctx(), |ui| {
+                ui.horizontal(|ui| {
+                    color_swatch(ui, trace.color, 12.0);
+                    ui.strong(format!("{}/{}", trace.topic, trace.col));
+                });
+                ui.separator();
+
+                egui::Grid::new(("trace_properties_grid", tile_id))
+                    .num_columns(2)
+                    .spacing([20.0, 6.0])
+                    .show(ui, |ui| {
+                        ui.label(tr("Color"));
+                        ui.color_edit_button_rgba_unmultiplied(&mut trace.color);
+                        ui.end_row();
+
+                        ui.label(tr("Operation"));
+                        egui::ComboBox::from_id_salt(("trace_math_op", tile_id))
+                            .selected_text(trace.math.op.label())
+                            .show_ui(ui, |ui| {
+                                for op in MathOp::ALL {
+                                    ui.selectable_value(&mut trace.math.op, op, op.label());
+                                }
+                            });
+                        ui.end_row();
+
+                        ui.label(tr("Gain"));
+                        ui.add(egui::DragValue::new(&mut trace.math.gain).speed(0.01));
+                        ui.end_row();
+
+                        ui.label(tr("Offset"));
+                        ui.add(egui::DragValue::new(&mut trace.math.offset).speed(0.01));
+                        ui.end_row();
+
+                        ui.label(tr("Group"));
+                        ui.horizontal(|ui| {
+                            egui::ComboBox::from_id_salt(("trace_group", tile_id))
+                                .selected_text(trace.group.as_deref().unwrap_or(tr("None")))
+                                .show_ui(ui, |ui| {
+                                    if ui
+                                        .selectable_label(trace.group.is_none(), tr("None"))
+                                        .clicked()
+                                    {
+                                        regroup = Some(None);
+                                    }
+                                    for group in groups {
+                                        let selected = trace.group.as_ref() == Some(&group.name);
+                                        if ui.selectable_label(selected, &group.name).clicked() {
+                                            regroup = Some(Some(group.name.clone()));
+                                        }
+                                    }
+                                });
+                            if let Some(name) =
+                                new_group_field(ui, egui::Id::new(("new_group", tile_id)))
+                            {
+                                regroup = Some(Some(name));
+                            }
+                        });
+                        ui.end_row();
+                    });
+
+                ui.label(
+                    egui::RichText::new("y = gain · op(x) + offset")
+                        .small()
+                        .weak(),
+                );
+                ui.separator();
+                if ui
+                    .add_enabled(!trace.math.is_identity(), egui::Button::new(tr("Reset")))
+                    .clicked()
+                {
+                    trace.math = TraceMath::default();
+                }
+
+                ui.separator();
+                let mut custom = trace.format.is_some();
+                if ui
+                    .checkbox(&mut custom, tr("Custom Value Format"))
+                    .on_hover_text(tr(
+                        "Format this trace's values instead of using the column's format",
+                    ))
+                    .changed()
+                {
+                    trace.format = custom.then(|| trace.column_format.clone());
+                }
+                if let Some(format) = &mut trace.format {
+                    value_format_editor(ui, egui::Id::new(("trace_format", tile_id)), format);
+                }
+            });
+
+        if trace.math != before {
+            tile.cached_tooltip_values.clear();
+            tile.cached_tooltip_time = f32::NEG_INFINITY;
+        }
+        if let Some(group) = regroup {
+            tile.set_trace_group(idx, group);
+        }
+        if !open {
+            tile.editing_trace = None;
+        }
     }
 
-    fn tab_bar_color(&self, _visuals: &egui::Visuals) -> egui::Color32 {
-        egui::Color32::from_rgb(30, 30, 30)
+    fn map_pane_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        tile_id: TileId,
+        tile: &mut PlotTile,
+    ) -> UiResponse {
+        let rect = ui.available_rect_before_wrap();
+        let response = ui.interact(
+            rect,
+            ui.id().with("map_interaction"),
+            egui::Sense::click_and_drag(),
+        );
+
+        if response.clicked() || response.secondary_clicked() {
+            *self.focused_tile = Some(tile_id);
+        }
+
+        let Some(view) = tile.map.as_mut() else {
+            return UiResponse::None;
+        };
+
+        if let Some(time) = render_map(
+            ui,
+            rect,
+            &response,
+            view,
+            self.data_store,
+            self.map_tiles,
+            *self.current_time,
+        ) {
+            *self.current_time = time;
+        }
+
+        let border_color = if *self.focused_tile == Some(tile_id) {
+            egui::Color32::from_rgb(70, 120, 200)
+        } else {
+            egui::Color32::from_gray(60)
+        };
+        ui.painter()
+            .rect_stroke(rect, 0.0, egui::Stroke::new(1.0, border_color));
+
+        let mut show_plot = false;
+        response.context_menu(|ui| {
+            for source in MapSource::ALL {
+                if ui.radio(view.source == source, source.label()).clicked() {
+                    view.source = source;
+                    ui.close_menu();
+                }
+            }
+
+            ui.separator();
+
+            if ui
+                .checkbox(&mut view.follow, tr("Follow Vehicle"))
+                .clicked()
+            {
+                ui.close_menu();
+            }
+
+            let mut offline = self.map_tiles.is_offline();
+            if ui
+                .checkbox(&mut offline, tr("Offline (cached tiles only)"))
+                .clicked()
+            {
+                self.map_tiles.set_offline(offline);
+                ui.close_menu();
+            }
+
+            if ui
+                .button(format!("{} {}", icons::ARROWS_OUT, tr("Fit Trajectory")))
+                .clicked()
+            {
+                view.center = None;
+                view.follow = false;
+                ui.close_menu();
+            }
+
+            ui.separator();
+
+            if ui
+                .button(format!("{} {}", icons::CHART_LINE, tr("Show as Plot")))
+                .clicked()
+            {
+                show_plot = true;
+                ui.close_menu();
+            }
+
+            self.detach_button(ui, tile_id);
+        });
+
+        if show_plot {
+            tile.map = None;
+        }
+
+        UiResponse::None
+    }
+
+    fn actuator_pane_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        tile_id: TileId,
+        tile: &mut PlotTile,
+    ) -> UiResponse {
+        let rect = ui.available_rect_before_wrap();
+        let response = ui.interact(
+            rect,
+            ui.id().with("actuator_interaction"),
+            egui::Sense::click_and_drag(),
+        );
+
+        if response.clicked() || response.secondary_clicked() {
+            *self.focused_tile = Some(tile_id);
+        }
+
+        let Some(view) = tile.actuators.as_mut() else {
+            return UiResponse::None;
+        };
+
+        if let Some(time) = render_actuators(
+            ui,
+            rect,
+            &response,
+            view,
+            self.data_store,
+            self.trace_palette,
+            *self.min_time,
+            *self.max_time,
+            *self.current_time,
+        ) {
+            *self.current_time = time;
+        }
+
+        let border_color = if *self.focused_tile == Some(tile_id) {
+            egui::Color32::from_rgb(70, 120, 200)
+        } else {
+            egui::Color32::from_gray(60)
+        };
+        ui.painter()
+            .rect_stroke(rect, 0.0, egui::Stroke::new(1.0, border_color));
+
+        let mut show_plot = false;
+        response.context_menu(|ui| {
+            ui.label(tr("Output Range"));
+            ui.horizontal(|ui| {
+                let speed = (view.max - view.min).abs().max(1.0) * 0.005;
+                ui.add(egui::DragValue::new(&mut view.min).speed(speed));
+                ui.label("–");
+                ui.add(egui::DragValue::new(&mut view.max).speed(speed));
+            });
+
+            ui.separator();
+
+            if ui
+                .button(format!("{} {}", icons::CHART_LINE, tr("Show as Plot")))
+                .clicked()
+            {
+                show_plot = true;
+                ui.close_menu();
+            }
+
+            self.detach_button(ui, tile_id);
+        });
+
+        if show_plot {
+            tile.actuators = None;
+        }
+
+        UiResponse::None
+    }
+
+    fn video_pane_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        tile_id: TileId,
+        tile: &mut PlotTile,
+    ) -> UiResponse {
+        let rect = ui.available_rect_before_wrap();
+        let response = ui.interact(
+            rect,
+            ui.id().with("video_interaction"),
+            egui::Sense::click_and_drag(),
+        );
+
+        if response.clicked() || response.secondary_clicked() {
+            *self.focused_tile = Some(tile_id);
+        }
+
+        let Some(view) = tile.video.as_mut() else {
+            return UiResponse::None;
+        };
+
+        render_video(ui, rect, view, self.video_frames, *self.current_time);
+
+        let border_color = if *self.focused_tile == Some(tile_id) {
+            egui::Color32::from_rgb(70, 120, 200)
+        } else {
+            egui::Color32::from_gray(60)
+        };
+        ui.painter()
+            .rect_stroke(rect, 0.0, egui::Stroke::new(1.0, border_color));
+
+        let mut show_plot = false;
+        response.context_menu(|ui| {
+            ui.label(tr("Video Start"));
+            ui.add(
+                egui::DragValue::new(&mut view.offset)
+                    .speed(0.01)
+                    .suffix(" s"),
+            )
+            .on_hover_text(tr("Timeline time at which the video starts"));
+            if ui
+                .button(format!("{} {}", icons::CLOCK, tr("Start Video at Cursor")))
+                .clicked()
+            {
+                view.offset = *self.current_time;
+                ui.close_menu();
+            }
+
+            ui.separator();
+
+            if ui
+                .button(format!("{} {}", icons::ARROW_CLOCKWISE, tr("Reload Video")))
+                .clicked()
+            {
+                self.video_frames.reload(&view.path);
+                ui.close_menu();
+            }
+
+            if ui
+                .button(format!("{} {}", icons::CHART_LINE, tr("Show as Plot")))
+                .clicked()
+            {
+                show_plot = true;
+                ui.close_menu();
+            }
+
+            self.detach_button(ui, tile_id);
+        });
+
+        if show_plot {
+            tile.video = None;
+        }
+
+        UiResponse::None
+    }
+
+    fn image_pane_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        tile_id: TileId,
+        tile: &mut PlotTile,
+    ) -> UiResponse {
+        let rect = ui.available_rect_before_wrap();
+        let response = ui.interact(
+            rect,
+            ui.id().with("image_interaction"),
+            egui::Sense::click_and_drag(),
+        );
+
+        if response.clicked() || response.secondary_clicked() {
+            *self.focused_tile = Some(tile_id);
+        }
+
+        let Some(view) = tile.image.as_mut() else {
+            return UiResponse::None;
+        };
+
+        render_image_sequence(
+            ui,
+            rect,
+            view,
+            self.data_store,
+            self.image_frames,
+            *self.current_time,
+        );
+
+        let border_color = if *self.focused_tile == Some(tile_id) {
+            egui::Color32::from_rgb(70, 120, 200)
+        } else {
+            egui::Color32::from_gray(60)
+        };
+        ui.painter()
+            .rect_stroke(rect, 0.0, egui::Stroke::new(1.0, border_color));
+
+        let mut show_plot = false;
+        response.context_menu(|ui| {
+            ui.horizontal(|ui| {
+                for (icon, label, step) in [
+                    (icons::CARET_LEFT, "Previous Frame", -1),
+                    (icons::CARET_RIGHT, "Next Frame", 1),
+                ] {
+                    if ui.button(icon).on_hover_text(tr(label)).clicked() {
+                        if let Some(time) = view.step(self.data_store, *self.current_time, step) {
+                            *self.current_time = time;
+                        }
+                    }
+                }
+            });
+
+            ui.separator();
+
+            if ui
+                .button(format!(
+                    "{} {}",
+                    icons::FOLDER_OPEN,
+                    tr("Set Image Folder...")
+                ))
+                .on_hover_text(tr("Folder that relative image paths are resolved against"))
+                .clicked()
+            {
+                if let Some(folder) = rfd::FileDialog::new().pick_folder() {
+                    view.folder = Some(folder);
+                    self.image_frames.clear();
+                }
+                ui.close_menu();
+            }
+
+            if ui
+                .button(format!("{} {}", icons::CHART_LINE, tr("Show as Plot")))
+                .clicked()
+            {
+                show_plot = true;
+                ui.close_menu();
+            }
+
+            self.detach_button(ui, tile_id);
+        });
+
+        if show_plot {
+            tile.image = None;
+        }
+
+        UiResponse::None
+    }
+
+    fn pid_pane_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        tile_id: TileId,
+        tile: &mut PlotTile,
+    ) -> UiResponse {
+        let rect = ui.available_rect_before_wrap();
+        let response = ui.interact(
+            rect,
+            ui.id().with("pid_interaction"),
+            egui::Sense::click_and_drag(),
+        );
+
+        if response.clicked() || response.secondary_clicked() {
+            *self.focused_tile = Some(tile_id);
+        }
+
+        let Some(view) = tile.pid.as_mut() else {
+            return UiResponse::None;
+        };
+
+        if let Some(time) = render_pid(
+            ui,
+            rect,
+            &response,
+            view,
+            self.data_store,
+            *self.min_time,
+            *self.max_time,
+            *self.current_time,
+        ) {
+            *self.current_time = time;
+        }
+
+        let border_color = if *self.focused_tile == Some(tile_id) {
+            egui::Color32::from_rgb(70, 120, 200)
+        } else {
+            egui::Color32::from_gray(60)
+        };
+        ui.painter()
+            .rect_stroke(rect, 0.0, egui::Stroke::new(1.0, border_color));
+
+        let mut show_plot = false;
+        response.context_menu(|ui| {
+            ui.horizontal(|ui| {
+                ui.label(tr("Step Threshold"));
+                ui.add(
+                    egui::DragValue::new(&mut view.step_threshold)
+                        .speed(0.01)
+                        .range(0.01..=1.0),
+                )
+                .on_hover_text(tr("Fraction of the visible setpoint range"));
+            });
+            ui.horizontal(|ui| {
+                ui.label(tr("Settling Band"));
+                ui.add(
+                    egui::DragValue::new(&mut view.settle_band)
+                        .speed(0.005)
+                        .range(0.005..=0.5),
+                )
+                .on_hover_text(tr("Fraction of the step size"));
+            });
+
+            if ui
+                .button(format!(
+                    "{} {}",
+                    icons::ARROWS_DOWN_UP,
+                    tr("Swap Setpoint and Measurement")
+                ))
+                .clicked()
+            {
+                std::mem::swap(&mut view.setpoint, &mut view.measurement);
+                ui.close_menu();
+            }
+
+            ui.separator();
+
+            if ui
+                .button(format!("{} {}", icons::CHART_LINE, tr("Show as Plot")))
+                .clicked()
+            {
+                show_plot = true;
+                ui.close_menu();
+            }
+
+            self.detach_button(ui, tile_id);
+        });
+
+        if show_plot {
+            tile.pid = None;
+        }
+
+        UiResponse::None
+    }
+
+    fn scene_pane_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        tile_id: TileId,
+        tile: &mut PlotTile,
+    ) -> UiResponse {
+        let Some(scene) = tile.scene.as_mut() else {
+            return UiResponse::None;
+        };
+
+        let rect = ui.available_rect_before_wrap();
+        let response = render_scene_tab(
+            ui,
+            self.frame,
+            self.vehicles,
+            self.data_store,
+            *self.current_time,
+            scene,
+            self.model_cache,
+        );
+        if let Some(time) = response.inner {
+            *self.current_time = time;
+        }
+
+        let response = response.response;
+        if response.clicked() || response.secondary_clicked() {
+            *self.focused_tile = Some(tile_id);
+        }
+
+        let border_color = if *self.focused_tile == Some(tile_id) {
+            egui::Color32::from_rgb(70, 120, 200)
+        } else {
+            egui::Color32::from_gray(60)
+        };
+        ui.painter()
+            .rect_stroke(rect, 0.0, egui::Stroke::new(1.0, border_color));
+
+        let mut show_plot = false;
+        response.context_menu(|ui| {
+            if ui
+                .button(format!("{} {}", icons::CHART_LINE, tr("Show as Plot")))
+                .clicked()
+            {
+                show_plot = true;
+                ui.close_menu();
+            }
+
+            self.detach_button(ui, tile_id);
+        });
+
+        if show_plot {
+            tile.scene = None;
+        }
+
+        UiResponse::None
+    }
+
+    fn detach_button(&mut self, ui: &mut egui::Ui, tile_id: TileId) {
+        let Some(detach_request) = self.detach_request.as_mut() else {
+            return;
+        };
+        if ui
+            .button(format!(
+                "{} {}",
+                icons::ARROW_SQUARE_OUT,
+                tr("Open in New Window")
+            ))
+            .clicked()
+        {
+            **detach_request = Some(tile_id);
+            ui.close_menu();
+        }
+    }
+
+    /// Tab title plus the plotted topics, so tiles with the same title can be told apart.
+    fn profile_label(&mut self, tile: &PlotTile) -> String {
+        let title = self.tab_title_for_pane(tile).text().to_string();
+        let mut topics: Vec<&str> = Vec::new();
+        for trace in &tile.traces {
+            if !topics.contains(&trace.topic.as_str()) {
+                topics.push(&trace.topic);
+            }
+        }
+        match topics.len() {
+            0 => title,
+            1..=2 => format!("{}: {}", title, topics.join(", ")),
+            n => format!("{}: {}, … (+{})", title, topics[..2].join(", "), n - 2),
+        }
     }
 
-    fn drag_preview_color(&self, _visuals: &egui::Visuals) -> egui::Color32 {
-        egui::Color32::from_rgba_unmultiplied(100, 150, 255, 180)
+    /// Time and value at `pos` in the plot, formatted for the readout. The value is read
+    /// off the lane under `pos`, with as many digits as one pixel resolves.
+    fn readout_at(
+        &self,
+        rect: egui::Rect,
+        lanes: &[Lane],
+        pos: egui::Pos2,
+    ) -> Option<(String, String)> {
+        let lane = lanes
+            .iter()
+            .find(|lane| lane.rect.height() > 0.0 && lane.rect.contains(pos))?;
+        let time = *self.min_time
+            + (pos.x - rect.min.x) / rect.width() * (*self.max_time - *self.min_time);
+        let span = lane.max_y - lane.min_y;
+        let value = lane.min_y + (lane.rect.max.y - pos.y) / lane.rect.height() * span;
+        let decimals = step_decimals(span / lane.rect.height()).min(6);
+        Some((
+            self.format_time_full(time),
+            format!("{:.*}", decimals, value),
+        ))
     }
 
-    fn retain_pane(&mut self, _pane: &PlotTile) -> bool {
-        true
-    }
+    /// Shifts the visible time range by `dt` seconds, stopping at the ends of the data.
+    fn pan_time(&mut self, dt: f32) {
+        let mut new_min = *self.min_time + dt;
+        let mut new_max = *self.max_time + dt;
 
-    fn simplification_options(&self) -> egui_tiles::SimplificationOptions {
-        egui_tiles::SimplificationOptions {
-            all_panes_must_have_tabs: true,
-            ..Default::default()
+        if new_min < self.global_min {
+            let offset = self.global_min - new_min;
+            new_min = self.global_min;
+            new_max += offset;
         }
+        if new_max > self.global_max {
+            let offset = new_max - self.global_max;
+            new_max = self.global_max;
+            new_min -= offset;
+        }
+
+        *self.min_time = new_min.max(self.global_min);
+        *self.max_time = new_max.min(self.global_max);
     }
-}
 
-impl<'a> TiPlotBehavior<'a> {
-    fn estimate_min_sample_interval(&self) -> f32 {
-        let mut min_interval = f32::MAX;
-
-        for (_topic_name, cols) in &self.data_store.topics {
-            if let Some(timestamps) = cols.get("timestamp") {
-                if timestamps.len() >= 2 {
-                    let samples_to_check = timestamps.len().min(100);
-                    for i in 1..samples_to_check {
-                        let interval = (timestamps[i] - timestamps[i - 1]).abs();
-                        if interval > 0.0 && interval < min_interval {
-                            min_interval = interval;
-                        }
-                    }
-                }
+    /// Scales the visible time range by `factor`, keeping the time at `anchor`, a fraction
+    /// of the plot width, in place.
+    fn zoom_time(&mut self, anchor: f32, factor: f32) {
+        let span = *self.max_time - *self.min_time;
+        let center = *self.min_time + anchor * span;
+        let new_span = span * factor;
+
+        let mut new_min = center - new_span * anchor;
+        let mut new_max = center + new_span * (1.0 - anchor);
+
+        let global_span = self.global_max - self.global_min;
+        if new_max - new_min > global_span {
+            new_min = self.global_min;
+            new_max = self.global_max;
+        } else {
+            if new_min < self.global_min {
+                let offset = self.global_min - new_min;
+                new_min = self.global_min;
+                new_max += offset;
+            }
+            if new_max > self.global_max {
+                let offset = new_max - self.global_max;
+                new_max = self.global_max;
+                new_min -= offset;
             }
+            new_min = new_min.max(self.global_min);
+            new_max = new_max.min(self.global_max);
         }
 
-        if min_interval == f32::MAX || min_interval <= 0.0 {
-            0.001
-        } else {
-            min_interval
+        let min_sample_interval = self.estimate_min_sample_interval();
+        let min_span = min_sample_interval * 2.0;
+
+        if new_max - new_min >= min_span {
+            *self.min_time = new_min;
+            *self.max_time = new_max;
         }
     }
 
-    fn calculate_y_bounds(&self, tile: &PlotTile) -> (f32, f32) {
-        let mut min_y = f32::MAX;
-        let mut max_y = f32::MIN;
-        let mut has_data = false;
-
-        for trace in &tile.traces {
-            if let (Some(times), Some(vals)) = (
-                self.data_store.get_column(&trace.topic, "timestamp"),
-                self.data_store.get_column(&trace.topic, &trace.col),
-            ) {
-                if times.is_empty() || vals.is_empty() {
-                    continue;
-                }
+    /// Visible time range.
+    fn view(&self) -> (f32, f32) {
+        (*self.min_time, *self.max_time)
+    }
 
-                let start_idx = times.partition_point(|&t| t < *self.min_time);
-                let end_idx = times.partition_point(|&t| t <= *self.max_time);
+    fn format_time_full(&self, t: f32) -> String {
+        self.time_format.format_full(t, self.data_store.start_time)
+    }
 
-                for i in start_idx..end_idx.min(vals.len()) {
-                    let v = vals[i];
-                    if v < min_y {
-                        min_y = v;
-                    }
-                    if v > max_y {
-                        max_y = v;
-                    }
-                    has_data = true;
-                }
-            }
-        }
+    fn estimate_min_sample_interval(&self) -> f32 {
+        self.data_store.min_sample_interval().unwrap_or(0.001)
+    }
 
-        if !has_data {
-            return (-1.0, 1.0);
+    /// One lane per trace. Unless the tile is stacked, all lanes cover the whole plot and
+    /// share the tile's value range. Stacked traces of hidden groups get empty lanes.
+    fn trace_lanes(&self, rect: egui::Rect, tile: &PlotTile) -> Vec<Lane> {
+        if !tile.stacked || tile.traces.len() < 2 {
+            let (min_y, max_y) =
+                tile.y_bounds(self.data_store, *self.min_time, *self.max_time, self.y_sync);
+            let lane = Lane { rect, min_y, max_y };
+            return vec![lane; tile.traces.len().max(1)];
         }
 
-        let range = max_y - min_y;
-        let pad = if range == 0.0 { 1.0 } else { range * 0.1 };
-        (min_y - pad, max_y + pad)
+        let visible = tile.traces.iter().filter(|t| tile.is_visible(t)).count();
+        let height = rect.height() / visible.max(1) as f32;
+        let mut row = 0;
+        tile.traces
+            .iter()
+            .map(|trace| {
+                let (min_y, max_y) = padded_bounds(tile.trace_range(
+                    trace,
+                    self.data_store,
+                    *self.min_time,
+                    *self.max_time,
+                ));
+                let top = rect.min.y + row as f32 * height;
+                let height = if tile.is_visible(trace) {
+                    row += 1;
+                    height
+                } else {
+                    0.0
+                };
+                Lane {
+                    rect: egui::Rect::from_x_y_ranges(rect.x_range(), top..=top + height),
+                    min_y,
+                    max_y,
+                }
+            })
+            .collect()
     }
 
-    fn draw_grid(&self, ui: &mut egui::Ui, rect: egui::Rect, min_y: f32, max_y: f32) {
+    /// Vertical grid lines across the plot and time labels in the strip below it, spaced
+    /// so that neighboring labels do not overlap.
+    fn draw_time_axis(&self, ui: &mut egui::Ui, rect: egui::Rect) {
+        let time_span = *self.max_time - *self.min_time;
+        if time_span <= 0.0 {
+            return;
+        }
         let grid_color = egui::Color32::from_gray(45);
         let text_color = egui::Color32::from_gray(150);
         let font_id = egui::FontId::proportional(10.0);
+        let label = |t: f32, step: f32| match self.time_format {
+            TimeFormat::Seconds => format!("{:.*}", step_decimals(step), t),
+            format => format.format(t, self.data_store.start_time, step_decimals(step)),
+        };
+        let label_width = |text: String| {
+            ui.fonts(|f| f.layout_no_wrap(text, font_id.clone(), text_color))
+                .size()
+                .x
+        };
 
-        let time_span = *self.max_time - *self.min_time;
-        if time_span > 0.0 {
-            let t_step = calculate_grid_step(time_span, 10);
-            let first_t = (*self.min_time / t_step).ceil() * t_step;
-
-            let mut t = first_t;
-            while t <= *self.max_time {
-                let x_norm = (t - *self.min_time) / time_span;
-                let x_px = rect.min.x + x_norm * rect.width();
-
-                if x_px >= rect.min.x && x_px <= rect.max.x {
-                    ui.painter().line_segment(
-                        [egui::pos2(x_px, rect.min.y), egui::pos2(x_px, rect.max.y)],
-                        egui::Stroke::new(1.0, grid_color),
-                    );
+        // Fewer ticks when the widest label would not fit between two of them.
+        let step = calculate_grid_step(time_span, 10);
+        let widest =
+            label_width(label(*self.max_time, step)).max(label_width(label(*self.min_time, step)));
+        let fitting = (rect.width() / (widest + TICK_LABEL_GAP)).floor() as usize;
+        let t_step = calculate_grid_step(time_span, fitting.clamp(1, 10));
+
+        let mut t = (*self.min_time / t_step).ceil() * t_step;
+        let mut last_right = f32::NEG_INFINITY;
+        while t <= *self.max_time {
+            let x = rect.min.x + (t - *self.min_time) / time_span * rect.width();
+            ui.painter().line_segment(
+                [egui::pos2(x, rect.min.y), egui::pos2(x, rect.max.y)],
+                egui::Stroke::new(1.0, grid_color),
+            );
 
-                    ui.painter().text(
-                        egui::pos2(x_px + 2.0, rect.max.y - 12.0),
-                        egui::Align2::LEFT_BOTTOM,
-                        format!("{:.1}", t),
-                        font_id.clone(),
-                        text_color,
-                    );
-                }
-                t += t_step;
+            let galley =
+                ui.fonts(|f| f.layout_no_wrap(label(t, t_step), font_id.clone(), text_color));
+            let left = (x - galley.size().x / 2.0)
+                .min(rect.max.x - galley.size().x)
+                .max(rect.min.x - Y_AXIS_WIDTH);
+            if left >= last_right + TICK_LABEL_GAP / 2.0 {
+                last_right = left + galley.size().x;
+                ui.painter()
+                    .galley(egui::pos2(left, rect.max.y + 2.0), galley, text_color);
             }
+            t += t_step;
         }
+    }
 
-        let val_span = max_y - min_y;
-        if val_span > 0.0 {
-            let v_step = calculate_grid_step(val_span, 8);
-            let first_v = (min_y / v_step).ceil() * v_step;
+    /// Horizontal grid lines of a lane with their values in the axis strip to the left of
+    /// the plot, skipping labels that would run into the previous one.
+    fn draw_value_axis(&self, ui: &mut egui::Ui, lane: &Lane) {
+        let rect = lane.rect;
+        let val_span = lane.max_y - lane.min_y;
+        if val_span <= 0.0 || rect.height() <= 0.0 {
+            return;
+        }
+        let grid_color = egui::Color32::from_gray(45);
+        let text_color = egui::Color32::from_gray(150);
+        let font_id = egui::FontId::proportional(10.0);
+        let label_height = ui.fonts(|f| f.row_height(&font_id));
 
-            let mut v = first_v;
-            while v <= max_y {
-                let y_norm = 1.0 - (v - min_y) / val_span;
-                let y_px = rect.min.y + y_norm * rect.height();
+        let fitting = (rect.height() / (label_height + TICK_LABEL_GAP)).floor() as usize;
+        let v_step = calculate_grid_step(val_span, fitting.clamp(1, 8));
 
-                if y_px >= rect.min.y && y_px <= rect.max.y {
-                    ui.painter().line_segment(
-                        [egui::pos2(rect.min.x, y_px), egui::pos2(rect.max.x, y_px)],
-                        egui::Stroke::new(1.0, grid_color),
-                    );
+        let mut v = (lane.min_y / v_step).ceil() * v_step;
+        let mut last_top = f32::INFINITY;
+        while v <= lane.max_y {
+            let y = rect.max.y - (v - lane.min_y) / val_span * rect.height();
+            ui.painter().line_segment(
+                [egui::pos2(rect.min.x, y), egui::pos2(rect.max.x, y)],
+                egui::Stroke::new(1.0, grid_color),
+            );
 
-                    ui.painter().text(
-                        egui::pos2(rect.min.x + 2.0, y_px - 2.0),
-                        egui::Align2::LEFT_BOTTOM,
-                        format!("{:.2}", v),
-                        font_id.clone(),
-                        text_color,
-                    );
-                }
-                v += v_step;
+            let center = y
+                .min(rect.max.y - label_height / 2.0)
+                .max(rect.min.y + label_height / 2.0);
+            if center + label_height / 2.0 <= last_top {
+                last_top = center - label_height / 2.0;
+                let mut job = egui::text::LayoutJob::single_section(
+                    format_tick(v, v_step),
+                    egui::TextFormat::simple(font_id.clone(), text_color),
+                );
+                job.wrap = egui::text::TextWrapping::truncate_at_width(Y_AXIS_WIDTH - 6.0);
+                let galley = ui.fonts(|f| f.layout_job(job));
+                let pos = egui::pos2(
+                    rect.min.x - 4.0 - galley.size().x,
+                    center - label_height / 2.0,
+                );
+                ui.painter().galley(pos, galley, text_color);
             }
+            v += v_step;
         }
     }
 
@@ -558,8 +2044,7 @@ impl<'a> TiPlotBehavior<'a> {
         ui: &mut egui::Ui,
         rect: egui::Rect,
         tile: &mut PlotTile,
-        min_y: f32,
-        max_y: f32,
+        lanes: &[Lane],
     ) {
         let is_dragging = ui.input(|i| i.pointer.primary_down());
         if is_dragging {
@@ -570,14 +2055,40 @@ impl<'a> TiPlotBehavior<'a> {
             return;
         }
 
-        if let Some(pointer_pos) = ui.input(|i| i.pointer.hover_pos()) {
-            if !rect.contains(pointer_pos) {
-                return;
+        let pointer_pos = ui
+            .input(|i| i.pointer.hover_pos())
+            .filter(|pos| rect.contains(*pos));
+        let Some(pointer_pos) = pointer_pos else {
+            if let Some(hover_time) = self.shared_hover_time {
+                self.draw_shared_crosshair(ui, rect, tile, lanes, hover_time);
             }
+            return;
+        };
 
+        {
             let view_width = *self.max_time - *self.min_time;
             let x_pct = (pointer_pos.x - rect.min.x) / rect.width();
             let hover_time = *self.min_time + x_pct * view_width;
+            *self.next_hover_time = Some(hover_time);
+
+            if let Some((time, value)) = self.readout_at(rect, lanes, pointer_pos) {
+                let offset = if tile.compare_offset.is_some() {
+                    16.0
+                } else {
+                    4.0
+                };
+                ui.painter().text(
+                    rect.right_bottom() + egui::vec2(-4.0, -offset),
+                    egui::Align2::RIGHT_BOTTOM,
+                    format!("{}  {}", time, value),
+                    egui::FontId::monospace(10.0),
+                    egui::Color32::from_gray(170),
+                );
+                // Middle-click copies both, tab separated to paste into a spreadsheet.
+                if ui.input(|i| i.pointer.button_clicked(egui::PointerButton::Middle)) {
+                    ui.ctx().copy_text(format!("{}\t{}", time, value));
+                }
+            }
 
             ui.painter().line_segment(
                 [
@@ -588,51 +2099,56 @@ impl<'a> TiPlotBehavior<'a> {
             );
 
             if tile.show_hover_circles || tile.show_hover_tooltip {
+                let start = Instant::now();
                 tile.update_tooltip_cache(hover_time, self.data_store, false);
+                self.profiler.add_since(ProfileSection::TooltipCache, start);
             }
 
             if tile.show_hover_circles {
-                let val_span = max_y - min_y;
-                if val_span > 0.0 {
-                    for (i, trace) in tile.traces.iter().enumerate() {
-                        if let Some(Some(value)) = tile.cached_tooltip_values.get(i) {
-                            let y_norm = 1.0 - (value - min_y) / val_span;
-                            let y_px = rect.min.y + y_norm * rect.height();
-
-                            if y_px >= rect.min.y && y_px <= rect.max.y {
-                                let point_pos = egui::pos2(pointer_pos.x, y_px);
-                                let trace_color = egui::Color32::from_rgb(
-                                    (trace.color[0] * 255.0) as u8,
-                                    (trace.color[1] * 255.0) as u8,
-                                    (trace.color[2] * 255.0) as u8,
-                                );
-
-                                ui.painter().circle_filled(point_pos, 3.0, trace_color);
-
-                                ui.painter().circle_stroke(
-                                    point_pos,
-                                    3.0,
-                                    egui::Stroke::new(1.5, egui::Color32::WHITE),
-                                );
-                            }
-                        }
-                    }
-                }
+                draw_hover_circles(ui, tile, lanes, rect, self.view(), pointer_pos.x);
             }
 
             if tile.show_hover_tooltip {
-                render_cursor_tooltip(ui, rect, pointer_pos, hover_time, tile);
+                let label = self.format_time_full(hover_time);
+                render_cursor_tooltip(ui, rect, pointer_pos, &label, tile);
             }
         }
     }
 
+    /// Crosshair of a plot hovered elsewhere, at the same time in this tile.
+    fn draw_shared_crosshair(
+        &mut self,
+        ui: &mut egui::Ui,
+        rect: egui::Rect,
+        tile: &mut PlotTile,
+        lanes: &[Lane],
+        hover_time: f32,
+    ) {
+        let time_span = *self.max_time - *self.min_time;
+        if time_span <= 0.0 || hover_time < *self.min_time || hover_time > *self.max_time {
+            return;
+        }
+        let x = rect.min.x + (hover_time - *self.min_time) / time_span * rect.width();
+
+        ui.painter().line_segment(
+            [egui::pos2(x, rect.min.y), egui::pos2(x, rect.max.y)],
+            egui::Stroke::new(1.0, egui::Color32::from_white_alpha(140)),
+        );
+
+        if tile.show_hover_circles {
+            let start = Instant::now();
+            tile.update_tooltip_cache(hover_time, self.data_store, false);
+            self.profiler.add_since(ProfileSection::TooltipCache, start);
+            draw_hover_circles(ui, tile, lanes, rect, self.view(), x);
+        }
+    }
+
     fn handle_playback_cursor(
         &mut self,
         ui: &mut egui::Ui,
         rect: egui::Rect,
         tile: &mut PlotTile,
-        min_y: f32,
-        max_y: f32,
+        lanes: &[Lane],
     ) {
         // Only show if current_time is within view
         if *self.current_time < *self.min_time || *self.current_time > *self.max_time {
@@ -648,42 +2164,20 @@ impl<'a> TiPlotBehavior<'a> {
         let cursor_x = rect.min.x + cursor_norm * rect.width();
 
         if tile.show_hover_circles || tile.show_hover_tooltip {
+            let start = Instant::now();
             tile.update_tooltip_cache(*self.current_time, self.data_store, true);
+            self.profiler.add_since(ProfileSection::TooltipCache, start);
         }
 
         if tile.show_hover_circles {
-            let val_span = max_y - min_y;
-            if val_span > 0.0 {
-                for (i, trace) in tile.traces.iter().enumerate() {
-                    if let Some(Some(value)) = tile.cached_tooltip_values.get(i) {
-                        let y_norm = 1.0 - (value - min_y) / val_span;
-                        let y_px = rect.min.y + y_norm * rect.height();
-
-                        if y_px >= rect.min.y && y_px <= rect.max.y {
-                            let point_pos = egui::pos2(cursor_x, y_px);
-                            let trace_color = egui::Color32::from_rgb(
-                                (trace.color[0] * 255.0) as u8,
-                                (trace.color[1] * 255.0) as u8,
-                                (trace.color[2] * 255.0) as u8,
-                            );
-
-                            ui.painter().circle_filled(point_pos, 3.0, trace_color);
-
-                            ui.painter().circle_stroke(
-                                point_pos,
-                                3.0,
-                                egui::Stroke::new(1.5, egui::Color32::WHITE),
-                            );
-                        }
-                    }
-                }
-            }
+            draw_hover_circles(ui, tile, lanes, rect, self.view(), cursor_x);
         }
 
         // Show tooltip at playback cursor
         if tile.show_hover_tooltip {
             let cursor_pos = egui::pos2(cursor_x, rect.center().y);
-            render_cursor_tooltip(ui, rect, cursor_pos, *self.current_time, tile);
+            let label = self.format_time_full(*self.current_time);
+            render_cursor_tooltip(ui, rect, cursor_pos, &label, tile);
         }
     }
 
@@ -709,9 +2203,7 @@ impl<'a> TiPlotBehavior<'a> {
             ui.interact(clear_rect, ui.id().with("clear_plot"), egui::Sense::click());
 
         if clear_response.clicked() {
-            tile.traces.clear();
-            tile.cached_tooltip_values.clear();
-            tile.cached_tooltip_time = f32::NEG_INFINITY;
+            tile.clear_traces();
         }
 
         let clear_bg_color = if clear_response.hovered() {
@@ -742,7 +2234,7 @@ impl<'a> TiPlotBehavior<'a> {
                 egui::LayerId::new(egui::Order::Middle, ui.id().with("clear_tooltip")),
                 ui.id().with("clear_tooltip"),
                 |ui| {
-                    ui.label("Clear plot");
+                    ui.label(tr("Clear plot"));
                 },
             );
         }
@@ -789,21 +2281,57 @@ impl<'a> TiPlotBehavior<'a> {
         );
 
         if !tile.show_legend {
+            tile.legend_rect = None;
             return;
         }
 
-        let legend_width = 200.0;
-        let legend_x = clear_button_pos.x - legend_width - 5.0;
-        let legend_y = rect.min.y + padding;
-
-        let legend_start_pos = egui::pos2(legend_x, legend_y);
-
         let line_height = 18.0;
         let legend_padding = 8.0;
-        let legend_height = (tile.traces.len() as f32 * line_height) + (legend_padding * 2.0);
+        let header_height = 18.0;
+
+        // Keep clear of the buttons in the top-right corner.
+        let buttons_left = clear_button_pos.x - 5.0;
+        let legend_width = if tile.show_legend_values {
+            280.0_f32
+        } else {
+            200.0
+        }
+        .min(buttons_left - rect.min.x - padding)
+        .max(80.0);
+
+        let rows = legend_rows(tile);
+        let max_rows = ((rect.height() - padding * 2.0 - header_height - legend_padding * 2.0)
+            / line_height)
+            .floor()
+            .max(1.0) as usize;
+        let visible_rows = if tile.legend_collapsed {
+            0
+        } else {
+            rows.len().min(max_rows)
+        };
+        let legend_height = header_height
+            + legend_padding * 2.0
+            + if visible_rows > 0 {
+                visible_rows as f32 * line_height
+            } else {
+                -legend_padding
+            };
+
+        let legend_x = match tile.legend_corner {
+            LegendCorner::TopLeft | LegendCorner::BottomLeft => rect.min.x + padding,
+            LegendCorner::TopRight | LegendCorner::BottomRight => buttons_left - legend_width,
+        };
+        let legend_y = match tile.legend_corner {
+            LegendCorner::TopLeft | LegendCorner::TopRight => rect.min.y + padding,
+            LegendCorner::BottomLeft | LegendCorner::BottomRight => {
+                (rect.max.y - padding - legend_height).max(rect.min.y + padding)
+            }
+        };
+        let legend_start_pos = egui::pos2(legend_x, legend_y);
 
         let legend_rect =
             egui::Rect::from_min_size(legend_start_pos, egui::vec2(legend_width, legend_height));
+        tile.legend_rect = Some(legend_rect);
 
         ui.painter().rect_filled(
             legend_rect,
@@ -820,13 +2348,151 @@ impl<'a> TiPlotBehavior<'a> {
             ),
         );
 
-        let mut y_offset = legend_start_pos.y + legend_padding;
+        // Header: drag to move the legend to another corner, click to collapse it.
+        let header_rect = egui::Rect::from_min_size(
+            legend_start_pos + egui::vec2(0.0, legend_padding / 2.0),
+            egui::vec2(legend_width, header_height),
+        );
+        let header_response = ui.interact(
+            header_rect,
+            ui.id().with("legend_header"),
+            egui::Sense::click_and_drag(),
+        );
+        if header_response.hovered() {
+            ui.ctx().set_cursor_icon(egui::CursorIcon::Move);
+        }
+        if header_response.clicked() {
+            tile.legend_collapsed = !tile.legend_collapsed;
+        }
+        if header_response.dragged() {
+            if let Some(pointer) = header_response.interact_pointer_pos() {
+                let left = pointer.x < rect.center().x;
+                let top = pointer.y < rect.center().y;
+                tile.legend_corner = match (left, top) {
+                    (true, true) => LegendCorner::TopLeft,
+                    (false, true) => LegendCorner::TopRight,
+                    (true, false) => LegendCorner::BottomLeft,
+                    (false, false) => LegendCorner::BottomRight,
+                };
+            }
+        }
+        let caret = if tile.legend_collapsed {
+            icons::CARET_RIGHT
+        } else {
+            icons::CARET_DOWN
+        };
+        ui.painter().text(
+            egui::pos2(header_rect.min.x + legend_padding, header_rect.center().y),
+            egui::Align2::LEFT_CENTER,
+            format!("{} {} ({})", caret, tr("Legend"), tile.traces.len()),
+            egui::FontId::proportional(11.0),
+            egui::Color32::from_gray(170),
+        );
+
+        if visible_rows == 0 {
+            return;
+        }
 
-        for trace in &tile.traces {
-            let text_pos = egui::pos2(legend_start_pos.x + legend_padding + 15.0, y_offset);
+        // Scroll through the traces when they do not all fit.
+        let hidden_rows = rows.len() - visible_rows;
+        if hidden_rows > 0 && ui.rect_contains_pointer(legend_rect) {
+            let scroll = ui.input(|i| i.smooth_scroll_delta.y);
+            tile.legend_scroll -= scroll / line_height;
+        }
+        tile.legend_scroll = tile.legend_scroll.clamp(0.0, hidden_rows as f32);
+        let first_row = tile.legend_scroll.round() as usize;
+
+        let mut y_offset = header_rect.max.y + legend_padding / 2.0;
+        let rows_top = y_offset;
+        let mut trace_move: Option<(usize, usize)> = None;
+        let mut group_edit: Option<GroupEdit> = None;
+
+        if hidden_rows > 0 {
+            let track = egui::Rect::from_min_max(
+                egui::pos2(legend_rect.right() - 4.0, rows_top),
+                egui::pos2(
+                    legend_rect.right() - 2.0,
+                    rows_top + visible_rows as f32 * line_height,
+                ),
+            );
+            let thumb_height = track.height() * visible_rows as f32 / rows.len() as f32;
+            let thumb_top = track.min.y
+                + (track.height() - thumb_height) * first_row as f32 / hidden_rows as f32;
+            ui.painter().rect_filled(
+                egui::Rect::from_min_size(
+                    egui::pos2(track.min.x, thumb_top),
+                    egui::vec2(track.width(), thumb_height),
+                ),
+                1.0,
+                egui::Color32::from_gray(140),
+            );
+        }
+
+        for (row, &entry) in rows.iter().enumerate().skip(first_row).take(visible_rows) {
+            let row_rect = egui::Rect::from_min_size(
+                egui::pos2(legend_start_pos.x, y_offset),
+                egui::vec2(legend_width, line_height),
+            );
+            let idx = match entry {
+                LegendRow::Trace(idx) => idx,
+                LegendRow::Group(group) => {
+                    let group = &tile.groups[group];
+                    if let Some(edit) = self.legend_group_row(ui, tile, group, row_rect) {
+                        group_edit = Some(edit);
+                    }
+                    y_offset += line_height;
+                    continue;
+                }
+            };
+            let trace = &tile.traces[idx];
+
+            // Dragging a row reorders the traces, and with them the draw order.
+            let row_response = ui.interact(
+                row_rect,
+                ui.id().with(("legend_row", idx)),
+                egui::Sense::click_and_drag(),
+            );
+            if row_response.hovered() {
+                ui.ctx().set_cursor_icon(egui::CursorIcon::Grab);
+            }
+            if row_response.dragged() || row_response.drag_stopped() {
+                ui.ctx().set_cursor_icon(egui::CursorIcon::Grabbing);
+                if let Some(pointer) = row_response.interact_pointer_pos() {
+                    let target = (first_row
+                        + ((pointer.y - rows_top) / line_height).floor().max(0.0) as usize)
+                        .min(rows.len() - 1);
+                    let marker_y =
+                        rows_top + (target.max(first_row) - first_row) as f32 * line_height;
+                    ui.painter().hline(
+                        legend_rect.x_range(),
+                        if target > row {
+                            marker_y + line_height
+                        } else {
+                            marker_y
+                        },
+                        egui::Stroke::new(1.5, egui::Color32::from_rgb(70, 120, 200)),
+                    );
+                    if row_response.drag_stopped() {
+                        trace_move = Some((idx, row_trace(tile, &rows, target)));
+                    }
+                }
+            }
+            row_response.context_menu(|ui| {
+                if let Some(edit) = trace_group_menu(ui, tile, idx) {
+                    group_edit = Some(edit);
+                    ui.close_menu();
+                }
+            });
+
+            // Members of a group are indented under its header.
+            let indent = if trace.group.is_some() { 10.0 } else { 0.0 };
+            let text_pos = egui::pos2(
+                legend_start_pos.x + legend_padding + 15.0 + indent,
+                y_offset,
+            );
 
             let swatch_center = egui::pos2(
-                legend_start_pos.x + legend_padding + 5.0,
+                legend_start_pos.x + legend_padding + 5.0 + indent,
                 y_offset + line_height / 2.0,
             );
             ui.painter().circle_filled(
@@ -839,16 +2505,402 @@ impl<'a> TiPlotBehavior<'a> {
                 ),
             );
 
-            let label_text = format!("{}/{}", trace.topic, trace.col);
-            ui.painter().text(
-                text_pos,
-                egui::Align2::LEFT_TOP,
-                label_text,
-                egui::FontId::proportional(11.0),
-                egui::Color32::from_gray(220),
+            let mut text_right = legend_rect.right() - legend_padding;
+            if tile.show_legend_values {
+                let value = tile
+                    .trace_value(trace, self.data_store, *self.current_time)
+                    .map_or("–".to_string(), |v| trace.format_value(v));
+                let value_rect = ui.painter().text(
+                    egui::pos2(text_right, y_offset),
+                    egui::Align2::RIGHT_TOP,
+                    value,
+                    egui::FontId::monospace(11.0),
+                    egui::Color32::WHITE,
+                );
+                text_right = value_rect.left() - 8.0;
+            }
+
+            let label_text = match &trace.unit {
+                Some(unit) => format!("{}/{} ({})", trace.topic, trace.display_name(), unit),
+                None => format!("{}/{}", trace.topic, trace.display_name()),
+            };
+            let color = egui::Color32::from_gray(220);
+            let mut job = egui::text::LayoutJob::single_section(
+                label_text.clone(),
+                egui::TextFormat::simple(egui::FontId::proportional(11.0), color),
             );
+            job.wrap = egui::text::TextWrapping::truncate_at_width(text_right - text_pos.x);
+            let galley = ui.fonts(|f| f.layout_job(job));
+            let elided = galley.elided;
+            ui.painter().galley(text_pos, galley, color);
+            if elided {
+                row_response.on_hover_text(label_text);
+            }
 
             y_offset += line_height;
         }
+
+        if let Some((from, to)) = trace_move {
+            tile.move_trace(from, to);
+        }
+        match group_edit {
+            Some(GroupEdit::Toggle(name)) => tile.toggle_group(&name),
+            Some(GroupEdit::Recolor(name, palette)) => tile.recolor_group(&name, palette),
+            Some(GroupEdit::Ungroup(name)) => tile.ungroup(&name),
+            Some(GroupEdit::Assign(idx, group)) => tile.set_trace_group(idx, group),
+            None => {}
+        }
+    }
+
+    /// Header row of a trace group in the legend: click to show or hide the group, hover
+    /// for its statistics, right-click to recolor or ungroup it.
+    fn legend_group_row(
+        &self,
+        ui: &mut egui::Ui,
+        tile: &PlotTile,
+        group: &TraceGroup,
+        row_rect: egui::Rect,
+    ) -> Option<GroupEdit> {
+        let mut edit = None;
+        let response = ui.interact(
+            row_rect,
+            ui.id().with(("legend_group", &group.name)),
+            egui::Sense::click(),
+        );
+        if response.clicked() {
+            edit = Some(GroupEdit::Toggle(group.name.clone()));
+        }
+
+        let stats = tile.group_stats(&group.name, self.data_store, *self.min_time, *self.max_time);
+        let eye = if group.hidden {
+            icons::EYE_SLASH
+        } else {
+            icons::EYE
+        };
+        let color = if response.hovered() {
+            egui::Color32::WHITE
+        } else if group.hidden {
+            egui::Color32::from_gray(120)
+        } else {
+            egui::Color32::from_gray(200)
+        };
+        ui.painter().text(
+            egui::pos2(row_rect.min.x + 8.0, row_rect.center().y),
+            egui::Align2::LEFT_CENTER,
+            format!("{} {} ({})", eye, group.name, stats.traces),
+            egui::FontId::proportional(11.0),
+            color,
+        );
+
+        let response = response.on_hover_ui(|ui| {
+            ui.strong(&group.name);
+            ui.label(format!("{}: {}", tr("Traces"), stats.traces));
+            match stats.range {
+                Some((min, max)) => {
+                    ui.label(format!("{}: {:.4}", tr("Min"), min));
+                    ui.label(format!("{}: {:.4}", tr("Max"), max));
+                    ui.label(format!("{}: {:.4}", tr("Range"), max - min));
+                }
+                None => {
+                    ui.label(egui::RichText::new(tr("No samples in view")).weak());
+                }
+            }
+        });
+        response.context_menu(|ui| {
+            ui.menu_button(format!("{} {}", icons::PALETTE, tr("Color Scheme")), |ui| {
+                for palette in TracePalette::ALL {
+                    if ui
+                        .selectable_label(group.palette == Some(palette), palette.name())
+                        .clicked()
+                    {
+                        edit = Some(GroupEdit::Recolor(group.name.clone(), palette));
+                        ui.close_menu();
+                    }
+                }
+            });
+            if ui
+                .button(format!("{} {}", icons::SELECTION_SLASH, tr("Ungroup")))
+                .clicked()
+            {
+                edit = Some(GroupEdit::Ungroup(group.name.clone()));
+                ui.close_menu();
+            }
+        });
+        edit
+    }
+}
+
+/// Line of the plot legend: the header of a trace group, by index into the tile's groups,
+/// or a trace.
+#[derive(Clone, Copy)]
+enum LegendRow {
+    Group(usize),
+    Trace(usize),
+}
+
+/// Change to a tile's trace groups picked in the legend, applied once it is drawn.
+enum GroupEdit {
+    Toggle(String),
+    Recolor(String, TracePalette),
+    Ungroup(String),
+    Assign(usize, Option<String>),
+}
+
+/// Legend lines of a tile: each group's header followed by its traces, which are left
+/// out while the group is hidden.
+fn legend_rows(tile: &PlotTile) -> Vec<LegendRow> {
+    let mut rows = Vec::new();
+    let mut previous: Option<&str> = None;
+    for (idx, trace) in tile.traces.iter().enumerate() {
+        let group = trace.group.as_deref();
+        if let Some(name) = group.filter(|&name| Some(name) != previous) {
+            if let Some(index) = tile.groups.iter().position(|g| g.name == name) {
+                rows.push(LegendRow::Group(index));
+            }
+        }
+        previous = group;
+        if tile.is_visible(trace) {
+            rows.push(LegendRow::Trace(idx));
+        }
+    }
+    rows
+}
+
+/// Trace a legend row stands for when dropping onto it; a group header stands for the
+/// group's first trace.
+fn row_trace(tile: &PlotTile, rows: &[LegendRow], row: usize) -> usize {
+    match rows[row] {
+        LegendRow::Trace(idx) => idx,
+        LegendRow::Group(group) => {
+            let name = &tile.groups[group].name;
+            tile.traces
+                .iter()
+                .position(|t| t.group.as_ref() == Some(name))
+                .unwrap_or(0)
+        }
+    }
+}
+
+/// Context menu of a trace's legend row, moving it between groups.
+fn trace_group_menu(ui: &mut egui::Ui, tile: &PlotTile, idx: usize) -> Option<GroupEdit> {
+    let current = tile.traces[idx].group.as_ref();
+    let mut edit = None;
+    ui.menu_button(format!("{} {}", icons::FOLDERS, tr("Group")), |ui| {
+        for group in &tile.groups {
+            if ui
+                .selectable_label(current == Some(&group.name), &group.name)
+                .clicked()
+            {
+                edit = Some(GroupEdit::Assign(idx, Some(group.name.clone())));
+            }
+        }
+        if !tile.groups.is_empty() {
+            ui.separator();
+        }
+        if let Some(name) = new_group_field(ui, ui.id().with(("new_group", idx))) {
+            edit = Some(GroupEdit::Assign(idx, Some(name)));
+        }
+    });
+    if current.is_some() && ui.button(tr("Remove from Group")).clicked() {
+        edit = Some(GroupEdit::Assign(idx, None));
+    }
+    edit
+}
+
+/// Width of the strip left of the plot holding the value labels.
+const Y_AXIS_WIDTH: f32 = 46.0;
+/// Width of the strip along the tile's left edge holding the value axis title.
+const Y_TITLE_WIDTH: f32 = 14.0;
+/// Height of the strip below the plot holding the time labels.
+const X_AXIS_HEIGHT: f32 = 14.0;
+/// Least space between neighboring tick labels.
+const TICK_LABEL_GAP: f32 = 12.0;
+
+/// Part of a tile's area the traces are drawn in, leaving room for the axis labels. The
+/// strips have the same size in every tile so that the time axes of tiles above each
+/// other line up.
+fn plot_area(outer: egui::Rect) -> egui::Rect {
+    let min = outer.min + egui::vec2(Y_TITLE_WIDTH + Y_AXIS_WIDTH, 0.0);
+    let max = outer.max - egui::vec2(0.0, X_AXIS_HEIGHT);
+    egui::Rect::from_min_max(min, max.max(min + egui::vec2(1.0, 1.0)))
+}
+
+/// Title of a tile's shared value axis: the trace and its unit when there is only one,
+/// otherwise the unit all traces share.
+fn value_axis_title(tile: &PlotTile) -> Option<String> {
+    let mut visible = tile.traces.iter().filter(|t| tile.is_visible(t));
+    let first = visible.next()?;
+    if visible.next().is_none() {
+        return Some(match &first.unit {
+            Some(unit) => format!("{} [{}]", first.display_name(), unit),
+            None => first.display_name(),
+        });
+    }
+    tile.common_unit().map(|unit| format!("[{}]", unit))
+}
+
+/// Draws `title` turned on its side in the title strip of `outer`, centered on `lane`.
+fn draw_axis_title(
+    ui: &egui::Ui,
+    outer: egui::Rect,
+    lane: egui::Rect,
+    title: &str,
+    color: egui::Color32,
+) {
+    let mut job = egui::text::LayoutJob::single_section(
+        title.to_string(),
+        egui::TextFormat::simple(egui::FontId::proportional(10.0), color),
+    );
+    job.wrap = egui::text::TextWrapping::truncate_at_width(lane.height() - 4.0);
+    let galley = ui.fonts(|f| f.layout_job(job));
+    let size = galley.size();
+    // Turned a quarter counter-clockwise around its top-left corner, the text runs
+    // upwards from `pos`.
+    let pos = egui::pos2(
+        outer.min.x + (Y_TITLE_WIDTH - size.y) / 2.0,
+        lane.center().y + size.x / 2.0,
+    );
+    ui.painter().add(
+        egui::epaint::TextShape::new(pos, galley, color).with_angle(-std::f32::consts::FRAC_PI_2),
+    );
+}
+
+/// Fractional digits that tell apart ticks `step` apart.
+fn step_decimals(step: f32) -> usize {
+    (-step.log10().floor()).max(0.0) as usize
+}
+
+/// Value axis label, switching to scientific notation for large magnitudes.
+fn format_tick(value: f32, step: f32) -> String {
+    if value != 0.0 && value.abs() >= 1e5 {
+        format!("{:.1e}", value)
+    } else {
+        format!("{:.*}", step_decimals(step), value)
+    }
+}
+
+/// Area and value range a trace is drawn in.
+#[derive(Clone, Copy)]
+struct Lane {
+    rect: egui::Rect,
+    min_y: f32,
+    max_y: f32,
+}
+
+fn color32(color: [f32; 4]) -> egui::Color32 {
+    egui::Color32::from_rgb(
+        (color[0] * 255.0) as u8,
+        (color[1] * 255.0) as u8,
+        (color[2] * 255.0) as u8,
+    )
+}
+
+/// Draws dashed horizontal limit lines across a lane.
+fn draw_limits(ui: &egui::Ui, lane: &Lane, limits: &[f32]) {
+    let color = egui::Color32::from_rgb(230, 90, 90);
+    let span = lane.max_y - lane.min_y;
+    if span <= 0.0 {
+        return;
+    }
+    for &limit in limits {
+        if limit < lane.min_y || limit > lane.max_y {
+            continue;
+        }
+        let y = lane.rect.bottom() - (limit - lane.min_y) / span * lane.rect.height();
+        ui.painter().extend(egui::Shape::dashed_line(
+            &[
+                egui::pos2(lane.rect.left(), y),
+                egui::pos2(lane.rect.right(), y),
+            ],
+            egui::Stroke::new(1.0, color),
+            6.0,
+            4.0,
+        ));
+        ui.painter().text(
+            egui::pos2(lane.rect.right() - 30.0, y - 2.0),
+            egui::Align2::RIGHT_BOTTOM,
+            format!("{}", limit),
+            egui::FontId::proportional(10.0),
+            color,
+        );
+    }
+}
+
+/// Marks the cached tooltip values of all traces at horizontal position `x`, or at their
+/// sample's time when snapped to samples.
+fn draw_hover_circles(
+    ui: &egui::Ui,
+    tile: &PlotTile,
+    lanes: &[Lane],
+    plot_rect: egui::Rect,
+    (min_time, max_time): (f32, f32),
+    x: f32,
+) {
+    for (i, (trace, lane)) in tile.traces.iter().zip(lanes).enumerate() {
+        let val_span = lane.max_y - lane.min_y;
+        if val_span <= 0.0 || !tile.is_visible(trace) {
+            continue;
+        }
+        if let Some(Some(value)) = tile.cached_tooltip_values.get(i) {
+            let rect = lane.rect;
+            let y_norm = 1.0 - (value - lane.min_y) / val_span;
+            let y_px = rect.min.y + y_norm * rect.height();
+            let x = match tile.cached_tooltip_samples.get(i) {
+                Some(Some(sample)) if max_time > min_time => {
+                    plot_rect.min.x
+                        + (sample.time - min_time) / (max_time - min_time) * plot_rect.width()
+                }
+                _ => x,
+            };
+
+            if y_px >= rect.min.y && y_px <= rect.max.y && plot_rect.x_range().contains(x) {
+                let point_pos = egui::pos2(x, y_px);
+                let trace_color = egui::Color32::from_rgb(
+                    (trace.color[0] * 255.0) as u8,
+                    (trace.color[1] * 255.0) as u8,
+                    (trace.color[2] * 255.0) as u8,
+                );
+
+                ui.painter().circle_filled(point_pos, 3.0, trace_color);
+
+                ui.painter().circle_stroke(
+                    point_pos,
+                    3.0,
+                    egui::Stroke::new(1.5, egui::Color32::WHITE),
+                );
+            }
+        }
+    }
+}
+
+/// Text field naming a new trace group, returning the name when Enter is pressed.
+fn new_group_field(ui: &mut egui::Ui, id: egui::Id) -> Option<String> {
+    let mut name = ui.data_mut(|d| d.get_temp::<String>(id).unwrap_or_default());
+    let response = ui.add(
+        egui::TextEdit::singleline(&mut name)
+            .hint_text(tr("New group"))
+            .desired_width(100.0),
+    );
+    let submitted = response.lost_focus()
+        && ui.input(|i| i.key_pressed(egui::Key::Enter))
+        && !name.trim().is_empty();
+    if submitted {
+        ui.data_mut(|d| d.remove::<String>(id));
+        return Some(name.trim().to_string());
     }
+    ui.data_mut(|d| d.insert_temp(id, name));
+    None
+}
+
+fn color_swatch(ui: &mut egui::Ui, color: [f32; 4], size: f32) {
+    let (swatch_rect, _) = ui.allocate_exact_size(egui::vec2(size, size), egui::Sense::hover());
+    ui.painter().rect_filled(
+        swatch_rect,
+        2.0,
+        egui::Color32::from_rgb(
+            (color[0] * 255.0) as u8,
+            (color[1] * 255.0) as u8,
+            (color[2] * 255.0) as u8,
+        ),
+    );
 }
@@ -0,0 +1,401 @@
+use crossbeam_channel::{unbounded, Receiver, Sender};
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStdout, Command, Stdio};
+use std::thread::JoinHandle;
+
+/// Decoded frames are at most this wide, to keep decoding fast during playback.
+const MAX_FRAME_WIDTH: u32 = 960;
+/// A new frame is decoded once the wanted video time is this far from the shown one.
+const FRAME_TOLERANCE: f32 = 1.0 / 30.0;
+/// Up to this many seconds ahead, decoding on is cheaper than seeking.
+const MAX_DECODE_AHEAD: f32 = 2.0;
+
+/// Configuration of a plot tile shown as a video file played along with the timeline.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct VideoView {
+    pub path: PathBuf,
+    /// Timeline time at which the video starts.
+    pub offset: f32,
+}
+
+impl VideoView {
+    pub fn new(path: PathBuf, offset: f32) -> Self {
+        Self { path, offset }
+    }
+
+    pub fn file_name(&self) -> String {
+        self.path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    }
+
+    pub fn video_time(&self, timeline_time: f32) -> f32 {
+        timeline_time - self.offset
+    }
+}
+
+enum DecodeResult {
+    Duration(Result<f32, String>),
+    Frame(f32, Result<egui::ColorImage, String>),
+}
+
+struct VideoState {
+    duration: Option<Result<f32, String>>,
+    frame: Option<(f32, egui::TextureHandle)>,
+    /// Video time that failed to decode and why.
+    error: Option<(f32, String)>,
+    decoding: bool,
+    /// Video times wanted from the file's decoder thread, which exits once this is dropped.
+    requests: Sender<f32>,
+}
+
+/// Frames decoded by `ffmpeg` for the video tiles, with one long-lived decoder per file
+/// and one decode in flight at a time.
+pub struct VideoFrameCache {
+    videos: HashMap<PathBuf, VideoState>,
+    tx: Sender<(PathBuf, DecodeResult)>,
+    rx: Receiver<(PathBuf, DecodeResult)>,
+}
+
+impl VideoFrameCache {
+    pub fn new() -> Self {
+        let (tx, rx) = unbounded();
+        Self {
+            videos: HashMap::new(),
+            tx,
+            rx,
+        }
+    }
+
+    fn receive(&mut self, ctx: &egui::Context) {
+        while let Ok((path, result)) = self.rx.try_recv() {
+            // Results of a decoder that was reloaded since are stale.
+            let Some(state) = self.videos.get_mut(&path) else {
+                continue;
+            };
+            match result {
+                DecodeResult::Duration(duration) => state.duration = Some(duration),
+                DecodeResult::Frame(time, Ok(image)) => {
+                    let name = format!("video_{}", path.display());
+                    state.frame = Some((
+                        time,
+                        ctx.load_texture(name, image, egui::TextureOptions::LINEAR),
+                    ));
+                    state.error = None;
+                    state.decoding = false;
+                }
+                DecodeResult::Frame(time, Err(e)) => {
+                    tracing::error!("Failed to decode {}: {}", path.display(), e);
+                    state.error = Some((time, e));
+                    state.decoding = false;
+                }
+            }
+        }
+    }
+
+    /// Asks for the frame at `time` unless the shown frame is close enough, a decode is
+    /// already running or the file could not be probed.
+    fn request(&mut self, ctx: &egui::Context, path: &Path, time: f32) {
+        let state = self.videos.entry(path.to_path_buf()).or_insert_with(|| {
+            let (requests, requests_rx) = unbounded();
+            let tx = self.tx.clone();
+            let path = path.to_path_buf();
+            let ctx = ctx.clone();
+            std::thread::spawn(move || run_decoder(path, requests_rx, tx, ctx));
+            VideoState {
+                duration: None,
+                frame: None,
+                error: None,
+                decoding: false,
+                requests,
+            }
+        });
+
+        let near = |t: f32| (t - time).abs() <= FRAME_TOLERANCE;
+        let shown = state.frame.as_ref().is_some_and(|(t, _)| near(*t));
+        let failed = state.error.as_ref().is_some_and(|(t, _)| near(*t));
+        let unreadable = matches!(state.duration, Some(Err(_)));
+        if state.decoding || shown || failed || unreadable {
+            return;
+        }
+
+        state.decoding = state.requests.send(time).is_ok();
+    }
+
+    /// Forgets a file and stops its decoder, e.g. after it changed on disk or failed to
+    /// decode.
+    pub fn reload(&mut self, path: &Path) {
+        self.videos.remove(path);
+    }
+}
+
+impl Default for VideoFrameCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Stream properties of a video file, and the size its frames are decoded at.
+struct VideoInfo {
+    duration: f32,
+    frame_interval: f32,
+    width: usize,
+    height: usize,
+}
+
+impl VideoInfo {
+    fn frame_len(&self) -> usize {
+        self.width * self.height * 3
+    }
+}
+
+fn probe(path: &Path) -> Result<VideoInfo, String> {
+    let output = Command::new("ffprobe")
+        .args([
+            "-v",
+            "error",
+            "-select_streams",
+            "v:0",
+            "-show_entries",
+            "stream=width,height,avg_frame_rate:format=duration",
+            "-of",
+            "default=noprint_wrappers=1",
+        ])
+        .arg(path)
+        .stderr(Stdio::null())
+        .output()
+        .map_err(|e| format!("ffprobe not available: {}", e))?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let entries: HashMap<&str, &str> = stdout
+        .lines()
+        .filter_map(|line| line.trim().split_once('='))
+        .collect();
+    let number = |key: &str| entries.get(key).and_then(|v| v.parse::<f32>().ok());
+
+    let duration = number("duration").ok_or("unknown video duration")?;
+    let (Some(width), Some(height)) = (number("width"), number("height")) else {
+        return Err("no video stream".to_string());
+    };
+    let frame_rate = entries
+        .get("avg_frame_rate")
+        .and_then(|rate| rate.split_once('/'))
+        .and_then(|(num, den)| Some(num.parse::<f32>().ok()? / den.parse::<f32>().ok()?))
+        .filter(|rate| rate.is_finite() && *rate > 0.0)
+        .unwrap_or(30.0);
+
+    // Even dimensions, as most pixel formats need them.
+    let scaled_width = (width.min(MAX_FRAME_WIDTH as f32) as usize / 2 * 2).max(2);
+    let scaled_height = ((height * scaled_width as f32 / width / 2.0).round() as usize * 2).max(2);
+    Ok(VideoInfo {
+        duration,
+        frame_interval: 1.0 / frame_rate,
+        width: scaled_width,
+        height: scaled_height,
+    })
+}
+
+/// An `ffmpeg` process writing raw RGB frames from a start time onwards.
+struct FrameStream {
+    child: Child,
+    stdout: ChildStdout,
+    /// Yields the first line `ffmpeg` logged. The log is read on a thread of its own so a
+    /// chatty decoder cannot fill the pipe and stall.
+    first_error: Option<JoinHandle<Option<String>>>,
+    /// Video time of the next frame to be read.
+    next_time: f32,
+}
+
+impl FrameStream {
+    fn start(path: &Path, info: &VideoInfo, time: f32) -> Result<Self, String> {
+        let mut child = Command::new("ffmpeg")
+            .args(["-v", "error", "-ss", &format!("{:.3}", time.max(0.0)), "-i"])
+            .arg(path)
+            .args([
+                "-vf",
+                &format!("scale={}:{}", info.width, info.height),
+                "-f",
+                "rawvideo",
+                "-pix_fmt",
+                "rgb24",
+                "-",
+            ])
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("ffmpeg not available: {}", e))?;
+        let stdout = child.stdout.take().ok_or("ffmpeg has no output")?;
+        let first_error = child.stderr.take().map(|stderr| {
+            std::thread::spawn(move || {
+                let mut lines = BufReader::new(stderr).lines().map_while(Result::ok);
+                let first = lines.next();
+                lines.for_each(drop);
+                first
+            })
+        });
+        Ok(Self {
+            child,
+            stdout,
+            first_error,
+            next_time: time.max(0.0),
+        })
+    }
+
+    fn read(&mut self, info: &VideoInfo) -> Result<Vec<u8>, String> {
+        let mut pixels = vec![0; info.frame_len()];
+        if self.stdout.read_exact(&mut pixels).is_err() {
+            let _ = self.child.wait();
+            let error = self
+                .first_error
+                .take()
+                .and_then(|log| log.join().ok().flatten());
+            return Err(error.unwrap_or_else(|| "no frame decoded".to_string()));
+        }
+        self.next_time += info.frame_interval;
+        Ok(pixels)
+    }
+}
+
+impl Drop for FrameStream {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Decodes frames of one file until `requests` disconnects. Frames are read in order
+/// from a single `ffmpeg` process, which is only restarted to jump back or far ahead.
+fn run_decoder(
+    path: PathBuf,
+    requests: Receiver<f32>,
+    results: Sender<(PathBuf, DecodeResult)>,
+    ctx: egui::Context,
+) {
+    let send = |result| {
+        let _ = results.send((path.clone(), result));
+        ctx.request_repaint();
+    };
+
+    let info = probe(&path);
+    send(DecodeResult::Duration(
+        info.as_ref()
+            .map(|info| info.duration)
+            .map_err(Clone::clone),
+    ));
+    let Ok(info) = info else {
+        return;
+    };
+
+    let mut stream = None;
+    while let Ok(mut time) = requests.recv() {
+        // Only the latest wanted time matters.
+        while let Ok(later) = requests.try_recv() {
+            time = later;
+        }
+        let frame = next_frame(&path, &info, &mut stream, time);
+        if frame.is_err() {
+            stream = None;
+        }
+        send(DecodeResult::Frame(time, frame));
+    }
+}
+
+/// The frame shown at `time`, read on from `stream` when it is a little ahead of the
+/// last frame read and from a fresh stream otherwise.
+fn next_frame(
+    path: &Path,
+    info: &VideoInfo,
+    stream: &mut Option<FrameStream>,
+    time: f32,
+) -> Result<egui::ColorImage, String> {
+    let ahead = stream
+        .as_ref()
+        .map(|s| time - (s.next_time - info.frame_interval));
+    if !ahead.is_some_and(|ahead| (0.0..MAX_DECODE_AHEAD).contains(&ahead)) {
+        *stream = None;
+        *stream = Some(FrameStream::start(path, info, time)?);
+    }
+    let stream = stream.as_mut().expect("stream was just started");
+
+    let mut pixels = stream.read(info)?;
+    while stream.next_time <= time {
+        pixels = stream.read(info)?;
+    }
+    Ok(egui::ColorImage::from_rgb(
+        [info.width, info.height],
+        &pixels,
+    ))
+}
+
+/// Draws the video frame at the playback cursor, letterboxed into `rect`.
+pub fn render_video(
+    ui: &mut egui::Ui,
+    rect: egui::Rect,
+    view: &VideoView,
+    cache: &mut VideoFrameCache,
+    current_time: f32,
+) {
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 0.0, egui::Color32::BLACK);
+    cache.receive(ui.ctx());
+
+    let time = view.video_time(current_time);
+    let duration = cache
+        .videos
+        .get(&view.path)
+        .and_then(|state| state.duration.clone())
+        .and_then(Result::ok);
+    let in_range = time >= 0.0 && duration.is_none_or(|d| time <= d);
+    if in_range {
+        cache.request(ui.ctx(), &view.path, time);
+    }
+
+    let message = |text: String| {
+        painter.text(
+            rect.center(),
+            egui::Align2::CENTER_CENTER,
+            text,
+            egui::FontId::proportional(14.0),
+            egui::Color32::GRAY,
+        );
+    };
+
+    let state = cache.videos.get(&view.path);
+    if !in_range {
+        message(if time < 0.0 {
+            format!("Video starts in {:.1} s", -time)
+        } else {
+            "Video ended".to_string()
+        });
+    } else if let Some((_, texture)) = state.and_then(|s| s.frame.as_ref()) {
+        let size = texture.size_vec2();
+        let scale = (rect.width() / size.x).min(rect.height() / size.y);
+        let image_rect = egui::Rect::from_center_size(rect.center(), size * scale);
+        painter.image(
+            texture.id(),
+            image_rect,
+            egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0)),
+            egui::Color32::WHITE,
+        );
+    } else if let Some(Some(Err(e))) = state.map(|s| &s.duration) {
+        message(e.clone());
+    } else if let Some((_, e)) = state.and_then(|s| s.error.as_ref()) {
+        message(e.clone());
+    } else {
+        message("Decoding…".to_string());
+    }
+
+    let duration_text = duration.map_or(String::new(), |d| format!(" / {:.1} s", d));
+    painter.text(
+        rect.left_bottom() + egui::vec2(6.0, -6.0),
+        egui::Align2::LEFT_BOTTOM,
+        format!("{}  {:.2} s{}", view.file_name(), time, duration_text),
+        egui::FontId::monospace(11.0),
+        egui::Color32::from_gray(200),
+    );
+}
@@ -0,0 +1,178 @@
+use crate::ui::i18n::tr;
+use serde::{Deserialize, Serialize};
+
+/// How times are shown on axes, tooltips and the timeline. Times are seconds from the
+/// start of the log; wall-clock formats add the data store's `start_time`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TimeFormat {
+    #[default]
+    Seconds,
+    /// `hh:mm:ss` since the start of the log.
+    Elapsed,
+    /// Assumes timestamps are Unix epoch based.
+    Utc,
+}
+
+impl TimeFormat {
+    pub const ALL: [TimeFormat; 3] = [TimeFormat::Seconds, TimeFormat::Elapsed, TimeFormat::Utc];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TimeFormat::Seconds => tr("Seconds from Start"),
+            TimeFormat::Elapsed => tr("Elapsed (hh:mm:ss)"),
+            TimeFormat::Utc => tr("UTC Wall Clock"),
+        }
+    }
+
+    /// Formats `t` with `decimals` fractional digits, e.g. `12.3s` or `14:02:51.3`.
+    pub fn format(&self, t: f32, start_time: f32, decimals: usize) -> String {
+        match self {
+            TimeFormat::Seconds => format!("{:.*}s", decimals, t),
+            TimeFormat::Elapsed => elapsed_clock(t, decimals),
+            TimeFormat::Utc => {
+                let (_, clock) = utc_parts(start_time, t, decimals);
+                clock
+            }
+        }
+    }
+
+    /// Like [`Self::format`] with millisecond precision, including the date for UTC.
+    pub fn format_full(&self, t: f32, start_time: f32) -> String {
+        match self {
+            TimeFormat::Seconds | TimeFormat::Elapsed => self.format(t, start_time, 3),
+            TimeFormat::Utc => {
+                let ([year, month, day], clock) = utc_parts(start_time, t, 3);
+                format!("{:04}-{:02}-{:02} {}", year, month, day, clock)
+            }
+        }
+    }
+
+    /// Parses a time typed by the user into seconds from start. Accepts plain seconds
+    /// (`83.5`), `mm:ss` or `hh:mm:ss`, and UTC datetimes such as `2024-05-01 14:02:51.3`
+    /// or `2024-05-01T14:02:51Z`. Clock times are offsets from the start, or the time of
+    /// day on the log's first day in UTC format.
+    pub fn parse(&self, input: &str, start_time: f32) -> Result<f32, String> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err("Enter a time".to_string());
+        }
+
+        if let Ok(seconds) = input.trim_end_matches('s').trim().parse::<f32>() {
+            return Ok(seconds);
+        }
+
+        if let Some((date, time)) = input.split_once(['T', ' ']) {
+            let date: Vec<i64> = date
+                .split('-')
+                .map(|p| p.parse::<i64>())
+                .collect::<Result<_, _>>()
+                .map_err(|_| format!("Invalid date in '{}'", input))?;
+            let [year, month, day] = date[..] else {
+                return Err(format!("Expected YYYY-MM-DD in '{}'", input));
+            };
+            let time = time
+                .trim()
+                .trim_end_matches(['Z', 'z'])
+                .trim_end_matches("UTC");
+            let seconds_of_day = parse_clock(time.trim())?;
+            let epoch = days_from_civil(year, month, day) as f64 * 86400.0 + seconds_of_day;
+            return Ok((epoch - start_time as f64) as f32);
+        }
+
+        let clock = parse_clock(input)?;
+        Ok(match self {
+            TimeFormat::Seconds | TimeFormat::Elapsed => clock as f32,
+            TimeFormat::Utc => {
+                let day_start = (start_time as f64).div_euclid(86400.0) * 86400.0;
+                (day_start + clock - start_time as f64) as f32
+            }
+        })
+    }
+}
+
+/// Date and `hh:mm:ss.f` time of day of `start_time + t`.
+fn utc_parts(start_time: f32, t: f32, decimals: usize) -> ([i64; 3], String) {
+    // Round first so the seconds never display as 60.
+    let scale = 10f64.powi(decimals as i32);
+    let epoch = ((start_time as f64 + t as f64) * scale).round() / scale;
+    let (date, [hour, minute, second]) = civil_from_epoch(epoch);
+    let seconds = second as f64 + epoch.rem_euclid(1.0);
+    let width = if decimals > 0 { decimals + 3 } else { 2 };
+    let clock = format!(
+        "{:02}:{:02}:{:0width$.decimals$}",
+        hour,
+        minute,
+        seconds,
+        width = width,
+        decimals = decimals
+    );
+    (date, clock)
+}
+
+/// `hh:mm:ss.f` of `t` seconds, with a sign before times ahead of the start.
+fn elapsed_clock(t: f32, decimals: usize) -> String {
+    let scale = 10f64.powi(decimals as i32);
+    let rounded = (t.abs() as f64 * scale).round() / scale;
+    let whole = rounded.floor() as i64;
+    let seconds = (whole % 60) as f64 + rounded.fract();
+    let width = if decimals > 0 { decimals + 3 } else { 2 };
+    format!(
+        "{}{:02}:{:02}:{:0width$.decimals$}",
+        if t < 0.0 && rounded > 0.0 { "-" } else { "" },
+        whole / 3600,
+        whole % 3600 / 60,
+        seconds,
+        width = width,
+        decimals = decimals
+    )
+}
+
+/// Parses `ss`, `mm:ss` or `hh:mm:ss`, each with an optional fraction on the seconds.
+fn parse_clock(text: &str) -> Result<f64, String> {
+    let parts: Vec<&str> = text.split(':').collect();
+    if parts.len() > 3 {
+        return Err(format!("Invalid time '{}'", text));
+    }
+
+    let mut total = 0.0;
+    for part in &parts {
+        let value: f64 = part
+            .parse()
+            .map_err(|_| format!("Invalid time '{}'", text))?;
+        total = total * 60.0 + value;
+    }
+    Ok(total)
+}
+
+/// Date and time of day of a Unix timestamp.
+fn civil_from_epoch(epoch: f64) -> ([i64; 3], [i64; 3]) {
+    let secs = epoch.floor() as i64;
+    let days = secs.div_euclid(86400);
+    let of_day = secs.rem_euclid(86400);
+
+    // Howard Hinnant's civil_from_days.
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    (
+        [year, month, day],
+        [of_day / 3600, of_day % 3600 / 60, of_day % 60],
+    )
+}
+
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
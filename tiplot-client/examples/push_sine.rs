@@ -0,0 +1,33 @@
+//! Streams a sine wave into TiPlot: `cargo run -p tiplot-client --example push_sine`.
+
+use arrow::array::{Float64Array, Int64Array};
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::record_batch::RecordBatch;
+use std::sync::Arc;
+use std::time::Duration;
+use tiplot_client::Client;
+
+fn main() -> anyhow::Result<()> {
+    let mut client = Client::connect(("127.0.0.1", tiplot_client::protocol::DEFAULT_PORT))?;
+    println!("✓ Connected (protocol v{})", client.version());
+
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("timestamp", DataType::Int64, false),
+        Field::new("value", DataType::Float64, false),
+    ]));
+
+    for chunk in 0..50i64 {
+        let times: Vec<i64> = (0..100).map(|i| (chunk * 100 + i) * 10_000).collect();
+        let values: Vec<f64> = times.iter().map(|&t| (t as f64 * 1e-6).sin()).collect();
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int64Array::from(times)),
+                Arc::new(Float64Array::from(values)),
+            ],
+        )?;
+        client.send(&[("sine", &batch)])?;
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    Ok(())
+}
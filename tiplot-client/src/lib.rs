@@ -0,0 +1,114 @@
+//! Client for pushing Arrow tables into a running TiPlot instance.
+//!
+//! ```no_run
+//! # fn main() -> anyhow::Result<()> {
+//! # let batch: arrow::record_batch::RecordBatch = unimplemented!();
+//! let mut client = tiplot_client::Client::connect("127.0.0.1:9999")?;
+//! client.send(&[("vehicle_attitude", &batch)])?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Every table needs an integer `timestamp` column in microseconds. The wire format
+//! is documented in [`protocol`] and `docs/protocol.md`.
+
+pub mod protocol;
+
+use anyhow::{bail, Context, Result};
+use arrow::record_batch::RecordBatch;
+use protocol::{Compression, HandshakeReply, PacketMetadata, TimelineRange};
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+
+/// A negotiated connection to TiPlot. Any number of packets can be sent over it.
+pub struct Client {
+    stream: TcpStream,
+    version: u16,
+    compression: Compression,
+}
+
+impl Client {
+    pub fn connect(addr: impl ToSocketAddrs) -> Result<Self> {
+        let mut stream = TcpStream::connect(addr).context("Failed to connect to TiPlot")?;
+        stream.write_all(&protocol::encode_hello(protocol::PROTOCOL_VERSION))?;
+
+        let mut reply = [0u8; protocol::REPLY_LEN];
+        stream
+            .read_exact(&mut reply)
+            .context("TiPlot closed the connection during the handshake")?;
+        match protocol::decode_reply(&reply)? {
+            HandshakeReply::Accepted(version) => Ok(Self {
+                stream,
+                version,
+                compression: Compression::None,
+            }),
+            HandshakeReply::Rejected(server_version) => bail!(
+                "TiPlot speaks protocol v{}, this client speaks v{}",
+                server_version,
+                protocol::PROTOCOL_VERSION
+            ),
+        }
+    }
+
+    /// Protocol version agreed on during the handshake.
+    pub fn version(&self) -> u16 {
+        self.version
+    }
+
+    /// Compresses the tables of subsequent packets. Ignored by TiPlot versions older
+    /// than [`protocol::COMPRESSION_VERSION`], which receive them uncompressed.
+    pub fn set_compression(&mut self, compression: Compression) {
+        self.compression = compression;
+    }
+
+    /// Sends one packet of named tables, deriving the timeline range from their
+    /// `timestamp` columns.
+    pub fn send(&mut self, tables: &[(&str, &RecordBatch)]) -> Result<()> {
+        let batches: Vec<&RecordBatch> = tables.iter().map(|(_, batch)| *batch).collect();
+        let metadata = PacketMetadata::new(
+            tables.iter().map(|(name, _)| name.to_string()).collect(),
+            TimelineRange::from_batches(&batches),
+        );
+        self.send_with_metadata(&metadata, tables)
+    }
+
+    /// Sends one packet with caller-provided metadata (parameters, version info).
+    pub fn send_with_metadata(
+        &mut self,
+        metadata: &PacketMetadata,
+        tables: &[(&str, &RecordBatch)],
+    ) -> Result<()> {
+        if metadata.table_count != tables.len() {
+            bail!(
+                "Metadata announces {} tables but {} were given",
+                metadata.table_count,
+                tables.len()
+            );
+        }
+
+        let mut packet = protocol::encode_metadata(metadata)?;
+        for (name, batch) in tables {
+            packet.extend(protocol::encode_table(
+                name,
+                batch,
+                self.version,
+                self.compression,
+            )?);
+        }
+        self.stream.write_all(&packet)?;
+        self.stream.flush()?;
+        Ok(())
+    }
+
+    /// Tells TiPlot the connection is alive while there is nothing to send. Once sent,
+    /// TiPlot drops the connection if neither data nor another heartbeat follows within
+    /// [`protocol::HEARTBEAT_TIMEOUT`]. Does nothing on connections older than
+    /// [`protocol::CHECKSUM_VERSION`].
+    pub fn heartbeat(&mut self) -> Result<()> {
+        if self.version >= protocol::CHECKSUM_VERSION {
+            self.stream.write_all(&protocol::HEARTBEAT)?;
+            self.stream.flush()?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,312 @@
+//! TiPlot wire protocol.
+//!
+//! All integers are little-endian.
+//!
+//! A versioned session begins with a handshake:
+//!
+//! | Direction       | Bytes                                                  |
+//! |-----------------|--------------------------------------------------------|
+//! | client → TiPlot | [`MAGIC`], `u16` highest protocol version supported     |
+//! | TiPlot → client | [`MAGIC`], `u16` negotiated version, `u8` [`STATUS_OK`] |
+//!
+//! After the handshake the client sends any number of packets until it closes the
+//! connection. A packet is:
+//!
+//! 1. `u32` metadata length, followed by the [`PacketMetadata`] JSON
+//! 2. `table_count` tables, each a `u32` name length, the UTF-8 name, since version 4 a
+//!    `u8` compression flag, a `u64` payload length, an Arrow IPC stream and, since
+//!    version 3, its `u32` CRC-32
+//!
+//! Since version 2 a packet may carry a `cursor` (absolute seconds) instead of or
+//! alongside tables, which TiPlot's mirror server uses to share its playback cursor.
+//!
+//! Since version 4 the payload length is preceded by a [`Compression`] flag byte and
+//! the payload is compressed accordingly; the checksum covers the payload as sent.
+//!
+//! Since version 3 every table frame ends in a `u32` CRC-32 of its payload, and a
+//! metadata length of zero is a [`HEARTBEAT`] rather than a packet. A peer that has
+//! sent one heartbeat is expected to send another within [`HEARTBEAT_TIMEOUT`] whenever
+//! it has nothing else to send, or it is considered gone.
+//!
+//! Legacy senders skip the handshake and send a single packet per connection. TiPlot
+//! tells them apart by the first four bytes, which never spell [`MAGIC`] as a
+//! metadata length.
+
+use anyhow::{bail, ensure, Result};
+use arrow::array::{Array, Int64Array, UInt64Array};
+use arrow::record_batch::RecordBatch;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::Read;
+use std::time::Duration;
+
+pub const MAGIC: [u8; 4] = *b"TIPL";
+pub const PROTOCOL_VERSION: u16 = 4;
+pub const MIN_PROTOCOL_VERSION: u16 = 1;
+/// First version with table checksums and heartbeats.
+pub const CHECKSUM_VERSION: u16 = 3;
+/// First version with compressed table payloads.
+pub const COMPRESSION_VERSION: u16 = 4;
+pub const DEFAULT_PORT: u16 = 9999;
+
+pub const HELLO_LEN: usize = 6;
+pub const REPLY_LEN: usize = 7;
+pub const STATUS_OK: u8 = 0;
+pub const STATUS_UNSUPPORTED_VERSION: u8 = 1;
+
+/// Keep-alive frame: an empty metadata length.
+pub const HEARTBEAT: [u8; 4] = [0; 4];
+pub const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(2);
+pub const HEARTBEAT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Codec of a table payload, sent as a flag byte in front of it.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Compression {
+    #[default]
+    None,
+    /// Fast, for links and checkpoints where CPU time matters more than size.
+    Lz4,
+    /// Smaller but slower, for archived logs and slow links.
+    Zstd,
+}
+
+impl Compression {
+    pub const ALL: [Compression; 3] = [Compression::None, Compression::Lz4, Compression::Zstd];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Compression::None => "None",
+            Compression::Lz4 => "LZ4",
+            Compression::Zstd => "Zstandard",
+        }
+    }
+
+    pub fn flag(self) -> u8 {
+        match self {
+            Compression::None => 0,
+            Compression::Lz4 => 1,
+            Compression::Zstd => 2,
+        }
+    }
+
+    pub fn from_flag(flag: u8) -> Result<Self> {
+        match flag {
+            0 => Ok(Compression::None),
+            1 => Ok(Compression::Lz4),
+            2 => Ok(Compression::Zstd),
+            other => bail!("Unknown compression flag {}", other),
+        }
+    }
+
+    pub fn compress(self, data: Vec<u8>) -> Result<Vec<u8>> {
+        Ok(match self {
+            Compression::None => data,
+            Compression::Lz4 => lz4_flex::compress_prepend_size(&data),
+            Compression::Zstd => zstd::bulk::compress(&data, ZSTD_LEVEL)?,
+        })
+    }
+
+    /// Expands `data` without limiting its size, for trusted sources such as saved files.
+    pub fn decompress(self, data: Vec<u8>) -> Result<Vec<u8>> {
+        self.decompress_within(data, usize::MAX)
+    }
+
+    /// Expands `data`, refusing payloads that claim or turn out to exceed `limit` bytes.
+    pub fn decompress_within(self, data: Vec<u8>, limit: usize) -> Result<Vec<u8>> {
+        Ok(match self {
+            Compression::None => data,
+            Compression::Lz4 => {
+                let (size, block) = lz4_flex::block::uncompressed_size(&data)?;
+                check_size(size as u64, limit)?;
+                lz4_flex::decompress(block, size)?
+            }
+            Compression::Zstd => match zstd::zstd_safe::get_frame_content_size(&data) {
+                Ok(Some(size)) => {
+                    check_size(size, limit)?;
+                    zstd::bulk::decompress(&data, size as usize)?
+                }
+                // Streaming encoders may leave the size out of the frame header.
+                _ => {
+                    let mut out = Vec::new();
+                    zstd::stream::read::Decoder::new(data.as_slice())?
+                        .take((limit as u64).saturating_add(1))
+                        .read_to_end(&mut out)?;
+                    check_size(out.len() as u64, limit)?;
+                    out
+                }
+            },
+        })
+    }
+}
+
+/// Largest size a table received over the network may expand to, so a corrupt or hostile
+/// size header cannot make the receiver allocate unbounded memory.
+pub const MAX_DECOMPRESSED_SIZE: usize = 1 << 30;
+
+fn check_size(size: u64, limit: usize) -> Result<()> {
+    ensure!(
+        size <= limit as u64,
+        "Decompressed size of {} bytes exceeds the limit of {} bytes",
+        size,
+        limit
+    );
+    Ok(())
+}
+
+const ZSTD_LEVEL: i32 = 3;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default)]
+pub struct TimelineRange {
+    pub min_timestamp: Option<i64>,
+    pub max_timestamp: Option<i64>,
+}
+
+impl TimelineRange {
+    /// Range covered by the `timestamp` columns of `batches`.
+    pub fn from_batches(batches: &[&RecordBatch]) -> Self {
+        let mut range = Self::default();
+        for batch in batches {
+            let Some(column) = batch.column_by_name("timestamp") else {
+                continue;
+            };
+            let bounds = if let Some(arr) = column.as_any().downcast_ref::<Int64Array>() {
+                arrow::compute::min(arr).zip(arrow::compute::max(arr))
+            } else if let Some(arr) = column.as_any().downcast_ref::<UInt64Array>() {
+                arrow::compute::min(arr)
+                    .zip(arrow::compute::max(arr))
+                    .map(|(lo, hi)| (lo as i64, hi as i64))
+            } else {
+                None
+            };
+            if let Some((lo, hi)) = bounds {
+                range.min_timestamp = Some(range.min_timestamp.map_or(lo, |m| m.min(lo)));
+                range.max_timestamp = Some(range.max_timestamp.map_or(hi, |m| m.max(hi)));
+            }
+        }
+        range
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct PacketMetadata {
+    #[serde(default)]
+    pub parameters: HashMap<String, serde_json::Value>,
+    #[serde(default)]
+    pub version_info: HashMap<String, String>,
+    /// Firmware defaults of `parameters`, by name.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub parameter_defaults: HashMap<String, serde_json::Value>,
+    pub table_count: usize,
+    #[serde(default)]
+    pub table_names: Vec<String>,
+    pub timeline_range: TimelineRange,
+    /// Playback cursor of the sending instance, in absolute seconds (version 2).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<f64>,
+}
+
+impl PacketMetadata {
+    pub fn new(table_names: Vec<String>, timeline_range: TimelineRange) -> Self {
+        Self {
+            table_count: table_names.len(),
+            table_names,
+            timeline_range,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HandshakeReply {
+    Accepted(u16),
+    /// Carries the server's own protocol version.
+    Rejected(u16),
+}
+
+impl HandshakeReply {
+    /// Server-side negotiation: the highest version both sides understand.
+    pub fn negotiate(client_version: u16) -> Self {
+        if client_version < MIN_PROTOCOL_VERSION {
+            Self::Rejected(PROTOCOL_VERSION)
+        } else {
+            Self::Accepted(client_version.min(PROTOCOL_VERSION))
+        }
+    }
+
+    pub fn encode(self) -> [u8; REPLY_LEN] {
+        let (version, status) = match self {
+            Self::Accepted(v) => (v, STATUS_OK),
+            Self::Rejected(v) => (v, STATUS_UNSUPPORTED_VERSION),
+        };
+        let mut buf = [0u8; REPLY_LEN];
+        buf[..4].copy_from_slice(&MAGIC);
+        buf[4..6].copy_from_slice(&version.to_le_bytes());
+        buf[6] = status;
+        buf
+    }
+}
+
+pub fn encode_hello(version: u16) -> [u8; HELLO_LEN] {
+    let mut buf = [0u8; HELLO_LEN];
+    buf[..4].copy_from_slice(&MAGIC);
+    buf[4..].copy_from_slice(&version.to_le_bytes());
+    buf
+}
+
+pub fn decode_reply(buf: &[u8; REPLY_LEN]) -> Result<HandshakeReply> {
+    if buf[..4] != MAGIC {
+        bail!("Peer is not a TiPlot server");
+    }
+    let version = u16::from_le_bytes([buf[4], buf[5]]);
+    match buf[6] {
+        STATUS_OK => Ok(HandshakeReply::Accepted(version)),
+        STATUS_UNSUPPORTED_VERSION => Ok(HandshakeReply::Rejected(version)),
+        other => bail!("Unknown handshake status {}", other),
+    }
+}
+
+/// Length-prefixed metadata JSON, the start of every packet.
+pub fn encode_metadata(metadata: &PacketMetadata) -> Result<Vec<u8>> {
+    let json = serde_json::to_vec(metadata)?;
+    let mut buf = Vec::with_capacity(4 + json.len());
+    buf.extend((json.len() as u32).to_le_bytes());
+    buf.extend(json);
+    Ok(buf)
+}
+
+/// CRC-32 trailing each table payload since [`CHECKSUM_VERSION`].
+pub fn checksum(payload: &[u8]) -> u32 {
+    crc32fast::hash(payload)
+}
+
+/// One named table framed as an Arrow IPC stream, as spoken in protocol `version`.
+/// `compression` only applies from [`COMPRESSION_VERSION`] on.
+pub fn encode_table(
+    name: &str,
+    batch: &RecordBatch,
+    version: u16,
+    compression: Compression,
+) -> Result<Vec<u8>> {
+    let mut ipc = Vec::new();
+    {
+        let mut writer = arrow::ipc::writer::StreamWriter::try_new(&mut ipc, &batch.schema())?;
+        writer.write(batch)?;
+        writer.finish()?;
+    }
+
+    let mut buf = Vec::with_capacity(4 + name.len() + 1 + 8 + ipc.len() + 4);
+    buf.extend((name.len() as u32).to_le_bytes());
+    buf.extend(name.as_bytes());
+    let ipc = if version >= COMPRESSION_VERSION {
+        buf.push(compression.flag());
+        compression.compress(ipc)?
+    } else {
+        ipc
+    };
+    buf.extend((ipc.len() as u64).to_le_bytes());
+    buf.extend(&ipc);
+    if version >= CHECKSUM_VERSION {
+        buf.extend(checksum(&ipc).to_le_bytes());
+    }
+    Ok(buf)
+}